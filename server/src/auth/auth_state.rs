@@ -271,10 +271,10 @@ impl AuthStateManager {
     /// ```
     pub async fn get_azure_ad_token(&self) -> Option<String> {
         let state = self.inner.read().await;
-        if let Some((token_str, expires_at)) = &state.azure_ad_token {
-            if Instant::now() < *expires_at {
-                return Some(token_str.clone());
-            }
+        if let Some((token_str, expires_at)) = &state.azure_ad_token
+            && Instant::now() < *expires_at
+        {
+            return Some(token_str.clone());
         }
         None
     }
@@ -307,10 +307,10 @@ impl AuthStateManager {
     /// ```
     pub async fn get_sas_token(&self) -> Option<String> {
         let state = self.inner.read().await;
-        if let Some((token_str, expires_at)) = &state.sas_token {
-            if Instant::now() < *expires_at {
-                return Some(token_str.clone());
-            }
+        if let Some((token_str, expires_at)) = &state.sas_token
+            && Instant::now() < *expires_at
+        {
+            return Some(token_str.clone());
         }
         None
     }
@@ -53,16 +53,16 @@ impl AuthConfig {
     pub fn get_encrypted_auth_methods(&self) -> Vec<String> {
         let mut methods = Vec::new();
 
-        if let Some(cs) = &self.connection_string {
-            if cs.is_encrypted() {
-                methods.push("Connection String".to_string());
-            }
+        if let Some(cs) = &self.connection_string
+            && cs.is_encrypted()
+        {
+            methods.push("Connection String".to_string());
         }
 
-        if let Some(ad) = &self.azure_ad {
-            if ad.has_encrypted_client_secret() {
-                methods.push("Azure AD Client Secret".to_string());
-            }
+        if let Some(ad) = &self.azure_ad
+            && ad.has_encrypted_client_secret()
+        {
+            methods.push("Azure AD Client Secret".to_string());
         }
 
         methods
@@ -337,8 +337,17 @@ impl BulkDeleter {
             .await?;
         }
 
-        // Add non-targets to pending list for lock management (abandon them once at the end)
-        params.pending_messages.extend(non_targets);
+        // Deferred non-targets leave the receive stream, so they can be released right
+        // away; otherwise keep them locked until the end so they are not received again
+        if params.context.non_targets.releases_immediately() {
+            for message in non_targets {
+                if let Err(e) = self.release_non_target(params.context, &message).await {
+                    log::warn!("Failed to release non-target message: {e}");
+                }
+            }
+        } else {
+            params.pending_messages.extend(non_targets);
+        }
         *params.processed_count += params.batch_size;
 
         // Safety check: if we've gone well beyond the target sequence and still have targets
@@ -387,29 +396,28 @@ impl BulkDeleter {
         );
 
         for message in targets_in_batch {
-            if let Some(msg_id) = message.message_id() {
-                if let Some(target) = target_map.get(msg_id.as_ref()) {
-                    match self.complete_message(context, &message).await {
-                        Ok(_) => {
-                            result.add_successful_message(target.clone());
-                            log::info!(
-                                "Deleted target {} ({}/{})",
-                                target.id,
-                                *found_targets,
-                                target_map.len()
+            if let Some(msg_id) = message.message_id()
+                && let Some(target) = target_map.get(msg_id.as_ref())
+            {
+                match self.complete_message(context, &message).await {
+                    Ok(_) => {
+                        result.add_successful_message(target.clone());
+                        log::info!(
+                            "Deleted target {} ({}/{})",
+                            target.id,
+                            *found_targets,
+                            target_map.len()
+                        );
+                        *found_targets += 1;
+                    }
+                    Err(e) => {
+                        log::error!("Failed to delete target {}: {}", target.id, e);
+                        result.add_failure(format!("Failed to delete {}: {}", target.id, e));
+                        // Abandon the message
+                        if let Err(abandon_err) = self.abandon_message(context, &message).await {
+                            log::warn!(
+                                "Failed to abandon message after delete failure: {abandon_err}"
                             );
-                            *found_targets += 1;
-                        }
-                        Err(e) => {
-                            log::error!("Failed to delete target {}: {}", target.id, e);
-                            result.add_failure(format!("Failed to delete {}: {}", target.id, e));
-                            // Abandon the message
-                            if let Err(abandon_err) = self.abandon_message(context, &message).await
-                            {
-                                log::warn!(
-                                    "Failed to abandon message after delete failure: {abandon_err}"
-                                );
-                            }
                         }
                     }
                 }
@@ -419,7 +427,7 @@ impl BulkDeleter {
         Ok(())
     }
 
-    /// Clean up pending messages by abandoning them
+    /// Clean up pending messages by releasing them
     async fn cleanup_pending_messages(
         &self,
         context: &BulkOperationContext,
@@ -427,12 +435,12 @@ impl BulkDeleter {
     ) {
         if !pending_messages.is_empty() {
             log::info!(
-                "Abandoning {} remaining non-target messages",
+                "Releasing {} remaining non-target messages",
                 pending_messages.len()
             );
             for message in pending_messages {
-                if let Err(e) = self.abandon_message(context, &message).await {
-                    log::warn!("Failed to abandon non-target message: {e}");
+                if let Err(e) = self.release_non_target(context, &message).await {
+                    log::warn!("Failed to release non-target message: {e}");
                 }
             }
         }
@@ -448,6 +456,7 @@ impl BulkDeleter {
             consumer: context.consumer.clone(),
             cancel_token: context.cancel_token.clone(),
             queue_name: context.queue_name.clone(),
+            non_targets: context.non_targets.clone(),
         };
 
         tokio::spawn(async move {
@@ -469,7 +478,7 @@ impl BulkDeleter {
         })
     }
 
-    /// Process a batch of messages, deleting targets and abandoning non-targets
+    /// Process a batch of messages, deleting targets and releasing non-targets
     async fn process_messages(
         &self,
         context: &BulkOperationContext,
@@ -503,15 +512,15 @@ impl BulkDeleter {
                         }
                     }
                 } else {
-                    // Not a target - abandon it (this is small batch mode, so immediate abandon is OK)
-                    if let Err(e) = self.abandon_message(context, &message).await {
-                        log::warn!("Failed to abandon non-target message {msg_id:?}: {e}");
+                    // Not a target - release it (this is small batch mode, so immediate release is OK)
+                    if let Err(e) = self.release_non_target(context, &message).await {
+                        log::warn!("Failed to release non-target message {msg_id:?}: {e}");
                     }
                 }
             } else {
-                // Message has no ID - abandon it
-                if let Err(e) = self.abandon_message(context, &message).await {
-                    log::warn!("Failed to abandon message with no ID: {e}");
+                // Message has no ID - release it
+                if let Err(e) = self.release_non_target(context, &message).await {
+                    log::warn!("Failed to release message with no ID: {e}");
                 }
             }
         }
@@ -570,6 +579,27 @@ impl BulkDeleter {
             .map_err(|e| format!("Failed to complete message: {e}").into())
     }
 
+    /// Release a message that is not a target, using the configured strategy
+    async fn release_non_target(
+        &self,
+        context: &BulkOperationContext,
+        message: &azservicebus::ServiceBusReceivedMessage,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut consumer = acquire_lock_with_timeout(
+            &context.consumer,
+            "release_non_target",
+            Duration::from_secs(self.config.lock_timeout_secs()),
+            Some(&context.cancel_token),
+        )
+        .await?;
+
+        context
+            .non_targets
+            .release(&mut consumer, message)
+            .await
+            .map_err(|e| format!("Failed to release message: {e}").into())
+    }
+
     /// Abandon a message (put it back in the queue)
     async fn abandon_message(
         &self,
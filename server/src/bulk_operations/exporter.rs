@@ -0,0 +1,247 @@
+use super::types::BatchConfig;
+use crate::consumer::Consumer;
+use crate::export::{ExportFormat, ExportWriter, ExportedMessage};
+use crate::model::MessageModel;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+/// Which messages of an entity to export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportScope {
+    /// Every message, from the first to the last
+    All,
+    /// Only the messages with these sequence numbers
+    Sequences(BTreeSet<i64>),
+}
+
+/// Progress update emitted after every peeked batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportProgress {
+    /// Messages written so far
+    pub exported: usize,
+    /// Messages examined so far
+    pub scanned: usize,
+    /// Time since the export started
+    pub elapsed: Duration,
+}
+
+/// Outcome of an export
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportResult {
+    /// File the messages were written to
+    pub path: PathBuf,
+    pub format: ExportFormat,
+    /// Number of messages written
+    pub exported: usize,
+    /// Number of messages examined
+    pub scanned: usize,
+    /// Requested sequence numbers that were no longer in the entity
+    pub missing: usize,
+    /// Total duration of the export
+    pub elapsed: Duration,
+    /// Whether the export was stopped through its cancellation token; the
+    /// file then holds the messages written until then
+    pub cancelled: bool,
+}
+
+/// Writes the messages of a queue, subscription or dead letter queue to a
+/// file in one of the [`ExportFormat`]s.
+///
+/// Messages are peeked, so nothing is locked and delivery counts are
+/// untouched. Bodies are exported from the original bytes rather than the
+/// parsed [`MessageModel`], which keeps the export lossless.
+///
+/// # Examples
+///
+/// ```no_run
+/// use quetty_server::bulk_operations::{BatchConfig, ExportScope, QueueExporter};
+/// use quetty_server::consumer::Consumer;
+/// use quetty_server::export::ExportFormat;
+/// use std::path::Path;
+/// use tokio_util::sync::CancellationToken;
+///
+/// async fn export(receiver: Consumer) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let exporter = QueueExporter::new(BatchConfig::default());
+///     let result = exporter
+///         .export(
+///             receiver,
+///             Path::new("orders-dlq.ndjson"),
+///             ExportFormat::Ndjson,
+///             &ExportScope::All,
+///             None,
+///             CancellationToken::new(),
+///         )
+///         .await?;
+///     println!("Exported {} messages", result.exported);
+///     Ok(())
+/// }
+/// ```
+pub struct QueueExporter {
+    config: BatchConfig,
+}
+
+impl QueueExporter {
+    /// Creates an exporter peeking batches of the configured chunk size.
+    pub fn new(config: BatchConfig) -> Self {
+        Self { config }
+    }
+
+    /// Peeks through the entity with `receiver` and writes the messages in
+    /// `scope` to a new file at `path`. The receiver is disposed afterwards.
+    ///
+    /// On cancellation the file is completed with the messages written so
+    /// far, so it stays valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver` - A receiver that has not peeked yet
+    /// * `path` - File to create; an existing file is never overwritten
+    /// * `format` - Layout of the file
+    /// * `scope` - Which messages to export
+    /// * `progress` - Channel receiving an [`ExportProgress`] after every batch
+    /// * `cancel_token` - Stops the export after the batch in flight
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written, or if
+    /// peeking fails
+    pub async fn export(
+        &self,
+        mut receiver: Consumer,
+        path: &Path,
+        format: ExportFormat,
+        scope: &ExportScope,
+        progress: Option<UnboundedSender<ExportProgress>>,
+        cancel_token: CancellationToken,
+    ) -> Result<ExportResult, Box<dyn Error + Send + Sync>> {
+        log::info!("Exporting messages to {} as {format}", path.display());
+
+        let started = Instant::now();
+        let outcome = match File::create_new(path) {
+            Ok(file) => {
+                self.scan(
+                    &mut receiver,
+                    BufWriter::new(file),
+                    format,
+                    scope,
+                    progress,
+                    &cancel_token,
+                    started,
+                )
+                .await
+            }
+            Err(e) => Err(format!("Failed to create {}: {e}", path.display())),
+        };
+        if let Err(e) = receiver.dispose().await {
+            log::warn!("Failed to dispose export receiver: {e}");
+        }
+
+        let (exported, scanned) = outcome?;
+        let missing = match scope {
+            ExportScope::All => 0,
+            ExportScope::Sequences(sequences) => sequences.len().saturating_sub(exported),
+        };
+        let result = ExportResult {
+            path: path.to_path_buf(),
+            format,
+            exported,
+            scanned,
+            missing,
+            elapsed: started.elapsed(),
+            cancelled: cancel_token.is_cancelled(),
+        };
+
+        log::info!(
+            "Exported {} of {} messages in {:.1}s{}",
+            result.exported,
+            result.scanned,
+            result.elapsed.as_secs_f64(),
+            if result.cancelled { ", cancelled" } else { "" }
+        );
+        Ok(result)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn scan(
+        &self,
+        receiver: &mut Consumer,
+        file: BufWriter<File>,
+        format: ExportFormat,
+        scope: &ExportScope,
+        progress: Option<UnboundedSender<ExportProgress>>,
+        cancel_token: &CancellationToken,
+        started: Instant,
+    ) -> Result<(usize, usize), String> {
+        let write_error = |e| format!("Failed to write export: {e}");
+        let batch_size = self.config.bulk_chunk_size() as u32;
+        let mut writer = ExportWriter::new(file, format).map_err(write_error)?;
+        let mut scanned = 0;
+
+        // Selected messages lie between their lowest and highest sequence number
+        let (mut from_sequence, last_sequence) = match scope {
+            ExportScope::All => (None, None),
+            ExportScope::Sequences(sequences) => match (sequences.first(), sequences.last()) {
+                (Some(first), Some(last)) => (Some(*first), Some(*last)),
+                _ => {
+                    writer.finish().map_err(write_error)?;
+                    return Ok((0, 0));
+                }
+            },
+        };
+
+        while !cancel_token.is_cancelled() {
+            let batch = tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                batch = receiver.peek_raw_messages(batch_size, from_sequence) => {
+                    batch.map_err(|e| format!("Failed to peek messages: {e}"))?
+                }
+            };
+
+            // Peeking past the last message returns an empty batch
+            let Some(last) = batch.last().map(|message| message.sequence_number()) else {
+                break;
+            };
+            from_sequence = Some(last + 1);
+            scanned += batch.len();
+
+            for message in batch {
+                let sequence = message.sequence_number();
+                if let ExportScope::Sequences(sequences) = scope
+                    && !sequences.contains(&sequence)
+                {
+                    continue;
+                }
+
+                match MessageModel::try_from(message) {
+                    Ok(model) => writer
+                        .write(&ExportedMessage::from_message(&model))
+                        .map_err(write_error)?,
+                    Err(e) => log::warn!("Skipping message {sequence} in export: {e:?}"),
+                }
+            }
+
+            if let Some(progress) = &progress {
+                // The receiving side may have gone away; the export carries on regardless
+                let _ = progress.send(ExportProgress {
+                    exported: writer.written(),
+                    scanned,
+                    elapsed: started.elapsed(),
+                });
+            }
+
+            if last_sequence.is_some_and(|last_sequence| last >= last_sequence) {
+                break;
+            }
+        }
+
+        let exported = writer.written();
+        writer.finish().map_err(write_error)?;
+        Ok((exported, scanned))
+    }
+}
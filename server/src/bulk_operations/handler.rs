@@ -1,4 +1,5 @@
 use super::deleter::BulkDeleter;
+use super::non_targets::NonTargetReleaser;
 use super::types::{
     BatchConfig, BulkOperationContext, BulkOperationResult, BulkSendParams, MessageIdentifier,
 };
@@ -25,23 +26,27 @@ use tokio_util::sync::CancellationToken;
 /// # Examples
 ///
 /// ```no_run
-/// use quetty_server::bulk_operations::{BulkOperationHandler, BatchConfig, MessageIdentifier};
+/// use quetty_server::bulk_operations::{
+///     BulkOperationHandler, BatchConfig, MessageIdentifier, NonTargetReleaser,
+/// };
 /// use quetty_server::consumer::Consumer;
 /// use std::sync::Arc;
 /// use tokio::sync::Mutex;
 ///
 /// async fn example(consumer: Arc<Mutex<Consumer>>) -> Result<(), Box<dyn std::error::Error>> {
 ///     let config = BatchConfig::default();
-///     let handler = BulkOperationHandler::new(config);
+///     let handler = BulkOperationHandler::new(config.clone());
 ///
 ///     let message_ids = vec![
 ///         MessageIdentifier::SequenceNumber(12345),
 ///         MessageIdentifier::SequenceNumber(12346),
 ///     ];
 ///
+///     let non_targets = Arc::new(NonTargetReleaser::new(config.non_target_strategy(), "my-queue"));
 ///     let result = handler.delete_messages(
 ///         consumer,
 ///         "my-queue".to_string(),
+///         non_targets,
 ///         message_ids,
 ///         100, // max_position
 ///     ).await?;
@@ -89,6 +94,7 @@ impl BulkOperationHandler {
     ///
     /// * `consumer` - Service Bus consumer for message operations
     /// * `queue_name` - Name of the queue containing the messages
+    /// * `non_targets` - Releaser for messages received during the scan that are not targets
     /// * `targets` - List of message identifiers to delete
     /// * `max_position` - Maximum position limit for message processing
     ///
@@ -107,7 +113,9 @@ impl BulkOperationHandler {
     /// # Examples
     ///
     /// ```no_run
-    /// use quetty_server::bulk_operations::{BulkOperationHandler, MessageIdentifier};
+    /// use quetty_server::bulk_operations::{
+    ///     BulkOperationHandler, MessageIdentifier, NonTargetReleaser, NonTargetStrategy,
+    /// };
     /// use quetty_server::consumer::Consumer;
     /// use std::sync::Arc;
     /// use tokio::sync::Mutex;
@@ -122,9 +130,14 @@ impl BulkOperationHandler {
     ///         MessageIdentifier::SequenceNumber(102),
     ///     ];
     ///
+    ///     let non_targets = Arc::new(NonTargetReleaser::new(
+    ///         NonTargetStrategy::Defer,
+    ///         "orders-queue",
+    ///     ));
     ///     let result = handler.delete_messages(
     ///         consumer,
     ///         "orders-queue".to_string(),
+    ///         non_targets,
     ///         messages_to_delete,
     ///         1000,
     ///     ).await?;
@@ -139,6 +152,7 @@ impl BulkOperationHandler {
         &self,
         consumer: Arc<Mutex<crate::consumer::Consumer>>,
         queue_name: String,
+        non_targets: Arc<NonTargetReleaser>,
         targets: Vec<MessageIdentifier>,
         max_position: usize,
     ) -> Result<BulkOperationResult, Box<dyn Error + Send + Sync>> {
@@ -146,6 +160,7 @@ impl BulkOperationHandler {
             consumer,
             cancel_token: CancellationToken::new(),
             queue_name: queue_name.clone(),
+            non_targets,
         };
 
         // Create BulkSendParams with max position
@@ -0,0 +1,229 @@
+use super::types::BatchConfig;
+use crate::export::read_ndjson;
+use crate::service_bus_manager::MessageData;
+use crate::service_bus_manager::producer_manager::ProducerManager;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+/// How an NDJSON export is replayed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportOptions {
+    /// Send messages with their original MessageId instead of a new one
+    pub keep_message_ids: bool,
+    /// Upper bound on messages sent per second; `None` sends as fast as possible
+    pub rate_limit: Option<u32>,
+    /// Read and validate the file without sending anything
+    pub dry_run: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            keep_message_ids: true,
+            rate_limit: None,
+            dry_run: false,
+        }
+    }
+}
+
+/// Progress update emitted after every sent batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportProgress {
+    /// Messages sent so far
+    pub sent: usize,
+    /// Messages whose batch failed so far
+    pub failed: usize,
+    /// Messages in the file
+    pub total: usize,
+    /// Time since the import started
+    pub elapsed: Duration,
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportResult {
+    /// Messages in the file
+    pub total: usize,
+    /// Messages sent
+    pub sent: usize,
+    /// Messages whose batch could not be sent
+    pub failed: usize,
+    /// Total duration of the import
+    pub elapsed: Duration,
+    /// Whether the import was stopped through its cancellation token
+    pub cancelled: bool,
+    /// Whether the file was only validated
+    pub dry_run: bool,
+}
+
+/// Replays the messages of an NDJSON export into a queue or topic.
+///
+/// The whole file is read and validated before the first message is sent.
+/// Messages are then sent in batches of the configured chunk size through the
+/// [`ProducerManager`], optionally paced to a rate limit.
+///
+/// # Examples
+///
+/// ```no_run
+/// use quetty_server::bulk_operations::{BatchConfig, ImportOptions, QueueImporter};
+/// use quetty_server::service_bus_manager::producer_manager::ProducerManager;
+/// use std::path::Path;
+/// use tokio::sync::Mutex;
+/// use tokio_util::sync::CancellationToken;
+///
+/// async fn replay(producers: &Mutex<ProducerManager>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let options = ImportOptions {
+///         keep_message_ids: false,
+///         rate_limit: Some(50),
+///         dry_run: false,
+///     };
+///     let result = QueueImporter::new(BatchConfig::default())
+///         .import(producers, "orders", Path::new("orders.ndjson"), &options, None, CancellationToken::new())
+///         .await?;
+///     println!("Sent {} of {} messages", result.sent, result.total);
+///     Ok(())
+/// }
+/// ```
+pub struct QueueImporter {
+    config: BatchConfig,
+}
+
+impl QueueImporter {
+    /// Creates an importer sending batches of the configured chunk size.
+    pub fn new(config: BatchConfig) -> Self {
+        Self { config }
+    }
+
+    /// Reads the NDJSON file at `path` and sends its messages to `queue_name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `producers` - Producer manager used for sending
+    /// * `queue_name` - Target queue; subscription paths send to their topic
+    /// * `path` - NDJSON export to replay
+    /// * `options` - MessageId handling, rate limit and dry run
+    /// * `progress` - Channel receiving an [`ImportProgress`] after every batch
+    /// * `cancel_token` - Stops the import after the batch in flight
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or holds an invalid
+    /// record. Batches that fail to send are counted in
+    /// [`ImportResult::failed`] instead.
+    pub async fn import(
+        &self,
+        producers: &Mutex<ProducerManager>,
+        queue_name: &str,
+        path: &Path,
+        options: &ImportOptions,
+        progress: Option<UnboundedSender<ImportProgress>>,
+        cancel_token: CancellationToken,
+    ) -> Result<ImportResult, Box<dyn Error + Send + Sync>> {
+        log::info!(
+            "Importing {} into {queue_name}{}",
+            path.display(),
+            if options.dry_run { " (dry run)" } else { "" }
+        );
+
+        let started = Instant::now();
+        let messages = Self::read_messages(path, options.keep_message_ids)?;
+        let total = messages.len();
+        let report = |sent: usize, failed: usize| {
+            if let Some(progress) = &progress {
+                // The receiving side may have gone away; the import carries on regardless
+                let _ = progress.send(ImportProgress {
+                    sent,
+                    failed,
+                    total,
+                    elapsed: started.elapsed(),
+                });
+            }
+        };
+
+        let (mut sent, mut failed) = (0, 0);
+        if !options.dry_run {
+            let batch_size = match options.rate_limit {
+                // Keep batches within one second's worth of messages so pacing stays smooth
+                Some(rate) => self.config.bulk_chunk_size().min(rate.max(1) as usize),
+                None => self.config.bulk_chunk_size(),
+            };
+
+            for batch in messages.chunks(batch_size) {
+                if cancel_token.is_cancelled() {
+                    break;
+                }
+
+                let stats = producers
+                    .lock()
+                    .await
+                    .send_messages(queue_name, batch.to_vec())
+                    .await;
+                match stats {
+                    Ok(stats) => {
+                        sent += stats.successful;
+                        failed += batch.len() - stats.successful;
+                    }
+                    Err(e) => {
+                        log::error!("Failed to send batch of {} messages: {e}", batch.len());
+                        failed += batch.len();
+                    }
+                }
+                report(sent, failed);
+
+                if let Some(rate) = options.rate_limit {
+                    let due = Duration::from_secs_f64((sent + failed) as f64 / rate.max(1) as f64);
+                    tokio::select! {
+                        _ = cancel_token.cancelled() => break,
+                        _ = tokio::time::sleep(due.saturating_sub(started.elapsed())) => {}
+                    }
+                }
+            }
+        } else {
+            report(0, 0);
+        }
+
+        let result = ImportResult {
+            total,
+            sent,
+            failed,
+            elapsed: started.elapsed(),
+            cancelled: cancel_token.is_cancelled(),
+            dry_run: options.dry_run,
+        };
+
+        log::info!(
+            "Imported {} of {} messages ({} failed) in {:.1}s{}",
+            result.sent,
+            result.total,
+            result.failed,
+            result.elapsed.as_secs_f64(),
+            if result.cancelled { ", cancelled" } else { "" }
+        );
+        Ok(result)
+    }
+
+    fn read_messages(path: &Path, keep_message_ids: bool) -> Result<Vec<MessageData>, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+        let records = read_ndjson(BufReader::new(file))
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+
+        records
+            .iter()
+            .map(|record| {
+                record.to_message_data(keep_message_ids).map_err(|e| {
+                    format!(
+                        "Invalid body of message {} (sequence {}): {e}",
+                        record.message_id, record.sequence_number
+                    )
+                })
+            })
+            .collect()
+    }
+}
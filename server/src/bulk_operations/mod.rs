@@ -6,16 +6,30 @@
 //! - `types`: Common types and data structures
 //! - `resource_guard`: RAII resource management utilities
 //! - `deleter`: Message deletion operations
+//! - `exporter`: Writing messages to export files with streaming peeks
+//! - `importer`: Replaying NDJSON exports into a queue
+//! - `purger`: Draining of whole entities with concurrent receivers
+//! - `searcher`: Filtering whole entities with streaming peeks
 //! - `handler`: Main coordinator that orchestrates operations
 
 pub mod deleter;
+pub mod exporter;
 pub mod handler;
+pub mod importer;
+pub mod non_targets;
+pub mod purger;
 pub mod resource_guard;
+pub mod searcher;
 pub mod types;
 
 // Re-export the main types and components
 pub use deleter::{BulkDeleter, MessageDeleter};
+pub use exporter::{ExportProgress, ExportResult, ExportScope, QueueExporter};
 pub use handler::BulkOperationHandler;
+pub use importer::{ImportOptions, ImportProgress, ImportResult, QueueImporter};
+pub use non_targets::{DeferredLedger, NonTargetReleaser, NonTargetStrategy};
+pub use purger::{PurgeCutoff, PurgeProgress, PurgeResult, QueuePurger};
+pub use searcher::{QueueSearcher, SearchProgress, SearchResult};
 pub use types::{
     BatchConfig, // Keep for backward compatibility
     BulkOperationContext,
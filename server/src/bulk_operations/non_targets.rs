@@ -0,0 +1,504 @@
+use crate::consumer::Consumer;
+use crate::producer::Producer;
+use crate::service_bus_manager::types::{DEAD_LETTER_SUFFIX, parse_subscription_path};
+use azservicebus::{ServiceBusMessage, ServiceBusReceivedMessage};
+use fe2o3_amqp_types::messaging::annotations::OwnedKey;
+use fe2o3_amqp_types::messaging::{
+    Body, Data, Header, Message, MessageAnnotations, MessageId, Properties,
+};
+use fe2o3_amqp_types::primitives::{Binary, SimpleValue, Value};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Application property holding the original MessageId on a re-queued copy
+pub const ORIGINAL_MESSAGE_ID_PROPERTY: &str = "x-original-message-id";
+
+/// Message annotations set by the broker, which must not be copied onto a re-queued message
+const BROKER_ANNOTATIONS: &[&str] = &[
+    "x-opt-locked-until",
+    "x-opt-sequence-number",
+    "x-opt-deadletter-source",
+    "x-opt-enqueue-sequence-number",
+    "x-opt-enqueued-time",
+    "x-opt-message-state",
+];
+
+/// How targeted operations release messages they receive but do not act on.
+///
+/// Targeted operations (delete, move to DLQ, resend, defer) locate their
+/// targets by receiving from the head of the queue, which also locks every
+/// message in front of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NonTargetStrategy {
+    /// Defer non-targets during the scan, then re-queue copies of them and
+    /// complete the deferred originals. Delivery counts are never incremented,
+    /// but re-queued messages get new sequence numbers and move to the end of
+    /// the queue. Copies get a new MessageId, so duplicate detection cannot
+    /// drop them; the original one is kept in [`ORIGINAL_MESSAGE_ID_PROPERTY`].
+    #[default]
+    Defer,
+    /// Abandon non-targets. Each abandon increments the delivery count and can
+    /// push messages past MaxDeliveryCount into the dead letter queue.
+    Abandon,
+}
+
+/// Sequence numbers of deferred non-targets that have not been restored yet,
+/// per entity.
+///
+/// The ledger outlives the operation that deferred the messages, so an
+/// operation that is cancelled, times out or fails part way leaves its
+/// entries behind and the next restore of the entity picks them up. Clones
+/// share the same entries.
+#[derive(Debug, Clone, Default)]
+pub struct DeferredLedger {
+    entries: Arc<std::sync::Mutex<BTreeMap<String, BTreeSet<i64>>>>,
+}
+
+impl DeferredLedger {
+    /// Sequence numbers still deferred in `entity_path`, in order
+    pub fn pending(&self, entity_path: &str) -> Vec<i64> {
+        self.entries()
+            .get(entity_path)
+            .map(|sequence_numbers| sequence_numbers.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    fn record(&self, entity_path: &str, sequence_numbers: impl IntoIterator<Item = i64>) {
+        let mut entries = self.entries();
+        let entry = entries.entry(entity_path.to_string()).or_default();
+        entry.extend(sequence_numbers);
+        if entry.is_empty() {
+            entries.remove(entity_path);
+        }
+    }
+
+    /// Take `sequence_numbers` out of the ledger while they are restored.
+    /// Only those still in the ledger are claimed, so concurrent restores
+    /// never handle the same message twice.
+    fn claim<'a>(&'a self, entity_path: &'a str, sequence_numbers: &[i64]) -> Claim<'a> {
+        let mut entries = self.entries();
+        let claimed = match entries.get_mut(entity_path) {
+            Some(entry) => {
+                let claimed = sequence_numbers
+                    .iter()
+                    .copied()
+                    .filter(|sequence| entry.remove(sequence))
+                    .collect();
+                if entry.is_empty() {
+                    entries.remove(entity_path);
+                }
+                claimed
+            }
+            None => Vec::new(),
+        };
+        Claim {
+            ledger: self,
+            entity_path,
+            sequence_numbers: claimed,
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, BTreeSet<i64>>> {
+        // A poisoned ledger still holds valid sequence numbers
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Sequence numbers claimed from a [`DeferredLedger`] for restoring. Those
+/// not marked as settled go back to the ledger when the claim is dropped,
+/// including when the restore is abandoned part way.
+struct Claim<'a> {
+    ledger: &'a DeferredLedger,
+    entity_path: &'a str,
+    sequence_numbers: Vec<i64>,
+}
+
+impl Claim<'_> {
+    /// The message is no longer deferred, or its copy was accepted
+    fn settle(&mut self, sequence: i64) {
+        self.sequence_numbers.retain(|claimed| *claimed != sequence);
+    }
+}
+
+impl Drop for Claim<'_> {
+    fn drop(&mut self) {
+        self.ledger
+            .record(self.entity_path, self.sequence_numbers.drain(..));
+    }
+}
+
+/// Releases non-target messages during a targeted scan and restores the
+/// deferred ones afterwards.
+///
+/// Messages can only be re-queued into a queue, so dead letter queues and
+/// subscriptions always fall back to [`NonTargetStrategy::Abandon`]. Dead
+/// letter queues have no MaxDeliveryCount, so abandoning there never creates
+/// new dead letters.
+#[derive(Debug)]
+pub struct NonTargetReleaser {
+    strategy: NonTargetStrategy,
+    entity_path: String,
+    deferred: DeferredLedger,
+}
+
+impl NonTargetReleaser {
+    /// Creates a releaser for a scan of `entity_path` using `strategy` where possible.
+    pub fn new(strategy: NonTargetStrategy, entity_path: &str) -> Self {
+        let can_requeue = !entity_path.ends_with(DEAD_LETTER_SUFFIX)
+            && parse_subscription_path(entity_path).is_none();
+        let strategy = if can_requeue {
+            strategy
+        } else {
+            NonTargetStrategy::Abandon
+        };
+
+        Self {
+            strategy,
+            entity_path: entity_path.to_string(),
+            deferred: DeferredLedger::default(),
+        }
+    }
+
+    /// Records deferred messages in `ledger`, shared with earlier scans of the
+    /// entity, instead of a ledger of this scan only
+    pub fn with_ledger(mut self, ledger: DeferredLedger) -> Self {
+        self.deferred = ledger;
+        self
+    }
+
+    /// The strategy in effect for this scan
+    pub fn strategy(&self) -> NonTargetStrategy {
+        self.strategy
+    }
+
+    /// Whether released messages leave the receive stream immediately.
+    ///
+    /// Deferred messages are never received again, so they can be released as
+    /// soon as they are seen; abandoned ones would be redelivered and must stay
+    /// locked until the scan is over.
+    pub fn releases_immediately(&self) -> bool {
+        self.strategy == NonTargetStrategy::Defer
+    }
+
+    /// Sequence numbers of the messages of the entity that are deferred and
+    /// not restored yet
+    pub fn deferred_sequence_numbers(&self) -> Vec<i64> {
+        self.deferred.pending(&self.entity_path)
+    }
+
+    /// Releases a non-target message according to the strategy.
+    ///
+    /// Only messages with a single data section can be sent again, so other
+    /// messages are abandoned rather than deferred.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the defer or abandon operation fails
+    pub async fn release(
+        &self,
+        consumer: &mut Consumer,
+        message: &ServiceBusReceivedMessage,
+    ) -> Result<(), Box<dyn Error>> {
+        match self.strategy {
+            NonTargetStrategy::Defer if message.body().is_ok() => {
+                consumer.defer_message(message).await?;
+                self.deferred
+                    .record(&self.entity_path, [message.sequence_number()]);
+                Ok(())
+            }
+            NonTargetStrategy::Defer | NonTargetStrategy::Abandon => {
+                consumer.abandon_message(message).await
+            }
+        }
+    }
+
+    /// Re-queues copies of the deferred non-targets of the entity and
+    /// completes the originals, including those left behind by earlier scans.
+    ///
+    /// Messages are processed in chunks of `chunk_size`. Copies keep the body
+    /// and all properties of the original, with a new MessageId. An original
+    /// is only completed once its copy has been sent; if completing it fails,
+    /// the copy is already queued, so the message may be delivered twice but
+    /// is never lost.
+    ///
+    /// # Returns
+    ///
+    /// The number of messages restored. Messages that were not restored stay
+    /// deferred and in [`deferred_sequence_numbers`](Self::deferred_sequence_numbers).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a chunk cannot be fetched or sent
+    pub async fn restore(
+        &self,
+        consumer: &Arc<Mutex<Consumer>>,
+        producer: &mut Producer,
+        chunk_size: usize,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let sequence_numbers = self.deferred_sequence_numbers();
+        let mut restored = 0;
+
+        for chunk in sequence_numbers.chunks(chunk_size.max(1)) {
+            let mut claim = self.deferred.claim(&self.entity_path, chunk);
+            if claim.sequence_numbers.is_empty() {
+                continue;
+            }
+            let claimed = claim.sequence_numbers.clone();
+            let messages = consumer
+                .lock()
+                .await
+                .receive_deferred_messages(&claimed)
+                .await?;
+
+            // Messages the broker no longer has were settled or expired
+            for sequence in &claimed {
+                if !messages
+                    .iter()
+                    .any(|message| message.sequence_number() == *sequence)
+                {
+                    claim.settle(*sequence);
+                }
+            }
+
+            let mut copies = Vec::with_capacity(messages.len());
+            let mut originals = Vec::with_capacity(messages.len());
+            for message in messages {
+                match message.body() {
+                    Ok(body) => {
+                        copies.push(requeue_copy(
+                            message.raw_amqp_message(),
+                            body,
+                            message.message_id().as_deref(),
+                        ));
+                        originals.push(message);
+                    }
+                    Err(_) => log::warn!(
+                        "Message {} has a non-binary body and cannot be re-queued; it stays deferred",
+                        message.sequence_number()
+                    ),
+                }
+            }
+            if copies.is_empty() {
+                continue;
+            }
+
+            producer.send_messages(copies).await.map_err(|e| {
+                format!("Failed to re-queue deferred messages ({restored} restored so far): {e}")
+            })?;
+
+            let mut consumer = consumer.lock().await;
+            for original in &originals {
+                claim.settle(original.sequence_number());
+                if let Err(e) = consumer.complete_message(original).await {
+                    log::error!(
+                        "Re-queued message {} but failed to complete the deferred original: {e}",
+                        original.sequence_number()
+                    );
+                    continue;
+                }
+                restored += 1;
+            }
+        }
+
+        let still_deferred = self.deferred_sequence_numbers();
+        if still_deferred.is_empty() {
+            log::info!("Restored {restored} deferred non-target messages");
+        } else {
+            log::warn!(
+                "Restored {restored} deferred non-target messages; {} stay deferred: {still_deferred:?}",
+                still_deferred.len()
+            );
+        }
+        Ok(restored)
+    }
+}
+
+/// Builds a copy of a received message for sending, without the broker-set
+/// state and with a new MessageId, keeping the original one in
+/// [`ORIGINAL_MESSAGE_ID_PROPERTY`]
+fn requeue_copy(
+    message: &Message<Body<Value>>,
+    body: &[u8],
+    message_id: Option<&str>,
+) -> ServiceBusMessage {
+    let message_annotations =
+        message
+            .message_annotations
+            .clone()
+            .map(|MessageAnnotations(annotations)| {
+                MessageAnnotations(
+                    annotations
+                        .into_iter()
+                        .filter(|(key, _)| match key {
+                            OwnedKey::Symbol(symbol) => {
+                                !BROKER_ANNOTATIONS.contains(&symbol.as_str())
+                            }
+                            OwnedKey::Ulong(_) => true,
+                        })
+                        .collect(),
+                )
+            });
+
+    let properties = Properties {
+        message_id: Some(MessageId::String(uuid::Uuid::new_v4().to_string())),
+        ..message.properties.clone().unwrap_or_default()
+    };
+    let mut application_properties = message.application_properties.clone().unwrap_or_default();
+    if let Some(message_id) = message_id {
+        application_properties.0.insert(
+            ORIGINAL_MESSAGE_ID_PROPERTY.to_string(),
+            SimpleValue::String(message_id.to_string()),
+        );
+    }
+
+    ServiceBusMessage::from_raw_amqp_message(Message {
+        header: message.header.clone().map(|header| Header {
+            delivery_count: 0,
+            ..header
+        }),
+        delivery_annotations: None,
+        message_annotations,
+        properties: Some(properties),
+        application_properties: Some(application_properties),
+        body: Data(Binary::from(body.to_vec())),
+        footer: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fe2o3_amqp_types::messaging::ApplicationProperties;
+
+    #[test]
+    fn test_defer_falls_back_to_abandon_where_messages_cannot_be_requeued() {
+        let queue = NonTargetReleaser::new(NonTargetStrategy::Defer, "orders");
+        assert_eq!(queue.strategy(), NonTargetStrategy::Defer);
+        assert!(queue.releases_immediately());
+
+        let dlq = NonTargetReleaser::new(NonTargetStrategy::Defer, "orders/$deadletterqueue");
+        assert_eq!(dlq.strategy(), NonTargetStrategy::Abandon);
+
+        let subscription =
+            NonTargetReleaser::new(NonTargetStrategy::Defer, "events/Subscriptions/audit");
+        assert_eq!(subscription.strategy(), NonTargetStrategy::Abandon);
+        assert!(!subscription.releases_immediately());
+    }
+
+    #[test]
+    fn test_requeue_copy_gets_a_new_message_id_and_no_broker_state() {
+        let original = Message {
+            header: Some(Header {
+                delivery_count: 4,
+                ..Default::default()
+            }),
+            delivery_annotations: None,
+            message_annotations: Some(MessageAnnotations(
+                [
+                    (
+                        OwnedKey::Symbol("x-opt-sequence-number".into()),
+                        Value::Long(42),
+                    ),
+                    (
+                        OwnedKey::Symbol("x-opt-partition-key".into()),
+                        Value::String("orders".into()),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            )),
+            properties: Some(Properties {
+                message_id: Some(MessageId::String("order-1".into())),
+                correlation_id: Some(MessageId::String("batch-7".into())),
+                ..Default::default()
+            }),
+            application_properties: Some(ApplicationProperties(
+                [("tenant".to_string(), SimpleValue::String("acme".into()))]
+                    .into_iter()
+                    .collect(),
+            )),
+            body: Body::Empty,
+            footer: None,
+        };
+
+        let copy = requeue_copy(&original, b"{\"id\":1}", Some("order-1"));
+        assert_eq!(copy.body(), b"{\"id\":1}");
+        let raw = copy.raw_amqp_message();
+        assert_eq!(raw.header.as_ref().unwrap().delivery_count, 0);
+
+        let annotations = &raw.message_annotations.as_ref().unwrap().0;
+        assert_eq!(annotations.len(), 1);
+        assert!(annotations.contains_key(&OwnedKey::Symbol("x-opt-partition-key".into())));
+
+        let properties = raw.properties.as_ref().unwrap();
+        assert_ne!(
+            properties.message_id,
+            Some(MessageId::String("order-1".into()))
+        );
+        assert_eq!(
+            properties.correlation_id,
+            Some(MessageId::String("batch-7".into()))
+        );
+        let application_properties = &raw.application_properties.as_ref().unwrap().0;
+        assert_eq!(
+            application_properties.get(ORIGINAL_MESSAGE_ID_PROPERTY),
+            Some(&SimpleValue::String("order-1".into()))
+        );
+        assert_eq!(
+            application_properties.get("tenant"),
+            Some(&SimpleValue::String("acme".into()))
+        );
+    }
+
+    #[test]
+    fn test_ledger_is_shared_with_later_scans() {
+        let ledger = DeferredLedger::default();
+        let scan =
+            NonTargetReleaser::new(NonTargetStrategy::Defer, "orders").with_ledger(ledger.clone());
+        ledger.record("orders", [7, 3]);
+        ledger.record("payments", [1]);
+        drop(scan);
+
+        // A cancelled scan leaves its messages for the next one to restore
+        let next =
+            NonTargetReleaser::new(NonTargetStrategy::Defer, "orders").with_ledger(ledger.clone());
+        assert_eq!(next.deferred_sequence_numbers(), [3, 7]);
+        assert_eq!(ledger.pending("payments"), [1]);
+    }
+
+    #[test]
+    fn test_unsettled_claims_return_to_the_ledger() {
+        let ledger = DeferredLedger::default();
+        ledger.record("orders", [1, 2, 3, 4]);
+
+        {
+            let mut claim = ledger.claim("orders", &[1, 2, 3]);
+            assert_eq!(claim.sequence_numbers, [1, 2, 3]);
+            // Claimed numbers are not handed to a concurrent restore
+            assert!(ledger.claim("orders", &[2]).sequence_numbers.is_empty());
+            assert_eq!(ledger.pending("orders"), [4]);
+            claim.settle(2);
+            // Dropped part way, as when the restore is cancelled
+        }
+        assert_eq!(ledger.pending("orders"), [1, 3, 4]);
+
+        let mut claim = ledger.claim("orders", &[1, 3, 4]);
+        for sequence in [1, 3, 4] {
+            claim.settle(sequence);
+        }
+        drop(claim);
+        assert!(ledger.pending("orders").is_empty());
+    }
+
+    #[test]
+    fn test_strategy_deserializes_from_snake_case() {
+        let strategy: NonTargetStrategy = serde_json::from_str("\"abandon\"").unwrap();
+        assert_eq!(strategy, NonTargetStrategy::Abandon);
+    }
+}
@@ -0,0 +1,547 @@
+use super::non_targets::NonTargetReleaser;
+use super::types::BatchConfig;
+use crate::consumer::Consumer;
+use azservicebus::primitives::service_bus_message_state::ServiceBusMessageState;
+use azservicebus::{ServiceBusPeekedMessage, ServiceBusReceiveMode, ServiceBusReceivedMessage};
+use azure_core::time::OffsetDateTime;
+use futures::future::join_all;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+/// Progress update emitted after every purged batch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PurgeProgress {
+    /// Messages purged so far across all receivers
+    pub purged: usize,
+    /// Time since the purge started
+    pub elapsed: Duration,
+}
+
+impl PurgeProgress {
+    /// Average throughput since the purge started
+    pub fn messages_per_second(&self) -> f64 {
+        messages_per_second(self.purged, self.elapsed)
+    }
+}
+
+/// Outcome of a purge operation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurgeResult {
+    /// Number of messages removed from the entity
+    pub purged: usize,
+    /// Total duration of the purge
+    pub elapsed: Duration,
+    /// Whether the purge was stopped through its cancellation token
+    pub cancelled: bool,
+    /// Errors of receivers that stopped early
+    pub errors: Vec<String>,
+    /// Sequence numbers of newer messages deferred by a cutoff purge that
+    /// could not be re-queued
+    pub still_deferred: Vec<i64>,
+    /// Sequence numbers of messages selected by a cutoff purge that were never
+    /// received, because another consumer took them, they expired or they are
+    /// locked elsewhere
+    pub missed: Vec<i64>,
+}
+
+impl PurgeResult {
+    /// Average throughput of the purge
+    pub fn messages_per_second(&self) -> f64 {
+        messages_per_second(self.purged, self.elapsed)
+    }
+}
+
+fn messages_per_second(count: usize, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 { count as f64 / secs } else { 0.0 }
+}
+
+/// Limits a purge to the messages enqueued before a cutoff
+#[derive(Debug, Clone, Copy)]
+pub struct PurgeCutoff<'a> {
+    /// Only messages enqueued before this time are purged
+    pub older_than: OffsetDateTime,
+    /// Releases the newer messages received while looking for the older ones
+    pub non_targets: &'a NonTargetReleaser,
+}
+
+/// State shared by the concurrent receivers of one purge
+struct PurgeState {
+    purged: AtomicUsize,
+    /// Sequence numbers still to purge, when purging with a cutoff
+    targets: Option<std::sync::Mutex<BTreeSet<i64>>>,
+    /// Highest sequence number selected, when purging with a cutoff
+    last_target: Option<i64>,
+    /// Highest sequence number received so far
+    highest_received: AtomicI64,
+    started: Instant,
+    progress: Option<UnboundedSender<PurgeProgress>>,
+    cancel_token: CancellationToken,
+}
+
+impl PurgeState {
+    fn new(
+        targets: Option<BTreeSet<i64>>,
+        progress: Option<UnboundedSender<PurgeProgress>>,
+        cancel_token: CancellationToken,
+    ) -> Self {
+        Self {
+            purged: AtomicUsize::new(0),
+            last_target: targets
+                .as_ref()
+                .map(|targets| targets.last().copied().unwrap_or(i64::MIN)),
+            targets: targets.map(std::sync::Mutex::new),
+            highest_received: AtomicI64::new(i64::MIN),
+            started: Instant::now(),
+            progress,
+            cancel_token,
+        }
+    }
+
+    /// Whether the purge is over: it was cancelled, every selected message is
+    /// gone, or the receivers are past the last selected message, so the
+    /// selected ones still left were taken or locked elsewhere
+    fn is_finished(&self) -> bool {
+        self.cancel_token.is_cancelled()
+            || self
+                .targets
+                .as_ref()
+                .is_some_and(|targets| Self::lock(targets).is_empty())
+            || self
+                .last_target
+                .is_some_and(|last| self.highest_received.load(Ordering::Relaxed) > last)
+    }
+
+    /// Splits a batch into the selected targets, which are claimed so no
+    /// other receiver counts them, and the rest
+    fn take_targets(
+        &self,
+        batch: Vec<ServiceBusReceivedMessage>,
+    ) -> (
+        Vec<ServiceBusReceivedMessage>,
+        Vec<ServiceBusReceivedMessage>,
+    ) {
+        if self.targets.is_none() {
+            return (batch, Vec::new());
+        }
+        batch
+            .into_iter()
+            .partition(|message| self.take_target(message.sequence_number()))
+    }
+
+    /// Records a received message, claiming it if it is a selected target
+    fn take_target(&self, sequence: i64) -> bool {
+        self.highest_received.fetch_max(sequence, Ordering::Relaxed);
+        self.targets
+            .as_ref()
+            .is_some_and(|targets| Self::lock(targets).remove(&sequence))
+    }
+
+    /// Selected messages that were never received
+    fn missed(&self) -> Vec<i64> {
+        self.targets
+            .as_ref()
+            .map(|targets| Self::lock(targets).iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    fn lock(targets: &std::sync::Mutex<BTreeSet<i64>>) -> std::sync::MutexGuard<'_, BTreeSet<i64>> {
+        // A poisoned set still holds valid sequence numbers
+        targets
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn record(&self, count: usize) {
+        let purged = self.purged.fetch_add(count, Ordering::Relaxed) + count;
+        if let Some(progress) = &self.progress {
+            // The receiving side may have gone away; the purge carries on regardless
+            let _ = progress.send(PurgeProgress {
+                purged,
+                elapsed: self.started.elapsed(),
+            });
+        }
+    }
+}
+
+/// Removes every message from a queue, subscription or dead letter queue.
+///
+/// All messages are drained with concurrent receivers in receive-and-delete
+/// mode, so nothing is locked and the scan is not bounded by
+/// `max_messages_to_process`. When a cutoff is given, the entity is peeked
+/// first to select the messages enqueued before it. Receivers then use
+/// peek-lock, complete the selected messages and release every other message
+/// they receive through a [`NonTargetReleaser`], so the purge scans past newer
+/// messages whatever order they are received in. It stops once every selected
+/// message is gone, or once a message newer than every selected one is
+/// received; selected messages not received by then are reported as missed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use quetty_server::bulk_operations::{BatchConfig, QueuePurger};
+/// use quetty_server::consumer::Consumer;
+/// use tokio_util::sync::CancellationToken;
+///
+/// async fn purge(receivers: Vec<Consumer>) {
+///     let purger = QueuePurger::new(BatchConfig::default());
+///     match purger.purge(receivers, None, None, CancellationToken::new()).await {
+///         Ok(result) => println!("Purged {} messages", result.purged),
+///         Err(e) => eprintln!("Purge failed: {e}"),
+///     }
+/// }
+/// ```
+pub struct QueuePurger {
+    config: BatchConfig,
+}
+
+impl QueuePurger {
+    /// Creates a purger using the batch size and receive timeout of `config`.
+    pub fn new(config: BatchConfig) -> Self {
+        Self { config }
+    }
+
+    /// Receive mode the purge receivers must be created with
+    pub fn receive_mode(older_than: Option<OffsetDateTime>) -> ServiceBusReceiveMode {
+        match older_than {
+            Some(_) => ServiceBusReceiveMode::PeekLock,
+            None => ServiceBusReceiveMode::ReceiveAndDelete,
+        }
+    }
+
+    /// Drains the entity with `receivers` until it is empty, every message
+    /// before the cutoff is purged, the receivers pass the last message before
+    /// the cutoff or `cancel_token` is cancelled. The receivers are disposed
+    /// afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `receivers` - Receivers created with [`QueuePurger::receive_mode`]
+    /// * `cutoff` - Only purge messages enqueued before a time
+    /// * `progress` - Channel receiving a [`PurgeProgress`] after every batch
+    /// * `cancel_token` - Stops the purge after the batches in flight
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the messages before the cutoff cannot be selected,
+    /// or if every receiver failed before anything was purged
+    pub async fn purge(
+        &self,
+        mut receivers: Vec<Consumer>,
+        cutoff: Option<PurgeCutoff<'_>>,
+        progress: Option<UnboundedSender<PurgeProgress>>,
+        cancel_token: CancellationToken,
+    ) -> Result<PurgeResult, Box<dyn Error + Send + Sync>> {
+        let receiver_count = receivers.len();
+        let started = Instant::now();
+
+        let targets = match (cutoff, receivers.first_mut()) {
+            (Some(cutoff), Some(receiver)) => {
+                match self
+                    .select_targets(receiver, Some(cutoff.older_than), &cancel_token)
+                    .await
+                {
+                    Ok(targets) => Some(targets),
+                    Err(e) => {
+                        for receiver in &receivers {
+                            if let Err(e) = receiver.dispose().await {
+                                log::warn!("Failed to dispose purge receiver: {e}");
+                            }
+                        }
+                        return Err(e.into());
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        let state = PurgeState {
+            started,
+            ..PurgeState::new(targets, progress, cancel_token)
+        };
+
+        log::info!(
+            "Purging with {receiver_count} receivers{}",
+            cutoff
+                .map(|cutoff| format!(" (messages enqueued before {})", cutoff.older_than))
+                .unwrap_or_default()
+        );
+
+        let state_ref = &state;
+        let non_targets = cutoff.map(|cutoff| cutoff.non_targets);
+        let outcomes = join_all(receivers.into_iter().map(|mut receiver| async move {
+            let outcome = self.drain(&mut receiver, non_targets, state_ref).await;
+            if let Err(e) = receiver.dispose().await {
+                log::warn!("Failed to dispose purge receiver: {e}");
+            }
+            outcome
+        }))
+        .await;
+
+        let errors: Vec<String> = outcomes.into_iter().filter_map(Result::err).collect();
+        let cancelled = state.cancel_token.is_cancelled();
+        let result = PurgeResult {
+            purged: state.purged.load(Ordering::Relaxed),
+            elapsed: state.started.elapsed(),
+            cancelled,
+            errors,
+            still_deferred: Vec::new(),
+            // A cancelled purge stops before reaching every selected message
+            missed: if cancelled {
+                Vec::new()
+            } else {
+                state.missed()
+            },
+        };
+        if !result.missed.is_empty() {
+            log::warn!(
+                "{} selected messages were never received: {:?}",
+                result.missed.len(),
+                result.missed
+            );
+        }
+
+        if result.purged == 0 && receiver_count > 0 && result.errors.len() == receiver_count {
+            return Err(result.errors.join("; ").into());
+        }
+
+        log::info!(
+            "Purged {} messages in {:.1}s ({:.0} msg/s){}",
+            result.purged,
+            result.elapsed.as_secs_f64(),
+            result.messages_per_second(),
+            if result.cancelled { ", cancelled" } else { "" }
+        );
+        Ok(result)
+    }
+
+    /// Sequence numbers of the messages a purge removes: the active messages,
+    /// or those enqueued before `older_than`, found by peeking through the
+    /// whole entity with `receiver`.
+    ///
+    /// Peeking locks nothing, so the selection does not depend on the order
+    /// in which the messages are received afterwards. Deferred and scheduled
+    /// messages cannot be received and are never selected. Dry runs use the
+    /// same selection as cutoff purges.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if peeking fails
+    pub async fn select_targets(
+        &self,
+        receiver: &mut Consumer,
+        older_than: Option<OffsetDateTime>,
+        cancel_token: &CancellationToken,
+    ) -> Result<BTreeSet<i64>, String> {
+        let batch_size = self.config.bulk_chunk_size() as u32;
+        let mut targets = BTreeSet::new();
+        let mut from_sequence = None;
+
+        while !cancel_token.is_cancelled() {
+            let batch = tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                batch = receiver.peek_raw_messages(batch_size, from_sequence) => {
+                    batch.map_err(|e| format!("Failed to peek messages: {e}"))?
+                }
+            };
+
+            // Peeking past the last message returns an empty batch
+            let Some(last) = batch.last() else {
+                break;
+            };
+            from_sequence = Some(last.sequence_number() + 1);
+            targets.extend(
+                batch
+                    .iter()
+                    .filter(|message| is_purge_target(message, older_than))
+                    .map(ServiceBusPeekedMessage::sequence_number),
+            );
+        }
+
+        log::info!(
+            "Selected {} messages to purge{}",
+            targets.len(),
+            older_than
+                .map(|cutoff| format!(" (enqueued before {cutoff})"))
+                .unwrap_or_default()
+        );
+        Ok(targets)
+    }
+
+    /// Receives batches with one receiver until there is nothing left to purge
+    async fn drain(
+        &self,
+        receiver: &mut Consumer,
+        non_targets: Option<&NonTargetReleaser>,
+        state: &PurgeState,
+    ) -> Result<(), String> {
+        // Abandoned messages would be received again, so they stay locked
+        // until this receiver is done
+        let mut held = Vec::new();
+        let outcome = self
+            .receive_targets(receiver, non_targets, &mut held, state)
+            .await;
+
+        if !held.is_empty()
+            && let Err(e) = receiver.abandon_messages(&held).await
+        {
+            log::warn!("Failed to abandon messages after the purge cutoff: {e}");
+        }
+        outcome
+    }
+
+    async fn receive_targets(
+        &self,
+        receiver: &mut Consumer,
+        non_targets: Option<&NonTargetReleaser>,
+        held: &mut Vec<ServiceBusReceivedMessage>,
+        state: &PurgeState,
+    ) -> Result<(), String> {
+        let batch_size = self.config.bulk_chunk_size() as u32;
+        let timeout = Duration::from_secs(self.config.receive_timeout_secs());
+
+        while !state.is_finished() {
+            let batch = tokio::select! {
+                _ = state.cancel_token.cancelled() => break,
+                batch = receiver.receive_messages_with_timeout(batch_size, timeout) => {
+                    batch.map_err(|e| format!("Failed to receive messages: {e}"))?
+                }
+            };
+
+            // An empty batch after the receive timeout means the entity is drained
+            if batch.is_empty() {
+                break;
+            }
+
+            let purged = match non_targets {
+                None => batch.len(),
+                Some(non_targets) => {
+                    Self::complete_targets(receiver, batch, non_targets, held, state).await?
+                }
+            };
+            state.record(purged);
+        }
+
+        Ok(())
+    }
+
+    /// Completes the selected messages of a peek-locked batch and releases
+    /// the rest
+    async fn complete_targets(
+        receiver: &mut Consumer,
+        batch: Vec<ServiceBusReceivedMessage>,
+        non_targets: &NonTargetReleaser,
+        held: &mut Vec<ServiceBusReceivedMessage>,
+        state: &PurgeState,
+    ) -> Result<usize, String> {
+        let (targets, others) = state.take_targets(batch);
+
+        for message in others {
+            if non_targets.releases_immediately() {
+                if let Err(e) = non_targets.release(receiver, &message).await {
+                    log::warn!("Failed to release message after the purge cutoff: {e}");
+                }
+            } else {
+                held.push(message);
+            }
+        }
+
+        if targets.is_empty() {
+            return Ok(0);
+        }
+        receiver
+            .complete_messages(&targets)
+            .await
+            .map_err(|e| format!("Failed to complete messages: {e}"))?;
+        Ok(targets.len())
+    }
+}
+
+/// Whether a peeked message is removed by a purge with the given cutoff
+fn is_purge_target(message: &ServiceBusPeekedMessage, older_than: Option<OffsetDateTime>) -> bool {
+    matches!(message.state(), ServiceBusMessageState::Active)
+        && older_than.is_none_or(|cutoff| message.enqueued_time() < cutoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cutoff_requires_peek_lock() {
+        assert!(matches!(
+            QueuePurger::receive_mode(None),
+            ServiceBusReceiveMode::ReceiveAndDelete
+        ));
+        assert!(matches!(
+            QueuePurger::receive_mode(Some(OffsetDateTime::now_utc())),
+            ServiceBusReceiveMode::PeekLock
+        ));
+    }
+
+    #[test]
+    fn test_cutoff_purge_finishes_once_every_target_is_gone() {
+        let state = |targets: Option<BTreeSet<i64>>| {
+            PurgeState::new(targets, None, CancellationToken::new())
+        };
+
+        assert!(!state(None).is_finished());
+        assert!(!state(Some(BTreeSet::from([7, 9]))).is_finished());
+        assert!(state(Some(BTreeSet::new())).is_finished());
+
+        let cancelled = state(Some(BTreeSet::from([7])));
+        cancelled.cancel_token.cancel();
+        assert!(cancelled.is_finished());
+
+        // Without a cutoff every received message is purged
+        let (targets, others) = state(None).take_targets(Vec::new());
+        assert!(targets.is_empty() && others.is_empty());
+        let unbounded = state(None);
+        assert!(!unbounded.take_target(100));
+        assert!(!unbounded.is_finished());
+        assert!(unbounded.missed().is_empty());
+    }
+
+    #[test]
+    fn test_cutoff_purge_stops_past_a_missing_target() {
+        let state = PurgeState::new(
+            Some(BTreeSet::from([3, 5, 9])),
+            None,
+            CancellationToken::new(),
+        );
+
+        assert!(state.take_target(3));
+        assert!(!state.take_target(4));
+        // 5 was taken by another consumer after the peek
+        assert!(state.take_target(9));
+        assert!(!state.is_finished());
+
+        // A newer message means every selected one still there was received
+        assert!(!state.take_target(10));
+        assert!(state.is_finished());
+        assert_eq!(state.missed(), vec![5]);
+    }
+
+    #[test]
+    fn test_throughput() {
+        let result = PurgeResult {
+            purged: 1500,
+            elapsed: Duration::from_secs(3),
+            cancelled: false,
+            errors: Vec::new(),
+            still_deferred: Vec::new(),
+            missed: Vec::new(),
+        };
+        assert_eq!(result.messages_per_second(), 500.0);
+
+        let progress = PurgeProgress {
+            purged: 10,
+            elapsed: Duration::ZERO,
+        };
+        assert_eq!(progress.messages_per_second(), 0.0);
+    }
+}
@@ -0,0 +1,228 @@
+use super::types::BatchConfig;
+use crate::consumer::Consumer;
+use crate::filter::SqlFilter;
+use crate::model::MessageModel;
+use std::error::Error;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+/// Progress update emitted after every peeked batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchProgress {
+    /// Messages examined so far
+    pub scanned: usize,
+    /// Matches found in this batch only
+    pub matches: Vec<MessageModel>,
+    /// Time since the search started
+    pub elapsed: Duration,
+}
+
+/// Outcome of a search
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    /// Every matching message, in queue order
+    pub matches: Vec<MessageModel>,
+    /// Number of messages examined
+    pub scanned: usize,
+    /// Total duration of the search
+    pub elapsed: Duration,
+    /// Whether the search was stopped through its cancellation token
+    pub cancelled: bool,
+    /// Whether the search stopped at the match limit with more matches left
+    pub truncated: bool,
+}
+
+/// Finds the messages of a queue, subscription or dead letter queue that
+/// satisfy a [`SqlFilter`].
+///
+/// The entity is walked from its first message with peeks, so nothing is
+/// locked, delivery counts are untouched and the scan is not bounded by
+/// `max_messages_to_process`. Matches are streamed after every batch.
+///
+/// # Examples
+///
+/// ```no_run
+/// use quetty_server::bulk_operations::{BatchConfig, QueueSearcher};
+/// use quetty_server::consumer::Consumer;
+/// use quetty_server::filter::SqlFilter;
+/// use tokio_util::sync::CancellationToken;
+///
+/// async fn search(receiver: Consumer) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let filter = SqlFilter::parse("sys.DeliveryCount > 3")?;
+///     let searcher = QueueSearcher::new(BatchConfig::default());
+///     let result = searcher
+///         .search(receiver, &filter, 100, None, CancellationToken::new())
+///         .await?;
+///     println!("{} of {} messages match", result.matches.len(), result.scanned);
+///     Ok(())
+/// }
+/// ```
+pub struct QueueSearcher {
+    config: BatchConfig,
+}
+
+impl QueueSearcher {
+    /// Creates a searcher peeking batches of the configured chunk size.
+    pub fn new(config: BatchConfig) -> Self {
+        Self { config }
+    }
+
+    /// Peeks through the entity with `receiver` until its end, `max_matches`
+    /// matches or cancellation. The receiver is disposed afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver` - A receiver that has not peeked yet
+    /// * `filter` - Predicate selecting the messages to return
+    /// * `max_matches` - Stop after this many matches
+    /// * `progress` - Channel receiving a [`SearchProgress`] after every batch
+    /// * `cancel_token` - Stops the search after the batch in flight
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if peeking fails
+    pub async fn search(
+        &self,
+        mut receiver: Consumer,
+        filter: &SqlFilter,
+        max_matches: usize,
+        progress: Option<UnboundedSender<SearchProgress>>,
+        cancel_token: CancellationToken,
+    ) -> Result<SearchResult, Box<dyn Error + Send + Sync>> {
+        log::info!("Searching for messages matching: {filter}");
+
+        let started = Instant::now();
+        let outcome = self
+            .scan(
+                &mut receiver,
+                filter,
+                max_matches,
+                progress,
+                &cancel_token,
+                started,
+            )
+            .await;
+        if let Err(e) = receiver.dispose().await {
+            log::warn!("Failed to dispose search receiver: {e}");
+        }
+
+        let (matches, scanned, truncated) = outcome?;
+        let result = SearchResult {
+            truncated,
+            matches,
+            scanned,
+            elapsed: started.elapsed(),
+            cancelled: cancel_token.is_cancelled(),
+        };
+
+        log::info!(
+            "Search matched {} of {} messages in {:.1}s{}",
+            result.matches.len(),
+            result.scanned,
+            result.elapsed.as_secs_f64(),
+            if result.cancelled { ", cancelled" } else { "" }
+        );
+        Ok(result)
+    }
+
+    async fn scan(
+        &self,
+        receiver: &mut Consumer,
+        filter: &SqlFilter,
+        max_matches: usize,
+        progress: Option<UnboundedSender<SearchProgress>>,
+        cancel_token: &CancellationToken,
+        started: Instant,
+    ) -> Result<(Vec<MessageModel>, usize, bool), String> {
+        let batch_size = self.config.bulk_chunk_size() as u32;
+        let mut matches = Vec::new();
+        let mut scanned = 0;
+        let mut from_sequence = None;
+        let mut truncated = false;
+
+        // Past the limit the scan goes on until one more match shows that
+        // the results are truncated, or the entity ends
+        while !truncated && !cancel_token.is_cancelled() {
+            let batch = tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                batch = receiver.peek_raw_messages(batch_size, from_sequence) => {
+                    batch.map_err(|e| format!("Failed to peek messages: {e}"))?
+                }
+            };
+
+            // Peeking past the last message returns an empty batch; a batch of
+            // messages that cannot be converted is skipped, not the end
+            let Some(last) = batch.last() else {
+                break;
+            };
+            from_sequence = Some(last.sequence_number() + 1);
+            scanned += batch.len();
+
+            let mut found: Vec<MessageModel> = MessageModel::try_convert_messages_collect(batch)
+                .into_iter()
+                .filter(|message| filter.matches(message))
+                .collect();
+            truncated = keep_within_limit(&mut found, matches.len(), max_matches);
+            matches.extend_from_slice(&found);
+
+            if let Some(progress) = &progress {
+                // The receiving side may have gone away; the search carries on regardless
+                let _ = progress.send(SearchProgress {
+                    scanned,
+                    matches: found,
+                    elapsed: started.elapsed(),
+                });
+            }
+        }
+
+        Ok((matches, scanned, truncated))
+    }
+}
+
+/// Drops the matches of a batch beyond `max_matches`, given `kept` earlier
+/// matches, and returns whether any were dropped
+fn keep_within_limit(found: &mut Vec<MessageModel>, kept: usize, max_matches: usize) -> bool {
+    let remaining = max_matches.saturating_sub(kept);
+    let truncated = found.len() > remaining;
+    found.truncate(remaining);
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BodyData, MessageState};
+    use azure_core::time::OffsetDateTime;
+
+    fn matches(count: i64) -> Vec<MessageModel> {
+        (1..=count)
+            .map(|sequence| {
+                MessageModel::new(
+                    sequence,
+                    format!("msg-{sequence}"),
+                    OffsetDateTime::now_utc(),
+                    0,
+                    MessageState::Active,
+                    BodyData::RawString(String::new()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_only_truncated_when_more_matches_remain() {
+        // Reaching the limit exactly is not truncation
+        let mut found = matches(3);
+        assert!(!keep_within_limit(&mut found, 2, 5));
+        assert_eq!(found.len(), 3);
+
+        let mut found = matches(4);
+        assert!(keep_within_limit(&mut found, 2, 5));
+        assert_eq!(found.len(), 3);
+
+        let mut found = matches(1);
+        assert!(keep_within_limit(&mut found, 5, 5));
+        assert!(found.is_empty());
+    }
+}
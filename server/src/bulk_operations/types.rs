@@ -3,6 +3,7 @@
 //! This module defines the core types used throughout the bulk operations system,
 //! including result tracking, message identification, configuration, and operation contexts.
 
+use super::non_targets::{NonTargetReleaser, NonTargetStrategy};
 use crate::consumer::Consumer;
 use azservicebus::ServiceBusClient;
 use azservicebus::core::BasicRetryPolicy;
@@ -48,6 +49,9 @@ pub struct BulkOperationResult {
     pub error_details: Vec<String>,
     /// Identifiers of messages that were processed successfully
     pub successful_message_ids: Vec<MessageIdentifier>,
+    /// Sequence numbers of non-target messages the operation deferred and
+    /// could not re-queue; they stay deferred until settled by sequence number
+    pub still_deferred: Vec<i64>,
 }
 
 impl BulkOperationResult {
@@ -68,6 +72,7 @@ impl BulkOperationResult {
             not_found: 0,
             error_details: Vec::new(),
             successful_message_ids: Vec::new(),
+            still_deferred: Vec::new(),
         }
     }
 
@@ -244,6 +249,12 @@ pub struct BatchConfig {
     auto_reload_threshold: Option<usize>,
     /// Timeout for individual receive message operations in seconds (default: 5)
     receive_timeout_secs: Option<u64>,
+    /// How targeted operations release messages they do not act on (default: defer)
+    non_target_strategy: Option<NonTargetStrategy>,
+    /// Number of concurrent receivers used to purge an entity (default: 4)
+    purge_concurrency: Option<usize>,
+    /// Maximum number of matches returned by a queue search (default: 1,000)
+    search_max_matches: Option<usize>,
 }
 
 impl BatchConfig {
@@ -269,6 +280,9 @@ impl BatchConfig {
             max_messages_to_process: None,
             auto_reload_threshold: None,
             receive_timeout_secs: None,
+            non_target_strategy: None,
+            purge_concurrency: None,
+            search_max_matches: None,
         }
     }
 
@@ -311,6 +325,21 @@ impl BatchConfig {
     pub fn receive_timeout_secs(&self) -> u64 {
         self.receive_timeout_secs.unwrap_or(5)
     }
+
+    /// Get how targeted operations release messages they do not act on
+    pub fn non_target_strategy(&self) -> NonTargetStrategy {
+        self.non_target_strategy.unwrap_or_default()
+    }
+
+    /// Get the number of concurrent receivers used to purge an entity
+    pub fn purge_concurrency(&self) -> usize {
+        self.purge_concurrency.unwrap_or(4).max(1)
+    }
+
+    /// Get the maximum number of matches returned by a queue search
+    pub fn search_max_matches(&self) -> usize {
+        self.search_max_matches.unwrap_or(1_000).max(1)
+    }
 }
 
 /// Context for Service Bus operations containing shared resources
@@ -428,6 +457,8 @@ pub struct BulkOperationContext {
     pub cancel_token: CancellationToken,
     /// Name of the queue this operation is targeting (used for deferred message persistence)
     pub queue_name: String,
+    /// Releases the messages received during the scan that are not targets
+    pub non_targets: Arc<NonTargetReleaser>,
 }
 
 /// Parameters for process_target_messages method
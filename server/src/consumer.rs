@@ -1,11 +1,100 @@
 use azservicebus::receiver::DeadLetterOptions;
-use azservicebus::{ServiceBusClient, ServiceBusReceiver, ServiceBusReceiverOptions};
+use azservicebus::{
+    ServiceBusClient, ServiceBusPeekedMessage, ServiceBusReceivedMessage, ServiceBusReceiver,
+    ServiceBusReceiverOptions, ServiceBusSessionReceiver, ServiceBusSessionReceiverOptions,
+};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
 
 use crate::model::MessageModel;
 
+/// Forwards a call to whichever receiver kind the consumer wraps.
+macro_rules! with_receiver {
+    ($receiver:expr, $inner:ident => $call:expr) => {
+        match $receiver {
+            ConsumerReceiver::Queue($inner) => $call,
+            ConsumerReceiver::Session($inner) => $call,
+        }
+    };
+}
+
+/// The receiver behind a [`Consumer`]: a plain queue receiver or one bound
+/// to a single session of a session-enabled queue.
+/// Error type for [`ConsumerReceiver`] operations
+type ReceiverError = Box<dyn std::error::Error>;
+
+#[derive(Debug)]
+enum ConsumerReceiver {
+    Queue(ServiceBusReceiver),
+    Session(ServiceBusSessionReceiver),
+}
+
+impl ConsumerReceiver {
+    async fn peek_messages(
+        &mut self,
+        max_count: u32,
+        from_sequence_number: Option<i64>,
+    ) -> Result<Vec<ServiceBusPeekedMessage>, ReceiverError> {
+        with_receiver!(self, r => r.peek_messages(max_count, from_sequence_number).await.map_err(Into::into))
+    }
+
+    async fn receive_messages(
+        &mut self,
+        max_count: u32,
+    ) -> Result<Vec<ServiceBusReceivedMessage>, ReceiverError> {
+        with_receiver!(self, r => r.receive_messages(max_count).await.map_err(Into::into))
+    }
+
+    async fn abandon_message(
+        &mut self,
+        message: &ServiceBusReceivedMessage,
+    ) -> Result<(), ReceiverError> {
+        with_receiver!(self, r => r.abandon_message(message, None).await.map_err(Into::into))
+    }
+
+    async fn dead_letter_message(
+        &mut self,
+        message: &ServiceBusReceivedMessage,
+        options: DeadLetterOptions,
+    ) -> Result<(), ReceiverError> {
+        with_receiver!(self, r => r.dead_letter_message(message, options).await.map_err(Into::into))
+    }
+
+    async fn complete_message(
+        &mut self,
+        message: &ServiceBusReceivedMessage,
+    ) -> Result<(), ReceiverError> {
+        with_receiver!(self, r => r.complete_message(message).await.map_err(Into::into))
+    }
+
+    async fn defer_message(
+        &mut self,
+        message: &ServiceBusReceivedMessage,
+    ) -> Result<(), ReceiverError> {
+        with_receiver!(self, r => r.defer_message(message, None).await.map_err(Into::into))
+    }
+
+    async fn renew_message_lock(
+        &mut self,
+        message: &mut ServiceBusReceivedMessage,
+    ) -> Result<(), ReceiverError> {
+        with_receiver!(self, r => r.renew_message_lock(message).await.map_err(Into::into))
+    }
+
+    async fn receive_deferred_messages(
+        &mut self,
+        sequence_numbers: &[i64],
+    ) -> Result<Vec<ServiceBusReceivedMessage>, Box<dyn std::error::Error + Send + Sync>> {
+        let sequence_numbers = sequence_numbers.iter().copied();
+        with_receiver!(self, r => r.receive_deferred_messages(sequence_numbers).await.map_err(Into::into))
+    }
+
+    async fn dispose(self) -> Result<(), ReceiverError> {
+        with_receiver!(self, r => r.dispose().await.map_err(Into::into))
+    }
+}
+
 /// A wrapper around Azure Service Bus receiver for consuming messages from queues.
 ///
 /// The Consumer provides a high-level interface for receiving, processing, and managing
@@ -40,7 +129,7 @@ use crate::model::MessageModel;
 /// ```
 #[derive(Debug)]
 pub struct Consumer {
-    receiver: Arc<Mutex<Option<ServiceBusReceiver>>>,
+    receiver: Arc<Mutex<Option<ConsumerReceiver>>>,
 }
 
 impl PartialEq for Consumer {
@@ -57,7 +146,71 @@ impl Consumer {
     /// * `receiver` - The Azure Service Bus receiver to wrap
     pub fn new(receiver: ServiceBusReceiver) -> Self {
         Self {
-            receiver: Arc::new(Mutex::new(Some(receiver))),
+            receiver: Arc::new(Mutex::new(Some(ConsumerReceiver::Queue(receiver)))),
+        }
+    }
+
+    /// Creates a new Consumer bound to a single session of a session-enabled queue.
+    ///
+    /// Peek, receive and settlement operations only see messages of that session.
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver` - The session receiver to wrap
+    pub fn new_session(receiver: ServiceBusSessionReceiver) -> Self {
+        Self {
+            receiver: Arc::new(Mutex::new(Some(ConsumerReceiver::Session(receiver)))),
+        }
+    }
+
+    /// Returns the ID of the session this consumer is bound to, if any.
+    pub async fn session_id(&self) -> Option<String> {
+        match self.receiver.lock().await.as_ref() {
+            Some(ConsumerReceiver::Session(receiver)) => Some(receiver.session_id().to_string()),
+            _ => None,
+        }
+    }
+
+    /// Reads the state stored on the consumer's session.
+    ///
+    /// # Returns
+    ///
+    /// The raw session state; empty if no state has been set
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the consumer is not bound to a session, has been
+    /// disposed, or if the Service Bus operation fails
+    pub async fn session_state(&mut self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut guard = self.receiver.lock().await;
+        match guard.as_mut() {
+            Some(ConsumerReceiver::Session(receiver)) => Ok(receiver.session_state().await?),
+            Some(ConsumerReceiver::Queue(_)) => Err("Consumer is not bound to a session".into()),
+            None => Err("Receiver already disposed".into()),
+        }
+    }
+
+    /// Replaces the state stored on the consumer's session.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The new raw session state
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the consumer is not bound to a session, has been
+    /// disposed, or if the Service Bus operation fails
+    pub async fn set_session_state(
+        &mut self,
+        state: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut guard = self.receiver.lock().await;
+        match guard.as_mut() {
+            Some(ConsumerReceiver::Session(receiver)) => {
+                Ok(receiver.set_session_state(state).await?)
+            }
+            Some(ConsumerReceiver::Queue(_)) => Err("Consumer is not bound to a session".into()),
+            None => Err("Receiver already disposed".into()),
         }
     }
 
@@ -83,13 +236,35 @@ impl Consumer {
         max_count: u32,
         from_sequence_number: Option<i64>,
     ) -> Result<Vec<MessageModel>, Box<dyn std::error::Error>> {
+        let messages = self
+            .peek_raw_messages(max_count, from_sequence_number)
+            .await?;
+        Ok(MessageModel::try_convert_messages_collect(messages))
+    }
+
+    /// Peeks at messages and returns them as received from the SDK.
+    ///
+    /// Unlike [`peek_messages`](Self::peek_messages) the original body bytes
+    /// are kept, and messages that cannot be converted are not dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_count` - Maximum number of messages to peek at
+    /// * `from_sequence_number` - Optional starting sequence number
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receiver has been disposed or if the Service Bus operation fails
+    pub async fn peek_raw_messages(
+        &mut self,
+        max_count: u32,
+        from_sequence_number: Option<i64>,
+    ) -> Result<Vec<ServiceBusPeekedMessage>, Box<dyn std::error::Error>> {
         let mut guard = self.receiver.lock().await;
         if let Some(receiver) = guard.as_mut() {
-            let messages = receiver
+            receiver
                 .peek_messages(max_count, from_sequence_number)
-                .await?;
-            let result = MessageModel::try_convert_messages_collect(messages);
-            Ok(result)
+                .await
         } else {
             Err("Receiver already disposed".into())
         }
@@ -121,7 +296,7 @@ impl Consumer {
         let mut guard = self.receiver.lock().await;
         if let Some(receiver) = guard.as_mut() {
             match tokio::time::timeout(timeout, receiver.receive_messages(max_count)).await {
-                Ok(result) => result.map_err(|e| e.into()),
+                Ok(result) => result,
                 Err(_) => {
                     // Timeout occurred - return empty vector instead of error
                     log::debug!(
@@ -152,7 +327,7 @@ impl Consumer {
     ) -> Result<(), Box<dyn std::error::Error>> {
         let mut guard = self.receiver.lock().await;
         if let Some(receiver) = guard.as_mut() {
-            receiver.abandon_message(message, None).await?;
+            receiver.abandon_message(message).await?;
             Ok(())
         } else {
             Err("Receiver already disposed".into())
@@ -217,6 +392,32 @@ impl Consumer {
         }
     }
 
+    /// Defers a received message.
+    ///
+    /// Deferred messages stay in the queue but are no longer delivered by
+    /// receive operations; they can only be fetched again by sequence number
+    /// with [`Consumer::receive_deferred_messages`].
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to defer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receiver has been disposed or if the Service Bus operation fails
+    pub async fn defer_message(
+        &mut self,
+        message: &azservicebus::ServiceBusReceivedMessage,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut guard = self.receiver.lock().await;
+        if let Some(receiver) = guard.as_mut() {
+            receiver.defer_message(message).await?;
+            Ok(())
+        } else {
+            Err("Receiver already disposed".into())
+        }
+    }
+
     /// Completes multiple messages in a batch for better performance.
     ///
     /// Attempts to complete all provided messages, logging results for each.
@@ -309,7 +510,7 @@ impl Consumer {
         let mut guard = self.receiver.lock().await;
         if let Some(receiver) = guard.as_mut() {
             for message in messages {
-                receiver.abandon_message(message, None).await?;
+                receiver.abandon_message(message).await?;
             }
             Ok(())
         } else {
@@ -449,9 +650,7 @@ impl Consumer {
     > {
         let mut guard = self.receiver.lock().await;
         if let Some(receiver) = guard.as_mut() {
-            let messages = receiver
-                .receive_deferred_messages(sequence_numbers.to_vec())
-                .await?;
+            let messages = receiver.receive_deferred_messages(sequence_numbers).await?;
             Ok(messages)
         } else {
             Err("Receiver already disposed".into())
@@ -483,6 +682,71 @@ pub trait ServiceBusClientExt {
         queue_name: impl Into<String> + Send,
         options: ServiceBusReceiverOptions,
     ) -> impl Future<Output = Result<Consumer, azure_core::Error>>;
+
+    /// Creates a Consumer for a subscription of a topic.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic_name` - Name of the topic
+    /// * `subscription_name` - Name of the subscription
+    /// * `options` - Configuration options for the receiver, including the sub-queue
+    ///
+    /// # Returns
+    ///
+    /// A Consumer instance configured for the specified subscription
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receiver creation fails
+    fn create_consumer_for_subscription(
+        &mut self,
+        topic_name: &str,
+        subscription_name: &str,
+        options: ServiceBusReceiverOptions,
+    ) -> impl Future<Output = Result<Consumer, azure_core::Error>>;
+
+    /// Creates a Consumer bound to one session of a session-enabled queue.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue_name` - Name of the queue
+    /// * `session_id` - ID of the session to accept
+    ///
+    /// # Returns
+    ///
+    /// A Consumer instance holding the session lock
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session cannot be accepted, e.g. because it is
+    /// locked by another receiver
+    fn create_session_consumer_for_queue(
+        &mut self,
+        queue_name: &str,
+        session_id: &str,
+    ) -> impl Future<Output = Result<Consumer, azure_core::Error>>;
+
+    /// Creates a Consumer bound to one session of a session-enabled subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic_name` - Name of the topic
+    /// * `subscription_name` - Name of the subscription
+    /// * `session_id` - ID of the session to accept
+    ///
+    /// # Returns
+    ///
+    /// A Consumer instance holding the session lock
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session cannot be accepted
+    fn create_session_consumer_for_subscription(
+        &mut self,
+        topic_name: &str,
+        subscription_name: &str,
+        session_id: &str,
+    ) -> impl Future<Output = Result<Consumer, azure_core::Error>>;
 }
 
 impl<RP> ServiceBusClientExt for ServiceBusClient<RP>
@@ -514,4 +778,72 @@ where
 
         Ok(Consumer::new(receiver))
     }
+
+    /// Creates a Consumer for a topic subscription using this ServiceBusClient.
+    async fn create_consumer_for_subscription(
+        &mut self,
+        topic_name: &str,
+        subscription_name: &str,
+        options: ServiceBusReceiverOptions,
+    ) -> Result<Consumer, azure_core::Error> {
+        let receiver = self
+            .create_receiver_for_subscription(topic_name, subscription_name, options)
+            .await
+            .map_err(|e| {
+                azure_core::Error::message(
+                    azure_core::error::ErrorKind::Other,
+                    format!("Receiver error: {e}"),
+                )
+            })?;
+
+        Ok(Consumer::new(receiver))
+    }
+
+    /// Accepts a queue session and wraps it in a Consumer.
+    async fn create_session_consumer_for_queue(
+        &mut self,
+        queue_name: &str,
+        session_id: &str,
+    ) -> Result<Consumer, azure_core::Error> {
+        let receiver = self
+            .accept_session_for_queue(
+                queue_name,
+                session_id,
+                ServiceBusSessionReceiverOptions::default(),
+            )
+            .await
+            .map_err(|e| {
+                azure_core::Error::message(
+                    azure_core::error::ErrorKind::Other,
+                    format!("Session receiver error: {e}"),
+                )
+            })?;
+
+        Ok(Consumer::new_session(receiver))
+    }
+
+    /// Accepts a subscription session and wraps it in a Consumer.
+    async fn create_session_consumer_for_subscription(
+        &mut self,
+        topic_name: &str,
+        subscription_name: &str,
+        session_id: &str,
+    ) -> Result<Consumer, azure_core::Error> {
+        let receiver = self
+            .accept_session_for_subscription(
+                topic_name,
+                subscription_name,
+                session_id,
+                ServiceBusSessionReceiverOptions::default(),
+            )
+            .await
+            .map_err(|e| {
+                azure_core::Error::message(
+                    azure_core::error::ErrorKind::Other,
+                    format!("Session receiver error: {e}"),
+                )
+            })?;
+
+        Ok(Consumer::new_session(receiver))
+    }
 }
@@ -0,0 +1,814 @@
+use super::{BodyDecoder, DecodeError, DecodedOutput};
+use base64::{Engine as _, engine::general_purpose};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+
+/// Decodes Avro binary bodies written with a single `.avsc` schema into JSON.
+///
+/// Bodies are expected to hold one datum without a container or registry
+/// header. Unions use Avro's JSON encoding, `null` or `{"type": value}` with
+/// the branch's type name, so the branch survives editing; `bytes` and
+/// `fixed` values are rendered as base64. NaN and infinite floats are shown
+/// as `"NaN"`, `"Infinity"` and `"-Infinity"` but cannot be encoded again.
+pub struct AvroDecoder {
+    name: String,
+    schema: AvroSchema,
+}
+
+impl AvroDecoder {
+    /// Creates a decoder from the JSON text of an Avro schema
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema is not valid JSON or not a supported
+    /// Avro schema
+    pub fn new(name: impl Into<String>, schema: &str) -> Result<Self, DecodeError> {
+        let name = name.into();
+        let schema = AvroSchema::parse(schema)
+            .map_err(|e| DecodeError::failed(&name, format!("invalid schema: {e}")))?;
+        Ok(Self { name, schema })
+    }
+}
+
+impl BodyDecoder for AvroDecoder {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn accepts_content_type(&self, _content_type: &str) -> bool {
+        // avro/binary does not say which schema the body was written with
+        false
+    }
+
+    fn sniff(&self, _body: &[u8]) -> bool {
+        false
+    }
+
+    fn decode(&self, body: &[u8]) -> Result<DecodedOutput, DecodeError> {
+        self.schema
+            .decode(body)
+            .map(DecodedOutput::Json)
+            .map_err(|e| DecodeError::failed(self.name(), e))
+    }
+
+    fn can_encode(&self) -> bool {
+        true
+    }
+
+    fn encode(&self, content: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let value: Value =
+            serde_json::from_slice(content).map_err(|e| DecodeError::failed(self.name(), e))?;
+        self.schema
+            .encode(&value)
+            .map_err(|e| DecodeError::failed(self.name(), e))
+    }
+}
+
+/// Index of a node in [`AvroSchema::nodes`]
+type NodeId = usize;
+
+#[derive(Debug)]
+enum Node {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    Record { name: String, fields: Vec<Field> },
+    Enum { name: String, symbols: Vec<String> },
+    Array(NodeId),
+    Map(NodeId),
+    Union(Vec<NodeId>),
+    Fixed { name: String, size: usize },
+}
+
+#[derive(Debug)]
+struct Field {
+    name: String,
+    schema: NodeId,
+    default: Option<Value>,
+}
+
+/// A parsed Avro schema.
+///
+/// Nodes live in an arena so named types can reference each other,
+/// including recursively.
+#[derive(Debug)]
+struct AvroSchema {
+    nodes: Vec<Node>,
+    root: NodeId,
+}
+
+impl AvroSchema {
+    fn parse(schema: &str) -> Result<Self, String> {
+        let json: Value = serde_json::from_str(schema).map_err(|e| e.to_string())?;
+        let mut parser = SchemaParser::default();
+        let root = parser.parse(&json, None)?;
+        Ok(Self {
+            nodes: parser.nodes,
+            root,
+        })
+    }
+
+    fn decode(&self, body: &[u8]) -> Result<Value, String> {
+        let mut reader = Reader { bytes: body };
+        let value = self.read(self.root, &mut reader, 0)?;
+        if !reader.bytes.is_empty() {
+            return Err(format!("{} trailing bytes", reader.bytes.len()));
+        }
+        Ok(value)
+    }
+
+    fn encode(&self, value: &Value) -> Result<Vec<u8>, String> {
+        let mut output = Vec::new();
+        self.write(self.root, value, &mut output, 0)?;
+        Ok(output)
+    }
+
+    /// Reads the value of `node`, nested `depth` levels deep
+    fn read(&self, node: NodeId, reader: &mut Reader, depth: usize) -> Result<Value, String> {
+        check_depth(depth)?;
+        Ok(match &self.nodes[node] {
+            Node::Null => Value::Null,
+            Node::Boolean => Value::Bool(reader.take(1)?[0] != 0),
+            Node::Int => {
+                let int = reader.long()?;
+                let int = i32::try_from(int).map_err(|_| format!("int {int} out of range"))?;
+                Value::from(int)
+            }
+            Node::Long => Value::from(reader.long()?),
+            Node::Float => {
+                let bytes = reader.take(4)?.try_into().map_err(|_| "invalid float")?;
+                float_to_json(f64::from(f32::from_le_bytes(bytes)))
+            }
+            Node::Double => {
+                let bytes = reader.take(8)?.try_into().map_err(|_| "invalid double")?;
+                float_to_json(f64::from_le_bytes(bytes))
+            }
+            Node::Bytes => {
+                let len = reader.length()?;
+                Value::String(general_purpose::STANDARD.encode(reader.take(len)?))
+            }
+            Node::String => {
+                let len = reader.length()?;
+                let text = std::str::from_utf8(reader.take(len)?).map_err(|e| e.to_string())?;
+                Value::String(text.to_string())
+            }
+            Node::Record { fields, .. } => {
+                let mut object = Map::new();
+                for field in fields {
+                    object.insert(
+                        field.name.clone(),
+                        self.read(field.schema, reader, depth + 1)?,
+                    );
+                }
+                Value::Object(object)
+            }
+            Node::Enum { symbols, .. } => {
+                let index = reader.long()?;
+                let symbol = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| symbols.get(index))
+                    .ok_or_else(|| format!("enum index {index} out of range"))?;
+                Value::String(symbol.clone())
+            }
+            Node::Array(items) => {
+                let mut values = Vec::new();
+                while let Some(count) = reader.block_count()? {
+                    for _ in 0..count {
+                        values.push(self.read(*items, reader, depth + 1)?);
+                    }
+                }
+                Value::Array(values)
+            }
+            Node::Map(values) => {
+                let mut object = Map::new();
+                while let Some(count) = reader.block_count()? {
+                    for _ in 0..count {
+                        let len = reader.length()?;
+                        let key = std::str::from_utf8(reader.take(len)?)
+                            .map_err(|e| e.to_string())?
+                            .to_string();
+                        object.insert(key, self.read(*values, reader, depth + 1)?);
+                    }
+                }
+                Value::Object(object)
+            }
+            Node::Union(branches) => {
+                let index = reader.long()?;
+                let branch = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| branches.get(index))
+                    .ok_or_else(|| format!("union index {index} out of range"))?;
+                match self.nodes[*branch] {
+                    Node::Null => Value::Null,
+                    _ => {
+                        let value = self.read(*branch, reader, depth + 1)?;
+                        Value::Object(Map::from_iter([(self.type_name(*branch), value)]))
+                    }
+                }
+            }
+            Node::Fixed { size, .. } => {
+                Value::String(general_purpose::STANDARD.encode(reader.take(*size)?))
+            }
+        })
+    }
+
+    /// Writes `value` as `node`, nested `depth` levels deep
+    fn write(
+        &self,
+        node: NodeId,
+        value: &Value,
+        output: &mut Vec<u8>,
+        depth: usize,
+    ) -> Result<(), String> {
+        check_depth(depth)?;
+        let mismatch = || format!("expected {}, found {value}", self.describe(node));
+
+        match (&self.nodes[node], value) {
+            (Node::Null, Value::Null) => {}
+            (Node::Boolean, Value::Bool(b)) => output.push(u8::from(*b)),
+            (Node::Int, Value::Number(n)) => {
+                let int = n
+                    .as_i64()
+                    .and_then(|int| i32::try_from(int).ok())
+                    .ok_or_else(mismatch)?;
+                write_long(output, i64::from(int));
+            }
+            (Node::Long, Value::Number(n)) => write_long(output, n.as_i64().ok_or_else(mismatch)?),
+            (Node::Float, Value::Number(n)) => {
+                let float = n.as_f64().ok_or_else(mismatch)? as f32;
+                if !float.is_finite() {
+                    return Err(format!("{n} does not fit in a float"));
+                }
+                output.extend_from_slice(&float.to_le_bytes());
+            }
+            (Node::Double, Value::Number(n)) => {
+                output.extend_from_slice(&n.as_f64().ok_or_else(mismatch)?.to_le_bytes());
+            }
+            (Node::Float | Node::Double, Value::String(s)) if NON_FINITE.contains(&s.as_str()) => {
+                return Err(format!("{s} cannot be encoded; use a finite number"));
+            }
+            (Node::Bytes, Value::String(s)) => {
+                let bytes = decode_base64(s)?;
+                write_long(output, bytes.len() as i64);
+                output.extend_from_slice(&bytes);
+            }
+            (Node::String, Value::String(s)) => {
+                write_long(output, s.len() as i64);
+                output.extend_from_slice(s.as_bytes());
+            }
+            (Node::Record { fields, .. }, Value::Object(object)) => {
+                for field in fields {
+                    let value = object
+                        .get(&field.name)
+                        .or(field.default.as_ref())
+                        .ok_or_else(|| format!("missing field '{}'", field.name))?;
+                    self.write(field.schema, value, output, depth + 1)
+                        .map_err(|e| format!("{}: {e}", field.name))?;
+                }
+            }
+            (Node::Enum { symbols, .. }, Value::String(s)) => {
+                let index = symbols
+                    .iter()
+                    .position(|symbol| symbol == s)
+                    .ok_or_else(|| format!("unknown enum symbol '{s}'"))?;
+                write_long(output, index as i64);
+            }
+            (Node::Array(items), Value::Array(values)) => {
+                if !values.is_empty() {
+                    write_long(output, values.len() as i64);
+                    for value in values {
+                        self.write(*items, value, output, depth + 1)?;
+                    }
+                }
+                write_long(output, 0);
+            }
+            (Node::Map(values), Value::Object(object)) => {
+                if !object.is_empty() {
+                    write_long(output, object.len() as i64);
+                    for (key, value) in object {
+                        write_long(output, key.len() as i64);
+                        output.extend_from_slice(key.as_bytes());
+                        self.write(*values, value, output, depth + 1)?;
+                    }
+                }
+                write_long(output, 0);
+            }
+            (Node::Union(branches), _) => {
+                let (type_name, value) = match value {
+                    Value::Null => ("null".to_string(), value),
+                    Value::Object(object) if object.len() == 1 => object
+                        .iter()
+                        .next()
+                        .map(|(type_name, value)| (type_name.clone(), value))
+                        .ok_or_else(mismatch)?,
+                    _ => return Err(mismatch()),
+                };
+                let (index, branch) = branches
+                    .iter()
+                    .enumerate()
+                    .find(|(_, branch)| self.type_name(**branch) == type_name)
+                    .ok_or_else(|| format!("union has no '{type_name}' branch"))?;
+                write_long(output, index as i64);
+                self.write(*branch, value, output, depth + 1)
+                    .map_err(|e| format!("{type_name}: {e}"))?;
+            }
+            (Node::Fixed { size, .. }, Value::String(s)) => {
+                let bytes = decode_base64(s)?;
+                if bytes.len() != *size {
+                    return Err(format!("expected {size} bytes, found {}", bytes.len()));
+                }
+                output.extend_from_slice(&bytes);
+            }
+            _ => return Err(mismatch()),
+        }
+        Ok(())
+    }
+
+    /// Name identifying a union branch in Avro's JSON encoding: the full
+    /// name of named types, the type otherwise
+    fn type_name(&self, node: NodeId) -> String {
+        match &self.nodes[node] {
+            Node::Record { name, .. } | Node::Enum { name, .. } | Node::Fixed { name, .. } => {
+                name.clone()
+            }
+            Node::Array(_) => "array".to_string(),
+            Node::Map(_) => "map".to_string(),
+            Node::Union(_) => "union".to_string(),
+            _ => self.describe(node).to_string(),
+        }
+    }
+
+    fn describe(&self, node: NodeId) -> &'static str {
+        match &self.nodes[node] {
+            Node::Null => "null",
+            Node::Boolean => "boolean",
+            Node::Int => "int",
+            Node::Long => "long",
+            Node::Float => "float",
+            Node::Double => "double",
+            Node::Bytes => "bytes",
+            Node::String => "string",
+            Node::Record { .. } => "record",
+            Node::Enum { .. } => "enum symbol",
+            Node::Array(_) => "array",
+            Node::Map(_) => "map",
+            Node::Union(_) => "null or a {\"type\": value} union object",
+            Node::Fixed { .. } => "base64 fixed",
+        }
+    }
+}
+
+#[derive(Default)]
+struct SchemaParser {
+    nodes: Vec<Node>,
+    named: HashMap<String, NodeId>,
+}
+
+impl SchemaParser {
+    fn parse(&mut self, json: &Value, namespace: Option<&str>) -> Result<NodeId, String> {
+        match json {
+            Value::String(name) => self.parse_name(name, namespace),
+            Value::Array(branches) => {
+                let branches = branches
+                    .iter()
+                    .map(|branch| self.parse(branch, namespace))
+                    .collect::<Result<_, _>>()?;
+                Ok(self.push(Node::Union(branches)))
+            }
+            Value::Object(object) => self.parse_complex(object, namespace),
+            other => Err(format!("invalid schema {other}")),
+        }
+    }
+
+    fn parse_name(&mut self, name: &str, namespace: Option<&str>) -> Result<NodeId, String> {
+        let primitive = match name {
+            "null" => Node::Null,
+            "boolean" => Node::Boolean,
+            "int" => Node::Int,
+            "long" => Node::Long,
+            "float" => Node::Float,
+            "double" => Node::Double,
+            "bytes" => Node::Bytes,
+            "string" => Node::String,
+            _ => {
+                let qualified = namespace
+                    .filter(|_| !name.contains('.'))
+                    .map(|namespace| format!("{namespace}.{name}"));
+                return qualified
+                    .and_then(|qualified| self.named.get(&qualified))
+                    .or_else(|| self.named.get(name))
+                    .copied()
+                    .ok_or_else(|| format!("unknown type '{name}'"));
+            }
+        };
+        Ok(self.push(primitive))
+    }
+
+    fn parse_complex(
+        &mut self,
+        object: &Map<String, Value>,
+        namespace: Option<&str>,
+    ) -> Result<NodeId, String> {
+        let type_name = match object.get("type") {
+            Some(Value::String(type_name)) => type_name.as_str(),
+            // {"type": {"type": "array", ...}} nests a schema
+            Some(nested) => return self.parse(nested, namespace),
+            None => return Err("schema object without 'type'".to_string()),
+        };
+
+        match type_name {
+            "record" | "error" => {
+                let (full_name, namespace) = self.full_name(object, namespace)?;
+                // Registered before the fields so records can refer to themselves
+                let id = self.push(Node::Record {
+                    name: full_name.clone(),
+                    fields: Vec::new(),
+                });
+                self.named.insert(full_name, id);
+
+                let fields = object
+                    .get("fields")
+                    .and_then(Value::as_array)
+                    .ok_or("record without 'fields'")?;
+                let mut parsed = Vec::with_capacity(fields.len());
+                for field in fields {
+                    let name = field
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .ok_or("field without 'name'")?;
+                    let schema = field
+                        .get("type")
+                        .ok_or_else(|| format!("field '{name}' without 'type'"))?;
+                    parsed.push(Field {
+                        name: name.to_string(),
+                        schema: self.parse(schema, namespace.as_deref())?,
+                        default: field.get("default").cloned(),
+                    });
+                }
+                if let Node::Record { fields, .. } = &mut self.nodes[id] {
+                    *fields = parsed;
+                }
+                Ok(id)
+            }
+            "enum" => {
+                let (full_name, _) = self.full_name(object, namespace)?;
+                let symbols = object
+                    .get("symbols")
+                    .and_then(Value::as_array)
+                    .ok_or("enum without 'symbols'")?
+                    .iter()
+                    .map(|symbol| symbol.as_str().map(str::to_string))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or("enum symbols must be strings")?;
+                let id = self.push(Node::Enum {
+                    name: full_name.clone(),
+                    symbols,
+                });
+                self.named.insert(full_name, id);
+                Ok(id)
+            }
+            "fixed" => {
+                let (full_name, _) = self.full_name(object, namespace)?;
+                let size = object
+                    .get("size")
+                    .and_then(Value::as_u64)
+                    .ok_or("fixed without 'size'")?;
+                let id = self.push(Node::Fixed {
+                    name: full_name.clone(),
+                    size: size as usize,
+                });
+                self.named.insert(full_name, id);
+                Ok(id)
+            }
+            "array" => {
+                let items = object.get("items").ok_or("array without 'items'")?;
+                let items = self.parse(items, namespace)?;
+                Ok(self.push(Node::Array(items)))
+            }
+            "map" => {
+                let values = object.get("values").ok_or("map without 'values'")?;
+                let values = self.parse(values, namespace)?;
+                Ok(self.push(Node::Map(values)))
+            }
+            // Primitives, possibly annotated with a logical type that is
+            // rendered as its underlying value
+            primitive => self.parse_name(primitive, namespace),
+        }
+    }
+
+    /// Full name and namespace of a named type
+    fn full_name(
+        &self,
+        object: &Map<String, Value>,
+        namespace: Option<&str>,
+    ) -> Result<(String, Option<String>), String> {
+        let name = object
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or("named type without 'name'")?;
+        if let Some((namespace, _)) = name.rsplit_once('.') {
+            return Ok((name.to_string(), Some(namespace.to_string())));
+        }
+
+        let namespace = object
+            .get("namespace")
+            .and_then(Value::as_str)
+            .or(namespace)
+            .filter(|namespace| !namespace.is_empty());
+        let full_name = match namespace {
+            Some(namespace) => format!("{namespace}.{name}"),
+            None => name.to_string(),
+        };
+        Ok((full_name, namespace.map(str::to_string)))
+    }
+
+    fn push(&mut self, node: Node) -> NodeId {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if len > self.bytes.len() {
+            return Err("unexpected end of body".to_string());
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    /// Reads a zigzag-encoded variable length integer
+    fn long(&mut self) -> Result<i64, String> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+            }
+        }
+        Err("integer overflows 64 bits".to_string())
+    }
+
+    fn length(&mut self) -> Result<usize, String> {
+        let len = self.long()?;
+        usize::try_from(len).map_err(|_| format!("negative length {len}"))
+    }
+
+    /// Reads the item count of the next array or map block, `None` at the end.
+    ///
+    /// Counts larger than the rest of the body are rejected, so a corrupt
+    /// count cannot make the decoder allocate or loop without bound.
+    fn block_count(&mut self) -> Result<Option<u64>, String> {
+        let count = self.long()?;
+        if count == 0 {
+            return Ok(None);
+        }
+        if count < 0 {
+            // A negative count is followed by the block size in bytes
+            self.long()?;
+        }
+        let count = count.unsigned_abs();
+        if count > self.bytes.len() as u64 {
+            return Err(format!(
+                "block of {count} items exceeds the {} remaining bytes",
+                self.bytes.len()
+            ));
+        }
+        Ok(Some(count))
+    }
+}
+
+/// Deepest nesting of records, arrays, maps and unions that is read or
+/// written, so a corrupt body or document for a recursive schema cannot
+/// overflow the stack
+const MAX_DEPTH: usize = 64;
+
+fn check_depth(depth: usize) -> Result<(), String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("values nested deeper than {MAX_DEPTH} levels"));
+    }
+    Ok(())
+}
+
+fn write_long(output: &mut Vec<u8>, value: i64) {
+    let mut zigzag = ((value << 1) ^ (value >> 63)) as u64;
+    while zigzag >= 0x80 {
+        output.push((zigzag as u8 & 0x7f) | 0x80);
+        zigzag >>= 7;
+    }
+    output.push(zigzag as u8);
+}
+
+fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    general_purpose::STANDARD
+        .decode(text)
+        .map_err(|e| format!("invalid base64: {e}"))
+}
+
+/// How NaN and infinite floats are shown, which JSON numbers cannot express
+const NON_FINITE: [&str; 3] = ["NaN", "Infinity", "-Infinity"];
+
+fn float_to_json(value: f64) -> Value {
+    match Number::from_f64(value) {
+        Some(number) => Value::Number(number),
+        None if value.is_nan() => Value::String(NON_FINITE[0].to_string()),
+        None if value > 0.0 => Value::String(NON_FINITE[1].to_string()),
+        None => Value::String(NON_FINITE[2].to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ORDER_SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "OrderCreated",
+        "namespace": "shop.orders",
+        "fields": [
+            {"name": "orderId", "type": "long"},
+            {"name": "status", "type": {"type": "enum", "name": "Status", "symbols": ["NEW", "PAID"]}},
+            {"name": "note", "type": ["null", "string"], "default": null},
+            {"name": "lines", "type": {"type": "array", "items": {
+                "type": "record", "name": "Line", "fields": [
+                    {"name": "sku", "type": "string"},
+                    {"name": "quantity", "type": "int"}
+                ]
+            }}},
+            {"name": "previous", "type": ["null", "Status"]}
+        ]
+    }"#;
+
+    #[test]
+    fn test_decode_and_encode_round_trip() {
+        let decoder = AvroDecoder::new("orders", ORDER_SCHEMA).unwrap();
+        let document = json!({
+            "orderId": 42,
+            "status": "PAID",
+            "note": {"string": "leave at door"},
+            "lines": [{"sku": "A-1", "quantity": 2}],
+            "previous": {"shop.orders.Status": "NEW"}
+        });
+
+        let body = decoder
+            .encode(serde_json::to_string(&document).unwrap().as_bytes())
+            .unwrap();
+        assert_eq!(&body[..3], [0x54, 0x02, 0x02]);
+        assert_eq!(
+            decoder.decode(&body).unwrap(),
+            DecodedOutput::Json(document)
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let decoder = AvroDecoder::new("orders", ORDER_SCHEMA).unwrap();
+        let edited = br#"{"orderId": -1, "status": "NEW", "lines": [], "previous": null}"#;
+
+        let DecodedOutput::Json(value) = decoder.decode(&decoder.encode(edited).unwrap()).unwrap()
+        else {
+            panic!("expected a JSON document");
+        };
+        assert_eq!(value["note"], Value::Null);
+        assert_eq!(value["orderId"], json!(-1));
+    }
+
+    #[test]
+    fn test_recursive_named_types() {
+        let schema = r#"{"type": "record", "name": "Node", "fields": [
+            {"name": "value", "type": "int"},
+            {"name": "next", "type": ["null", "Node"]}
+        ]}"#;
+        let decoder = AvroDecoder::new("list", schema).unwrap();
+        let document = json!({"value": 1, "next": {"Node": {"value": 2, "next": null}}});
+
+        let body = decoder.encode(document.to_string().as_bytes()).unwrap();
+        assert_eq!(body, [0x02, 0x02, 0x04, 0x00]);
+        assert_eq!(
+            decoder.decode(&body).unwrap(),
+            DecodedOutput::Json(document)
+        );
+    }
+
+    #[test]
+    fn test_nesting_is_bounded() {
+        let schema = r#"{"type": "record", "name": "Node", "fields": [
+            {"name": "next", "type": ["null", "Node"]}
+        ]}"#;
+        let decoder = AvroDecoder::new("list", schema).unwrap();
+
+        // Every node takes the "Node" branch of the union, without an end
+        let error = decoder.decode(&[0x02; 10_000]).unwrap_err().to_string();
+        assert!(error.contains("nested deeper"), "{error}");
+
+        let mut document = json!(null);
+        // Each node is a record in a union, two levels, within serde_json's limit
+        for _ in 0..40 {
+            document = json!({"Node": {"next": document}});
+        }
+        let error = decoder
+            .encode(json!({"next": document}).to_string().as_bytes())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("nested deeper"), "{error}");
+
+        // A short list still works
+        let body = decoder
+            .encode(br#"{"next": {"Node": {"next": null}}}"#)
+            .unwrap();
+        assert!(decoder.decode(&body).is_ok());
+    }
+
+    #[test]
+    fn test_ints_out_of_range_are_rejected() {
+        let decoder = AvroDecoder::new("count", r#""int""#).unwrap();
+        let mut body = Vec::new();
+        write_long(&mut body, i64::from(i32::MAX) + 1);
+
+        let error = decoder.decode(&body).unwrap_err().to_string();
+        assert!(error.contains("out of range"), "{error}");
+
+        let mut body = Vec::new();
+        write_long(&mut body, i64::from(i32::MIN));
+        assert_eq!(
+            decoder.decode(&body).unwrap(),
+            DecodedOutput::Json(json!(i32::MIN))
+        );
+
+        // A long takes the same value
+        let long = AvroDecoder::new("count", r#""long""#).unwrap();
+        let mut body = Vec::new();
+        write_long(&mut body, i64::from(i32::MAX) + 1);
+        assert_eq!(
+            long.decode(&body).unwrap(),
+            DecodedOutput::Json(json!(i64::from(i32::MAX) + 1))
+        );
+    }
+
+    #[test]
+    fn test_union_keeps_its_branch() {
+        let decoder = AvroDecoder::new("id", r#"["null", "long", "double"]"#).unwrap();
+
+        // 1.0 as a double would be written as the long 1 if the branch were guessed
+        let body = decoder.encode(br#"{"double": 1.0}"#).unwrap();
+        assert_eq!(body[0], 0x04);
+        assert_eq!(
+            decoder.decode(&body).unwrap(),
+            DecodedOutput::Json(json!({"double": 1.0}))
+        );
+
+        assert!(decoder.encode(b"1").is_err());
+        assert!(decoder.encode(br#"{"string": "1"}"#).is_err());
+    }
+
+    #[test]
+    fn test_block_counts_are_bounded_by_the_body() {
+        let decoder = AvroDecoder::new("ids", r#"{"type": "array", "items": "long"}"#).unwrap();
+        let mut body = Vec::new();
+        write_long(&mut body, i64::MAX);
+        body.push(0x02);
+
+        let error = decoder.decode(&body).unwrap_err().to_string();
+        assert!(error.contains("exceeds"), "{error}");
+    }
+
+    #[test]
+    fn test_non_finite_floats_are_shown_but_not_encoded() {
+        let decoder = AvroDecoder::new("reading", r#""double""#).unwrap();
+
+        let nan = decoder.decode(&f64::NAN.to_le_bytes()).unwrap();
+        assert_eq!(nan, DecodedOutput::Json(json!("NaN")));
+        let infinity = decoder.decode(&f64::NEG_INFINITY.to_le_bytes()).unwrap();
+        assert_eq!(infinity, DecodedOutput::Json(json!("-Infinity")));
+
+        assert!(decoder.encode(br#""NaN""#).is_err());
+        let float = AvroDecoder::new("reading", r#""float""#).unwrap();
+        assert!(float.encode(b"1e300").is_err());
+    }
+
+    #[test]
+    fn test_invalid_bodies_and_documents() {
+        let decoder = AvroDecoder::new("orders", ORDER_SCHEMA).unwrap();
+        assert!(decoder.decode(&[0x54, 0x08]).is_err());
+        assert!(decoder.encode(br#"{"orderId": "42"}"#).is_err());
+        assert!(
+            AvroDecoder::new(
+                "broken",
+                r#"{"type": "record", "name": "X", "fields": [{"name": "a", "type": "Missing"}]}"#
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,548 @@
+use super::{
+    Base64Decoder, BodyDecoder, BrotliDecoder, CborDecoder, DecodeError, DecodedOutput,
+    DeflateDecoder, GzipDecoder, MessagePackDecoder, SchemaConfig,
+};
+use crate::model::{BodyData, MessageModel};
+use crate::service_bus_manager::types::{DEAD_LETTER_SUFFIX, parse_subscription_path};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Settings for choosing body decoders.
+///
+/// Without a queue entry, decoders are picked by the message ContentType and
+/// then by sniffing the body. A queue entry replaces detection with a fixed
+/// list of decoders applied in order; dead letter queues use the entry of
+/// their queue, subscriptions the entry of their topic unless they have one
+/// of their own. Registered schemas apply to the messages they are mapped to
+/// when the queue has no entry.
+///
+/// # Examples
+///
+/// ```toml
+/// [decoders]
+/// sniff = true
+///
+/// [decoders.queues]
+/// telemetry = ["gzip", "msgpack"]
+///
+/// [[decoders.schemas]]
+/// name = "orders"
+/// format = "avro"
+/// path = "~/.config/quetty/schemas/order.avsc"
+/// subjects = ["OrderCreated"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct DecoderConfig {
+    /// Whether bodies are decoded at all
+    pub enabled: bool,
+    /// Whether decoders may be chosen by inspecting the body when the
+    /// ContentType does not name one
+    pub sniff: bool,
+    /// Maximum number of decoding steps applied to one body
+    pub max_depth: usize,
+    /// Decoder names applied in order for specific queues, topics or subscriptions
+    pub queues: HashMap<String, Vec<String>>,
+    /// Protocol Buffers and Avro schemas, tried in order
+    pub schemas: Vec<SchemaConfig>,
+}
+
+impl Default for DecoderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sniff: true,
+            max_depth: 4,
+            queues: HashMap::new(),
+            schemas: Vec::new(),
+        }
+    }
+}
+
+impl DecoderConfig {
+    /// Decoder list configured for `entity_path`, if any
+    pub fn decoders_for(&self, entity_path: &str) -> Option<&[String]> {
+        entity_keys(entity_path)
+            .into_iter()
+            .find_map(|key| self.queues.get(key))
+            .map(Vec::as_slice)
+    }
+}
+
+/// Configuration keys that apply to `entity_path`, most specific first: the
+/// path itself, the queue of a dead letter queue and the topic of a
+/// subscription
+fn entity_keys(entity_path: &str) -> Vec<&str> {
+    let queue = entity_path
+        .strip_suffix(DEAD_LETTER_SUFFIX)
+        .unwrap_or(entity_path);
+    let topic = parse_subscription_path(entity_path).map(|(topic, _)| topic);
+
+    [Some(entity_path), Some(queue), topic]
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// A decoded message body together with the decoders that produced it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedBody {
+    /// Names of the decoders applied, outermost first
+    pub steps: Vec<String>,
+    /// The decoded content
+    pub body: BodyData,
+    /// Whether every step can encode, so edits to the decoded content can be
+    /// turned back into a body with [`DecoderChain::encode`]
+    pub encodable: bool,
+}
+
+/// Applies body decoders to message bodies.
+///
+/// Each step picks a decoder, applies it and continues with its output until
+/// a decoder produces a structured document, no decoder matches or
+/// `max_depth` is reached. The ContentType only selects the first decoder;
+/// later steps rely on sniffing.
+pub struct DecoderChain {
+    decoders: Vec<Box<dyn BodyDecoder>>,
+    config: DecoderConfig,
+    /// Schemas that failed to load, reported when a message needs them
+    schema_errors: HashMap<String, DecodeError>,
+}
+
+impl DecoderChain {
+    /// Creates a chain with the built-in decoders and the configured schemas.
+    ///
+    /// Decoders are tried in registration order, so the cheap and
+    /// unambiguous magic-byte checks come first. Schema files are read here;
+    /// a schema that fails to load is logged and its error shown for the
+    /// messages mapped to it.
+    pub fn new(config: DecoderConfig) -> Self {
+        let mut decoders: Vec<Box<dyn BodyDecoder>> = vec![
+            Box::new(GzipDecoder),
+            Box::new(DeflateDecoder),
+            Box::new(BrotliDecoder),
+            Box::new(CborDecoder),
+            Box::new(MessagePackDecoder),
+            Box::new(Base64Decoder),
+        ];
+        let mut schema_errors = HashMap::new();
+        for schema in &config.schemas {
+            match schema.load() {
+                Ok(decoder) => decoders.push(decoder),
+                Err(e) => {
+                    log::warn!("Failed to load schema '{}': {e}", schema.name);
+                    schema_errors.insert(schema.name.to_ascii_lowercase(), e);
+                }
+            }
+        }
+
+        Self {
+            decoders,
+            config,
+            schema_errors,
+        }
+    }
+
+    /// Registers an additional decoder, tried before the built-in ones
+    pub fn with_decoder(mut self, decoder: Box<dyn BodyDecoder>) -> Self {
+        self.decoders.insert(0, decoder);
+        self
+    }
+
+    /// Names of all registered decoders
+    pub fn decoder_names(&self) -> Vec<&str> {
+        self.decoders.iter().map(|decoder| decoder.name()).collect()
+    }
+
+    /// Decodes a message body received from `entity_path`.
+    ///
+    /// Schemas mapped by Subject or property value need the message; use
+    /// [`DecoderChain::decode_message`] for those.
+    ///
+    /// # Returns
+    ///
+    /// `None` if decoding is disabled or no decoder applies
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configured decoder is unknown or a decoder
+    /// rejects the body
+    pub fn decode(
+        &self,
+        entity_path: &str,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Result<Option<DecodedBody>, DecodeError> {
+        self.decode_body(entity_path, content_type, body, None)
+    }
+
+    /// Decodes the body of a peeked message using its ContentType, Subject
+    /// and application properties.
+    ///
+    /// # Errors
+    ///
+    /// See [`DecoderChain::decode`]
+    pub fn decode_message(
+        &self,
+        entity_path: &str,
+        message: &MessageModel,
+    ) -> Result<Option<DecodedBody>, DecodeError> {
+        self.decode_body(
+            entity_path,
+            message.properties.content_type.as_deref(),
+            &message.raw_body,
+            Some(message),
+        )
+    }
+
+    /// Encodes edited decoded content back into a body by applying the
+    /// encoders of `steps` innermost first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a step is unknown, cannot encode or rejects the
+    /// content
+    pub fn encode(&self, steps: &[String], content: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut current = content.to_vec();
+        for name in steps.iter().rev() {
+            current = self.find(name)?.encode(&current)?;
+        }
+        Ok(current)
+    }
+
+    fn decode_body(
+        &self,
+        entity_path: &str,
+        content_type: Option<&str>,
+        body: &[u8],
+        message: Option<&MessageModel>,
+    ) -> Result<Option<DecodedBody>, DecodeError> {
+        if !self.config.enabled {
+            return Ok(None);
+        }
+
+        if let Some(names) = self.config.decoders_for(entity_path) {
+            return self.decode_with(names, body).map(Some);
+        }
+
+        let keys = entity_keys(entity_path);
+        match self
+            .config
+            .schemas
+            .iter()
+            .find(|schema| schema.matches(&keys, message))
+        {
+            Some(schema) => self
+                .decode_with(std::slice::from_ref(&schema.name), body)
+                .map(Some),
+            None => self.detect_and_decode(content_type, body),
+        }
+    }
+
+    /// Applies the named decoders in order
+    fn decode_with(&self, names: &[String], body: &[u8]) -> Result<DecodedBody, DecodeError> {
+        let mut steps = Vec::with_capacity(names.len());
+        let mut current = DecodedOutput::Bytes(body.to_vec());
+
+        for name in names {
+            let decoder = self.find(name)?;
+            let DecodedOutput::Bytes(bytes) = &current else {
+                return Err(DecodeError::failed(
+                    name,
+                    "the previous decoder already produced a document",
+                ));
+            };
+            current = decoder.decode(bytes)?;
+            steps.push(decoder.name().to_string());
+        }
+
+        Ok(self.decoded(steps, into_body(current)))
+    }
+
+    /// Picks decoders by content type and sniffing until nothing matches
+    fn detect_and_decode(
+        &self,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Result<Option<DecodedBody>, DecodeError> {
+        let mut content_type = content_type.map(normalize_content_type);
+        let mut steps = Vec::new();
+        let mut current = body.to_vec();
+
+        while steps.len() < self.config.max_depth {
+            let declared = content_type.take().and_then(|content_type| {
+                self.decoders
+                    .iter()
+                    .find(|decoder| decoder.accepts_content_type(&content_type))
+            });
+            let sniffed = || {
+                self.config
+                    .sniff
+                    .then(|| self.decoders.iter().find(|decoder| decoder.sniff(&current)))
+                    .flatten()
+            };
+            let (decoder, is_declared) = match declared {
+                Some(decoder) => (decoder, true),
+                None => match sniffed() {
+                    Some(decoder) => (decoder, false),
+                    None => break,
+                },
+            };
+
+            let output = match decoder.decode(&current) {
+                Ok(output) => output,
+                Err(e) if is_declared => return Err(e),
+                Err(e) => {
+                    // Sniffing is a guess; a body that merely looked encoded
+                    // is shown as it is
+                    log::debug!("Ignoring sniffed {} decoder: {e}", decoder.name());
+                    break;
+                }
+            };
+            steps.push(decoder.name().to_string());
+            match output {
+                DecodedOutput::Bytes(bytes) => current = bytes,
+                DecodedOutput::Json(value) => {
+                    return Ok(Some(self.decoded(steps, BodyData::ValidJson(value))));
+                }
+            }
+        }
+
+        if steps.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(self.decoded(steps, BodyData::from_bytes(&current))))
+    }
+
+    fn decoded(&self, steps: Vec<String>, body: BodyData) -> DecodedBody {
+        let encodable = steps
+            .iter()
+            .all(|step| self.find(step).is_ok_and(|decoder| decoder.can_encode()));
+        DecodedBody {
+            steps,
+            body,
+            encodable,
+        }
+    }
+
+    fn find(&self, name: &str) -> Result<&dyn BodyDecoder, DecodeError> {
+        if let Some(decoder) = self
+            .decoders
+            .iter()
+            .find(|decoder| decoder.name().eq_ignore_ascii_case(name))
+        {
+            return Ok(decoder.as_ref());
+        }
+
+        match self.schema_errors.get(&name.to_ascii_lowercase()) {
+            Some(error) => Err(error.clone()),
+            None => Err(DecodeError::UnknownDecoder(name.to_string())),
+        }
+    }
+}
+
+fn into_body(output: DecodedOutput) -> BodyData {
+    match output {
+        DecodedOutput::Bytes(bytes) => BodyData::from_bytes(&bytes),
+        DecodedOutput::Json(value) => BodyData::ValidJson(value),
+    }
+}
+
+/// Lowercases a content type and strips parameters such as `charset`
+fn normalize_content_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoding::SchemaFormat;
+    use crate::model::{MessageProperties, MessageState};
+    use azure_core::time::OffsetDateTime;
+    use base64::{Engine as _, engine::general_purpose};
+    use flate2::{Compression, write::GzEncoder};
+    use serde_json::json;
+    use std::io::Write;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_sniffs_base64_wrapped_gzip_json() {
+        let body = general_purpose::STANDARD.encode(gzip(br#"{"orderId":42}"#));
+        let chain = DecoderChain::new(DecoderConfig::default());
+
+        let decoded = chain
+            .decode("orders", None, body.as_bytes())
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded.steps, vec!["base64", "gzip"]);
+        assert_eq!(decoded.body, BodyData::ValidJson(json!({"orderId": 42})));
+    }
+
+    #[test]
+    fn test_plain_bodies_are_left_alone() {
+        let chain = DecoderChain::new(DecoderConfig::default());
+        for body in [
+            &br#"{"orderId":42}"#[..],
+            b"Hello, Service Bus!",
+            b"abcdefghijklmnopqrstuvwx",
+        ] {
+            assert_eq!(chain.decode("orders", None, body).unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn test_content_type_selects_decoder_without_sniffing() {
+        let config = DecoderConfig {
+            sniff: false,
+            ..Default::default()
+        };
+        let chain = DecoderChain::new(config);
+        let body = gzip(b"compressed text");
+
+        assert_eq!(chain.decode("orders", None, &body).unwrap(), None);
+        let decoded = chain
+            .decode("orders", Some("application/gzip; charset=binary"), &body)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            decoded.body,
+            BodyData::RawString("compressed text".to_string())
+        );
+    }
+
+    #[test]
+    fn test_queue_config_applies_to_dead_letter_queue() {
+        let mut config = DecoderConfig::default();
+        config
+            .queues
+            .insert("telemetry".to_string(), vec!["gzip".to_string()]);
+        let chain = DecoderChain::new(config);
+
+        let decoded = chain
+            .decode("telemetry/$deadletterqueue", None, &gzip(b"reading"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded.steps, vec!["gzip"]);
+
+        // A body that does not match the configured decoder is an error
+        assert!(chain.decode("telemetry", None, b"not gzip").is_err());
+    }
+
+    #[test]
+    fn test_unknown_configured_decoder() {
+        let mut config = DecoderConfig::default();
+        config
+            .queues
+            .insert("orders".to_string(), vec!["zstd".to_string()]);
+        let chain = DecoderChain::new(config);
+
+        assert!(matches!(
+            chain.decode("orders", None, b"body"),
+            Err(DecodeError::UnknownDecoder(name)) if name == "zstd"
+        ));
+    }
+
+    fn avro_schema(path: std::path::PathBuf) -> SchemaConfig {
+        SchemaConfig {
+            name: "reading".to_string(),
+            format: SchemaFormat::Avro,
+            path,
+            message_type: None,
+            queues: Vec::new(),
+            subjects: vec!["Reading".to_string()],
+            property: None,
+        }
+    }
+
+    fn message_with_subject(subject: &str, body: &[u8]) -> MessageModel {
+        MessageModel::new(
+            1,
+            "id".to_string(),
+            OffsetDateTime::now_utc(),
+            0,
+            MessageState::Active,
+            BodyData::from_bytes(body),
+        )
+        .with_properties(MessageProperties {
+            subject: Some(subject.to_string()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_schema_selected_by_subject_round_trips_edits() {
+        let path = std::env::temp_dir().join(format!("quetty-reading-{}.avsc", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"type": "record", "name": "Reading", "fields": [{"name": "celsius", "type": "int"}]}"#,
+        )
+        .unwrap();
+        let config = DecoderConfig {
+            schemas: vec![avro_schema(path.clone())],
+            ..Default::default()
+        };
+        let chain = DecoderChain::new(config);
+        std::fs::remove_file(path).unwrap();
+
+        // Avro int 21 is the zigzag varint 42
+        let message = message_with_subject("Reading", &[42]);
+        let decoded = chain.decode_message("sensors", &message).unwrap().unwrap();
+        assert_eq!(decoded.steps, vec!["reading"]);
+        assert_eq!(decoded.body, BodyData::ValidJson(json!({"celsius": 21})));
+        assert!(decoded.encodable);
+
+        let encoded = chain.encode(&decoded.steps, br#"{"celsius": -1}"#).unwrap();
+        assert_eq!(encoded, [1]);
+
+        // Other subjects are left to detection
+        let other = message_with_subject("Alert", &[42]);
+        assert_eq!(chain.decode_message("sensors", &other).unwrap(), None);
+    }
+
+    #[test]
+    fn test_missing_schema_file_is_reported_for_mapped_messages() {
+        let config = DecoderConfig {
+            schemas: vec![avro_schema("/nonexistent/reading.avsc".into())],
+            ..Default::default()
+        };
+        let chain = DecoderChain::new(config);
+
+        let message = message_with_subject("Reading", &[42]);
+        let error = chain.decode_message("sensors", &message).unwrap_err();
+        assert!(error.to_string().contains("cannot read"), "{error}");
+    }
+
+    #[test]
+    fn test_detected_structured_decoders_are_not_encodable() {
+        let mut body = Vec::new();
+        ciborium::into_writer(&json!({"a": 1}), &mut body).unwrap();
+        let chain = DecoderChain::new(DecoderConfig::default());
+
+        let decoded = chain
+            .decode("orders", Some("application/cbor"), &body)
+            .unwrap()
+            .unwrap();
+        assert!(!decoded.encodable);
+        assert!(chain.encode(&decoded.steps, b"{}").is_err());
+    }
+
+    #[test]
+    fn test_disabled_chain_decodes_nothing() {
+        let config = DecoderConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        let chain = DecoderChain::new(config);
+        assert_eq!(chain.decode("orders", None, &gzip(b"x")).unwrap(), None);
+    }
+}
@@ -0,0 +1,198 @@
+use super::{BodyDecoder, DecodeError, DecodedOutput, MAX_DECODED_SIZE};
+use flate2::Compression;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use std::io::{Read, Write};
+
+/// gzip magic bytes
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Reads a decompressing reader to the end, refusing output larger than
+/// [`MAX_DECODED_SIZE`]
+fn read_limited(decoder: &str, reader: impl Read) -> Result<DecodedOutput, DecodeError> {
+    let mut output = Vec::new();
+    reader
+        .take(MAX_DECODED_SIZE + 1)
+        .read_to_end(&mut output)
+        .map_err(|e| DecodeError::failed(decoder, e))?;
+
+    if output.len() as u64 > MAX_DECODED_SIZE {
+        return Err(DecodeError::TooLarge {
+            decoder: decoder.to_string(),
+        });
+    }
+    Ok(DecodedOutput::Bytes(output))
+}
+
+/// Writes `content` through a compressing writer and returns its output
+fn write_all<W: Write>(
+    decoder: &str,
+    mut writer: W,
+    content: &[u8],
+    finish: impl FnOnce(W) -> std::io::Result<Vec<u8>>,
+) -> Result<Vec<u8>, DecodeError> {
+    writer
+        .write_all(content)
+        .map_err(|e| DecodeError::failed(decoder, e))?;
+    finish(writer).map_err(|e| DecodeError::failed(decoder, e))
+}
+
+/// Decompresses gzip bodies
+pub struct GzipDecoder;
+
+impl BodyDecoder for GzipDecoder {
+    fn name(&self) -> &str {
+        "gzip"
+    }
+
+    fn accepts_content_type(&self, content_type: &str) -> bool {
+        matches!(content_type, "application/gzip" | "application/x-gzip")
+            || content_type.ends_with("+gzip")
+    }
+
+    fn sniff(&self, body: &[u8]) -> bool {
+        body.starts_with(&GZIP_MAGIC)
+    }
+
+    fn decode(&self, body: &[u8]) -> Result<DecodedOutput, DecodeError> {
+        read_limited(self.name(), GzDecoder::new(body))
+    }
+
+    fn can_encode(&self) -> bool {
+        true
+    }
+
+    fn encode(&self, content: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let encoder = GzEncoder::new(Vec::new(), Compression::default());
+        write_all(self.name(), encoder, content, GzEncoder::finish)
+    }
+}
+
+/// Decompresses deflate bodies in the zlib format
+pub struct DeflateDecoder;
+
+impl BodyDecoder for DeflateDecoder {
+    fn name(&self) -> &str {
+        "deflate"
+    }
+
+    fn accepts_content_type(&self, content_type: &str) -> bool {
+        content_type == "application/zlib" || content_type.contains("deflate")
+    }
+
+    fn sniff(&self, body: &[u8]) -> bool {
+        // CMF byte declaring deflate with a window of at most 32K, and a
+        // header checksum that makes CMF * 256 + FLG a multiple of 31
+        match body {
+            [cmf, flg, _, ..] => {
+                cmf & 0x0f == 8
+                    && cmf >> 4 <= 7
+                    && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0
+            }
+            _ => false,
+        }
+    }
+
+    fn decode(&self, body: &[u8]) -> Result<DecodedOutput, DecodeError> {
+        read_limited(self.name(), ZlibDecoder::new(body))
+    }
+
+    fn can_encode(&self) -> bool {
+        true
+    }
+
+    fn encode(&self, content: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        write_all(self.name(), encoder, content, ZlibEncoder::finish)
+    }
+}
+
+/// Decompresses brotli bodies.
+///
+/// Brotli streams have no magic bytes, so this decoder is only selected by
+/// content type or queue configuration.
+pub struct BrotliDecoder;
+
+impl BodyDecoder for BrotliDecoder {
+    fn name(&self) -> &str {
+        "brotli"
+    }
+
+    fn accepts_content_type(&self, content_type: &str) -> bool {
+        matches!(content_type, "application/x-brotli" | "application/brotli")
+            || content_type.ends_with("+br")
+    }
+
+    fn sniff(&self, _body: &[u8]) -> bool {
+        false
+    }
+
+    fn decode(&self, body: &[u8]) -> Result<DecodedOutput, DecodeError> {
+        read_limited(self.name(), brotli::Decompressor::new(body, 4096))
+    }
+
+    fn can_encode(&self) -> bool {
+        true
+    }
+
+    fn encode(&self, content: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let writer = brotli::CompressorWriter::new(Vec::new(), 4096, 5, 22);
+        write_all(self.name(), writer, content, |mut writer| {
+            writer.flush()?;
+            Ok(writer.into_inner())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deflate_round_trip_and_sniffing() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"deflated payload").unwrap();
+        let body = encoder.finish().unwrap();
+
+        assert!(DeflateDecoder.sniff(&body));
+        assert!(!DeflateDecoder.sniff(b"{\"a\":1}"));
+        assert_eq!(
+            DeflateDecoder.decode(&body).unwrap(),
+            DecodedOutput::Bytes(b"deflated payload".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_brotli_round_trip() {
+        let mut body = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut body, 4096, 5, 22);
+            writer.write_all(b"brotli payload").unwrap();
+        }
+
+        assert!(BrotliDecoder.accepts_content_type("application/json+br"));
+        assert_eq!(
+            BrotliDecoder.decode(&body).unwrap(),
+            DecodedOutput::Bytes(b"brotli payload".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_encoders_round_trip() {
+        let decoders: [&dyn BodyDecoder; 3] = [&GzipDecoder, &DeflateDecoder, &BrotliDecoder];
+        for decoder in decoders {
+            let encoded = decoder.encode(b"edited payload").unwrap();
+            assert_eq!(
+                decoder.decode(&encoded).unwrap(),
+                DecodedOutput::Bytes(b"edited payload".to_vec()),
+                "{}",
+                decoder.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_corrupt_gzip_is_an_error() {
+        assert!(GzipDecoder.decode(&[0x1f, 0x8b, 0x00]).is_err());
+    }
+}
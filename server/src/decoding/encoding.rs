@@ -0,0 +1,99 @@
+use super::{
+    BodyDecoder, CborDecoder, DecodeError, DecodedOutput, DeflateDecoder, GzipDecoder,
+    MessagePackDecoder,
+};
+use base64::{Engine as _, engine::general_purpose};
+
+/// Shortest body considered for base64 sniffing
+const MIN_SNIFF_LENGTH: usize = 16;
+
+/// Unwraps base64-encoded bodies.
+///
+/// Plain words are often valid base64 too, so sniffing only accepts bodies
+/// whose decoded bytes are recognisably something else: compressed data, a
+/// MessagePack or CBOR document, or JSON.
+pub struct Base64Decoder;
+
+impl Base64Decoder {
+    fn decode_text(body: &[u8]) -> Option<Vec<u8>> {
+        // MIME-style encoders wrap lines; strip line breaks but nothing else
+        let compact: Vec<u8> = body
+            .trim_ascii()
+            .iter()
+            .copied()
+            .filter(|byte| !matches!(byte, b'\r' | b'\n'))
+            .collect();
+        general_purpose::STANDARD
+            .decode(&compact)
+            .or_else(|_| general_purpose::URL_SAFE.decode(&compact))
+            .ok()
+    }
+}
+
+impl BodyDecoder for Base64Decoder {
+    fn name(&self) -> &str {
+        "base64"
+    }
+
+    fn accepts_content_type(&self, content_type: &str) -> bool {
+        content_type.contains("base64")
+    }
+
+    fn sniff(&self, body: &[u8]) -> bool {
+        let trimmed = body.trim_ascii();
+        if trimmed.len() < MIN_SNIFF_LENGTH
+            || !trimmed.iter().all(|byte| {
+                byte.is_ascii_alphanumeric()
+                    || matches!(byte, b'+' | b'/' | b'-' | b'_' | b'=' | b'\r' | b'\n')
+            })
+        {
+            return false;
+        }
+
+        let Some(decoded) = Self::decode_text(trimmed) else {
+            return false;
+        };
+        GzipDecoder.sniff(&decoded)
+            || DeflateDecoder.sniff(&decoded)
+            || CborDecoder.sniff(&decoded)
+            || MessagePackDecoder.sniff(&decoded)
+            || (matches!(decoded.first(), Some(b'{' | b'['))
+                && serde_json::from_slice::<serde_json::Value>(&decoded).is_ok())
+    }
+
+    fn decode(&self, body: &[u8]) -> Result<DecodedOutput, DecodeError> {
+        Self::decode_text(body)
+            .map(DecodedOutput::Bytes)
+            .ok_or_else(|| DecodeError::failed(self.name(), "body is not valid base64"))
+    }
+
+    fn can_encode(&self) -> bool {
+        true
+    }
+
+    fn encode(&self, content: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        Ok(general_purpose::STANDARD.encode(content).into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniffing_requires_a_recognisable_payload() {
+        let json = general_purpose::STANDARD.encode(br#"{"orderId": 42, "status": "new"}"#);
+        assert!(Base64Decoder.sniff(json.as_bytes()));
+
+        // Valid base64, but the decoded bytes are noise
+        assert!(!Base64Decoder.sniff(b"ThisIsJustAnIdentifier"));
+        assert!(!Base64Decoder.sniff(b"short"));
+        assert!(!Base64Decoder.sniff(b"plain text with spaces in it"));
+    }
+
+    #[test]
+    fn test_decodes_wrapped_lines() {
+        let decoded = Base64Decoder.decode(b"aGVsbG8g\r\nd29ybGQ=\n").unwrap();
+        assert_eq!(decoded, DecodedOutput::Bytes(b"hello world".to_vec()));
+    }
+}
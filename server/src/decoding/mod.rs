@@ -0,0 +1,174 @@
+//! Message body decoders for compressed, wrapped and binary-serialized payloads.
+//!
+//! Service Bus treats message bodies as opaque bytes, so producers are free to
+//! compress them, wrap them in base64 or serialize them with a binary format.
+//! This module turns such bodies back into something readable:
+//!
+//! - [`BodyDecoder`] - A single decoding step (decompress, unwrap, deserialize)
+//! - [`DecoderChain`] - Picks decoders per message and applies them in sequence
+//! - [`DecoderConfig`] - Detection settings, per-queue decoder lists and schemas
+//! - [`SchemaConfig`] - A Protocol Buffers or Avro schema file mapped to messages
+//!
+//! ## Built-in Decoders
+//!
+//! | Name | Content types | Detected by content |
+//! |------|---------------|---------------------|
+//! | `gzip` | `application/gzip`, `*+gzip` | gzip magic bytes |
+//! | `deflate` | `application/zlib`, `*deflate*` | zlib header |
+//! | `brotli` | `application/x-brotli`, `*+br` | no (content type or queue config only) |
+//! | `base64` | `*base64*` | base64 text wrapping a recognisable payload |
+//! | `msgpack` | `application/msgpack`, `application/x-msgpack` | top-level map |
+//! | `cbor` | `application/cbor` | top-level map or self-describe tag |
+//!
+//! Schema-based decoders ([`ProtobufDecoder`], [`AvroDecoder`]) are never
+//! detected; they apply to the queues, Subjects or property values their
+//! [`SchemaConfig`] maps them to. They can also encode edited JSON back into
+//! the binary format, as can the compression and base64 decoders.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use quetty_server::decoding::{DecoderChain, DecoderConfig};
+//!
+//! let chain = DecoderChain::new(DecoderConfig::default());
+//! let body = std::fs::read("payload.bin")?;
+//!
+//! match chain.decode("orders", Some("application/gzip"), &body) {
+//!     Ok(Some(decoded)) => println!("Decoded with {}", decoded.steps.join(" → ")),
+//!     Ok(None) => println!("No decoder applies"),
+//!     Err(e) => eprintln!("Decoding failed: {e}"),
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+mod avro;
+mod chain;
+mod compression;
+mod encoding;
+mod protobuf;
+mod schema;
+mod structured;
+
+pub use avro::AvroDecoder;
+pub use chain::{DecodedBody, DecoderChain, DecoderConfig};
+pub use compression::{BrotliDecoder, DeflateDecoder, GzipDecoder};
+pub use encoding::Base64Decoder;
+pub use protobuf::ProtobufDecoder;
+pub use schema::{PropertyMatch, SchemaConfig, SchemaFormat};
+pub use structured::{CborDecoder, MessagePackDecoder};
+
+use serde_json::Value;
+use thiserror::Error;
+
+/// Upper bound for the output of a single decoding step, protecting the UI
+/// against decompression bombs
+pub const MAX_DECODED_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Output of a single decoding step
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodedOutput {
+    /// Bytes that may be decoded further, e.g. a decompressed payload
+    Bytes(Vec<u8>),
+    /// A structured document; decoding stops here
+    Json(Value),
+}
+
+/// Errors raised while decoding a message body
+#[derive(Debug, Clone, Error)]
+pub enum DecodeError {
+    #[error("{decoder} decoding failed: {reason}")]
+    Failed { decoder: String, reason: String },
+
+    #[error("{decoder} output exceeds {MAX_DECODED_SIZE} bytes")]
+    TooLarge { decoder: String },
+
+    #[error("Unknown body decoder '{0}'")]
+    UnknownDecoder(String),
+}
+
+impl DecodeError {
+    pub fn failed(decoder: &str, reason: impl ToString) -> Self {
+        Self::Failed {
+            decoder: decoder.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// A single step of turning an encoded message body into a readable one.
+///
+/// Decoders are selected either by the message ContentType, by sniffing the
+/// body, or explicitly through the per-queue configuration. Implementations
+/// must be cheap to query: [`BodyDecoder::sniff`] runs for every displayed
+/// message without a matching content type.
+///
+/// # Examples
+///
+/// ```no_run
+/// use quetty_server::decoding::{BodyDecoder, DecodeError, DecodedOutput, DecoderChain, DecoderConfig};
+///
+/// /// Strips a fixed-length framing header added by a legacy producer
+/// struct FramedDecoder;
+///
+/// impl BodyDecoder for FramedDecoder {
+///     fn name(&self) -> &str {
+///         "framed"
+///     }
+///
+///     fn accepts_content_type(&self, content_type: &str) -> bool {
+///         content_type == "application/x-framed"
+///     }
+///
+///     fn sniff(&self, body: &[u8]) -> bool {
+///         body.starts_with(b"FRM1")
+///     }
+///
+///     fn decode(&self, body: &[u8]) -> Result<DecodedOutput, DecodeError> {
+///         Ok(DecodedOutput::Bytes(body[4..].to_vec()))
+///     }
+/// }
+///
+/// let chain = DecoderChain::new(DecoderConfig::default()).with_decoder(Box::new(FramedDecoder));
+/// ```
+pub trait BodyDecoder: Send + Sync {
+    /// Name used in configuration and shown in the decoded view
+    fn name(&self) -> &str;
+
+    /// Whether the decoder handles bodies declared with `content_type`
+    /// (lowercase, without parameters)
+    fn accepts_content_type(&self, content_type: &str) -> bool;
+
+    /// Whether the body looks like something this decoder handles.
+    ///
+    /// Should be conservative with plain text and JSON; when a sniffed
+    /// decoder fails, the body is shown undecoded.
+    fn sniff(&self, body: &[u8]) -> bool;
+
+    /// Decodes the body
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError`] if the body is not valid for this decoder
+    fn decode(&self, body: &[u8]) -> Result<DecodedOutput, DecodeError>;
+
+    /// Whether [`BodyDecoder::encode`] can turn edited output back into a body
+    fn can_encode(&self) -> bool {
+        false
+    }
+
+    /// Encodes edited decoder output, the inverse of [`BodyDecoder::decode`].
+    ///
+    /// `content` is what the decoded view shows: JSON text for decoders that
+    /// produce documents, the decoded bytes otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError`] if the content cannot be encoded or the
+    /// decoder does not support encoding
+    fn encode(&self, _content: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        Err(DecodeError::failed(
+            self.name(),
+            "re-encoding is not supported",
+        ))
+    }
+}
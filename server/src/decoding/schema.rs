@@ -0,0 +1,207 @@
+use super::{AvroDecoder, BodyDecoder, DecodeError, ProtobufDecoder};
+use crate::model::MessageModel;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Binary format described by a registered schema file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaFormat {
+    /// Compiled Protocol Buffers descriptor set (`protoc --descriptor_set_out`)
+    Protobuf,
+    /// Avro schema in its JSON form (`.avsc`)
+    Avro,
+}
+
+/// Matches messages by the value of an application property
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PropertyMatch {
+    /// Application property name
+    pub name: String,
+    /// Expected value, compared with the property rendered as text
+    pub value: String,
+}
+
+/// A schema file registered for decoding, and the messages it applies to.
+///
+/// The schema becomes a decoder named `name`, so it can also be listed in
+/// `[decoders.queues]` after other decoders, e.g. `["gzip", "orders"]`.
+/// A message uses the schema when every criterion that is set matches: its
+/// entity is one of `queues`, its Subject one of `subjects`, and it carries
+/// `property`. A schema without criteria is only used through
+/// `[decoders.queues]`.
+///
+/// # Examples
+///
+/// ```toml
+/// [[decoders.schemas]]
+/// name = "order-created"
+/// format = "protobuf"
+/// path = "~/.config/quetty/schemas/orders.desc"
+/// message_type = "shop.orders.v1.OrderCreated"
+/// subjects = ["OrderCreated"]
+///
+/// [[decoders.schemas]]
+/// name = "payments"
+/// format = "avro"
+/// path = "~/.config/quetty/schemas/payment.avsc"
+/// queues = ["payments"]
+/// property = { name = "schema", value = "payment.v2" }
+/// ```
+///
+/// The second schema only applies to messages of the `payments` queue that
+/// also have the `schema` property set to `payment.v2`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SchemaConfig {
+    /// Decoder name shown in the decoded view and usable in queue lists
+    pub name: String,
+    /// Format of the schema file
+    pub format: SchemaFormat,
+    /// Path of the descriptor set or `.avsc` file; `~` expands to the home directory
+    pub path: PathBuf,
+    /// Fully qualified message type, required for Protocol Buffers
+    #[serde(default)]
+    pub message_type: Option<String>,
+    /// Queues, topics or subscriptions whose messages use this schema
+    #[serde(default)]
+    pub queues: Vec<String>,
+    /// Message Subjects that select this schema
+    #[serde(default)]
+    pub subjects: Vec<String>,
+    /// Application property value that selects this schema
+    #[serde(default)]
+    pub property: Option<PropertyMatch>,
+}
+
+impl SchemaConfig {
+    /// Reads the schema file and builds its decoder
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not hold a valid
+    /// schema
+    pub fn load(&self) -> Result<Box<dyn BodyDecoder>, DecodeError> {
+        let path = self.resolved_path();
+        let contents = std::fs::read(&path).map_err(|e| {
+            DecodeError::failed(&self.name, format!("cannot read {}: {e}", path.display()))
+        })?;
+
+        match self.format {
+            SchemaFormat::Protobuf => {
+                let message_type = self.message_type.as_deref().ok_or_else(|| {
+                    DecodeError::failed(&self.name, "protobuf schemas need a message_type")
+                })?;
+                Ok(Box::new(ProtobufDecoder::new(
+                    &self.name,
+                    &contents,
+                    message_type,
+                )?))
+            }
+            SchemaFormat::Avro => {
+                let schema =
+                    String::from_utf8(contents).map_err(|e| DecodeError::failed(&self.name, e))?;
+                Ok(Box::new(AvroDecoder::new(&self.name, &schema)?))
+            }
+        }
+    }
+
+    /// Whether a message meets every criterion of this schema; `entity_keys`
+    /// are the entity path and the queue or topic it belongs to
+    pub(crate) fn matches(&self, entity_keys: &[&str], message: Option<&MessageModel>) -> bool {
+        if self.queues.is_empty() && self.subjects.is_empty() && self.property.is_none() {
+            return false;
+        }
+
+        let by_queue = entity_keys
+            .iter()
+            .any(|key| self.queues.iter().any(|queue| queue == key));
+        let properties = message.map(|message| &message.properties);
+        let by_subject = properties
+            .and_then(|properties| properties.subject.as_ref())
+            .is_some_and(|subject| self.subjects.contains(subject));
+        let by_property =
+            self.property
+                .as_ref()
+                .zip(properties)
+                .is_some_and(|(property, properties)| {
+                    properties
+                        .application_properties
+                        .get(&property.name)
+                        .is_some_and(|value| value.to_string() == property.value)
+                });
+
+        (self.queues.is_empty() || by_queue)
+            && (self.subjects.is_empty() || by_subject)
+            && (self.property.is_none() || by_property)
+    }
+
+    fn resolved_path(&self) -> PathBuf {
+        match (self.path.strip_prefix("~"), dirs::home_dir()) {
+            (Ok(relative), Some(home)) => home.join(relative),
+            _ => self.path.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BodyData, MessageProperties, MessageState, PropertyValue};
+    use azure_core::time::OffsetDateTime;
+
+    fn message(subject: &str, schema: &str) -> MessageModel {
+        let mut properties = MessageProperties {
+            subject: Some(subject.to_string()),
+            ..Default::default()
+        };
+        properties.application_properties.insert(
+            "schema".to_string(),
+            PropertyValue::String(schema.to_string()),
+        );
+        MessageModel::new(
+            1,
+            "id".to_string(),
+            OffsetDateTime::now_utc(),
+            0,
+            MessageState::Active,
+            BodyData::Binary(vec![0x02]),
+        )
+        .with_properties(properties)
+    }
+
+    #[test]
+    fn test_every_configured_criterion_must_match() {
+        let schema = SchemaConfig {
+            name: "payments".to_string(),
+            format: SchemaFormat::Avro,
+            path: "payment.avsc".into(),
+            message_type: None,
+            queues: vec!["payments".to_string()],
+            subjects: Vec::new(),
+            property: Some(PropertyMatch {
+                name: "schema".to_string(),
+                value: "payment.v2".to_string(),
+            }),
+        };
+
+        let v2 = message("Paid", "payment.v2");
+        assert!(schema.matches(&["payments"], Some(&v2)));
+        assert!(!schema.matches(&["refunds"], Some(&v2)));
+        assert!(!schema.matches(&["payments"], Some(&message("Paid", "payment.v1"))));
+        assert!(!schema.matches(&["payments"], None));
+
+        let by_subject = SchemaConfig {
+            queues: Vec::new(),
+            subjects: vec!["Paid".to_string()],
+            property: None,
+            ..schema.clone()
+        };
+        assert!(by_subject.matches(&["refunds"], Some(&v2)));
+
+        let unmapped = SchemaConfig {
+            subjects: Vec::new(),
+            ..by_subject
+        };
+        assert!(!unmapped.matches(&["payments"], Some(&v2)));
+    }
+}
@@ -0,0 +1,216 @@
+use super::{BodyDecoder, DecodeError, DecodedOutput};
+use base64::{Engine as _, engine::general_purpose};
+use serde_json::{Map, Number, Value};
+
+/// Decodes MessagePack bodies into JSON.
+///
+/// Binary values become base64 strings, non-string map keys are rendered as
+/// text and extension values become `{"$ext": type, "data": base64}`.
+pub struct MessagePackDecoder;
+
+impl MessagePackDecoder {
+    fn read(body: &[u8]) -> Result<rmpv::Value, String> {
+        let mut reader = body;
+        let value = rmpv::decode::read_value(&mut reader).map_err(|e| e.to_string())?;
+        if !reader.is_empty() {
+            return Err(format!("{} trailing bytes", reader.len()));
+        }
+        Ok(value)
+    }
+}
+
+impl BodyDecoder for MessagePackDecoder {
+    fn name(&self) -> &str {
+        "msgpack"
+    }
+
+    fn accepts_content_type(&self, content_type: &str) -> bool {
+        matches!(
+            content_type,
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack"
+        )
+    }
+
+    fn sniff(&self, body: &[u8]) -> bool {
+        // Only documents with a map at the top level: fixmap, map16 or map32
+        matches!(body.first(), Some(0x80..=0x8f | 0xde | 0xdf))
+            && matches!(Self::read(body), Ok(rmpv::Value::Map(_)))
+    }
+
+    fn decode(&self, body: &[u8]) -> Result<DecodedOutput, DecodeError> {
+        Self::read(body)
+            .map(|value| DecodedOutput::Json(msgpack_to_json(value)))
+            .map_err(|e| DecodeError::failed(self.name(), e))
+    }
+}
+
+fn msgpack_to_json(value: rmpv::Value) -> Value {
+    match value {
+        rmpv::Value::Nil => Value::Null,
+        rmpv::Value::Boolean(b) => Value::Bool(b),
+        rmpv::Value::Integer(int) => int
+            .as_i64()
+            .map(Value::from)
+            .or_else(|| int.as_u64().map(Value::from))
+            .unwrap_or(Value::Null),
+        rmpv::Value::F32(f) => float_to_json(f64::from(f)),
+        rmpv::Value::F64(f) => float_to_json(f),
+        rmpv::Value::String(s) => match s.into_str() {
+            Some(s) => Value::String(s),
+            None => Value::Null,
+        },
+        rmpv::Value::Binary(bytes) => Value::String(general_purpose::STANDARD.encode(bytes)),
+        rmpv::Value::Array(values) => {
+            Value::Array(values.into_iter().map(msgpack_to_json).collect())
+        }
+        rmpv::Value::Map(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        rmpv::Value::String(s) => s.into_str().unwrap_or_default(),
+                        other => other.to_string(),
+                    };
+                    (key, msgpack_to_json(value))
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        rmpv::Value::Ext(kind, data) => serde_json::json!({
+            "$ext": kind,
+            "data": general_purpose::STANDARD.encode(data),
+        }),
+    }
+}
+
+/// Decodes CBOR bodies into JSON.
+///
+/// Byte strings become base64 strings, tags are dropped in favour of the
+/// tagged value and non-string map keys are rendered as text.
+pub struct CborDecoder;
+
+/// Self-describe CBOR tag 55799
+const CBOR_SELF_DESCRIBE: [u8; 3] = [0xd9, 0xd9, 0xf7];
+
+impl CborDecoder {
+    fn read(body: &[u8]) -> Result<ciborium::Value, String> {
+        let mut reader = body;
+        let value: ciborium::Value =
+            ciborium::de::from_reader(&mut reader).map_err(|e| e.to_string())?;
+        if !reader.is_empty() {
+            return Err(format!("{} trailing bytes", reader.len()));
+        }
+        Ok(value)
+    }
+}
+
+impl BodyDecoder for CborDecoder {
+    fn name(&self) -> &str {
+        "cbor"
+    }
+
+    fn accepts_content_type(&self, content_type: &str) -> bool {
+        content_type == "application/cbor" || content_type.ends_with("+cbor")
+    }
+
+    fn sniff(&self, body: &[u8]) -> bool {
+        if body.starts_with(&CBOR_SELF_DESCRIBE) {
+            return Self::read(body).is_ok();
+        }
+        // Major type 5 (map) at the top level
+        matches!(body.first(), Some(0xa0..=0xbf))
+            && matches!(Self::read(body), Ok(ciborium::Value::Map(_)))
+    }
+
+    fn decode(&self, body: &[u8]) -> Result<DecodedOutput, DecodeError> {
+        Self::read(body)
+            .map(|value| DecodedOutput::Json(cbor_to_json(value)))
+            .map_err(|e| DecodeError::failed(self.name(), e))
+    }
+}
+
+fn cbor_to_json(value: ciborium::Value) -> Value {
+    match value {
+        ciborium::Value::Null => Value::Null,
+        ciborium::Value::Bool(b) => Value::Bool(b),
+        ciborium::Value::Integer(int) => {
+            let int = i128::from(int);
+            i64::try_from(int)
+                .map(Value::from)
+                .or_else(|_| u64::try_from(int).map(Value::from))
+                .unwrap_or_else(|_| Value::String(int.to_string()))
+        }
+        ciborium::Value::Float(f) => float_to_json(f),
+        ciborium::Value::Text(s) => Value::String(s),
+        ciborium::Value::Bytes(bytes) => Value::String(general_purpose::STANDARD.encode(bytes)),
+        ciborium::Value::Tag(_, value) => cbor_to_json(*value),
+        ciborium::Value::Array(values) => {
+            Value::Array(values.into_iter().map(cbor_to_json).collect())
+        }
+        ciborium::Value::Map(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match cbor_to_json(key) {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    (key, cbor_to_json(value))
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+/// JSON has no NaN or infinity; those become null
+fn float_to_json(f: f64) -> Value {
+    Number::from_f64(f)
+        .map(Value::Number)
+        .unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_msgpack_map_decodes_to_json() {
+        // {"id": 7, "tags": ["a"], "raw": bin[0x01, 0x02]}
+        let body = [
+            0x83, 0xa2, b'i', b'd', 0x07, 0xa4, b't', b'a', b'g', b's', 0x91, 0xa1, b'a', 0xa3,
+            b'r', b'a', b'w', 0xc4, 0x02, 0x01, 0x02,
+        ];
+
+        assert!(MessagePackDecoder.sniff(&body));
+        assert_eq!(
+            MessagePackDecoder.decode(&body).unwrap(),
+            DecodedOutput::Json(json!({"id": 7, "tags": ["a"], "raw": "AQI="}))
+        );
+        // Trailing garbage is rejected rather than silently ignored
+        let mut trailing = body.to_vec();
+        trailing.push(0x00);
+        assert!(!MessagePackDecoder.sniff(&trailing));
+    }
+
+    #[test]
+    fn test_cbor_map_decodes_to_json() {
+        let mut body = Vec::new();
+        ciborium::ser::into_writer(&json!({"id": 7, "ok": true}), &mut body).unwrap();
+
+        assert!(CborDecoder.sniff(&body));
+        assert!(!MessagePackDecoder.sniff(&body));
+        assert_eq!(
+            CborDecoder.decode(&body).unwrap(),
+            DecodedOutput::Json(json!({"id": 7, "ok": true}))
+        );
+    }
+
+    #[test]
+    fn test_text_is_never_sniffed_as_binary_format() {
+        for body in [&b"{\"id\": 7}"[..], b"hello"] {
+            assert!(!MessagePackDecoder.sniff(body));
+            assert!(!CborDecoder.sniff(body));
+        }
+    }
+}
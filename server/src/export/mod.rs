@@ -0,0 +1,150 @@
+//! Lossless message exports to NDJSON, JSON and CSV files.
+//!
+//! Every exported message becomes an [`ExportedMessage`] record holding the
+//! broker metadata, the typed application properties and the body exactly as
+//! stored, base64 encoded. [`ExportWriter`] streams records into one of the
+//! [`ExportFormat`]s so large exports never have to fit in memory.
+//! [`read_ndjson`] reads an NDJSON export back, e.g. to replay it into a
+//! queue with [`ExportedMessage::to_message_data`].
+//!
+//! ## Formats
+//!
+//! | Format | Extensions | Layout |
+//! |--------|------------|--------|
+//! | `ndjson` | `.ndjson`, `.jsonl` | One compact JSON record per line |
+//! | `json` | `.json` | A pretty-printed JSON array of records |
+//! | `csv` | `.csv` | A header row and one row per record; properties as JSON text |
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use quetty_server::export::{ExportFormat, ExportWriter, ExportedMessage};
+//!
+//! # fn records() -> Vec<ExportedMessage> { Vec::new() }
+//! let file = std::fs::File::create("dlq.ndjson")?;
+//! let mut writer = ExportWriter::new(file, ExportFormat::Ndjson)?;
+//! for record in records() {
+//!     writer.write(&record)?;
+//! }
+//! writer.finish()?;
+//! # Ok::<(), quetty_server::export::ExportError>(())
+//! ```
+
+mod reader;
+mod record;
+mod writer;
+
+pub use reader::read_ndjson;
+pub use record::ExportedMessage;
+pub use writer::ExportWriter;
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Errors raised while writing or reading an export
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Failed to write export: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to serialize message: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Unknown export format '{0}', expected ndjson, json or csv")]
+    UnknownFormat(String),
+
+    #[error("Invalid message on line {line}: {message}")]
+    InvalidRecord { line: usize, message: String },
+}
+
+/// File layout of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// Newline-delimited JSON, one record per line
+    #[default]
+    Ndjson,
+    /// A pretty-printed JSON array
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+impl ExportFormat {
+    /// Picks the format matching the extension of `path`, if any
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    /// The usual file extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Ndjson => "ndjson",
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = ExportError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "ndjson" | "jsonl" => Ok(Self::Ndjson),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            other => Err(ExportError::UnknownFormat(other.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("/tmp/dlq.ndjson")),
+            Some(ExportFormat::Ndjson)
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("dlq.JSONL")),
+            Some(ExportFormat::Ndjson)
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("dlq.json")),
+            Some(ExportFormat::Json)
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("dlq.csv")),
+            Some(ExportFormat::Csv)
+        );
+        assert_eq!(ExportFormat::from_path(Path::new("dlq.txt")), None);
+        assert_eq!(ExportFormat::from_path(Path::new("dlq")), None);
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("CSV".parse::<ExportFormat>().unwrap(), ExportFormat::Csv);
+        assert_eq!(
+            "jsonl".parse::<ExportFormat>().unwrap(),
+            ExportFormat::Ndjson
+        );
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+}
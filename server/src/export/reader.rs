@@ -0,0 +1,81 @@
+use super::{ExportError, ExportedMessage};
+use std::io::BufRead;
+
+/// Reads the records of an NDJSON export, skipping blank lines.
+///
+/// Every record is parsed and its body decoded before anything is returned,
+/// so a damaged file is rejected as a whole.
+///
+/// # Errors
+///
+/// Returns [`ExportError::InvalidRecord`] with the 1-based line number of the
+/// first line that is not an exported message, or [`ExportError::Io`] if
+/// reading fails
+pub fn read_ndjson<R: BufRead>(reader: R) -> Result<Vec<ExportedMessage>, ExportError> {
+    let mut records = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let invalid = |message: String| ExportError::InvalidRecord {
+            line: index + 1,
+            message,
+        };
+        let record: ExportedMessage =
+            serde_json::from_str(&line).map_err(|e| invalid(e.to_string()))?;
+        record
+            .body()
+            .map_err(|e| invalid(format!("body_base64 is not valid base64: {e}")))?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{ExportFormat, ExportWriter};
+    use crate::model::{BodyData, MessageModel, MessageState};
+    use azure_core::time::OffsetDateTime;
+
+    fn ndjson(count: i64) -> String {
+        let mut writer = ExportWriter::new(Vec::new(), ExportFormat::Ndjson).unwrap();
+        for sequence in 1..=count {
+            let message = MessageModel::new(
+                sequence,
+                format!("msg-{sequence}"),
+                OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap(),
+                0,
+                MessageState::Active,
+                BodyData::RawString("body".to_string()),
+            );
+            writer
+                .write(&ExportedMessage::from_message(&message))
+                .unwrap();
+        }
+        String::from_utf8(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_reads_exported_records() {
+        let input = format!("{}\n\n", ndjson(3));
+        let records = read_ndjson(input.as_bytes()).unwrap();
+
+        let ids: Vec<&str> = records.iter().map(|r| r.message_id.as_str()).collect();
+        assert_eq!(ids, ["msg-1", "msg-2", "msg-3"]);
+        assert_eq!(records[0].body().unwrap(), b"body");
+    }
+
+    #[test]
+    fn test_reports_the_invalid_line() {
+        let input = format!("{}{{\"message_id\": \"x\"}}\n", ndjson(2));
+        let error = read_ndjson(input.as_bytes()).unwrap_err();
+        assert!(matches!(error, ExportError::InvalidRecord { line: 3, .. }));
+
+        let input = ndjson(1).replace("\"Ym9keQ==\"", "\"!!\"");
+        let error = read_ndjson(input.as_bytes()).unwrap_err();
+        assert!(matches!(error, ExportError::InvalidRecord { line: 1, .. }));
+    }
+}
@@ -0,0 +1,287 @@
+use crate::model::{MessageModel, PropertyValue};
+use crate::service_bus_manager::{MessageData, SystemProperties};
+use azure_core::time::OffsetDateTime;
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A message as written to an export file.
+///
+/// The record has a fixed set of fields so exports can be diffed, loaded
+/// into spreadsheets and read back. Unset properties are `null` rather than
+/// missing. The body is kept byte for byte in `body_base64`; `body_text`
+/// repeats it as text when it is valid UTF-8, purely for readability.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedMessage {
+    pub sequence_number: i64,
+    pub message_id: String,
+    #[serde(with = "azure_core::time::iso8601")]
+    pub enqueued_time: OffsetDateTime,
+    pub delivery_count: usize,
+    /// `Active`, `Deferred`, `Scheduled` or `DeadLettered`
+    pub state: String,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub subject: Option<String>,
+    #[serde(default)]
+    pub reply_to: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Time-to-live in milliseconds, the precision the broker stores
+    #[serde(default)]
+    pub time_to_live_ms: Option<i64>,
+    #[serde(default, with = "azure_core::time::iso8601::option")]
+    pub scheduled_enqueue_time: Option<OffsetDateTime>,
+    #[serde(default, with = "azure_core::time::iso8601::option")]
+    pub locked_until: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub dead_letter_reason: Option<String>,
+    #[serde(default)]
+    pub dead_letter_error_description: Option<String>,
+    #[serde(default)]
+    pub dead_letter_source: Option<String>,
+    #[serde(default)]
+    pub application_properties: BTreeMap<String, PropertyValue>,
+    /// Type of every application property, as [`PropertyValue::type_name`];
+    /// UUIDs and timestamps would otherwise read back as strings
+    #[serde(default)]
+    pub application_property_types: BTreeMap<String, String>,
+    pub body_base64: String,
+    #[serde(default)]
+    pub body_text: Option<String>,
+}
+
+impl ExportedMessage {
+    /// Builds the record of a message from its model, keeping the body bytes
+    /// as received.
+    pub fn from_message(message: &MessageModel) -> Self {
+        let properties = &message.properties;
+        let body = message.raw_body.as_slice();
+        let application_property_types = properties
+            .application_properties
+            .iter()
+            .map(|(key, value)| (key.clone(), value.type_name().to_string()))
+            .collect();
+
+        Self {
+            sequence_number: message.sequence,
+            message_id: message.id.clone(),
+            enqueued_time: message.enqueued_at,
+            delivery_count: message.delivery_count,
+            state: format!("{:?}", message.state),
+            correlation_id: properties.correlation_id.clone(),
+            session_id: properties.session_id.clone(),
+            content_type: properties.content_type.clone(),
+            subject: properties.subject.clone(),
+            reply_to: properties.reply_to.clone(),
+            to: properties.to.clone(),
+            time_to_live_ms: properties
+                .time_to_live
+                .map(|ttl| i64::try_from(ttl.whole_milliseconds()).unwrap_or(i64::MAX)),
+            scheduled_enqueue_time: properties.scheduled_enqueue_time,
+            locked_until: properties.locked_until,
+            dead_letter_reason: properties.dead_letter_reason.clone(),
+            dead_letter_error_description: properties.dead_letter_error_description.clone(),
+            dead_letter_source: properties.dead_letter_source.clone(),
+            application_properties: properties.application_properties.clone(),
+            application_property_types,
+            body_base64: general_purpose::STANDARD.encode(body),
+            body_text: std::str::from_utf8(body).ok().map(str::to_string),
+        }
+    }
+
+    /// Decodes the original body bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `body_base64` is not valid base64
+    pub fn body(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        general_purpose::STANDARD.decode(&self.body_base64)
+    }
+
+    /// Application properties with the types recorded at export restored.
+    ///
+    /// Values whose recorded type cannot be restored are kept as read.
+    pub fn typed_application_properties(&self) -> BTreeMap<String, PropertyValue> {
+        self.application_properties
+            .iter()
+            .map(|(key, value)| {
+                let typed = match (
+                    self.application_property_types.get(key).map(String::as_str),
+                    value,
+                ) {
+                    (Some("uuid"), PropertyValue::String(text)) => {
+                        uuid::Uuid::parse_str(text).map(PropertyValue::Uuid).ok()
+                    }
+                    (Some("timestamp"), PropertyValue::String(text)) => {
+                        azure_core::time::parse_rfc3339(text)
+                            .map(PropertyValue::Timestamp)
+                            .ok()
+                    }
+                    (Some("double"), PropertyValue::Int(number)) => {
+                        Some(PropertyValue::Double(*number as f64))
+                    }
+                    _ => None,
+                };
+                (key.clone(), typed.unwrap_or_else(|| value.clone()))
+            })
+            .collect()
+    }
+
+    /// Rebuilds the message for sending it again.
+    ///
+    /// The body, application properties and sendable system properties are
+    /// kept. Broker-assigned data (sequence number, state, lock and
+    /// dead-letter details) and the schedule are not, so the replayed message
+    /// is delivered immediately. With `keep_message_id` unset the message
+    /// gets a new MessageId, which sidesteps duplicate detection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `body_base64` is not valid base64
+    pub fn to_message_data(
+        &self,
+        keep_message_id: bool,
+    ) -> Result<MessageData, base64::DecodeError> {
+        let message_id = if keep_message_id {
+            self.message_id.clone()
+        } else {
+            uuid::Uuid::new_v4().to_string()
+        };
+        let system_properties = SystemProperties {
+            message_id: Some(message_id),
+            correlation_id: self.correlation_id.clone(),
+            session_id: self.session_id.clone(),
+            content_type: self.content_type.clone(),
+            subject: self.subject.clone(),
+            reply_to: self.reply_to.clone(),
+            to: self.to.clone(),
+            time_to_live: self
+                .time_to_live_ms
+                .and_then(|ms| u64::try_from(ms).ok())
+                .map(std::time::Duration::from_millis),
+            scheduled_enqueue_time: None,
+        };
+
+        Ok(MessageData::from_bytes(self.body()?)
+            .with_application_properties(self.typed_application_properties())
+            .with_system_properties(system_properties))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BodyData, MessageProperties, MessageState};
+    use azure_core::time::Duration;
+
+    fn message() -> MessageModel {
+        let mut properties = MessageProperties {
+            correlation_id: Some("corr-1".to_string()),
+            time_to_live: Some(Duration::milliseconds(90_500)),
+            dead_letter_reason: Some("MaxDeliveryCountExceeded".to_string()),
+            ..Default::default()
+        };
+        let application_properties = &mut properties.application_properties;
+        application_properties.insert("retries".to_string(), PropertyValue::Int(2));
+        application_properties.insert("ratio".to_string(), PropertyValue::Double(1.0));
+        application_properties.insert(
+            "trace".to_string(),
+            PropertyValue::Uuid(uuid::Uuid::from_u128(0x1234)),
+        );
+        application_properties.insert(
+            "created".to_string(),
+            PropertyValue::Timestamp(OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap()),
+        );
+
+        MessageModel::new(
+            7,
+            "msg-7".to_string(),
+            OffsetDateTime::from_unix_timestamp(1_700_000_100).unwrap(),
+            3,
+            MessageState::Active,
+            BodyData::from_bytes(b"{ \"id\":  1 }"),
+        )
+        .with_raw_body(b"{ \"id\":  1 }".to_vec())
+        .with_properties(properties)
+    }
+
+    #[test]
+    fn test_record_keeps_original_body_bytes() {
+        let body = b"{ \"id\":  1 }";
+        let record = ExportedMessage::from_message(&message());
+
+        assert_eq!(record.body().unwrap(), body);
+        assert_eq!(record.body_text.as_deref(), Some("{ \"id\":  1 }"));
+        assert_eq!(record.time_to_live_ms, Some(90_500));
+        assert_eq!(record.state, "Active");
+
+        let binary =
+            ExportedMessage::from_message(&message().with_raw_body(vec![0xff, 0x00, 0x9f]));
+        assert_eq!(binary.body().unwrap(), vec![0xff, 0x00, 0x9f]);
+        assert_eq!(binary.body_text, None);
+    }
+
+    #[test]
+    fn test_record_json_round_trip_restores_property_types() {
+        let model = message().with_raw_body(b"payload".to_vec());
+        let record = ExportedMessage::from_message(&model);
+        let json = serde_json::to_string(&record).unwrap();
+        let read: ExportedMessage = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(read.enqueued_time, record.enqueued_time);
+        assert_eq!(read.body().unwrap(), b"payload");
+        assert_eq!(
+            read.typed_application_properties(),
+            model.properties.application_properties
+        );
+    }
+
+    #[test]
+    fn test_to_message_data_keeps_or_regenerates_message_id() {
+        let model = message().with_raw_body(vec![0xff, 0x01]);
+        let record = ExportedMessage::from_message(&model);
+
+        let kept = record.to_message_data(true).unwrap();
+        assert_eq!(kept.system_properties.message_id.as_deref(), Some("msg-7"));
+        assert_eq!(kept.body_bytes(), vec![0xff, 0x01]);
+        assert_eq!(
+            kept.system_properties.correlation_id.as_deref(),
+            Some("corr-1")
+        );
+        assert_eq!(
+            kept.system_properties.time_to_live,
+            Some(std::time::Duration::from_millis(90_500))
+        );
+        assert_eq!(
+            kept.application_properties,
+            model.properties.application_properties
+        );
+
+        let regenerated = record.to_message_data(false).unwrap();
+        let message_id = regenerated.system_properties.message_id.unwrap();
+        assert_ne!(message_id, "msg-7");
+        assert!(uuid::Uuid::parse_str(&message_id).is_ok());
+
+        let broken = ExportedMessage {
+            body_base64: "not base64!".to_string(),
+            ..record
+        };
+        assert!(broken.to_message_data(true).is_err());
+    }
+
+    #[test]
+    fn test_unset_properties_serialize_as_null() {
+        let record = ExportedMessage::from_message(&message().with_raw_body(Vec::new()));
+        let json = serde_json::to_value(&record).unwrap();
+
+        assert!(json["session_id"].is_null());
+        assert!(json["scheduled_enqueue_time"].is_null());
+        assert_eq!(json["correlation_id"], "corr-1");
+    }
+}
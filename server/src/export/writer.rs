@@ -0,0 +1,223 @@
+use super::{ExportError, ExportFormat, ExportedMessage};
+use serde_json::Value;
+use std::io::Write;
+
+/// CSV columns, in the order of the [`ExportedMessage`] fields
+const CSV_COLUMNS: &[&str] = &[
+    "sequence_number",
+    "message_id",
+    "enqueued_time",
+    "delivery_count",
+    "state",
+    "correlation_id",
+    "session_id",
+    "content_type",
+    "subject",
+    "reply_to",
+    "to",
+    "time_to_live_ms",
+    "scheduled_enqueue_time",
+    "locked_until",
+    "dead_letter_reason",
+    "dead_letter_error_description",
+    "dead_letter_source",
+    "application_properties",
+    "application_property_types",
+    "body_base64",
+    "body_text",
+];
+
+/// Streams [`ExportedMessage`]s into a file in one of the [`ExportFormat`]s.
+///
+/// Call [`finish`](Self::finish) once all records are written; the JSON
+/// array is only closed there.
+pub struct ExportWriter<W: Write> {
+    writer: W,
+    format: ExportFormat,
+    written: usize,
+}
+
+impl<W: Write> ExportWriter<W> {
+    /// Starts an export, writing the CSV header or opening the JSON array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `writer` fails
+    pub fn new(mut writer: W, format: ExportFormat) -> Result<Self, ExportError> {
+        match format {
+            ExportFormat::Ndjson => {}
+            ExportFormat::Json => writer.write_all(b"[")?,
+            ExportFormat::Csv => {
+                let header: Vec<String> = CSV_COLUMNS.iter().map(|c| csv_field(c)).collect();
+                // RFC 4180 line endings
+                write!(writer, "{}\r\n", header.join(","))?;
+            }
+        }
+
+        Ok(Self {
+            writer,
+            format,
+            written: 0,
+        })
+    }
+
+    /// Appends one record
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record cannot be serialized or written
+    pub fn write(&mut self, record: &ExportedMessage) -> Result<(), ExportError> {
+        match self.format {
+            ExportFormat::Ndjson => {
+                serde_json::to_writer(&mut self.writer, record)?;
+                self.writer.write_all(b"\n")?;
+            }
+            ExportFormat::Json => {
+                let separator = if self.written == 0 { "\n" } else { ",\n" };
+                self.writer.write_all(separator.as_bytes())?;
+                // Indent each record as an array element
+                let pretty = serde_json::to_string_pretty(record)?;
+                for (index, line) in pretty.lines().enumerate() {
+                    if index > 0 {
+                        self.writer.write_all(b"\n")?;
+                    }
+                    write!(self.writer, "  {line}")?;
+                }
+            }
+            ExportFormat::Csv => {
+                let Value::Object(fields) = serde_json::to_value(record)? else {
+                    unreachable!("records serialize as objects");
+                };
+                let row: Vec<String> = CSV_COLUMNS
+                    .iter()
+                    .map(|column| match fields.get(*column) {
+                        None | Some(Value::Null) => String::new(),
+                        Some(Value::String(text)) => csv_field(text),
+                        // Numbers as written, maps as compact JSON text
+                        Some(other) => csv_field(&other.to_string()),
+                    })
+                    .collect();
+                write!(self.writer, "{}\r\n", row.join(","))?;
+            }
+        }
+
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Number of records written so far
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Completes the export and flushes it, returning the underlying writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing or flushing fails
+    pub fn finish(mut self) -> Result<W, ExportError> {
+        if self.format == ExportFormat::Json {
+            let closing: &[u8] = if self.written == 0 { b"]\n" } else { b"\n]\n" };
+            self.writer.write_all(closing)?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BodyData, MessageModel, MessageProperties, MessageState, PropertyValue};
+    use azure_core::time::OffsetDateTime;
+
+    fn record(sequence: i64, body: &[u8]) -> ExportedMessage {
+        let mut properties = MessageProperties {
+            subject: Some("Order, \"urgent\"".to_string()),
+            ..Default::default()
+        };
+        properties.application_properties.insert(
+            "region".to_string(),
+            PropertyValue::String("eu".to_string()),
+        );
+        let message = MessageModel::new(
+            sequence,
+            format!("msg-{sequence}"),
+            OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap(),
+            1,
+            MessageState::Active,
+            BodyData::from_bytes(body),
+        )
+        .with_raw_body(body.to_vec())
+        .with_properties(properties);
+        ExportedMessage::from_message(&message)
+    }
+
+    fn export(format: ExportFormat, records: &[ExportedMessage]) -> String {
+        let mut writer = ExportWriter::new(Vec::new(), format).unwrap();
+        for record in records {
+            writer.write(record).unwrap();
+        }
+        assert_eq!(writer.written(), records.len());
+        String::from_utf8(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_ndjson_writes_one_record_per_line() {
+        let records = [record(1, b"a"), record(2, b"line\nbreak")];
+        let output = export(ExportFormat::Ndjson, &records);
+
+        let read: Vec<ExportedMessage> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(read, records);
+    }
+
+    #[test]
+    fn test_json_writes_a_valid_array() {
+        let records = [record(1, b"a"), record(2, br#"{"id": 2}"#)];
+        let output = export(ExportFormat::Json, &records);
+
+        assert!(output.starts_with("[\n  {"));
+        let read: Vec<ExportedMessage> = serde_json::from_str(&output).unwrap();
+        assert_eq!(read, records);
+
+        let empty: Vec<ExportedMessage> =
+            serde_json::from_str(&export(ExportFormat::Json, &[])).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_csv_quotes_fields() {
+        let output = export(ExportFormat::Csv, &[record(1, b"one, \"two\"\nthree")]);
+        let (header, row) = output.split_once("\r\n").unwrap();
+
+        assert_eq!(header.split(',').count(), CSV_COLUMNS.len());
+        assert!(row.starts_with("1,msg-1,"));
+        assert!(row.contains(",\"Order, \"\"urgent\"\"\","));
+        assert!(row.contains(",\"{\"\"region\"\":\"\"eu\"\"}\","));
+        assert!(row.ends_with(",\"one, \"\"two\"\"\nthree\"\r\n"));
+    }
+
+    #[test]
+    fn test_csv_columns_cover_every_field() {
+        let Value::Object(fields) = serde_json::to_value(record(1, b"")).unwrap() else {
+            panic!("record is not an object");
+        };
+        let mut keys: Vec<&str> = fields.keys().map(String::as_str).collect();
+        let mut columns = CSV_COLUMNS.to_vec();
+        keys.sort_unstable();
+        columns.sort_unstable();
+        assert_eq!(columns, keys);
+    }
+}
@@ -0,0 +1,332 @@
+use super::parser::{
+    ArithmeticOp, CompareOp, Expr, Literal, PathSegment, Property, SystemProperty,
+};
+use crate::model::{BodyData, MessageModel, PropertyValue};
+use azure_core::time::OffsetDateTime;
+use std::cmp::Ordering;
+
+/// Runtime value of an operand
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Timestamp(OffsetDateTime),
+}
+
+/// Evaluates `expr` against a message with SQL three-valued logic; `None`
+/// is UNKNOWN, which does not match
+pub(crate) fn evaluate(expr: &Expr, message: &MessageModel) -> Option<bool> {
+    match expr {
+        Expr::Not(inner) => evaluate(inner, message).map(|value| !value),
+        Expr::And(left, right) => match (evaluate(left, message), evaluate(right, message)) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        },
+        Expr::Or(left, right) => match (evaluate(left, message), evaluate(right, message)) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        },
+        Expr::Compare(op, left, right) => {
+            compare(*op, &value(left, message), &value(right, message))
+        }
+        Expr::IsNull { expr, negated } => {
+            let is_null = value(expr, message) == Value::Null;
+            Some(is_null != *negated)
+        }
+        Expr::Like {
+            expr,
+            pattern,
+            escape,
+            negated,
+        } => match value(expr, message) {
+            Value::String(text) => Some(like(&text, pattern, *escape) != *negated),
+            _ => None,
+        },
+        Expr::In {
+            expr,
+            list,
+            negated,
+        } => {
+            let needle = value(expr, message);
+            let mut result = Some(false);
+            for item in list {
+                match compare(CompareOp::Eq, &needle, &value(item, message)) {
+                    Some(true) => {
+                        result = Some(true);
+                        break;
+                    }
+                    None => result = None,
+                    Some(false) => {}
+                }
+            }
+            result.map(|found| found != *negated)
+        }
+        Expr::Exists(property) => Some(resolve(property, message).is_some()),
+        Expr::Literal(_) | Expr::Property(_) | Expr::Negate(_) | Expr::Arithmetic(..) => {
+            match value(expr, message) {
+                Value::Bool(value) => Some(value),
+                _ => None,
+            }
+        }
+    }
+}
+
+fn value(expr: &Expr, message: &MessageModel) -> Value {
+    match expr {
+        Expr::Literal(literal) => match literal {
+            Literal::Null => Value::Null,
+            Literal::Bool(value) => Value::Bool(*value),
+            Literal::Int(value) => Value::Int(*value),
+            Literal::Float(value) => Value::Float(*value),
+            Literal::String(value) => Value::String(value.clone()),
+        },
+        Expr::Property(property) => resolve(property, message).unwrap_or(Value::Null),
+        Expr::Negate(inner) => match value(inner, message) {
+            Value::Int(value) => value.checked_neg().map_or(Value::Null, Value::Int),
+            Value::Float(value) => Value::Float(-value),
+            _ => Value::Null,
+        },
+        Expr::Arithmetic(op, left, right) => {
+            arithmetic(*op, value(left, message), value(right, message))
+        }
+        _ => evaluate(expr, message).map_or(Value::Null, Value::Bool),
+    }
+}
+
+/// Looks up a property; `None` when the message does not carry it
+fn resolve(property: &Property, message: &MessageModel) -> Option<Value> {
+    match property {
+        Property::System(property) => system_property(*property, message),
+        Property::User(name) => user_property(name, message),
+        Property::Body(path) => body_value(path, &message.body),
+    }
+}
+
+fn system_property(property: SystemProperty, message: &MessageModel) -> Option<Value> {
+    let properties = &message.properties;
+    let text = |value: &Option<String>| value.clone().map(Value::String);
+    match property {
+        SystemProperty::MessageId => Some(Value::String(message.id.clone())),
+        SystemProperty::CorrelationId => text(&properties.correlation_id),
+        SystemProperty::SessionId => text(&properties.session_id),
+        SystemProperty::ContentType => text(&properties.content_type),
+        SystemProperty::Label => text(&properties.subject),
+        SystemProperty::To => text(&properties.to),
+        SystemProperty::ReplyTo => text(&properties.reply_to),
+        SystemProperty::DeliveryCount => Some(Value::Int(
+            i64::try_from(message.delivery_count).unwrap_or(i64::MAX),
+        )),
+        SystemProperty::SequenceNumber => Some(Value::Int(message.sequence)),
+        SystemProperty::EnqueuedTimeUtc => Some(Value::Timestamp(message.enqueued_at)),
+        SystemProperty::ScheduledEnqueueTimeUtc => {
+            properties.scheduled_enqueue_time.map(Value::Timestamp)
+        }
+        SystemProperty::LockedUntilUtc => properties.locked_until.map(Value::Timestamp),
+        SystemProperty::TimeToLive => properties
+            .time_to_live
+            .map(|ttl| Value::Int(ttl.whole_seconds())),
+        SystemProperty::DeadLetterSource => text(&properties.dead_letter_source),
+        SystemProperty::State => Some(Value::String(format!("{:?}", message.state))),
+    }
+}
+
+fn user_property(name: &str, message: &MessageModel) -> Option<Value> {
+    let properties = &message.properties;
+    let value = properties.application_properties.get(name).or_else(|| {
+        properties
+            .application_properties
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    });
+    if let Some(value) = value {
+        return Some(match value {
+            PropertyValue::Null => Value::Null,
+            PropertyValue::Bool(value) => Value::Bool(*value),
+            PropertyValue::Int(value) => Value::Int(*value),
+            PropertyValue::Double(value) => Value::Float(*value),
+            PropertyValue::String(value) => Value::String(value.clone()),
+            PropertyValue::Uuid(value) => Value::String(value.to_string()),
+            PropertyValue::Timestamp(value) => Value::Timestamp(*value),
+        });
+    }
+
+    // Dead-letter details travel as application properties on the wire
+    let dead_letter = if name.eq_ignore_ascii_case("DeadLetterReason") {
+        &properties.dead_letter_reason
+    } else if name.eq_ignore_ascii_case("DeadLetterErrorDescription") {
+        &properties.dead_letter_error_description
+    } else {
+        return None;
+    };
+    dead_letter.clone().map(Value::String)
+}
+
+fn body_value(path: &[PathSegment], body: &BodyData) -> Option<Value> {
+    let json = match body {
+        BodyData::ValidJson(json) => json,
+        BodyData::RawString(text) if path.is_empty() => return Some(Value::String(text.clone())),
+        BodyData::RawString(_) | BodyData::Binary(_) => return None,
+    };
+
+    let mut current = json;
+    for segment in path {
+        current = match segment {
+            PathSegment::Key(key) => current.get(key.as_str())?,
+            PathSegment::Index(index) => current.get(*index)?,
+        };
+    }
+
+    let value = match current {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(value) => Value::Bool(*value),
+        serde_json::Value::Number(number) => number
+            .as_i64()
+            .map(Value::Int)
+            .or_else(|| number.as_f64().map(Value::Float))
+            .unwrap_or(Value::Null),
+        serde_json::Value::String(text) => Value::String(text.clone()),
+        // Objects and arrays compare as their JSON text, which keeps LIKE useful
+        other => Value::String(other.to_string()),
+    };
+    Some(value)
+}
+
+fn arithmetic(op: ArithmeticOp, left: Value, right: Value) -> Value {
+    match (left, right) {
+        (Value::Int(left), Value::Int(right)) => {
+            let result = match op {
+                ArithmeticOp::Add => left.checked_add(right),
+                ArithmeticOp::Subtract => left.checked_sub(right),
+                ArithmeticOp::Multiply => left.checked_mul(right),
+                ArithmeticOp::Divide => left.checked_div(right),
+                ArithmeticOp::Modulo => left.checked_rem(right),
+            };
+            result.map_or(Value::Null, Value::Int)
+        }
+        (left, right) => match (as_float(&left), as_float(&right)) {
+            (Some(left), Some(right)) => {
+                let result = match op {
+                    ArithmeticOp::Add => left + right,
+                    ArithmeticOp::Subtract => left - right,
+                    ArithmeticOp::Multiply => left * right,
+                    ArithmeticOp::Divide if right == 0.0 => return Value::Null,
+                    ArithmeticOp::Divide => left / right,
+                    ArithmeticOp::Modulo if right == 0.0 => return Value::Null,
+                    ArithmeticOp::Modulo => left % right,
+                };
+                Value::Float(result)
+            }
+            _ => Value::Null,
+        },
+    }
+}
+
+fn as_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int(value) => Some(*value as f64),
+        Value::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn compare(op: CompareOp, left: &Value, right: &Value) -> Option<bool> {
+    let ordering = order(left, right)?;
+    let result = match op {
+        CompareOp::Eq => ordering == Ordering::Equal,
+        CompareOp::NotEq => ordering != Ordering::Equal,
+        CompareOp::Lt => ordering == Ordering::Less,
+        CompareOp::LtEq => ordering != Ordering::Greater,
+        CompareOp::Gt => ordering == Ordering::Greater,
+        CompareOp::GtEq => ordering != Ordering::Less,
+    };
+    Some(result)
+}
+
+/// Orders two values of compatible types. Numeric text compares with
+/// numbers and RFC 3339 text with timestamps, so `body.orderId = '123'`
+/// also matches a numeric id; anything else mixed is UNKNOWN.
+fn order(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Null, _) | (_, Value::Null) => None,
+        (Value::Int(left), Value::Int(right)) => Some(left.cmp(right)),
+        (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+        (Value::Bool(left), Value::Bool(right)) => Some(left.cmp(right)),
+        (Value::Timestamp(left), Value::Timestamp(right)) => Some(left.cmp(right)),
+        (Value::Timestamp(time), Value::String(text)) => azure_core::time::parse_rfc3339(text)
+            .ok()
+            .map(|other| time.cmp(&other)),
+        (Value::String(_), Value::Timestamp(_)) => order(right, left).map(Ordering::reverse),
+        (Value::String(text), number) => {
+            let parsed = text.trim().parse::<f64>().ok()?;
+            as_float(number)?
+                .partial_cmp(&parsed)
+                .map(Ordering::reverse)
+        }
+        (number, Value::String(text)) => {
+            let parsed = text.trim().parse::<f64>().ok()?;
+            as_float(number)?.partial_cmp(&parsed)
+        }
+        (left, right) => as_float(left)?.partial_cmp(&as_float(right)?),
+    }
+}
+
+/// Matches `text` against a LIKE pattern, where `%` is any run of
+/// characters and `_` any single character
+fn like(text: &str, pattern: &str, escape: Option<char>) -> bool {
+    enum Part {
+        Literal(char),
+        AnyOne,
+        AnyRun,
+    }
+
+    let mut parts = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        let part = match c {
+            c if Some(c) == escape => match chars.next() {
+                Some(escaped) => Part::Literal(escaped),
+                None => Part::Literal(c),
+            },
+            '%' => Part::AnyRun,
+            '_' => Part::AnyOne,
+            c => Part::Literal(c),
+        };
+        parts.push(part);
+    }
+
+    let text: Vec<char> = text.chars().collect();
+    // Greedy matching with backtracking to the last `%`
+    let (mut t, mut p) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match parts.get(p) {
+            Some(Part::AnyRun) => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(Part::AnyOne) => {
+                t += 1;
+                p += 1;
+            }
+            Some(Part::Literal(c)) if *c == text[t] => {
+                t += 1;
+                p += 1;
+            }
+            _ => match backtrack {
+                Some((run, start)) => {
+                    p = run + 1;
+                    t = start + 1;
+                    backtrack = Some((run, start + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    parts[p..].iter().all(|part| matches!(part, Part::AnyRun))
+}
@@ -0,0 +1,212 @@
+use super::FilterError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Token {
+    /// Bare identifier or keyword
+    Ident(String),
+    /// `[bracketed identifier]`, never a keyword
+    QuotedIdent(String),
+    String(String),
+    Integer(i64),
+    Float(f64),
+    LParen,
+    RParen,
+    Comma,
+    Dot,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+}
+
+/// A token and the 1-based character position it starts at
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Spanned {
+    pub token: Token,
+    pub position: usize,
+}
+
+pub(super) fn tokenize(input: &str) -> Result<Vec<Spanned>, FilterError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens: Vec<Spanned> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let position = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let token = match c {
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            // After a name a dot separates path segments; elsewhere `.5` is a number
+            '.' if !chars.get(i + 1).is_some_and(char::is_ascii_digit)
+                || matches!(
+                    tokens.last(),
+                    Some(Spanned {
+                        token: Token::Ident(_) | Token::QuotedIdent(_) | Token::Integer(_),
+                        ..
+                    })
+                ) =>
+            {
+                Token::Dot
+            }
+            '=' => Token::Eq,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '<' => match chars.get(i + 1) {
+                Some('=') => {
+                    i += 1;
+                    Token::LtEq
+                }
+                Some('>') => {
+                    i += 1;
+                    Token::NotEq
+                }
+                _ => Token::Lt,
+            },
+            '>' => match chars.get(i + 1) {
+                Some('=') => {
+                    i += 1;
+                    Token::GtEq
+                }
+                _ => Token::Gt,
+            },
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                i += 1;
+                Token::NotEq
+            }
+            '\'' => {
+                let (text, end) = read_quoted(&chars, i, '\'')
+                    .ok_or_else(|| FilterError::syntax(position, "unterminated string"))?;
+                i = end;
+                tokens.push(Spanned {
+                    token: Token::String(text),
+                    position,
+                });
+                continue;
+            }
+            '[' => {
+                let (text, end) = read_quoted(&chars, i, ']')
+                    .ok_or_else(|| FilterError::syntax(position, "unterminated [identifier]"))?;
+                i = end;
+                tokens.push(Spanned {
+                    token: Token::QuotedIdent(text),
+                    position,
+                });
+                continue;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                // Path segments such as `body.items.0.sku` are integers, not decimals
+                let after_dot = matches!(
+                    tokens.last(),
+                    Some(Spanned {
+                        token: Token::Dot,
+                        ..
+                    })
+                );
+                let (token, end) = read_number(&chars, i, after_dot)
+                    .ok_or_else(|| FilterError::syntax(position, "invalid number"))?;
+                i = end;
+                tokens.push(Spanned { token, position });
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' || c == '@' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$' | '@'))
+                {
+                    i += 1;
+                }
+                tokens.push(Spanned {
+                    token: Token::Ident(chars[start..i].iter().collect()),
+                    position,
+                });
+                continue;
+            }
+            other => {
+                return Err(FilterError::syntax(
+                    position,
+                    format!("unexpected character '{other}'"),
+                ));
+            }
+        };
+
+        tokens.push(Spanned { token, position });
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// Reads text up to the closing `quote`, where a doubled quote stands for
+/// itself. Returns the text and the index after the closing quote.
+fn read_quoted(chars: &[char], start: usize, quote: char) -> Option<(String, usize)> {
+    let mut text = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                text.push(quote);
+                i += 2;
+                continue;
+            }
+            return Some((text, i + 1));
+        }
+        text.push(chars[i]);
+        i += 1;
+    }
+    None
+}
+
+fn read_number(chars: &[char], start: usize, integer_only: bool) -> Option<(Token, usize)> {
+    let mut i = start;
+    let mut is_float = false;
+    while i < chars.len() && chars[i].is_ascii_digit() {
+        i += 1;
+    }
+    if !integer_only {
+        if chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+            is_float = true;
+            i += 1;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+        }
+        if matches!(chars.get(i), Some('e' | 'E')) {
+            let mut j = i + 1;
+            if matches!(chars.get(j), Some('+' | '-')) {
+                j += 1;
+            }
+            if chars.get(j).is_some_and(char::is_ascii_digit) {
+                is_float = true;
+                i = j;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    let text: String = chars[start..i].iter().collect();
+    let token = if is_float {
+        Token::Float(text.parse().ok()?)
+    } else {
+        Token::Integer(text.parse().ok()?)
+    };
+    Some((token, i))
+}
@@ -0,0 +1,286 @@
+//! Message predicates written in the Service Bus SQL filter grammar.
+//!
+//! [`SqlFilter`] parses the same expression language that subscription SQL
+//! rules use and evaluates it locally against [`MessageModel`]s, so a queue
+//! can be searched without creating a rule on the broker.
+//!
+//! ## Properties
+//!
+//! | Reference | Resolves to |
+//! |-----------|-------------|
+//! | `sys.DeliveryCount`, `sys.MessageId`, ... | Broker properties, case-insensitive |
+//! | `user.region`, `region`, `[my-prop]` | Application properties |
+//! | `body.order.id`, `body.items.0.sku` | Fields of a JSON body; numbers index arrays |
+//! | `body` | The whole body as text |
+//!
+//! Supported system properties are `MessageId`, `CorrelationId`, `SessionId`,
+//! `ContentType`, `Label` (or `Subject`), `To`, `ReplyTo`, `DeliveryCount`,
+//! `SequenceNumber`, `EnqueuedTimeUtc`, `ScheduledEnqueueTimeUtc`,
+//! `LockedUntilUtc`, `TimeToLive` (seconds), `DeadLetterSource` and `State`.
+//!
+//! ## Operators
+//!
+//! `AND`, `OR`, `NOT`, `=`, `<>` (or `!=`), `<`, `<=`, `>`, `>=`,
+//! `IS [NOT] NULL`, `[NOT] LIKE 'pattern' [ESCAPE 'c']`, `[NOT] IN (...)`,
+//! `EXISTS(property)` and the arithmetic operators `+ - * / %`.
+//!
+//! Missing properties are NULL and comparisons involving NULL are unknown,
+//! so they never match. Timestamps compare with RFC 3339 strings and numbers
+//! with numeric strings.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use quetty_server::filter::SqlFilter;
+//!
+//! let filter = SqlFilter::parse("sys.DeliveryCount > 3 AND body.orderId = '123'")?;
+//! # let messages: Vec<quetty_server::model::MessageModel> = Vec::new();
+//! let stuck: Vec<_> = messages.iter().filter(|message| filter.matches(message)).collect();
+//! # Ok::<(), quetty_server::filter::FilterError>(())
+//! ```
+
+mod eval;
+mod lexer;
+mod parser;
+
+use crate::model::MessageModel;
+use parser::{Expr, PathSegment, Property};
+use std::fmt;
+use thiserror::Error;
+
+/// Errors raised while parsing a filter expression
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FilterError {
+    /// The expression is not valid; `position` is the 1-based character
+    /// where the problem was found
+    #[error("Syntax error at position {position}: {message}")]
+    Syntax { position: usize, message: String },
+    /// The expression is valid for searches but refers to something a
+    /// subscription rule cannot see, such as the body or the delivery count
+    #[error("{0} cannot be used in a subscription rule")]
+    NotInRules(String),
+}
+
+impl FilterError {
+    fn syntax(position: usize, message: impl Into<String>) -> Self {
+        Self::Syntax {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+/// A parsed SQL filter expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlFilter {
+    expression: String,
+    expr: Expr,
+}
+
+impl SqlFilter {
+    /// Parses a filter expression
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterError::Syntax`] if the expression is not valid
+    pub fn parse(expression: &str) -> Result<Self, FilterError> {
+        let expr = parser::parse(expression)?;
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            expr,
+        })
+    }
+
+    /// Parses the expression of a subscription rule.
+    ///
+    /// Service Bus evaluates rules when a message is published, so only
+    /// application properties and the `sys.` headers set by the sender are
+    /// available; body paths and message state such as `sys.DeliveryCount`
+    /// are rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterError::Syntax`] if the expression is not valid, or
+    /// [`FilterError::NotInRules`] if it uses a property rules cannot see
+    pub fn parse_rule(expression: &str) -> Result<Self, FilterError> {
+        let filter = Self::parse(expression)?;
+        let unsupported =
+            filter
+                .expr
+                .properties()
+                .into_iter()
+                .find_map(|property| match property {
+                    Property::User(_) => None,
+                    Property::System(property) if property.is_header() => None,
+                    Property::System(property) => Some(format!("sys.{property:?}")),
+                    Property::Body(path) => Some(body_path(path)),
+                });
+        match unsupported {
+            Some(property) => Err(FilterError::NotInRules(property)),
+            None => Ok(filter),
+        }
+    }
+
+    /// The expression as written
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Whether the message satisfies the filter; unknown results do not match
+    pub fn matches(&self, message: &MessageModel) -> bool {
+        eval::evaluate(&self.expr, message) == Some(true)
+    }
+}
+
+/// A body path as written in an expression, e.g. `body.items.0.sku`
+fn body_path(path: &[PathSegment]) -> String {
+    path.iter()
+        .fold("body".to_string(), |text, segment| match segment {
+            PathSegment::Key(key) => format!("{text}.{key}"),
+            PathSegment::Index(index) => format!("{text}.{index}"),
+        })
+}
+
+impl fmt::Display for SqlFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BodyData, MessageProperties, MessageState, PropertyValue};
+    use azure_core::time::{Duration, OffsetDateTime};
+    use serde_json::json;
+
+    fn message() -> MessageModel {
+        let mut properties = MessageProperties {
+            subject: Some("OrderCreated".to_string()),
+            correlation_id: Some("corr-1".to_string()),
+            time_to_live: Some(Duration::minutes(5)),
+            dead_letter_reason: Some("MaxDeliveryCountExceeded".to_string()),
+            ..Default::default()
+        };
+        properties.application_properties.insert(
+            "region".to_string(),
+            PropertyValue::String("eu".to_string()),
+        );
+        properties
+            .application_properties
+            .insert("retries".to_string(), PropertyValue::Int(2));
+        properties.application_properties.insert(
+            "tenant-id".to_string(),
+            PropertyValue::String("t1".to_string()),
+        );
+
+        MessageModel::new(
+            42,
+            "msg-1".to_string(),
+            OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap(),
+            5,
+            MessageState::DeadLettered,
+            BodyData::ValidJson(json!({
+                "orderId": "123",
+                "total": 99.5,
+                "customer": {"name": "Acme", "vip": true},
+                "items": [{"sku": "A-1"}, {"sku": "B-2"}],
+                "note": null
+            })),
+        )
+        .with_properties(properties)
+    }
+
+    fn matches(expression: &str) -> bool {
+        SqlFilter::parse(expression)
+            .unwrap_or_else(|e| panic!("{expression}: {e}"))
+            .matches(&message())
+    }
+
+    #[test]
+    fn test_system_properties() {
+        assert!(matches("sys.DeliveryCount > 3"));
+        assert!(matches(
+            "sys.deliverycount = 5 AND sys.SequenceNumber >= 42"
+        ));
+        assert!(matches(
+            "sys.Label = 'OrderCreated' AND sys.Subject = 'OrderCreated'"
+        ));
+        assert!(matches("sys.MessageId = 'msg-1'"));
+        assert!(matches("sys.TimeToLive = 300"));
+        assert!(matches("sys.State = 'DeadLettered'"));
+        assert!(matches("sys.EnqueuedTimeUtc > '2023-11-14T00:00:00Z'"));
+        assert!(!matches("sys.EnqueuedTimeUtc > '2024-01-01T00:00:00Z'"));
+        assert!(matches("sys.SessionId IS NULL"));
+    }
+
+    #[test]
+    fn test_application_properties() {
+        assert!(matches("region = 'eu'"));
+        assert!(matches("user.region = 'eu' AND user.retries < 3"));
+        assert!(matches("[tenant-id] = 't1'"));
+        assert!(matches("EXISTS(region) AND NOT EXISTS(missing)"));
+        assert!(matches("DeadLetterReason LIKE 'MaxDelivery%'"));
+        assert!(!matches("missing = 'x'"));
+        assert!(!matches("NOT (missing = 'x')"));
+    }
+
+    #[test]
+    fn test_body_paths() {
+        assert!(matches("sys.DeliveryCount > 3 AND body.orderId = '123'"));
+        assert!(matches("body.orderId = 123"));
+        assert!(matches(
+            "body.customer.name = 'Acme' AND body.customer.vip = TRUE"
+        ));
+        assert!(matches("body.items.1.sku = 'B-2'"));
+        assert!(matches("body.total * 2 = 199"));
+        assert!(matches("body.note IS NULL AND EXISTS(body.note)"));
+        assert!(matches("body LIKE '%Acme%'"));
+        assert!(!matches("body.items.5.sku = 'B-2'"));
+    }
+
+    #[test]
+    fn test_operators() {
+        assert!(matches("region IN ('us', 'eu')"));
+        assert!(matches("region NOT IN ('us', 'apac')"));
+        assert!(matches("sys.CorrelationId LIKE 'corr-_'"));
+        assert!(matches("sys.CorrelationId NOT LIKE 'x%'"));
+        assert!(matches("'50%' LIKE '50!%' ESCAPE '!'"));
+        assert!(matches(
+            "retries + 1 = 3 AND -retries = -2 AND 7 % retries = 1"
+        ));
+        assert!(matches("region <> 'us' AND region != 'us'"));
+        assert!(matches(
+            "(region = 'us' OR retries = 2) AND NOT region = 'us'"
+        ));
+        assert!(!matches("retries / 0 = 0"));
+    }
+
+    #[test]
+    fn test_unknown_propagates() {
+        // UNKNOWN OR TRUE is TRUE, UNKNOWN AND FALSE is FALSE
+        assert!(matches("missing = 1 OR region = 'eu'"));
+        assert!(!matches("NOT (missing = 1 AND region = 'eu')"));
+        assert!(matches("NOT (missing = 1 AND region = 'us')"));
+        assert!(!matches("region = 1"));
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let error = SqlFilter::parse("sys.DeliveryCount >").unwrap_err();
+        assert_eq!(
+            error,
+            FilterError::Syntax {
+                position: 20,
+                message: "unexpected end of expression".to_string()
+            }
+        );
+
+        assert!(SqlFilter::parse("sys.Unknown = 1").is_err());
+        assert!(SqlFilter::parse("region = 'eu").is_err());
+        assert!(SqlFilter::parse("region = 'eu' region").is_err());
+        assert!(SqlFilter::parse("region LIKE 5").is_err());
+        assert!(SqlFilter::parse("props.region = 1").is_err());
+        assert!(SqlFilter::parse("AND = 1").is_err());
+    }
+}
@@ -0,0 +1,496 @@
+use super::FilterError;
+use super::lexer::{Spanned, Token, tokenize};
+
+/// Literal value in an expression
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Literal {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+/// Message property referenced by an expression
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Property {
+    /// Broker property such as `sys.DeliveryCount`
+    System(SystemProperty),
+    /// Application property; unqualified names resolve here
+    User(String),
+    /// Path into a JSON body; an empty path is the whole body
+    Body(Vec<PathSegment>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SystemProperty {
+    MessageId,
+    CorrelationId,
+    SessionId,
+    ContentType,
+    Label,
+    To,
+    ReplyTo,
+    DeliveryCount,
+    SequenceNumber,
+    EnqueuedTimeUtc,
+    ScheduledEnqueueTimeUtc,
+    LockedUntilUtc,
+    TimeToLive,
+    DeadLetterSource,
+    State,
+}
+
+impl SystemProperty {
+    /// Whether the sender sets the property, as opposed to the broker
+    /// recording the state of a stored message
+    pub(crate) fn is_header(self) -> bool {
+        !matches!(
+            self,
+            Self::DeliveryCount
+                | Self::SequenceNumber
+                | Self::EnqueuedTimeUtc
+                | Self::LockedUntilUtc
+                | Self::DeadLetterSource
+                | Self::State
+        )
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let property = match name.to_ascii_lowercase().as_str() {
+            "messageid" => Self::MessageId,
+            "correlationid" => Self::CorrelationId,
+            "sessionid" => Self::SessionId,
+            "contenttype" => Self::ContentType,
+            "label" | "subject" => Self::Label,
+            "to" => Self::To,
+            "replyto" => Self::ReplyTo,
+            "deliverycount" => Self::DeliveryCount,
+            "sequencenumber" => Self::SequenceNumber,
+            "enqueuedtimeutc" => Self::EnqueuedTimeUtc,
+            "scheduledenqueuetimeutc" => Self::ScheduledEnqueueTimeUtc,
+            "lockeduntilutc" => Self::LockedUntilUtc,
+            "timetolive" => Self::TimeToLive,
+            "deadlettersource" => Self::DeadLetterSource,
+            "state" => Self::State,
+            _ => return None,
+        };
+        Some(property)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArithmeticOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+}
+
+/// Parsed filter expression
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr {
+    Literal(Literal),
+    Property(Property),
+    Negate(Box<Expr>),
+    Arithmetic(ArithmeticOp, Box<Expr>, Box<Expr>),
+    Compare(CompareOp, Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    IsNull {
+        expr: Box<Expr>,
+        negated: bool,
+    },
+    Like {
+        expr: Box<Expr>,
+        pattern: String,
+        escape: Option<char>,
+        negated: bool,
+    },
+    In {
+        expr: Box<Expr>,
+        list: Vec<Expr>,
+        negated: bool,
+    },
+    Exists(Property),
+}
+
+impl Expr {
+    /// Every property the expression references, in order
+    pub(crate) fn properties(&self) -> Vec<&Property> {
+        let mut properties = Vec::new();
+        self.collect_properties(&mut properties);
+        properties
+    }
+
+    fn collect_properties<'a>(&'a self, properties: &mut Vec<&'a Property>) {
+        match self {
+            Expr::Literal(_) => {}
+            Expr::Property(property) | Expr::Exists(property) => properties.push(property),
+            Expr::Negate(expr)
+            | Expr::Not(expr)
+            | Expr::IsNull { expr, .. }
+            | Expr::Like { expr, .. } => expr.collect_properties(properties),
+            Expr::Arithmetic(_, left, right)
+            | Expr::Compare(_, left, right)
+            | Expr::And(left, right)
+            | Expr::Or(left, right) => {
+                left.collect_properties(properties);
+                right.collect_properties(properties);
+            }
+            Expr::In { expr, list, .. } => {
+                expr.collect_properties(properties);
+                for item in list {
+                    item.collect_properties(properties);
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn parse(input: &str) -> Result<Expr, FilterError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        end: input.chars().count() + 1,
+    };
+    let expr = parser.or_expr()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(_) => Err(parser.error("unexpected input after the end of the expression")),
+    }
+}
+
+/// Recursive-descent parser, one method per precedence level
+struct Parser {
+    tokens: Vec<Spanned>,
+    index: usize,
+    /// Position reported for errors at the end of the input
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|spanned| &spanned.token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens
+            .get(self.index + offset)
+            .map(|spanned| &spanned.token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.index)
+            .map_or(self.end, |spanned| spanned.position)
+    }
+
+    fn error(&self, message: impl Into<String>) -> FilterError {
+        FilterError::syntax(self.position(), message)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self
+            .tokens
+            .get(self.index)
+            .map(|spanned| spanned.token.clone());
+        self.index += 1;
+        token
+    }
+
+    fn eat(&mut self, expected: &Token) -> bool {
+        if self.peek() == Some(expected) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: &Token, description: &str) -> Result<(), FilterError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected {description}")))
+        }
+    }
+
+    fn is_keyword_at(&self, offset: usize, keyword: &str) -> bool {
+        matches!(self.peek_at(offset), Some(Token::Ident(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.is_keyword_at(0, keyword) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or_expr(&mut self) -> Result<Expr, FilterError> {
+        let mut left = self.and_expr()?;
+        while self.eat_keyword("OR") {
+            let right = self.and_expr()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, FilterError> {
+        let mut left = self.not_expr()?;
+        while self.eat_keyword("AND") {
+            let right = self.not_expr()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn not_expr(&mut self) -> Result<Expr, FilterError> {
+        if self.eat_keyword("NOT") {
+            return Ok(Expr::Not(Box::new(self.not_expr()?)));
+        }
+        self.predicate()
+    }
+
+    fn predicate(&mut self) -> Result<Expr, FilterError> {
+        if self.is_keyword_at(0, "EXISTS") && self.peek_at(1) == Some(&Token::LParen) {
+            self.index += 2;
+            let Expr::Property(property) = self.operand()? else {
+                return Err(self.error("EXISTS takes a property name"));
+            };
+            self.expect(&Token::RParen, "')'")?;
+            return Ok(Expr::Exists(property));
+        }
+
+        let left = self.additive()?;
+
+        if self.eat_keyword("IS") {
+            let negated = self.eat_keyword("NOT");
+            if !self.eat_keyword("NULL") {
+                return Err(self.error("expected NULL after IS"));
+            }
+            return Ok(Expr::IsNull {
+                expr: Box::new(left),
+                negated,
+            });
+        }
+
+        let negated = self.is_keyword_at(0, "NOT")
+            && (self.is_keyword_at(1, "LIKE") || self.is_keyword_at(1, "IN"));
+        if negated {
+            self.index += 1;
+        }
+
+        if self.eat_keyword("LIKE") {
+            let pattern = self.string_literal("a pattern string after LIKE")?;
+            let escape = if self.eat_keyword("ESCAPE") {
+                let escape = self.string_literal("an escape string after ESCAPE")?;
+                let mut chars = escape.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(c),
+                    _ => return Err(self.error("ESCAPE takes a single character")),
+                }
+            } else {
+                None
+            };
+            return Ok(Expr::Like {
+                expr: Box::new(left),
+                pattern,
+                escape,
+                negated,
+            });
+        }
+
+        if self.eat_keyword("IN") {
+            self.expect(&Token::LParen, "'(' after IN")?;
+            let mut list = vec![self.additive()?];
+            while self.eat(&Token::Comma) {
+                list.push(self.additive()?);
+            }
+            self.expect(&Token::RParen, "')' to close the IN list")?;
+            return Ok(Expr::In {
+                expr: Box::new(left),
+                list,
+                negated,
+            });
+        }
+
+        let op = match self.peek() {
+            Some(Token::Eq) => CompareOp::Eq,
+            Some(Token::NotEq) => CompareOp::NotEq,
+            Some(Token::Lt) => CompareOp::Lt,
+            Some(Token::LtEq) => CompareOp::LtEq,
+            Some(Token::Gt) => CompareOp::Gt,
+            Some(Token::GtEq) => CompareOp::GtEq,
+            _ => return Ok(left),
+        };
+        self.index += 1;
+        let right = self.additive()?;
+        Ok(Expr::Compare(op, Box::new(left), Box::new(right)))
+    }
+
+    fn additive(&mut self) -> Result<Expr, FilterError> {
+        let mut left = self.multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => ArithmeticOp::Add,
+                Some(Token::Minus) => ArithmeticOp::Subtract,
+                _ => return Ok(left),
+            };
+            self.index += 1;
+            let right = self.multiplicative()?;
+            left = Expr::Arithmetic(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, FilterError> {
+        let mut left = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => ArithmeticOp::Multiply,
+                Some(Token::Slash) => ArithmeticOp::Divide,
+                Some(Token::Percent) => ArithmeticOp::Modulo,
+                _ => return Ok(left),
+            };
+            self.index += 1;
+            let right = self.unary()?;
+            left = Expr::Arithmetic(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, FilterError> {
+        if self.eat(&Token::Minus) {
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+        if self.eat(&Token::Plus) {
+            return self.unary();
+        }
+        self.operand()
+    }
+
+    fn operand(&mut self) -> Result<Expr, FilterError> {
+        let position = self.position();
+        let token = self
+            .advance()
+            .ok_or_else(|| FilterError::syntax(position, "unexpected end of expression"))?;
+
+        let expr = match token {
+            Token::LParen => {
+                let expr = self.or_expr()?;
+                self.expect(&Token::RParen, "')'")?;
+                expr
+            }
+            Token::String(text) => Expr::Literal(Literal::String(text)),
+            Token::Integer(value) => Expr::Literal(Literal::Int(value)),
+            Token::Float(value) => Expr::Literal(Literal::Float(value)),
+            Token::Ident(word) if word.eq_ignore_ascii_case("TRUE") => {
+                Expr::Literal(Literal::Bool(true))
+            }
+            Token::Ident(word) if word.eq_ignore_ascii_case("FALSE") => {
+                Expr::Literal(Literal::Bool(false))
+            }
+            Token::Ident(word) if word.eq_ignore_ascii_case("NULL") => Expr::Literal(Literal::Null),
+            Token::Ident(word) if is_reserved(&word) => {
+                return Err(FilterError::syntax(
+                    position,
+                    format!("unexpected keyword {}", word.to_ascii_uppercase()),
+                ));
+            }
+            Token::Ident(name) | Token::QuotedIdent(name) => {
+                Expr::Property(self.property(name, position)?)
+            }
+            _ => {
+                return Err(FilterError::syntax(
+                    position,
+                    "expected a value or property",
+                ));
+            }
+        };
+        Ok(expr)
+    }
+
+    /// Resolves `name` and any `.segment` suffixes into a property reference
+    fn property(&mut self, name: String, position: usize) -> Result<Property, FilterError> {
+        let mut segments = Vec::new();
+        while self.eat(&Token::Dot) {
+            let segment = match self.advance() {
+                Some(Token::Ident(segment) | Token::QuotedIdent(segment)) => {
+                    PathSegment::Key(segment)
+                }
+                Some(Token::Integer(index)) => usize::try_from(index)
+                    .map(PathSegment::Index)
+                    .map_err(|_| self.error("invalid array index"))?,
+                _ => {
+                    self.index -= 1;
+                    return Err(self.error("expected a name after '.'"));
+                }
+            };
+            segments.push(segment);
+        }
+
+        let single_name = |segments: Vec<PathSegment>| match segments.as_slice() {
+            [PathSegment::Key(key)] => Some(key.clone()),
+            _ => None,
+        };
+
+        match name.to_ascii_lowercase().as_str() {
+            "body" => Ok(Property::Body(segments)),
+            _ if segments.is_empty() => Ok(Property::User(name)),
+            "sys" => {
+                let key = single_name(segments)
+                    .ok_or_else(|| FilterError::syntax(position, "expected sys.<PropertyName>"))?;
+                SystemProperty::from_name(&key)
+                    .map(Property::System)
+                    .ok_or_else(|| {
+                        FilterError::syntax(position, format!("unknown system property '{key}'"))
+                    })
+            }
+            "user" => single_name(segments)
+                .map(Property::User)
+                .ok_or_else(|| FilterError::syntax(position, "expected user.<PropertyName>")),
+            _ => Err(FilterError::syntax(
+                position,
+                format!("unknown property scope '{name}', expected sys, user or body"),
+            )),
+        }
+    }
+
+    fn string_literal(&mut self, description: &str) -> Result<String, FilterError> {
+        match self.peek() {
+            Some(Token::String(text)) => {
+                let text = text.clone();
+                self.index += 1;
+                Ok(text)
+            }
+            _ => Err(self.error(format!("expected {description}"))),
+        }
+    }
+}
+
+fn is_reserved(word: &str) -> bool {
+    const RESERVED: [&str; 8] = ["AND", "OR", "NOT", "IS", "LIKE", "IN", "ESCAPE", "EXISTS"];
+    RESERVED
+        .iter()
+        .any(|reserved| word.eq_ignore_ascii_case(reserved))
+}
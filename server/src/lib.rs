@@ -13,6 +13,9 @@
 //! - **[`producer`]** and **[`consumer`]** - Message production and consumption
 //! - **[`bulk_operations`]** - Efficient bulk message processing
 //! - **[`model`]** - Data models and message representations
+//! - **[`decoding`]** - Decoders for compressed and binary-serialized message bodies
+//! - **[`filter`]** - Service Bus SQL filter expressions evaluated against messages
+//! - **[`export`]** - Lossless message exports to NDJSON, JSON and CSV files
 //! - **[`taskpool`]** - Thread pool management for concurrent operations
 //! - **[`utils`]** - Utility functions and helpers
 //!
@@ -130,7 +133,10 @@ pub mod auth;
 pub mod bulk_operations;
 pub mod common;
 pub mod consumer;
+pub mod decoding;
 pub mod encryption;
+pub mod export;
+pub mod filter;
 pub mod model;
 pub mod producer;
 pub mod service_bus_manager;
@@ -18,6 +18,11 @@
 //! - **Raw Text Messages** - Support for plain text and binary message content
 //! - **Lossless Conversion** - Preserves original message data during transformation
 //!
+//! ### Message Properties
+//! - **System Properties** - CorrelationId, SessionId, ContentType, Subject, ReplyTo, To,
+//!   TTL, scheduled enqueue time, lock expiry and dead-letter details
+//! - **Application Properties** - Typed user-defined properties via [`PropertyValue`]
+//!
 //! ### Message State Management
 //! - **State Tracking** - Comprehensive message state representation
 //! - **State Transitions** - Support for all Azure Service Bus message states
@@ -69,7 +74,7 @@
 //! ```
 //!
 //! ### BodyData
-//! Flexible message body representation supporting both JSON and raw text:
+//! Flexible message body representation supporting JSON, raw text and binary payloads:
 //!
 //! ```no_run
 //! use quetty_server::model::BodyData;
@@ -158,9 +163,14 @@
 //! - `Send` and `Sync` for thread safety
 //! - `Debug` for development and logging
 
+mod properties;
+
+pub use properties::{MessageProperties, PropertyValue};
+
 use azservicebus::prelude::ServiceBusPeekedMessage;
 use azservicebus::primitives::service_bus_message_state::ServiceBusMessageState;
 use azure_core::time::OffsetDateTime;
+use base64::{Engine as _, engine::general_purpose};
 use serde::Serialize;
 use serde::ser::Serializer;
 use serde_json::Value;
@@ -181,6 +191,7 @@ use std::convert::TryFrom;
 /// - `delivery_count` - Number of delivery attempts for this message
 /// - `state` - Current state of the message in the queue
 /// - `body` - Message content (JSON or raw text)
+/// - `properties` - Broker system properties and user application properties
 ///
 /// # Examples
 ///
@@ -236,7 +247,7 @@ use std::convert::TryFrom;
 ///
 /// ## JSON Serialization
 /// ```no_run
-/// use quetty_server::model::{MessageModel, MessageState, BodyData};
+/// use quetty_server::model::{MessageModel, MessageProperties, MessageState, BodyData};
 /// use serde_json::json;
 ///
 /// let message = MessageModel {
@@ -246,6 +257,8 @@ use std::convert::TryFrom;
 ///     delivery_count: 0,
 ///     state: MessageState::Active,
 ///     body: BodyData::ValidJson(json!({"type": "test", "data": "value"})),
+///     raw_body: br#"{"type": "test", "data": "value"}"#.to_vec(),
+///     properties: MessageProperties::default(),
 /// };
 ///
 /// // Serialize to JSON for export or API responses
@@ -279,6 +292,13 @@ pub struct MessageModel {
     pub state: MessageState,
     /// Message content, either parsed JSON or raw text
     pub body: BodyData,
+    /// The body exactly as received, sent again on resend and copy so JSON
+    /// bodies keep their key order, whitespace and number precision
+    #[serde(skip)]
+    pub raw_body: Vec<u8>,
+    /// System properties (correlation, session, routing, TTL, dead-letter info)
+    /// and user-defined application properties
+    pub properties: MessageProperties,
 }
 
 /// Represents the current state of a message within Azure Service Bus.
@@ -465,10 +485,25 @@ impl MessageModel {
             enqueued_at,
             delivery_count,
             state,
+            raw_body: body.to_bytes(),
             body,
+            properties: MessageProperties::default(),
         }
     }
 
+    /// Attaches broker and application properties to the message.
+    pub fn with_properties(mut self, properties: MessageProperties) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    /// Keeps the body bytes as received, which [`MessageModel::new`] can only
+    /// derive from the parsed body.
+    pub fn with_raw_body(mut self, raw_body: Vec<u8>) -> Self {
+        self.raw_body = raw_body;
+        self
+    }
+
     pub fn try_convert_messages_collect(
         messages: Vec<ServiceBusPeekedMessage>,
     ) -> Vec<MessageModel> {
@@ -483,32 +518,25 @@ impl MessageModel {
         valid_models
     }
 
-    fn parse_message_body(msg: &ServiceBusPeekedMessage) -> Result<BodyData, MessageModelError> {
-        let bytes = match msg.body() {
-            Ok(body) => body,
-            Err(_) => return Err(MessageModelError::MissingMessageBody),
-        };
-
-        match serde_json::from_slice::<Value>(bytes) {
-            Ok(val) => Ok(BodyData::ValidJson(val)),
-            Err(_) => Ok(BodyData::RawString(
-                String::from_utf8_lossy(bytes).into_owned(),
-            )),
-        }
+    fn message_body(msg: &ServiceBusPeekedMessage) -> Result<&[u8], MessageModelError> {
+        msg.body()
+            .map_err(|_| MessageModelError::MissingMessageBody)
     }
 }
 
-/// Flexible representation of message body content supporting both JSON and raw text.
+/// Flexible representation of message body content supporting JSON, raw text and binary payloads.
 ///
 /// This enum handles the diverse nature of Service Bus message content, providing
-/// type-safe handling for both structured JSON data and plain text messages.
-/// The parser attempts JSON deserialization first, falling back to raw string
-/// storage to ensure no message content is lost.
+/// type-safe handling for structured JSON data, plain text messages and binary
+/// payloads such as Avro or gzip. The parser attempts JSON deserialization first,
+/// then UTF-8 text, and keeps anything else as the original bytes so that no
+/// message content is lost.
 ///
 /// # Variants
 ///
 /// - **ValidJson** - Successfully parsed JSON content stored as `serde_json::Value`
 /// - **RawString** - Plain text or unparseable content stored as UTF-8 string
+/// - **Binary** - Content that is not valid UTF-8, stored as the original bytes
 ///
 /// # Examples
 ///
@@ -587,6 +615,9 @@ impl MessageModel {
 ///                 process_plain_text_message(text);
 ///             }
 ///         }
+///         BodyData::Binary(bytes) => {
+///             println!("Processing binary message of {} bytes", bytes.len());
+///         }
 ///     }
 /// }
 /// ```
@@ -605,6 +636,11 @@ impl MessageModel {
 /// let text_body = BodyData::RawString("Hello, World!".to_string());
 /// let text_serialized = to_string(&text_body)?;
 /// assert_eq!(text_serialized, r#""Hello, World!""#);
+///
+/// // Binary bodies serialize as base64 strings
+/// let binary_body = BodyData::Binary(vec![0xff, 0x00, 0x10]);
+/// let binary_serialized = to_string(&binary_body)?;
+/// assert_eq!(binary_serialized, r#""/wAQ""#);
 /// ```
 ///
 /// ## Content Type Detection
@@ -622,6 +658,7 @@ impl MessageModel {
 ///             let line_count = text.lines().count();
 ///             format!("Text message: {} characters, {} lines", char_count, line_count)
 ///         }
+///         BodyData::Binary(bytes) => format!("Binary message: {} bytes", bytes.len()),
 ///     }
 /// }
 /// ```
@@ -629,24 +666,13 @@ impl MessageModel {
 /// ## Creating from Raw Data
 /// ```no_run
 /// use quetty_server::model::BodyData;
-/// use serde_json::{from_str, Value};
-///
-/// fn create_body_from_bytes(data: &[u8]) -> BodyData {
-///     // Try to parse as UTF-8 first
-///     match std::str::from_utf8(data) {
-///         Ok(text) => {
-///             // Try to parse as JSON
-///             match from_str::<Value>(text) {
-///                 Ok(json) => BodyData::ValidJson(json),
-///                 Err(_) => BodyData::RawString(text.to_string()),
-///             }
-///         }
-///         Err(_) => {
-///             // Fallback to lossy UTF-8 conversion for binary data
-///             BodyData::RawString(String::from_utf8_lossy(data).into_owned())
-///         }
-///     }
-/// }
+///
+/// // JSON, then UTF-8 text, then the original bytes
+/// let body = BodyData::from_bytes(&[0x1f, 0x8b, 0x08, 0x00]);
+/// assert!(matches!(body, BodyData::Binary(_)));
+///
+/// // The bytes can be recovered for resending
+/// assert_eq!(body.to_bytes(), vec![0x1f, 0x8b, 0x08, 0x00]);
 /// ```
 ///
 /// # Performance Notes
@@ -675,6 +701,44 @@ pub enum BodyData {
     /// This preserves all message data even for non-JSON content
     /// or malformed JSON that couldn't be parsed.
     RawString(String),
+
+    /// Binary content that is not valid UTF-8.
+    ///
+    /// Contains the original message bytes unchanged, so binary payloads
+    /// such as Avro records or gzip archives survive a resend intact.
+    Binary(Vec<u8>),
+}
+
+impl BodyData {
+    /// Parses a message body, trying JSON first, then UTF-8 text, and
+    /// keeping the original bytes when neither applies.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        if let Ok(val) = serde_json::from_slice::<Value>(bytes) {
+            return BodyData::ValidJson(val);
+        }
+        match std::str::from_utf8(bytes) {
+            Ok(text) => BodyData::RawString(text.to_string()),
+            Err(_) => BodyData::Binary(bytes.to_vec()),
+        }
+    }
+
+    /// Returns the body as bytes.
+    ///
+    /// JSON bodies are re-serialized in compact form; text and binary bodies
+    /// are returned as stored. Use [`MessageModel::raw_body`] to send a
+    /// received message again unchanged.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            BodyData::ValidJson(val) => serde_json::to_vec(val).unwrap_or_default(),
+            BodyData::RawString(s) => s.as_bytes().to_vec(),
+            BodyData::Binary(bytes) => bytes.clone(),
+        }
+    }
+
+    /// Whether the body is binary content that cannot be edited as text
+    pub fn is_binary(&self) -> bool {
+        matches!(self, BodyData::Binary(_))
+    }
 }
 
 impl Serialize for BodyData {
@@ -685,6 +749,9 @@ impl Serialize for BodyData {
         match self {
             BodyData::ValidJson(val) => val.serialize(serializer),
             BodyData::RawString(s) => serializer.serialize_str(s),
+            BodyData::Binary(bytes) => {
+                serializer.serialize_str(&general_purpose::STANDARD.encode(bytes))
+            }
         }
     }
 }
@@ -706,7 +773,8 @@ impl TryFrom<ServiceBusPeekedMessage> for MessageModel {
             .ok_or(MessageModelError::MissingMessageId)?
             .to_string();
 
-        let body = MessageModel::parse_message_body(&msg)?;
+        let raw_body = MessageModel::message_body(&msg)?.to_vec();
+        let body = BodyData::from_bytes(&raw_body);
 
         let delivery_count = msg
             .delivery_count()
@@ -719,6 +787,8 @@ impl TryFrom<ServiceBusPeekedMessage> for MessageModel {
             ServiceBusMessageState::Scheduled => MessageState::Scheduled,
         };
 
+        let properties = MessageProperties::from_peeked_message(&msg);
+
         Ok(Self {
             sequence: msg.sequence_number(),
             id,
@@ -726,6 +796,38 @@ impl TryFrom<ServiceBusPeekedMessage> for MessageModel {
             delivery_count,
             state,
             body,
+            raw_body,
+            properties,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_parsing_keeps_binary_payloads() {
+        assert!(matches!(
+            BodyData::from_bytes(br#"{"id": 1}"#),
+            BodyData::ValidJson(_)
+        ));
+        assert_eq!(
+            BodyData::from_bytes(b"plain text"),
+            BodyData::RawString("plain text".to_string())
+        );
+
+        // gzip header followed by bytes that are not valid UTF-8
+        let gzip = vec![0x1f, 0x8b, 0x08, 0x00, 0xff, 0xfe];
+        let body = BodyData::from_bytes(&gzip);
+        assert_eq!(body, BodyData::Binary(gzip.clone()));
+        assert!(body.is_binary());
+        assert_eq!(body.to_bytes(), gzip);
+    }
+
+    #[test]
+    fn test_binary_body_serializes_as_base64() {
+        let body = BodyData::Binary(vec![0xff, 0x00, 0x10]);
+        assert_eq!(serde_json::to_string(&body).unwrap(), r#""/wAQ""#);
+    }
+}
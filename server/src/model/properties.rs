@@ -0,0 +1,475 @@
+//! Broker and application properties carried by Service Bus messages.
+//!
+//! [`MessageProperties`] collects the system properties that Azure Service Bus
+//! attaches to a message (correlation, session, routing, TTL, lock and
+//! dead-letter information) together with the user-defined application
+//! properties. Application property values are represented by
+//! [`PropertyValue`], which keeps the AMQP type information that matters for
+//! display and resend.
+
+use azservicebus::ServiceBusReceivedMessage;
+use azservicebus::prelude::ServiceBusPeekedMessage;
+use azure_core::time::{Duration, OffsetDateTime};
+use fe2o3_amqp_types::messaging::{ApplicationProperties, annotations::AnnotationKey};
+use fe2o3_amqp_types::primitives::{SimpleValue, Timestamp, Uuid as AmqpUuid, Value as AmqpValue};
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Message annotation holding the lock expiry for peek-locked messages.
+const LOCKED_UNTIL_ANNOTATION: &str = "x-opt-locked-until";
+
+/// System and application properties of a Service Bus message.
+///
+/// All system properties are optional because the broker only populates the
+/// ones the sender set (or, for lock and dead-letter data, the ones that apply
+/// to the message's current state).
+#[derive(Serialize, Clone, PartialEq, Debug, Default)]
+pub struct MessageProperties {
+    /// Correlation identifier used to relate a reply to its request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// Session the message belongs to on session-enabled entities
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// MIME content type of the body
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Application specific label (a.k.a. `Label` in older SDKs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Address of the entity replies should be sent to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+    /// Intended destination of the message (used for auto-forward chains)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Time-to-live after which the message expires
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_duration_secs"
+    )]
+    pub time_to_live: Option<Duration>,
+    /// UTC time at which a scheduled message becomes active
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "azure_core::time::iso8601::option"
+    )]
+    pub scheduled_enqueue_time: Option<OffsetDateTime>,
+    /// UTC time until which the message is locked by a receiver
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "azure_core::time::iso8601::option"
+    )]
+    pub locked_until: Option<OffsetDateTime>,
+    /// Reason the message was dead-lettered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dead_letter_reason: Option<String>,
+    /// Detailed description of why the message was dead-lettered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dead_letter_error_description: Option<String>,
+    /// Entity the message was dead-lettered from (auto-forward scenarios)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dead_letter_source: Option<String>,
+    /// User-defined application properties, ordered by key
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub application_properties: BTreeMap<String, PropertyValue>,
+}
+
+/// Builds [`MessageProperties`] from any SDK message type; peeked and
+/// received messages expose the same accessors but share no trait.
+macro_rules! properties_from_message {
+    ($msg:expr, $locked_until:expr) => {{
+        let msg = $msg;
+        // The SDK reports an unset schedule as `DateTime.MinValue`
+        let scheduled_enqueue_time =
+            Some(msg.scheduled_enqueue_time()).filter(|time| *time > OffsetDateTime::UNIX_EPOCH);
+
+        MessageProperties {
+            correlation_id: msg.correlation_id().map(|id| id.into_owned()),
+            session_id: msg.session_id().map(String::from),
+            content_type: msg.content_type().map(String::from),
+            subject: msg.subject().map(String::from),
+            reply_to: msg.reply_to().map(String::from),
+            to: msg.to().map(String::from),
+            time_to_live: msg
+                .time_to_live()
+                .and_then(|ttl| Duration::try_from(ttl).ok()),
+            scheduled_enqueue_time,
+            locked_until: $locked_until,
+            dead_letter_reason: msg.dead_letter_reason().map(String::from),
+            dead_letter_error_description: msg.dead_letter_error_description().map(String::from),
+            dead_letter_source: msg.dead_letter_source().map(String::from),
+            application_properties: msg
+                .application_properties()
+                .map(convert_application_properties)
+                .unwrap_or_default(),
+        }
+    }};
+}
+
+impl MessageProperties {
+    /// Extracts all system and application properties from a peeked message.
+    pub fn from_peeked_message(msg: &ServiceBusPeekedMessage) -> Self {
+        let locked_until = msg
+            .raw_amqp_message()
+            .message_annotations
+            .as_ref()
+            .and_then(|annotations| annotations.get(&LOCKED_UNTIL_ANNOTATION as &dyn AnnotationKey))
+            .and_then(|value| match value {
+                AmqpValue::Timestamp(timestamp) => Some(timestamp_to_datetime(timestamp)),
+                _ => None,
+            });
+
+        properties_from_message!(msg, locked_until)
+    }
+
+    /// Extracts all system and application properties from a received (locked) message.
+    pub fn from_received_message(msg: &ServiceBusReceivedMessage) -> Self {
+        properties_from_message!(msg, msg.locked_until())
+    }
+
+    /// Returns `true` when the message carries any dead-letter information.
+    pub fn is_dead_lettered(&self) -> bool {
+        self.dead_letter_reason.is_some()
+            || self.dead_letter_error_description.is_some()
+            || self.dead_letter_source.is_some()
+    }
+
+    /// Lists the populated system properties as display name / value pairs,
+    /// in the order they are usually shown in Service Bus tooling.
+    pub fn system_property_entries(&self) -> Vec<(&'static str, String)> {
+        let mut entries = Vec::new();
+        let mut push = |name: &'static str, value: Option<String>| {
+            if let Some(value) = value {
+                entries.push((name, value));
+            }
+        };
+
+        push("CorrelationId", self.correlation_id.clone());
+        push("SessionId", self.session_id.clone());
+        push("ContentType", self.content_type.clone());
+        push("Subject", self.subject.clone());
+        push("ReplyTo", self.reply_to.clone());
+        push("To", self.to.clone());
+        push("TimeToLive", self.time_to_live.map(format_duration));
+        push(
+            "ScheduledEnqueueTime",
+            self.scheduled_enqueue_time.map(format_datetime),
+        );
+        push("LockedUntil", self.locked_until.map(format_datetime));
+        push("DeadLetterReason", self.dead_letter_reason.clone());
+        push(
+            "DeadLetterErrorDescription",
+            self.dead_letter_error_description.clone(),
+        );
+        push("DeadLetterSource", self.dead_letter_source.clone());
+
+        entries
+    }
+}
+
+/// Typed value of an application property.
+///
+/// AMQP allows a wide range of primitive types in the application properties
+/// map. Integer widths are widened to `i64`, floats to `f64`, and types with no
+/// direct counterpart (symbols, chars, decimals, binary) are kept as their
+/// string representation.
+#[derive(Clone, PartialEq, Debug)]
+pub enum PropertyValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    String(String),
+    Uuid(uuid::Uuid),
+    Timestamp(OffsetDateTime),
+}
+
+impl PropertyValue {
+    /// Short type name used when displaying or editing typed properties.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            PropertyValue::Null => "null",
+            PropertyValue::Bool(_) => "bool",
+            PropertyValue::Int(_) => "int",
+            PropertyValue::Double(_) => "double",
+            PropertyValue::String(_) => "string",
+            PropertyValue::Uuid(_) => "uuid",
+            PropertyValue::Timestamp(_) => "timestamp",
+        }
+    }
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyValue::Null => write!(f, "null"),
+            PropertyValue::Bool(value) => write!(f, "{value}"),
+            PropertyValue::Int(value) => write!(f, "{value}"),
+            PropertyValue::Double(value) => write!(f, "{value}"),
+            PropertyValue::String(value) => write!(f, "{value}"),
+            PropertyValue::Uuid(value) => write!(f, "{value}"),
+            PropertyValue::Timestamp(value) => write!(f, "{}", format_datetime(*value)),
+        }
+    }
+}
+
+impl Serialize for PropertyValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            PropertyValue::Null => serializer.serialize_none(),
+            PropertyValue::Bool(value) => serializer.serialize_bool(*value),
+            PropertyValue::Int(value) => serializer.serialize_i64(*value),
+            PropertyValue::Double(value) => serializer.serialize_f64(*value),
+            PropertyValue::String(value) => serializer.serialize_str(value),
+            PropertyValue::Uuid(value) => serializer.collect_str(value),
+            PropertyValue::Timestamp(value) => {
+                azure_core::time::iso8601::serialize(value, serializer)
+            }
+        }
+    }
+}
+
+/// Deserializes the untyped JSON form produced by [`Serialize`].
+///
+/// UUIDs and timestamps serialize as plain strings, so they come back as
+/// [`PropertyValue::String`]; all other types round-trip exactly.
+impl<'de> Deserialize<'de> for PropertyValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PropertyValueVisitor;
+
+        impl Visitor<'_> for PropertyValueVisitor {
+            type Value = PropertyValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a null, boolean, number or string property value")
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(PropertyValue::Null)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(PropertyValue::Null)
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(PropertyValue::Bool(value))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(PropertyValue::Int(value))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(match i64::try_from(value) {
+                    Ok(value) => PropertyValue::Int(value),
+                    Err(_) => PropertyValue::String(value.to_string()),
+                })
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                Ok(PropertyValue::Double(value))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(PropertyValue::String(value.to_string()))
+            }
+
+            fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+                Ok(PropertyValue::String(value))
+            }
+        }
+
+        deserializer.deserialize_any(PropertyValueVisitor)
+    }
+}
+
+impl From<&SimpleValue> for PropertyValue {
+    fn from(value: &SimpleValue) -> Self {
+        match value {
+            SimpleValue::Null => PropertyValue::Null,
+            SimpleValue::Bool(v) => PropertyValue::Bool(*v),
+            SimpleValue::Ubyte(v) => PropertyValue::Int(i64::from(*v)),
+            SimpleValue::Ushort(v) => PropertyValue::Int(i64::from(*v)),
+            SimpleValue::Uint(v) => PropertyValue::Int(i64::from(*v)),
+            SimpleValue::Ulong(v) => match i64::try_from(*v) {
+                Ok(v) => PropertyValue::Int(v),
+                Err(_) => PropertyValue::String(v.to_string()),
+            },
+            SimpleValue::Byte(v) => PropertyValue::Int(i64::from(*v)),
+            SimpleValue::Short(v) => PropertyValue::Int(i64::from(*v)),
+            SimpleValue::Int(v) => PropertyValue::Int(i64::from(*v)),
+            SimpleValue::Long(v) => PropertyValue::Int(*v),
+            SimpleValue::Float(v) => PropertyValue::Double(f64::from(v.into_inner())),
+            SimpleValue::Double(v) => PropertyValue::Double(v.into_inner()),
+            SimpleValue::Char(v) => PropertyValue::String(v.to_string()),
+            SimpleValue::Timestamp(v) => PropertyValue::Timestamp(timestamp_to_datetime(v)),
+            SimpleValue::Uuid(v) => PropertyValue::Uuid(uuid::Uuid::from_bytes(*v.as_inner())),
+            SimpleValue::Binary(v) => PropertyValue::String(
+                v.iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<String>(),
+            ),
+            SimpleValue::String(v) => PropertyValue::String(v.clone()),
+            SimpleValue::Symbol(v) => PropertyValue::String(v.0.clone()),
+            other => PropertyValue::String(format!("{other:?}")),
+        }
+    }
+}
+
+impl From<PropertyValue> for SimpleValue {
+    fn from(value: PropertyValue) -> Self {
+        match value {
+            PropertyValue::Null => SimpleValue::Null,
+            PropertyValue::Bool(v) => SimpleValue::from(v),
+            PropertyValue::Int(v) => SimpleValue::from(v),
+            PropertyValue::Double(v) => SimpleValue::from(v),
+            PropertyValue::String(v) => SimpleValue::from(v),
+            PropertyValue::Uuid(v) => SimpleValue::from(AmqpUuid::from(v.into_bytes())),
+            PropertyValue::Timestamp(v) => {
+                let millis = (v - OffsetDateTime::UNIX_EPOCH).whole_milliseconds() as i64;
+                SimpleValue::from(Timestamp::from_milliseconds(millis))
+            }
+        }
+    }
+}
+
+fn convert_application_properties(
+    properties: &ApplicationProperties,
+) -> BTreeMap<String, PropertyValue> {
+    properties
+        .iter()
+        .map(|(key, value)| (key.clone(), PropertyValue::from(value)))
+        .collect()
+}
+
+fn timestamp_to_datetime(timestamp: &Timestamp) -> OffsetDateTime {
+    OffsetDateTime::UNIX_EPOCH + Duration::milliseconds(timestamp.milliseconds())
+}
+
+fn format_datetime(value: OffsetDateTime) -> String {
+    azure_core::time::to_rfc3339(&value)
+}
+
+fn format_duration(value: Duration) -> String {
+    let total_secs = value.whole_seconds();
+    let days = total_secs / 86_400;
+    let hours = (total_secs % 86_400) / 3_600;
+    let minutes = (total_secs % 3_600) / 60;
+    let seconds = total_secs % 60;
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+fn serialize_duration_secs<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(duration) => serializer.serialize_i64(duration.whole_seconds()),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_value_integers_are_widened() {
+        assert_eq!(
+            PropertyValue::from(&SimpleValue::Ushort(42)),
+            PropertyValue::Int(42)
+        );
+        assert_eq!(
+            PropertyValue::from(&SimpleValue::Ulong(u64::MAX)),
+            PropertyValue::String(u64::MAX.to_string())
+        );
+    }
+
+    #[test]
+    fn test_property_value_round_trip() {
+        let values = vec![
+            PropertyValue::Null,
+            PropertyValue::Bool(true),
+            PropertyValue::Int(-7),
+            PropertyValue::Double(1.5),
+            PropertyValue::String("tenant-a".to_string()),
+            PropertyValue::Uuid(uuid::Uuid::new_v4()),
+            PropertyValue::Timestamp(
+                OffsetDateTime::UNIX_EPOCH + Duration::milliseconds(1_700_000_000_123),
+            ),
+        ];
+
+        for value in values {
+            let amqp = SimpleValue::from(value.clone());
+            assert_eq!(PropertyValue::from(&amqp), value);
+        }
+    }
+
+    #[test]
+    fn test_property_value_json_round_trip() {
+        let json =
+            serde_json::json!({"flag": true, "count": 3, "ratio": 0.5, "name": "a", "none": null});
+        let values: BTreeMap<String, PropertyValue> = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(values["count"], PropertyValue::Int(3));
+        assert_eq!(values["none"], PropertyValue::Null);
+        assert_eq!(serde_json::to_value(&values).unwrap(), json);
+    }
+
+    #[test]
+    fn test_serialization_skips_unset_properties() {
+        let mut properties = MessageProperties {
+            correlation_id: Some("corr-1".to_string()),
+            time_to_live: Some(Duration::minutes(5)),
+            ..Default::default()
+        };
+        properties
+            .application_properties
+            .insert("retries".to_string(), PropertyValue::Int(3));
+
+        let json = serde_json::to_value(&properties).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "correlation_id": "corr-1",
+                "time_to_live": 300,
+                "application_properties": {"retries": 3}
+            })
+        );
+    }
+
+    #[test]
+    fn test_system_property_entries_order() {
+        let properties = MessageProperties {
+            subject: Some("order.created".to_string()),
+            session_id: Some("s-1".to_string()),
+            time_to_live: Some(Duration::days(1) + Duration::seconds(61)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            properties.system_property_entries(),
+            vec![
+                ("SessionId", "s-1".to_string()),
+                ("Subject", "order.created".to_string()),
+                ("TimeToLive", "1d 00:01:01".to_string()),
+            ]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use azservicebus::{
     ServiceBusClient, ServiceBusMessage, ServiceBusSender, ServiceBusSenderOptions,
 };
+use azure_core::time::OffsetDateTime;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -107,6 +108,55 @@ impl Producer {
         }
     }
 
+    /// Schedules messages to be enqueued at a later time.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - Vector of ServiceBusMessage instances to schedule
+    /// * `enqueue_time` - When the messages should become visible to receivers
+    ///
+    /// # Returns
+    ///
+    /// The sequence numbers of the scheduled messages, used to cancel them
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sender has been disposed or if the Service Bus operation fails
+    pub async fn schedule_messages(
+        &mut self,
+        messages: Vec<ServiceBusMessage>,
+        enqueue_time: OffsetDateTime,
+    ) -> Result<Vec<i64>, Box<dyn std::error::Error>> {
+        let mut guard = self.sender.lock().await;
+        if let Some(sender) = guard.as_mut() {
+            Ok(sender.schedule_messages(messages, enqueue_time).await?)
+        } else {
+            Err("Sender already disposed".into())
+        }
+    }
+
+    /// Cancels previously scheduled messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence_numbers` - Sequence numbers of the scheduled messages
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sender has been disposed or if the Service Bus operation fails
+    pub async fn cancel_scheduled_messages(
+        &mut self,
+        sequence_numbers: Vec<i64>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut guard = self.sender.lock().await;
+        if let Some(sender) = guard.as_mut() {
+            sender.cancel_scheduled_messages(sequence_numbers).await?;
+            Ok(())
+        } else {
+            Err("Sender already disposed".into())
+        }
+    }
+
     /// Creates a new message with the given byte array body.
     ///
     /// # Arguments
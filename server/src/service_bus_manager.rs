@@ -12,7 +12,9 @@
 //!
 //! ## Features
 //!
-//! - **Queue Management** - Create, list, and manage Service Bus queues
+//! - **Queue Management** - Create, update, delete, list, and manage Service Bus queues
+//! - **Topics** - Discover topics, peek their subscriptions and send to topics
+//! - **Subscription Rules** - List, create, edit and delete rules, and test them locally
 //! - **Message Operations** - Send, receive, and bulk process messages
 //! - **Authentication** - Multiple auth methods (Device Code, Client Credentials, Connection String)
 //! - **Resource Discovery** - Discover Azure subscriptions, resource groups, and namespaces
@@ -45,7 +47,13 @@ pub use self::azure_management_client::{
 pub use self::commands::ServiceBusCommand;
 pub use self::errors::{ServiceBusError, ServiceBusResult};
 pub use self::manager::ServiceBusManager;
+pub use self::queue_details::{
+    EntityStatus, QueueDetails, QueueSettings, format_iso8601_duration, parse_iso8601_duration,
+};
 pub use self::responses::ServiceBusResponse;
+pub use self::subscription_rules::{
+    CorrelationFilter, DEFAULT_RULE_NAME, RuleFilter, RuleMatcher, SubscriptionRule,
+};
 pub use self::types::*;
 
 /// Azure Management Client for resource discovery and management
@@ -62,10 +70,14 @@ pub mod errors;
 pub mod manager;
 /// Producer management for message sending
 pub mod producer_manager;
+/// Runtime and configuration properties of queues and subscriptions
+pub mod queue_details;
 /// Queue statistics and monitoring services
 pub mod queue_statistics_service;
 /// Response types for Service Bus operations
 pub mod responses;
+/// Filters and actions of topic subscription rules, with local evaluation
+pub mod subscription_rules;
 /// Core types and data structures
 pub mod types;
 
@@ -326,12 +338,11 @@ impl AzureAdConfig {
         };
 
         // If device code flow is configured, try to use UI-integrated auth first
-        if self.auth_method == "device_code" {
-            if let Ok(ui_provider) = create_auth_provider(None) {
-                if let Ok(token) = get_azure_ad_token_with_auth(&ui_provider).await {
-                    return Ok(token);
-                }
-            }
+        if self.auth_method == "device_code"
+            && let Ok(ui_provider) = create_auth_provider(None)
+            && let Ok(token) = get_azure_ad_token_with_auth(&ui_provider).await
+        {
+            return Ok(token);
         }
 
         // For connection string authentication, we cannot get Azure AD tokens
@@ -388,6 +399,40 @@ impl AzureAdConfig {
         Ok(queues)
     }
 
+    /// Lists all topics in the configured namespace together with their
+    /// subscriptions using Azure AD authentication.
+    ///
+    /// # Arguments
+    ///
+    /// * `http_client` - HTTP client for making API requests
+    ///
+    /// # Returns
+    ///
+    /// A vector of topics with their subscription names
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Authentication fails
+    /// - Azure Management API request fails
+    /// - Required configuration (subscription, resource group, namespace) is missing
+    pub async fn list_topics_azure_ad(
+        &self,
+        http_client: &reqwest::Client,
+    ) -> Result<Vec<TopicInfo>, Box<dyn std::error::Error>> {
+        let token = self.get_azure_ad_token(http_client).await?;
+        let client = AzureManagementClient::new(http_client.clone());
+        let topics = client
+            .list_topics_with_subscriptions(
+                &token,
+                &self.subscription_id()?,
+                &self.resource_group()?,
+                &self.namespace()?,
+            )
+            .await?;
+        Ok(topics)
+    }
+
     /// Lists all Service Bus namespaces in the configured resource group using Azure AD authentication.
     ///
     /// # Arguments
@@ -1,4 +1,5 @@
-use super::{AzureAdConfig, ServiceBusError};
+use super::types::{TopicInfo, parse_subscription_path};
+use super::{AzureAdConfig, QueueDetails, QueueSettings, ServiceBusError, SubscriptionRule};
 use crate::common::HttpError;
 use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
 use serde::{Deserialize, Serialize};
@@ -431,9 +432,103 @@ impl AzureManagementClient {
             ),
         };
 
+        self.list_entity_names_page(token, &url, "list_queues")
+            .await
+    }
+
+    /// List all topics in a Service Bus namespace using automatic pagination
+    pub async fn list_all_topics(
+        &self,
+        token: &str,
+        subscription_id: &str,
+        resource_group: &str,
+        namespace: &str,
+    ) -> Result<Vec<String>, ServiceBusError> {
+        let url = format!(
+            "{AZURE_MANAGEMENT_URL}/subscriptions/{subscription_id}/resourceGroups/{resource_group}/providers/Microsoft.ServiceBus/namespaces/{namespace}/topics?api-version={API_VERSION_SERVICE_BUS}"
+        );
+        self.list_all_entity_names(token, url, "list_topics").await
+    }
+
+    /// List all subscriptions of a topic using automatic pagination
+    pub async fn list_all_topic_subscriptions(
+        &self,
+        token: &str,
+        subscription_id: &str,
+        resource_group: &str,
+        namespace: &str,
+        topic: &str,
+    ) -> Result<Vec<String>, ServiceBusError> {
+        let encoded_topic = urlencoding::encode(topic);
+        let url = format!(
+            "{AZURE_MANAGEMENT_URL}/subscriptions/{subscription_id}/resourceGroups/{resource_group}/providers/Microsoft.ServiceBus/namespaces/{namespace}/topics/{encoded_topic}/subscriptions?api-version={API_VERSION_SERVICE_BUS}"
+        );
+        self.list_all_entity_names(token, url, "list_topic_subscriptions")
+            .await
+    }
+
+    /// List all topics of a namespace together with their subscriptions
+    pub async fn list_topics_with_subscriptions(
+        &self,
+        token: &str,
+        subscription_id: &str,
+        resource_group: &str,
+        namespace: &str,
+    ) -> Result<Vec<TopicInfo>, ServiceBusError> {
+        let topics = self
+            .list_all_topics(token, subscription_id, resource_group, namespace)
+            .await?;
+
+        let mut topic_infos = Vec::with_capacity(topics.len());
+        for topic in topics {
+            let subscriptions = self
+                .list_all_topic_subscriptions(
+                    token,
+                    subscription_id,
+                    resource_group,
+                    namespace,
+                    &topic,
+                )
+                .await?;
+            topic_infos.push(TopicInfo {
+                name: topic,
+                subscriptions,
+            });
+        }
+
+        Ok(topic_infos)
+    }
+
+    /// Follow `nextLink` pagination and collect entity names from every page
+    async fn list_all_entity_names(
+        &self,
+        token: &str,
+        url: String,
+        operation: &str,
+    ) -> Result<Vec<String>, ServiceBusError> {
+        let mut names = Vec::new();
+        let mut next_url = Some(url);
+
+        while let Some(url) = next_url {
+            let (mut page_names, next_link) =
+                self.list_entity_names_page(token, &url, operation).await?;
+            names.append(&mut page_names);
+            next_url = next_link;
+        }
+
+        Ok(names)
+    }
+
+    /// Fetch a single page of a Service Bus entity listing
+    async fn list_entity_names_page(
+        &self,
+        token: &str,
+        url: &str,
+        operation: &str,
+    ) -> Result<(Vec<String>, Option<String>), ServiceBusError> {
         let request = self
             .client
-            .get(&url)
+            .get(url)
             .header(AUTHORIZATION, format!("Bearer {token}"));
 
         let response = request
@@ -442,7 +537,7 @@ impl AzureManagementClient {
             .map_err(|e| ServiceBusError::ConnectionFailed(e.to_string()))?;
 
         if !response.status().is_success() {
-            return Err(ServiceBusError::from_azure_response(response, "list_queues").await);
+            return Err(ServiceBusError::from_azure_response(response, operation).await);
         }
 
         let list_response: ListResponse<serde_json::Value> = response
@@ -450,13 +545,13 @@ impl AzureManagementClient {
             .await
             .map_err(|e| ServiceBusError::ConfigurationError(e.to_string()))?;
 
-        let queue_names: Vec<String> = list_response
+        let names: Vec<String> = list_response
             .value
             .iter()
-            .filter_map(|queue| queue["name"].as_str().map(|s| s.to_string()))
+            .filter_map(|entity| entity["name"].as_str().map(|s| s.to_string()))
             .collect();
 
-        Ok((queue_names, list_response.next_link))
+        Ok((names, list_response.next_link))
     }
 
     /// List all queues using automatic pagination for large environments
@@ -492,6 +587,13 @@ impl AzureManagementClient {
         Ok(all_queues)
     }
 
+    /// List the queues of the configured namespace
+    pub async fn list_namespace_queues(&self) -> Result<Vec<String>, ServiceBusError> {
+        let token = self.get_management_api_token().await?;
+        let url = self.namespace_resource_url("queues")?;
+        self.list_all_entity_names(&token, url, "list_queues").await
+    }
+
     // ===== Queue Statistics Operations =====
 
     /// Get the actual message count for a queue from Azure Management API
@@ -502,19 +604,41 @@ impl AzureManagementClient {
 
     /// Get both active and dead-letter counts from Azure Management API
     pub async fn get_queue_counts(&self, queue_name: &str) -> Result<(u64, u64), ServiceBusError> {
-        self.get_queue_counts_with_retry(queue_name, 3).await
+        self.with_entity_retry(queue_name, 3, || self.get_queue_counts_internal(queue_name))
+            .await
     }
 
-    /// Get queue counts with retry logic for transient failures
-    async fn get_queue_counts_with_retry(
+    /// Get the runtime counts and configuration of a queue or subscription
+    pub async fn get_queue_details(
+        &self,
+        queue_name: &str,
+    ) -> Result<QueueDetails, ServiceBusError> {
+        self.with_entity_retry(queue_name, 3, || async {
+            let response_text = self
+                .get_entity_properties(queue_name, "get_queue_details")
+                .await?;
+            QueueDetails::from_management_json(queue_name, &response_text).map_err(|e| {
+                ServiceBusError::ConfigurationError(format!("Failed to parse JSON: {e}"))
+            })
+        })
+        .await
+    }
+
+    /// Run an entity request with retry logic for transient failures
+    async fn with_entity_retry<T, F, Fut>(
         &self,
         queue_name: &str,
         max_retries: u32,
-    ) -> Result<(u64, u64), ServiceBusError> {
+        request: F,
+    ) -> Result<T, ServiceBusError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, ServiceBusError>>,
+    {
         let mut last_error = None;
 
         for attempt in 0..=max_retries {
-            match self.get_queue_counts_internal(queue_name).await {
+            match request().await {
                 Ok(result) => return Ok(result),
                 Err(e) => {
                     last_error = Some(e);
@@ -562,6 +686,250 @@ impl AzureManagementClient {
     ) -> Result<(u64, u64), ServiceBusError> {
         log::debug!("Getting queue counts for: {queue_name}");
 
+        let response_text = self
+            .get_entity_properties(queue_name, "get_queue_counts")
+            .await?;
+
+        let queue_response: QueuePropertiesResponse = serde_json::from_str(&response_text)
+            .map_err(|e| {
+                ServiceBusError::ConfigurationError(format!("Failed to parse JSON: {e}"))
+            })?;
+
+        let active_raw = queue_response.properties.count_details.active_message_count;
+        let dlq_raw = queue_response
+            .properties
+            .count_details
+            .dead_letter_message_count;
+
+        let active = if active_raw < 0 { 0 } else { active_raw as u64 };
+        let dlq = if dlq_raw < 0 { 0 } else { dlq_raw as u64 };
+
+        Ok((active, dlq))
+    }
+
+    /// Fetch the properties of a queue or subscription as JSON (single attempt)
+    async fn get_entity_properties(
+        &self,
+        queue_name: &str,
+        operation: &str,
+    ) -> Result<String, ServiceBusError> {
+        let url = self.entity_url(queue_name)?;
+        log::debug!("Requesting queue properties from Azure Management API: {url}");
+
+        let request = self.client.get(&url);
+        self.send_entity_request(request, queue_name, operation)
+            .await
+    }
+
+    // ===== Queue Lifecycle Operations =====
+
+    /// Create a queue with the given settings; fails if it already exists
+    pub async fn create_queue(
+        &self,
+        queue_name: &str,
+        settings: &QueueSettings,
+    ) -> Result<QueueDetails, ServiceBusError> {
+        if parse_subscription_path(queue_name).is_some() {
+            return Err(ServiceBusError::InvalidQueueName(format!(
+                "'{queue_name}' is a subscription path; only queues can be created"
+            )));
+        }
+
+        // The PUT creates or replaces, so an existing queue must not be overwritten
+        match self.get_entity_properties(queue_name, "create_queue").await {
+            Ok(_) => {
+                return Err(ServiceBusError::InvalidConfiguration(format!(
+                    "Queue '{queue_name}' already exists"
+                )));
+            }
+            Err(ServiceBusError::AzureApiError {
+                status_code: 404, ..
+            }) => {}
+            Err(e) => return Err(e),
+        }
+
+        let mut properties = serde_json::Map::new();
+        settings.apply_to(&mut properties);
+        log::info!("Creating queue {queue_name}");
+        self.put_queue(queue_name, properties, "create_queue").await
+    }
+
+    /// Change settings of an existing queue, keeping all other properties
+    pub async fn update_queue(
+        &self,
+        queue_name: &str,
+        settings: &QueueSettings,
+    ) -> Result<QueueDetails, ServiceBusError> {
+        let current = self
+            .get_entity_properties(queue_name, "update_queue")
+            .await?;
+        let mut current: serde_json::Value = serde_json::from_str(&current).map_err(|e| {
+            ServiceBusError::ConfigurationError(format!("Failed to parse JSON: {e}"))
+        })?;
+        let mut properties = match current.get_mut("properties").map(serde_json::Value::take) {
+            Some(serde_json::Value::Object(properties)) => properties,
+            _ => serde_json::Map::new(),
+        };
+
+        // Runtime values are reported but cannot be written
+        for read_only in [
+            "countDetails",
+            "createdAt",
+            "updatedAt",
+            "accessedAt",
+            "sizeInBytes",
+            "messageCount",
+        ] {
+            properties.remove(read_only);
+        }
+        settings.apply_to(&mut properties);
+
+        log::info!("Updating queue {queue_name}");
+        self.put_queue(queue_name, properties, "update_queue").await
+    }
+
+    /// Delete a queue and all of its messages
+    pub async fn delete_queue(&self, queue_name: &str) -> Result<(), ServiceBusError> {
+        let url = self.entity_url(queue_name)?;
+        log::info!("Deleting queue {queue_name}");
+
+        let request = self.client.delete(&url);
+        self.send_entity_request(request, queue_name, "delete_queue")
+            .await
+            .map(|_| ())
+    }
+
+    // ===== Subscription Rule Operations =====
+
+    /// List the rules of a topic subscription
+    pub async fn list_subscription_rules(
+        &self,
+        subscription_path: &str,
+    ) -> Result<Vec<SubscriptionRule>, ServiceBusError> {
+        let mut rules = Vec::new();
+        let mut next_url = Some(self.rules_url(subscription_path, None)?);
+
+        while let Some(url) = next_url {
+            let request = self.client.get(&url);
+            let response_text = self
+                .send_entity_request(request, subscription_path, "list_subscription_rules")
+                .await?;
+            let (mut page, next_link) = SubscriptionRule::list_from_management_json(&response_text)
+                .map_err(|e| {
+                    ServiceBusError::ConfigurationError(format!("Failed to parse JSON: {e}"))
+                })?;
+            rules.append(&mut page);
+            next_url = next_link;
+        }
+
+        Ok(rules)
+    }
+
+    /// Create a rule on a topic subscription, or replace the rule of the same name
+    pub async fn save_subscription_rule(
+        &self,
+        subscription_path: &str,
+        rule: &SubscriptionRule,
+    ) -> Result<SubscriptionRule, ServiceBusError> {
+        let url = self.rules_url(subscription_path, Some(&rule.name))?;
+        log::info!("Saving rule {} of {subscription_path}", rule.name);
+
+        let request = self
+            .client
+            .put(&url)
+            .body(rule.to_management_json().to_string());
+        let response_text = self
+            .send_entity_request(request, subscription_path, "save_subscription_rule")
+            .await?;
+        SubscriptionRule::from_management_json(&response_text)
+            .map_err(|e| ServiceBusError::ConfigurationError(format!("Failed to parse JSON: {e}")))
+    }
+
+    /// Delete a rule of a topic subscription
+    pub async fn delete_subscription_rule(
+        &self,
+        subscription_path: &str,
+        rule_name: &str,
+    ) -> Result<(), ServiceBusError> {
+        let url = self.rules_url(subscription_path, Some(rule_name))?;
+        log::info!("Deleting rule {rule_name} of {subscription_path}");
+
+        let request = self.client.delete(&url);
+        match self
+            .send_entity_request(request, subscription_path, "delete_subscription_rule")
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(ServiceBusError::AzureApiError {
+                status_code: 404, ..
+            }) => Err(ServiceBusError::azure_api_error(
+                "delete_subscription_rule",
+                "RuleNotFound",
+                404,
+                format!("Rule '{rule_name}' not found on {subscription_path}"),
+            )),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn put_queue(
+        &self,
+        queue_name: &str,
+        properties: serde_json::Map<String, serde_json::Value>,
+        operation: &str,
+    ) -> Result<QueueDetails, ServiceBusError> {
+        let url = self.entity_url(queue_name)?;
+        let body = serde_json::json!({ "properties": properties });
+
+        let request = self.client.put(&url).body(body.to_string());
+        let response_text = self
+            .send_entity_request(request, queue_name, operation)
+            .await?;
+        QueueDetails::from_management_json(queue_name, &response_text)
+            .map_err(|e| ServiceBusError::ConfigurationError(format!("Failed to parse JSON: {e}")))
+    }
+
+    /// Management API URL of a queue or subscription
+    fn entity_url(&self, queue_name: &str) -> Result<String, ServiceBusError> {
+        // Build the management API URL with encoded entity names; subscriptions
+        // report the same count details under their topic
+        let entity_resource = match parse_subscription_path(queue_name) {
+            Some((topic, subscription)) => format!(
+                "topics/{}/subscriptions/{}",
+                urlencoding::encode(topic),
+                urlencoding::encode(subscription)
+            ),
+            None => format!("queues/{}", urlencoding::encode(queue_name)),
+        };
+        self.namespace_resource_url(&entity_resource)
+    }
+
+    /// Management API URL of the rules of a subscription, or of one of them
+    fn rules_url(
+        &self,
+        subscription_path: &str,
+        rule_name: Option<&str>,
+    ) -> Result<String, ServiceBusError> {
+        let (topic, subscription) = parse_subscription_path(subscription_path).ok_or_else(|| {
+            ServiceBusError::InvalidQueueName(format!(
+                "'{subscription_path}' is not a topic subscription; only subscriptions have rules"
+            ))
+        })?;
+
+        let mut resource = format!(
+            "topics/{}/subscriptions/{}/rules",
+            urlencoding::encode(topic),
+            urlencoding::encode(subscription)
+        );
+        if let Some(rule_name) = rule_name {
+            resource.push('/');
+            resource.push_str(&urlencoding::encode(rule_name));
+        }
+        self.namespace_resource_url(&resource)
+    }
+
+    /// Management API URL of a resource in the configured namespace
+    fn namespace_resource_url(&self, resource: &str) -> Result<String, ServiceBusError> {
         // Get configuration from Azure AD config
         let config = self.azure_ad_config.as_ref().ok_or_else(|| {
             ServiceBusError::ConfigurationError(
@@ -573,24 +941,24 @@ impl AzureManagementClient {
         let resource_group = config.resource_group()?;
         let namespace = config.namespace()?;
 
+        Ok(format!(
+            "{AZURE_MANAGEMENT_URL}/subscriptions/{subscription_id}/resourceGroups/{resource_group}/providers/Microsoft.ServiceBus/namespaces/{namespace}/{resource}?api-version={API_VERSION_SERVICE_BUS}"
+        ))
+    }
+
+    /// Authorize and send a request on an entity, returning the response body
+    async fn send_entity_request(
+        &self,
+        request: reqwest::RequestBuilder,
+        queue_name: &str,
+        operation: &str,
+    ) -> Result<String, ServiceBusError> {
         // Get access token
         let access_token = self.get_management_api_token().await?;
 
-        // Build the management API URL with encoded queue name
-        let encoded_queue_name = urlencoding::encode(queue_name);
-        let url = format!(
-            "{AZURE_MANAGEMENT_URL}/subscriptions/{subscription_id}/resourceGroups/{resource_group}/providers/Microsoft.ServiceBus/namespaces/{namespace}/queues/{encoded_queue_name}?api-version={API_VERSION_SERVICE_BUS}"
-        );
-
-        log::debug!("Requesting queue properties from Azure Management API: {url}");
-
-        let request = self
-            .client
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {access_token}"))
-            .header(CONTENT_TYPE, "application/json");
-
         let response = request
+            .header(AUTHORIZATION, format!("Bearer {access_token}"))
+            .header(CONTENT_TYPE, "application/json")
             .send()
             .await
             .map_err(|e| ServiceBusError::ConnectionFailed(e.to_string()))?;
@@ -600,36 +968,20 @@ impl AzureManagementClient {
 
             if status == 404 {
                 return Err(ServiceBusError::azure_api_error(
-                    "get_queue_counts",
+                    operation,
                     "QueueNotFound",
                     404,
                     format!("Queue not found: {queue_name}"),
                 ));
             }
 
-            return Err(ServiceBusError::from_azure_response(response, "get_queue_counts").await);
+            return Err(ServiceBusError::from_azure_response(response, operation).await);
         }
 
-        let response_text = response
+        response
             .text()
             .await
-            .map_err(|e| ServiceBusError::InternalError(format!("Failed to read response: {e}")))?;
-
-        let queue_response: QueuePropertiesResponse = serde_json::from_str(&response_text)
-            .map_err(|e| {
-                ServiceBusError::ConfigurationError(format!("Failed to parse JSON: {e}"))
-            })?;
-
-        let active_raw = queue_response.properties.count_details.active_message_count;
-        let dlq_raw = queue_response
-            .properties
-            .count_details
-            .dead_letter_message_count;
-
-        let active = if active_raw < 0 { 0 } else { active_raw as u64 };
-        let dlq = if dlq_raw < 0 { 0 } else { dlq_raw as u64 };
-
-        Ok((active, dlq))
+            .map_err(|e| ServiceBusError::InternalError(format!("Failed to read response: {e}")))
     }
 }
 
@@ -780,10 +1132,10 @@ impl AzureResourceCache {
     /// Remove expired entries from all caches
     pub fn clean_expired(&mut self) {
         // Clean subscriptions
-        if let Some(ref entry) = self.subscriptions {
-            if entry.is_expired(self.cache_ttl) {
-                self.subscriptions = None;
-            }
+        if let Some(ref entry) = self.subscriptions
+            && entry.is_expired(self.cache_ttl)
+        {
+            self.subscriptions = None;
         }
 
         // Clean resource groups
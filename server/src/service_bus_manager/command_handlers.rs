@@ -1,31 +1,58 @@
+use super::azure_management_client::AzureManagementClient;
 use super::consumer_manager::ConsumerManager;
 use super::producer_manager::ProducerManager;
+use super::queue_details::QueueSettings;
 use super::queue_statistics_service::QueueStatisticsService;
+use super::subscription_rules::SubscriptionRule;
 use super::types::{QueueInfo, QueueType};
 
 use crate::bulk_operations::BulkOperationResult;
-use crate::bulk_operations::{BulkOperationHandler, MessageIdentifier, types::BatchConfig};
+use crate::bulk_operations::{
+    BulkOperationHandler, ExportProgress, ExportScope, ImportOptions, ImportProgress,
+    MessageIdentifier, NonTargetReleaser, PurgeCutoff, PurgeProgress, QueueExporter, QueueImporter,
+    QueuePurger, QueueSearcher, SearchProgress, types::BatchConfig,
+};
 use crate::consumer::Consumer;
+use crate::export::ExportFormat;
+use crate::filter::SqlFilter;
+use crate::model::MessageProperties;
 use crate::service_bus_manager::{
-    errors::ServiceBusError, responses::ServiceBusResponse, types::MessageData,
+    errors::ServiceBusError,
+    responses::ServiceBusResponse,
+    types::{DeferredMessageAction, MessageData, ResendOptions, TargetNamespace},
 };
+use azservicebus::{ServiceBusClient, ServiceBusClientOptions};
+use azure_core::time::OffsetDateTime;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
 
 /// Parameters for target message processing in bulk send
 #[derive(Debug)]
 struct TargetMessageParams<'a> {
     consumer: &'a mut Consumer,
     msg: &'a azservicebus::ServiceBusReceivedMessage,
-    is_dlq_operation: bool,
-    should_delete_source: bool,
-    message_bytes: &'a mut Vec<Vec<u8>>,
     successful_count: &'a mut usize,
     failed_count: &'a mut usize,
 }
 
+/// Parameters for forwarding collected target messages to the target queue
+struct ForwardTargetsParams<'a> {
+    consumer: &'a mut Consumer,
+    targets: Vec<azservicebus::ServiceBusReceivedMessage>,
+    /// Releases the originals of copies, which stay in the source
+    non_targets: &'a NonTargetReleaser,
+    producers: &'a Mutex<ProducerManager>,
+    target_queue: &'a str,
+    should_delete_source: bool,
+    repeat_count: usize,
+    resend_options: &'a ResendOptions,
+}
+
 /// Parameters for bulk send result finalization
 #[derive(Debug)]
 struct BulkSendResultParams {
@@ -42,6 +69,9 @@ const ERROR_INDIVIDUAL_MSG_OPERATIONS: &str =
     "Individual message operations by ID require message to be received first";
 const ERROR_BULK_OPERATIONS: &str = "Bulk operations require message to be received first";
 
+/// How long to wait for the next available session when listing sessions
+const SESSION_ACCEPT_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Handles queue-related commands including queue switching and statistics.
 ///
 /// Provides functionality for managing queue connections, retrieving queue
@@ -57,7 +87,8 @@ const ERROR_BULK_OPERATIONS: &str = "Bulk operations require message to be recei
 /// // Switch to a different queue
 /// let response = handler.handle_switch_queue(
 ///     "orders".to_string(),
-///     QueueType::Main
+///     QueueType::Main,
+///     None
 /// ).await?;
 ///
 /// // Get current queue information
@@ -79,17 +110,50 @@ impl QueueCommandHandler {
         }
     }
 
+    /// The management client, for callers that run many requests concurrently
+    pub async fn management_client(&self) -> ServiceBusResult<AzureManagementClient> {
+        self.statistics_service.management_client().await
+    }
+
     pub async fn handle_switch_queue(
         &self,
         queue_name: String,
         queue_type: QueueType,
+        session_id: Option<String>,
     ) -> ServiceBusResult<ServiceBusResponse> {
-        let queue_info = QueueInfo::new(queue_name, queue_type);
+        let queue_info = QueueInfo::new(queue_name, queue_type).with_session(session_id);
         let mut manager = self.consumer_manager.lock().await;
         manager.switch_queue(queue_info.clone()).await?;
         Ok(ServiceBusResponse::QueueSwitched { queue_info })
     }
 
+    pub async fn handle_list_sessions(
+        &self,
+        queue_name: String,
+        max_sessions: u32,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let manager = self.consumer_manager.lock().await;
+        let sessions = manager
+            .list_sessions(&queue_name, max_sessions, SESSION_ACCEPT_TIMEOUT)
+            .await?;
+        Ok(ServiceBusResponse::SessionsListed { sessions })
+    }
+
+    pub async fn handle_get_session_state(&self) -> ServiceBusResult<ServiceBusResponse> {
+        let manager = self.consumer_manager.lock().await;
+        let (session_id, state) = manager.get_session_state().await?;
+        Ok(ServiceBusResponse::SessionState { session_id, state })
+    }
+
+    pub async fn handle_set_session_state(
+        &self,
+        state: Vec<u8>,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let manager = self.consumer_manager.lock().await;
+        let session_id = manager.set_session_state(state).await?;
+        Ok(ServiceBusResponse::SessionStateUpdated { session_id })
+    }
+
     pub async fn handle_get_current_queue(&self) -> ServiceBusResult<ServiceBusResponse> {
         let manager = self.consumer_manager.lock().await;
         let queue_info = manager.current_queue().cloned();
@@ -123,6 +187,89 @@ impl QueueCommandHandler {
             retrieved_at,
         })
     }
+
+    pub async fn handle_get_queue_details(
+        &self,
+        queue_name: String,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let details = self
+            .statistics_service
+            .get_queue_details(&queue_name)
+            .await?;
+        Ok(ServiceBusResponse::QueueDetails {
+            details: Box::new(details),
+        })
+    }
+
+    pub async fn handle_create_queue(
+        &self,
+        queue_name: String,
+        settings: QueueSettings,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let details = self
+            .statistics_service
+            .create_queue(&queue_name, &settings)
+            .await?;
+        Ok(ServiceBusResponse::QueueCreated {
+            details: Box::new(details),
+        })
+    }
+
+    pub async fn handle_update_queue(
+        &self,
+        queue_name: String,
+        settings: QueueSettings,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let details = self
+            .statistics_service
+            .update_queue(&queue_name, &settings)
+            .await?;
+        Ok(ServiceBusResponse::QueueUpdated {
+            details: Box::new(details),
+        })
+    }
+
+    pub async fn handle_delete_queue(
+        &self,
+        queue_name: String,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        self.statistics_service.delete_queue(&queue_name).await?;
+        Ok(ServiceBusResponse::QueueDeleted { queue_name })
+    }
+
+    pub async fn handle_list_subscription_rules(
+        &self,
+        subscription_path: String,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let rules = self
+            .statistics_service
+            .list_subscription_rules(&subscription_path)
+            .await?;
+        Ok(ServiceBusResponse::SubscriptionRules { rules })
+    }
+
+    pub async fn handle_save_subscription_rule(
+        &self,
+        subscription_path: String,
+        rule: SubscriptionRule,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let rule = self
+            .statistics_service
+            .save_subscription_rule(&subscription_path, &rule)
+            .await?;
+        Ok(ServiceBusResponse::SubscriptionRuleSaved { rule })
+    }
+
+    pub async fn handle_delete_subscription_rule(
+        &self,
+        subscription_path: String,
+        rule_name: String,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        self.statistics_service
+            .delete_subscription_rule(&subscription_path, &rule_name)
+            .await?;
+        Ok(ServiceBusResponse::SubscriptionRuleDeleted { rule_name })
+    }
 }
 
 /// Handles message retrieval commands including peek and receive operations.
@@ -172,6 +319,32 @@ impl MessageCommandHandler {
         Ok(ServiceBusResponse::ReceivedMessages { messages })
     }
 
+    pub async fn handle_defer_message(
+        &self,
+        message_id: String,
+        sequence_number: i64,
+        max_position: usize,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let manager = self.consumer_manager.lock().await;
+        let still_deferred = manager
+            .defer_message(&message_id, sequence_number, Some(max_position))
+            .await?;
+        Ok(ServiceBusResponse::MessageDeferred {
+            message_id,
+            sequence_number,
+            still_deferred,
+        })
+    }
+
+    pub async fn handle_receive_deferred_messages(
+        &self,
+        sequence_numbers: Vec<i64>,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let manager = self.consumer_manager.lock().await;
+        let messages = manager.receive_deferred_messages(&sequence_numbers).await?;
+        Ok(ServiceBusResponse::ReceivedMessages { messages })
+    }
+
     pub async fn handle_complete_message(
         &self,
         _message_id: String,
@@ -274,7 +447,7 @@ impl BulkCommandHandler {
             message_ids.len()
         );
 
-        let (consumer, queue_name) = {
+        let (consumer, queue_name, non_targets) = {
             let manager = self.consumer_manager.lock().await;
             let consumer_arc = manager
                 .get_raw_consumer()
@@ -285,7 +458,7 @@ impl BulkCommandHandler {
                 .ok_or(ServiceBusError::ConsumerNotFound)?
                 .name
                 .clone();
-            (consumer_arc, queue)
+            (consumer_arc, queue, manager.non_target_restore()?)
         };
 
         // Validate that we have messages to work with
@@ -298,12 +471,21 @@ impl BulkCommandHandler {
         // Log which queue we're deleting from for debugging
         log::info!("Bulk delete operating on queue: {queue_name}");
 
-        match self
+        let outcome = self
             .bulk_handler
-            .delete_messages(consumer, queue_name, message_ids, max_position)
-            .await
-        {
-            Ok(result) => {
+            .delete_messages(
+                consumer,
+                queue_name,
+                Arc::clone(non_targets.releaser()),
+                message_ids,
+                max_position,
+            )
+            .await;
+        let still_deferred = non_targets.restore().await;
+
+        match outcome {
+            Ok(mut result) => {
+                result.still_deferred = still_deferred;
                 log::info!(
                     "Bulk delete completed: {} successful, {} failed, {} not found",
                     result.successful,
@@ -341,13 +523,299 @@ impl BulkCommandHandler {
         ))
     }
 
+    /// Purge a queue, subscription or dead letter queue with concurrent receivers.
+    ///
+    /// The receivers are independent of the current consumer, so the purge is
+    /// not limited to the messages loaded in the UI.
+    pub async fn handle_purge_queue(
+        &self,
+        queue_name: String,
+        older_than: Option<OffsetDateTime>,
+        progress: Option<UnboundedSender<PurgeProgress>>,
+        cancel_token: CancellationToken,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        log::info!("Starting purge of {queue_name}");
+
+        let (receivers, non_targets) = {
+            let consumer_manager = self.consumer_manager.lock().await;
+            let receivers = consumer_manager
+                .create_purge_receivers(&queue_name, older_than)
+                .await?;
+            let non_targets = match older_than {
+                Some(_) => Some(consumer_manager.create_purge_restore(&queue_name).await?),
+                None => None,
+            };
+            (receivers, non_targets)
+        };
+
+        let cutoff = older_than
+            .zip(non_targets.as_ref())
+            .map(|(older_than, non_targets)| PurgeCutoff {
+                older_than,
+                non_targets: non_targets.releaser(),
+            });
+        let purged = QueuePurger::new(self.batch_config.clone())
+            .purge(receivers, cutoff, progress, cancel_token)
+            .await;
+        let still_deferred = match non_targets {
+            Some(non_targets) => non_targets.restore().await,
+            None => Vec::new(),
+        };
+
+        let mut result = purged.map_err(|e| {
+            let note = if still_deferred.is_empty() {
+                String::new()
+            } else {
+                format!("; newer messages stay deferred: {still_deferred:?}")
+            };
+            ServiceBusError::BulkOperationFailed(format!("Failed to purge {queue_name}: {e}{note}"))
+        })?;
+        result.still_deferred = still_deferred;
+
+        Ok(ServiceBusResponse::QueuePurged { queue_name, result })
+    }
+
+    /// Select the messages a purge of `queue_name` would remove, peeking with
+    /// a dedicated receiver like a cutoff purge does.
+    pub async fn handle_select_purge_targets(
+        &self,
+        queue_name: String,
+        older_than: Option<OffsetDateTime>,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let mut receiver = self
+            .consumer_manager
+            .lock()
+            .await
+            .create_peek_receiver(&queue_name)
+            .await?;
+
+        let selected = QueuePurger::new(self.batch_config.clone())
+            .select_targets(&mut receiver, older_than, &CancellationToken::new())
+            .await;
+        if let Err(e) = receiver.dispose().await {
+            log::warn!("Failed to dispose purge selection receiver: {e}");
+        }
+
+        let sequence_numbers = selected.map_err(|e| {
+            ServiceBusError::BulkOperationFailed(format!(
+                "Failed to select messages to purge from {queue_name}: {e}"
+            ))
+        })?;
+        Ok(ServiceBusResponse::PurgeTargetsSelected {
+            queue_name,
+            sequence_numbers: sequence_numbers.into_iter().collect(),
+        })
+    }
+
+    /// Search a queue, subscription or dead letter queue with a SQL filter.
+    ///
+    /// A dedicated receiver peeks from the first message, so the search
+    /// covers the whole entity whatever page the UI is showing.
+    pub async fn handle_search_messages(
+        &self,
+        queue_name: String,
+        filter: SqlFilter,
+        max_matches: Option<usize>,
+        progress: Option<UnboundedSender<SearchProgress>>,
+        cancel_token: CancellationToken,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        log::info!("Starting search of {queue_name}");
+
+        let receiver = self
+            .consumer_manager
+            .lock()
+            .await
+            .create_peek_receiver(&queue_name)
+            .await?;
+        let max_matches = max_matches.unwrap_or(self.batch_config.search_max_matches());
+
+        let result = QueueSearcher::new(self.batch_config.clone())
+            .search(receiver, &filter, max_matches, progress, cancel_token)
+            .await
+            .map_err(|e| {
+                ServiceBusError::BulkOperationFailed(format!("Failed to search {queue_name}: {e}"))
+            })?;
+
+        Ok(ServiceBusResponse::SearchCompleted { queue_name, result })
+    }
+
+    /// Export a queue, subscription or dead letter queue to a file.
+    ///
+    /// Like searches, exports peek with a dedicated receiver from the first
+    /// message, or from the lowest selected sequence number.
+    pub async fn handle_export_messages(
+        &self,
+        queue_name: String,
+        path: PathBuf,
+        format: ExportFormat,
+        scope: ExportScope,
+        progress: Option<UnboundedSender<ExportProgress>>,
+        cancel_token: CancellationToken,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        log::info!("Starting export of {queue_name} to {}", path.display());
+
+        let receiver = self
+            .consumer_manager
+            .lock()
+            .await
+            .create_peek_receiver(&queue_name)
+            .await?;
+
+        let result = QueueExporter::new(self.batch_config.clone())
+            .export(receiver, &path, format, &scope, progress, cancel_token)
+            .await
+            .map_err(|e| {
+                ServiceBusError::BulkOperationFailed(format!("Failed to export {queue_name}: {e}"))
+            })?;
+
+        Ok(ServiceBusResponse::MessagesExported { queue_name, result })
+    }
+
+    /// Replay an NDJSON export into a queue through the producer manager.
+    pub async fn handle_import_messages(
+        &self,
+        queue_name: String,
+        path: PathBuf,
+        options: ImportOptions,
+        progress: Option<UnboundedSender<ImportProgress>>,
+        cancel_token: CancellationToken,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        log::info!("Starting import of {} into {queue_name}", path.display());
+
+        let result = QueueImporter::new(self.batch_config.clone())
+            .import(
+                &self.producer_manager,
+                &queue_name,
+                &path,
+                &options,
+                progress,
+                cancel_token,
+            )
+            .await
+            .map_err(|e| {
+                ServiceBusError::BulkOperationFailed(format!(
+                    "Failed to import into {queue_name}: {e}"
+                ))
+            })?;
+
+        Ok(ServiceBusResponse::MessagesImported { queue_name, result })
+    }
+
+    /// Fetch deferred messages by sequence number and settle them.
+    ///
+    /// Deferred messages are never returned by a normal receive, so they are
+    /// fetched directly instead of being searched for from the head of the queue.
+    pub async fn handle_settle_deferred_messages(
+        &self,
+        message_ids: Vec<MessageIdentifier>,
+        action: DeferredMessageAction,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let mut result = BulkOperationResult::new(message_ids.len());
+        if message_ids.is_empty() {
+            return Ok(ServiceBusResponse::BulkOperationCompleted { result });
+        }
+
+        let sequence_numbers: Vec<i64> = message_ids.iter().map(|id| id.sequence).collect();
+        let (consumer_arc, messages) = {
+            let manager = self.consumer_manager.lock().await;
+            let consumer_arc = manager
+                .get_raw_consumer()
+                .ok_or(ServiceBusError::ConsumerNotFound)?;
+            let messages = manager.receive_deferred_messages(&sequence_numbers).await?;
+            (consumer_arc, messages)
+        };
+        log::info!(
+            "Fetched {} of {} deferred messages for {action:?}",
+            messages.len(),
+            message_ids.len()
+        );
+
+        // For a resend, the copies must be sent before the originals are completed
+        let sent = match &action {
+            DeferredMessageAction::Resend {
+                target_queue,
+                resend_options,
+            } => {
+                let outgoing: Vec<MessageData> = messages
+                    .iter()
+                    .map(|msg| resend_message_from_received(msg, resend_options))
+                    .collect();
+                match self
+                    .producer_manager
+                    .lock()
+                    .await
+                    .send_bulk_messages(target_queue, outgoing, 1)
+                    .await
+                {
+                    Ok(sent) if sent.stats.failed == 0 => true,
+                    Ok(sent) => {
+                        log::error!(
+                            "Resending deferred messages to {target_queue} partially failed: {} successful, {} failed",
+                            sent.stats.successful,
+                            sent.stats.failed
+                        );
+                        false
+                    }
+                    Err(e) => {
+                        log::error!("Resending deferred messages to {target_queue} failed: {e}");
+                        false
+                    }
+                }
+            }
+            _ => true,
+        };
+
+        let mut consumer = consumer_arc.lock().await;
+        for msg in &messages {
+            let sequence = msg.sequence_number();
+            let Some(target) = message_ids.iter().find(|id| id.sequence == sequence) else {
+                continue;
+            };
+
+            if !sent {
+                if let Err(e) = consumer.abandon_message(msg).await {
+                    log::warn!("Failed to release deferred message {sequence}: {e}");
+                }
+                result.add_failure(format!("Failed to resend {}", target.id));
+                continue;
+            }
+
+            let settled = match &action {
+                DeferredMessageAction::DeadLetter {
+                    reason,
+                    error_description,
+                } => {
+                    consumer
+                        .dead_letter_message(msg, reason.clone(), error_description.clone())
+                        .await
+                }
+                DeferredMessageAction::Complete | DeferredMessageAction::Resend { .. } => {
+                    consumer.complete_message(msg).await
+                }
+            };
+            match settled {
+                Ok(()) => result.add_successful_message(target.clone()),
+                Err(e) => {
+                    log::error!("Failed to settle deferred message {}: {e}", target.id);
+                    result.add_failure(format!("Failed to settle {}: {e}", target.id));
+                }
+            }
+        }
+
+        result.not_found = message_ids.len().saturating_sub(messages.len());
+        Ok(ServiceBusResponse::BulkOperationCompleted { result })
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn handle_bulk_send(
         &self,
         message_ids: Vec<MessageIdentifier>,
         target_queue: String,
+        target_namespace: Option<TargetNamespace>,
         should_delete_source: bool,
         repeat_count: usize,
         _max_position: usize,
+        resend_options: ResendOptions,
     ) -> ServiceBusResult<ServiceBusResponse> {
         // Wrap the entire operation in a timeout
         let operation_timeout = Duration::from_secs(self.batch_config.operation_timeout_secs());
@@ -357,9 +825,11 @@ impl BulkCommandHandler {
             self.handle_bulk_send_internal(
                 message_ids,
                 target_queue,
+                target_namespace.as_ref(),
                 should_delete_source,
                 repeat_count,
                 _max_position,
+                resend_options,
             ),
         )
         .await
@@ -378,33 +848,39 @@ impl BulkCommandHandler {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn handle_bulk_send_internal(
         &self,
         message_ids: Vec<MessageIdentifier>,
         target_queue: String,
+        target_namespace: Option<&TargetNamespace>,
         should_delete_source: bool,
         repeat_count: usize,
         _max_position: usize,
+        resend_options: ResendOptions,
     ) -> ServiceBusResult<ServiceBusResponse> {
         log::info!(
-            "Starting bulk send: {} -> {}, delete_source={}, repeat={}",
+            "Starting bulk send: {} -> {}{}, delete_source={}, repeat={}",
             message_ids.len(),
             target_queue,
+            target_namespace
+                .map(|target| format!(" in {}", target.label))
+                .unwrap_or_default(),
             should_delete_source,
             repeat_count
         );
 
         // Check if this is a DLQ operation
-        let is_dlq_operation = target_queue.ends_with("/$deadletterqueue");
+        let is_dlq_operation =
+            target_namespace.is_none() && target_queue.ends_with("/$deadletterqueue");
 
         // Setup operation state
-        let (
-            consumer_arc,
-            mut remaining,
-            mut message_bytes,
-            mut successful_count,
-            mut failed_count,
-        ) = self.setup_bulk_send_operation(&message_ids).await?;
+        let (consumer_arc, mut remaining, mut successful_count, mut failed_count) =
+            self.setup_bulk_send_operation(&message_ids).await?;
+        let non_targets = self.consumer_manager.lock().await.non_target_restore()?;
+
+        // Connect to the target before receiving, so nothing is locked if that fails
+        let producers = self.target_producers(target_namespace).await?;
 
         // Main processing loop
         let batch_size = self.batch_config.bulk_chunk_size() as u32;
@@ -416,6 +892,7 @@ impl BulkCommandHandler {
             .max()
             .unwrap_or(0);
         let mut pending_non_targets: Vec<azservicebus::ServiceBusReceivedMessage> = Vec::new();
+        let mut pending_targets: Vec<azservicebus::ServiceBusReceivedMessage> = Vec::new();
         let mut consecutive_empty_batches = 0;
         let max_empty_batches = 3; // Exit after 3 consecutive empty batches
 
@@ -462,16 +939,22 @@ impl BulkCommandHandler {
                     highest_sequence_seen = msg_sequence;
                 }
                 if remaining.remove(&msg_id).is_some() {
-                    let params = TargetMessageParams {
-                        consumer: &mut consumer,
-                        msg: &msg,
-                        is_dlq_operation,
-                        should_delete_source,
-                        message_bytes: &mut message_bytes,
-                        successful_count: &mut successful_count,
-                        failed_count: &mut failed_count,
-                    };
-                    self.process_target_message(params).await;
+                    if is_dlq_operation {
+                        let params = TargetMessageParams {
+                            consumer: &mut consumer,
+                            msg: &msg,
+                            successful_count: &mut successful_count,
+                            failed_count: &mut failed_count,
+                        };
+                        self.process_target_message(params).await;
+                    } else {
+                        // Keep the lock on targets until they are sent to the target queue
+                        pending_targets.push(msg);
+                    }
+                } else if non_targets.releaser().releases_immediately() {
+                    if let Err(e) = non_targets.releaser().release(&mut consumer, &msg).await {
+                        log::warn!("Failed to release non-target message: {e}");
+                    }
                 } else {
                     pending_non_targets.push(msg);
                 }
@@ -511,18 +994,48 @@ impl BulkCommandHandler {
             remaining.len()
         );
 
-        // Abandon non-target messages (acquire lock one final time)
-        if !pending_non_targets.is_empty() {
+        // Send collected targets, then settle their source messages
+        if !pending_targets.is_empty() {
             let mut consumer = consumer_arc.lock().await;
-            self.abandon_pending_non_targets(&mut consumer, pending_non_targets)
+            let (successful, failed) = self
+                .forward_pending_targets(ForwardTargetsParams {
+                    consumer: &mut consumer,
+                    targets: pending_targets,
+                    non_targets: non_targets.releaser(),
+                    producers: &producers,
+                    target_queue: &target_queue,
+                    should_delete_source,
+                    repeat_count,
+                    resend_options: &resend_options,
+                })
                 .await;
+            successful_count += successful;
+            failed_count += failed;
+        }
+
+        // Release non-target messages (acquire lock one final time)
+        if !pending_non_targets.is_empty() {
+            let mut consumer = consumer_arc.lock().await;
+            self.release_pending_non_targets(
+                &mut consumer,
+                non_targets.releaser(),
+                pending_non_targets,
+            )
+            .await;
         }
+        let still_deferred = non_targets.restore().await;
+        self.release_target_producers(&producers, target_namespace)
+            .await;
         let params = BulkSendResultParams {
             message_ids,
             successful_count,
             failed_count,
         };
-        self.finalize_bulk_send_result(params)
+        let mut response = self.finalize_bulk_send_result(params)?;
+        if let ServiceBusResponse::BulkOperationCompleted { result } = &mut response {
+            result.still_deferred = still_deferred;
+        }
+        Ok(response)
     }
 
     async fn setup_bulk_send_operation(
@@ -531,7 +1044,6 @@ impl BulkCommandHandler {
     ) -> ServiceBusResult<(
         Arc<Mutex<Consumer>>,
         HashMap<String, MessageIdentifier>,
-        Vec<Vec<u8>>,
         usize,
         usize,
     )> {
@@ -546,16 +1058,9 @@ impl BulkCommandHandler {
             .iter()
             .map(|m| (m.id.clone(), m.clone()))
             .collect();
-        let message_bytes: Vec<Vec<u8>> = Vec::new();
         let successful_count: usize = 0;
         let failed_count: usize = 0;
-        Ok((
-            consumer_arc,
-            remaining,
-            message_bytes,
-            successful_count,
-            failed_count,
-        ))
+        Ok((consumer_arc, remaining, successful_count, failed_count))
     }
 
     fn should_continue_bulk_send(
@@ -590,61 +1095,158 @@ impl BulkCommandHandler {
     }
 
     async fn process_target_message(&self, params: TargetMessageParams<'_>) {
-        if params.is_dlq_operation {
-            if let Err(e) = params
-                .consumer
-                .dead_letter_message(params.msg, Some("Bulk moved to DLQ".to_string()), None)
-                .await
-            {
-                *params.failed_count += 1;
-                log::error!(
-                    "Failed to dead letter message {:?}: {}",
-                    params.msg.message_id(),
-                    e
-                );
-                return;
+        if let Err(e) = params
+            .consumer
+            .dead_letter_message(params.msg, Some("Bulk moved to DLQ".to_string()), None)
+            .await
+        {
+            *params.failed_count += 1;
+            log::error!(
+                "Failed to dead letter message {:?}: {}",
+                params.msg.message_id(),
+                e
+            );
+            return;
+        }
+        *params.successful_count += 1;
+    }
+
+    /// Send locked target messages (with their properties) to the target queue, then
+    /// settle the originals. A move completes the originals that were sent and
+    /// abandons the others; a copy releases every original like a non-target, so
+    /// copying never raises its delivery count. Returns the successful and
+    /// failed counts.
+    async fn forward_pending_targets(&self, params: ForwardTargetsParams<'_>) -> (usize, usize) {
+        let outgoing: Vec<MessageData> = params
+            .targets
+            .iter()
+            .map(|msg| resend_message_from_received(msg, params.resend_options))
+            .collect();
+
+        let send_result = params
+            .producers
+            .lock()
+            .await
+            .send_bulk_messages(params.target_queue, outgoing, params.repeat_count)
+            .await;
+
+        let sent = match send_result {
+            Ok(sent) => {
+                if sent.stats.failed > 0 {
+                    log::error!(
+                        "Bulk send to {} partially failed: {} successful, {} failed",
+                        params.target_queue,
+                        sent.stats.successful,
+                        sent.stats.failed
+                    );
+                }
+                Some(sent)
             }
-            *params.successful_count += 1;
-        } else {
-            if let Ok(body) = params.msg.body() {
-                params.message_bytes.push(body.to_vec());
+            Err(e) => {
+                log::error!("Bulk send to {} failed: {e}", params.target_queue);
+                None
             }
-            let res = if params.should_delete_source {
-                params.consumer.complete_message(params.msg).await
+        };
+
+        let mut successful = 0;
+        let mut failed = 0;
+        for (index, msg) in params.targets.iter().enumerate() {
+            let is_sent = sent.as_ref().is_some_and(|sent| sent.is_sent(index));
+            let res = if !params.should_delete_source {
+                params.non_targets.release(params.consumer, msg).await
+            } else if is_sent {
+                params.consumer.complete_message(msg).await
             } else {
-                params.consumer.abandon_message(params.msg).await
+                params.consumer.abandon_message(msg).await
             };
-            if let Err(e) = res {
-                *params.failed_count += 1;
-                log::error!(
-                    "Failed to finalise original message {:?}: {}",
-                    params.msg.message_id(),
-                    e
-                );
-                return;
+            if !is_sent {
+                failed += 1;
+                if let Err(e) = res {
+                    log::warn!("Failed to release target after send failure: {e}");
+                }
+                continue;
+            }
+            match res {
+                Ok(()) => successful += 1,
+                Err(e) => {
+                    failed += 1;
+                    log::error!(
+                        "Failed to finalise original message {:?}: {}",
+                        msg.message_id(),
+                        e
+                    );
+                }
             }
-            *params.successful_count += 1;
         }
+
+        (successful, failed)
     }
 
-    async fn abandon_pending_non_targets(
+    async fn release_pending_non_targets(
         &self,
         consumer: &mut Consumer,
+        non_targets: &NonTargetReleaser,
         pending_non_targets: Vec<azservicebus::ServiceBusReceivedMessage>,
     ) {
         if !pending_non_targets.is_empty() {
             log::info!(
-                "Abandoning {} non-target messages accumulated during scan",
+                "Releasing {} non-target messages accumulated during scan",
                 pending_non_targets.len()
             );
             for msg in pending_non_targets.into_iter() {
-                if let Err(e) = consumer.abandon_message(&msg).await {
-                    log::warn!("Failed to abandon non-target message after scan: {e}");
+                if let Err(e) = non_targets.release(consumer, &msg).await {
+                    log::warn!("Failed to release non-target message after scan: {e}");
                 }
             }
         }
     }
 
+    /// Producers for the target of a bulk send: the shared ones for the
+    /// connected namespace, or ones on a dedicated client for another namespace
+    async fn target_producers(
+        &self,
+        target_namespace: Option<&TargetNamespace>,
+    ) -> ServiceBusResult<Arc<Mutex<ProducerManager>>> {
+        let Some(target) = target_namespace else {
+            return Ok(self.producer_manager.clone());
+        };
+
+        log::info!(
+            "Connecting to target namespace {} ({})",
+            target.label,
+            target.host().unwrap_or("unknown host")
+        );
+        let client = ServiceBusClient::new_from_connection_string(
+            &target.connection_string,
+            ServiceBusClientOptions::default(),
+        )
+        .await
+        .map_err(|e| {
+            ServiceBusError::ConnectionFailed(format!(
+                "Failed to connect to target namespace {}: {e}",
+                target.label
+            ))
+        })?;
+
+        Ok(Arc::new(Mutex::new(ProducerManager::new(
+            Arc::new(Mutex::new(client)),
+            self.batch_config.clone(),
+        ))))
+    }
+
+    /// Closes the producers opened for another namespace by [`Self::target_producers`]
+    async fn release_target_producers(
+        &self,
+        producers: &Mutex<ProducerManager>,
+        target_namespace: Option<&TargetNamespace>,
+    ) {
+        if target_namespace.is_some()
+            && let Err(e) = producers.lock().await.dispose_all_producers().await
+        {
+            log::warn!("Failed to close producers of the target namespace: {e}");
+        }
+    }
+
     fn finalize_bulk_send_result(
         &self,
         params: BulkSendResultParams,
@@ -661,27 +1263,35 @@ impl BulkCommandHandler {
 
     pub async fn handle_bulk_send_peeked(
         &self,
-        messages_data: Vec<(MessageIdentifier, Vec<u8>)>,
+        messages_data: Vec<(MessageIdentifier, MessageData)>,
         target_queue: String,
+        target_namespace: Option<TargetNamespace>,
         repeat_count: usize,
     ) -> ServiceBusResult<ServiceBusResponse> {
         log::info!(
-            "Bulk send (peeked) {} messages to {} (repeat={})",
+            "Bulk send (peeked) {} messages to {}{} (repeat={})",
             messages_data.len(),
             target_queue,
+            target_namespace
+                .as_ref()
+                .map(|target| format!(" in {}", target.label))
+                .unwrap_or_default(),
             repeat_count
         );
 
-        // Extract raw bytes
-        let raw_vec: Vec<Vec<u8>> = messages_data
-            .iter()
-            .map(|(_id, data)| data.clone())
-            .collect();
+        let outgoing: Vec<MessageData> =
+            messages_data.into_iter().map(|(_id, data)| data).collect();
 
-        let mut producer_mgr = self.producer_manager.lock().await;
-        let stats = producer_mgr
-            .send_raw_messages(&target_queue, raw_vec, repeat_count)
+        let producers = self.target_producers(target_namespace.as_ref()).await?;
+        let send_result = producers
+            .lock()
             .await
+            .send_bulk_messages(&target_queue, outgoing, repeat_count)
+            .await;
+        self.release_target_producers(&producers, target_namespace.as_ref())
+            .await;
+        let stats = send_result
+            .map(|sent| sent.stats)
             .map_err(|e| ServiceBusError::BulkOperationFailed(format!("Bulk send failed: {e}")))?;
 
         Ok(ServiceBusResponse::MessagesSent {
@@ -758,6 +1368,37 @@ impl SendCommandHandler {
             stats,
         })
     }
+
+    pub async fn handle_schedule_messages(
+        &self,
+        queue_name: String,
+        messages: Vec<MessageData>,
+        enqueue_time: OffsetDateTime,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let mut manager = self.producer_manager.lock().await;
+        let sequence_numbers = manager
+            .schedule_messages(&queue_name, messages, enqueue_time)
+            .await?;
+        Ok(ServiceBusResponse::MessagesScheduled {
+            queue_name,
+            sequence_numbers,
+        })
+    }
+
+    pub async fn handle_cancel_scheduled_messages(
+        &self,
+        queue_name: String,
+        sequence_numbers: Vec<i64>,
+    ) -> ServiceBusResult<ServiceBusResponse> {
+        let mut manager = self.producer_manager.lock().await;
+        manager
+            .cancel_scheduled_messages(&queue_name, &sequence_numbers)
+            .await?;
+        Ok(ServiceBusResponse::ScheduledMessagesCancelled {
+            queue_name,
+            count: sequence_numbers.len(),
+        })
+    }
 }
 
 /// Handles status and health check commands for monitoring Service Bus connections.
@@ -877,6 +1518,21 @@ impl ResourceCommandHandler {
     }
 }
 
+/// Rebuilds a received message for resending, keeping its body and properties.
+fn resend_message_from_received(
+    msg: &azservicebus::ServiceBusReceivedMessage,
+    options: &ResendOptions,
+) -> MessageData {
+    let body = msg.body().map(|body| body.to_vec()).unwrap_or_default();
+
+    options.build_message(
+        body,
+        msg.message_id().as_deref(),
+        msg.sequence_number(),
+        &MessageProperties::from_received_message(msg),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
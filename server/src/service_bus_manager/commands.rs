@@ -1,5 +1,16 @@
-use super::types::{MessageData, QueueType};
-use crate::bulk_operations::MessageIdentifier;
+use super::queue_details::QueueSettings;
+use super::subscription_rules::SubscriptionRule;
+use super::types::{DeferredMessageAction, MessageData, QueueType, ResendOptions, TargetNamespace};
+use crate::bulk_operations::{
+    ExportProgress, ExportScope, ImportOptions, ImportProgress, MessageIdentifier, PurgeProgress,
+    SearchProgress,
+};
+use crate::export::ExportFormat;
+use crate::filter::SqlFilter;
+use azure_core::time::OffsetDateTime;
+use std::path::PathBuf;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
 
 /// Commands for Service Bus operations using the command pattern.
 ///
@@ -27,6 +38,7 @@ use crate::bulk_operations::MessageIdentifier;
 /// let command = ServiceBusCommand::SwitchQueue {
 ///     queue_name: "my-queue".to_string(),
 ///     queue_type: QueueType::Queue,
+///     session_id: None,
 /// };
 ///
 /// // Peek messages
@@ -51,6 +63,25 @@ pub enum ServiceBusCommand {
         queue_name: String,
         /// Type of queue (Queue or Topic)
         queue_type: QueueType,
+        /// Session to open, required for session-enabled queues
+        session_id: Option<String>,
+    },
+
+    /// List sessions of a session-enabled queue that currently hold messages.
+    ListSessions {
+        /// Name of the queue or subscription entity path
+        queue_name: String,
+        /// Maximum number of sessions to discover
+        max_sessions: u32,
+    },
+
+    /// Read the state of the session the current queue is opened with.
+    GetSessionState,
+
+    /// Replace the state of the session the current queue is opened with.
+    SetSessionState {
+        /// New raw session state
+        state: Vec<u8>,
     },
 
     /// Get information about the currently active queue.
@@ -66,6 +97,60 @@ pub enum ServiceBusCommand {
         queue_type: QueueType,
     },
 
+    /// Retrieve the runtime counts and configuration of a queue or subscription.
+    ///
+    /// Needs the Azure Management API; see [`QueueDetails`](super::QueueDetails).
+    GetQueueDetails {
+        /// Queue name or subscription path
+        queue_name: String,
+    },
+
+    /// Create a queue through the Azure Management API.
+    ///
+    /// Fails if a queue with that name already exists.
+    CreateQueue {
+        /// Name of the new queue
+        queue_name: String,
+        /// Settings to create the queue with; unset ones use the defaults
+        settings: QueueSettings,
+    },
+
+    /// Change settings of a queue through the Azure Management API.
+    UpdateQueue {
+        /// Name of the queue to change
+        queue_name: String,
+        /// Settings to change; unset ones keep their value
+        settings: QueueSettings,
+    },
+
+    /// Delete a queue and all of its messages through the Azure Management API.
+    DeleteQueue {
+        /// Name of the queue to delete
+        queue_name: String,
+    },
+
+    /// List the rules of a topic subscription through the Azure Management API.
+    ListSubscriptionRules {
+        /// Subscription path, `<topic>/Subscriptions/<subscription>`
+        subscription_path: String,
+    },
+
+    /// Create a subscription rule, or replace the rule of the same name.
+    SaveSubscriptionRule {
+        /// Subscription path, `<topic>/Subscriptions/<subscription>`
+        subscription_path: String,
+        /// Rule to save
+        rule: SubscriptionRule,
+    },
+
+    /// Delete a subscription rule.
+    DeleteSubscriptionRule {
+        /// Subscription path, `<topic>/Subscriptions/<subscription>`
+        subscription_path: String,
+        /// Name of the rule to delete
+        rule_name: String,
+    },
+
     /// Peek at messages without removing them from the queue.
     ///
     /// Messages remain in the queue and can be retrieved again.
@@ -108,6 +193,33 @@ pub enum ServiceBusCommand {
         error_description: Option<String>,
     },
 
+    /// Defer a message so that it is only delivered when fetched by sequence number.
+    ///
+    /// The message is located by receiving from the head of the queue, like
+    /// other targeted operations.
+    DeferMessage {
+        /// ID of the message to defer
+        message_id: String,
+        /// Sequence number of the message to defer
+        sequence_number: i64,
+        /// Maximum position to scan when looking for the message
+        max_position: usize,
+    },
+
+    /// Fetch deferred messages by sequence number, locking them for settlement.
+    ReceiveDeferredMessages {
+        /// Sequence numbers of the deferred messages
+        sequence_numbers: Vec<i64>,
+    },
+
+    /// Fetch deferred messages and complete, dead letter or resend them.
+    SettleDeferredMessages {
+        /// Deferred messages to settle
+        message_ids: Vec<MessageIdentifier>,
+        /// What to do with the fetched messages
+        action: DeferredMessageAction,
+    },
+
     /// Complete multiple messages in a single bulk operation.
     BulkComplete {
         /// List of message identifiers to complete
@@ -130,6 +242,85 @@ pub enum ServiceBusCommand {
         message_ids: Vec<MessageIdentifier>,
     },
 
+    /// Remove all messages from a queue, subscription or dead letter queue.
+    ///
+    /// Uses concurrent receive-and-delete receivers and is not limited by
+    /// `max_messages_to_process`.
+    PurgeQueue {
+        /// Entity path of the queue, subscription or dead letter queue
+        queue_name: String,
+        /// Only purge messages enqueued before this time
+        older_than: Option<OffsetDateTime>,
+        /// Channel receiving progress updates while the purge runs
+        progress: Option<UnboundedSender<PurgeProgress>>,
+        /// Token that stops the purge
+        cancel_token: CancellationToken,
+    },
+
+    /// Select the messages a purge would remove without removing them, as
+    /// the purge itself selects them.
+    SelectPurgeTargets {
+        /// Entity path of the queue, subscription or dead letter queue
+        queue_name: String,
+        /// Only select messages enqueued before this time
+        older_than: Option<OffsetDateTime>,
+    },
+
+    /// Find the messages of a queue, subscription or dead letter queue that
+    /// match a SQL filter.
+    ///
+    /// Peeks through the whole entity, so messages are neither locked nor
+    /// counted as delivered.
+    SearchMessages {
+        /// Entity path of the queue, subscription or dead letter queue
+        queue_name: String,
+        /// Predicate selecting the messages to return
+        filter: SqlFilter,
+        /// Stop after this many matches; defaults to `search_max_matches`
+        max_matches: Option<usize>,
+        /// Channel receiving matches while the search runs
+        progress: Option<UnboundedSender<SearchProgress>>,
+        /// Token that stops the search
+        cancel_token: CancellationToken,
+    },
+
+    /// Write messages of a queue, subscription or dead letter queue to a
+    /// new file, with their metadata and original body bytes.
+    ///
+    /// Peeks through the entity, so messages are neither locked nor counted
+    /// as delivered.
+    ExportMessages {
+        /// Entity path of the queue, subscription or dead letter queue
+        queue_name: String,
+        /// File to create; existing files are not overwritten
+        path: PathBuf,
+        /// Layout of the file
+        format: ExportFormat,
+        /// Whole entity or selected sequence numbers
+        scope: ExportScope,
+        /// Channel receiving progress while the export runs
+        progress: Option<UnboundedSender<ExportProgress>>,
+        /// Token that stops the export
+        cancel_token: CancellationToken,
+    },
+
+    /// Replay the messages of an NDJSON export into a queue.
+    ///
+    /// Sends the body, application properties and sendable system properties
+    /// of every record, optionally rate limited or as a dry run.
+    ImportMessages {
+        /// Target queue; subscription paths send to their topic
+        queue_name: String,
+        /// NDJSON file written by an export
+        path: PathBuf,
+        /// MessageId handling, rate limit and dry run
+        options: ImportOptions,
+        /// Channel receiving progress while the import runs
+        progress: Option<UnboundedSender<ImportProgress>>,
+        /// Token that stops the import
+        cancel_token: CancellationToken,
+    },
+
     /// Move multiple messages to the dead letter queue.
     BulkDeadLetter {
         /// List of message identifiers to dead letter
@@ -142,28 +333,35 @@ pub enum ServiceBusCommand {
 
     /// Send multiple messages to a target queue with optional source deletion.
     ///
-    /// Can optionally delete source messages after successful send.
+    /// Can optionally delete source messages after successful send. Sources
+    /// are only deleted once the target confirmed the send.
     BulkSend {
         /// List of message identifiers to send
         message_ids: Vec<MessageIdentifier>,
         /// Name of the target queue to send messages to
         target_queue: String,
+        /// Namespace of the target queue; `None` for the connected namespace
+        target_namespace: Option<TargetNamespace>,
         /// Whether to delete source messages after sending
         should_delete_source: bool,
         /// Number of times to repeat each message
         repeat_count: usize,
         /// Maximum position to scan when retrieving messages
         max_position: usize,
+        /// How the sent copies are rebuilt from the original messages
+        resend_options: ResendOptions,
     },
 
     /// Send pre-fetched message data to a target queue.
     ///
     /// Used when message content has already been retrieved via peek operations.
     BulkSendPeeked {
-        /// Pre-fetched message data (identifier and content)
-        messages_data: Vec<(MessageIdentifier, Vec<u8>)>,
+        /// Pre-fetched message data (identifier, content and properties)
+        messages_data: Vec<(MessageIdentifier, MessageData)>,
         /// Name of the target queue to send messages to
         target_queue: String,
+        /// Namespace of the target queue; `None` for the connected namespace
+        target_namespace: Option<TargetNamespace>,
         /// Number of times to repeat each message
         repeat_count: usize,
     },
@@ -184,6 +382,24 @@ pub enum ServiceBusCommand {
         messages: Vec<MessageData>,
     },
 
+    /// Schedule messages to be enqueued on a queue at a future time.
+    ScheduleMessages {
+        /// Name of the target queue
+        queue_name: String,
+        /// List of messages to schedule
+        messages: Vec<MessageData>,
+        /// When the messages should become visible to receivers
+        enqueue_time: OffsetDateTime,
+    },
+
+    /// Cancel scheduled messages by sequence number.
+    CancelScheduledMessages {
+        /// Name of the queue the messages were scheduled on
+        queue_name: String,
+        /// Sequence numbers of the scheduled messages
+        sequence_numbers: Vec<i64>,
+    },
+
     /// Check the current connection status to Service Bus.
     GetConnectionStatus,
 
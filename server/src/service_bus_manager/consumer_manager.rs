@@ -1,9 +1,14 @@
 use super::errors::{ServiceBusError, ServiceBusResult};
-use super::types::QueueInfo;
+use super::types::{DEAD_LETTER_SUFFIX, QueueInfo, QueueType, parse_subscription_path};
 use crate::bulk_operations::types::BatchConfig;
+use crate::bulk_operations::{DeferredLedger, NonTargetReleaser, QueuePurger};
 use crate::consumer::{Consumer, ServiceBusClientExt};
 use crate::model::MessageModel;
-use azservicebus::{ServiceBusClient, ServiceBusReceiverOptions, core::BasicRetryPolicy};
+use crate::producer::ServiceBusClientProducerExt;
+use azservicebus::{
+    ServiceBusClient, ServiceBusReceiveMode, ServiceBusReceiverOptions, ServiceBusSenderOptions,
+    ServiceBusSessionReceiverOptions, SubQueue, core::BasicRetryPolicy,
+};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
@@ -13,6 +18,134 @@ pub struct ConsumerManager {
     current_queue: Option<QueueInfo>,
     service_bus_client: Arc<Mutex<ServiceBusClient<BasicRetryPolicy>>>,
     batch_config: BatchConfig,
+    /// Non-targets deferred by targeted scans and not restored yet
+    deferred_non_targets: DeferredLedger,
+}
+
+/// Restores the non-targets a targeted scan deferred.
+///
+/// Dropping the guard without calling [`restore`](Self::restore), because the
+/// operation was cancelled, timed out or returned early, restores them in a
+/// background task instead. Messages that cannot be restored stay in the
+/// consumer manager's ledger and are retried by the next targeted operation
+/// on the entity.
+pub struct NonTargetRestore {
+    releaser: Arc<NonTargetReleaser>,
+    consumer: Arc<Mutex<Consumer>>,
+    service_bus_client: Arc<Mutex<ServiceBusClient<BasicRetryPolicy>>>,
+    queue_name: String,
+    chunk_size: usize,
+    /// Whether `consumer` is dedicated to this guard and disposed with it
+    owns_consumer: bool,
+    finished: bool,
+}
+
+impl NonTargetRestore {
+    /// Releaser to hand to the scan
+    pub fn releaser(&self) -> &Arc<NonTargetReleaser> {
+        &self.releaser
+    }
+
+    /// Restore the deferred non-targets now.
+    ///
+    /// # Returns
+    ///
+    /// Sequence numbers of the messages that stay deferred
+    pub async fn restore(mut self) -> Vec<i64> {
+        if let Err(e) = self.run().await {
+            log::error!("Failed to restore non-target messages: {e}");
+        }
+        if self.owns_consumer
+            && let Err(e) = self.consumer.lock().await.dispose().await
+        {
+            log::warn!("Failed to dispose restore receiver: {e}");
+        }
+        self.finished = true;
+        self.releaser.deferred_sequence_numbers()
+    }
+
+    async fn run(&self) -> ServiceBusResult<usize> {
+        Self::restore_deferred(
+            Arc::clone(&self.releaser),
+            Arc::clone(&self.consumer),
+            Arc::clone(&self.service_bus_client),
+            self.queue_name.clone(),
+            self.chunk_size,
+        )
+        .await
+    }
+
+    async fn restore_deferred(
+        releaser: Arc<NonTargetReleaser>,
+        consumer: Arc<Mutex<Consumer>>,
+        service_bus_client: Arc<Mutex<ServiceBusClient<BasicRetryPolicy>>>,
+        queue_name: String,
+        chunk_size: usize,
+    ) -> ServiceBusResult<usize> {
+        if releaser.deferred_sequence_numbers().is_empty() {
+            return Ok(0);
+        }
+
+        let mut producer = service_bus_client
+            .lock()
+            .await
+            .create_producer_for_queue(&queue_name, ServiceBusSenderOptions::default())
+            .await
+            .map_err(|e| {
+                ServiceBusError::ProducerCreationFailed(format!(
+                    "Failed to create producer for queue {queue_name}: {e}"
+                ))
+            })?;
+
+        let result = releaser
+            .restore(&consumer, &mut producer, chunk_size)
+            .await
+            .map_err(|e| ServiceBusError::BulkOperationFailed(e.to_string()));
+
+        if let Err(e) = producer.dispose().await {
+            log::warn!("Failed to dispose producer for queue {queue_name}: {e}");
+        }
+
+        result
+    }
+}
+
+impl Drop for NonTargetRestore {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        let pending = self.releaser.deferred_sequence_numbers();
+        if pending.is_empty() {
+            return;
+        }
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            log::error!(
+                "{} non-target messages of {} stay deferred: {pending:?}",
+                pending.len(),
+                self.queue_name
+            );
+            return;
+        };
+        log::warn!(
+            "Operation on {} ended before restoring {} deferred non-target messages; restoring them in the background",
+            self.queue_name,
+            pending.len()
+        );
+        let restore = Self::restore_deferred(
+            Arc::clone(&self.releaser),
+            Arc::clone(&self.consumer),
+            Arc::clone(&self.service_bus_client),
+            self.queue_name.clone(),
+            self.chunk_size,
+        );
+        runtime.spawn(async move {
+            if let Err(e) = restore.await {
+                log::error!("Failed to restore non-target messages in the background: {e}");
+            }
+        });
+    }
 }
 
 impl ConsumerManager {
@@ -25,6 +158,7 @@ impl ConsumerManager {
             current_queue: None,
             service_bus_client,
             batch_config,
+            deferred_non_targets: DeferredLedger::default(),
         }
     }
 
@@ -37,13 +171,13 @@ impl ConsumerManager {
         );
 
         // Check if we're already connected to this queue
-        if let Some(current_queue) = &self.current_queue {
-            if current_queue.name == queue_info.name
-                && current_queue.queue_type == queue_info.queue_type
-            {
-                log::debug!("Already connected to queue: {}", queue_info.name);
-                return Ok(());
-            }
+        if let Some(current_queue) = &self.current_queue
+            && current_queue.name == queue_info.name
+            && current_queue.queue_type == queue_info.queue_type
+            && current_queue.session_id == queue_info.session_id
+        {
+            log::debug!("Already connected to queue: {}", queue_info.name);
+            return Ok(());
         }
 
         // Dispose current consumer if exists
@@ -54,22 +188,55 @@ impl ConsumerManager {
                 // Continue anyway - we'll create a new one
             }
         }
+        self.current_consumer = None;
+        self.current_queue = None;
 
         // Create new consumer
         log::debug!("Creating new consumer for queue: {}", queue_info.name);
         let mut client = self.service_bus_client.lock().await;
-        let consumer = client
-            .create_consumer_for_queue(
-                queue_info.name.clone(),
-                ServiceBusReceiverOptions::default(),
-            )
-            .await
-            .map_err(|e| {
+        let consumer = match (&queue_info.session_id, queue_info.topic_subscription()) {
+            (Some(session_id), Some((topic, subscription))) => {
+                client
+                    .create_session_consumer_for_subscription(topic, subscription, session_id)
+                    .await
+            }
+            (Some(session_id), None) => {
+                client
+                    .create_session_consumer_for_queue(&queue_info.name, session_id)
+                    .await
+            }
+            (None, Some((topic, subscription))) => {
+                let options = ServiceBusReceiverOptions {
+                    sub_queue: match queue_info.queue_type {
+                        QueueType::Main => SubQueue::None,
+                        QueueType::DeadLetter => SubQueue::DeadLetter,
+                    },
+                    ..Default::default()
+                };
+                client
+                    .create_consumer_for_subscription(topic, subscription, options)
+                    .await
+            }
+            (None, None) => {
+                client
+                    .create_consumer_for_queue(
+                        queue_info.name.clone(),
+                        ServiceBusReceiverOptions::default(),
+                    )
+                    .await
+            }
+        }
+        .map_err(|e| {
+            let error = e.to_string();
+            if is_session_required_error(&error) {
+                ServiceBusError::SessionRequired(queue_info.name.clone())
+            } else {
                 ServiceBusError::ConsumerCreationFailed(format!(
                     "Failed to create consumer for queue {}: {}",
-                    queue_info.name, e
+                    queue_info.name, error
                 ))
-            })?;
+            }
+        })?;
 
         // Update state
         self.current_consumer = Some(Arc::new(Mutex::new(consumer)));
@@ -81,6 +248,116 @@ impl ConsumerManager {
         Ok(())
     }
 
+    /// List sessions of a session-enabled queue that currently hold messages.
+    ///
+    /// Service Bus has no browse operation for sessions, so sessions are
+    /// discovered by accepting the next available session until none is left
+    /// or `max_sessions` is reached. Every accepted session is released right
+    /// away; messages are neither received nor locked. Sessions locked by
+    /// another receiver at the time are not listed.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue_name` - Entity path of the queue or subscription
+    /// * `max_sessions` - Maximum number of sessions to discover
+    /// * `accept_timeout` - How long to wait for each next session
+    ///
+    /// # Returns
+    ///
+    /// The discovered session IDs, sorted
+    pub async fn list_sessions(
+        &self,
+        queue_name: &str,
+        max_sessions: u32,
+        accept_timeout: Duration,
+    ) -> ServiceBusResult<Vec<String>> {
+        log::info!("Listing sessions for queue: {queue_name} (max: {max_sessions})");
+
+        let mut client = self.service_bus_client.lock().await;
+        let mut receivers = Vec::new();
+
+        while receivers.len() < max_sessions as usize {
+            let accept = async {
+                match parse_subscription_path(queue_name) {
+                    Some((topic, subscription)) => {
+                        client
+                            .accept_next_session_for_subscription(
+                                topic,
+                                subscription,
+                                ServiceBusSessionReceiverOptions::default(),
+                            )
+                            .await
+                    }
+                    None => {
+                        client
+                            .accept_next_session_for_queue(
+                                queue_name,
+                                ServiceBusSessionReceiverOptions::default(),
+                            )
+                            .await
+                    }
+                }
+            };
+
+            match tokio::time::timeout(accept_timeout, accept).await {
+                Ok(Ok(receiver)) => receivers.push(receiver),
+                Ok(Err(e)) => {
+                    // No further session available (or the entity is not session-enabled)
+                    log::debug!("Stopped accepting sessions for {queue_name}: {e}");
+                    break;
+                }
+                Err(_) => {
+                    log::debug!("Timed out waiting for next session of {queue_name}");
+                    break;
+                }
+            }
+        }
+
+        let mut sessions = Vec::with_capacity(receivers.len());
+        for receiver in receivers {
+            sessions.push(receiver.session_id().to_string());
+            if let Err(e) = receiver.dispose().await {
+                log::warn!("Failed to release session receiver: {e}");
+            }
+        }
+        sessions.sort();
+
+        log::info!("Found {} sessions for queue {queue_name}", sessions.len());
+        Ok(sessions)
+    }
+
+    /// Read the state of the session the current consumer is bound to
+    pub async fn get_session_state(&self) -> ServiceBusResult<(String, Vec<u8>)> {
+        let consumer = self.get_consumer()?;
+        let mut consumer_guard = consumer.lock().await;
+
+        let session_id = consumer_guard
+            .session_id()
+            .await
+            .ok_or(ServiceBusError::NoActiveSession)?;
+        let state = consumer_guard.session_state().await.map_err(|e| {
+            ServiceBusError::InternalError(format!("Failed to read session state: {e}"))
+        })?;
+
+        Ok((session_id, state))
+    }
+
+    /// Replace the state of the session the current consumer is bound to
+    pub async fn set_session_state(&self, state: Vec<u8>) -> ServiceBusResult<String> {
+        let consumer = self.get_consumer()?;
+        let mut consumer_guard = consumer.lock().await;
+
+        let session_id = consumer_guard
+            .session_id()
+            .await
+            .ok_or(ServiceBusError::NoActiveSession)?;
+        consumer_guard.set_session_state(state).await.map_err(|e| {
+            ServiceBusError::InternalError(format!("Failed to set session state: {e}"))
+        })?;
+
+        Ok(session_id)
+    }
+
     /// Get current queue information
     pub fn current_queue(&self) -> Option<&QueueInfo> {
         self.current_queue.as_ref()
@@ -194,22 +471,109 @@ impl ConsumerManager {
             .map_err(|e| ServiceBusError::MessageDeadLetterFailed(e.to_string()))
     }
 
+    /// Find a message by ID and sequence number and defer it, returning the
+    /// sequence numbers of the non-targets that stay deferred.
+    ///
+    /// The non-targets are restored once the target is deferred, so its lock
+    /// cannot expire while they are re-queued.
+    pub async fn defer_message(
+        &self,
+        message_id: &str,
+        sequence_number: i64,
+        max_position: Option<usize>,
+    ) -> ServiceBusResult<Vec<i64>> {
+        let (message, non_targets) = self
+            .find_message(message_id, sequence_number, max_position)
+            .await?;
+        let Some(message) = message else {
+            let still_deferred = non_targets.restore().await;
+            return Err(ServiceBusError::MessageDeferFailed(format!(
+                "Message {message_id} (sequence: {sequence_number}) not found{}",
+                still_deferred_note(&still_deferred)
+            )));
+        };
+
+        let deferred = self
+            .get_consumer()?
+            .lock()
+            .await
+            .defer_message(&message)
+            .await
+            .map_err(|e| e.to_string());
+        let still_deferred = non_targets.restore().await;
+
+        deferred.map_err(|e| {
+            ServiceBusError::MessageDeferFailed(format!(
+                "{e}{}",
+                still_deferred_note(&still_deferred)
+            ))
+        })?;
+        Ok(still_deferred)
+    }
+
+    /// Receive deferred messages by sequence number, locking them for settlement
+    pub async fn receive_deferred_messages(
+        &self,
+        sequence_numbers: &[i64],
+    ) -> ServiceBusResult<Vec<azservicebus::ServiceBusReceivedMessage>> {
+        let consumer = self.get_consumer()?;
+        let mut consumer_guard = consumer.lock().await;
+
+        consumer_guard
+            .receive_deferred_messages(sequence_numbers)
+            .await
+            .map_err(|e| ServiceBusError::MessageReceiveFailed(e.to_string()))
+    }
+
     /// Find a specific message by ID and sequence number (used for targeted operations)
+    ///
+    /// # Returns
+    ///
+    /// The message if found, and the guard restoring the non-targets deferred
+    /// to reach it. Settle the message before calling
+    /// [`NonTargetRestore::restore`], so its lock does not expire while the
+    /// non-targets are re-queued.
     pub async fn find_message(
         &self,
         message_id: &str,
         sequence_number: i64,
         max_position: Option<usize>,
-    ) -> ServiceBusResult<Option<azservicebus::ServiceBusReceivedMessage>> {
+    ) -> ServiceBusResult<(
+        Option<azservicebus::ServiceBusReceivedMessage>,
+        NonTargetRestore,
+    )> {
         let consumer = self.get_consumer()?;
-        let batch_size = self.batch_config.bulk_chunk_size() as u32;
-        let timeout = Duration::from_secs(self.batch_config.bulk_processing_time_secs());
+        let batch_size = self.batch_config.bulk_chunk_size();
         let max_position = max_position.unwrap_or(self.batch_config.max_messages_to_process());
 
         log::info!(
             "Searching for message {message_id} (sequence: {sequence_number}) in batches of {batch_size} up to position {max_position}"
         );
 
+        let non_targets = self.non_target_restore()?;
+        let found = self
+            .scan_for_message(
+                &consumer,
+                non_targets.releaser(),
+                message_id,
+                sequence_number,
+                max_position,
+            )
+            .await?;
+
+        Ok((found, non_targets))
+    }
+
+    async fn scan_for_message(
+        &self,
+        consumer: &Arc<Mutex<Consumer>>,
+        releaser: &NonTargetReleaser,
+        message_id: &str,
+        sequence_number: i64,
+        max_position: usize,
+    ) -> ServiceBusResult<Option<azservicebus::ServiceBusReceivedMessage>> {
+        let batch_size = self.batch_config.bulk_chunk_size() as u32;
+        let timeout = Duration::from_secs(self.batch_config.bulk_processing_time_secs());
         let mut processed_count = 0;
 
         while processed_count < max_position {
@@ -244,10 +608,10 @@ impl ConsumerManager {
                     return Ok(Some(message));
                 }
 
-                // Abandon non-target messages to keep the queue flowing
+                // Release non-target messages to keep the queue flowing
                 let mut consumer_guard = consumer.lock().await;
-                if let Err(e) = consumer_guard.abandon_message(&message).await {
-                    log::warn!("Failed to abandon non-target message: {e}");
+                if let Err(e) = releaser.release(&mut consumer_guard, &message).await {
+                    log::warn!("Failed to release non-target message: {e}");
                 }
             }
         }
@@ -258,6 +622,116 @@ impl ConsumerManager {
         Ok(None)
     }
 
+    /// Create the releaser for non-target messages received while scanning the
+    /// current queue, and the guard that restores the ones it defers
+    pub fn non_target_restore(&self) -> ServiceBusResult<NonTargetRestore> {
+        let queue = self
+            .current_queue
+            .as_ref()
+            .ok_or(ServiceBusError::ConsumerNotFound)?;
+        Ok(self.restore_guard(&queue.name, self.get_consumer()?, false))
+    }
+
+    /// Create the releaser for the newer messages a cutoff purge of
+    /// `queue_name` receives, and the guard that restores the ones it defers
+    /// with a dedicated receiver
+    pub async fn create_purge_restore(
+        &self,
+        queue_name: &str,
+    ) -> ServiceBusResult<NonTargetRestore> {
+        let receiver = self.create_peek_receiver(queue_name).await?;
+        Ok(self.restore_guard(queue_name, Arc::new(Mutex::new(receiver)), true))
+    }
+
+    fn restore_guard(
+        &self,
+        queue_name: &str,
+        consumer: Arc<Mutex<Consumer>>,
+        owns_consumer: bool,
+    ) -> NonTargetRestore {
+        let releaser = NonTargetReleaser::new(self.batch_config.non_target_strategy(), queue_name)
+            .with_ledger(self.deferred_non_targets.clone());
+        NonTargetRestore {
+            releaser: Arc::new(releaser),
+            consumer,
+            service_bus_client: Arc::clone(&self.service_bus_client),
+            queue_name: queue_name.to_string(),
+            chunk_size: self.batch_config.bulk_chunk_size(),
+            owns_consumer,
+            finished: false,
+        }
+    }
+
+    /// Create the receivers for purging `queue_name`, independent of the current consumer
+    pub async fn create_purge_receivers(
+        &self,
+        queue_name: &str,
+        older_than: Option<azure_core::time::OffsetDateTime>,
+    ) -> ServiceBusResult<Vec<Consumer>> {
+        let count = self.batch_config.purge_concurrency();
+        let receive_mode = QueuePurger::receive_mode(older_than);
+        let mut client = self.service_bus_client.lock().await;
+        let mut receivers = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let receiver = Self::create_receiver(&mut client, queue_name, receive_mode)
+                .await
+                .map_err(|e| {
+                    ServiceBusError::ConsumerCreationFailed(format!(
+                        "Failed to create purge receiver for {queue_name}: {e}"
+                    ))
+                })?;
+            receivers.push(receiver);
+        }
+
+        Ok(receivers)
+    }
+
+    /// Create a receiver for peeking through `queue_name` from its first
+    /// message, independent of the current consumer and its position.
+    /// Used by searches and exports.
+    pub async fn create_peek_receiver(&self, queue_name: &str) -> ServiceBusResult<Consumer> {
+        let mut client = self.service_bus_client.lock().await;
+        Self::create_receiver(&mut client, queue_name, ServiceBusReceiveMode::PeekLock)
+            .await
+            .map_err(|e| {
+                ServiceBusError::ConsumerCreationFailed(format!(
+                    "Failed to create peek receiver for {queue_name}: {e}"
+                ))
+            })
+    }
+
+    /// Create a receiver for a queue, subscription or dead letter queue path
+    async fn create_receiver(
+        client: &mut ServiceBusClient<BasicRetryPolicy>,
+        queue_name: &str,
+        receive_mode: ServiceBusReceiveMode,
+    ) -> Result<Consumer, azure_core::Error> {
+        match parse_subscription_path(queue_name) {
+            Some((topic, subscription)) => {
+                let options = ServiceBusReceiverOptions {
+                    receive_mode,
+                    sub_queue: if queue_name.ends_with(DEAD_LETTER_SUFFIX) {
+                        SubQueue::DeadLetter
+                    } else {
+                        SubQueue::None
+                    },
+                    ..Default::default()
+                };
+                client
+                    .create_consumer_for_subscription(topic, subscription, options)
+                    .await
+            }
+            None => {
+                let options = ServiceBusReceiverOptions {
+                    receive_mode,
+                    ..Default::default()
+                };
+                client.create_consumer_for_queue(queue_name, options).await
+            }
+        }
+    }
+
     /// Dispose current consumer
     pub async fn dispose_consumer(&mut self) -> ServiceBusResult<()> {
         if let Some(consumer) = self.current_consumer.take() {
@@ -299,3 +773,21 @@ impl ConsumerManager {
         Ok(())
     }
 }
+
+/// Whether a receiver error means the entity only accepts session receivers
+fn is_session_required_error(error: &str) -> bool {
+    let error = error.to_ascii_lowercase();
+    error.contains("requires session") || error.contains("non-sessionful")
+}
+
+/// Sentence appended to an error about non-targets that stay deferred
+fn still_deferred_note(still_deferred: &[i64]) -> String {
+    if still_deferred.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "; {} messages in front of it stay deferred: {still_deferred:?}",
+            still_deferred.len()
+        )
+    }
+}
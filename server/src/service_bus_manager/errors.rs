@@ -90,6 +90,8 @@ pub enum ServiceBusError {
     MessageAbandonFailed(String),
     /// Message dead letter operation failed
     MessageDeadLetterFailed(String),
+    /// Message defer operation failed
+    MessageDeferFailed(String),
 
     /// Bulk operation failed completely
     BulkOperationFailed(String),
@@ -109,6 +111,10 @@ pub enum ServiceBusError {
     QueueSwitchFailed(String),
     /// Queue name format is invalid
     InvalidQueueName(String),
+    /// Queue requires sessions and must be opened with a session ID
+    SessionRequired(String),
+    /// Operation needs a session but the current consumer is not bound to one
+    NoActiveSession,
 
     /// Configuration value is missing or invalid
     ConfigurationError(String),
@@ -180,6 +186,7 @@ impl fmt::Display for ServiceBusError {
             ServiceBusError::MessageDeadLetterFailed(msg) => {
                 write!(f, "Message dead letter failed: {msg}")
             }
+            ServiceBusError::MessageDeferFailed(msg) => write!(f, "Message defer failed: {msg}"),
 
             ServiceBusError::BulkOperationFailed(msg) => {
                 write!(f, "Bulk operation failed: {msg}")
@@ -201,6 +208,12 @@ impl fmt::Display for ServiceBusError {
             ServiceBusError::QueueNotFound(queue) => write!(f, "Queue not found: {queue}"),
             ServiceBusError::QueueSwitchFailed(msg) => write!(f, "Queue switch failed: {msg}"),
             ServiceBusError::InvalidQueueName(queue) => write!(f, "Invalid queue name: {queue}"),
+            ServiceBusError::SessionRequired(queue) => {
+                write!(f, "Queue requires a session: {queue}")
+            }
+            ServiceBusError::NoActiveSession => {
+                write!(f, "The current queue is not opened with a session")
+            }
 
             ServiceBusError::ConfigurationError(msg) => write!(f, "Configuration error: {msg}"),
             ServiceBusError::InvalidConfiguration(msg) => {
@@ -1,5 +1,5 @@
 use super::AzureAdConfig;
-use super::azure_management_client::StatisticsConfig;
+use super::azure_management_client::{AzureManagementClient, StatisticsConfig};
 use super::command_handlers::*;
 use super::commands::ServiceBusCommand;
 use super::consumer_manager::ConsumerManager;
@@ -7,7 +7,7 @@ use super::errors::{ServiceBusError, ServiceBusResult};
 use super::producer_manager::ProducerManager;
 use super::queue_statistics_service::QueueStatisticsService;
 use super::responses::ServiceBusResponse;
-use super::types::QueueInfo;
+use super::types::{QueueInfo, TopicInfo};
 use crate::bulk_operations::{BulkOperationHandler, types::BatchConfig};
 use azservicebus::{ServiceBusClient, ServiceBusClientOptions, core::BasicRetryPolicy};
 use std::sync::Arc;
@@ -47,6 +47,7 @@ use tokio::sync::Mutex;
 ///     ServiceBusCommand::SwitchQueue {
 ///         queue_name: "my-queue".to_string(),
 ///         queue_type: "Queue".to_string(),
+///         session_id: None,
 ///     }
 /// ).await;
 ///
@@ -216,11 +217,26 @@ impl ServiceBusManager {
             ServiceBusCommand::SwitchQueue {
                 queue_name,
                 queue_type,
+                session_id,
             } => {
                 self.queue_handler
-                    .handle_switch_queue(queue_name, queue_type)
+                    .handle_switch_queue(queue_name, queue_type, session_id)
                     .await
             }
+            ServiceBusCommand::ListSessions {
+                queue_name,
+                max_sessions,
+            } => {
+                self.queue_handler
+                    .handle_list_sessions(queue_name, max_sessions)
+                    .await
+            }
+            ServiceBusCommand::GetSessionState => {
+                self.queue_handler.handle_get_session_state().await
+            }
+            ServiceBusCommand::SetSessionState { state } => {
+                self.queue_handler.handle_set_session_state(state).await
+            }
             ServiceBusCommand::GetCurrentQueue => {
                 self.queue_handler.handle_get_current_queue().await
             }
@@ -232,6 +248,51 @@ impl ServiceBusManager {
                     .handle_get_queue_statistics(queue_name, queue_type)
                     .await
             }
+            ServiceBusCommand::GetQueueDetails { queue_name } => {
+                self.queue_handler
+                    .handle_get_queue_details(queue_name)
+                    .await
+            }
+            ServiceBusCommand::CreateQueue {
+                queue_name,
+                settings,
+            } => {
+                self.queue_handler
+                    .handle_create_queue(queue_name, settings)
+                    .await
+            }
+            ServiceBusCommand::UpdateQueue {
+                queue_name,
+                settings,
+            } => {
+                self.queue_handler
+                    .handle_update_queue(queue_name, settings)
+                    .await
+            }
+            ServiceBusCommand::DeleteQueue { queue_name } => {
+                self.queue_handler.handle_delete_queue(queue_name).await
+            }
+            ServiceBusCommand::ListSubscriptionRules { subscription_path } => {
+                self.queue_handler
+                    .handle_list_subscription_rules(subscription_path)
+                    .await
+            }
+            ServiceBusCommand::SaveSubscriptionRule {
+                subscription_path,
+                rule,
+            } => {
+                self.queue_handler
+                    .handle_save_subscription_rule(subscription_path, rule)
+                    .await
+            }
+            ServiceBusCommand::DeleteSubscriptionRule {
+                subscription_path,
+                rule_name,
+            } => {
+                self.queue_handler
+                    .handle_delete_subscription_rule(subscription_path, rule_name)
+                    .await
+            }
 
             // Message retrieval commands
             ServiceBusCommand::PeekMessages {
@@ -247,6 +308,20 @@ impl ServiceBusManager {
                     .handle_receive_messages(max_count)
                     .await
             }
+            ServiceBusCommand::DeferMessage {
+                message_id,
+                sequence_number,
+                max_position,
+            } => {
+                self.message_handler
+                    .handle_defer_message(message_id, sequence_number, max_position)
+                    .await
+            }
+            ServiceBusCommand::ReceiveDeferredMessages { sequence_numbers } => {
+                self.message_handler
+                    .handle_receive_deferred_messages(sequence_numbers)
+                    .await
+            }
             ServiceBusCommand::CompleteMessage { message_id } => {
                 self.message_handler
                     .handle_complete_message(message_id)
@@ -291,30 +366,100 @@ impl ServiceBusManager {
                     .handle_bulk_dead_letter(message_ids, reason, error_description)
                     .await
             }
+            ServiceBusCommand::SettleDeferredMessages {
+                message_ids,
+                action,
+            } => {
+                self.bulk_handler
+                    .handle_settle_deferred_messages(message_ids, action)
+                    .await
+            }
+            ServiceBusCommand::PurgeQueue {
+                queue_name,
+                older_than,
+                progress,
+                cancel_token,
+            } => {
+                self.bulk_handler
+                    .handle_purge_queue(queue_name, older_than, progress, cancel_token)
+                    .await
+            }
+            ServiceBusCommand::SelectPurgeTargets {
+                queue_name,
+                older_than,
+            } => {
+                self.bulk_handler
+                    .handle_select_purge_targets(queue_name, older_than)
+                    .await
+            }
+            ServiceBusCommand::SearchMessages {
+                queue_name,
+                filter,
+                max_matches,
+                progress,
+                cancel_token,
+            } => {
+                self.bulk_handler
+                    .handle_search_messages(queue_name, filter, max_matches, progress, cancel_token)
+                    .await
+            }
+            ServiceBusCommand::ExportMessages {
+                queue_name,
+                path,
+                format,
+                scope,
+                progress,
+                cancel_token,
+            } => {
+                self.bulk_handler
+                    .handle_export_messages(queue_name, path, format, scope, progress, cancel_token)
+                    .await
+            }
+            ServiceBusCommand::ImportMessages {
+                queue_name,
+                path,
+                options,
+                progress,
+                cancel_token,
+            } => {
+                self.bulk_handler
+                    .handle_import_messages(queue_name, path, options, progress, cancel_token)
+                    .await
+            }
             ServiceBusCommand::BulkSend {
                 message_ids,
                 target_queue,
+                target_namespace,
                 should_delete_source,
                 repeat_count,
                 max_position,
+                resend_options,
             } => {
                 self.bulk_handler
                     .handle_bulk_send(
                         message_ids,
                         target_queue,
+                        target_namespace,
                         should_delete_source,
                         repeat_count,
                         max_position,
+                        resend_options,
                     )
                     .await
             }
             ServiceBusCommand::BulkSendPeeked {
                 messages_data,
                 target_queue,
+                target_namespace,
                 repeat_count,
             } => {
                 self.bulk_handler
-                    .handle_bulk_send_peeked(messages_data, target_queue, repeat_count)
+                    .handle_bulk_send_peeked(
+                        messages_data,
+                        target_queue,
+                        target_namespace,
+                        repeat_count,
+                    )
                     .await
             }
 
@@ -335,6 +480,23 @@ impl ServiceBusManager {
                     .handle_send_messages(queue_name, messages)
                     .await
             }
+            ServiceBusCommand::ScheduleMessages {
+                queue_name,
+                messages,
+                enqueue_time,
+            } => {
+                self.send_handler
+                    .handle_schedule_messages(queue_name, messages, enqueue_time)
+                    .await
+            }
+            ServiceBusCommand::CancelScheduledMessages {
+                queue_name,
+                sequence_numbers,
+            } => {
+                self.send_handler
+                    .handle_cancel_scheduled_messages(queue_name, sequence_numbers)
+                    .await
+            }
 
             // Status and health commands
             ServiceBusCommand::GetConnectionStatus => {
@@ -372,6 +534,14 @@ impl ServiceBusManager {
         config.list_queues_azure_ad(&http_client).await
     }
 
+    pub async fn list_topics_azure_ad(
+        config: &AzureAdConfig,
+    ) -> Result<Vec<TopicInfo>, Box<dyn std::error::Error>> {
+        // Create a default HTTP client for backward compatibility
+        let http_client = reqwest::Client::new();
+        config.list_topics_azure_ad(&http_client).await
+    }
+
     pub async fn list_namespaces_azure_ad(
         config: &AzureAdConfig,
     ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
@@ -380,6 +550,19 @@ impl ServiceBusManager {
         config.list_namespaces_azure_ad(&http_client).await
     }
 
+    /// Clone of the Azure Management API client.
+    ///
+    /// Commands run one at a time behind the manager's lock, so callers that
+    /// fetch many entities in parallel use the client directly instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ServiceBusError::ConfigurationError`] when the management API
+    /// is unavailable, e.g. with connection string authentication
+    pub async fn management_client(&self) -> ServiceBusResult<AzureManagementClient> {
+        self.queue_handler.management_client().await
+    }
+
     // Helper methods with clean interfaces
     pub async fn get_current_queue(&self) -> Option<QueueInfo> {
         let consumer = self.consumer_manager.lock().await;
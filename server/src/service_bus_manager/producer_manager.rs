@@ -1,9 +1,12 @@
 use super::errors::{ServiceBusError, ServiceBusResult};
-use super::types::{MessageData, OperationStats};
+use super::types::{BulkSendStats, MessageData, OperationStats, send_target};
 use crate::producer::{Producer, ServiceBusClientProducerExt};
 use azservicebus::{
     ServiceBusClient, ServiceBusMessage, ServiceBusSenderOptions, core::BasicRetryPolicy,
 };
+use azure_core::time::OffsetDateTime;
+use fe2o3_amqp_types::messaging::ApplicationProperties;
+use fe2o3_amqp_types::primitives::SimpleValue;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -35,7 +38,7 @@ impl ProducerManager {
         log::info!(
             "Sending message to queue '{}' (content: {} bytes)",
             queue_name,
-            message.content.len()
+            message.body_len()
         );
 
         // Get or create producer for the queue
@@ -60,6 +63,88 @@ impl ProducerManager {
         Ok(())
     }
 
+    /// Schedule messages to be enqueued on a queue at `enqueue_time`
+    ///
+    /// Returns the sequence numbers assigned to the scheduled messages, which
+    /// are needed to cancel them later.
+    pub async fn schedule_messages(
+        &mut self,
+        queue_name: &str,
+        messages: Vec<MessageData>,
+        enqueue_time: OffsetDateTime,
+    ) -> ServiceBusResult<Vec<i64>> {
+        log::info!(
+            "Scheduling {} messages on queue '{}' for {}",
+            messages.len(),
+            queue_name,
+            enqueue_time
+        );
+
+        if messages.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let producer = self.get_or_create_producer(queue_name).await?;
+        let service_bus_messages = messages
+            .iter()
+            .map(|message| self.create_service_bus_message(message))
+            .collect::<ServiceBusResult<Vec<_>>>()?;
+
+        let mut sequence_numbers = Vec::with_capacity(service_bus_messages.len());
+        for batch in service_bus_messages.chunks(self.batch_config.bulk_chunk_size()) {
+            let scheduled = producer
+                .lock()
+                .await
+                .schedule_messages(batch.to_vec(), enqueue_time)
+                .await
+                .map_err(|e| {
+                    ServiceBusError::MessageSendFailed(format!(
+                        "Failed to schedule messages on queue {queue_name}: {e}"
+                    ))
+                })?;
+            sequence_numbers.extend(scheduled);
+        }
+
+        log::info!(
+            "Scheduled {} messages on queue {queue_name}",
+            sequence_numbers.len()
+        );
+        Ok(sequence_numbers)
+    }
+
+    /// Cancel scheduled messages on a queue by sequence number
+    pub async fn cancel_scheduled_messages(
+        &mut self,
+        queue_name: &str,
+        sequence_numbers: &[i64],
+    ) -> ServiceBusResult<()> {
+        log::info!(
+            "Cancelling {} scheduled messages on queue '{}'",
+            sequence_numbers.len(),
+            queue_name
+        );
+
+        if sequence_numbers.is_empty() {
+            return Ok(());
+        }
+
+        let producer = self.get_or_create_producer(queue_name).await?;
+        for batch in sequence_numbers.chunks(self.batch_config.bulk_chunk_size()) {
+            producer
+                .lock()
+                .await
+                .cancel_scheduled_messages(batch.to_vec())
+                .await
+                .map_err(|e| {
+                    ServiceBusError::MessageSendFailed(format!(
+                        "Failed to cancel scheduled messages on queue {queue_name}: {e}"
+                    ))
+                })?;
+        }
+
+        Ok(())
+    }
+
     /// Send multiple messages to a queue
     pub async fn send_messages(
         &mut self,
@@ -190,13 +275,16 @@ impl ProducerManager {
         Ok(stats)
     }
 
-    /// Send raw message data (used for bulk operations with existing message data)
-    pub async fn send_raw_messages(
+    /// Send existing message data in bulk (used for bulk operations with peeked or received messages).
+    ///
+    /// The result tells which of `messages_data` were sent, so callers can
+    /// settle the sources of those messages only.
+    pub async fn send_bulk_messages(
         &mut self,
         queue_name: &str,
-        messages_data: Vec<Vec<u8>>,
+        messages_data: Vec<MessageData>,
         repeat_count: usize,
-    ) -> ServiceBusResult<OperationStats> {
+    ) -> ServiceBusResult<BulkSendStats> {
         log::info!(
             "Sending {} messages to queue '{}' with repeat count {}",
             messages_data.len(),
@@ -204,9 +292,9 @@ impl ProducerManager {
             repeat_count
         );
 
-        let mut stats = OperationStats::new();
+        let mut result = BulkSendStats::default();
         let total_messages = messages_data.len() * repeat_count;
-        stats.total = total_messages;
+        result.stats.total = total_messages;
 
         // Check if this is a DLQ operation
         if queue_name.ends_with("/$deadletterqueue") {
@@ -214,19 +302,34 @@ impl ProducerManager {
             // We cannot directly send to DLQ - this needs to be done via dead_letter_message operation
             // on received messages, not by sending new messages
             log::error!("Cannot send messages directly to DLQ: {queue_name}");
-            stats.failed = total_messages;
-            return Ok(stats);
+            result.stats.failed = total_messages;
+            for index in 0..messages_data.len() {
+                result.add_failed_message(index);
+            }
+            return Ok(result);
         }
 
         // Get or create producer for the queue
         let producer = self.get_or_create_producer(queue_name).await?;
 
-        // Convert raw data to ServiceBusMessage objects
+        // Convert MessageData to ServiceBusMessage objects, remembering which
+        // of the given messages each one is a copy of
         let mut all_messages = Vec::new();
+        let mut sources = Vec::new();
+        let mut failed_count = 0;
         for _ in 0..repeat_count {
-            for data in &messages_data {
-                let message = azservicebus::ServiceBusMessage::new(data.to_vec());
-                all_messages.push(message);
+            for (index, data) in messages_data.iter().enumerate() {
+                match self.create_service_bus_message(data) {
+                    Ok(message) => {
+                        all_messages.push(message);
+                        sources.push(index);
+                    }
+                    Err(e) => {
+                        log::error!("Failed to create ServiceBusMessage: {e}");
+                        failed_count += 1;
+                        result.add_failed_message(index);
+                    }
+                }
             }
         }
 
@@ -240,9 +343,12 @@ impl ProducerManager {
         };
 
         let mut successful_count = 0;
-        let mut failed_count = 0;
 
-        for (batch_index, batch) in all_messages.chunks(batch_size).enumerate() {
+        for (batch_index, (batch, batch_sources)) in all_messages
+            .chunks(batch_size)
+            .zip(sources.chunks(batch_size))
+            .enumerate()
+        {
             log::debug!(
                 "Sending batch {} of {} messages",
                 batch_index + 1,
@@ -256,6 +362,9 @@ impl ProducerManager {
                 }
                 Err(e) => {
                     failed_count += batch.len();
+                    for index in batch_sources {
+                        result.add_failed_message(*index);
+                    }
                     log::error!("Failed to send batch of {} messages: {}", batch.len(), e);
                 }
             }
@@ -267,24 +376,27 @@ impl ProducerManager {
             }
         }
 
-        stats.successful = successful_count;
-        stats.failed = failed_count;
+        result.stats.successful = successful_count;
+        result.stats.failed = failed_count;
 
         log::info!(
             "Bulk send completed: {} successful, {} failed out of {} total",
-            stats.successful,
-            stats.failed,
-            stats.total
+            result.stats.successful,
+            result.stats.failed,
+            result.stats.total
         );
 
-        Ok(stats)
+        Ok(result)
     }
 
-    /// Get or create a producer for the specified queue
+    /// Get or create a producer for the specified queue or topic
     async fn get_or_create_producer(
         &mut self,
         queue_name: &str,
     ) -> ServiceBusResult<Arc<Mutex<Producer>>> {
+        // Subscriptions cannot be sent to directly, route to their topic instead
+        let queue_name = send_target(queue_name);
+
         // Check if producer already exists
         if let Some(producer) = self.producers.get(queue_name) {
             return Ok(Arc::clone(producer));
@@ -310,19 +422,44 @@ impl ProducerManager {
         Ok(producer_arc)
     }
 
-    /// Convert MessageData to ServiceBusMessage
+    /// Convert MessageData to ServiceBusMessage, applying system and application properties
     fn create_service_bus_message(
         &self,
         message_data: &MessageData,
     ) -> ServiceBusResult<ServiceBusMessage> {
-        let message = ServiceBusMessage::new(message_data.content.clone().into_bytes());
+        let mut message = ServiceBusMessage::new(message_data.body_bytes());
+        let system = &message_data.system_properties;
 
-        // Note: Application properties not currently supported in this version
-        // If needed, this can be implemented when the azservicebus crate supports it
-        if message_data.properties.is_some() {
-            log::debug!(
-                "Application properties provided but not yet supported by azservicebus crate"
-            );
+        if let Some(message_id) = &system.message_id {
+            message
+                .set_message_id(message_id.clone())
+                .map_err(|e| invalid_property("MessageId", e))?;
+        }
+        if let Some(session_id) = &system.session_id {
+            message
+                .set_session_id(session_id.clone())
+                .map_err(|e| invalid_property("SessionId", e))?;
+        }
+        if let Some(time_to_live) = system.time_to_live {
+            message
+                .set_time_to_live(time_to_live)
+                .map_err(|e| invalid_property("TimeToLive", e))?;
+        }
+        if let Some(enqueue_time) = system.scheduled_enqueue_time {
+            message.set_scheduled_enqueue_time(enqueue_time);
+        }
+        message.set_correlation_id(system.correlation_id.clone());
+        message.set_content_type(system.content_type.clone());
+        message.set_subject(system.subject.clone());
+        message.set_reply_to(system.reply_to.clone());
+        message.set_to(system.to.clone());
+
+        if !message_data.application_properties.is_empty() {
+            let mut properties = ApplicationProperties::default();
+            for (key, value) in &message_data.application_properties {
+                properties.insert(key.clone(), SimpleValue::from(value.clone()));
+            }
+            *message.application_properties_mut() = Some(properties);
         }
 
         Ok(message)
@@ -399,3 +536,7 @@ impl ProducerManager {
         Ok(())
     }
 }
+
+fn invalid_property(name: &str, error: impl std::fmt::Display) -> ServiceBusError {
+    ServiceBusError::MessageSendFailed(format!("Invalid {name} property: {error}"))
+}
@@ -0,0 +1,542 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Longest duration Service Bus reports, `TimeSpan.MaxValue`; it stands for "never"
+const UNLIMITED_DURATION: Duration = Duration::from_secs(10_675_199 * 24 * 60 * 60);
+
+/// Runtime counts and configuration of a queue or subscription, as reported
+/// by the Azure Management API.
+///
+/// Every field is optional because subscriptions lack some queue settings
+/// (size, partitioning, duplicate detection) and older API versions omit others.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueueDetails {
+    /// Queue name, or `topic/Subscriptions/subscription` path
+    pub entity_path: String,
+    /// Entity status such as `Active`, `Disabled` or `ReceiveDisabled`
+    pub status: Option<String>,
+
+    /// All messages, including dead-lettered, scheduled and transfer messages
+    pub message_count: Option<u64>,
+    pub active_message_count: Option<u64>,
+    pub dead_letter_message_count: Option<u64>,
+    pub scheduled_message_count: Option<u64>,
+    pub transfer_message_count: Option<u64>,
+    pub transfer_dead_letter_message_count: Option<u64>,
+    pub size_in_bytes: Option<u64>,
+    pub max_size_in_megabytes: Option<u64>,
+
+    /// Deliveries after which a message is dead-lettered
+    pub max_delivery_count: Option<u32>,
+    pub lock_duration: Option<Duration>,
+    /// Time to live of messages sent without one; see [`Self::is_unlimited`]
+    pub default_message_time_to_live: Option<Duration>,
+    pub duplicate_detection_history_time_window: Option<Duration>,
+    /// Idle time after which the entity is deleted; see [`Self::is_unlimited`]
+    pub auto_delete_on_idle: Option<Duration>,
+    pub requires_session: Option<bool>,
+    pub requires_duplicate_detection: Option<bool>,
+    pub enable_partitioning: Option<bool>,
+    pub dead_lettering_on_message_expiration: Option<bool>,
+    /// Entity messages are forwarded to; `None` when not forwarding
+    pub forward_to: Option<String>,
+    /// Entity dead-lettered messages are forwarded to; `None` when not forwarding
+    pub forward_dead_lettered_messages_to: Option<String>,
+
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Last time the entity was used; `None` if it never was
+    pub accessed_at: Option<DateTime<Utc>>,
+}
+
+impl QueueDetails {
+    /// Parses the body of a management API `GET` on a queue or subscription
+    pub fn from_management_json(entity_path: &str, json: &str) -> Result<Self, serde_json::Error> {
+        let response: EntityResponse = serde_json::from_str(json)?;
+        let properties = response.properties;
+        let counts = properties.count_details.unwrap_or_default();
+
+        Ok(Self {
+            entity_path: entity_path.to_string(),
+            status: properties.status,
+            message_count: properties.message_count.map(non_negative),
+            active_message_count: counts.active_message_count.map(non_negative),
+            dead_letter_message_count: counts.dead_letter_message_count.map(non_negative),
+            scheduled_message_count: counts.scheduled_message_count.map(non_negative),
+            transfer_message_count: counts.transfer_message_count.map(non_negative),
+            transfer_dead_letter_message_count: counts
+                .transfer_dead_letter_message_count
+                .map(non_negative),
+            size_in_bytes: properties.size_in_bytes.map(non_negative),
+            max_size_in_megabytes: properties.max_size_in_megabytes.map(non_negative),
+            max_delivery_count: properties.max_delivery_count,
+            lock_duration: parse_duration_property(properties.lock_duration),
+            default_message_time_to_live: parse_duration_property(
+                properties.default_message_time_to_live,
+            ),
+            duplicate_detection_history_time_window: parse_duration_property(
+                properties.duplicate_detection_history_time_window,
+            ),
+            auto_delete_on_idle: parse_duration_property(properties.auto_delete_on_idle),
+            requires_session: properties.requires_session,
+            requires_duplicate_detection: properties.requires_duplicate_detection,
+            enable_partitioning: properties.enable_partitioning,
+            dead_lettering_on_message_expiration: properties.dead_lettering_on_message_expiration,
+            forward_to: properties.forward_to.filter(|to| !to.is_empty()),
+            forward_dead_lettered_messages_to: properties
+                .forward_dead_lettered_messages_to
+                .filter(|to| !to.is_empty()),
+            created_at: parse_timestamp(properties.created_at),
+            updated_at: parse_timestamp(properties.updated_at),
+            accessed_at: parse_timestamp(properties.accessed_at),
+        })
+    }
+
+    /// Whether a duration is Service Bus' "never", e.g. a default TTL that was not set
+    pub fn is_unlimited(duration: Duration) -> bool {
+        duration >= UNLIMITED_DURATION
+    }
+}
+
+/// Whether an entity accepts sends and receives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityStatus {
+    Active,
+    Disabled,
+    SendDisabled,
+    ReceiveDisabled,
+}
+
+impl EntityStatus {
+    /// Name used by the management API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "Active",
+            Self::Disabled => "Disabled",
+            Self::SendDisabled => "SendDisabled",
+            Self::ReceiveDisabled => "ReceiveDisabled",
+        }
+    }
+}
+
+impl fmt::Display for EntityStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EntityStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value
+            .trim()
+            .to_ascii_lowercase()
+            .replace(['-', '_'], "")
+            .as_str()
+        {
+            "active" => Ok(Self::Active),
+            "disabled" => Ok(Self::Disabled),
+            "senddisabled" => Ok(Self::SendDisabled),
+            "receivedisabled" => Ok(Self::ReceiveDisabled),
+            other => Err(format!(
+                "unknown status '{other}', expected Active, Disabled, SendDisabled or ReceiveDisabled"
+            )),
+        }
+    }
+}
+
+/// Queue settings to create a queue with or to change on an existing one.
+///
+/// `None` keeps the current value, or the Service Bus default when creating.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueueSettings {
+    /// Deliveries after which a message is dead-lettered
+    pub max_delivery_count: Option<u32>,
+    pub lock_duration: Option<Duration>,
+    /// Durations beyond the Service Bus maximum mean "never expire"
+    pub default_message_time_to_live: Option<Duration>,
+    /// Entity to forward messages to; an empty name stops forwarding
+    pub forward_to: Option<String>,
+    /// Entity to forward dead-lettered messages to; an empty name stops forwarding
+    pub forward_dead_lettered_messages_to: Option<String>,
+    pub status: Option<EntityStatus>,
+}
+
+impl QueueSettings {
+    /// Whether no setting is given
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Writes the given settings into the `properties` object of a
+    /// management API `PUT`, leaving the other properties untouched
+    pub fn apply_to(&self, properties: &mut Map<String, Value>) {
+        if let Some(max) = self.max_delivery_count {
+            properties.insert("maxDeliveryCount".to_string(), max.into());
+        }
+        if let Some(lock) = self.lock_duration {
+            properties.insert(
+                "lockDuration".to_string(),
+                format_iso8601_duration(lock).into(),
+            );
+        }
+        if let Some(ttl) = self.default_message_time_to_live {
+            properties.insert(
+                "defaultMessageTimeToLive".to_string(),
+                format_iso8601_duration(ttl).into(),
+            );
+        }
+        for (key, target) in [
+            ("forwardTo", &self.forward_to),
+            (
+                "forwardDeadLetteredMessagesTo",
+                &self.forward_dead_lettered_messages_to,
+            ),
+        ] {
+            match target.as_deref().map(str::trim) {
+                // The PUT replaces the entity, so a missing target ends forwarding
+                Some("") => {
+                    properties.remove(key);
+                }
+                Some(target) => {
+                    properties.insert(key.to_string(), target.into());
+                }
+                None => {}
+            }
+        }
+        if let Some(status) = self.status {
+            properties.insert("status".to_string(), status.as_str().into());
+        }
+    }
+}
+
+/// Formats a duration for the management API, e.g. `PT30S` or `P14D`.
+///
+/// Durations beyond the Service Bus maximum are capped to it.
+pub fn format_iso8601_duration(duration: Duration) -> String {
+    let seconds = duration.min(UNLIMITED_DURATION).as_secs();
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds % 86_400 / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60,
+    );
+
+    let mut formatted = "P".to_string();
+    if days > 0 {
+        formatted.push_str(&format!("{days}D"));
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 || days == 0 {
+        formatted.push('T');
+        for (value, unit) in [(hours, 'H'), (minutes, 'M'), (seconds, 'S')] {
+            if value > 0 {
+                formatted.push_str(&format!("{value}{unit}"));
+            }
+        }
+        if formatted.ends_with('T') {
+            formatted.push_str("0S");
+        }
+    }
+    formatted
+}
+
+/// Parses an ISO 8601 duration as used by the management API, e.g. `PT1M`,
+/// `P14D` or `P10675199DT2H48M5.4775807S`.
+///
+/// Years and months are rejected because their length is ambiguous.
+pub fn parse_iso8601_duration(value: &str) -> Option<Duration> {
+    let rest = value.trim().strip_prefix('P')?;
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (rest, None),
+    };
+    if date.is_empty() && time.is_none_or(str::is_empty) {
+        return None;
+    }
+
+    let mut seconds = 0.0;
+    for (number, unit) in duration_components(date)? {
+        seconds += number
+            * match unit {
+                'W' => 7.0 * 86_400.0,
+                'D' => 86_400.0,
+                _ => return None,
+            };
+    }
+    if let Some(time) = time {
+        for (number, unit) in duration_components(time)? {
+            seconds += number
+                * match unit {
+                    'H' => 3_600.0,
+                    'M' => 60.0,
+                    'S' => 1.0,
+                    _ => return None,
+                };
+        }
+    }
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Splits `1H30M` into `[(1, 'H'), (30, 'M')]`
+fn duration_components(part: &str) -> Option<Vec<(f64, char)>> {
+    let mut components = Vec::new();
+    let mut number = String::new();
+    for c in part.chars() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+        } else {
+            components.push((number.parse().ok()?, c));
+            number.clear();
+        }
+    }
+    // A trailing number without a unit is malformed
+    number.is_empty().then_some(components)
+}
+
+fn parse_duration_property(value: Option<String>) -> Option<Duration> {
+    let value = value?;
+    let duration = parse_iso8601_duration(&value);
+    if duration.is_none() {
+        log::warn!("Ignoring unparseable duration from management API: {value}");
+    }
+    duration
+}
+
+/// Parses management API timestamps, which may lack a time zone; the
+/// `0001-01-01` placeholder of entities never accessed becomes `None`
+fn parse_timestamp(value: Option<String>) -> Option<DateTime<Utc>> {
+    let value = value?;
+    let timestamp = DateTime::parse_from_rfc3339(&value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(&value, "%Y-%m-%dT%H:%M:%S%.f")
+                .map(|timestamp| timestamp.and_utc())
+        })
+        .ok()?;
+    (timestamp.timestamp() > 0).then_some(timestamp)
+}
+
+fn non_negative(value: i64) -> u64 {
+    value.max(0) as u64
+}
+
+#[derive(Debug, Deserialize)]
+struct EntityResponse {
+    properties: EntityProperties,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EntityProperties {
+    count_details: Option<CountDetails>,
+    message_count: Option<i64>,
+    size_in_bytes: Option<i64>,
+    max_size_in_megabytes: Option<i64>,
+    max_delivery_count: Option<u32>,
+    lock_duration: Option<String>,
+    default_message_time_to_live: Option<String>,
+    duplicate_detection_history_time_window: Option<String>,
+    auto_delete_on_idle: Option<String>,
+    requires_session: Option<bool>,
+    requires_duplicate_detection: Option<bool>,
+    enable_partitioning: Option<bool>,
+    dead_lettering_on_message_expiration: Option<bool>,
+    forward_to: Option<String>,
+    forward_dead_lettered_messages_to: Option<String>,
+    status: Option<String>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    accessed_at: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CountDetails {
+    active_message_count: Option<i64>,
+    dead_letter_message_count: Option<i64>,
+    scheduled_message_count: Option<i64>,
+    transfer_message_count: Option<i64>,
+    transfer_dead_letter_message_count: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iso8601_duration() {
+        assert_eq!(
+            parse_iso8601_duration("PT1M"),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_iso8601_duration("PT30S"),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_iso8601_duration("P14D"),
+            Some(Duration::from_secs(14 * 86_400))
+        );
+        assert_eq!(
+            parse_iso8601_duration("P1DT2H3M4.5S"),
+            Some(Duration::from_secs_f64(93_784.5))
+        );
+        assert_eq!(
+            parse_iso8601_duration("P1W"),
+            Some(Duration::from_secs(7 * 86_400))
+        );
+
+        let max = parse_iso8601_duration("P10675199DT2H48M5.4775807S").unwrap();
+        assert!(QueueDetails::is_unlimited(max));
+        assert!(!QueueDetails::is_unlimited(Duration::from_secs(
+            14 * 86_400
+        )));
+
+        for invalid in ["", "P", "PT", "1M", "P1M", "P1Y", "PT5", "PTxS"] {
+            assert_eq!(parse_iso8601_duration(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_format_iso8601_duration_round_trips() {
+        for (duration, formatted) in [
+            (Duration::from_secs(30), "PT30S"),
+            (Duration::from_secs(90), "PT1M30S"),
+            (Duration::from_secs(14 * 86_400), "P14D"),
+            (Duration::from_secs(86_400 + 3_600), "P1DT1H"),
+            (Duration::ZERO, "PT0S"),
+        ] {
+            assert_eq!(format_iso8601_duration(duration), formatted);
+            assert_eq!(parse_iso8601_duration(formatted), Some(duration));
+        }
+        assert!(QueueDetails::is_unlimited(
+            parse_iso8601_duration(&format_iso8601_duration(Duration::MAX)).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_settings_apply_to_properties() {
+        let mut properties = serde_json::json!({
+            "maxDeliveryCount": 10,
+            "lockDuration": "PT1M",
+            "forwardTo": "audit",
+            "requiresSession": false
+        });
+        let properties = properties.as_object_mut().unwrap();
+
+        QueueSettings {
+            max_delivery_count: Some(3),
+            default_message_time_to_live: Some(Duration::from_secs(86_400)),
+            forward_to: Some(String::new()),
+            forward_dead_lettered_messages_to: Some("dlq-archive".to_string()),
+            status: Some("send-disabled".parse().unwrap()),
+            ..QueueSettings::default()
+        }
+        .apply_to(properties);
+
+        assert_eq!(properties["maxDeliveryCount"], 3);
+        assert_eq!(properties["lockDuration"], "PT1M");
+        assert_eq!(properties["defaultMessageTimeToLive"], "P1D");
+        assert!(!properties.contains_key("forwardTo"));
+        assert_eq!(properties["forwardDeadLetteredMessagesTo"], "dlq-archive");
+        assert_eq!(properties["status"], "SendDisabled");
+        assert_eq!(properties["requiresSession"], false);
+
+        assert!(QueueSettings::default().is_empty());
+        assert!("paused".parse::<EntityStatus>().is_err());
+    }
+
+    #[test]
+    fn test_from_management_json_for_queue() {
+        let json = r#"{
+            "id": "/subscriptions/x/resourceGroups/rg/providers/Microsoft.ServiceBus/namespaces/ns/queues/orders",
+            "name": "orders",
+            "properties": {
+                "countDetails": {
+                    "activeMessageCount": 12,
+                    "deadLetterMessageCount": 3,
+                    "scheduledMessageCount": 2,
+                    "transferMessageCount": 0,
+                    "transferDeadLetterMessageCount": -1
+                },
+                "createdAt": "2024-03-01T10:00:00.1234567Z",
+                "updatedAt": "2024-03-02T11:00:00Z",
+                "accessedAt": "0001-01-01T00:00:00",
+                "sizeInBytes": 4096,
+                "messageCount": 17,
+                "maxSizeInMegabytes": 1024,
+                "lockDuration": "PT30S",
+                "maxDeliveryCount": 10,
+                "requiresDuplicateDetection": true,
+                "duplicateDetectionHistoryTimeWindow": "PT10M",
+                "requiresSession": false,
+                "defaultMessageTimeToLive": "P10675199DT2H48M5.4775807S",
+                "deadLetteringOnMessageExpiration": true,
+                "enablePartitioning": false,
+                "autoDeleteOnIdle": "P10675199DT2H48M5.4775807S",
+                "status": "Active",
+                "forwardTo": "",
+                "forwardDeadLetteredMessagesTo": "dlq-archive"
+            }
+        }"#;
+
+        let details = QueueDetails::from_management_json("orders", json).unwrap();
+        assert_eq!(details.entity_path, "orders");
+        assert_eq!(details.status.as_deref(), Some("Active"));
+        assert_eq!(details.active_message_count, Some(12));
+        assert_eq!(details.dead_letter_message_count, Some(3));
+        assert_eq!(details.scheduled_message_count, Some(2));
+        assert_eq!(details.transfer_dead_letter_message_count, Some(0));
+        assert_eq!(details.size_in_bytes, Some(4096));
+        assert_eq!(details.max_delivery_count, Some(10));
+        assert_eq!(details.lock_duration, Some(Duration::from_secs(30)));
+        assert_eq!(
+            details.duplicate_detection_history_time_window,
+            Some(Duration::from_secs(600))
+        );
+        assert!(QueueDetails::is_unlimited(
+            details.default_message_time_to_live.unwrap()
+        ));
+        assert_eq!(details.requires_duplicate_detection, Some(true));
+        assert_eq!(details.forward_to, None);
+        assert_eq!(
+            details.forward_dead_lettered_messages_to.as_deref(),
+            Some("dlq-archive")
+        );
+        assert_eq!(
+            details.updated_at.map(|at| at.to_rfc3339()),
+            Some("2024-03-02T11:00:00+00:00".to_string())
+        );
+        assert!(details.created_at.is_some());
+        assert_eq!(details.accessed_at, None);
+    }
+
+    #[test]
+    fn test_from_management_json_for_subscription() {
+        // Subscriptions carry no size, partitioning or duplicate detection settings
+        let json = r#"{
+            "properties": {
+                "countDetails": { "activeMessageCount": 1, "deadLetterMessageCount": 0 },
+                "lockDuration": "PT1M",
+                "maxDeliveryCount": 5,
+                "requiresSession": true,
+                "status": "ReceiveDisabled"
+            }
+        }"#;
+
+        let details =
+            QueueDetails::from_management_json("events/Subscriptions/audit", json).unwrap();
+        assert_eq!(details.max_delivery_count, Some(5));
+        assert_eq!(details.requires_session, Some(true));
+        assert_eq!(details.size_in_bytes, None);
+        assert_eq!(details.enable_partitioning, None);
+        assert_eq!(details.scheduled_message_count, None);
+
+        assert!(QueueDetails::from_management_json("orders", "{}").is_err());
+    }
+}
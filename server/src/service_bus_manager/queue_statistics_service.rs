@@ -1,10 +1,12 @@
-use super::ServiceBusError;
 use super::azure_management_client::{AzureManagementClient, StatisticsConfig};
 use super::types::QueueType;
+use super::{QueueDetails, QueueSettings, ServiceBusError, SubscriptionRule};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-/// Service for getting real queue statistics from Azure Management API
+/// Service for getting real queue statistics from Azure Management API, and for
+/// the queue properties, queue lifecycle and subscription rule operations that
+/// go through the same client
 pub struct QueueStatisticsService {
     management_client: Arc<Mutex<Option<AzureManagementClient>>>,
     config: StatisticsConfig,
@@ -164,6 +166,99 @@ impl QueueStatisticsService {
         }
     }
 
+    /// Get the runtime counts and configuration of a queue or subscription.
+    ///
+    /// Unlike the counts, the details are requested explicitly, so they do not
+    /// depend on statistics being displayed; they do need the management API.
+    pub async fn get_queue_details(
+        &self,
+        queue_name: &str,
+    ) -> Result<QueueDetails, ServiceBusError> {
+        log::info!("Getting details for queue: {queue_name}");
+        self.management_client()
+            .await?
+            .get_queue_details(queue_name)
+            .await
+    }
+
+    /// Create a queue through the management API
+    pub async fn create_queue(
+        &self,
+        queue_name: &str,
+        settings: &QueueSettings,
+    ) -> Result<QueueDetails, ServiceBusError> {
+        self.management_client()
+            .await?
+            .create_queue(queue_name, settings)
+            .await
+    }
+
+    /// Change settings of a queue through the management API
+    pub async fn update_queue(
+        &self,
+        queue_name: &str,
+        settings: &QueueSettings,
+    ) -> Result<QueueDetails, ServiceBusError> {
+        self.management_client()
+            .await?
+            .update_queue(queue_name, settings)
+            .await
+    }
+
+    /// Delete a queue through the management API
+    pub async fn delete_queue(&self, queue_name: &str) -> Result<(), ServiceBusError> {
+        self.management_client()
+            .await?
+            .delete_queue(queue_name)
+            .await
+    }
+
+    /// List the rules of a topic subscription through the management API
+    pub async fn list_subscription_rules(
+        &self,
+        subscription_path: &str,
+    ) -> Result<Vec<SubscriptionRule>, ServiceBusError> {
+        self.management_client()
+            .await?
+            .list_subscription_rules(subscription_path)
+            .await
+    }
+
+    /// Create or replace a subscription rule through the management API
+    pub async fn save_subscription_rule(
+        &self,
+        subscription_path: &str,
+        rule: &SubscriptionRule,
+    ) -> Result<SubscriptionRule, ServiceBusError> {
+        self.management_client()
+            .await?
+            .save_subscription_rule(subscription_path, rule)
+            .await
+    }
+
+    /// Delete a subscription rule through the management API
+    pub async fn delete_subscription_rule(
+        &self,
+        subscription_path: &str,
+        rule_name: &str,
+    ) -> Result<(), ServiceBusError> {
+        self.management_client()
+            .await?
+            .delete_subscription_rule(subscription_path, rule_name)
+            .await
+    }
+
+    /// The management client for explicitly requested operations
+    pub async fn management_client(&self) -> Result<AzureManagementClient, ServiceBusError> {
+        self.ensure_initialized().await;
+
+        self.management_client.lock().await.clone().ok_or_else(|| {
+            ServiceBusError::ConfigurationError(
+                "This operation needs the Azure Management API, which is not available with connection string authentication or when use_management_api is disabled".to_string(),
+            )
+        })
+    }
+
     /// Get the current configuration
     pub fn config(&self) -> &StatisticsConfig {
         &self.config
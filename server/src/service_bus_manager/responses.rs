@@ -1,5 +1,9 @@
+use super::queue_details::QueueDetails;
+use super::subscription_rules::SubscriptionRule;
 use super::types::{OperationStats, QueueInfo, QueueType};
-use crate::bulk_operations::{BulkOperationResult, MessageIdentifier};
+use crate::bulk_operations::{
+    BulkOperationResult, ExportResult, ImportResult, MessageIdentifier, PurgeResult, SearchResult,
+};
 use crate::model::MessageModel;
 
 /// Response types for Service Bus operations.
@@ -50,6 +54,30 @@ pub enum ServiceBusResponse {
         queue_info: QueueInfo,
     },
 
+    /// Sessions discovered on a session-enabled queue.
+    ///
+    /// Returned by [`ListSessions`] command.
+    SessionsListed {
+        /// IDs of sessions that currently hold messages
+        sessions: Vec<String>,
+    },
+
+    /// State of the current session.
+    ///
+    /// Returned by [`GetSessionState`] command.
+    SessionState {
+        /// ID of the session
+        session_id: String,
+        /// Raw session state, empty if none has been set
+        state: Vec<u8>,
+    },
+
+    /// Confirmation that the session state was replaced.
+    SessionStateUpdated {
+        /// ID of the session
+        session_id: String,
+    },
+
     /// Information about the currently active queue.
     ///
     /// Returned by [`GetCurrentQueue`] command.
@@ -74,6 +102,50 @@ pub enum ServiceBusResponse {
         retrieved_at: chrono::DateTime<chrono::Utc>,
     },
 
+    /// Runtime counts and configuration of a queue or subscription.
+    ///
+    /// Returned by [`GetQueueDetails`] command.
+    QueueDetails {
+        /// Properties reported by the management API
+        details: Box<QueueDetails>,
+    },
+
+    /// Queue created by [`CreateQueue`] command.
+    QueueCreated {
+        /// Properties of the new queue
+        details: Box<QueueDetails>,
+    },
+
+    /// Queue changed by [`UpdateQueue`] command.
+    QueueUpdated {
+        /// Properties of the queue after the change
+        details: Box<QueueDetails>,
+    },
+
+    /// Queue removed by [`DeleteQueue`] command.
+    QueueDeleted {
+        /// Name of the deleted queue
+        queue_name: String,
+    },
+
+    /// Rules returned by [`ListSubscriptionRules`] command.
+    SubscriptionRules {
+        /// Rules of the subscription, as listed by the management API
+        rules: Vec<SubscriptionRule>,
+    },
+
+    /// Rule saved by [`SaveSubscriptionRule`] command.
+    SubscriptionRuleSaved {
+        /// Rule as stored by the broker
+        rule: SubscriptionRule,
+    },
+
+    /// Rule removed by [`DeleteSubscriptionRule`] command.
+    SubscriptionRuleDeleted {
+        /// Name of the deleted rule
+        rule_name: String,
+    },
+
     /// Messages retrieved via peek operations.
     ///
     /// Contains parsed message models from [`PeekMessages`] command.
@@ -108,6 +180,17 @@ pub enum ServiceBusResponse {
         message_id: String,
     },
 
+    /// Confirmation that a message was deferred.
+    MessageDeferred {
+        /// ID of the deferred message
+        message_id: String,
+        /// Sequence number used to fetch the message later
+        sequence_number: i64,
+        /// Sequence numbers of messages in front of it that were deferred
+        /// during the search and could not be re-queued
+        still_deferred: Vec<i64>,
+    },
+
     /// Result of a bulk operation with comprehensive statistics.
     ///
     /// Used for complex bulk operations like delete, send, etc.
@@ -116,6 +199,46 @@ pub enum ServiceBusResponse {
         result: BulkOperationResult,
     },
 
+    /// Result of purging a queue, subscription or dead letter queue.
+    QueuePurged {
+        /// Entity path of the purged entity
+        queue_name: String,
+        /// Purged count, duration and whether the purge was cancelled
+        result: PurgeResult,
+    },
+
+    /// Messages a purge would remove.
+    PurgeTargetsSelected {
+        /// Entity path of the entity
+        queue_name: String,
+        /// Sequence numbers of the selected messages, in order
+        sequence_numbers: Vec<i64>,
+    },
+
+    /// Result of searching a queue, subscription or dead letter queue.
+    SearchCompleted {
+        /// Entity path of the searched entity
+        queue_name: String,
+        /// Matches, scanned count and whether the search stopped early
+        result: SearchResult,
+    },
+
+    /// Result of exporting messages to a file.
+    MessagesExported {
+        /// Entity path of the exported entity
+        queue_name: String,
+        /// File written, message counts and whether the export stopped early
+        result: ExportResult,
+    },
+
+    /// Result of replaying an NDJSON export into a queue.
+    MessagesImported {
+        /// Entity path of the target queue
+        queue_name: String,
+        /// Sent and failed counts and whether the import stopped early
+        result: ImportResult,
+    },
+
     /// Result of bulk message completion operation.
     BulkMessagesCompleted {
         /// List of successfully completed message identifiers
@@ -162,6 +285,22 @@ pub enum ServiceBusResponse {
         stats: OperationStats,
     },
 
+    /// Confirmation that messages were scheduled.
+    MessagesScheduled {
+        /// Name of the queue the messages were scheduled on
+        queue_name: String,
+        /// Sequence numbers assigned to the scheduled messages
+        sequence_numbers: Vec<i64>,
+    },
+
+    /// Confirmation that scheduled messages were cancelled.
+    ScheduledMessagesCancelled {
+        /// Name of the queue the messages were scheduled on
+        queue_name: String,
+        /// Number of cancelled messages
+        count: usize,
+    },
+
     /// Current connection status and health information.
     ConnectionStatus {
         /// Whether the connection is currently active
@@ -0,0 +1,705 @@
+use crate::filter::{FilterError, SqlFilter};
+use crate::model::MessageModel;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Name of the rule Service Bus adds to every new subscription
+pub const DEFAULT_RULE_NAME: &str = "$Default";
+
+/// A rule deciding which messages of a topic a subscription receives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriptionRule {
+    pub name: String,
+    pub filter: RuleFilter,
+    /// SQL action applied to matching messages, e.g. `SET sys.Label = 'routed'`
+    pub action: Option<String>,
+}
+
+/// Condition of a subscription rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleFilter {
+    /// SQL filter expression, e.g. `region = 'eu' AND sys.Label LIKE 'Order%'`
+    Sql(String),
+    /// Exact matches on system and application properties
+    Correlation(CorrelationFilter),
+}
+
+/// Correlation filter; a message matches when every condition that is set
+/// equals the message's value exactly.
+///
+/// Application property conditions keep their JSON type, which the broker
+/// matches on, but are compared locally by their text, so a condition `42`
+/// also matches a string property of "42".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorrelationFilter {
+    pub correlation_id: Option<String>,
+    pub message_id: Option<String>,
+    pub to: Option<String>,
+    pub reply_to: Option<String>,
+    /// `Label` in the management API
+    pub subject: Option<String>,
+    pub session_id: Option<String>,
+    /// Not captured on peeked messages, so a condition on it never matches locally
+    pub reply_to_session_id: Option<String>,
+    pub content_type: Option<String>,
+    /// Application property conditions, as strings, numbers or booleans
+    pub properties: BTreeMap<String, Value>,
+}
+
+/// A rule filter prepared for testing against messages
+#[derive(Debug, Clone)]
+pub enum RuleMatcher {
+    Sql(SqlFilter),
+    Correlation(CorrelationFilter),
+}
+
+impl SubscriptionRule {
+    /// Parses one page of a management API rule listing, returning the rules
+    /// and the link to the next page
+    pub fn list_from_management_json(
+        json: &str,
+    ) -> Result<(Vec<Self>, Option<String>), serde_json::Error> {
+        let response: RuleListResponse = serde_json::from_str(json)?;
+        let rules = response.value.into_iter().map(Self::from).collect();
+        Ok((rules, response.next_link))
+    }
+
+    /// Parses the body of a management API `GET` or `PUT` on a single rule
+    pub fn from_management_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str::<RuleResource>(json).map(Self::from)
+    }
+
+    /// Body of a management API `PUT` that creates or replaces the rule
+    pub fn to_management_json(&self) -> Value {
+        let mut properties = Map::new();
+        match &self.filter {
+            RuleFilter::Sql(expression) => {
+                properties.insert("filterType".to_string(), "SqlFilter".into());
+                properties.insert(
+                    "sqlFilter".to_string(),
+                    json!({ "sqlExpression": expression }),
+                );
+            }
+            RuleFilter::Correlation(filter) => {
+                properties.insert("filterType".to_string(), "CorrelationFilter".into());
+                properties.insert("correlationFilter".to_string(), filter.to_management_json());
+            }
+        }
+        if let Some(action) = self
+            .action
+            .as_deref()
+            .filter(|action| !action.trim().is_empty())
+        {
+            properties.insert("action".to_string(), json!({ "sqlExpression": action }));
+        }
+        json!({ "properties": properties })
+    }
+}
+
+impl RuleFilter {
+    /// Prepares the filter for local evaluation
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterError::Syntax`] if a SQL expression cannot be parsed,
+    /// or [`FilterError::NotInRules`] if it uses a body path or message state
+    /// that Service Bus does not accept in rules
+    pub fn matcher(&self) -> Result<RuleMatcher, FilterError> {
+        match self {
+            Self::Sql(expression) => SqlFilter::parse_rule(expression).map(RuleMatcher::Sql),
+            Self::Correlation(filter) => Ok(RuleMatcher::Correlation(filter.clone())),
+        }
+    }
+
+    /// The messages the filter would let into the subscription
+    pub fn matching<'a>(
+        &self,
+        messages: &'a [MessageModel],
+    ) -> Result<Vec<&'a MessageModel>, FilterError> {
+        let matcher = self.matcher()?;
+        Ok(messages
+            .iter()
+            .filter(|message| matcher.matches(message))
+            .collect())
+    }
+}
+
+/// Written as `sql: <expression>` or `correlation: key=value ...`; see [`RuleFilter::from_str`]
+impl fmt::Display for RuleFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sql(expression) => write!(f, "sql: {expression}"),
+            Self::Correlation(filter) => write!(f, "correlation: {filter}"),
+        }
+    }
+}
+
+impl FromStr for RuleFilter {
+    type Err = String;
+
+    /// Reads `correlation: key=value ...` as a correlation filter and anything
+    /// else, with an optional `sql:` prefix, as a SQL expression.
+    ///
+    /// Correlation keys are `correlation_id`, `message_id`, `to`, `reply_to`,
+    /// `subject` (or `label`), `session_id`, `reply_to_session_id`,
+    /// `content_type` and `user.<property>`. Values containing spaces are
+    /// written in single quotes, doubling quotes inside them. Unquoted
+    /// property values that read as numbers or `true`/`false` keep that type;
+    /// quote them to match a string.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if let Some(conditions) = strip_prefix_ignore_case(text, "correlation:") {
+            return conditions.parse().map(Self::Correlation);
+        }
+
+        let expression = strip_prefix_ignore_case(text, "sql:")
+            .unwrap_or(text)
+            .trim();
+        if expression.is_empty() {
+            return Err(
+                "The SQL filter expression is empty; use 1=1 to match every message".to_string(),
+            );
+        }
+        Ok(Self::Sql(expression.to_string()))
+    }
+}
+
+impl CorrelationFilter {
+    /// Whether no condition is set; Service Bus rejects such filters
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the message satisfies every condition
+    pub fn matches(&self, message: &MessageModel) -> bool {
+        let properties = &message.properties;
+        let system = [
+            (&self.correlation_id, properties.correlation_id.as_deref()),
+            (&self.message_id, Some(message.id.as_str())),
+            (&self.to, properties.to.as_deref()),
+            (&self.reply_to, properties.reply_to.as_deref()),
+            (&self.subject, properties.subject.as_deref()),
+            (&self.session_id, properties.session_id.as_deref()),
+            (&self.reply_to_session_id, None),
+            (&self.content_type, properties.content_type.as_deref()),
+        ];
+        let system_matches = system.iter().all(|(expected, actual)| match expected {
+            Some(expected) => *actual == Some(expected.as_str()),
+            None => true,
+        });
+
+        system_matches
+            && self.properties.iter().all(|(name, expected)| {
+                properties
+                    .application_properties
+                    .get(name)
+                    .is_some_and(|actual| actual.to_string() == value_text(expected))
+            })
+    }
+
+    fn system_conditions(&self) -> [(&'static str, &Option<String>); 8] {
+        [
+            ("correlation_id", &self.correlation_id),
+            ("message_id", &self.message_id),
+            ("to", &self.to),
+            ("reply_to", &self.reply_to),
+            ("subject", &self.subject),
+            ("session_id", &self.session_id),
+            ("reply_to_session_id", &self.reply_to_session_id),
+            ("content_type", &self.content_type),
+        ]
+    }
+
+    fn to_management_json(&self) -> Value {
+        let mut filter = Map::new();
+        for (key, value) in [
+            ("correlationId", &self.correlation_id),
+            ("messageId", &self.message_id),
+            ("to", &self.to),
+            ("replyTo", &self.reply_to),
+            ("label", &self.subject),
+            ("sessionId", &self.session_id),
+            ("replyToSessionId", &self.reply_to_session_id),
+            ("contentType", &self.content_type),
+        ] {
+            if let Some(value) = value {
+                filter.insert(key.to_string(), value.as_str().into());
+            }
+        }
+        if !self.properties.is_empty() {
+            filter.insert("properties".to_string(), json!(self.properties));
+        }
+        Value::Object(filter)
+    }
+}
+
+impl fmt::Display for CorrelationFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let system = self
+            .system_conditions()
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.to_string(), value.as_deref()?)));
+        let user = self.properties.iter().map(|(name, value)| {
+            let text = match value {
+                Value::String(text) => quote_text(text),
+                other => value_text(other),
+            };
+            (format!("user.{name}"), text)
+        });
+
+        let pairs: Vec<String> = system
+            .map(|(key, value)| (key, quote_value(value)))
+            .chain(user)
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect();
+        f.write_str(&pairs.join(" "))
+    }
+}
+
+impl FromStr for CorrelationFilter {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::default();
+        for (key, value, quoted) in split_pairs(text)? {
+            if let Some(name) = key.strip_prefix("user.") {
+                if name.is_empty() {
+                    return Err("Missing property name after 'user.'".to_string());
+                }
+                let value = if quoted {
+                    Value::String(value)
+                } else {
+                    infer_value(value)
+                };
+                filter.properties.insert(name.to_string(), value);
+                continue;
+            }
+
+            let slot = match key.to_ascii_lowercase().as_str() {
+                "correlation_id" => &mut filter.correlation_id,
+                "message_id" => &mut filter.message_id,
+                "to" => &mut filter.to,
+                "reply_to" => &mut filter.reply_to,
+                "subject" | "label" => &mut filter.subject,
+                "session_id" => &mut filter.session_id,
+                "reply_to_session_id" => &mut filter.reply_to_session_id,
+                "content_type" => &mut filter.content_type,
+                _ => {
+                    return Err(format!(
+                        "Unknown correlation key '{key}'; expected correlation_id, message_id, to, reply_to, subject, session_id, reply_to_session_id, content_type or user.<property>"
+                    ));
+                }
+            };
+            *slot = Some(value);
+        }
+
+        if filter.is_empty() {
+            return Err("A correlation filter needs at least one condition".to_string());
+        }
+        Ok(filter)
+    }
+}
+
+impl RuleMatcher {
+    /// Whether the message would be let into the subscription
+    pub fn matches(&self, message: &MessageModel) -> bool {
+        match self {
+            Self::Sql(filter) => filter.matches(message),
+            Self::Correlation(filter) => filter.matches(message),
+        }
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    text.get(..prefix.len())
+        .filter(|start| start.eq_ignore_ascii_case(prefix))
+        .map(|_| &text[prefix.len()..])
+}
+
+/// Splits `key=value key='quoted value'` into pairs, telling whether each
+/// value was quoted
+fn split_pairs(text: &str) -> Result<Vec<(String, String, bool)>, String> {
+    let mut pairs = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(pairs);
+        }
+
+        let key: String =
+            std::iter::from_fn(|| chars.next_if(|c| *c != '=' && !c.is_whitespace())).collect();
+        if chars.next() != Some('=') {
+            return Err(format!("Expected key=value, got '{key}'"));
+        }
+
+        let mut value = String::new();
+        let quoted = chars.next_if_eq(&'\'').is_some();
+        if quoted {
+            loop {
+                match chars.next() {
+                    Some('\'') if chars.next_if_eq(&'\'').is_some() => value.push('\''),
+                    Some('\'') => break,
+                    Some(c) => value.push(c),
+                    None => return Err(format!("Unterminated quote in the value of '{key}'")),
+                }
+            }
+        } else {
+            value.extend(std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace())));
+        }
+        pairs.push((key, value, quoted));
+    }
+}
+
+/// Reads an unquoted property value as a boolean or number if it is one
+fn infer_value(text: String) -> Value {
+    if let Ok(flag) = text.parse::<bool>() {
+        return Value::Bool(flag);
+    }
+    if let Ok(number) = text.parse::<i64>() {
+        return Value::from(number);
+    }
+    text.parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+        .map_or(Value::String(text), Value::Number)
+}
+
+/// Text of a property value as the local evaluator compares it
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Quotes a string property value that would otherwise read as a boolean or number
+fn quote_text(text: &str) -> String {
+    if infer_value(text.to_string()).is_string() {
+        quote_value(text)
+    } else {
+        format!("'{text}'")
+    }
+}
+
+fn quote_value(value: &str) -> String {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '\'') {
+        format!("'{}'", value.replace('\'', "''"))
+    } else {
+        value.to_string()
+    }
+}
+
+#[derive(Deserialize)]
+struct RuleListResponse {
+    #[serde(default)]
+    value: Vec<RuleResource>,
+    #[serde(rename = "nextLink")]
+    next_link: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RuleResource {
+    name: String,
+    #[serde(default)]
+    properties: RuleProperties,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct RuleProperties {
+    filter_type: Option<String>,
+    sql_filter: Option<SqlExpression>,
+    correlation_filter: Option<CorrelationProperties>,
+    action: Option<SqlExpression>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SqlExpression {
+    sql_expression: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct CorrelationProperties {
+    correlation_id: Option<String>,
+    message_id: Option<String>,
+    to: Option<String>,
+    reply_to: Option<String>,
+    label: Option<String>,
+    session_id: Option<String>,
+    reply_to_session_id: Option<String>,
+    content_type: Option<String>,
+    #[serde(default)]
+    properties: BTreeMap<String, Value>,
+}
+
+impl From<RuleResource> for SubscriptionRule {
+    fn from(resource: RuleResource) -> Self {
+        let properties = resource.properties;
+        let is_correlation = properties
+            .filter_type
+            .as_deref()
+            .is_some_and(|filter_type| filter_type.eq_ignore_ascii_case("CorrelationFilter"));
+
+        let filter = if is_correlation {
+            let correlation = properties.correlation_filter.unwrap_or_default();
+            RuleFilter::Correlation(CorrelationFilter {
+                correlation_id: correlation.correlation_id,
+                message_id: correlation.message_id,
+                to: correlation.to,
+                reply_to: correlation.reply_to,
+                subject: correlation.label,
+                session_id: correlation.session_id,
+                reply_to_session_id: correlation.reply_to_session_id,
+                content_type: correlation.content_type,
+                properties: correlation.properties,
+            })
+        } else {
+            // Rules without a SQL expression are the broker's TrueFilter
+            RuleFilter::Sql(
+                properties
+                    .sql_filter
+                    .and_then(|filter| filter.sql_expression)
+                    .unwrap_or_else(|| "1=1".to_string()),
+            )
+        };
+
+        Self {
+            name: resource.name,
+            filter,
+            action: properties
+                .action
+                .and_then(|action| action.sql_expression)
+                .filter(|action| !action.trim().is_empty()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BodyData, MessageState, PropertyValue};
+    use azure_core::time::OffsetDateTime;
+
+    fn message(id: &str, subject: &str, region: PropertyValue) -> MessageModel {
+        let mut message = MessageModel::new(
+            1,
+            id.to_string(),
+            OffsetDateTime::UNIX_EPOCH,
+            0,
+            MessageState::Active,
+            BodyData::RawString(String::new()),
+        );
+        message.properties.subject = Some(subject.to_string());
+        message
+            .properties
+            .application_properties
+            .insert("region".to_string(), region);
+        message
+    }
+
+    #[test]
+    fn test_list_from_management_json() {
+        let json = r#"{
+            "value": [
+                {
+                    "name": "$Default",
+                    "properties": {
+                        "filterType": "SqlFilter",
+                        "sqlFilter": { "sqlExpression": "1=1", "compatibilityLevel": 20 },
+                        "action": {}
+                    }
+                },
+                {
+                    "name": "eu-orders",
+                    "properties": {
+                        "filterType": "CorrelationFilter",
+                        "correlationFilter": {
+                            "label": "OrderCreated",
+                            "properties": { "region": "eu" }
+                        },
+                        "action": { "sqlExpression": "SET sys.To = 'eu'" }
+                    }
+                }
+            ],
+            "nextLink": "https://management.azure.com/next"
+        }"#;
+
+        let (rules, next_link) = SubscriptionRule::list_from_management_json(json).unwrap();
+        assert_eq!(
+            next_link.as_deref(),
+            Some("https://management.azure.com/next")
+        );
+        assert_eq!(rules[0].name, DEFAULT_RULE_NAME);
+        assert_eq!(rules[0].filter, RuleFilter::Sql("1=1".to_string()));
+        assert_eq!(rules[0].action, None);
+
+        let RuleFilter::Correlation(filter) = &rules[1].filter else {
+            panic!("expected a correlation filter");
+        };
+        assert_eq!(filter.subject.as_deref(), Some("OrderCreated"));
+        assert_eq!(
+            filter.properties.get("region").and_then(Value::as_str),
+            Some("eu")
+        );
+        assert_eq!(rules[1].action.as_deref(), Some("SET sys.To = 'eu'"));
+    }
+
+    #[test]
+    fn test_to_management_json() {
+        let rule = SubscriptionRule {
+            name: "eu".to_string(),
+            filter: "correlation: subject=OrderCreated user.region=eu"
+                .parse()
+                .unwrap(),
+            action: Some(" ".to_string()),
+        };
+        assert_eq!(
+            rule.to_management_json(),
+            json!({
+                "properties": {
+                    "filterType": "CorrelationFilter",
+                    "correlationFilter": {
+                        "label": "OrderCreated",
+                        "properties": { "region": "eu" }
+                    }
+                }
+            })
+        );
+
+        let rule = SubscriptionRule {
+            name: "late".to_string(),
+            filter: RuleFilter::Sql("sys.DeliveryCount > 3".to_string()),
+            action: Some("SET late = 1".to_string()),
+        };
+        assert_eq!(
+            rule.to_management_json()["properties"]["action"]["sqlExpression"],
+            "SET late = 1"
+        );
+    }
+
+    #[test]
+    fn test_typed_properties_round_trip() {
+        let json = r#"{
+            "name": "urgent",
+            "properties": {
+                "filterType": "CorrelationFilter",
+                "correlationFilter": {
+                    "properties": { "priority": 1, "weight": 0.5, "urgent": true, "code": "7" }
+                }
+            }
+        }"#;
+        let rule = SubscriptionRule::from_management_json(json).unwrap();
+        let RuleFilter::Correlation(filter) = &rule.filter else {
+            panic!("expected a correlation filter");
+        };
+        assert_eq!(filter.properties["priority"], json!(1));
+        assert_eq!(filter.properties["code"], json!("7"));
+        assert_eq!(
+            rule.to_management_json()["properties"]["correlationFilter"]["properties"],
+            json!({ "priority": 1, "weight": 0.5, "urgent": true, "code": "7" })
+        );
+
+        let text = rule.filter.to_string();
+        assert_eq!(
+            text,
+            "correlation: user.code='7' user.priority=1 user.urgent=true user.weight=0.5"
+        );
+        assert_eq!(text.parse::<RuleFilter>().unwrap(), rule.filter);
+    }
+
+    #[test]
+    fn test_filter_text_round_trips() {
+        let filter: RuleFilter =
+            "correlation: correlation_id=abc user.team='order ops' user.note='it''s'"
+                .parse()
+                .unwrap();
+        let RuleFilter::Correlation(correlation) = &filter else {
+            panic!("expected a correlation filter");
+        };
+        assert_eq!(correlation.correlation_id.as_deref(), Some("abc"));
+        assert_eq!(correlation.properties["team"], "order ops");
+        assert_eq!(correlation.properties["note"], "it's");
+        assert_eq!(filter.to_string().parse::<RuleFilter>().unwrap(), filter);
+
+        assert_eq!(
+            "region = 'eu'".parse::<RuleFilter>().unwrap(),
+            RuleFilter::Sql("region = 'eu'".to_string())
+        );
+        assert_eq!(
+            "SQL: 1=1".parse::<RuleFilter>().unwrap(),
+            RuleFilter::Sql("1=1".to_string())
+        );
+        assert!("sql:  ".parse::<RuleFilter>().is_err());
+        assert!("correlation:".parse::<RuleFilter>().is_err());
+        assert!("correlation: region=eu".parse::<RuleFilter>().is_err());
+        assert!(
+            "correlation: user.note='open"
+                .parse::<RuleFilter>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_matching_messages() {
+        let messages = vec![
+            message("a", "OrderCreated", PropertyValue::String("eu".to_string())),
+            message("b", "OrderCreated", PropertyValue::String("us".to_string())),
+            message(
+                "c",
+                "OrderCancelled",
+                PropertyValue::String("eu".to_string()),
+            ),
+            message("d", "OrderCreated", PropertyValue::Int(7)),
+        ];
+        let ids = |filter: &RuleFilter| -> Vec<String> {
+            filter
+                .matching(&messages)
+                .unwrap()
+                .iter()
+                .map(|message| message.id.clone())
+                .collect()
+        };
+
+        let correlation: RuleFilter = "correlation: subject=OrderCreated user.region=eu"
+            .parse()
+            .unwrap();
+        assert_eq!(ids(&correlation), vec!["a"]);
+
+        let typed: RuleFilter = "correlation: user.region=7".parse().unwrap();
+        assert_eq!(ids(&typed), vec!["d"]);
+
+        let sql = RuleFilter::Sql("region = 'eu' OR sys.MessageId = 'b'".to_string());
+        assert_eq!(ids(&sql), vec!["a", "b", "c"]);
+        assert_eq!(ids(&RuleFilter::Sql("1=1".to_string())).len(), 4);
+
+        let reply_session: RuleFilter = "correlation: reply_to_session_id=s1".parse().unwrap();
+        assert!(ids(&reply_session).is_empty());
+
+        assert!(RuleFilter::Sql("region = ".to_string()).matcher().is_err());
+    }
+
+    #[test]
+    fn test_sql_rules_only_use_properties_and_headers() {
+        let rule = |expression: &str| RuleFilter::Sql(expression.to_string()).matcher();
+
+        assert!(rule("region = 'eu' AND sys.Label = 'OrderCreated'").is_ok());
+        assert!(rule("EXISTS(sys.CorrelationId) OR sys.To IN ('a', 'b')").is_ok());
+
+        assert_eq!(
+            rule("region = 'eu' AND body.items.0.sku = 'A-1'").err(),
+            Some(FilterError::NotInRules("body.items.0.sku".to_string()))
+        );
+        assert_eq!(
+            rule("NOT (sys.DeliveryCount > 3)").err(),
+            Some(FilterError::NotInRules("sys.DeliveryCount".to_string()))
+        );
+        assert!(rule("sys.EnqueuedTimeUtc IS NOT NULL").is_err());
+    }
+}
@@ -1,4 +1,8 @@
+use crate::model::{MessageProperties, PropertyValue};
+use azure_core::time::OffsetDateTime;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 
 /// Type of Service Bus queue for routing and processing messages.
 ///
@@ -39,7 +43,7 @@ impl QueueType {
     /// [`QueueType::DeadLetter`] if the name ends with `/$deadletterqueue`,
     /// [`QueueType::Main`] otherwise
     pub fn from_queue_name(queue_name: &str) -> Self {
-        if queue_name.ends_with("/$deadletterqueue") {
+        if queue_name.ends_with(DEAD_LETTER_SUFFIX) {
             QueueType::DeadLetter
         } else {
             QueueType::Main
@@ -47,6 +51,98 @@ impl QueueType {
     }
 }
 
+/// Suffix of a dead letter sub-queue entity path
+pub const DEAD_LETTER_SUFFIX: &str = "/$deadletterqueue";
+
+/// Path segment separating a topic from one of its subscriptions
+pub const SUBSCRIPTIONS_SEGMENT: &str = "/Subscriptions/";
+
+/// Kind of Service Bus entity messages are received from.
+///
+/// Queues and topic subscriptions are both addressed by an entity path;
+/// subscriptions use `<topic>/Subscriptions/<subscription>`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use quetty_server::service_bus_manager::EntityType;
+///
+/// assert_eq!(EntityType::from_entity_path("orders"), EntityType::Queue);
+/// assert_eq!(
+///     EntityType::from_entity_path("events/Subscriptions/billing/$deadletterqueue"),
+///     EntityType::Subscription
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntityType {
+    /// A queue
+    Queue,
+    /// A subscription of a topic
+    Subscription,
+}
+
+impl EntityType {
+    /// Determines the entity type from an entity path.
+    pub fn from_entity_path(entity_path: &str) -> Self {
+        if parse_subscription_path(entity_path).is_some() {
+            EntityType::Subscription
+        } else {
+            EntityType::Queue
+        }
+    }
+}
+
+/// Builds the entity path of a topic subscription.
+pub fn format_subscription_path(topic: &str, subscription: &str) -> String {
+    format!("{topic}{SUBSCRIPTIONS_SEGMENT}{subscription}")
+}
+
+/// Splits a subscription entity path into its topic and subscription names.
+///
+/// The `Subscriptions` segment is matched case-insensitively and a trailing
+/// dead letter suffix is ignored.
+///
+/// # Returns
+///
+/// `Some((topic, subscription))` for subscription paths, `None` otherwise
+pub fn parse_subscription_path(entity_path: &str) -> Option<(&str, &str)> {
+    let path = entity_path
+        .strip_suffix(DEAD_LETTER_SUFFIX)
+        .unwrap_or(entity_path);
+    let segment_start = path
+        .to_ascii_lowercase()
+        .find(&SUBSCRIPTIONS_SEGMENT.to_ascii_lowercase())?;
+    let topic = &path[..segment_start];
+    let subscription = &path[segment_start + SUBSCRIPTIONS_SEGMENT.len()..];
+
+    if topic.is_empty() || subscription.is_empty() || subscription.contains('/') {
+        return None;
+    }
+    Some((topic, subscription))
+}
+
+/// Returns the entity messages should be sent to for an entity path.
+///
+/// Subscriptions cannot be sent to directly, so their topic is used instead.
+/// Dead letter queues resolve to their parent entity.
+pub fn send_target(entity_path: &str) -> &str {
+    match parse_subscription_path(entity_path) {
+        Some((topic, _)) => topic,
+        None => entity_path
+            .strip_suffix(DEAD_LETTER_SUFFIX)
+            .unwrap_or(entity_path),
+    }
+}
+
+/// A topic together with the names of its subscriptions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicInfo {
+    /// Name of the topic
+    pub name: String,
+    /// Names of the topic's subscriptions
+    pub subscriptions: Vec<String>,
+}
+
 /// Information about a Service Bus queue including name and type.
 ///
 /// Represents a queue with its full name and type classification. Provides
@@ -74,6 +170,8 @@ pub struct QueueInfo {
     pub name: String,
     /// Type classification of the queue
     pub queue_type: QueueType,
+    /// Session the queue is opened with, for session-enabled queues
+    pub session_id: Option<String>,
 }
 
 impl QueueInfo {
@@ -88,7 +186,31 @@ impl QueueInfo {
     ///
     /// A new QueueInfo instance
     pub fn new(name: String, queue_type: QueueType) -> Self {
-        Self { name, queue_type }
+        Self {
+            name,
+            queue_type,
+            session_id: None,
+        }
+    }
+
+    /// Binds the queue to a session.
+    ///
+    /// Dead letter queues are never session-bound, so the session is ignored
+    /// for them.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - The session to open, or `None` for a plain receiver
+    ///
+    /// # Returns
+    ///
+    /// The QueueInfo with the session set
+    pub fn with_session(mut self, session_id: Option<String>) -> Self {
+        self.session_id = match self.queue_type {
+            QueueType::Main => session_id,
+            QueueType::DeadLetter => None,
+        };
+        self
     }
 
     /// Creates a QueueInfo for a main queue.
@@ -120,6 +242,38 @@ impl QueueInfo {
         Self::new(dlq_name, QueueType::DeadLetter)
     }
 
+    /// Creates a QueueInfo for the main queue of a topic subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - Name of the topic
+    /// * `subscription` - Name of the subscription
+    ///
+    /// # Returns
+    ///
+    /// A QueueInfo whose name is the subscription entity path
+    pub fn subscription(topic: &str, subscription: &str) -> Self {
+        Self::main_queue(format_subscription_path(topic, subscription))
+    }
+
+    /// Gets the kind of entity this queue belongs to.
+    pub fn entity_type(&self) -> EntityType {
+        EntityType::from_entity_path(&self.name)
+    }
+
+    /// Gets the topic and subscription names if this is a subscription.
+    pub fn topic_subscription(&self) -> Option<(&str, &str)> {
+        parse_subscription_path(&self.name)
+    }
+
+    /// Gets the entity messages should be sent to for this queue.
+    ///
+    /// For subscriptions this is the topic, so sent messages are delivered to
+    /// every subscription whose rules match.
+    pub fn send_target(&self) -> &str {
+        send_target(&self.name)
+    }
+
     /// Gets the base name of the queue without any type-specific suffixes.
     ///
     /// For main queues, returns the name as-is. For dead letter queues,
@@ -169,34 +323,50 @@ impl QueueInfo {
 /// Data structure for message content and metadata.
 ///
 /// Represents a message to be sent to a Service Bus queue, including the
-/// message content and optional custom properties. Used for sending new
-/// messages and representing message data in transit.
+/// message content, typed application properties and the system properties
+/// the sender is allowed to set. Used for sending new messages and
+/// representing message data in transit.
 ///
 /// # Examples
 ///
 /// ```no_run
+/// use quetty_server::model::PropertyValue;
 /// use quetty_server::service_bus_manager::MessageData;
-/// use std::collections::HashMap;
+/// use std::collections::BTreeMap;
 ///
 /// // Simple message with just content
 /// let message = MessageData::new("Hello, world!".to_string());
 ///
-/// // Message with custom properties
-/// let mut properties = HashMap::new();
-/// properties.insert("priority".to_string(), "high".to_string());
-/// properties.insert("source".to_string(), "orders-service".to_string());
+/// // Message with application properties and routing information
+/// let mut properties = BTreeMap::new();
+/// properties.insert("priority".to_string(), PropertyValue::String("high".to_string()));
+/// properties.insert("attempt".to_string(), PropertyValue::Int(1));
 ///
-/// let message = MessageData::with_properties(
+/// let mut message = MessageData::with_properties(
 ///     "Order processed: #12345".to_string(),
 ///     properties
 /// );
+/// message.system_properties.correlation_id = Some("order-12345".to_string());
+/// message.system_properties.subject = Some("order.processed".to_string());
+///
+/// // Binary payload that is sent byte for byte
+/// let binary = MessageData::from_bytes(vec![0x1f, 0x8b, 0x08, 0x00, 0xff]);
+/// assert_eq!(binary.body_bytes(), vec![0x1f, 0x8b, 0x08, 0x00, 0xff]);
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageData {
     /// The message content/body
     pub content: String,
-    /// Optional custom properties for the message
-    pub properties: Option<std::collections::HashMap<String, String>>,
+    /// Original body bytes for content that is not valid UTF-8; sent instead
+    /// of `content` when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_content: Option<Vec<u8>>,
+    /// User-defined application properties
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub application_properties: BTreeMap<String, PropertyValue>,
+    /// Broker system properties set by the sender
+    #[serde(default)]
+    pub system_properties: SystemProperties,
 }
 
 impl MessageData {
@@ -208,33 +378,325 @@ impl MessageData {
     ///
     /// # Returns
     ///
-    /// A new MessageData with no custom properties
+    /// A new MessageData with no application or system properties
     pub fn new(content: String) -> Self {
         Self {
             content,
-            properties: None,
+            binary_content: None,
+            application_properties: BTreeMap::new(),
+            system_properties: SystemProperties::default(),
+        }
+    }
+
+    /// Creates a new MessageData from a raw message body.
+    ///
+    /// UTF-8 bodies become regular text content; anything else is kept as
+    /// the original bytes so it can be sent unchanged.
+    pub fn from_bytes(body: Vec<u8>) -> Self {
+        match String::from_utf8(body) {
+            Ok(content) => Self::new(content),
+            Err(e) => Self {
+                binary_content: Some(e.into_bytes()),
+                ..Self::new(String::new())
+            },
         }
     }
 
-    /// Creates a new MessageData with content and custom properties.
+    /// Creates a new MessageData with content and application properties.
     ///
     /// # Arguments
     ///
     /// * `content` - The message content/body
-    /// * `properties` - Custom key-value properties for the message
+    /// * `properties` - Typed application properties for the message
     ///
     /// # Returns
     ///
     /// A new MessageData with the specified content and properties
-    pub fn with_properties(
-        content: String,
-        properties: std::collections::HashMap<String, String>,
-    ) -> Self {
+    pub fn with_properties(content: String, properties: BTreeMap<String, PropertyValue>) -> Self {
         Self {
             content,
-            properties: Some(properties),
+            binary_content: None,
+            application_properties: properties,
+            system_properties: SystemProperties::default(),
         }
     }
+
+    /// Sets the application properties of the message.
+    pub fn with_application_properties(
+        mut self,
+        properties: BTreeMap<String, PropertyValue>,
+    ) -> Self {
+        self.application_properties = properties;
+        self
+    }
+
+    /// Sets the system properties of the message.
+    pub fn with_system_properties(mut self, system_properties: SystemProperties) -> Self {
+        self.system_properties = system_properties;
+        self
+    }
+
+    /// Replaces the message body, keeping its properties.
+    ///
+    /// As with [`MessageData::from_bytes`], UTF-8 bodies become text content.
+    pub fn with_body(self, body: Vec<u8>) -> Self {
+        let body = Self::from_bytes(body);
+        Self {
+            content: body.content,
+            binary_content: body.binary_content,
+            ..self
+        }
+    }
+
+    /// The bytes sent as the message body
+    pub fn body_bytes(&self) -> Vec<u8> {
+        match &self.binary_content {
+            Some(bytes) => bytes.clone(),
+            None => self.content.as_bytes().to_vec(),
+        }
+    }
+
+    /// Size of the message body in bytes
+    pub fn body_len(&self) -> usize {
+        self.binary_content
+            .as_ref()
+            .map_or(self.content.len(), Vec::len)
+    }
+}
+
+/// System properties that can be set on an outgoing message.
+///
+/// Every property is optional; unset properties are left for the broker to
+/// fill in (or to default from the entity configuration, as for TTL).
+///
+/// # Examples
+///
+/// ```no_run
+/// use quetty_server::service_bus_manager::SystemProperties;
+/// use std::time::Duration;
+///
+/// let properties = SystemProperties {
+///     session_id: Some("customer-42".to_string()),
+///     content_type: Some("application/json".to_string()),
+///     time_to_live: Some(Duration::from_secs(3600)),
+///     ..Default::default()
+/// };
+/// assert!(!properties.is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemProperties {
+    /// Application-defined message identifier (used for duplicate detection)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    /// Correlation identifier used to relate a reply to its request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// Session the message belongs to on session-enabled entities
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// MIME content type of the body
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Application specific label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Address of the entity replies should be sent to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+    /// Intended destination of the message (used for auto-forward chains)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Time-to-live after which the message expires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_to_live: Option<std::time::Duration>,
+    /// UTC time at which the message becomes available to receivers
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "azure_core::time::iso8601::option"
+    )]
+    pub scheduled_enqueue_time: Option<OffsetDateTime>,
+}
+
+impl SystemProperties {
+    /// Returns `true` when no system property is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Builds the sendable system properties of a message that was received
+    /// from a queue.
+    ///
+    /// The MessageId and the scheduled enqueue time are not carried over:
+    /// the resent message is a new message and is delivered immediately.
+    pub fn from_message_properties(properties: &MessageProperties) -> Self {
+        Self {
+            message_id: None,
+            correlation_id: properties.correlation_id.clone(),
+            session_id: properties.session_id.clone(),
+            content_type: properties.content_type.clone(),
+            subject: properties.subject.clone(),
+            reply_to: properties.reply_to.clone(),
+            to: properties.to.clone(),
+            time_to_live: properties
+                .time_to_live
+                .and_then(|ttl| std::time::Duration::try_from(ttl).ok()),
+            scheduled_enqueue_time: None,
+        }
+    }
+}
+
+/// Options controlling how an existing message is rebuilt when it is resent.
+///
+/// Resending (for example from a dead letter queue back to its main queue)
+/// keeps the body, all application properties and the sendable system
+/// properties of the original message. On top of that the original MessageId
+/// can be kept, and trace properties can be added that record where the
+/// message came from.
+///
+/// # Examples
+///
+/// ```no_run
+/// use quetty_server::model::MessageProperties;
+/// use quetty_server::service_bus_manager::{ResendOptions, TraceProperties};
+///
+/// let options = ResendOptions {
+///     preserve_message_id: true,
+///     trace_properties: TraceProperties {
+///         original_sequence_number: Some("x-original-sequence-number".to_string()),
+///         dead_letter_reason: Some("x-dead-letter-reason".to_string()),
+///         ..Default::default()
+///     },
+/// };
+///
+/// let message = options.build_message(
+///     b"{\"orderId\": 42}".to_vec(),
+///     Some("msg-1"),
+///     1234,
+///     &MessageProperties::default(),
+/// );
+/// assert_eq!(message.system_properties.message_id.as_deref(), Some("msg-1"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResendOptions {
+    /// Keep the original MessageId instead of letting the broker assign a new one
+    #[serde(default)]
+    pub preserve_message_id: bool,
+    /// Application properties recording the origin of the resent message
+    #[serde(default)]
+    pub trace_properties: TraceProperties,
+}
+
+/// Names of the application properties used to trace a resent message back to
+/// the original. A property is only added when its name is configured and the
+/// original message has the corresponding value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceProperties {
+    /// Property receiving the original sequence number
+    #[serde(default)]
+    pub original_sequence_number: Option<String>,
+    /// Property receiving the original MessageId
+    #[serde(default)]
+    pub original_message_id: Option<String>,
+    /// Property receiving the DeadLetterReason
+    #[serde(default)]
+    pub dead_letter_reason: Option<String>,
+    /// Property receiving the DeadLetterErrorDescription
+    #[serde(default)]
+    pub dead_letter_error_description: Option<String>,
+    /// Property receiving the DeadLetterSource
+    #[serde(default)]
+    pub dead_letter_source: Option<String>,
+}
+
+impl ResendOptions {
+    /// Builds the outgoing message for resending an existing message.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The original message body, sent byte for byte
+    /// * `message_id` - The original MessageId, if any
+    /// * `sequence` - The original sequence number
+    /// * `properties` - The original system and application properties
+    ///
+    /// # Returns
+    ///
+    /// MessageData carrying the original properties plus configured trace properties
+    pub fn build_message(
+        &self,
+        body: Vec<u8>,
+        message_id: Option<&str>,
+        sequence: i64,
+        properties: &MessageProperties,
+    ) -> MessageData {
+        let mut system_properties = SystemProperties::from_message_properties(properties);
+        if self.preserve_message_id {
+            system_properties.message_id = message_id.map(String::from);
+        }
+
+        let mut application_properties = properties.application_properties.clone();
+        let trace = &self.trace_properties;
+        let mut add_trace = |name: &Option<String>, value: Option<PropertyValue>| {
+            if let (Some(name), Some(value)) = (name, value) {
+                application_properties.insert(name.clone(), value);
+            }
+        };
+        add_trace(
+            &trace.original_sequence_number,
+            Some(PropertyValue::Int(sequence)),
+        );
+        add_trace(
+            &trace.original_message_id,
+            message_id.map(|id| PropertyValue::String(id.to_string())),
+        );
+        add_trace(
+            &trace.dead_letter_reason,
+            properties
+                .dead_letter_reason
+                .clone()
+                .map(PropertyValue::String),
+        );
+        add_trace(
+            &trace.dead_letter_error_description,
+            properties
+                .dead_letter_error_description
+                .clone()
+                .map(PropertyValue::String),
+        );
+        add_trace(
+            &trace.dead_letter_source,
+            properties
+                .dead_letter_source
+                .clone()
+                .map(PropertyValue::String),
+        );
+
+        MessageData::from_bytes(body)
+            .with_application_properties(application_properties)
+            .with_system_properties(system_properties)
+    }
+}
+
+/// How deferred messages are settled once they have been fetched by
+/// sequence number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeferredMessageAction {
+    /// Complete the messages, removing them from the queue
+    Complete,
+    /// Move the messages to the dead letter queue
+    DeadLetter {
+        /// Optional reason for dead lettering
+        reason: Option<String>,
+        /// Optional detailed error description
+        error_description: Option<String>,
+    },
+    /// Send copies of the messages to a queue, then complete the deferred originals
+    Resend {
+        /// Queue receiving the copies
+        target_queue: String,
+        /// How the copies are rebuilt from the originals
+        resend_options: ResendOptions,
+    },
 }
 
 /// Statistics about Service Bus operations including success and failure counts.
@@ -291,3 +753,260 @@ impl OperationStats {
         }
     }
 }
+
+/// Outcome of sending a list of messages in bulk: the counts, and which of
+/// the messages were not sent.
+///
+/// A message sent with a repeat count only counts as sent when every copy of
+/// it was sent.
+#[derive(Debug, Clone, Default)]
+pub struct BulkSendStats {
+    /// Counts over every copy sent
+    pub stats: OperationStats,
+    /// Positions, in the list given to the send, of the messages with a copy
+    /// that failed
+    failed_messages: BTreeSet<usize>,
+}
+
+impl BulkSendStats {
+    /// Records that a copy of the message at `index` failed to send
+    pub fn add_failed_message(&mut self, index: usize) {
+        self.failed_messages.insert(index);
+    }
+
+    /// Whether every copy of the message at `index` was sent
+    pub fn is_sent(&self, index: usize) -> bool {
+        !self.failed_messages.contains(&index)
+    }
+}
+
+/// Another Service Bus namespace that bulk sends can target.
+///
+/// The namespace is reached with its own connection string, so it can live
+/// in a different subscription or be configured in a different profile than
+/// the one messages are read from. For profiles using Azure AD, the caller
+/// signs in as that profile and fetches the connection string first.
+///
+/// # Examples
+///
+/// ```no_run
+/// use quetty_server::service_bus_manager::TargetNamespace;
+///
+/// let staging = TargetNamespace::new(
+///     "staging",
+///     "Endpoint=sb://contoso-staging.servicebus.windows.net/;SharedAccessKeyName=send;SharedAccessKey=...",
+/// );
+/// assert_eq!(staging.host(), Some("contoso-staging.servicebus.windows.net"));
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct TargetNamespace {
+    /// Name shown to the user, such as the profile it comes from
+    pub label: String,
+    /// Connection string used to send to the namespace
+    pub connection_string: String,
+}
+
+impl TargetNamespace {
+    pub fn new(label: impl Into<String>, connection_string: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            connection_string: connection_string.into(),
+        }
+    }
+
+    /// Host name of the namespace endpoint, if the connection string has one
+    pub fn host(&self) -> Option<&str> {
+        self.connection_string
+            .split(';')
+            .find_map(|part| {
+                let (key, value) = part.split_once('=')?;
+                key.trim().eq_ignore_ascii_case("Endpoint").then_some(value)
+            })
+            .map(|endpoint| {
+                let endpoint = endpoint.trim();
+                let endpoint = endpoint
+                    .split_once("://")
+                    .map_or(endpoint, |(_, rest)| rest);
+                endpoint.trim_end_matches('/')
+            })
+            .filter(|host| !host.is_empty())
+    }
+}
+
+// The connection string holds a secret, so it never ends up in logs
+impl fmt::Debug for TargetNamespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TargetNamespace")
+            .field("label", &self.label)
+            .field("host", &self.host())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dead_lettered_properties() -> MessageProperties {
+        let mut properties = MessageProperties {
+            correlation_id: Some("corr-1".to_string()),
+            session_id: Some("session-1".to_string()),
+            subject: Some("order.created".to_string()),
+            dead_letter_reason: Some("MaxDeliveryCountExceeded".to_string()),
+            ..Default::default()
+        };
+        properties.application_properties.insert(
+            "tenant".to_string(),
+            PropertyValue::String("acme".to_string()),
+        );
+        properties
+    }
+
+    #[test]
+    fn test_resend_keeps_properties_without_message_id_by_default() {
+        let message = ResendOptions::default().build_message(
+            b"{}".to_vec(),
+            Some("msg-1"),
+            42,
+            &dead_lettered_properties(),
+        );
+
+        assert_eq!(message.system_properties.message_id, None);
+        assert_eq!(
+            message.system_properties.correlation_id.as_deref(),
+            Some("corr-1")
+        );
+        assert_eq!(
+            message.system_properties.session_id.as_deref(),
+            Some("session-1")
+        );
+        assert_eq!(message.application_properties.len(), 1);
+    }
+
+    #[test]
+    fn test_resend_adds_configured_trace_properties() {
+        let options = ResendOptions {
+            preserve_message_id: true,
+            trace_properties: TraceProperties {
+                original_sequence_number: Some("x-seq".to_string()),
+                dead_letter_reason: Some("x-reason".to_string()),
+                dead_letter_source: Some("x-source".to_string()),
+                ..Default::default()
+            },
+        };
+
+        let message = options.build_message(
+            b"{}".to_vec(),
+            Some("msg-1"),
+            42,
+            &dead_lettered_properties(),
+        );
+
+        assert_eq!(
+            message.system_properties.message_id.as_deref(),
+            Some("msg-1")
+        );
+        assert_eq!(
+            message.application_properties["x-seq"],
+            PropertyValue::Int(42)
+        );
+        assert_eq!(
+            message.application_properties["x-reason"],
+            PropertyValue::String("MaxDeliveryCountExceeded".to_string())
+        );
+        // No dead letter source on the original, so no trace property either
+        assert!(!message.application_properties.contains_key("x-source"));
+    }
+
+    #[test]
+    fn test_resend_keeps_binary_body_intact() {
+        let avro = vec![0x4f, 0x62, 0x6a, 0x01, 0xff, 0x80, 0x00];
+        let message = ResendOptions::default().build_message(
+            avro.clone(),
+            None,
+            7,
+            &MessageProperties::default(),
+        );
+
+        assert_eq!(message.binary_content.as_deref(), Some(avro.as_slice()));
+        assert_eq!(message.body_bytes(), avro);
+        assert_eq!(message.body_len(), avro.len());
+
+        let text = MessageData::from_bytes(b"hello".to_vec());
+        assert_eq!(text.content, "hello");
+        assert!(text.binary_content.is_none());
+    }
+
+    #[test]
+    fn test_with_session_only_applies_to_main_queue() {
+        let main = QueueInfo::main_queue("orders".to_string())
+            .with_session(Some("customer-1".to_string()));
+        assert_eq!(main.session_id.as_deref(), Some("customer-1"));
+
+        let dlq = main.to_dlq().with_session(Some("customer-1".to_string()));
+        assert_eq!(dlq.session_id, None);
+    }
+
+    #[test]
+    fn test_subscription_paths() {
+        let subscription = QueueInfo::subscription("events", "billing");
+        assert_eq!(subscription.name, "events/Subscriptions/billing");
+        assert_eq!(subscription.entity_type(), EntityType::Subscription);
+        assert_eq!(subscription.send_target(), "events");
+
+        let dlq = subscription.to_dlq();
+        assert_eq!(dlq.name, "events/Subscriptions/billing/$deadletterqueue");
+        assert_eq!(dlq.topic_subscription(), Some(("events", "billing")));
+        assert_eq!(dlq.to_main(), subscription);
+
+        assert_eq!(
+            parse_subscription_path("events/subscriptions/audit"),
+            Some(("events", "audit"))
+        );
+        assert_eq!(parse_subscription_path("orders"), None);
+        assert_eq!(parse_subscription_path("/Subscriptions/audit"), None);
+    }
+
+    #[test]
+    fn test_send_target_for_queues() {
+        assert_eq!(send_target("orders"), "orders");
+        assert_eq!(send_target("orders/$deadletterqueue"), "orders");
+        assert_eq!(
+            QueueInfo::main_queue("orders".to_string()).entity_type(),
+            EntityType::Queue
+        );
+    }
+
+    #[test]
+    fn test_target_namespace_host_and_redacted_debug() {
+        let target = TargetNamespace::new(
+            "staging",
+            "Endpoint=sb://contoso-staging.servicebus.windows.net/;SharedAccessKeyName=send;SharedAccessKey=secret",
+        );
+        assert_eq!(
+            target.host(),
+            Some("contoso-staging.servicebus.windows.net")
+        );
+
+        let debug = format!("{target:?}");
+        assert!(debug.contains("contoso-staging"));
+        assert!(!debug.contains("secret"));
+
+        assert_eq!(
+            TargetNamespace::new("bad", "not a connection string").host(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bulk_send_stats_tracks_unsent_messages() {
+        let mut sent = BulkSendStats::default();
+        // The second copy of message 1 failed, so message 1 is not sent
+        sent.add_failed_message(1);
+        sent.add_failed_message(1);
+
+        assert!(sent.is_sent(0));
+        assert!(!sent.is_sent(1));
+        assert!(sent.is_sent(2));
+    }
+}
@@ -48,7 +48,7 @@ use tokio_util::sync::CancellationToken;
 ///
 /// TaskPool is thread-safe and can be cloned to share across multiple contexts.
 /// All clones share the same underlying semaphore and cancellation token.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct TaskPool {
     semaphore: Arc<Semaphore>,
     cancel_token: Arc<CancellationToken>,
@@ -264,7 +264,7 @@ mod azure_error_context_preservation_tests {
     fn test_error_categorization() {
         // Enhanced errors enable better error categorization and handling
 
-        let errors = vec![
+        let errors = [
             ServiceBusError::azure_api_error("op1", "SubscriptionNotFound", 404, "Not found"),
             ServiceBusError::azure_api_error("op2", "AuthenticationFailed", 401, "Auth failed"),
             ServiceBusError::azure_api_error("op3", "InsufficientPermissions", 403, "No access"),
@@ -290,10 +290,10 @@ impl ApplicationLifecycle {
         model: &mut Model<CrosstermTerminalAdapter>,
         theme_init_result: ThemeInitializationResult,
     ) -> Result<(), Box<dyn StdError>> {
-        if let ThemeInitializationResult::FallbackSuccess { error_message } = theme_init_result {
-            if let Err(e) = model.mount_error_popup(&AppError::Config(error_message)) {
-                model.error_reporter.report_config_error("theme", &e);
-            }
+        if let ThemeInitializationResult::FallbackSuccess { error_message } = theme_init_result
+            && let Err(e) = model.mount_error_popup(&AppError::Config(error_message))
+        {
+            model.error_reporter.report_config_error("theme", &e);
         }
         Ok(())
     }
@@ -15,6 +15,8 @@ pub struct BulkOperationContext {
     pub reload_threshold: usize,
     pub current_message_count: usize,
     pub selected_from_current_page: usize,
+    /// Non-target messages the operation deferred and could not re-queue
+    pub still_deferred: Vec<i64>,
 }
 
 /// Type of bulk operation being performed
@@ -174,20 +176,18 @@ impl BulkOperationPostProcessor {
         }
 
         // After operations that remove messages from the main queue (delete or send-with-delete), ensure selections are cleared
-        if matches!(context.operation_type, BulkOperationType::Delete)
+        if (matches!(context.operation_type, BulkOperationType::Delete)
             || matches!(
                 context.operation_type,
                 BulkOperationType::Send {
                     should_delete: true,
                     ..
                 }
-            )
-        {
-            if let Err(e) =
+            ))
+            && let Err(e) =
                 tx_to_main.send(Msg::MessageActivity(MessageActivityMsg::ClearAllSelections))
-            {
-                error_reporter.report_send_error("clear selections", &e);
-            }
+        {
+            error_reporter.report_send_error("clear selections", &e);
         }
 
         Ok(())
@@ -288,6 +288,29 @@ impl BulkOperationPostProcessor {
         }
     }
 
+    /// Warning about non-target messages that stay deferred after an operation
+    pub fn format_still_deferred_notice(still_deferred: &[i64]) -> Option<String> {
+        if still_deferred.is_empty() {
+            return None;
+        }
+        let sequence_numbers = still_deferred
+            .iter()
+            .map(|sequence| sequence.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(format!(
+            "⚠️  {} message{} in front of the targets could not be re-queued and {} deferred (sequence numbers {sequence_numbers}).\n\
+            They are listed with the Deferred state; resend them to make them deliverable again.",
+            still_deferred.len(),
+            if still_deferred.len() == 1 { "" } else { "s" },
+            if still_deferred.len() == 1 {
+                "stays"
+            } else {
+                "stay"
+            },
+        ))
+    }
+
     /// Send the appropriate completion message for the operation type
     fn send_completion_message(
         context: &BulkOperationContext,
@@ -301,6 +324,7 @@ impl BulkOperationPostProcessor {
                         successful_count: context.successful_count,
                         failed_count: context.failed_count,
                         total_count: context.total_count,
+                        still_deferred: context.still_deferred.clone(),
                     },
                 )) {
                     error_reporter.report_send_error("bulk delete completion message", &e);
@@ -319,7 +343,7 @@ impl BulkOperationPostProcessor {
                 let queue_name_combined = format!("{from_queue_display} → {to_queue_display}");
                 let operation = if *should_delete { "move" } else { "copy" };
                 let is_delete = *should_delete;
-                let message = Self::format_bulk_operation_result_message(
+                let mut message = Self::format_bulk_operation_result_message(
                     operation,
                     &queue_name_combined,
                     context.successful_count,
@@ -328,6 +352,10 @@ impl BulkOperationPostProcessor {
                     context.total_count,
                     is_delete,
                 );
+                if let Some(notice) = Self::format_still_deferred_notice(&context.still_deferred) {
+                    message.push_str("\n\n");
+                    message.push_str(&notice);
+                }
                 if let Err(e) =
                     tx_to_main.send(Msg::PopupActivity(PopupActivityMsg::ShowSuccess(message)))
                 {
@@ -357,6 +385,7 @@ impl BulkOperationPostProcessor {
             reload_threshold,
             current_message_count,
             selected_from_current_page,
+            still_deferred: result.still_deferred.clone(),
         }
     }
 
@@ -386,6 +415,7 @@ impl BulkOperationPostProcessor {
             reload_threshold,
             current_message_count,
             selected_from_current_page,
+            still_deferred: result.still_deferred.clone(),
         }
     }
 
@@ -437,6 +467,7 @@ mod tests {
             reload_threshold: 50,
             current_message_count: 20,
             selected_from_current_page: 20, // All current messages deleted
+            still_deferred: vec![],
         };
 
         match BulkOperationPostProcessor::determine_reload_strategy(&context) {
@@ -462,6 +493,7 @@ mod tests {
             reload_threshold: 50,
             current_message_count: 20,
             selected_from_current_page: 10, // Only partial current page deleted
+            still_deferred: vec![],
         };
 
         match BulkOperationPostProcessor::determine_reload_strategy(&context) {
@@ -485,6 +517,7 @@ mod tests {
             reload_threshold: 50,
             current_message_count: 5,
             selected_from_current_page: 5, // All current deleted but small operation
+            still_deferred: vec![],
         };
 
         match BulkOperationPostProcessor::determine_reload_strategy(&context) {
@@ -508,6 +541,7 @@ mod tests {
             reload_threshold: 50,
             current_message_count: 20,
             selected_from_current_page: 3,
+            still_deferred: vec![],
         };
 
         match BulkOperationPostProcessor::determine_reload_strategy(&context) {
@@ -532,6 +566,7 @@ mod tests {
             reload_threshold: 50,
             current_message_count: 1000, // Less than successful_count, so queue will be emptied
             selected_from_current_page: 1000,
+            still_deferred: vec![],
         };
 
         match BulkOperationPostProcessor::determine_reload_strategy(&context) {
@@ -560,6 +595,7 @@ mod tests {
             reload_threshold: 10,
             current_message_count: 1000,
             selected_from_current_page: 50,
+            still_deferred: vec![],
         };
 
         match BulkOperationPostProcessor::determine_reload_strategy(&context) {
@@ -585,6 +621,7 @@ mod tests {
             reload_threshold: 50,
             current_message_count: 3000, // 3000 total messages loaded
             selected_from_current_page: 1000, // Only 1000 from current page (not all current page deleted)
+            still_deferred: vec![],
         };
 
         match BulkOperationPostProcessor::determine_reload_strategy(&context) {
@@ -612,6 +649,7 @@ mod tests {
             reload_threshold: 50,
             current_message_count: 100, // All messages would be processed
             selected_from_current_page: 100,
+            still_deferred: vec![],
         };
 
         match BulkOperationPostProcessor::determine_reload_strategy(&context) {
@@ -1,4 +1,5 @@
 pub mod queue_manager;
+pub mod queue_stats_history;
 pub mod queue_stats_manager;
 pub mod state_manager;
 
@@ -7,12 +7,17 @@ use crate::constants::env_vars::*;
 use crate::error::AppError;
 use crate::utils::auth::AuthUtils;
 use crate::utils::connection_string::ConnectionStringParser;
-use quetty_server::service_bus_manager::ServiceBusManager;
-use quetty_server::service_bus_manager::{QueueType, ServiceBusCommand, ServiceBusResponse};
+use quetty_server::service_bus_manager::{AzureAdConfig, ServiceBusManager};
+use quetty_server::service_bus_manager::{
+    QueueType, ServiceBusCommand, ServiceBusError, ServiceBusResponse,
+};
 use std::sync::Arc;
 use std::sync::mpsc::Sender;
 use tokio::sync::Mutex;
 
+/// Maximum number of sessions discovered for the session picker
+const MAX_LISTED_SESSIONS: u32 = 50;
+
 /// Manages queue operations and queue state
 pub struct QueueManager {
     pub queue_state: QueueState,
@@ -128,8 +133,8 @@ impl QueueManager {
                 let should_use_saved_config = matches!(navigation_context, NavigationContext::Startup);
                 if should_use_saved_config {
                     // Only use saved namespace during startup auto-progression
-                    if let Ok(saved_namespace) = std::env::var(AZURE_AD_NAMESPACE) {
-                        if !saved_namespace.trim().is_empty() {
+                    if let Ok(saved_namespace) = std::env::var(AZURE_AD_NAMESPACE)
+                        && !saved_namespace.trim().is_empty() {
                             log::info!(
                                 "Startup mode: Found saved namespace '{saved_namespace}', using it directly"
                             );
@@ -142,7 +147,6 @@ impl QueueManager {
                             }
                             return Ok(());
                         }
-                    }
                 } else {
                     log::debug!("Navigation mode ({navigation_context:?}): forcing namespace discovery to allow user selection");
                 }
@@ -232,6 +236,41 @@ impl QueueManager {
         });
     }
 
+    /// Load topics and their subscriptions for the topic picker
+    ///
+    /// Topic discovery needs the Azure Management API, so connection string
+    /// authentication gets an empty list and falls back to manual entry.
+    pub fn load_topics(&self, azure_ad_config: AzureAdConfig) {
+        let tx_to_main = self.tx_to_main.clone();
+        let connection_string_auth =
+            AuthUtils::is_connection_string_auth(config::get_config_or_panic());
+
+        self.task_manager.execute("Loading topics...", async move {
+            let topics = if connection_string_auth {
+                log::info!("Connection string authentication - topic discovery unavailable");
+                Vec::new()
+            } else {
+                ServiceBusManager::list_topics_azure_ad(&azure_ad_config)
+                    .await
+                    .map_err(|e| {
+                        log::error!("Failed to list topics: {e}");
+                        AppError::ServiceBus(e.to_string())
+                    })?
+            };
+
+            log::info!("Loaded {} topics", topics.len());
+
+            if let Err(e) =
+                tx_to_main.send(Msg::QueueActivity(QueueActivityMsg::TopicsLoaded(topics)))
+            {
+                log::error!("Failed to send topics loaded message: {e}");
+                return Err(AppError::Component(e.to_string()));
+            }
+
+            Ok(())
+        });
+    }
+
     /// Send empty queue list to trigger manual queue selection UI
     fn send_empty_queue_list_for_manual_selection(&self) {
         let tx_to_main = self.tx_to_main.clone();
@@ -387,6 +426,7 @@ impl QueueManager {
 
         // Determine the correct queue type from the queue name
         let queue_type = QueueType::from_queue_name(&queue_name);
+        let session_id = self.queue_state.current_session_id.clone();
 
         // Generate unique operation ID for cancellation support
         let operation_id = format!(
@@ -410,6 +450,7 @@ impl QueueManager {
                     let command = ServiceBusCommand::SwitchQueue {
                         queue_name: queue_name.clone(),
                         queue_type,
+                        session_id,
                     };
 
                     progress.report_progress("Switching to queue...");
@@ -423,6 +464,20 @@ impl QueueManager {
                             log::info!("Successfully switched to queue: {}", queue_info.name);
                             queue_info
                         }
+                        ServiceBusResponse::Error {
+                            error: ServiceBusError::SessionRequired(_),
+                        } => {
+                            log::info!(
+                                "Queue {queue_name} requires a session, opening session picker"
+                            );
+                            if let Err(e) = tx_to_main.send(Msg::QueueActivity(
+                                QueueActivityMsg::SessionRequired(queue_name),
+                            )) {
+                                log::error!("Failed to send session required message: {e}");
+                                return Err(AppError::Component(e.to_string()));
+                            }
+                            return Ok(());
+                        }
                         ServiceBusResponse::Error { error } => {
                             log::error!("Failed to switch to queue {queue_name}: {error}");
                             return Err(AppError::ServiceBus(error.to_string()));
@@ -457,4 +512,134 @@ impl QueueManager {
             },
         );
     }
+
+    /// Discover the sessions of a session-enabled queue for the session picker
+    pub fn load_sessions(&self, queue_name: String) {
+        let Some(service_bus_manager) = self.service_bus_manager.clone() else {
+            log::error!("Service bus manager not initialized, cannot list sessions");
+            return;
+        };
+        let tx_to_main = self.tx_to_main.clone();
+
+        self.task_manager
+            .execute("Looking for sessions...", async move {
+                let command = ServiceBusCommand::ListSessions {
+                    queue_name: queue_name.clone(),
+                    max_sessions: MAX_LISTED_SESSIONS,
+                };
+
+                let response = service_bus_manager
+                    .lock()
+                    .await
+                    .execute_command(command)
+                    .await;
+
+                let sessions = match response {
+                    ServiceBusResponse::SessionsListed { sessions } => sessions,
+                    ServiceBusResponse::Error { error } => {
+                        log::error!("Failed to list sessions for {queue_name}: {error}");
+                        return Err(AppError::ServiceBus(error.to_string()));
+                    }
+                    _ => {
+                        return Err(AppError::ServiceBus(
+                            "Unexpected response for list sessions".to_string(),
+                        ));
+                    }
+                };
+
+                log::info!("Found {} sessions on {queue_name}", sessions.len());
+
+                if let Err(e) = tx_to_main.send(Msg::QueueActivity(
+                    QueueActivityMsg::SessionsLoaded(sessions),
+                )) {
+                    log::error!("Failed to send sessions loaded message: {e}");
+                    return Err(AppError::Component(e.to_string()));
+                }
+
+                Ok(())
+            });
+    }
+
+    /// Read the state of the session the current queue is opened with
+    pub fn load_session_state(&self) {
+        let Some(service_bus_manager) = self.service_bus_manager.clone() else {
+            log::error!("Service bus manager not initialized, cannot read session state");
+            return;
+        };
+        let tx_to_main = self.tx_to_main.clone();
+
+        self.task_manager
+            .execute("Reading session state...", async move {
+                let response = service_bus_manager
+                    .lock()
+                    .await
+                    .execute_command(ServiceBusCommand::GetSessionState)
+                    .await;
+
+                let (session_id, state) = match response {
+                    ServiceBusResponse::SessionState { session_id, state } => (session_id, state),
+                    ServiceBusResponse::Error { error } => {
+                        log::error!("Failed to read session state: {error}");
+                        return Err(AppError::ServiceBus(error.to_string()));
+                    }
+                    _ => {
+                        return Err(AppError::ServiceBus(
+                            "Unexpected response for get session state".to_string(),
+                        ));
+                    }
+                };
+
+                if let Err(e) =
+                    tx_to_main.send(Msg::QueueActivity(QueueActivityMsg::SessionStateLoaded {
+                        session_id,
+                        state,
+                    }))
+                {
+                    log::error!("Failed to send session state loaded message: {e}");
+                    return Err(AppError::Component(e.to_string()));
+                }
+
+                Ok(())
+            });
+    }
+
+    /// Replace the state of the session the current queue is opened with
+    pub fn save_session_state(&self, state: Vec<u8>) {
+        let Some(service_bus_manager) = self.service_bus_manager.clone() else {
+            log::error!("Service bus manager not initialized, cannot update session state");
+            return;
+        };
+        let tx_to_main = self.tx_to_main.clone();
+
+        self.task_manager
+            .execute("Saving session state...", async move {
+                let response = service_bus_manager
+                    .lock()
+                    .await
+                    .execute_command(ServiceBusCommand::SetSessionState { state })
+                    .await;
+
+                let session_id = match response {
+                    ServiceBusResponse::SessionStateUpdated { session_id } => session_id,
+                    ServiceBusResponse::Error { error } => {
+                        log::error!("Failed to update session state: {error}");
+                        return Err(AppError::ServiceBus(error.to_string()));
+                    }
+                    _ => {
+                        return Err(AppError::ServiceBus(
+                            "Unexpected response for set session state".to_string(),
+                        ));
+                    }
+                };
+
+                if let Err(e) = tx_to_main.send(Msg::ShowSuccess(format!(
+                    "✅ State of session '{session_id}' updated"
+                ))) {
+                    log::error!("Failed to send session state updated message: {e}");
+                    return Err(AppError::Component(e.to_string()));
+                }
+
+                Ok(())
+            });
+    }
 }
@@ -0,0 +1,193 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+
+/// Active and dead letter counts of a queue at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueStatsSample {
+    pub at: DateTime<Utc>,
+    pub active_count: u64,
+    pub dlq_count: u64,
+}
+
+/// Estimated message flow between the samples of a time range, per minute.
+///
+/// Counts only reveal the net change between two samples, so a queue that
+/// received and delivered messages in between shows just the difference.
+/// The rates are therefore lower bounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueueStatsRates {
+    pub active_in_per_minute: f64,
+    pub active_out_per_minute: f64,
+    pub dlq_in_per_minute: f64,
+    pub dlq_out_per_minute: f64,
+}
+
+impl QueueStatsRates {
+    /// Estimates the rates over consecutive samples; `None` for fewer than
+    /// two samples or samples without time between them
+    pub fn estimate(samples: &[QueueStatsSample]) -> Option<Self> {
+        let (first, last) = (samples.first()?, samples.last()?);
+        let minutes = (last.at - first.at).num_milliseconds() as f64 / 60_000.0;
+        if minutes <= 0.0 {
+            return None;
+        }
+
+        let (mut active_in, mut active_out, mut dlq_in, mut dlq_out) = (0, 0, 0, 0);
+        for pair in samples.windows(2) {
+            let (before, after) = (pair[0], pair[1]);
+            active_in += after.active_count.saturating_sub(before.active_count);
+            active_out += before.active_count.saturating_sub(after.active_count);
+            dlq_in += after.dlq_count.saturating_sub(before.dlq_count);
+            dlq_out += before.dlq_count.saturating_sub(after.dlq_count);
+        }
+
+        Some(Self {
+            active_in_per_minute: active_in as f64 / minutes,
+            active_out_per_minute: active_out as f64 / minutes,
+            dlq_in_per_minute: dlq_in as f64 / minutes,
+            dlq_out_per_minute: dlq_out as f64 / minutes,
+        })
+    }
+
+    /// Growth of the dead letter queue per minute; negative while it shrinks
+    pub fn dlq_net_per_minute(&self) -> f64 {
+        self.dlq_in_per_minute - self.dlq_out_per_minute
+    }
+}
+
+/// Rolling time series of queue statistics, keyed by base queue name.
+///
+/// Samples older than the retention passed to [`Self::record`] are dropped.
+/// The history can be stored as JSON, e.g. per profile, to survive restarts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct QueueStatsHistory {
+    queues: HashMap<String, VecDeque<QueueStatsSample>>,
+}
+
+impl QueueStatsHistory {
+    /// Create an empty history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a history written by [`Self::save`]
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+    }
+
+    /// Write the history as JSON, replacing the file
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string(self)
+            .map_err(|e| format!("Failed to serialize queue statistics history: {e}"))?;
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// Append a sample and drop the samples of the queue older than `retention`
+    pub fn record(&mut self, queue_name: &str, sample: QueueStatsSample, retention: Duration) {
+        let samples = self.queues.entry(queue_name.to_string()).or_default();
+        // Samples arrive in order; a repeated or older one adds nothing to the series
+        if samples.back().is_some_and(|last| last.at >= sample.at) {
+            return;
+        }
+        samples.push_back(sample);
+
+        let cutoff = sample.at - retention;
+        while samples.front().is_some_and(|oldest| oldest.at < cutoff) {
+            samples.pop_front();
+        }
+    }
+
+    /// Samples of the queue taken within the last `minutes` before `now`, oldest first
+    pub fn last_minutes(
+        &self,
+        queue_name: &str,
+        minutes: i64,
+        now: DateTime<Utc>,
+    ) -> Vec<QueueStatsSample> {
+        let since = now - Duration::minutes(minutes);
+        self.queues
+            .get(queue_name)
+            .map(|samples| {
+                samples
+                    .iter()
+                    .filter(|sample| sample.at >= since)
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(minute: i64, active_count: u64, dlq_count: u64) -> QueueStatsSample {
+        QueueStatsSample {
+            at: DateTime::<Utc>::UNIX_EPOCH + Duration::minutes(minute),
+            active_count,
+            dlq_count,
+        }
+    }
+
+    #[test]
+    fn test_record_keeps_samples_within_retention() {
+        let mut history = QueueStatsHistory::new();
+        for minute in 0..10 {
+            history.record(
+                "orders",
+                sample(minute, minute as u64, 0),
+                Duration::minutes(5),
+            );
+        }
+        // A sample older than the latest one is ignored
+        history.record("orders", sample(3, 100, 0), Duration::minutes(5));
+
+        let now = DateTime::<Utc>::UNIX_EPOCH + Duration::minutes(9);
+        let all = history.last_minutes("orders", 60, now);
+        assert_eq!(all.first(), Some(&sample(4, 4, 0)));
+        assert_eq!(all.len(), 6);
+
+        let recent = history.last_minutes("orders", 2, now);
+        assert_eq!(
+            recent,
+            vec![sample(7, 7, 0), sample(8, 8, 0), sample(9, 9, 0)]
+        );
+        assert!(history.last_minutes("payments", 60, now).is_empty());
+    }
+
+    #[test]
+    fn test_rates_split_growth_and_shrinkage() {
+        let samples = [sample(0, 10, 5), sample(1, 30, 7), sample(2, 20, 4)];
+        let rates = QueueStatsRates::estimate(&samples).unwrap();
+        assert_eq!(rates.active_in_per_minute, 10.0);
+        assert_eq!(rates.active_out_per_minute, 5.0);
+        assert_eq!(rates.dlq_in_per_minute, 1.0);
+        assert_eq!(rates.dlq_out_per_minute, 1.5);
+        assert_eq!(rates.dlq_net_per_minute(), -0.5);
+
+        assert_eq!(QueueStatsRates::estimate(&samples[..1]), None);
+        assert_eq!(QueueStatsRates::estimate(&[]), None);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("queue_stats_history.json");
+
+        let mut history = QueueStatsHistory::new();
+        history.record("orders", sample(0, 1, 2), Duration::minutes(60));
+        history.record("orders", sample(1, 3, 4), Duration::minutes(60));
+        history.save(&path).unwrap();
+
+        assert_eq!(QueueStatsHistory::load(&path).unwrap(), history);
+        assert!(QueueStatsHistory::load(&dir.path().join("missing.json")).is_err());
+    }
+}
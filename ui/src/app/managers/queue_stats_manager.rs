@@ -1,11 +1,17 @@
+use super::queue_stats_history::{QueueStatsHistory, QueueStatsSample};
 use crate::app::updates::messages::pagination::QueueStatsCache;
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Manages queue statistics caching and retrieval
 #[derive(Debug)]
 pub struct QueueStatsManager {
     /// Cache for queue statistics - supports multiple queues
     stats_cache: HashMap<String, QueueStatsCache>,
+    /// Time series of every statistics update, per queue
+    history: QueueStatsHistory,
+    /// File the history is stored in after every update, if persisted
+    history_path: Option<PathBuf>,
 }
 
 impl QueueStatsManager {
@@ -13,9 +19,28 @@ impl QueueStatsManager {
     pub fn new() -> Self {
         Self {
             stats_cache: HashMap::new(),
+            history: QueueStatsHistory::new(),
+            history_path: None,
         }
     }
 
+    /// Continue the history stored at `path` and keep storing it there
+    pub fn persist_history(&mut self, path: PathBuf) {
+        if path.exists() {
+            match QueueStatsHistory::load(&path) {
+                Ok(history) => self.history = history,
+                Err(e) => log::warn!("Starting a new queue statistics history: {e}"),
+            }
+        }
+        log::info!("Storing queue statistics history in {}", path.display());
+        self.history_path = Some(path);
+    }
+
+    /// Time series of the statistics updates
+    pub fn history(&self) -> &QueueStatsHistory {
+        &self.history
+    }
+
     /// Generate cache key for a queue name using current authentication method
     fn make_cache_key_for_queue(&self, queue_name: &str) -> String {
         let config = crate::config::get_config_or_panic();
@@ -44,9 +69,29 @@ impl QueueStatsManager {
             cache.active_count,
             cache.dlq_count
         );
+        self.record_history(&cache);
         self.stats_cache.insert(cache_key, cache);
     }
 
+    fn record_history(&mut self, cache: &QueueStatsCache) {
+        let retention = crate::config::get_config_or_panic().queue_stats_history_minutes();
+        self.history.record(
+            &cache.queue_name,
+            QueueStatsSample {
+                at: cache.fetched_at,
+                active_count: cache.active_count,
+                dlq_count: cache.dlq_count,
+            },
+            chrono::Duration::minutes(retention as i64),
+        );
+
+        if let Some(path) = &self.history_path
+            && let Err(e) = self.history.save(path)
+        {
+            log::warn!("Failed to store queue statistics history: {e}");
+        }
+    }
+
     /// Get cached stats if valid for specific queue
     pub fn get_cached_stats(&self, queue_name: &str) -> Option<&QueueStatsCache> {
         let cache_key = self.make_cache_key_for_queue(queue_name);
@@ -9,6 +9,8 @@ use std::time::Duration;
 pub enum AppState {
     NamespacePicker,
     QueuePicker,
+    TopicPicker,
+    SessionPicker,
     MessagePicker,
     MessageDetails,
     Loading,
@@ -124,10 +126,22 @@ impl StateManager {
             // Queue picker transitions
             (AppState::QueuePicker, AppState::MessagePicker) => true,
             (AppState::QueuePicker, AppState::NamespacePicker) => true,
+            (AppState::QueuePicker, AppState::TopicPicker) => true,
+            (AppState::QueuePicker, AppState::SessionPicker) => true,
+
+            // Topic picker transitions
+            (AppState::TopicPicker, AppState::MessagePicker) => true,
+            (AppState::TopicPicker, AppState::QueuePicker) => true,
+            (AppState::TopicPicker, AppState::SessionPicker) => true,
+
+            // Session picker transitions
+            (AppState::SessionPicker, AppState::MessagePicker) => true,
+            (AppState::SessionPicker, AppState::QueuePicker) => true,
 
             // Message picker transitions
             (AppState::MessagePicker, AppState::MessageDetails) => true,
             (AppState::MessagePicker, AppState::QueuePicker) => true,
+            (AppState::MessagePicker, AppState::SessionPicker) => true,
 
             // Message details transitions
             (AppState::MessageDetails, AppState::MessagePicker) => true,
@@ -54,6 +54,7 @@ where
         self.reset_pagination_state();
         self.queue_state_mut().messages = None;
         self.queue_state_mut().bulk_selection.clear_all();
+        self.queue_state_mut().search = None;
 
         // Reset last loaded sequence to force loading from beginning
         self.queue_state_mut()
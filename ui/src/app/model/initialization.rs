@@ -2,7 +2,7 @@ use super::Model;
 use crate::app::managers::{QueueManager, StateManager};
 use crate::app::queue_state::QueueState;
 use crate::app::task_manager::TaskManager;
-use crate::components::common::{ComponentId, Msg};
+use crate::components::common::{ComponentId, MessageActivityMsg, Msg, QueueActivityMsg};
 use crate::components::global_key_watcher::GlobalKeyWatcher;
 use crate::components::loading_indicator::LoadingIndicator;
 use crate::components::message_details::MessageDetails;
@@ -15,6 +15,7 @@ use crate::config;
 use crate::constants::env_vars::*;
 use crate::error::{AppError, AppResult, ErrorReporter};
 use crate::utils::auth::AuthUtils;
+use quetty_server::decoding::DecoderChain;
 use quetty_server::service_bus_manager::ServiceBusManager;
 use quetty_server::taskpool::TaskPool;
 use std::sync::Arc;
@@ -140,6 +141,7 @@ impl Model<CrosstermTerminalAdapter> {
             state_manager,
             queue_manager,
             auth_service,
+            body_decoders: DecoderChain::new(config.decoders().clone()),
         };
 
         // Don't mount loading indicator if we need authentication
@@ -152,12 +154,68 @@ impl Model<CrosstermTerminalAdapter> {
             )?;
         }
 
+        if config.queue_stats_history_persist()
+            && let Some(profile_dir) = config::current_profile_dir()
+        {
+            app.queue_manager
+                .queue_state
+                .stats_manager
+                .persist_history(profile_dir.join("queue_stats_history.json"));
+        }
+        Self::start_queue_stats_sampling(config, tx_to_main.clone());
+        Self::start_alert_checks(config, tx_to_main.clone());
+
         // Trigger initial authentication or load namespaces
         Self::trigger_initial_flow(needs_auth, &mut app)?;
 
         Ok(app)
     }
 
+    /// Periodically sample the open queue's statistics into its history
+    fn start_queue_stats_sampling(
+        config: &crate::config::AppConfig,
+        tx_to_main: mpsc::Sender<Msg>,
+    ) {
+        let interval_seconds = config.queue_stats_history_interval_seconds();
+        if interval_seconds == 0 {
+            return;
+        }
+
+        let period = std::time::Duration::from_secs(interval_seconds);
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let sample = Msg::MessageActivity(MessageActivityMsg::SampleQueueStatistics);
+                if tx_to_main.send(sample).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Periodically check the configured alert thresholds of the watched queues
+    fn start_alert_checks(config: &crate::config::AppConfig, tx_to_main: mpsc::Sender<Msg>) {
+        if config.alerts().thresholds().is_empty() {
+            return;
+        }
+
+        let period = config.alerts().check_interval();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let check = Msg::QueueActivity(QueueActivityMsg::AlertCheckDue);
+                if tx_to_main.send(check).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
     /// Create optimized HTTP client with connection pooling
     fn create_http_client() -> reqwest::Client {
         use std::time::Duration;
@@ -6,6 +6,7 @@ use crate::components::common::{ComponentId, Msg};
 use crate::error::AppError;
 use crate::error::ErrorReporter;
 use crate::services::AuthService;
+use quetty_server::decoding::DecoderChain;
 use quetty_server::service_bus_manager::ServiceBusManager;
 use quetty_server::service_bus_manager::{ServiceBusCommand, ServiceBusResponse};
 use quetty_server::taskpool::TaskPool;
@@ -54,6 +55,9 @@ where
 
     // Authentication service
     pub auth_service: Option<Arc<AuthService>>,
+
+    // Decoders applied to message bodies shown in the details view
+    pub body_decoders: DecoderChain,
 }
 
 impl<T> Model<T>
@@ -1,5 +1,6 @@
 use super::{AppState, Model};
-use crate::components::common::ComponentId;
+use crate::components::alerts_popup::{AlertStatus, AlertsPopup};
+use crate::components::common::{ComponentId, Msg};
 use crate::components::confirmation_popup::ConfirmationPopup;
 use crate::components::error_popup::ErrorPopup;
 use crate::components::global_key_watcher::GlobalKeyWatcher;
@@ -7,10 +8,15 @@ use crate::components::loading_indicator::LoadingIndicator;
 use crate::components::number_input_popup::NumberInputPopup;
 use crate::components::page_size_popup::PageSizePopup;
 use crate::components::password_popup::PasswordPopup;
+use crate::components::queue_dashboard::{DashboardRow, DashboardView, QueueDashboard};
+use crate::components::queue_info_popup::QueueInfoPopup;
 use crate::components::state::ComponentStateMount;
+use crate::components::subscription_rules_popup::{RuleTestResult, SubscriptionRulesPopup};
 use crate::components::success_popup::SuccessPopup;
+use crate::components::text_input_popup::TextInputPopup;
 use crate::components::theme_picker::ThemePicker;
 use crate::error::{AppError, AppResult};
+use quetty_server::service_bus_manager::{QueueDetails, SubscriptionRule};
 use tuirealm::terminal::TerminalAdapter;
 use tuirealm::{Sub, SubClause, SubEventClause};
 
@@ -22,10 +28,10 @@ where
         log::debug!("Mounting loading indicator with message: {message}");
 
         // Unmount existing loading indicator if any
-        if self.app.mounted(&ComponentId::LoadingIndicator) {
-            if let Err(e) = self.app.umount(&ComponentId::LoadingIndicator) {
-                log::error!("Failed to unmount loading indicator: {e}");
-            }
+        if self.app.mounted(&ComponentId::LoadingIndicator)
+            && let Err(e) = self.app.umount(&ComponentId::LoadingIndicator)
+        {
+            log::error!("Failed to unmount loading indicator: {e}");
         }
 
         // Mount with ComponentState pattern using extension trait
@@ -118,6 +124,130 @@ where
         Ok(())
     }
 
+    /// Mount the queue properties popup and give focus to it
+    pub fn mount_queue_info_popup(&mut self, details: QueueDetails) -> AppResult<()> {
+        self.app.remount_with_state(
+            ComponentId::QueueInfoPopup,
+            QueueInfoPopup::new(details),
+            Vec::default(),
+        )?;
+
+        self.app
+            .active(&ComponentId::QueueInfoPopup)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.set_redraw(true);
+        Ok(())
+    }
+
+    /// Unmount the queue properties popup and return focus to previous component
+    pub fn unmount_queue_info_popup(&mut self) -> AppResult<()> {
+        self.app
+            .umount(&ComponentId::QueueInfoPopup)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.activate_component_for_current_state()?;
+        self.set_redraw(true);
+        Ok(())
+    }
+
+    /// Mount the subscription rules popup and give focus to it
+    pub fn mount_subscription_rules_popup(
+        &mut self,
+        subscription_path: String,
+        rules: Vec<SubscriptionRule>,
+        selected: usize,
+        test: Option<RuleTestResult>,
+    ) -> AppResult<()> {
+        self.app.remount_with_state(
+            ComponentId::SubscriptionRulesPopup,
+            SubscriptionRulesPopup::new(subscription_path, rules, selected, test),
+            Vec::default(),
+        )?;
+
+        self.app
+            .active(&ComponentId::SubscriptionRulesPopup)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.set_redraw(true);
+        Ok(())
+    }
+
+    /// Unmount the subscription rules popup and return focus to previous component
+    pub fn unmount_subscription_rules_popup(&mut self) -> AppResult<()> {
+        self.app
+            .umount(&ComponentId::SubscriptionRulesPopup)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.activate_component_for_current_state()?;
+        self.set_redraw(true);
+        Ok(())
+    }
+
+    /// Mount the queue dashboard and give focus to it
+    pub fn mount_queue_dashboard(
+        &mut self,
+        rows: Vec<DashboardRow>,
+        view: DashboardView,
+        status: String,
+    ) -> AppResult<()> {
+        self.app.remount_with_state(
+            ComponentId::QueueDashboard,
+            QueueDashboard::new(rows, view, status),
+            Vec::default(),
+        )?;
+
+        self.app
+            .active(&ComponentId::QueueDashboard)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.set_redraw(true);
+        Ok(())
+    }
+
+    /// Unmount the queue dashboard and return focus to previous component
+    pub fn unmount_queue_dashboard(&mut self) -> AppResult<()> {
+        self.app
+            .umount(&ComponentId::QueueDashboard)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.activate_component_for_current_state()?;
+        self.set_redraw(true);
+        Ok(())
+    }
+
+    /// Mount the alerts popup and give focus to it
+    pub fn mount_alerts_popup(
+        &mut self,
+        statuses: Vec<AlertStatus>,
+        selected: usize,
+        status: String,
+    ) -> AppResult<()> {
+        self.app.remount_with_state(
+            ComponentId::AlertsPopup,
+            AlertsPopup::new(statuses, selected, status),
+            Vec::default(),
+        )?;
+
+        self.app
+            .active(&ComponentId::AlertsPopup)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.set_redraw(true);
+        Ok(())
+    }
+
+    /// Unmount the alerts popup and return focus to previous component
+    pub fn unmount_alerts_popup(&mut self) -> AppResult<()> {
+        self.app
+            .umount(&ComponentId::AlertsPopup)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.activate_component_for_current_state()?;
+        self.set_redraw(true);
+        Ok(())
+    }
+
     pub fn mount_confirmation_popup(&mut self, title: &str, message: &str) -> AppResult<()> {
         self.app.remount_with_state(
             ComponentId::ConfirmationPopup,
@@ -152,6 +282,26 @@ where
         Ok(())
     }
 
+    pub fn mount_text_input_popup(
+        &mut self,
+        title: String,
+        message: String,
+        initial_value: String,
+        on_submit: fn(String) -> Msg,
+    ) -> AppResult<()> {
+        self.app.remount_with_state(
+            ComponentId::TextInputPopup,
+            TextInputPopup::new(title, message, initial_value, on_submit),
+            Vec::default(),
+        )?;
+
+        self.app
+            .active(&ComponentId::TextInputPopup)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        Ok(())
+    }
+
     pub fn mount_page_size_popup(&mut self) -> AppResult<()> {
         self.app.remount_with_state(
             ComponentId::PageSizePopup,
@@ -188,6 +338,17 @@ where
         Ok(())
     }
 
+    pub fn unmount_text_input_popup(&mut self) -> AppResult<()> {
+        self.app
+            .umount(&ComponentId::TextInputPopup)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        // Return to appropriate state
+        self.activate_component_for_current_state()?;
+        self.set_redraw(true);
+        Ok(())
+    }
+
     pub fn unmount_page_size_popup(&mut self) -> AppResult<()> {
         self.app
             .umount(&ComponentId::PageSizePopup)
@@ -406,6 +567,16 @@ where
                     .active(&ComponentId::QueuePicker)
                     .map_err(|e| AppError::Component(e.to_string()))?;
             }
+            AppState::TopicPicker => {
+                self.app
+                    .active(&ComponentId::TopicPicker)
+                    .map_err(|e| AppError::Component(e.to_string()))?;
+            }
+            AppState::SessionPicker => {
+                self.app
+                    .active(&ComponentId::SessionPicker)
+                    .map_err(|e| AppError::Component(e.to_string()))?;
+            }
             AppState::MessagePicker => {
                 self.app
                     .active(&ComponentId::Messages)
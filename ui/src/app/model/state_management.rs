@@ -1,5 +1,6 @@
 use super::{AppState, Model};
 use crate::app::view::*;
+use crate::components::alerts_popup::alert_badge;
 use crate::components::common::ComponentId;
 use crate::components::help_bar::HelpBar;
 use crate::error::{AppError, AppResult};
@@ -19,6 +20,8 @@ where
         let active_component = match current_app_state {
             AppState::NamespacePicker => ComponentId::NamespacePicker,
             AppState::QueuePicker => ComponentId::QueuePicker,
+            AppState::TopicPicker => ComponentId::TopicPicker,
+            AppState::SessionPicker => ComponentId::SessionPicker,
             AppState::MessagePicker => ComponentId::Messages,
             AppState::MessageDetails => ComponentId::MessageDetails,
             AppState::Loading => ComponentId::LoadingIndicator,
@@ -58,6 +61,9 @@ where
             (None, None, None)
         };
 
+        let alerts = &queue_state.alerts;
+        let (breached_alerts, unseen_alerts) = (alerts.breached_count(), alerts.unseen);
+
         // Draw to the terminal and handle any errors
         if let Err(e) = self.terminal.draw(|f| {
             let chunks = Layout::default()
@@ -76,6 +82,9 @@ where
                 .split(f.area());
 
             self.app.view(&ComponentId::TextLabel, f, chunks[1]);
+            if let Some(badge) = alert_badge(breached_alerts, unseen_alerts) {
+                f.render_widget(badge, chunks[0]);
+            }
 
             // Apply the view based on the app state, with error popup handling
             view_result = match current_app_state {
@@ -83,6 +92,10 @@ where
                     with_popup(&mut self.app, f, &chunks, view_namespace_picker)
                 }
                 AppState::QueuePicker => with_popup(&mut self.app, f, &chunks, view_queue_picker),
+                AppState::TopicPicker => with_popup(&mut self.app, f, &chunks, view_topic_picker),
+                AppState::SessionPicker => {
+                    with_popup(&mut self.app, f, &chunks, view_session_picker)
+                }
                 AppState::MessagePicker => {
                     with_popup(&mut self.app, f, &chunks, view_message_picker)
                 }
@@ -103,8 +116,13 @@ where
             // View help bar (if not showing any popup) with active component
             if !self.app.mounted(&ComponentId::ErrorPopup)
                 && !self.app.mounted(&ComponentId::SuccessPopup)
+                && !self.app.mounted(&ComponentId::QueueInfoPopup)
+                && !self.app.mounted(&ComponentId::SubscriptionRulesPopup)
+                && !self.app.mounted(&ComponentId::QueueDashboard)
+                && !self.app.mounted(&ComponentId::AlertsPopup)
                 && !self.app.mounted(&ComponentId::ConfirmationPopup)
                 && !self.app.mounted(&ComponentId::NumberInputPopup)
+                && !self.app.mounted(&ComponentId::TextInputPopup)
                 && !self.app.mounted(&ComponentId::PageSizePopup)
                 && !self.app.mounted(&ComponentId::ThemePicker)
                 && !self.app.mounted(&ComponentId::AuthPopup)
@@ -1,9 +1,16 @@
 use crate::app::managers::queue_stats_manager::QueueStatsManager;
+use crate::app::updates::alerts::AlertsState;
+use crate::app::updates::dashboard::DashboardState;
 use crate::app::updates::messages::MessagePaginationState;
-use quetty_server::bulk_operations::MessageIdentifier;
+use crate::app::updates::messages::follow::FollowState;
+use crate::app::updates::messages::transfer::PendingTransfer;
+use crate::app::updates::queue_admin::QueueAdminOperation;
+use crate::app::updates::subscription_rules::SubscriptionRulesState;
+use quetty_server::bulk_operations::{ExportScope, MessageIdentifier};
 use quetty_server::model::MessageModel;
 use quetty_server::service_bus_manager::QueueType;
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 /// Unique identifier for a message combining ID and sequence
 /// State for managing bulk selection of messages
@@ -178,6 +185,40 @@ impl BulkSelectionState {
     }
 }
 
+/// A whole-queue search whose matches are shown in place of the loaded pages
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageSearch {
+    /// Identifies the run, so updates from a replaced search are ignored
+    pub id: u64,
+    /// SQL filter expression as entered
+    pub expression: String,
+    /// Matches found so far, in queue order
+    pub matches: Vec<MessageModel>,
+    /// Messages examined so far
+    pub scanned: usize,
+    /// Whether the search is still walking the queue
+    pub running: bool,
+    /// Whether the search stopped at the configured match limit
+    pub truncated: bool,
+    /// Whether the user cancelled the search
+    pub cancelled: bool,
+}
+
+impl MessageSearch {
+    /// Start tracking a new search run
+    pub fn new(id: u64, expression: String) -> Self {
+        Self {
+            id,
+            expression,
+            matches: Vec::new(),
+            scanned: 0,
+            running: true,
+            truncated: false,
+            cancelled: false,
+        }
+    }
+}
+
 /// Encapsulates all queue-related state and data
 #[derive(Debug)]
 pub struct QueueState {
@@ -187,6 +228,8 @@ pub struct QueueState {
     pub current_queue_name: Option<String>,
     /// Current queue type (Main or DeadLetter)
     pub current_queue_type: QueueType,
+    /// Session the queue is opened with, for session-enabled queues
+    pub current_session_id: Option<String>,
     /// Currently loaded messages
     pub messages: Option<Vec<MessageModel>>,
     /// Message pagination state
@@ -197,6 +240,24 @@ pub struct QueueState {
     pub bulk_selection: BulkSelectionState,
     /// Message repeat count for bulk sending (1-1000)
     pub message_repeat_count: usize,
+    /// Search whose matches replace the loaded pages while it is set
+    pub search: Option<MessageSearch>,
+    /// Messages chosen for an export while its file path is being entered
+    pub pending_export: Option<ExportScope>,
+    /// File chosen for an import while its options are being entered
+    pub pending_import: Option<PathBuf>,
+    /// Copy or move to another namespace while its target is being entered
+    pub pending_transfer: Option<PendingTransfer>,
+    /// Follow mode, peeking for new messages while it is set
+    pub follow: Option<FollowState>,
+    /// Queue create, edit or delete while its settings and confirmation are entered
+    pub pending_queue_admin: Option<QueueAdminOperation>,
+    /// Rules of the current subscription while the rules popup is open
+    pub subscription_rules: Option<SubscriptionRulesState>,
+    /// Namespace dashboard while it is open
+    pub dashboard: Option<DashboardState>,
+    /// Alert thresholds of the watched queues, checked in the background
+    pub alerts: AlertsState,
 }
 
 impl Default for QueueState {
@@ -205,11 +266,21 @@ impl Default for QueueState {
             pending_queue: None,
             current_queue_name: None,
             current_queue_type: QueueType::Main,
+            current_session_id: None,
             messages: None,
             message_pagination: MessagePaginationState::default(),
             stats_manager: QueueStatsManager::new(),
             bulk_selection: BulkSelectionState::default(),
             message_repeat_count: 1, // Default to sending once
+            search: None,
+            pending_export: None,
+            pending_import: None,
+            pending_transfer: None,
+            follow: None,
+            pending_queue_admin: None,
+            subscription_rules: None,
+            dashboard: None,
+            alerts: AlertsState::default(),
         }
     }
 }
@@ -225,9 +296,16 @@ impl QueueState {
         self.pending_queue = Some(queue_name.clone());
         self.current_queue_name = Some(queue_name);
         self.current_queue_type = QueueType::Main;
+        self.current_session_id = None;
         // Clear previous messages and pagination when switching queues
         self.messages = None;
         self.message_pagination.reset();
+        self.search = None;
+        self.pending_export = None;
+        self.pending_import = None;
+        self.pending_transfer = None;
+        self.follow = None;
+        self.subscription_rules = None;
     }
 
     /// Toggle between main queue and dead letter queue
@@ -263,6 +341,11 @@ impl QueueState {
             self.current_queue_type = new_queue_type;
             self.messages = None;
             self.message_pagination.reset();
+            self.search = None;
+            self.pending_export = None;
+            self.pending_import = None;
+            self.pending_transfer = None;
+            self.follow = None;
 
             log::info!(
                 "Queue toggle: cleared all message cache, switching from {:?} to {:?} ({})",
@@ -1,11 +1,19 @@
+use crate::app::managers::queue_stats_history::QueueStatsRates;
 use crate::app::model::Model;
 use crate::components::common::ComponentId;
 use crate::components::message_details::MessageDetails;
-use crate::components::messages::{Messages, PaginationInfo};
+use crate::components::message_details::body_view::DecodedView;
+use crate::components::messages::{
+    FollowStatus, Messages, PaginationInfo, SearchStatus, StatsTrend,
+};
 use crate::components::namespace_picker::NamespacePicker;
 use crate::components::queue_picker::QueuePicker;
+use crate::components::session_picker::SessionPicker;
 use crate::components::state::ComponentStateMount;
+use crate::components::topic_picker::TopicPicker;
 use crate::error::{AppError, AppResult};
+use quetty_server::model::MessageModel;
+use quetty_server::service_bus_manager::TopicInfo;
 use tuirealm::terminal::TerminalAdapter;
 
 use crate::app::managers::state_manager::AppState;
@@ -27,16 +35,38 @@ where
             None
         };
 
+        let decoded = message
+            .as_ref()
+            .and_then(|message| self.decode_message_body(message));
+
         // Use ComponentState extension trait for single-call remounting
         self.app.remount_with_state(
             ComponentId::MessageDetails,
-            MessageDetails::new_with_focus(message, is_focused),
+            MessageDetails::new_with_focus(message, is_focused).with_decoded_body(decoded),
             Vec::default(),
         )?;
 
         Ok(())
     }
 
+    /// Run the body decoders on a message of the current queue
+    fn decode_message_body(&self, message: &MessageModel) -> Option<DecodedView> {
+        let queue_name = self
+            .queue_manager
+            .queue_state
+            .current_queue_name
+            .as_deref()
+            .unwrap_or_default();
+
+        self.body_decoders
+            .decode_message(queue_name, message)
+            .map_err(|e| {
+                log::warn!("Failed to decode body of message {}: {e}", message.id);
+                e.to_string()
+            })
+            .transpose()
+    }
+
     pub fn remount_message_details_for_composition(&mut self) -> AppResult<()> {
         // Always focused when in composition mode
         let is_focused = true;
@@ -291,9 +321,68 @@ where
                 .selection_count(),
             queue_total_messages,
             queue_stats_age_seconds,
+            search: self
+                .queue_state()
+                .search
+                .as_ref()
+                .map(|search| SearchStatus {
+                    expression: search.expression.clone(),
+                    matched: search.matches.len(),
+                    scanned: search.scanned,
+                    running: search.running,
+                    truncated: search.truncated,
+                    cancelled: search.cancelled,
+                }),
+            follow: self
+                .queue_state()
+                .follow
+                .as_ref()
+                .map(|follow| FollowStatus {
+                    paused: follow.paused,
+                    received: follow.received(),
+                    rate_per_second: follow.rate_per_second(std::time::Instant::now()),
+                    new_sequences: follow.new_sequences.clone(),
+                }),
+            stats_trend: self.create_stats_trend(),
         }
     }
 
+    /// Sparkline data and rates from the statistics history of the current queue
+    fn create_stats_trend(&self) -> Option<StatsTrend> {
+        /// Minutes of history the in and out rates are estimated over
+        const RATE_MINUTES: i64 = 10;
+
+        let config = crate::config::get_config_or_panic();
+        if !config.queue_stats_display_enabled() {
+            return None;
+        }
+        let queue_name = self.queue_state().current_queue_name.as_deref()?;
+        let base_queue_name = queue_name
+            .strip_suffix("/$deadletterqueue")
+            .unwrap_or(queue_name);
+
+        let history = self.queue_state().stats_manager.history();
+        let now = chrono::Utc::now();
+        let samples = history.last_minutes(
+            base_queue_name,
+            config.queue_stats_history_minutes() as i64,
+            now,
+        );
+        if samples.len() < 2 {
+            return None;
+        }
+
+        Some(StatsTrend {
+            active_counts: samples.iter().map(|sample| sample.active_count).collect(),
+            dlq_counts: samples.iter().map(|sample| sample.dlq_count).collect(),
+            rates: QueueStatsRates::estimate(&history.last_minutes(
+                base_queue_name,
+                RATE_MINUTES,
+                now,
+            )),
+        })
+    }
+
     pub fn remount_queue_picker(&mut self, queues: Option<Vec<String>>) -> AppResult<()> {
         // If we're in discovery mode and namespace picker is mounted, unmount it
         // This prevents view errors when transitioning from namespace picker to queue picker
@@ -323,6 +412,46 @@ where
         Ok(())
     }
 
+    pub fn remount_topic_picker(&mut self, topics: Vec<TopicInfo>) -> AppResult<()> {
+        self.app
+            .remount(
+                ComponentId::TopicPicker,
+                Box::new(TopicPicker::new(topics)),
+                Vec::default(),
+            )
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.app
+            .active(&ComponentId::TopicPicker)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.set_app_state(AppState::TopicPicker);
+
+        Ok(())
+    }
+
+    pub fn remount_session_picker(
+        &mut self,
+        queue_name: String,
+        sessions: Vec<String>,
+    ) -> AppResult<()> {
+        self.app
+            .remount(
+                ComponentId::SessionPicker,
+                Box::new(SessionPicker::new(queue_name, sessions)),
+                Vec::default(),
+            )
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.app
+            .active(&ComponentId::SessionPicker)
+            .map_err(|e| AppError::Component(e.to_string()))?;
+
+        self.set_app_state(AppState::SessionPicker);
+
+        Ok(())
+    }
+
     pub fn remount_namespace_picker(&mut self, namespaces: Option<Vec<String>>) -> AppResult<()> {
         // Check if component is mounted, if not mount it first
         if self.app.mounted(&ComponentId::NamespacePicker) {
@@ -149,7 +149,7 @@ impl TaskManager {
         let operation_id_cleanup = operation_id.clone();
 
         self.taskpool.execute(async move {
-            let progress_reporter = ProgressReporter::new(tx_to_main.clone());
+            let progress_reporter = ProgressReporter::new(tx_to_main.clone(), cancel_token.clone());
             let operation_future = operation(progress_reporter);
 
             let result = tokio::select! {
@@ -210,11 +210,21 @@ impl TaskManager {
 #[derive(Clone)]
 pub struct ProgressReporter {
     tx_to_main: Sender<Msg>,
+    cancel_token: CancellationToken,
 }
 
 impl ProgressReporter {
-    pub fn new(tx_to_main: Sender<Msg>) -> Self {
-        Self { tx_to_main }
+    pub fn new(tx_to_main: Sender<Msg>, cancel_token: CancellationToken) -> Self {
+        Self {
+            tx_to_main,
+            cancel_token,
+        }
+    }
+
+    /// Token cancelled when the user cancels the operation, for handing on to
+    /// server operations that stop cooperatively
+    pub fn cancel_token(&self) -> CancellationToken {
+        self.cancel_token.clone()
     }
 
     /// Report progress update to the UI
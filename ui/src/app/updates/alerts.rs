@@ -0,0 +1,320 @@
+use crate::app::model::{AppState, Model};
+use crate::app::updates::messages::pagination::QueueStatsCache;
+use crate::components::alerts_popup::AlertStatus;
+use crate::components::common::{ComponentId, Msg, QueueActivityMsg};
+use crate::config::{self, alerts::AlertThreshold};
+use crate::error::AppError;
+use chrono::{DateTime, Local};
+use quetty_server::service_bus_manager::{QueueType, ServiceBusCommand, ServiceBusResponse};
+use tuirealm::terminal::TerminalAdapter;
+
+/// Configured queue thresholds and what the background checks found
+#[derive(Debug, Default)]
+pub struct AlertsState {
+    pub statuses: Vec<AlertStatus>,
+    /// Selection in the alerts list when it is next shown
+    pub selected: usize,
+    /// Whether a check of the watched queues is running
+    pub checking: bool,
+    pub checked_at: Option<DateTime<Local>>,
+    /// Whether a threshold was breached after the alerts were last shown
+    pub unseen: bool,
+}
+
+impl AlertsState {
+    /// Follow the configured thresholds, keeping what is known about those
+    /// that are still configured
+    pub fn sync_thresholds(&mut self, thresholds: Vec<(String, AlertThreshold)>) {
+        let mut previous = std::mem::take(&mut self.statuses);
+        self.statuses = thresholds
+            .into_iter()
+            .map(|(queue_name, threshold)| {
+                match previous.iter().position(|status| {
+                    status.queue_name == queue_name && status.threshold == threshold
+                }) {
+                    Some(index) => previous.swap_remove(index),
+                    None => AlertStatus::new(queue_name, threshold),
+                }
+            })
+            .collect();
+    }
+
+    /// Queues with at least one threshold, each once
+    pub fn watched_queues(&self) -> Vec<String> {
+        let mut queues: Vec<String> = Vec::new();
+        for status in &self.statuses {
+            if !queues.contains(&status.queue_name) {
+                queues.push(status.queue_name.clone());
+            }
+        }
+        queues
+    }
+
+    /// Evaluate the thresholds of a queue against its counts, returning those
+    /// that became breached
+    pub fn record(
+        &mut self,
+        queue_name: &str,
+        counts: Option<(u64, u64)>,
+        now: DateTime<Local>,
+    ) -> Vec<AlertStatus> {
+        let mut raised = Vec::new();
+        for status in self
+            .statuses
+            .iter_mut()
+            .filter(|status| status.queue_name == queue_name)
+        {
+            status.value = counts.map(|(active, dlq)| status.threshold.metric.value(active, dlq));
+            match status.value {
+                Some(value) if !status.threshold.is_breached_by(value) => {
+                    status.breached_since = None
+                }
+                Some(_) if status.breached_since.is_none() => {
+                    status.breached_since = Some(now);
+                    raised.push(status.clone());
+                }
+                // Still breached since an earlier check
+                Some(_) => {}
+                // A failed read keeps the previous verdict rather than clearing an alert
+                None => {}
+            }
+        }
+        if !raised.is_empty() {
+            self.unseen = true;
+        }
+        raised
+    }
+
+    pub fn breached_count(&self) -> usize {
+        self.statuses
+            .iter()
+            .filter(|status| status.is_breached())
+            .count()
+    }
+
+    /// Line below the alerts list describing the checks
+    pub fn status(&self) -> String {
+        let interval = config::get_config_or_panic()
+            .alerts()
+            .check_interval()
+            .as_secs();
+        match (self.checking, self.checked_at) {
+            (true, _) => "Checking the watched queues…".to_string(),
+            (false, Some(at)) => format!(
+                "{} of {} breached • checked {} • every {interval}s",
+                self.breached_count(),
+                self.statuses.len(),
+                at.format("%H:%M:%S")
+            ),
+            (false, None) => format!("Not checked yet • every {interval}s once connected"),
+        }
+    }
+}
+
+impl<T> Model<T>
+where
+    T: TerminalAdapter,
+{
+    /// Read the counts of every watched queue through the queue statistics
+    pub fn handle_alert_check_due(&mut self) -> Option<Msg> {
+        let thresholds = config::get_config_or_panic().alerts().thresholds();
+        let alerts = &mut self.queue_state_mut().alerts;
+        alerts.sync_thresholds(thresholds);
+        if alerts.statuses.is_empty() || alerts.checking {
+            return None;
+        }
+        let queues = alerts.watched_queues();
+
+        let Some(service_bus_manager) = self.service_bus_manager.clone() else {
+            log::debug!("Skipping alert check: not connected to a namespace");
+            return None;
+        };
+        self.queue_state_mut().alerts.checking = true;
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+
+        self.task_manager.execute_background(async move {
+            for queue_name in queues {
+                // Lock per queue, so user commands are not held up by the whole check
+                let response = service_bus_manager
+                    .lock()
+                    .await
+                    .execute_command(ServiceBusCommand::GetQueueStatistics {
+                        queue_name: queue_name.clone(),
+                        queue_type: QueueType::Main,
+                    })
+                    .await;
+                let counts = match response {
+                    ServiceBusResponse::QueueStatistics {
+                        active_message_count: Some(active),
+                        dead_letter_message_count: Some(dlq),
+                        ..
+                    } => Some((active, dlq)),
+                    ServiceBusResponse::Error { error } => {
+                        log::warn!("Alert check failed to read {queue_name}: {error}");
+                        None
+                    }
+                    _ => {
+                        log::warn!("Alert check got no counts for {queue_name}");
+                        None
+                    }
+                };
+                tx_to_main
+                    .send(Msg::QueueActivity(QueueActivityMsg::AlertQueueChecked {
+                        queue_name,
+                        counts,
+                    }))
+                    .map_err(|e| AppError::Component(e.to_string()))?;
+            }
+            tx_to_main
+                .send(Msg::QueueActivity(QueueActivityMsg::AlertCheckFinished))
+                .map_err(|e| AppError::Component(e.to_string()))
+        });
+        None
+    }
+
+    /// Evaluate the thresholds of a checked queue and record its counts
+    pub fn handle_alert_queue_checked(
+        &mut self,
+        queue_name: String,
+        counts: Option<(u64, u64)>,
+    ) -> Option<Msg> {
+        if let Some((active, dlq)) = counts {
+            self.queue_state_mut()
+                .stats_manager
+                .update_stats_cache(QueueStatsCache::new(queue_name.clone(), active, dlq));
+        }
+
+        let raised = self
+            .queue_state_mut()
+            .alerts
+            .record(&queue_name, counts, Local::now());
+        for status in &raised {
+            log::warn!(
+                "Queue alert: {} breached {} (now {})",
+                status.queue_name,
+                status.threshold,
+                status.value.unwrap_or_default()
+            );
+        }
+        self.set_redraw(true);
+        None
+    }
+
+    pub fn handle_alert_check_finished(&mut self) -> Option<Msg> {
+        let alerts = &mut self.queue_state_mut().alerts;
+        alerts.checking = false;
+        alerts.checked_at = Some(Local::now());
+        if self.app.mounted(&ComponentId::AlertsPopup) {
+            self.remount_alerts();
+        }
+        self.set_redraw(true);
+        None
+    }
+
+    /// Show the alerts list, marking its breaches as seen
+    pub fn handle_alerts_requested(&mut self) -> Option<Msg> {
+        // These screens are drawn without popups
+        if matches!(
+            self.state_manager.app_state,
+            AppState::ThemePicker | AppState::ConfigScreen | AppState::PasswordPopup
+        ) {
+            return None;
+        }
+
+        let thresholds = config::get_config_or_panic().alerts().thresholds();
+        let alerts = &mut self.queue_state_mut().alerts;
+        alerts.sync_thresholds(thresholds);
+        alerts.unseen = false;
+        self.remount_alerts();
+        None
+    }
+
+    pub fn handle_alerts_closed(&mut self) -> Option<Msg> {
+        if let Err(e) = self.unmount_alerts_popup() {
+            self.error_reporter
+                .report_mount_error("AlertsPopup", "unmount", e);
+        }
+        None
+    }
+
+    pub fn handle_alert_selected(&mut self, selected: usize) -> Option<Msg> {
+        self.queue_state_mut().alerts.selected = selected;
+        self.set_redraw(true);
+        None
+    }
+
+    /// Close the alerts list, and the dashboard it may cover, and open the queue
+    pub fn handle_alert_queue_opened(&mut self, queue_name: String) -> Option<Msg> {
+        self.handle_alerts_closed();
+        if self.queue_state().dashboard.is_some() {
+            self.handle_dashboard_closed();
+        }
+        Some(Msg::QueueActivity(QueueActivityMsg::QueueSelected(
+            queue_name,
+        )))
+    }
+
+    fn remount_alerts(&mut self) {
+        let alerts = &self.queue_state().alerts;
+        let (statuses, selected, status) =
+            (alerts.statuses.clone(), alerts.selected, alerts.status());
+        if let Err(e) = self.mount_alerts_popup(statuses, selected, status) {
+            self.error_reporter
+                .report_mount_error("AlertsPopup", "mount", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn threshold(text: &str) -> AlertThreshold {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_record_raises_each_breach_once() {
+        let mut alerts = AlertsState::default();
+        alerts.sync_thresholds(vec![
+            ("orders".to_string(), threshold("dlq_count > 0")),
+            ("orders".to_string(), threshold("active_count > 100")),
+            ("payments".to_string(), threshold("dlq_count > 0")),
+        ]);
+        assert_eq!(alerts.watched_queues(), ["orders", "payments"]);
+
+        let raised = alerts.record("orders", Some((5, 2)), Local::now());
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].threshold, threshold("dlq_count > 0"));
+        assert!(alerts.unseen);
+        assert_eq!(alerts.breached_count(), 1);
+
+        // Still breached: not raised again, and a failed read keeps the alert
+        assert!(
+            alerts
+                .record("orders", Some((5, 3)), Local::now())
+                .is_empty()
+        );
+        alerts.record("orders", None, Local::now());
+        assert_eq!(alerts.breached_count(), 1);
+
+        alerts.record("orders", Some((5, 0)), Local::now());
+        assert_eq!(alerts.breached_count(), 0);
+    }
+
+    #[test]
+    fn test_sync_keeps_known_statuses() {
+        let mut alerts = AlertsState::default();
+        alerts.sync_thresholds(vec![("orders".to_string(), threshold("dlq_count > 0"))]);
+        alerts.record("orders", Some((0, 4)), Local::now());
+
+        alerts.sync_thresholds(vec![
+            ("invoices".to_string(), threshold("dlq_count > 0")),
+            ("orders".to_string(), threshold("dlq_count > 0")),
+        ]);
+        assert_eq!(alerts.statuses.len(), 2);
+        assert_eq!(alerts.statuses[1].value, Some(4));
+        assert!(alerts.statuses[1].is_breached());
+        assert_eq!(alerts.statuses[0].value, None);
+    }
+}
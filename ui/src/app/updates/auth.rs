@@ -233,9 +233,9 @@ where
 
                     // No saved queue - show queue picker for manual entry
                     log::info!("No saved queue found - showing queue picker for manual entry");
-                    return Ok(Some(Msg::QueueActivity(
+                    Ok(Some(Msg::QueueActivity(
                         crate::components::common::QueueActivityMsg::QueuesLoaded(vec![]),
-                    )));
+                    )))
                 } else if config.servicebus().has_connection_string() {
                     // Other auth methods with connection string available
                     log::info!("Connection string available, loading namespaces directly");
@@ -324,11 +324,11 @@ where
             AuthActivityMsg::CopyDeviceCode => {
                 // Debounce rapid copy requests
                 let now = std::time::Instant::now();
-                if let Some(last_copy) = self.state_manager.last_device_code_copy {
-                    if now.duration_since(last_copy).as_millis() < 500 {
-                        // Ignore if less than 500ms since last copy
-                        return Ok(None);
-                    }
+                if let Some(last_copy) = self.state_manager.last_device_code_copy
+                    && now.duration_since(last_copy).as_millis() < 500
+                {
+                    // Ignore if less than 500ms since last copy
+                    return Ok(None);
                 }
                 self.state_manager.last_device_code_copy = Some(now);
 
@@ -97,10 +97,10 @@ impl NamespaceHandler {
         let namespace_names: Vec<String> = namespaces.iter().map(|ns| ns.name.clone()).collect();
 
         // Update existing namespace picker with discovered namespaces
-        if model.app.mounted(&ComponentId::NamespacePicker) {
-            if let Err(e) = model.app.umount(&ComponentId::NamespacePicker) {
-                log::warn!("Failed to unmount existing namespace picker: {e}");
-            }
+        if model.app.mounted(&ComponentId::NamespacePicker)
+            && let Err(e) = model.app.umount(&ComponentId::NamespacePicker)
+        {
+            log::warn!("Failed to unmount existing namespace picker: {e}");
         }
 
         match model.app.mount(
@@ -99,21 +99,17 @@ where
         );
 
         // Validate that client secret auth method requires master password for encryption
-        if config_data.auth_method == "client_secret" {
-            if let Some(client_secret) = &config_data.client_secret {
-                if !client_secret.trim().is_empty()
-                    && !client_secret.contains(PLACEHOLDER_ENCRYPTED_CLIENT_SECRET)
-                    && config_data.master_password.is_none()
-                {
-                    log::error!(
-                        "Client secret provided without master password - encryption required"
-                    );
-                    return Err(crate::error::AppError::Config(
-                        "Master password is required when providing a client secret for encryption"
-                            .to_string(),
-                    ));
-                }
-            }
+        if config_data.auth_method == "client_secret"
+            && let Some(client_secret) = &config_data.client_secret
+            && !client_secret.trim().is_empty()
+            && !client_secret.contains(PLACEHOLDER_ENCRYPTED_CLIENT_SECRET)
+            && config_data.master_password.is_none()
+        {
+            log::error!("Client secret provided without master password - encryption required");
+            return Err(crate::error::AppError::Config(
+                "Master password is required when providing a client secret for encryption"
+                    .to_string(),
+            ));
         }
 
         // Save sensitive data to environment variables (which will be written to .env)
@@ -244,11 +240,11 @@ where
         self.state_manager.pending_config_data = None;
 
         // Check which component is mounted and unmount appropriately
-        if self.app.mounted(&ComponentId::ConfigScreen) {
-            if let Err(e) = self.unmount_config_screen() {
-                self.error_reporter
-                    .report_mount_error("ConfigScreen", "unmount", e);
-            }
+        if self.app.mounted(&ComponentId::ConfigScreen)
+            && let Err(e) = self.unmount_config_screen()
+        {
+            self.error_reporter
+                .report_mount_error("ConfigScreen", "unmount", e);
         }
 
         if self.app.mounted(&ComponentId::PasswordPopup) {
@@ -531,47 +527,41 @@ where
         log::info!("Saving client secret configuration directly");
 
         // First encrypt the client secret if provided
-        if let Some(client_secret) = &config_data.client_secret {
-            if let Some(master_password) = &config_data.master_password {
-                if !client_secret.trim().is_empty()
-                    && !client_secret.contains(PLACEHOLDER_ENCRYPTED_CLIENT_SECRET)
-                    && !master_password.trim().is_empty()
-                {
-                    log::info!("Encrypting client secret with master password");
-                    let encryption = quetty_server::encryption::ClientSecretEncryption::new();
-                    match encryption.encrypt_client_secret(client_secret, master_password) {
-                        Ok(encrypted) => {
-                            safe_set_env_var(AZURE_AD_ENCRYPTED_CLIENT_SECRET, &encrypted)?;
-                            safe_set_env_var(
-                                AZURE_AD_CLIENT_SECRET_ENCRYPTION_SALT,
-                                &encryption.salt_base64(),
-                            )?;
-                            safe_remove_env_var(AZURE_AD_CLIENT_SECRET)?;
-                            log::info!("Client secret encrypted successfully");
-
-                            // Immediately decrypt the client secret for runtime use
-                            if let Some(master_password) = &config_data.master_password {
-                                match self.decrypt_and_set_client_secret(master_password) {
-                                    Ok(_) => log::info!(
-                                        "Client secret decrypted and set for runtime use"
-                                    ),
-                                    Err(e) => {
-                                        log::error!(
-                                            "Failed to decrypt newly encrypted client secret: {e}"
-                                        );
-                                        return Err(e);
-                                    }
-                                }
+        if let Some(client_secret) = &config_data.client_secret
+            && let Some(master_password) = &config_data.master_password
+            && !client_secret.trim().is_empty()
+            && !client_secret.contains(PLACEHOLDER_ENCRYPTED_CLIENT_SECRET)
+            && !master_password.trim().is_empty()
+        {
+            log::info!("Encrypting client secret with master password");
+            let encryption = quetty_server::encryption::ClientSecretEncryption::new();
+            match encryption.encrypt_client_secret(client_secret, master_password) {
+                Ok(encrypted) => {
+                    safe_set_env_var(AZURE_AD_ENCRYPTED_CLIENT_SECRET, &encrypted)?;
+                    safe_set_env_var(
+                        AZURE_AD_CLIENT_SECRET_ENCRYPTION_SALT,
+                        &encryption.salt_base64(),
+                    )?;
+                    safe_remove_env_var(AZURE_AD_CLIENT_SECRET)?;
+                    log::info!("Client secret encrypted successfully");
+
+                    // Immediately decrypt the client secret for runtime use
+                    if let Some(master_password) = &config_data.master_password {
+                        match self.decrypt_and_set_client_secret(master_password) {
+                            Ok(_) => log::info!("Client secret decrypted and set for runtime use"),
+                            Err(e) => {
+                                log::error!("Failed to decrypt newly encrypted client secret: {e}");
+                                return Err(e);
                             }
                         }
-                        Err(e) => {
-                            log::error!("Failed to encrypt client secret: {e}");
-                            return Err(crate::error::AppError::Config(format!(
-                                "Client secret encryption failed: {e}"
-                            )));
-                        }
                     }
                 }
+                Err(e) => {
+                    log::error!("Failed to encrypt client secret: {e}");
+                    return Err(crate::error::AppError::Config(format!(
+                        "Client secret encryption failed: {e}"
+                    )));
+                }
             }
         }
 
@@ -593,41 +583,40 @@ where
                 log::info!("Password validation successful - connection string decrypted");
 
                 // Check if we have pending config data with queue name that needs to be saved
-                if let Some(pending_config) = &self.state_manager.pending_config_data {
-                    if pending_config.queue_name.is_some() {
-                        log::info!("Saving queue name from pending config data to .env file");
-
-                        // Create a minimal config data with just the queue name for saving
-                        let queue_config_data = crate::components::common::ConfigUpdateData {
-                            auth_method: crate::utils::auth::AUTH_METHOD_CONNECTION_STRING
-                                .to_string(),
-                            tenant_id: None,
-                            client_id: None,
-                            client_secret: None,
-                            subscription_id: None,
-                            resource_group: None,
-                            namespace: None,
-                            connection_string: None,
-                            master_password: None,
-                            queue_name: pending_config.queue_name.clone(),
-                        };
-
-                        // Save queue name to environment and .env file
-                        if let Some(queue_name) = &queue_config_data.queue_name {
-                            safe_set_env_var(SERVICEBUS_QUEUE_NAME, queue_name)?;
-                            log::info!("Set queue name in environment: '{queue_name}'");
-                        }
-
-                        // Write to .env file
-                        if let Err(e) = self.write_env_file(&queue_config_data) {
-                            log::error!("Failed to write queue name to .env file: {e}");
-                        } else {
-                            log::info!("Queue name saved to .env file successfully");
-                        }
+                if let Some(pending_config) = &self.state_manager.pending_config_data
+                    && pending_config.queue_name.is_some()
+                {
+                    log::info!("Saving queue name from pending config data to .env file");
+
+                    // Create a minimal config data with just the queue name for saving
+                    let queue_config_data = crate::components::common::ConfigUpdateData {
+                        auth_method: crate::utils::auth::AUTH_METHOD_CONNECTION_STRING.to_string(),
+                        tenant_id: None,
+                        client_id: None,
+                        client_secret: None,
+                        subscription_id: None,
+                        resource_group: None,
+                        namespace: None,
+                        connection_string: None,
+                        master_password: None,
+                        queue_name: pending_config.queue_name.clone(),
+                    };
+
+                    // Save queue name to environment and .env file
+                    if let Some(queue_name) = &queue_config_data.queue_name {
+                        safe_set_env_var(SERVICEBUS_QUEUE_NAME, queue_name)?;
+                        log::info!("Set queue name in environment: '{queue_name}'");
+                    }
 
-                        // Clear pending config data since we've processed it
-                        self.state_manager.pending_config_data = None;
+                    // Write to .env file
+                    if let Err(e) = self.write_env_file(&queue_config_data) {
+                        log::error!("Failed to write queue name to .env file: {e}");
+                    } else {
+                        log::info!("Queue name saved to .env file successfully");
                     }
+
+                    // Clear pending config data since we've processed it
+                    self.state_manager.pending_config_data = None;
                 }
 
                 // Reset authenticating flag since password is now valid
@@ -685,40 +674,40 @@ where
                 log::info!("Password validation successful - client secret decrypted");
 
                 // Check if we have pending config data with queue name that needs to be saved
-                if let Some(pending_config) = &self.state_manager.pending_config_data {
-                    if pending_config.queue_name.is_some() {
-                        log::info!("Saving queue name from pending config data to .env file");
-
-                        // Create a minimal config data with just the queue name for saving
-                        let queue_config_data = crate::components::common::ConfigUpdateData {
-                            auth_method: crate::utils::auth::AUTH_METHOD_CLIENT_SECRET.to_string(),
-                            tenant_id: None,
-                            client_id: None,
-                            client_secret: None,
-                            subscription_id: None,
-                            resource_group: None,
-                            namespace: None,
-                            connection_string: None,
-                            master_password: None,
-                            queue_name: pending_config.queue_name.clone(),
-                        };
-
-                        // Save queue name to environment and .env file
-                        if let Some(queue_name) = &queue_config_data.queue_name {
-                            safe_set_env_var(SERVICEBUS_QUEUE_NAME, queue_name)?;
-                            log::info!("Set queue name in environment: '{queue_name}'");
-                        }
-
-                        // Write to .env file
-                        if let Err(e) = self.write_env_file(&queue_config_data) {
-                            log::error!("Failed to write queue name to .env file: {e}");
-                        } else {
-                            log::info!("Queue name saved to .env file successfully");
-                        }
+                if let Some(pending_config) = &self.state_manager.pending_config_data
+                    && pending_config.queue_name.is_some()
+                {
+                    log::info!("Saving queue name from pending config data to .env file");
+
+                    // Create a minimal config data with just the queue name for saving
+                    let queue_config_data = crate::components::common::ConfigUpdateData {
+                        auth_method: crate::utils::auth::AUTH_METHOD_CLIENT_SECRET.to_string(),
+                        tenant_id: None,
+                        client_id: None,
+                        client_secret: None,
+                        subscription_id: None,
+                        resource_group: None,
+                        namespace: None,
+                        connection_string: None,
+                        master_password: None,
+                        queue_name: pending_config.queue_name.clone(),
+                    };
+
+                    // Save queue name to environment and .env file
+                    if let Some(queue_name) = &queue_config_data.queue_name {
+                        safe_set_env_var(SERVICEBUS_QUEUE_NAME, queue_name)?;
+                        log::info!("Set queue name in environment: '{queue_name}'");
+                    }
 
-                        // Clear pending config data since we've processed it
-                        self.state_manager.pending_config_data = None;
+                    // Write to .env file
+                    if let Err(e) = self.write_env_file(&queue_config_data) {
+                        log::error!("Failed to write queue name to .env file: {e}");
+                    } else {
+                        log::info!("Queue name saved to .env file successfully");
                     }
+
+                    // Clear pending config data since we've processed it
+                    self.state_manager.pending_config_data = None;
                 }
 
                 // Close password popup and proceed with authentication
@@ -983,22 +972,20 @@ where
         }
 
         // Check for new secrets being provided (existing encrypted secrets don't count as changes)
-        if let Some(connection_string) = &config_data.connection_string {
-            if !connection_string.trim().is_empty()
-                && !connection_string.contains(PLACEHOLDER_ENCRYPTED_CONNECTION_STRING)
-            {
-                log::debug!("New connection string provided");
-                return true;
-            }
+        if let Some(connection_string) = &config_data.connection_string
+            && !connection_string.trim().is_empty()
+            && !connection_string.contains(PLACEHOLDER_ENCRYPTED_CONNECTION_STRING)
+        {
+            log::debug!("New connection string provided");
+            return true;
         }
 
-        if let Some(client_secret) = &config_data.client_secret {
-            if !client_secret.trim().is_empty()
-                && !client_secret.contains(PLACEHOLDER_ENCRYPTED_CLIENT_SECRET)
-            {
-                log::debug!("New client secret provided");
-                return true;
-            }
+        if let Some(client_secret) = &config_data.client_secret
+            && !client_secret.trim().is_empty()
+            && !client_secret.contains(PLACEHOLDER_ENCRYPTED_CLIENT_SECRET)
+        {
+            log::debug!("New client secret provided");
+            return true;
         }
 
         // If master password is provided, it means user wants to update/verify encryption
@@ -1053,11 +1040,11 @@ where
         self.state_manager.pending_config_data = None;
 
         // Cleanup UI components
-        if self.app.mounted(&ComponentId::ConfigScreen) {
-            if let Err(e) = self.unmount_config_screen() {
-                self.error_reporter
-                    .report_mount_error("ConfigScreen", "unmount", e);
-            }
+        if self.app.mounted(&ComponentId::ConfigScreen)
+            && let Err(e) = self.unmount_config_screen()
+        {
+            self.error_reporter
+                .report_mount_error("ConfigScreen", "unmount", e);
         }
         if self.app.mounted(&ComponentId::PasswordPopup) {
             if let Err(e) = self.app.umount(&ComponentId::PasswordPopup) {
@@ -1228,18 +1215,18 @@ where
         new_value: &Option<String>,
     ) {
         // Use new value if provided and not empty
-        if let Some(value) = new_value {
-            if !value.trim().is_empty() {
-                Self::append_env_line(env_content, key, value);
-                return;
-            }
+        if let Some(value) = new_value
+            && !value.trim().is_empty()
+        {
+            Self::append_env_line(env_content, key, value);
+            return;
         }
 
         // Fall back to existing value if available
-        if let Some(existing_value) = existing_values.get(key) {
-            if !existing_value.trim().is_empty() {
-                Self::append_env_line(env_content, key, existing_value);
-            }
+        if let Some(existing_value) = existing_values.get(key)
+            && !existing_value.trim().is_empty()
+        {
+            Self::append_env_line(env_content, key, existing_value);
         }
     }
 
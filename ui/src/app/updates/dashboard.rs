@@ -0,0 +1,465 @@
+use crate::app::model::Model;
+use crate::app::updates::messages::pagination::QueueStatsCache;
+use crate::app::updates::queue_admin::management_api_available;
+use crate::components::common::{ComponentId, Msg, QueueActivityMsg};
+use crate::components::queue_dashboard::{DashboardRow, DashboardView};
+use crate::config;
+use crate::error::AppError;
+use chrono::{DateTime, Local, Utc};
+use quetty_server::service_bus_manager::QueueDetails;
+use quetty_server::taskpool::TaskPool;
+use std::collections::HashSet;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tuirealm::terminal::TerminalAdapter;
+
+/// Dashboard of every queue in the namespace, refreshed on the
+/// `dashboard_refresh_seconds` interval while it is open
+#[derive(Debug)]
+pub struct DashboardState {
+    /// Identifies the dashboard, so results of a closed one are ignored
+    pub id: u64,
+    pub rows: Vec<DashboardRow>,
+    pub view: DashboardView,
+    /// Queues of the running refresh that have not been read yet
+    pub pending: HashSet<String>,
+    /// Whether a refresh is running, including the listing of the queues
+    pub refreshing: bool,
+    pub refreshed_at: Option<DateTime<Local>>,
+    /// Why listing the queues failed in the last refresh
+    pub error: Option<String>,
+    /// Reads the queues with at most `dashboard_concurrency` requests at a time
+    pool: TaskPool,
+    /// Stops the refresh timer; cancelled when the dashboard closes
+    timer: CancellationToken,
+}
+
+impl DashboardState {
+    /// Start tracking a dashboard whose refreshes stop with `timer`
+    pub fn new(id: u64, pool: TaskPool, timer: CancellationToken) -> Self {
+        Self {
+            id,
+            rows: Vec::new(),
+            view: DashboardView::default(),
+            pending: HashSet::new(),
+            refreshing: false,
+            refreshed_at: None,
+            error: None,
+            pool,
+            timer,
+        }
+    }
+
+    /// Keep the rows of the listed queues, adding rows for new ones, and wait
+    /// for all of them to be read
+    pub fn start_reading(&mut self, queues: Vec<String>) {
+        self.rows.retain(|row| queues.contains(&row.queue_name));
+        for queue_name in &queues {
+            if !self.rows.iter().any(|row| &row.queue_name == queue_name) {
+                self.rows.push(DashboardRow {
+                    queue_name: queue_name.clone(),
+                    ..DashboardRow::default()
+                });
+            }
+        }
+        self.pending = queues.into_iter().collect();
+        self.error = None;
+        self.finish_if_done();
+    }
+
+    /// Fill in the counts of a queue; `dead_letter_growth` comes from the history
+    pub fn record(&mut self, details: &QueueDetails, dead_letter_growth: Option<i64>) {
+        self.pending.remove(&details.entity_path);
+        if let Some(row) = self
+            .rows
+            .iter_mut()
+            .find(|row| row.queue_name == details.entity_path)
+        {
+            row.active = details.active_message_count;
+            row.dead_letter = details.dead_letter_message_count;
+            row.dead_letter_growth = dead_letter_growth;
+            row.scheduled = details.scheduled_message_count;
+            row.size_in_bytes = details.size_in_bytes;
+            row.error = None;
+        }
+        self.finish_if_done();
+    }
+
+    /// Mark a queue as unreadable, or the whole refresh as failed without a queue
+    pub fn fail(&mut self, queue_name: Option<String>, error: String) {
+        match queue_name {
+            Some(queue_name) => {
+                self.pending.remove(&queue_name);
+                if let Some(row) = self
+                    .rows
+                    .iter_mut()
+                    .find(|row| row.queue_name == queue_name)
+                {
+                    row.error = Some(error);
+                }
+                self.finish_if_done();
+            }
+            None => {
+                self.pending.clear();
+                self.refreshing = false;
+                self.error = Some(error);
+            }
+        }
+    }
+
+    fn finish_if_done(&mut self) {
+        if self.pending.is_empty() {
+            self.refreshing = false;
+            self.refreshed_at = Some(Local::now());
+        }
+    }
+
+    /// Line below the table: progress of a refresh, or when the last one ended
+    pub fn status(&self) -> String {
+        let interval = config::get_config_or_panic()
+            .dashboard_refresh_interval()
+            .as_secs();
+        if let Some(error) = &self.error {
+            return format!("⚠ Listing queues failed: {error}");
+        }
+        if self.refreshing {
+            let read = self.rows.len().saturating_sub(self.pending.len());
+            return format!("Refreshing… {read}/{} queues read", self.rows.len());
+        }
+        let failed = self.rows.iter().filter(|row| row.error.is_some()).count();
+        let failed = if failed > 0 {
+            format!(" • {failed} could not be read")
+        } else {
+            String::new()
+        };
+        match self.refreshed_at {
+            Some(at) => format!(
+                "Updated {} • refreshes every {interval}s{failed}",
+                at.format("%H:%M:%S")
+            ),
+            None => "Loading queues…".to_string(),
+        }
+    }
+}
+
+impl Drop for DashboardState {
+    fn drop(&mut self) {
+        self.timer.cancel();
+        self.pool.cancel_all();
+    }
+}
+
+impl<T> Model<T>
+where
+    T: TerminalAdapter,
+{
+    /// Open the dashboard and start refreshing it on the configured interval
+    pub fn handle_dashboard_requested(&mut self) -> Option<Msg> {
+        if let Err(e) = management_api_available("The queue dashboard") {
+            return Some(Msg::ShowError(e));
+        }
+        if self.service_bus_manager.is_none() {
+            return Some(Msg::ShowError(
+                "❌ Not connected to a namespace.".to_string(),
+            ));
+        }
+
+        let dashboard_id = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let config = config::get_config_or_panic();
+        let interval = config.dashboard_refresh_interval();
+        let pool = TaskPool::new(config.dashboard_concurrency());
+        let timer = CancellationToken::new();
+
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+        let cancelled = timer.clone();
+        tokio::spawn(async move {
+            // The first tick is immediate and loads the dashboard
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    _ = cancelled.cancelled() => break,
+                    _ = ticks.tick() => {
+                        let tick = Msg::QueueActivity(QueueActivityMsg::DashboardRefreshRequested);
+                        if tx_to_main.send(tick).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        log::info!("Opened the queue dashboard, refreshing every {interval:?}");
+        self.queue_state_mut().dashboard = Some(DashboardState::new(dashboard_id, pool, timer));
+        self.remount_dashboard()
+    }
+
+    /// Close the dashboard, stopping its refreshes
+    pub fn handle_dashboard_closed(&mut self) -> Option<Msg> {
+        let filter_editing = self
+            .queue_state_mut()
+            .dashboard
+            .take()
+            .is_some_and(|dashboard| dashboard.view.filter_editing);
+        if filter_editing {
+            self.set_dashboard_editing(false);
+        }
+        if self.app.mounted(&ComponentId::QueueDashboard)
+            && let Err(e) = self.unmount_queue_dashboard()
+        {
+            self.error_reporter
+                .report_mount_error("QueueDashboard", "unmount", e);
+        }
+        None
+    }
+
+    /// Remember how the dashboard is sorted and filtered for the next remount
+    pub fn handle_dashboard_view_changed(&mut self, view: DashboardView) -> Option<Msg> {
+        let dashboard = self.queue_state_mut().dashboard.as_mut()?;
+        let editing_changed = dashboard.view.filter_editing != view.filter_editing;
+        let filter_editing = view.filter_editing;
+        dashboard.view = view;
+
+        // Typed filter characters must not trigger the global keys
+        if editing_changed {
+            self.set_dashboard_editing(filter_editing);
+        }
+        self.set_redraw(true);
+        None
+    }
+
+    /// List the queues and read each of them through the dashboard's task pool
+    pub fn handle_dashboard_refresh_requested(&mut self) -> Option<Msg> {
+        let dashboard = self.queue_state_mut().dashboard.as_mut()?;
+        if dashboard.refreshing {
+            log::debug!("Skipping dashboard refresh: the previous one is still running");
+            return None;
+        }
+        dashboard.refreshing = true;
+        let dashboard_id = dashboard.id;
+        let pool = dashboard.pool.clone();
+
+        let Some(service_bus_manager) = self.service_bus_manager.clone() else {
+            return Some(Msg::ShowError(
+                "❌ Not connected to a namespace.".to_string(),
+            ));
+        };
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+
+        self.task_manager.execute_background(async move {
+            let send = |msg: QueueActivityMsg| {
+                tx_to_main
+                    .send(Msg::QueueActivity(msg))
+                    .map_err(|e| AppError::Component(e.to_string()))
+            };
+            let failed = |queue_name: Option<String>, error: String| {
+                QueueActivityMsg::DashboardRefreshFailed {
+                    dashboard_id,
+                    queue_name,
+                    error,
+                }
+            };
+
+            // Commands run one at a time behind the manager's lock, so the
+            // client is used directly to read the queues in parallel
+            let listing = async {
+                let client = service_bus_manager.lock().await.management_client().await?;
+                let queues = client.list_namespace_queues().await?;
+                Ok::<_, quetty_server::service_bus_manager::ServiceBusError>((client, queues))
+            };
+            let (client, queues) = match listing.await {
+                Ok(listed) => listed,
+                Err(e) => {
+                    log::warn!("Dashboard failed to list queues: {e}");
+                    return send(failed(None, e.to_string()));
+                }
+            };
+
+            send(QueueActivityMsg::DashboardQueuesListed {
+                dashboard_id,
+                queues: queues.clone(),
+            })?;
+
+            for queue_name in queues {
+                let client = client.clone();
+                let tx_to_main = tx_to_main.clone();
+                pool.execute(async move {
+                    let msg = match client.get_queue_details(&queue_name).await {
+                        Ok(details) => QueueActivityMsg::DashboardQueueLoaded {
+                            dashboard_id,
+                            details: Box::new(details),
+                        },
+                        Err(e) => {
+                            log::warn!("Dashboard failed to read {queue_name}: {e}");
+                            QueueActivityMsg::DashboardRefreshFailed {
+                                dashboard_id,
+                                queue_name: Some(queue_name),
+                                error: e.to_string(),
+                            }
+                        }
+                    };
+                    if let Err(e) = tx_to_main.send(Msg::QueueActivity(msg)) {
+                        log::error!("Failed to send dashboard result: {e}");
+                    }
+                });
+            }
+            Ok(())
+        });
+        self.remount_dashboard()
+    }
+
+    /// Show the listed queues while their counts are read
+    pub fn handle_dashboard_queues_listed(
+        &mut self,
+        dashboard_id: u64,
+        queues: Vec<String>,
+    ) -> Option<Msg> {
+        let dashboard = self.current_dashboard(dashboard_id)?;
+        dashboard.start_reading(queues);
+        self.remount_dashboard()
+    }
+
+    /// Show the counts of a queue and record them in the statistics history
+    pub fn handle_dashboard_queue_loaded(
+        &mut self,
+        dashboard_id: u64,
+        details: Box<QueueDetails>,
+    ) -> Option<Msg> {
+        self.current_dashboard(dashboard_id)?;
+
+        // Recording feeds the statistics cache and the history the growth is read from
+        let stats_manager = &mut self.queue_state_mut().stats_manager;
+        if let (Some(active), Some(dead_letter)) = (
+            details.active_message_count,
+            details.dead_letter_message_count,
+        ) {
+            stats_manager.update_stats_cache(QueueStatsCache::new(
+                details.entity_path.clone(),
+                active,
+                dead_letter,
+            ));
+        }
+        let minutes = config::get_config_or_panic().queue_stats_history_minutes() as i64;
+        let samples =
+            stats_manager
+                .history()
+                .last_minutes(&details.entity_path, minutes, Utc::now());
+        let growth = dead_letter_growth(
+            samples
+                .iter()
+                .map(|sample| sample.dlq_count)
+                .collect::<Vec<_>>()
+                .as_slice(),
+        );
+
+        self.current_dashboard(dashboard_id)?
+            .record(&details, growth);
+        self.remount_dashboard()
+    }
+
+    /// Show why a queue, or the listing of the queues, could not be read
+    pub fn handle_dashboard_refresh_failed(
+        &mut self,
+        dashboard_id: u64,
+        queue_name: Option<String>,
+        error: String,
+    ) -> Option<Msg> {
+        self.current_dashboard(dashboard_id)?
+            .fail(queue_name, error);
+        self.remount_dashboard()
+    }
+
+    /// Close the dashboard and open the chosen queue
+    pub fn handle_dashboard_queue_opened(&mut self, queue_name: String) -> Option<Msg> {
+        self.handle_dashboard_closed();
+        Some(Msg::QueueActivity(QueueActivityMsg::QueueSelected(
+            queue_name,
+        )))
+    }
+
+    /// The open dashboard, if results of `dashboard_id` still belong to it
+    fn current_dashboard(&mut self, dashboard_id: u64) -> Option<&mut DashboardState> {
+        self.queue_state_mut()
+            .dashboard
+            .as_mut()
+            .filter(|dashboard| dashboard.id == dashboard_id)
+    }
+
+    fn set_dashboard_editing(&mut self, editing: bool) {
+        self.set_editing_message(editing);
+        if let Err(e) = self.update_global_key_watcher_editing_state() {
+            self.error_reporter.report_key_watcher_error(e);
+        }
+    }
+
+    /// Show the dashboard with its current rows and view
+    fn remount_dashboard(&mut self) -> Option<Msg> {
+        let dashboard = self.queue_state().dashboard.as_ref()?;
+        let (rows, view, status) = (
+            dashboard.rows.clone(),
+            dashboard.view.clone(),
+            dashboard.status(),
+        );
+        if let Err(e) = self.mount_queue_dashboard(rows, view, status) {
+            self.error_reporter
+                .report_mount_error("QueueDashboard", "mount", e);
+        }
+        None
+    }
+}
+
+/// Change of the dead letter count between the oldest and newest sample
+fn dead_letter_growth(dead_letter_counts: &[u64]) -> Option<i64> {
+    match dead_letter_counts {
+        [first, .., last] => Some(*last as i64 - *first as i64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(queue_name: &str, dead_letter: u64) -> QueueDetails {
+        QueueDetails {
+            entity_path: queue_name.to_string(),
+            active_message_count: Some(1),
+            dead_letter_message_count: Some(dead_letter),
+            ..QueueDetails::default()
+        }
+    }
+
+    #[test]
+    fn test_dead_letter_growth_needs_two_samples() {
+        assert_eq!(dead_letter_growth(&[]), None);
+        assert_eq!(dead_letter_growth(&[4]), None);
+        assert_eq!(dead_letter_growth(&[4, 9, 7]), Some(3));
+        assert_eq!(dead_letter_growth(&[9, 2]), Some(-7));
+    }
+
+    #[test]
+    fn test_refresh_keeps_listed_rows_and_finishes_when_all_are_read() {
+        let mut dashboard = DashboardState::new(1, TaskPool::new(1), CancellationToken::new());
+        dashboard.refreshing = true;
+        dashboard.start_reading(vec!["orders".to_string(), "payments".to_string()]);
+        dashboard.record(&details("orders", 3), Some(2));
+        assert!(dashboard.refreshing);
+
+        dashboard.fail(Some("payments".to_string()), "Forbidden".to_string());
+        assert!(!dashboard.refreshing);
+        assert!(dashboard.refreshed_at.is_some());
+        assert_eq!(dashboard.rows[0].dead_letter, Some(3));
+        assert_eq!(dashboard.rows[0].dead_letter_growth, Some(2));
+        assert_eq!(dashboard.rows[1].error.as_deref(), Some("Forbidden"));
+
+        // A queue deleted since is dropped, its counts are kept for the others
+        dashboard.refreshing = true;
+        dashboard.start_reading(vec!["orders".to_string()]);
+        assert_eq!(dashboard.rows.len(), 1);
+        assert_eq!(dashboard.rows[0].dead_letter, Some(3));
+        assert!(dashboard.refreshing);
+    }
+}
@@ -34,6 +34,18 @@ where
                                 .report_activation_error("QueuePicker", &e);
                         }
                     }
+                    AppState::TopicPicker => {
+                        if let Err(e) = self.app.active(&ComponentId::TopicPicker) {
+                            self.error_reporter
+                                .report_activation_error("TopicPicker", &e);
+                        }
+                    }
+                    AppState::SessionPicker => {
+                        if let Err(e) = self.app.active(&ComponentId::SessionPicker) {
+                            self.error_reporter
+                                .report_activation_error("SessionPicker", &e);
+                        }
+                    }
                     AppState::MessagePicker => {
                         if let Err(e) = self.app.active(&ComponentId::Messages) {
                             self.error_reporter.report_activation_error("Messages", &e);
@@ -79,15 +91,15 @@ where
             self.set_app_state(AppState::HelpScreen);
 
             // Mount help screen component if not already mounted
-            if !self.app.mounted(&ComponentId::HelpScreen) {
-                if let Err(e) = self.app.mount(
+            if !self.app.mounted(&ComponentId::HelpScreen)
+                && let Err(e) = self.app.mount(
                     ComponentId::HelpScreen,
                     Box::new(HelpScreen::new()),
                     Vec::default(),
-                ) {
-                    self.error_reporter
-                        .report_mount_error("HelpScreen", "mount", &e);
-                }
+                )
+            {
+                self.error_reporter
+                    .report_mount_error("HelpScreen", "mount", &e);
             }
 
             // Activate the help screen
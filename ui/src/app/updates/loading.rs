@@ -1,4 +1,5 @@
 use crate::app::model::{AppState, Model};
+use crate::app::updates::messages::bulk_execution::search_operations::SEARCH_OPERATION_PREFIX;
 use crate::components::common::{ComponentId, LoadingActivityMsg, Msg};
 use crate::components::state::ComponentStateMount;
 use tuirealm::terminal::TerminalAdapter;
@@ -78,30 +79,30 @@ where
 
                 // For progress updates, we'll store the progress and remount the loading indicator
                 // This is simpler than trying to access component internals through tuirealm
-                if self.app.mounted(&ComponentId::LoadingIndicator) {
-                    if let Some((base_message, _)) = &self.state_manager.loading_message {
-                        let mut updated_indicator =
-                            crate::components::loading_indicator::LoadingIndicator::new(
-                                base_message,
-                                true,
-                            );
-                        updated_indicator.update_progress(progress_message);
-
-                        // Preserve cancel button state if it was previously shown
-                        if let Some(ref operation_id) = self.state_manager.loading_cancel_button {
-                            updated_indicator.show_cancel_button(operation_id.clone());
-                        }
+                if self.app.mounted(&ComponentId::LoadingIndicator)
+                    && let Some((base_message, _)) = &self.state_manager.loading_message
+                {
+                    let mut updated_indicator =
+                        crate::components::loading_indicator::LoadingIndicator::new(
+                            base_message,
+                            true,
+                        );
+                    updated_indicator.update_progress(progress_message);
+
+                    // Preserve cancel button state if it was previously shown
+                    if let Some(ref operation_id) = self.state_manager.loading_cancel_button {
+                        updated_indicator.show_cancel_button(operation_id.clone());
+                    }
 
-                        if let Err(e) = self.app.remount_with_state(
-                            ComponentId::LoadingIndicator,
-                            updated_indicator,
-                            vec![
-                                Sub::new(SubEventClause::Tick, SubClause::Always),
-                                Sub::new(SubEventClause::Any, SubClause::Always),
-                            ],
-                        ) {
-                            log::error!("Failed to remount loading indicator with progress: {e}");
-                        }
+                    if let Err(e) = self.app.remount_with_state(
+                        ComponentId::LoadingIndicator,
+                        updated_indicator,
+                        vec![
+                            Sub::new(SubEventClause::Tick, SubClause::Always),
+                            Sub::new(SubEventClause::Any, SubClause::Always),
+                        ],
+                    ) {
+                        log::error!("Failed to remount loading indicator with progress: {e}");
                     }
                 }
 
@@ -115,27 +116,25 @@ where
                 self.state_manager.loading_cancel_button = Some(operation_id.clone());
 
                 // For cancel button, remount with cancel button enabled
-                if self.app.mounted(&ComponentId::LoadingIndicator) {
-                    if let Some((base_message, _)) = &self.state_manager.loading_message {
-                        let mut updated_indicator =
-                            crate::components::loading_indicator::LoadingIndicator::new(
-                                base_message,
-                                true,
-                            );
-                        updated_indicator.show_cancel_button(operation_id);
-
-                        if let Err(e) = self.app.remount_with_state(
-                            ComponentId::LoadingIndicator,
-                            updated_indicator,
-                            vec![
-                                Sub::new(SubEventClause::Tick, SubClause::Always),
-                                Sub::new(SubEventClause::Any, SubClause::Always),
-                            ],
-                        ) {
-                            log::error!(
-                                "Failed to remount loading indicator with cancel button: {e}",
-                            );
-                        }
+                if self.app.mounted(&ComponentId::LoadingIndicator)
+                    && let Some((base_message, _)) = &self.state_manager.loading_message
+                {
+                    let mut updated_indicator =
+                        crate::components::loading_indicator::LoadingIndicator::new(
+                            base_message,
+                            true,
+                        );
+                    updated_indicator.show_cancel_button(operation_id);
+
+                    if let Err(e) = self.app.remount_with_state(
+                        ComponentId::LoadingIndicator,
+                        updated_indicator,
+                        vec![
+                            Sub::new(SubEventClause::Tick, SubClause::Always),
+                            Sub::new(SubEventClause::Any, SubClause::Always),
+                        ],
+                    ) {
+                        log::error!("Failed to remount loading indicator with cancel button: {e}",);
                     }
                 }
 
@@ -149,26 +148,26 @@ where
                 self.state_manager.loading_cancel_button = None;
 
                 // For hiding cancel button, remount without cancel button
-                if self.app.mounted(&ComponentId::LoadingIndicator) {
-                    if let Some((base_message, _)) = &self.state_manager.loading_message {
-                        let updated_indicator =
-                            crate::components::loading_indicator::LoadingIndicator::new(
-                                base_message,
-                                true,
-                            );
-
-                        if let Err(e) = self.app.remount_with_state(
-                            ComponentId::LoadingIndicator,
-                            updated_indicator,
-                            vec![
-                                Sub::new(SubEventClause::Tick, SubClause::Always),
-                                Sub::new(SubEventClause::Any, SubClause::Always),
-                            ],
-                        ) {
-                            log::error!(
-                                "Failed to remount loading indicator without cancel button: {e}",
-                            );
-                        }
+                if self.app.mounted(&ComponentId::LoadingIndicator)
+                    && let Some((base_message, _)) = &self.state_manager.loading_message
+                {
+                    let updated_indicator =
+                        crate::components::loading_indicator::LoadingIndicator::new(
+                            base_message,
+                            true,
+                        );
+
+                    if let Err(e) = self.app.remount_with_state(
+                        ComponentId::LoadingIndicator,
+                        updated_indicator,
+                        vec![
+                            Sub::new(SubEventClause::Tick, SubClause::Always),
+                            Sub::new(SubEventClause::Any, SubClause::Always),
+                        ],
+                    ) {
+                        log::error!(
+                            "Failed to remount loading indicator without cancel button: {e}",
+                        );
                     }
                 }
 
@@ -185,15 +184,25 @@ where
                     self.task_manager.cancel_operation(&operation_id);
 
                     // If the user aborted a queue switch, inform the UI so it can roll back
-                    if operation_id.starts_with("switch_queue_") {
-                        if let Err(e) =
+                    if operation_id.starts_with("switch_queue_")
+                        && let Err(e) =
                             self.tx_to_main()
                                 .send(crate::components::common::Msg::QueueActivity(
                                 crate::components::common::QueueActivityMsg::QueueSwitchCancelled,
                             ))
-                        {
-                            log::error!("Failed to notify queue switch cancellation: {e}");
-                        }
+                    {
+                        log::error!("Failed to notify queue switch cancellation: {e}");
+                    }
+
+                    // A cancelled search keeps the matches it found so far
+                    if operation_id.starts_with(SEARCH_OPERATION_PREFIX)
+                        && let Err(e) =
+                            self.tx_to_main()
+                                .send(crate::components::common::Msg::MessageActivity(
+                                    crate::components::common::MessageActivityMsg::SearchCancelled,
+                                ))
+                    {
+                        log::error!("Failed to notify search cancellation: {e}");
                     }
                 }
 
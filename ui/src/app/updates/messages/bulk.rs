@@ -1,9 +1,61 @@
 use crate::app::model::Model;
 use crate::components::common::{ComponentId, MessageActivityMsg, Msg, PopupActivityMsg};
-use quetty_server::bulk_operations::MessageIdentifier;
-use quetty_server::model::MessageModel;
+use crate::components::message_details::envelope::parse_duration;
+use crate::config;
+use quetty_server::bulk_operations::{MessageIdentifier, NonTargetReleaser, NonTargetStrategy};
+use quetty_server::model::{MessageModel, MessageState};
+use quetty_server::service_bus_manager::DeferredMessageAction;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
 use tuirealm::terminal::TerminalAdapter;
 
+/// Split message identifiers by whether the loaded message they refer to is in `state`
+fn partition_by_state(
+    message_ids: Vec<MessageIdentifier>,
+    loaded: &[MessageModel],
+    state: MessageState,
+) -> (Vec<MessageIdentifier>, Vec<MessageIdentifier>) {
+    message_ids.into_iter().partition(|id| {
+        loaded
+            .iter()
+            .any(|message| message.sequence == id.sequence && message.state == state)
+    })
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+/// Parse the cutoff of a purge: empty for every message, otherwise an RFC 3339
+/// timestamp or a minimum age relative to `now` (e.g. `7d`)
+pub(crate) fn parse_purge_cutoff(
+    value: &str,
+    now: OffsetDateTime,
+) -> Result<Option<OffsetDateTime>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    if let Ok(cutoff) = OffsetDateTime::parse(value, &Rfc3339) {
+        return Ok(Some(cutoff));
+    }
+    parse_duration(value)
+        .map(|age| Some(now - age))
+        .map_err(|_| "expected an age such as 7d or 12h, or an RFC 3339 timestamp".to_string())
+}
+
+/// Explain what a targeted operation does to the messages in front of its targets
+fn non_target_warning(strategy: NonTargetStrategy, subject: &str) -> String {
+    match strategy {
+        NonTargetStrategy::Defer => format!(
+            "\n\nℹ️  QUEUE ORDER NOTICE:\n{subject} not from the beginning of the queue.\nMessages in between will be re-queued at the end of the queue with\nnew sequence numbers. Their delivery counts are not changed."
+        ),
+        NonTargetStrategy::Abandon => format!(
+            "\n\n🚨 DELIVERY COUNT WARNING:\n{subject} not from the beginning of the queue.\nThis operation may increase delivery count of messages in between,\npotentially moving them to the Dead Letter Queue if count exceeds 9."
+        ),
+    }
+}
+
 impl<T> Model<T>
 where
     T: TerminalAdapter,
@@ -13,16 +65,91 @@ where
         // Get the current cursor position from the messages component
         if let Ok(tuirealm::State::One(tuirealm::StateValue::Usize(selected_index))) =
             self.app.state(&ComponentId::Messages)
+            && let Some(current_messages) = &self.queue_manager.queue_state.messages
+            && selected_index < current_messages.len()
         {
-            if let Some(current_messages) = &self.queue_manager.queue_state.messages {
-                if selected_index < current_messages.len() {
-                    return Some(current_messages[selected_index].clone());
-                }
-            }
+            return Some(current_messages[selected_index].clone());
         }
         None
     }
 
+    /// Get the selected messages, or the highlighted message when nothing is selected
+    pub(super) fn selected_or_current_messages(&self) -> Vec<MessageIdentifier> {
+        let selected_messages = self
+            .queue_manager
+            .queue_state
+            .bulk_selection
+            .get_selected_messages();
+        if !selected_messages.is_empty() {
+            return selected_messages;
+        }
+        self.get_current_message()
+            .map(|message| vec![MessageIdentifier::from_message(&message)])
+            .unwrap_or_default()
+    }
+
+    /// Split message identifiers into deferred and other loaded messages
+    pub(super) fn partition_deferred(
+        &self,
+        message_ids: Vec<MessageIdentifier>,
+    ) -> (Vec<MessageIdentifier>, Vec<MessageIdentifier>) {
+        partition_by_state(
+            message_ids,
+            &self.queue_state().message_pagination.all_loaded_messages,
+            MessageState::Deferred,
+        )
+    }
+
+    /// Ask for confirmation before settling deferred messages
+    ///
+    /// Deferred messages are fetched by sequence number rather than received from
+    /// the head of the queue, so they cannot share an operation with other messages.
+    fn confirm_settle_deferred(
+        &self,
+        deferred: Vec<MessageIdentifier>,
+        other_count: usize,
+        action: DeferredMessageAction,
+    ) -> Msg {
+        if other_count > 0 {
+            return Msg::ShowError(
+                "❌ Selection mixes deferred and non-deferred messages.\n\n💡 Deferred messages are fetched by sequence number and are handled separately.\n📖 Select only deferred messages, or only non-deferred ones.".to_string(),
+            );
+        }
+
+        let count = deferred.len();
+        let (title, message) = match &action {
+            DeferredMessageAction::Complete => (
+                "Delete Deferred Messages".to_string(),
+                format!(
+                    "You are about to delete {count} deferred message{}.\n\n🗑️  Action: Messages will be permanently removed\n⚠️   Warning: This action CANNOT be undone!",
+                    plural(count)
+                ),
+            ),
+            DeferredMessageAction::DeadLetter { .. } => (
+                "Move Deferred Messages to DLQ".to_string(),
+                format!(
+                    "You are about to move {count} deferred message{} to the dead letter queue.\n\n📤 Action: Messages will be moved to the DLQ\n🗑️  Result: Messages will be DELETED from the main queue",
+                    plural(count)
+                ),
+            ),
+            DeferredMessageAction::Resend { target_queue, .. } => (
+                "Resend Deferred Messages".to_string(),
+                format!(
+                    "You are about to resend {count} deferred message{} to {target_queue}.\n\n📤 Action: Copies will be sent as new active messages\n🗑️  Result: The deferred originals will be DELETED",
+                    plural(count)
+                ),
+            ),
+        };
+
+        Msg::PopupActivity(PopupActivityMsg::ShowConfirmation {
+            title,
+            message,
+            on_confirm: Box::new(Msg::MessageActivity(
+                MessageActivityMsg::SettleDeferredMessages(deferred, action),
+            )),
+        })
+    }
+
     /// Handle bulk delete operation
     pub fn handle_bulk_delete_messages(
         &mut self,
@@ -32,6 +159,16 @@ where
             return None;
         }
 
+        let (deferred, others) = self.partition_deferred(message_ids);
+        if !deferred.is_empty() {
+            return Some(self.confirm_settle_deferred(
+                deferred,
+                others.len(),
+                DeferredMessageAction::Complete,
+            ));
+        }
+        let message_ids = others;
+
         let count = message_ids.len();
         let title = "Delete Messages".to_string();
 
@@ -61,15 +198,10 @@ where
             if count == 1 { "" } else { "s" }
         );
 
-        // Add delivery count warning if selections are not contiguous from the beginning
-        // or if it's a single message that's not at the beginning
+        // Explain the effect on messages in between if selections are not contiguous
+        // from the beginning or if it's a single message that's not at the beginning
         if !selections_contiguous || (count == 1 && !is_single_message_at_start) {
-            message.push_str("\n\n🚨 DELIVERY COUNT WARNING:\n");
-            message.push_str("Selected messages are not from the beginning of the queue.\n");
-            message
-                .push_str("This operation may increase delivery count of messages in between,\n");
-            message
-                .push_str("potentially moving them to the Dead Letter Queue if count exceeds 9.");
+            message.push_str(&self.non_target_warning("Selected messages are"));
         }
 
         Some(Msg::PopupActivity(PopupActivityMsg::ShowConfirmation {
@@ -90,6 +222,19 @@ where
             return None;
         }
 
+        let (deferred, others) = self.partition_deferred(message_ids);
+        if !deferred.is_empty() {
+            return Some(self.confirm_settle_deferred(
+                deferred,
+                others.len(),
+                DeferredMessageAction::DeadLetter {
+                    reason: Some("Bulk moved to DLQ".to_string()),
+                    error_description: None,
+                },
+            ));
+        }
+        let message_ids = others;
+
         let count = message_ids.len();
         let title = "Move to Dead Letter Queue".to_string();
 
@@ -119,15 +264,10 @@ where
             if count == 1 { "" } else { "s" }
         );
 
-        // Add delivery count warning if selections are not contiguous from the beginning
-        // or if it's a single message that's not at the beginning
+        // Explain the effect on messages in between if selections are not contiguous
+        // from the beginning or if it's a single message that's not at the beginning
         if !selections_contiguous || (count == 1 && !is_single_message_at_start) {
-            message.push_str("\n\n🚨 DELIVERY COUNT WARNING:\n");
-            message.push_str("Selected messages are not from the beginning of the queue.\n");
-            message
-                .push_str("This operation may increase delivery count of messages in between,\n");
-            message
-                .push_str("potentially moving them to the Dead Letter Queue if count exceeds 9.");
+            message.push_str(&self.non_target_warning("Selected messages are"));
         }
 
         Some(Msg::PopupActivity(PopupActivityMsg::ShowConfirmation {
@@ -149,6 +289,25 @@ where
             return None;
         }
 
+        // Resending without deletion works from the peeked copies; only a
+        // resend that deletes from the DLQ has to fetch deferred messages
+        let message_ids = if delete_from_dlq {
+            let (deferred, others) = self.partition_deferred(message_ids);
+            if !deferred.is_empty() {
+                return Some(self.confirm_settle_deferred(
+                    deferred,
+                    others.len(),
+                    DeferredMessageAction::Resend {
+                        target_queue: self.main_queue_name()?,
+                        resend_options: config::get_config_or_panic().resend().clone(),
+                    },
+                ));
+            }
+            others
+        } else {
+            message_ids
+        };
+
         let count = message_ids.len();
 
         let (title, base_message) = if delete_from_dlq {
@@ -191,6 +350,187 @@ where
         Some(Msg::PopupActivity(popup_msg))
     }
 
+    /// Handle cancelling the selected scheduled messages, or the current message
+    ///
+    /// Only messages in the `Scheduled` state can be cancelled; other selected
+    /// messages are left untouched.
+    pub fn handle_cancel_scheduled_selected(&mut self) -> Option<Msg> {
+        let candidates = self.selected_or_current_messages();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let (scheduled, skipped) = partition_by_state(
+            candidates,
+            &self.queue_state().message_pagination.all_loaded_messages,
+            MessageState::Scheduled,
+        );
+
+        if scheduled.is_empty() {
+            return Some(Msg::ShowError(
+                "❌ No scheduled messages selected.\n\n💡 Only messages in the Scheduled state can be cancelled.".to_string(),
+            ));
+        }
+
+        let count = scheduled.len();
+        let mut message = format!(
+            "You are about to cancel {} scheduled message{}.\n\n🗑️  Result: The message{} will never be delivered\n⚠️   Warning: This action CANNOT be undone!",
+            count,
+            if count == 1 { "" } else { "s" },
+            if count == 1 { "" } else { "s" }
+        );
+        if !skipped.is_empty() {
+            message.push_str(&format!(
+                "\n\nℹ️  {} selected message{} not scheduled and will be skipped.",
+                skipped.len(),
+                if skipped.len() == 1 { " is" } else { "s are" }
+            ));
+        }
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowConfirmation {
+            title: "Cancel Scheduled Messages".to_string(),
+            message,
+            on_confirm: Box::new(Msg::MessageActivity(
+                MessageActivityMsg::CancelScheduledMessages(scheduled),
+            )),
+        }))
+    }
+
+    /// Handle deferring the selected (or current) active messages
+    ///
+    /// When the selection holds deferred messages instead, they are resent to
+    /// the main queue as new active messages and the deferred originals removed.
+    pub fn handle_defer_selected(&mut self) -> Option<Msg> {
+        let candidates = self.selected_or_current_messages();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let (deferred, others) = self.partition_deferred(candidates);
+        if !deferred.is_empty() {
+            return Some(self.confirm_settle_deferred(
+                deferred,
+                others.len(),
+                DeferredMessageAction::Resend {
+                    target_queue: self.main_queue_name()?,
+                    resend_options: config::get_config_or_panic().resend().clone(),
+                },
+            ));
+        }
+
+        let (active, skipped) = partition_by_state(
+            others,
+            &self.queue_state().message_pagination.all_loaded_messages,
+            MessageState::Active,
+        );
+        if active.is_empty() {
+            return Some(Msg::ShowError(
+                "❌ No active messages selected.\n\n💡 Only messages in the Active state can be deferred.".to_string(),
+            ));
+        }
+
+        let count = active.len();
+        let mut message = format!(
+            "You are about to defer {count} message{}.\n\n⏸️  Action: Messages stay in the queue but are only delivered when fetched by sequence number\n💡 Press '{}' on a deferred message to resend it as an active message",
+            plural(count),
+            config::get_config_or_panic().keys().defer_message()
+        );
+        if !skipped.is_empty() {
+            message.push_str(&format!(
+                "\n\nℹ️  {} selected message{} not active and will be skipped.",
+                skipped.len(),
+                if skipped.len() == 1 { " is" } else { "s are" }
+            ));
+        }
+        message.push_str(&self.non_target_warning("Selected messages may be"));
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowConfirmation {
+            title: "Defer Messages".to_string(),
+            message,
+            on_confirm: Box::new(Msg::MessageActivity(MessageActivityMsg::DeferMessages(
+                active,
+            ))),
+        }))
+    }
+
+    /// Name of the main queue, also when the dead letter queue is open
+    /// Warning about messages in front of the targets of a targeted operation,
+    /// based on how the current queue releases them
+    pub(crate) fn non_target_warning(&self, subject: &str) -> String {
+        let queue_name = self
+            .queue_state()
+            .current_queue_name
+            .as_deref()
+            .unwrap_or_default();
+        let releaser = NonTargetReleaser::new(
+            config::get_config_or_panic().batch().non_target_strategy(),
+            queue_name,
+        );
+        non_target_warning(releaser.strategy(), subject)
+    }
+
+    /// Ask for the cutoff of a purge of the current queue or DLQ
+    pub fn handle_purge_queue_requested(&mut self) -> Option<Msg> {
+        let Some(queue_name) = self.queue_state().current_queue_name.clone() else {
+            return Some(Msg::ShowError("❌ No queue selected.".to_string()));
+        };
+        if self.queue_state().current_session_id.is_some() {
+            return Some(Msg::ShowError(
+                "❌ Purge is not available for session-enabled queues.".to_string(),
+            ));
+        }
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Purge Queue".to_string(),
+            message: format!(
+                "Purge messages from '{queue_name}'.\nLeave empty to purge every message, or enter a minimum age (e.g. 7d, 12h)\nor an RFC 3339 timestamp to purge only older messages."
+            ),
+            initial_value: String::new(),
+            on_submit: |value| Msg::MessageActivity(MessageActivityMsg::PurgeCutoffEntered(value)),
+        }))
+    }
+
+    /// Confirm a purge once its cutoff has been entered
+    pub fn handle_purge_cutoff_entered(&mut self, cutoff: String) -> Option<Msg> {
+        let queue_name = self.queue_state().current_queue_name.clone()?;
+        let older_than = match parse_purge_cutoff(&cutoff, OffsetDateTime::now_utc()) {
+            Ok(older_than) => older_than,
+            Err(e) => return Some(Msg::ShowError(format!("❌ Invalid purge cutoff: {e}"))),
+        };
+
+        let mut message = match older_than {
+            None => format!("You are about to purge ALL messages from '{queue_name}'."),
+            Some(cutoff) => format!(
+                "You are about to purge all messages enqueued before {} from '{queue_name}'.",
+                cutoff
+                    .format(&Rfc3339)
+                    .unwrap_or_else(|_| cutoff.to_string())
+            ),
+        };
+        message.push_str("\n\n🗑️  Action: Messages will be permanently removed, including ones not loaded\n⚠️   Warning: This action CANNOT be undone!");
+        if older_than.is_some() {
+            message.push_str("\n\nℹ️  Messages are purged in queue order; the purge stops at the first newer message.");
+        }
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowConfirmation {
+            title: "Purge Queue".to_string(),
+            message,
+            on_confirm: Box::new(Msg::MessageActivity(MessageActivityMsg::PurgeQueue(
+                older_than,
+            ))),
+        }))
+    }
+
+    fn main_queue_name(&self) -> Option<String> {
+        let queue_name = self.queue_state().current_queue_name.as_ref()?;
+        Some(
+            queue_name
+                .strip_suffix("/$deadletterqueue")
+                .unwrap_or(queue_name)
+                .to_string(),
+        )
+    }
+
     /// Handle bulk delete for currently selected messages or current message
     pub fn handle_bulk_delete_selected(&mut self) -> Option<Msg> {
         let selected_messages = self
@@ -258,3 +598,62 @@ where
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quetty_server::model::BodyData;
+
+    fn message(sequence: i64, state: MessageState) -> MessageModel {
+        MessageModel::new(
+            sequence,
+            format!("msg-{sequence}"),
+            time::OffsetDateTime::UNIX_EPOCH,
+            0,
+            state,
+            BodyData::RawString(String::new()),
+        )
+    }
+
+    #[test]
+    fn test_partition_by_state_matches_loaded_sequence() {
+        let loaded = vec![
+            message(1, MessageState::Active),
+            message(2, MessageState::Deferred),
+            message(3, MessageState::Deferred),
+        ];
+        let ids = vec![
+            MessageIdentifier::new("msg-1".to_string(), 1),
+            MessageIdentifier::new("msg-3".to_string(), 3),
+            MessageIdentifier::new("unknown".to_string(), 4),
+        ];
+
+        let (deferred, others) = partition_by_state(ids, &loaded, MessageState::Deferred);
+
+        assert_eq!(
+            deferred,
+            vec![MessageIdentifier::new("msg-3".to_string(), 3)]
+        );
+        assert_eq!(others.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_purge_cutoff() {
+        let now = OffsetDateTime::parse("2024-05-08T12:00:00Z", &Rfc3339).unwrap();
+
+        assert_eq!(parse_purge_cutoff("  ", now), Ok(None));
+        assert_eq!(
+            parse_purge_cutoff("7d", now),
+            Ok(Some(
+                OffsetDateTime::parse("2024-05-01T12:00:00Z", &Rfc3339).unwrap()
+            ))
+        );
+        assert_eq!(
+            parse_purge_cutoff("2024-01-01T00:00:00Z", now),
+            Ok(Some(
+                OffsetDateTime::parse("2024-01-01T00:00:00Z", &Rfc3339).unwrap()
+            ))
+        );
+        assert!(parse_purge_cutoff("last week", now).is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use super::operation_setup::BulkOperationValidation;
+use crate::app::bulk_operation_processor::BulkOperationPostProcessor;
+use crate::app::model::Model;
+use crate::app::task_manager::ProgressReporter;
+use crate::components::common::{LoadingActivityMsg, MessageActivityMsg, Msg, PopupActivityMsg};
+use crate::error::AppError;
+use quetty_server::bulk_operations::MessageIdentifier;
+use quetty_server::service_bus_manager::{
+    DeferredMessageAction, ServiceBusCommand, ServiceBusResponse,
+};
+use tuirealm::terminal::TerminalAdapter;
+
+/// Generate a unique operation ID for cancellation support
+fn operation_id(prefix: &str) -> String {
+    format!(
+        "{prefix}_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+    )
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+/// Execute deferral of active messages, one targeted lookup per message
+pub fn handle_defer_execution<T: TerminalAdapter>(
+    model: &mut Model<T>,
+    message_ids: Vec<MessageIdentifier>,
+) -> Option<Msg> {
+    if Model::<T>::validate_not_empty(&message_ids).is_err() {
+        return None;
+    }
+
+    let Some(service_bus_manager) = model.service_bus_manager.clone() else {
+        log::warn!("Service bus manager not initialized");
+        return None;
+    };
+    let tx_to_main = model.tx_to_main().clone();
+    let count = message_ids.len();
+
+    // Scan at least as far as the messages loaded in the UI
+    let page_size = model.get_current_page_size() as usize;
+    let total_loaded_messages = model
+        .queue_state()
+        .message_pagination
+        .all_loaded_messages
+        .len();
+    let max_position = std::cmp::max(total_loaded_messages, page_size);
+
+    model.task_manager.execute_with_progress(
+        format!("Deferring {count} message{}...", plural(count)),
+        operation_id("defer_messages"),
+        move |progress: ProgressReporter| {
+            Box::pin(async move {
+                let mut deferred = 0;
+                let mut errors = Vec::new();
+                let mut still_deferred = Vec::new();
+
+                for (index, message_id) in message_ids.iter().enumerate() {
+                    progress.report_progress(format!("Deferring message {}/{count}...", index + 1));
+
+                    let command = ServiceBusCommand::DeferMessage {
+                        message_id: message_id.id.clone(),
+                        sequence_number: message_id.sequence,
+                        max_position,
+                    };
+                    match service_bus_manager
+                        .lock()
+                        .await
+                        .execute_command(command)
+                        .await
+                    {
+                        ServiceBusResponse::MessageDeferred {
+                            still_deferred: non_targets,
+                            ..
+                        } => {
+                            deferred += 1;
+                            still_deferred = non_targets;
+                        }
+                        ServiceBusResponse::Error { error } => {
+                            log::error!("Failed to defer message {message_id}: {error}");
+                            errors.push(error.to_string());
+                        }
+                        _ => errors.push("Unexpected response for defer message".to_string()),
+                    }
+                }
+
+                if deferred == 0 {
+                    return Err(AppError::ServiceBus(errors.join("\n")));
+                }
+
+                let mut summary = format!("✅ Deferred {deferred} message{}", plural(deferred));
+                if !errors.is_empty() {
+                    summary.push_str(&format!(
+                        "\n\n⚠️  {} message{} could not be deferred",
+                        errors.len(),
+                        plural(errors.len())
+                    ));
+                }
+                if let Some(notice) =
+                    BulkOperationPostProcessor::format_still_deferred_notice(&still_deferred)
+                {
+                    summary.push_str("\n\n");
+                    summary.push_str(&notice);
+                }
+
+                let messages = [
+                    Msg::LoadingActivity(LoadingActivityMsg::Stop),
+                    Msg::MessageActivity(MessageActivityMsg::ForceReloadMessages),
+                    Msg::PopupActivity(PopupActivityMsg::ShowSuccess(summary)),
+                ];
+                for msg in messages {
+                    tx_to_main
+                        .send(msg)
+                        .map_err(|e| AppError::Component(e.to_string()))?;
+                }
+
+                Ok(())
+            })
+        },
+    );
+
+    None
+}
+
+/// Execute completion, dead lettering or resending of deferred messages
+pub fn handle_settle_deferred_execution<T: TerminalAdapter>(
+    model: &mut Model<T>,
+    message_ids: Vec<MessageIdentifier>,
+    action: DeferredMessageAction,
+) -> Option<Msg> {
+    if Model::<T>::validate_not_empty(&message_ids).is_err() {
+        return None;
+    }
+
+    let Some(service_bus_manager) = model.service_bus_manager.clone() else {
+        log::warn!("Service bus manager not initialized");
+        return None;
+    };
+    let tx_to_main = model.tx_to_main().clone();
+    let count = message_ids.len();
+
+    let (verb, past) = match &action {
+        DeferredMessageAction::Complete => ("Deleting", "Deleted"),
+        DeferredMessageAction::DeadLetter { .. } => ("Moving to DLQ", "Moved to DLQ"),
+        DeferredMessageAction::Resend { .. } => ("Resending", "Resent"),
+    };
+
+    model.task_manager.execute_with_progress(
+        format!("{verb} {count} deferred message{}...", plural(count)),
+        operation_id("settle_deferred"),
+        move |progress: ProgressReporter| {
+            Box::pin(async move {
+                progress.report_progress("Fetching deferred messages...");
+
+                let is_resend = matches!(action, DeferredMessageAction::Resend { .. });
+                let command = ServiceBusCommand::SettleDeferredMessages {
+                    message_ids,
+                    action,
+                };
+                let response = service_bus_manager
+                    .lock()
+                    .await
+                    .execute_command(command)
+                    .await;
+
+                let result = match response {
+                    ServiceBusResponse::BulkOperationCompleted { result } => result,
+                    ServiceBusResponse::Error { error } => {
+                        log::error!("Failed to settle deferred messages: {error}");
+                        return Err(AppError::ServiceBus(error.to_string()));
+                    }
+                    _ => {
+                        return Err(AppError::ServiceBus(
+                            "Unexpected response for settle deferred messages".to_string(),
+                        ));
+                    }
+                };
+
+                log::info!(
+                    "{past} deferred messages: {} successful, {} failed, {} not found",
+                    result.successful,
+                    result.failed,
+                    result.not_found
+                );
+
+                if result.successful == 0 {
+                    let mut error = format!("No deferred messages were {}", past.to_lowercase());
+                    for detail in &result.error_details {
+                        error.push('\n');
+                        error.push_str(detail);
+                    }
+                    if result.not_found > 0 {
+                        error.push_str(&format!(
+                            "\n{} message{} no longer deferred",
+                            result.not_found,
+                            if result.not_found == 1 {
+                                " is"
+                            } else {
+                                "s are"
+                            }
+                        ));
+                    }
+                    return Err(AppError::ServiceBus(error));
+                }
+
+                let mut summary = format!(
+                    "✅ {past} {} deferred message{}",
+                    result.successful,
+                    plural(result.successful)
+                );
+                let unsettled = result.failed + result.not_found;
+                if unsettled > 0 {
+                    summary.push_str(&format!(
+                        "\n\n⚠️  {unsettled} message{} could not be processed",
+                        plural(unsettled)
+                    ));
+                }
+
+                // A resend adds new messages to the queue, so reload instead of
+                // only dropping the settled ones from the list
+                let update = if is_resend {
+                    MessageActivityMsg::ForceReloadMessages
+                } else {
+                    MessageActivityMsg::BulkRemoveMessagesFromState(
+                        result
+                            .successful_message_ids
+                            .iter()
+                            .map(|id| id.to_string())
+                            .collect(),
+                    )
+                };
+                let messages = [
+                    Msg::LoadingActivity(LoadingActivityMsg::Stop),
+                    Msg::MessageActivity(update),
+                    Msg::PopupActivity(PopupActivityMsg::ShowSuccess(summary)),
+                ];
+                for msg in messages {
+                    tx_to_main
+                        .send(msg)
+                        .map_err(|e| AppError::Component(e.to_string()))?;
+                }
+
+                Ok(())
+            })
+        },
+    );
+
+    None
+}
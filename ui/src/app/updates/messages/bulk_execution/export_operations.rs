@@ -0,0 +1,116 @@
+use crate::app::model::Model;
+use crate::app::task_manager::ProgressReporter;
+use crate::components::common::{Msg, PopupActivityMsg};
+use crate::error::AppError;
+use quetty_server::bulk_operations::{ExportResult, ExportScope};
+use quetty_server::export::ExportFormat;
+use quetty_server::service_bus_manager::{ServiceBusCommand, ServiceBusResponse};
+use std::path::PathBuf;
+use tuirealm::terminal::TerminalAdapter;
+
+/// Export messages of the current queue or DLQ to a new file
+pub fn handle_export_execution<T: TerminalAdapter>(
+    model: &mut Model<T>,
+    path: PathBuf,
+    format: ExportFormat,
+    scope: ExportScope,
+) -> Option<Msg> {
+    let queue_name = match model.get_current_queue() {
+        Ok(name) => name,
+        Err(e) => return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(e))),
+    };
+
+    let Some(service_bus_manager) = model.service_bus_manager.clone() else {
+        log::warn!("Service bus manager not initialized");
+        return None;
+    };
+    let tx_to_main = model.tx_to_main().clone();
+
+    // Generate unique operation ID for cancellation support
+    let operation_id = format!(
+        "export_messages_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+    );
+
+    model.task_manager.execute_with_progress(
+        format!("Exporting {queue_name}..."),
+        operation_id,
+        move |progress: ProgressReporter| {
+            Box::pin(async move {
+                let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+                let command = ServiceBusCommand::ExportMessages {
+                    queue_name: queue_name.clone(),
+                    path,
+                    format,
+                    scope,
+                    progress: Some(progress_tx),
+                    cancel_token: progress.cancel_token(),
+                };
+
+                // The task manager drops this future on cancel, so the export
+                // runs on its own task to complete the file and report how far it got
+                let export = tokio::spawn(async move {
+                    let response = service_bus_manager
+                        .lock()
+                        .await
+                        .execute_command(command)
+                        .await;
+                    let msg = match response {
+                        ServiceBusResponse::MessagesExported { result, .. } => {
+                            PopupActivityMsg::ShowSuccess(export_summary(&result))
+                        }
+                        ServiceBusResponse::Error { error } => {
+                            log::error!("Failed to export {queue_name}: {error}");
+                            PopupActivityMsg::ShowError(AppError::ServiceBus(error.to_string()))
+                        }
+                        _ => PopupActivityMsg::ShowError(AppError::ServiceBus(
+                            "Unexpected response for export messages".to_string(),
+                        )),
+                    };
+                    if let Err(e) = tx_to_main.send(Msg::PopupActivity(msg)) {
+                        log::error!("Failed to send export result: {e}");
+                    }
+                });
+
+                // The channel closes once the export drops its sender
+                while let Some(update) = progress_rx.recv().await {
+                    progress.report_progress(format!(
+                        "Exported {} messages, scanned {}...",
+                        update.exported, update.scanned
+                    ));
+                }
+
+                export
+                    .await
+                    .map_err(|e| AppError::Component(format!("Export task failed: {e}")))
+            })
+        },
+    );
+
+    None
+}
+
+fn export_summary(result: &ExportResult) -> String {
+    let mut summary = format!(
+        "✅ Exported {} message{} to {} ({})",
+        result.exported,
+        if result.exported == 1 { "" } else { "s" },
+        result.path.display(),
+        result.format
+    );
+    if result.missing > 0 {
+        summary.push_str(&format!(
+            "\n\n{} requested message(s) were no longer in the queue.",
+            result.missing
+        ));
+    }
+    if result.cancelled {
+        summary.push_str(
+            "\n\nThe export was cancelled; the file holds the messages written until then.",
+        );
+    }
+    summary
+}
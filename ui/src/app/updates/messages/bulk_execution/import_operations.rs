@@ -0,0 +1,142 @@
+use crate::app::model::Model;
+use crate::app::task_manager::ProgressReporter;
+use crate::components::common::{MessageActivityMsg, Msg, PopupActivityMsg};
+use crate::error::AppError;
+use quetty_server::bulk_operations::{ImportOptions, ImportResult};
+use quetty_server::service_bus_manager::{ServiceBusCommand, ServiceBusResponse};
+use std::path::PathBuf;
+use tuirealm::terminal::TerminalAdapter;
+
+/// Replay an NDJSON export into the current queue
+pub fn handle_import_execution<T: TerminalAdapter>(
+    model: &mut Model<T>,
+    path: PathBuf,
+    options: ImportOptions,
+) -> Option<Msg> {
+    let queue_name = match model.get_current_queue() {
+        Ok(name) => name,
+        Err(e) => return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(e))),
+    };
+
+    let Some(service_bus_manager) = model.service_bus_manager.clone() else {
+        log::warn!("Service bus manager not initialized");
+        return None;
+    };
+    let tx_to_main = model.tx_to_main().clone();
+
+    // Generate unique operation ID for cancellation support
+    let operation_id = format!(
+        "import_messages_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+    );
+    let title = if options.dry_run {
+        format!("Validating {}...", path.display())
+    } else {
+        format!("Importing into {queue_name}...")
+    };
+
+    model.task_manager.execute_with_progress(
+        title,
+        operation_id,
+        move |progress: ProgressReporter| {
+            Box::pin(async move {
+                let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+                let command = ServiceBusCommand::ImportMessages {
+                    queue_name: queue_name.clone(),
+                    path,
+                    options,
+                    progress: Some(progress_tx),
+                    cancel_token: progress.cancel_token(),
+                };
+
+                // The task manager drops this future on cancel, so the import
+                // runs on its own task to report how many messages were sent
+                let import = tokio::spawn(async move {
+                    let response = service_bus_manager
+                        .lock()
+                        .await
+                        .execute_command(command)
+                        .await;
+                    let mut messages = Vec::new();
+                    match response {
+                        ServiceBusResponse::MessagesImported { result, .. } => {
+                            if result.sent > 0 {
+                                messages.push(Msg::MessageActivity(
+                                    MessageActivityMsg::ForceReloadMessages,
+                                ));
+                            }
+                            messages.push(Msg::PopupActivity(PopupActivityMsg::ShowSuccess(
+                                import_summary(&queue_name, &result),
+                            )));
+                        }
+                        ServiceBusResponse::Error { error } => {
+                            log::error!("Failed to import into {queue_name}: {error}");
+                            messages.push(Msg::PopupActivity(PopupActivityMsg::ShowError(
+                                AppError::ServiceBus(error.to_string()),
+                            )));
+                        }
+                        _ => messages.push(Msg::PopupActivity(PopupActivityMsg::ShowError(
+                            AppError::ServiceBus(
+                                "Unexpected response for import messages".to_string(),
+                            ),
+                        ))),
+                    }
+                    for msg in messages {
+                        if let Err(e) = tx_to_main.send(msg) {
+                            log::error!("Failed to send import result: {e}");
+                        }
+                    }
+                });
+
+                // The channel closes once the import drops its sender
+                while let Some(update) = progress_rx.recv().await {
+                    if update.sent + update.failed == 0 {
+                        progress.report_progress(format!("Validated {} messages", update.total));
+                    } else {
+                        progress.report_progress(format!(
+                            "Sent {} of {} messages...",
+                            update.sent + update.failed,
+                            update.total
+                        ));
+                    }
+                }
+
+                import
+                    .await
+                    .map_err(|e| AppError::Component(format!("Import task failed: {e}")))
+            })
+        },
+    );
+
+    None
+}
+
+fn import_summary(queue_name: &str, result: &ImportResult) -> String {
+    if result.dry_run {
+        return format!(
+            "✅ Dry run: {} message{} would be sent to {queue_name}",
+            result.total,
+            if result.total == 1 { "" } else { "s" }
+        );
+    }
+
+    let mut summary = format!(
+        "✅ Imported {} of {} message{} into {queue_name}",
+        result.sent,
+        result.total,
+        if result.total == 1 { "" } else { "s" }
+    );
+    if result.failed > 0 {
+        summary.push_str(&format!(
+            "\n\n{} message(s) could not be sent; see the log for details.",
+            result.failed
+        ));
+    }
+    if result.cancelled {
+        summary.push_str("\n\nThe import was cancelled; the remaining messages were not sent.");
+    }
+    summary
+}
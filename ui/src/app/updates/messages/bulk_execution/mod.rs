@@ -1,4 +1,10 @@
+pub mod defer_operations;
 pub mod delete_operations;
+pub mod export_operations;
+pub mod import_operations;
 pub mod operation_setup;
+pub mod purge_operations;
+pub mod schedule_operations;
+pub mod search_operations;
 pub mod send_operations;
 pub mod task_manager;
@@ -64,6 +64,12 @@ pub enum BulkOperationType {
     ResendFromDlq { delete_source: bool },
     /// Send to DLQ from main queue (with deletion)
     SendToDlq { delete_source: bool },
+    /// Copy or move to a queue in another namespace
+    Transfer {
+        target_queue: String,
+        target_label: String,
+        delete_source: bool,
+    },
 }
 
 impl<T: TerminalAdapter> BulkOperationSetup<T> {
@@ -181,8 +187,8 @@ impl<T: TerminalAdapter> BulkOperationSetup<T> {
     /// Get required queue type for the operation
     fn get_required_queue_type(&self) -> QueueType {
         match self.operation_type {
-            BulkOperationType::Delete => {
-                // Delete can work from either queue type
+            BulkOperationType::Delete | BulkOperationType::Transfer { .. } => {
+                // Delete and transfer can work from either queue type
                 // We'll use the current queue type (no restriction)
                 unsafe { &*self.model }
                     .queue_manager
@@ -295,6 +301,20 @@ impl<T: TerminalAdapter> ValidatedBulkOperation<T> {
             } => {
                 format!("Bulk copying {count} messages from main queue to DLQ...")
             }
+            BulkOperationType::Transfer {
+                target_label,
+                delete_source: true,
+                ..
+            } => {
+                format!("Bulk moving {count} messages to {target_label}...")
+            }
+            BulkOperationType::Transfer {
+                target_label,
+                delete_source: false,
+                ..
+            } => {
+                format!("Bulk copying {count} messages to {target_label}...")
+            }
         }
     }
 
@@ -326,6 +346,7 @@ impl<T: TerminalAdapter> ValidatedBulkOperation<T> {
                 // Add DLQ suffix to current queue name
                 Ok(format!("{current_queue_name}/$deadletterqueue"))
             }
+            BulkOperationType::Transfer { target_queue, .. } => Ok(target_queue.clone()),
         }
     }
 
@@ -335,6 +356,20 @@ impl<T: TerminalAdapter> ValidatedBulkOperation<T> {
             BulkOperationType::Delete => ("Current".to_string(), "Deleted".to_string()),
             BulkOperationType::ResendFromDlq { .. } => ("DLQ".to_string(), "Main".to_string()),
             BulkOperationType::SendToDlq { .. } => ("Main".to_string(), "DLQ".to_string()),
+            BulkOperationType::Transfer {
+                target_queue,
+                target_label,
+                ..
+            } => {
+                let from = match self.model().queue_state().current_queue_type {
+                    QueueType::Main => "Main",
+                    QueueType::DeadLetter => "DLQ",
+                };
+                (
+                    from.to_string(),
+                    format!("{target_queue} in {target_label}"),
+                )
+            }
         }
     }
 
@@ -344,6 +379,7 @@ impl<T: TerminalAdapter> ValidatedBulkOperation<T> {
             BulkOperationType::Delete => true, // Delete operations always delete
             BulkOperationType::ResendFromDlq { delete_source } => *delete_source,
             BulkOperationType::SendToDlq { delete_source } => *delete_source,
+            BulkOperationType::Transfer { delete_source, .. } => *delete_source,
         }
     }
 
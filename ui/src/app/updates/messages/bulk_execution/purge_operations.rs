@@ -0,0 +1,132 @@
+use crate::app::bulk_operation_processor::BulkOperationPostProcessor;
+use crate::app::model::Model;
+use crate::app::task_manager::ProgressReporter;
+use crate::components::common::{LoadingActivityMsg, MessageActivityMsg, Msg, PopupActivityMsg};
+use crate::error::AppError;
+use quetty_server::service_bus_manager::{ServiceBusCommand, ServiceBusResponse};
+use tuirealm::terminal::TerminalAdapter;
+
+/// Execute a purge of the current queue or DLQ, reporting throughput while it runs
+pub fn handle_purge_execution<T: TerminalAdapter>(
+    model: &mut Model<T>,
+    older_than: Option<time::OffsetDateTime>,
+) -> Option<Msg> {
+    let queue_name = match model.get_current_queue() {
+        Ok(name) => name,
+        Err(e) => return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(e))),
+    };
+
+    let Some(service_bus_manager) = model.service_bus_manager.clone() else {
+        log::warn!("Service bus manager not initialized");
+        return None;
+    };
+    let tx_to_main = model.tx_to_main().clone();
+
+    // Generate unique operation ID for cancellation support
+    let operation_id = format!(
+        "purge_queue_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+    );
+
+    model.task_manager.execute_with_progress(
+        format!("Purging {queue_name}..."),
+        operation_id,
+        move |progress: ProgressReporter| {
+            Box::pin(async move {
+                let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+                let command = ServiceBusCommand::PurgeQueue {
+                    queue_name: queue_name.clone(),
+                    older_than,
+                    progress: Some(progress_tx),
+                    cancel_token: progress.cancel_token(),
+                };
+
+                // The channel closes once the purge drops its sender
+                let report_progress = async {
+                    while let Some(update) = progress_rx.recv().await {
+                        progress.report_progress(format!(
+                            "Purged {} messages ({:.0} msg/s)...",
+                            update.purged,
+                            update.messages_per_second()
+                        ));
+                    }
+                };
+                let execute = async {
+                    service_bus_manager
+                        .lock()
+                        .await
+                        .execute_command(command)
+                        .await
+                };
+                let (response, ()) = tokio::join!(execute, report_progress);
+
+                let result = match response {
+                    ServiceBusResponse::QueuePurged { result, .. } => result,
+                    ServiceBusResponse::Error { error } => {
+                        log::error!("Failed to purge {queue_name}: {error}");
+                        return Err(AppError::ServiceBus(error.to_string()));
+                    }
+                    _ => {
+                        return Err(AppError::ServiceBus(
+                            "Unexpected response for purge queue".to_string(),
+                        ));
+                    }
+                };
+
+                let mut summary = format!(
+                    "✅ Purged {} message{} from {queue_name} in {:.1}s ({:.0} msg/s)",
+                    result.purged,
+                    if result.purged == 1 { "" } else { "s" },
+                    result.elapsed.as_secs_f64(),
+                    result.messages_per_second()
+                );
+                if !result.errors.is_empty() {
+                    summary.push_str(&format!(
+                        "\n\n⚠️  {} receiver{} stopped early:\n{}",
+                        result.errors.len(),
+                        if result.errors.len() == 1 { "" } else { "s" },
+                        result.errors.join("\n")
+                    ));
+                }
+                if !result.missed.is_empty() {
+                    let sequence_numbers = result
+                        .missed
+                        .iter()
+                        .map(|sequence| sequence.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    summary.push_str(&format!(
+                        "\n\n⚠️  {} selected message{} not purged: another consumer took {} or {} expired (sequence numbers {sequence_numbers})",
+                        result.missed.len(),
+                        if result.missed.len() == 1 { " was" } else { "s were" },
+                        if result.missed.len() == 1 { "it" } else { "them" },
+                        if result.missed.len() == 1 { "it" } else { "they" },
+                    ));
+                }
+                if let Some(notice) =
+                    BulkOperationPostProcessor::format_still_deferred_notice(&result.still_deferred)
+                {
+                    summary.push_str(&format!("\n\n{notice}"));
+                }
+
+                let messages = [
+                    Msg::LoadingActivity(LoadingActivityMsg::Stop),
+                    Msg::MessageActivity(MessageActivityMsg::ForceReloadMessages),
+                    Msg::PopupActivity(PopupActivityMsg::ShowSuccess(summary)),
+                ];
+                for msg in messages {
+                    tx_to_main
+                        .send(msg)
+                        .map_err(|e| AppError::Component(e.to_string()))?;
+                }
+
+                Ok(())
+            })
+        },
+    );
+
+    None
+}
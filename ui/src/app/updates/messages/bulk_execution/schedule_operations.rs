@@ -0,0 +1,96 @@
+use super::operation_setup::BulkOperationValidation;
+use crate::app::model::Model;
+use crate::app::task_manager::ProgressReporter;
+use crate::components::common::{LoadingActivityMsg, MessageActivityMsg, Msg, PopupActivityMsg};
+use crate::error::AppError;
+use quetty_server::bulk_operations::MessageIdentifier;
+use quetty_server::service_bus_manager::{ServiceBusCommand, ServiceBusResponse};
+use tuirealm::terminal::TerminalAdapter;
+
+/// Execute cancellation of scheduled messages by sequence number
+pub fn handle_cancel_scheduled_execution<T: TerminalAdapter>(
+    model: &mut Model<T>,
+    message_ids: Vec<MessageIdentifier>,
+) -> Option<Msg> {
+    if Model::<T>::validate_not_empty(&message_ids).is_err() {
+        return None;
+    }
+
+    let queue_name = match model.get_current_queue() {
+        Ok(name) => name,
+        Err(e) => return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(e))),
+    };
+
+    let Some(service_bus_manager) = model.service_bus_manager.clone() else {
+        log::warn!("Service bus manager not initialized");
+        return None;
+    };
+    let tx_to_main = model.tx_to_main().clone();
+    let count = message_ids.len();
+
+    // Generate unique operation ID for cancellation support
+    let operation_id = format!(
+        "cancel_scheduled_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+    );
+
+    model.task_manager.execute_with_progress(
+        format!("Cancelling {count} scheduled messages..."),
+        operation_id,
+        move |progress: ProgressReporter| {
+            Box::pin(async move {
+                progress.report_progress("Cancelling scheduled messages...");
+
+                let command = ServiceBusCommand::CancelScheduledMessages {
+                    queue_name: queue_name.clone(),
+                    sequence_numbers: message_ids.iter().map(|id| id.sequence).collect(),
+                };
+
+                let response = service_bus_manager
+                    .lock()
+                    .await
+                    .execute_command(command)
+                    .await;
+
+                let cancelled = match response {
+                    ServiceBusResponse::ScheduledMessagesCancelled { count, .. } => count,
+                    ServiceBusResponse::Error { error } => {
+                        log::error!("Failed to cancel scheduled messages on {queue_name}: {error}");
+                        return Err(AppError::ServiceBus(error.to_string()));
+                    }
+                    _ => {
+                        return Err(AppError::ServiceBus(
+                            "Unexpected response for cancel scheduled messages".to_string(),
+                        ));
+                    }
+                };
+
+                log::info!("Cancelled {cancelled} scheduled messages on {queue_name}");
+
+                let message_ids_str = message_ids.iter().map(|id| id.to_string()).collect();
+                let messages = [
+                    Msg::LoadingActivity(LoadingActivityMsg::Stop),
+                    Msg::MessageActivity(MessageActivityMsg::BulkRemoveMessagesFromState(
+                        message_ids_str,
+                    )),
+                    Msg::PopupActivity(PopupActivityMsg::ShowSuccess(format!(
+                        "✅ Cancelled {cancelled} scheduled message{}",
+                        if cancelled == 1 { "" } else { "s" }
+                    ))),
+                ];
+                for msg in messages {
+                    tx_to_main
+                        .send(msg)
+                        .map_err(|e| AppError::Component(e.to_string()))?;
+                }
+
+                Ok(())
+            })
+        },
+    );
+
+    None
+}
@@ -0,0 +1,112 @@
+use crate::app::model::Model;
+use crate::app::task_manager::ProgressReporter;
+use crate::components::common::{MessageActivityMsg, Msg, PopupActivityMsg};
+use crate::error::AppError;
+use quetty_server::filter::SqlFilter;
+use quetty_server::service_bus_manager::{ServiceBusCommand, ServiceBusResponse};
+use tuirealm::terminal::TerminalAdapter;
+
+/// Operation ID prefix of searches, used to recognise them on cancellation
+pub const SEARCH_OPERATION_PREFIX: &str = "search_messages_";
+
+/// Search the current queue or DLQ, streaming matches into the message list
+pub fn handle_search_execution<T: TerminalAdapter>(
+    model: &mut Model<T>,
+    filter: SqlFilter,
+    search_id: u64,
+) -> Option<Msg> {
+    let queue_name = match model.get_current_queue() {
+        Ok(name) => name,
+        Err(e) => return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(e))),
+    };
+
+    let Some(service_bus_manager) = model.service_bus_manager.clone() else {
+        log::warn!("Service bus manager not initialized");
+        return None;
+    };
+    let tx_to_main = model.tx_to_main().clone();
+
+    model.task_manager.execute_with_progress(
+        format!("Searching {queue_name}..."),
+        format!("{SEARCH_OPERATION_PREFIX}{search_id}"),
+        move |progress: ProgressReporter| {
+            Box::pin(async move {
+                let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+                let command = ServiceBusCommand::SearchMessages {
+                    queue_name: queue_name.clone(),
+                    filter,
+                    max_matches: None,
+                    progress: Some(progress_tx),
+                    cancel_token: progress.cancel_token(),
+                };
+
+                // The channel closes once the search drops its sender
+                let stream_matches = async {
+                    let (mut scanned, mut matched) = (0, 0);
+                    while let Some(update) = progress_rx.recv().await {
+                        scanned = update.scanned;
+                        matched += update.matches.len();
+                        progress.report_progress(format!(
+                            "Scanned {} messages, {matched} match{}...",
+                            update.scanned,
+                            if matched == 1 { "" } else { "es" }
+                        ));
+                        if update.matches.is_empty() {
+                            continue;
+                        }
+                        let msg = Msg::MessageActivity(MessageActivityMsg::SearchProgress {
+                            search_id,
+                            scanned: update.scanned,
+                            matches: update.matches,
+                        });
+                        if let Err(e) = tx_to_main.send(msg) {
+                            log::error!("Failed to send search matches: {e}");
+                        }
+                    }
+                    scanned
+                };
+                let execute = async {
+                    service_bus_manager
+                        .lock()
+                        .await
+                        .execute_command(command)
+                        .await
+                };
+                let (response, scanned) = tokio::join!(execute, stream_matches);
+
+                let result = match response {
+                    ServiceBusResponse::SearchCompleted { result, .. } => result,
+                    ServiceBusResponse::Error { error } => {
+                        log::error!("Failed to search {queue_name}: {error}");
+                        // Keep what was found so far, but stop showing the search as running
+                        let _ = tx_to_main.send(Msg::MessageActivity(
+                            MessageActivityMsg::SearchFinished {
+                                search_id,
+                                scanned,
+                                truncated: false,
+                            },
+                        ));
+                        return Err(AppError::ServiceBus(error.to_string()));
+                    }
+                    _ => {
+                        return Err(AppError::ServiceBus(
+                            "Unexpected response for search messages".to_string(),
+                        ));
+                    }
+                };
+
+                tx_to_main
+                    .send(Msg::MessageActivity(MessageActivityMsg::SearchFinished {
+                        search_id,
+                        scanned: result.scanned,
+                        truncated: result.truncated,
+                    }))
+                    .map_err(|e| AppError::Component(e.to_string()))?;
+
+                Ok(())
+            })
+        },
+    );
+
+    None
+}
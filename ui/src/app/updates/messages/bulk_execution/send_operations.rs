@@ -3,11 +3,11 @@ use super::task_manager::{BulkSendData, BulkSendParams};
 use crate::app::bulk_operation_processor::BulkOperationPostProcessor;
 use crate::app::model::Model;
 use crate::app::task_manager::ProgressReporter;
+use crate::app::updates::messages::transfer::MessageTransfer;
 use crate::components::common::Msg;
 use crate::error::AppError;
 use quetty_server::bulk_operations::MessageIdentifier;
-use quetty_server::model::BodyData;
-use quetty_server::service_bus_manager::{ServiceBusCommand, ServiceBusResponse};
+use quetty_server::service_bus_manager::{MessageData, ServiceBusCommand, ServiceBusResponse};
 use std::sync::Arc;
 use tuirealm::terminal::TerminalAdapter;
 
@@ -178,25 +178,91 @@ pub fn handle_bulk_send_to_dlq_with_delete_execution<T: TerminalAdapter>(
     )
 }
 
+/// Execute a copy or move to a queue in another namespace
+///
+/// Copies are sent from the peeked messages. Moves receive the messages and
+/// only complete them once the other namespace confirmed the send.
+pub fn handle_bulk_transfer_execution<T: TerminalAdapter>(
+    model: &mut Model<T>,
+    transfer: MessageTransfer,
+) -> Option<Msg> {
+    let MessageTransfer {
+        message_ids,
+        target_namespace,
+        target_queue,
+        delete_source,
+    } = transfer;
+
+    let validated_operation = match BulkOperationSetup::new(model, message_ids)
+        .operation_type(BulkOperationType::Transfer {
+            target_queue: target_queue.clone(),
+            target_label: target_namespace.label.clone(),
+            delete_source,
+        })
+        .validate_and_build()
+    {
+        Ok(op) => op,
+        Err(e) => {
+            model
+                .error_reporter
+                .report_simple(e, "BulkTransfer", "validation");
+            return None;
+        }
+    };
+
+    let (from_display, to_display) = validated_operation.get_queue_display_names();
+    let loading_template = validated_operation.get_loading_message();
+    let context = validated_operation.calculate_post_processing_context();
+    let params = BulkSendParams::new(
+        target_queue,
+        delete_source,
+        &loading_template.replace(&validated_operation.message_ids().len().to_string(), "{}"),
+        &from_display,
+        &to_display,
+    )
+    .with_target_namespace(target_namespace);
+
+    if delete_source {
+        return start_bulk_send_operation(
+            model,
+            validated_operation.message_ids().to_vec(),
+            params,
+            context,
+        );
+    }
+
+    let messages_data =
+        match extract_message_data_from_current_state(model, validated_operation.message_ids()) {
+            Ok(data) => data,
+            Err(_) => return None,
+        };
+    start_bulk_send_with_data_operation(model, messages_data, params, context)
+}
+
 /// Extract message data from current state (works for any queue)
 fn extract_message_data_from_current_state<T: TerminalAdapter>(
     model: &Model<T>,
     message_ids: &[MessageIdentifier],
-) -> Result<Vec<(MessageIdentifier, Vec<u8>)>, bool> {
+) -> Result<Vec<(MessageIdentifier, MessageData)>, bool> {
     let mut messages_data = Vec::new();
 
+    let resend_options = crate::config::get_config_or_panic().resend();
+
     // Get messages from pagination state (these are peeked messages)
     let all_messages = &model.queue_state().message_pagination.all_loaded_messages;
 
     for message_id in message_ids {
         // Find the message in our loaded state
         if let Some(message) = all_messages.iter().find(|m| m.id == *message_id) {
-            // Extract the message body as bytes
-            let body = match &message.body {
-                BodyData::ValidJson(json) => serde_json::to_vec(json).unwrap_or_default(),
-                BodyData::RawString(s) => s.as_bytes().to_vec(),
-            };
-            messages_data.push((message_id.clone(), body));
+            // Extract the message body together with its properties; bodies
+            // are sent byte for byte as received
+            let data = resend_options.build_message(
+                message.raw_body.clone(),
+                Some(&message.id),
+                message.sequence,
+                &message.properties,
+            );
+            messages_data.push((message_id.clone(), data));
             log::debug!("Extracted message data for {message_id}");
         } else {
             let error = AppError::State(format!(
@@ -222,19 +288,20 @@ async fn execute_bulk_send_with_data(
     service_bus_manager: Arc<
         tokio::sync::Mutex<quetty_server::service_bus_manager::ServiceBusManager>,
     >,
-    messages_data: &[(MessageIdentifier, Vec<u8>)],
-    target_queue: String,
+    messages_data: &[(MessageIdentifier, MessageData)],
+    operation_params: &BulkSendParams,
     repeat_count: usize,
     progress: &ProgressReporter,
 ) -> Result<quetty_server::bulk_operations::BulkOperationResult, AppError> {
     progress.report_progress("Preparing message data...");
-    let messages_data_converted: Vec<(MessageIdentifier, Vec<u8>)> = messages_data
+    let messages_data_converted: Vec<(MessageIdentifier, MessageData)> = messages_data
         .iter()
         .map(|(id, data)| (id.clone(), data.clone()))
         .collect();
     let command = ServiceBusCommand::BulkSendPeeked {
         messages_data: messages_data_converted,
-        target_queue,
+        target_queue: operation_params.target_queue.clone(),
+        target_namespace: operation_params.target_namespace.clone(),
         repeat_count,
     };
     progress.report_progress("Executing send operation...");
@@ -275,8 +342,7 @@ async fn execute_bulk_send_with_ids(
         tokio::sync::Mutex<quetty_server::service_bus_manager::ServiceBusManager>,
     >,
     message_ids: &[MessageIdentifier],
-    target_queue: String,
-    should_delete_source: bool,
+    operation_params: &BulkSendParams,
     repeat_count: usize,
     max_position: usize,
     progress: &ProgressReporter,
@@ -285,10 +351,12 @@ async fn execute_bulk_send_with_ids(
     let message_ids_converted: Vec<MessageIdentifier> = message_ids.to_vec();
     let command = ServiceBusCommand::BulkSend {
         message_ids: message_ids_converted,
-        target_queue,
-        should_delete_source,
+        target_queue: operation_params.target_queue.clone(),
+        target_namespace: operation_params.target_namespace.clone(),
+        should_delete_source: operation_params.should_delete,
         repeat_count,
         max_position,
+        resend_options: crate::config::get_config_or_panic().resend().clone(),
     };
     progress.report_progress("Executing send operation...");
     let response = service_bus_manager
@@ -428,7 +496,7 @@ fn start_bulk_send_generic<T: TerminalAdapter>(
                         execute_bulk_send_with_data(
                             service_bus_manager.clone(),
                             messages_data,
-                            operation_params.target_queue.clone(),
+                            &operation_params,
                             repeat_count,
                             &progress,
                         )
@@ -438,8 +506,7 @@ fn start_bulk_send_generic<T: TerminalAdapter>(
                         execute_bulk_send_with_ids(
                             service_bus_manager.clone(),
                             message_ids,
-                            operation_params.target_queue.clone(),
-                            operation_params.should_delete,
+                            &operation_params,
                             repeat_count,
                             max_position,
                             &progress,
@@ -526,7 +593,7 @@ fn start_bulk_send_operation<T: TerminalAdapter>(
 /// Method to start bulk send operation with pre-fetched message data
 fn start_bulk_send_with_data_operation<T: TerminalAdapter>(
     model: &Model<T>,
-    messages_data: Vec<(MessageIdentifier, Vec<u8>)>,
+    messages_data: Vec<(MessageIdentifier, MessageData)>,
     params: BulkSendParams,
     context: BulkOperationContext,
 ) -> Option<Msg> {
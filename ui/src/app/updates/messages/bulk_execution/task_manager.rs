@@ -1,9 +1,12 @@
 use quetty_server::bulk_operations::MessageIdentifier;
+use quetty_server::service_bus_manager::{MessageData, TargetNamespace};
 
 /// Parameters for bulk send operations
 #[derive(Debug, Clone)]
 pub struct BulkSendParams {
     pub target_queue: String,
+    /// Namespace of the target queue; `None` for the connected namespace
+    pub target_namespace: Option<TargetNamespace>,
     pub should_delete: bool,
     pub loading_message_template: String,
     pub from_queue_display: String,
@@ -20,18 +23,25 @@ impl BulkSendParams {
     ) -> Self {
         Self {
             target_queue,
+            target_namespace: None,
             should_delete,
             loading_message_template: loading_message_template.to_string(),
             from_queue_display: from_queue_display.to_string(),
             to_queue_display: to_queue_display.to_string(),
         }
     }
+
+    /// Send to a queue in another namespace
+    pub fn with_target_namespace(mut self, target_namespace: TargetNamespace) -> Self {
+        self.target_namespace = Some(target_namespace);
+        self
+    }
 }
 
 /// Data types for bulk send operations
 pub enum BulkSendData {
     MessageIds(Vec<MessageIdentifier>),
-    MessageData(Vec<(MessageIdentifier, Vec<u8>)>),
+    MessageData(Vec<(MessageIdentifier, MessageData)>),
 }
 
 impl BulkSendData {
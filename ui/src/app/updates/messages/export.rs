@@ -0,0 +1,152 @@
+use crate::app::model::Model;
+use crate::app::updates::messages::bulk_execution;
+use crate::components::common::{MessageActivityMsg, Msg, PopupActivityMsg};
+use quetty_server::bulk_operations::ExportScope;
+use quetty_server::export::ExportFormat;
+use std::path::PathBuf;
+use tuirealm::terminal::TerminalAdapter;
+
+impl<T> Model<T>
+where
+    T: TerminalAdapter,
+{
+    /// Ask which messages of the current queue or DLQ to export
+    pub fn handle_export_requested(&mut self) -> Option<Msg> {
+        let Some(queue_name) = self.queue_state().current_queue_name.clone() else {
+            return Some(Msg::ShowError("❌ No queue selected.".to_string()));
+        };
+        if self.queue_state().current_session_id.is_some() {
+            return Some(Msg::ShowError(
+                "❌ Export is not available for session-enabled queues.".to_string(),
+            ));
+        }
+
+        let selected = self.queue_state().bulk_selection.selection_count();
+        let shown = self.queue_state().messages.as_ref().map_or(0, Vec::len);
+        let initial_value = if selected > 0 { "selection" } else { "page" };
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Export Messages".to_string(),
+            message: format!(
+                "What to export from '{queue_name}':\nselection - the {selected} selected message(s)\npage - the {shown} message(s) shown\nall - every message in the queue"
+            ),
+            initial_value: initial_value.to_string(),
+            on_submit: |value| Msg::MessageActivity(MessageActivityMsg::ExportScopeEntered(value)),
+        }))
+    }
+
+    /// Resolve the entered scope and ask for the file to write
+    pub fn handle_export_scope_entered(&mut self, scope: String) -> Option<Msg> {
+        let scope = match scope.trim().to_ascii_lowercase().as_str() {
+            "s" | "selection" => {
+                let selected = &self.queue_state().bulk_selection.selected_messages;
+                if selected.is_empty() {
+                    return Some(Msg::ShowError("❌ No messages are selected.".to_string()));
+                }
+                ExportScope::Sequences(selected.iter().map(|id| id.sequence).collect())
+            }
+            "p" | "page" => {
+                let shown = self.queue_state().messages.as_deref().unwrap_or_default();
+                if shown.is_empty() {
+                    return Some(Msg::ShowError("❌ No messages are shown.".to_string()));
+                }
+                ExportScope::Sequences(shown.iter().map(|message| message.sequence).collect())
+            }
+            "a" | "all" => ExportScope::All,
+            other => {
+                return Some(Msg::ShowError(format!(
+                    "❌ Unknown export scope '{other}', expected selection, page or all."
+                )));
+            }
+        };
+        self.queue_state_mut().pending_export = Some(scope);
+
+        let queue_name = self
+            .queue_state()
+            .current_queue_name
+            .clone()
+            .unwrap_or_default();
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Export Messages".to_string(),
+            message: "File to create; .ndjson (or .jsonl), .json or .csv picks the format."
+                .to_string(),
+            initial_value: default_export_path(&queue_name),
+            on_submit: |value| Msg::MessageActivity(MessageActivityMsg::ExportPathEntered(value)),
+        }))
+    }
+
+    /// Validate the entered path and start the export
+    pub fn handle_export_path_entered(&mut self, path: String) -> Option<Msg> {
+        let Some(scope) = self.queue_state_mut().pending_export.take() else {
+            log::warn!("Export path entered without a pending export");
+            return None;
+        };
+
+        let path = expand_home(path.trim());
+        if path.as_os_str().is_empty() {
+            return None;
+        }
+        let Some(format) = ExportFormat::from_path(&path) else {
+            return Some(Msg::ShowError(
+                "❌ Unknown export format, use a .ndjson, .jsonl, .json or .csv file.".to_string(),
+            ));
+        };
+        if path.exists() {
+            return Some(Msg::ShowError(format!(
+                "❌ {} already exists; exports never overwrite files.",
+                path.display()
+            )));
+        }
+
+        bulk_execution::export_operations::handle_export_execution(self, path, format, scope)
+    }
+}
+
+/// Suggested file name: the entity path and the current time, as NDJSON
+fn default_export_path(queue_name: &str) -> String {
+    let mut name = String::new();
+    for c in queue_name.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+            name.push(c);
+        } else if !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    format!("{}-{timestamp}.ndjson", name.trim_matches('-'))
+}
+
+/// Expands a leading `~` to the home directory
+pub(super) fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_export_path_is_a_plain_file_name() {
+        let path = default_export_path("orders/$DeadLetterQueue");
+        assert!(path.starts_with("orders-DeadLetterQueue-"));
+        assert!(path.ends_with(".ndjson"));
+        assert!(!path.contains('/'));
+
+        let path = default_export_path("topic/Subscriptions/audit");
+        assert!(path.starts_with("topic-Subscriptions-audit-"));
+    }
+
+    #[test]
+    fn test_expand_home() {
+        assert_eq!(expand_home("out.csv"), PathBuf::from("out.csv"));
+        assert_eq!(expand_home("~user/out.csv"), PathBuf::from("~user/out.csv"));
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(expand_home("~/out.csv"), home.join("out.csv"));
+        }
+    }
+}
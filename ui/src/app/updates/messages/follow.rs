@@ -0,0 +1,298 @@
+use crate::app::model::Model;
+use crate::components::common::{MessageActivityMsg, Msg};
+use crate::config;
+use quetty_server::model::MessageModel;
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tuirealm::terminal::TerminalAdapter;
+
+/// Window the arrival rate of a followed queue is averaged over
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Follow mode: the message list keeps peeking for messages past the last
+/// loaded sequence number on the configured `peek_interval`
+#[derive(Debug)]
+pub struct FollowState {
+    /// Identifies the run, so ticks of a stopped timer are ignored
+    pub id: u64,
+    /// Whether ticks are skipped until the user resumes
+    pub paused: bool,
+    /// Whether a peek started by a tick has not returned yet
+    pub polling: bool,
+    /// Sequence numbers of the messages that arrived while following
+    pub new_sequences: HashSet<i64>,
+    /// Arrival time and size of the non-empty peeks within the rate window
+    arrivals: VecDeque<(Instant, usize)>,
+    started_at: Instant,
+    /// Stops the interval timer; cancelled when following stops
+    timer: CancellationToken,
+}
+
+impl FollowState {
+    /// Start tracking a follow run whose ticks stop with `timer`
+    pub fn new(id: u64, timer: CancellationToken, now: Instant) -> Self {
+        Self {
+            id,
+            paused: false,
+            polling: false,
+            new_sequences: HashSet::new(),
+            arrivals: VecDeque::new(),
+            started_at: now,
+            timer,
+        }
+    }
+
+    /// Remember the messages of a peek as new and count them towards the rate
+    pub fn record(&mut self, messages: &[MessageModel], now: Instant) {
+        if messages.is_empty() {
+            return;
+        }
+        self.new_sequences
+            .extend(messages.iter().map(|message| message.sequence));
+        self.arrivals.push_back((now, messages.len()));
+        self.prune(now);
+    }
+
+    /// Number of messages that arrived while following
+    pub fn received(&self) -> usize {
+        self.new_sequences.len()
+    }
+
+    /// Messages per second over the last minute, or since following started
+    pub fn rate_per_second(&self, now: Instant) -> f64 {
+        let window = now
+            .saturating_duration_since(self.started_at)
+            .min(RATE_WINDOW)
+            .max(Duration::from_secs(1));
+        let arrived: usize = self
+            .arrivals
+            .iter()
+            .filter(|(at, _)| now.saturating_duration_since(*at) <= RATE_WINDOW)
+            .map(|(_, count)| count)
+            .sum();
+        arrived as f64 / window.as_secs_f64()
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while self
+            .arrivals
+            .front()
+            .is_some_and(|(at, _)| now.saturating_duration_since(*at) > RATE_WINDOW)
+        {
+            self.arrivals.pop_front();
+        }
+    }
+}
+
+impl Drop for FollowState {
+    fn drop(&mut self) {
+        self.timer.cancel();
+    }
+}
+
+impl<T> Model<T>
+where
+    T: TerminalAdapter,
+{
+    /// Start following the current queue or DLQ, or stop following it
+    pub fn handle_toggle_follow(&mut self) -> Option<Msg> {
+        if let Some(follow) = self.queue_state_mut().follow.take() {
+            log::info!(
+                "Stopped following after {} new message(s)",
+                follow.received()
+            );
+            self.refresh_followed_view();
+            return None;
+        }
+
+        if self.queue_state().current_queue_name.is_none() {
+            return Some(Msg::ShowError("❌ No queue selected.".to_string()));
+        }
+        if self.queue_state().search.is_some() {
+            return Some(Msg::ShowError(
+                "❌ Leave the search results before following the queue.".to_string(),
+            ));
+        }
+
+        let follow_id = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let interval = config::get_config_or_panic().peek_interval();
+        let timer = CancellationToken::new();
+
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+        let cancelled = timer.clone();
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            // A slow peek should not be followed by a burst of catch-up ticks
+            ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    _ = cancelled.cancelled() => break,
+                    _ = ticks.tick() => {
+                        let tick = Msg::MessageActivity(MessageActivityMsg::FollowTick(follow_id));
+                        if tx_to_main.send(tick).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        log::info!("Following the current queue every {interval:?}");
+        self.queue_state_mut().follow = Some(FollowState::new(follow_id, timer, Instant::now()));
+        self.refresh_followed_view();
+        None
+    }
+
+    /// Pause or resume follow mode
+    pub fn handle_toggle_follow_paused(&mut self) -> Option<Msg> {
+        let Some(follow) = self.queue_state_mut().follow.as_mut() else {
+            let key = config::get_config_or_panic().keys().follow_messages();
+            return Some(Msg::ShowError(format!(
+                "❌ Follow mode is off; press '{key}' to follow the queue."
+            )));
+        };
+        follow.paused = !follow.paused;
+        log::info!(
+            "Follow mode {}",
+            if follow.paused { "paused" } else { "resumed" }
+        );
+
+        self.refresh_followed_view();
+        None
+    }
+
+    /// Peek for messages past the last loaded one, unless paused or still busy
+    pub fn handle_follow_tick(&mut self, follow_id: u64) -> Option<Msg> {
+        let busy = self.queue_state().message_pagination.is_loading()
+            || self.queue_state().search.is_some();
+        let follow = self
+            .queue_state_mut()
+            .follow
+            .as_mut()
+            .filter(|follow| follow.id == follow_id)?;
+        if follow.paused || follow.polling || busy {
+            return None;
+        }
+        follow.polling = true;
+
+        if let Err(e) = self.load_followed_messages() {
+            log::error!("Failed to peek followed queue: {e}");
+            if let Some(follow) = self.queue_state_mut().follow.as_mut() {
+                follow.polling = false;
+            }
+        }
+        None
+    }
+
+    /// Append the messages a follow peek returned and highlight them.
+    ///
+    /// When the last page was shown it stays shown, so new messages scroll into view.
+    pub fn handle_followed_messages_loaded(&mut self, messages: Vec<MessageModel>) -> Option<Msg> {
+        let now = Instant::now();
+        self.queue_state_mut().message_pagination.set_loading(false);
+
+        if messages.is_empty() {
+            // Keep refreshing while the rate decays towards zero
+            if self
+                .queue_state()
+                .follow
+                .as_ref()
+                .is_some_and(|follow| follow.rate_per_second(now) > 0.0)
+            {
+                self.refresh_followed_view();
+            }
+            return None;
+        }
+
+        log::debug!("Follow mode received {} new message(s)", messages.len());
+        if let Some(follow) = self.queue_state_mut().follow.as_mut() {
+            follow.record(&messages, now);
+        }
+
+        let page_size = config::get_current_page_size();
+        let pagination = &mut self.queue_state_mut().message_pagination;
+        let last_page = |total: usize| total.div_ceil(page_size as usize).saturating_sub(1);
+        let was_on_last_page = pagination.current_page >= last_page(pagination.total_messages());
+        pagination.append_messages(messages);
+        if was_on_last_page {
+            pagination.current_page = last_page(pagination.total_messages());
+        }
+        pagination.update(page_size);
+
+        if let Err(e) = self.update_current_page_view() {
+            log::error!("Failed to show followed messages: {e}");
+        }
+        None
+    }
+
+    /// Redraw the message list so the follow status and highlights are current
+    fn refresh_followed_view(&mut self) {
+        if let Err(e) = self.update_current_page_view() {
+            log::error!("Failed to refresh the message list for follow mode: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quetty_server::model::{BodyData, MessageState};
+
+    fn message(sequence: i64) -> MessageModel {
+        MessageModel::new(
+            sequence,
+            format!("msg-{sequence}"),
+            time::OffsetDateTime::UNIX_EPOCH,
+            0,
+            MessageState::Active,
+            BodyData::RawString(String::new()),
+        )
+    }
+
+    #[test]
+    fn test_record_collects_new_sequences() {
+        let start = Instant::now();
+        let mut follow = FollowState::new(1, CancellationToken::new(), start);
+
+        follow.record(&[], start);
+        assert_eq!(follow.received(), 0);
+        assert_eq!(follow.rate_per_second(start), 0.0);
+
+        follow.record(&[message(10), message(11)], start + Duration::from_secs(1));
+        follow.record(&[message(12)], start + Duration::from_secs(2));
+        assert_eq!(follow.received(), 3);
+        assert!(follow.new_sequences.contains(&12));
+        assert!(!follow.new_sequences.contains(&9));
+    }
+
+    #[test]
+    fn test_rate_is_averaged_over_the_last_minute() {
+        let start = Instant::now();
+        let mut follow = FollowState::new(1, CancellationToken::new(), start);
+
+        let batch: Vec<_> = (0..30).map(message).collect();
+        follow.record(&batch, start + Duration::from_secs(5));
+        assert_eq!(follow.rate_per_second(start + Duration::from_secs(10)), 3.0);
+
+        // Once the window is full the rate is per minute of arrivals
+        assert_eq!(follow.rate_per_second(start + Duration::from_secs(60)), 0.5);
+
+        // Arrivals older than a minute no longer count
+        assert_eq!(follow.rate_per_second(start + Duration::from_secs(70)), 0.0);
+        assert_eq!(follow.received(), 30);
+    }
+
+    #[test]
+    fn test_dropping_the_state_stops_the_timer() {
+        let timer = CancellationToken::new();
+        let follow = FollowState::new(1, timer.clone(), Instant::now());
+        assert!(!timer.is_cancelled());
+        drop(follow);
+        assert!(timer.is_cancelled());
+    }
+}
@@ -0,0 +1,151 @@
+use super::export::expand_home;
+use crate::app::model::Model;
+use crate::components::common::{MessageActivityMsg, Msg, PopupActivityMsg};
+use quetty_server::bulk_operations::ImportOptions;
+use quetty_server::service_bus_manager::QueueType;
+use tuirealm::terminal::TerminalAdapter;
+
+impl<T> Model<T>
+where
+    T: TerminalAdapter,
+{
+    /// Ask for the NDJSON export to replay into the current queue
+    pub fn handle_import_requested(&mut self) -> Option<Msg> {
+        let Some(queue_name) = self.queue_state().current_queue_name.clone() else {
+            return Some(Msg::ShowError("❌ No queue selected.".to_string()));
+        };
+        if self.queue_state().current_queue_type == QueueType::DeadLetter {
+            return Some(Msg::ShowError(
+                "❌ Cannot import messages into a Dead Letter Queue.\n\n💡 Switch to the main queue first."
+                    .to_string(),
+            ));
+        }
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Import Messages".to_string(),
+            message: format!("NDJSON export to replay into '{queue_name}'"),
+            initial_value: String::new(),
+            on_submit: |value| Msg::MessageActivity(MessageActivityMsg::ImportPathEntered(value)),
+        }))
+    }
+
+    /// Check the entered file and ask for the import options
+    pub fn handle_import_path_entered(&mut self, path: String) -> Option<Msg> {
+        if path.trim().is_empty() {
+            return None;
+        }
+        let path = expand_home(path.trim());
+        if !path.is_file() {
+            return Some(Msg::ShowError(format!(
+                "❌ {} is not a file.",
+                path.display()
+            )));
+        }
+        self.queue_state_mut().pending_import = Some(path);
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Import Options".to_string(),
+            message: "ids=keep|new - keep the original MessageIds or generate new ones\nrate=<n>|none - messages per second\ndry-run - only validate the file".to_string(),
+            initial_value: "ids=keep rate=none".to_string(),
+            on_submit: |value| Msg::MessageActivity(MessageActivityMsg::ImportOptionsEntered(value)),
+        }))
+    }
+
+    /// Parse the options and confirm the import, or start a dry run directly
+    pub fn handle_import_options_entered(&mut self, options: String) -> Option<Msg> {
+        let Some(path) = self.queue_state_mut().pending_import.take() else {
+            log::warn!("Import options entered without a pending import");
+            return None;
+        };
+        let options = match parse_import_options(&options) {
+            Ok(options) => options,
+            Err(e) => return Some(Msg::ShowError(format!("❌ {e}"))),
+        };
+
+        let import = Msg::MessageActivity(MessageActivityMsg::ImportMessages(
+            path.clone(),
+            options.clone(),
+        ));
+        if options.dry_run {
+            return Some(import);
+        }
+
+        let queue_name = self
+            .queue_state()
+            .current_queue_name
+            .clone()
+            .unwrap_or_default();
+        let rate = options
+            .rate_limit
+            .map_or("unlimited".to_string(), |rate| format!("{rate}/s"));
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowConfirmation {
+            title: "Import Messages".to_string(),
+            message: format!(
+                "Send every message of\n{}\nto '{queue_name}'?\n\nMessageIds: {}\nRate: {rate}",
+                path.display(),
+                if options.keep_message_ids {
+                    "original"
+                } else {
+                    "new"
+                }
+            ),
+            on_confirm: Box::new(import),
+        }))
+    }
+}
+
+/// Parses space or comma separated import options such as
+/// `ids=new rate=50 dry-run`; unspecified options keep their defaults
+fn parse_import_options(input: &str) -> Result<ImportOptions, String> {
+    let mut options = ImportOptions::default();
+    for option in input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|option| !option.is_empty())
+    {
+        match option.to_ascii_lowercase().split_once('=') {
+            None if matches!(option, "dry-run" | "dry") => options.dry_run = true,
+            Some(("ids", "keep")) => options.keep_message_ids = true,
+            Some(("ids", "new")) => options.keep_message_ids = false,
+            Some(("rate", "none" | "unlimited" | "0")) => options.rate_limit = None,
+            Some(("rate", rate)) => {
+                let rate = rate
+                    .parse()
+                    .map_err(|_| format!("Invalid rate '{rate}', expected messages per second"))?;
+                options.rate_limit = Some(rate);
+            }
+            _ => {
+                return Err(format!(
+                    "Unknown import option '{option}', expected ids=keep|new, rate=<n>|none or dry-run"
+                ));
+            }
+        }
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_import_options() {
+        assert_eq!(parse_import_options("").unwrap(), ImportOptions::default());
+        assert_eq!(
+            parse_import_options("ids=new, rate=50 dry-run").unwrap(),
+            ImportOptions {
+                keep_message_ids: false,
+                rate_limit: Some(50),
+                dry_run: true,
+            }
+        );
+        assert_eq!(
+            parse_import_options("ids=keep rate=none")
+                .unwrap()
+                .rate_limit,
+            None
+        );
+        assert!(parse_import_options("rate=fast").is_err());
+        assert!(parse_import_options("ids=maybe").is_err());
+        assert!(parse_import_options("replay").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::app::model::Model;
 use crate::components::common::{MessageActivityMsg, Msg};
+use crate::config;
 use crate::error::{AppError, AppResult};
 use quetty_server::model::MessageModel;
 use std::sync::mpsc::Sender;
@@ -93,6 +94,43 @@ where
         Ok(())
     }
 
+    /// Peek for messages past the last loaded one without a loading indicator,
+    /// for follow mode; the result arrives as `NewMessagesLoaded`
+    pub fn load_followed_messages(&mut self) -> AppResult<()> {
+        let Some(service_bus_manager) = self.get_service_bus_manager() else {
+            return Err(AppError::Config(
+                "Service Bus manager not initialized. Please configure authentication first."
+                    .to_string(),
+            ));
+        };
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+        let from_sequence = self
+            .queue_state()
+            .message_pagination
+            .last_loaded_sequence
+            .map(|seq| seq + 1);
+        let message_count = config::get_current_page_size();
+
+        self.queue_state_mut().message_pagination.set_loading(true);
+        self.task_manager.execute_background(async move {
+            let result = Self::execute_loading_task(
+                tx_to_main.clone(),
+                service_bus_manager,
+                from_sequence,
+                message_count,
+            )
+            .await;
+
+            // An empty result clears the loading state and lets the next tick peek again
+            if result.is_err() {
+                let _ = Self::send_loaded_messages(&tx_to_main, Vec::new());
+            }
+            result
+        });
+
+        Ok(())
+    }
+
     pub(crate) fn get_service_bus_manager(
         &self,
     ) -> Option<
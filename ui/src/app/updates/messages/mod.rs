@@ -5,8 +5,14 @@ use tuirealm::terminal::TerminalAdapter;
 pub mod async_operations;
 pub mod bulk;
 pub mod bulk_execution;
+pub mod export;
+pub mod follow;
+pub mod import;
 pub mod loading;
 pub mod pagination;
+pub mod queue_info;
+pub mod search;
+pub mod transfer;
 pub mod updates;
 pub use pagination::MessagePaginationState;
 
@@ -39,10 +45,68 @@ where
             | MessageActivityMsg::BulkSendToDLQWithDelete(_)
             | MessageActivityMsg::BulkResendFromDLQ(_, _)
             | MessageActivityMsg::BulkRemoveMessagesFromState(_)
+            | MessageActivityMsg::CancelScheduledSelected
+            | MessageActivityMsg::CancelScheduledMessages(_)
+            | MessageActivityMsg::DeferSelected
+            | MessageActivityMsg::DeferMessages(_)
+            | MessageActivityMsg::SettleDeferredMessages(_, _)
+            | MessageActivityMsg::PurgeQueueRequested
+            | MessageActivityMsg::PurgeCutoffEntered(_)
+            | MessageActivityMsg::PurgeQueue(_)
             | MessageActivityMsg::BulkDeleteCompleted { .. } => {
                 self.handle_bulk_execution_operations(msg)
             }
 
+            // Whole-queue search operations
+            MessageActivityMsg::SearchRequested
+            | MessageActivityMsg::SearchExpressionEntered(_)
+            | MessageActivityMsg::SearchProgress { .. }
+            | MessageActivityMsg::SearchFinished { .. }
+            | MessageActivityMsg::SearchCancelled
+            | MessageActivityMsg::ClearSearch => self.handle_search_operations(msg),
+
+            // Export operations
+            MessageActivityMsg::ExportRequested => self.handle_export_requested(),
+            MessageActivityMsg::ExportScopeEntered(scope) => {
+                self.handle_export_scope_entered(scope)
+            }
+            MessageActivityMsg::ExportPathEntered(path) => self.handle_export_path_entered(path),
+
+            // Import operations
+            MessageActivityMsg::ImportRequested => self.handle_import_requested(),
+            MessageActivityMsg::ImportPathEntered(path) => self.handle_import_path_entered(path),
+            MessageActivityMsg::ImportOptionsEntered(options) => {
+                self.handle_import_options_entered(options)
+            }
+            MessageActivityMsg::ImportMessages(path, options) => {
+                bulk_execution::import_operations::handle_import_execution(self, path, options)
+            }
+
+            // Copy or move to another namespace
+            MessageActivityMsg::TransferRequested => self.handle_transfer_requested(),
+            MessageActivityMsg::TransferTargetEntered(target) => {
+                self.handle_transfer_target_entered(target)
+            }
+            MessageActivityMsg::TransferTargetResolved(target_namespace) => {
+                self.handle_transfer_target_resolved(target_namespace)
+            }
+            MessageActivityMsg::TransferQueueEntered(queue) => {
+                self.handle_transfer_queue_entered(queue)
+            }
+            MessageActivityMsg::TransferModeEntered(mode) => {
+                self.handle_transfer_mode_entered(mode)
+            }
+            MessageActivityMsg::TransferMessages(transfer) => {
+                bulk_execution::send_operations::handle_bulk_transfer_execution(self, transfer)
+            }
+
+            // Follow mode
+            MessageActivityMsg::ToggleFollow => self.handle_toggle_follow(),
+            MessageActivityMsg::ToggleFollowPaused => self.handle_toggle_follow_paused(),
+            MessageActivityMsg::FollowTick(follow_id) => self.handle_follow_tick(follow_id),
+
+            MessageActivityMsg::QueueInfoRequested => self.handle_queue_info_requested(),
+
             // Pagination operations
             MessageActivityMsg::NextPage | MessageActivityMsg::PreviousPage => {
                 self.handle_pagination_operations(msg)
@@ -94,6 +158,13 @@ where
 
     /// Handle bulk selection operations
     fn handle_bulk_selection_operations(&mut self, msg: MessageActivityMsg) -> Option<Msg> {
+        if self.queue_state().search.is_some() && msg != MessageActivityMsg::ClearAllSelections {
+            return Some(Msg::ShowError(
+                "❌ Selection is not available in search results. Press Esc to leave the search."
+                    .to_string(),
+            ));
+        }
+
         match msg {
             MessageActivityMsg::ToggleMessageSelectionByIndex(index) => {
                 self.handle_toggle_message_selection_by_index(index)
@@ -140,11 +211,65 @@ where
             MessageActivityMsg::BulkRemoveMessagesFromState(message_ids) => {
                 self.handle_bulk_remove_messages_from_state(message_ids)
             }
+            MessageActivityMsg::CancelScheduledSelected => self.handle_cancel_scheduled_selected(),
+            MessageActivityMsg::CancelScheduledMessages(message_ids) => {
+                bulk_execution::schedule_operations::handle_cancel_scheduled_execution(
+                    self,
+                    message_ids,
+                )
+            }
+            MessageActivityMsg::DeferSelected => self.handle_defer_selected(),
+            MessageActivityMsg::DeferMessages(message_ids) => {
+                bulk_execution::defer_operations::handle_defer_execution(self, message_ids)
+            }
+            MessageActivityMsg::SettleDeferredMessages(message_ids, action) => {
+                bulk_execution::defer_operations::handle_settle_deferred_execution(
+                    self,
+                    message_ids,
+                    action,
+                )
+            }
+            MessageActivityMsg::PurgeQueueRequested => self.handle_purge_queue_requested(),
+            MessageActivityMsg::PurgeCutoffEntered(cutoff) => {
+                self.handle_purge_cutoff_entered(cutoff)
+            }
+            MessageActivityMsg::PurgeQueue(older_than) => {
+                bulk_execution::purge_operations::handle_purge_execution(self, older_than)
+            }
             MessageActivityMsg::BulkDeleteCompleted {
                 successful_count,
                 failed_count,
                 total_count,
-            } => self.handle_bulk_delete_completed(successful_count, failed_count, total_count),
+                still_deferred,
+            } => self.handle_bulk_delete_completed(
+                successful_count,
+                failed_count,
+                total_count,
+                still_deferred,
+            ),
+            _ => None,
+        }
+    }
+
+    /// Handle whole-queue search operations
+    fn handle_search_operations(&mut self, msg: MessageActivityMsg) -> Option<Msg> {
+        match msg {
+            MessageActivityMsg::SearchRequested => self.handle_search_requested(),
+            MessageActivityMsg::SearchExpressionEntered(expression) => {
+                self.handle_search_expression_entered(expression)
+            }
+            MessageActivityMsg::SearchProgress {
+                search_id,
+                scanned,
+                matches,
+            } => self.handle_search_progress(search_id, scanned, matches),
+            MessageActivityMsg::SearchFinished {
+                search_id,
+                scanned,
+                truncated,
+            } => self.handle_search_finished(search_id, scanned, truncated),
+            MessageActivityMsg::SearchCancelled => self.handle_search_cancelled(),
+            MessageActivityMsg::ClearSearch => self.handle_clear_search(),
             _ => None,
         }
     }
@@ -193,6 +318,7 @@ where
             }
             MessageActivityMsg::ForceReloadMessages => self.handle_force_reload_messages(),
             MessageActivityMsg::RefreshQueueStatistics => self.handle_refresh_queue_statistics(),
+            MessageActivityMsg::SampleQueueStatistics => self.handle_sample_queue_statistics(),
             _ => None,
         }
     }
@@ -511,8 +511,13 @@ where
             log::debug!("Current page messages: first_id={first_id}, last_id={last_id}");
         }
 
-        // Update the queue state messages to reflect current page
-        self.queue_state_mut().messages = Some(current_page_messages);
+        // Update the queue state messages to reflect current page, unless
+        // search matches are shown in its place
+        let messages = match &self.queue_state().search {
+            Some(search) => search.matches.clone(),
+            None => current_page_messages,
+        };
+        self.queue_state_mut().messages = Some(messages);
 
         if let Err(e) = self.remount_messages() {
             log::error!("Failed to remount messages: {e}");
@@ -0,0 +1,57 @@
+use crate::app::model::Model;
+use crate::components::common::{Msg, PopupActivityMsg};
+use crate::error::AppError;
+use quetty_server::service_bus_manager::{ServiceBusCommand, ServiceBusResponse};
+use tuirealm::terminal::TerminalAdapter;
+
+impl<T> Model<T>
+where
+    T: TerminalAdapter,
+{
+    /// Load the runtime counts and configuration of the current queue and show them
+    pub fn handle_queue_info_requested(&mut self) -> Option<Msg> {
+        let Some(queue_name) = self.queue_state().current_queue_name.clone() else {
+            return Some(Msg::ShowError("❌ No queue selected.".to_string()));
+        };
+        let Some(service_bus_manager) = self.service_bus_manager.clone() else {
+            return Some(Msg::ShowError(
+                "❌ Not connected to a namespace.".to_string(),
+            ));
+        };
+
+        // The DLQ has no properties of its own; show those of its queue
+        let queue_name = queue_name.trim_end_matches("/$deadletterqueue").to_string();
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+
+        self.task_manager.execute(
+            format!("Loading properties of {queue_name}..."),
+            async move {
+                let command = ServiceBusCommand::GetQueueDetails {
+                    queue_name: queue_name.clone(),
+                };
+                let response = service_bus_manager
+                    .lock()
+                    .await
+                    .execute_command(command)
+                    .await;
+
+                match response {
+                    ServiceBusResponse::QueueDetails { details } => {
+                        tx_to_main
+                            .send(Msg::PopupActivity(PopupActivityMsg::ShowQueueInfo(details)))
+                            .map_err(|e| AppError::Component(e.to_string()))?;
+                        Ok(())
+                    }
+                    ServiceBusResponse::Error { error } => {
+                        log::warn!("Failed to load properties of {queue_name}: {error}");
+                        Err(AppError::from(error))
+                    }
+                    _ => Err(AppError::ServiceBus(
+                        "Unexpected response for queue properties".to_string(),
+                    )),
+                }
+            },
+        );
+        None
+    }
+}
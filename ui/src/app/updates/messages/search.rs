@@ -0,0 +1,153 @@
+use crate::app::model::Model;
+use crate::app::queue_state::MessageSearch;
+use crate::app::updates::messages::bulk_execution;
+use crate::components::common::{MessageActivityMsg, Msg, PopupActivityMsg};
+use quetty_server::filter::SqlFilter;
+use quetty_server::model::MessageModel;
+use tuirealm::terminal::TerminalAdapter;
+
+impl<T> Model<T>
+where
+    T: TerminalAdapter,
+{
+    /// Ask for the SQL filter of a search of the current queue or DLQ
+    pub fn handle_search_requested(&mut self) -> Option<Msg> {
+        let Some(queue_name) = self.queue_state().current_queue_name.clone() else {
+            return Some(Msg::ShowError("❌ No queue selected.".to_string()));
+        };
+        if self.queue_state().current_session_id.is_some() {
+            return Some(Msg::ShowError(
+                "❌ Search is not available for session-enabled queues.".to_string(),
+            ));
+        }
+
+        let initial_value = self
+            .queue_state()
+            .search
+            .as_ref()
+            .map(|search| search.expression.clone())
+            .unwrap_or_default();
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Search Messages".to_string(),
+            message: format!(
+                "Search every message in '{queue_name}' with a Service Bus SQL filter, e.g.\nsys.DeliveryCount > 3 AND body.orderId = '123'\nLeave empty to go back to the loaded pages."
+            ),
+            initial_value,
+            on_submit: |value| {
+                Msg::MessageActivity(MessageActivityMsg::SearchExpressionEntered(value))
+            },
+        }))
+    }
+
+    /// Validate the entered filter and start searching with it
+    pub fn handle_search_expression_entered(&mut self, expression: String) -> Option<Msg> {
+        if expression.trim().is_empty() {
+            return self.handle_clear_search();
+        }
+        let filter = match SqlFilter::parse(&expression) {
+            Ok(filter) => filter,
+            Err(e) => return Some(Msg::ShowError(format!("❌ Invalid search filter: {e}"))),
+        };
+
+        let search_id = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        // Selections refer to positions in the loaded pages, not in the matches
+        self.queue_state_mut().bulk_selection.clear_all();
+        self.queue_state_mut().search = Some(MessageSearch::new(
+            search_id,
+            filter.expression().to_string(),
+        ));
+        self.show_search_results(false);
+
+        bulk_execution::search_operations::handle_search_execution(self, filter, search_id)
+    }
+
+    /// Append the matches of a scanned batch to the running search
+    pub fn handle_search_progress(
+        &mut self,
+        search_id: u64,
+        scanned: usize,
+        matches: Vec<MessageModel>,
+    ) -> Option<Msg> {
+        let search = self.current_search_mut(search_id)?;
+        let first_matches = search.matches.is_empty() && !matches.is_empty();
+        search.scanned = scanned;
+        search.matches.extend(matches);
+
+        self.show_search_results(true);
+        if first_matches && let Err(e) = self.remount_message_details(0) {
+            log::error!("Failed to show the first search match: {e}");
+        }
+        None
+    }
+
+    /// Mark the search as complete once the queue has been walked
+    pub fn handle_search_finished(
+        &mut self,
+        search_id: u64,
+        scanned: usize,
+        truncated: bool,
+    ) -> Option<Msg> {
+        let search = self.current_search_mut(search_id)?;
+        search.scanned = scanned;
+        search.truncated = truncated;
+        search.running = false;
+
+        self.show_search_results(true);
+        None
+    }
+
+    /// Keep the matches found before the user cancelled the search
+    pub fn handle_search_cancelled(&mut self) -> Option<Msg> {
+        let search = self
+            .queue_state_mut()
+            .search
+            .as_mut()
+            .filter(|search| search.running)?;
+        search.running = false;
+        search.cancelled = true;
+
+        self.show_search_results(true);
+        None
+    }
+
+    /// Leave the search results and show the loaded pages again
+    pub fn handle_clear_search(&mut self) -> Option<Msg> {
+        self.queue_state_mut().search.take()?;
+        log::info!("Cleared message search");
+
+        if let Err(e) = self.update_current_page_view() {
+            log::error!("Failed to restore page view after search: {e}");
+        }
+        if let Err(e) = self.remount_message_details(0) {
+            log::error!("Failed to refresh message details after search: {e}");
+        }
+        None
+    }
+
+    /// The active search, unless `search_id` belongs to one it replaced
+    fn current_search_mut(&mut self, search_id: u64) -> Option<&mut MessageSearch> {
+        self.queue_state_mut()
+            .search
+            .as_mut()
+            .filter(|search| search.id == search_id)
+    }
+
+    /// Show the matches of the active search in the message list
+    fn show_search_results(&mut self, preserve_cursor: bool) {
+        let matches = self
+            .queue_state()
+            .search
+            .as_ref()
+            .map(|search| search.matches.clone());
+        self.queue_state_mut().messages = matches;
+
+        if let Err(e) = self.remount_messages_with_cursor_and_focus_control(preserve_cursor, true) {
+            log::error!("Failed to show search results: {e}");
+        }
+    }
+}
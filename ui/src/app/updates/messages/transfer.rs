@@ -0,0 +1,500 @@
+use crate::app::model::Model;
+use crate::components::common::{
+    AuthActivityMsg, LoadingActivityMsg, MessageActivityMsg, Msg, PopupActivityMsg,
+};
+use crate::config::{self, ProfileConfig};
+use crate::constants::env_vars::{
+    AZURE_AD_CLIENT_SECRET_ENCRYPTION_SALT, AZURE_AD_ENCRYPTED_CLIENT_SECRET,
+};
+use crate::error::AppError;
+use crate::utils::auth::{AUTH_METHOD_DEVICE_CODE, AuthUtils};
+use crate::utils::connection_string::ConnectionStringParser;
+use quetty_server::auth::types::AzureAdAuthConfig;
+use quetty_server::auth::{AuthProvider, AzureAdProvider};
+use quetty_server::bulk_operations::MessageIdentifier;
+use quetty_server::service_bus_manager::azure_management_client::AzureManagementClient;
+use quetty_server::service_bus_manager::{DEAD_LETTER_SUFFIX, TargetNamespace, send_target};
+use std::sync::mpsc::Sender;
+use tuirealm::terminal::TerminalAdapter;
+
+/// Messages picked for a copy or move to another namespace while its target is entered
+#[derive(Debug, Clone, Default)]
+pub struct PendingTransfer {
+    /// Messages to copy or move
+    pub message_ids: Vec<MessageIdentifier>,
+    /// Namespace resolved from the entered profile or connection string
+    pub target_namespace: Option<TargetNamespace>,
+    /// Queue or topic in the target namespace
+    pub target_queue: Option<String>,
+}
+
+/// A confirmed copy or move of messages to a queue in another namespace
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageTransfer {
+    pub message_ids: Vec<MessageIdentifier>,
+    pub target_namespace: TargetNamespace,
+    pub target_queue: String,
+    /// Whether the messages are deleted here once the target confirmed the send
+    pub delete_source: bool,
+}
+
+impl<T> Model<T>
+where
+    T: TerminalAdapter,
+{
+    /// Ask for the namespace to copy or move the selected or current messages to
+    pub fn handle_transfer_requested(&mut self) -> Option<Msg> {
+        if self.queue_state().current_queue_name.is_none() {
+            return Some(Msg::ShowError("❌ No queue selected.".to_string()));
+        }
+        let message_ids = self.selected_or_current_messages();
+        if message_ids.is_empty() {
+            return None;
+        }
+
+        let count = message_ids.len();
+        let profiles = config::list_available_profiles();
+        self.queue_state_mut().pending_transfer = Some(PendingTransfer {
+            message_ids,
+            ..PendingTransfer::default()
+        });
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Copy or Move to Another Namespace".to_string(),
+            message: format!(
+                "Target namespace for {count} message(s): a profile name or a connection string.\nProfiles: {}",
+                if profiles.is_empty() {
+                    "none".to_string()
+                } else {
+                    profiles.join(", ")
+                }
+            ),
+            initial_value: String::new(),
+            on_submit: |value| {
+                Msg::MessageActivity(MessageActivityMsg::TransferTargetEntered(value))
+            },
+        }))
+    }
+
+    /// Resolve the entered namespace and ask for the target queue
+    pub fn handle_transfer_target_entered(&mut self, target: String) -> Option<Msg> {
+        let target = target.trim();
+        if target.is_empty() {
+            self.queue_state_mut().pending_transfer = None;
+            return None;
+        }
+        match resolve_target(target) {
+            Ok(TransferTarget::Namespace(target_namespace)) => {
+                self.handle_transfer_target_resolved(target_namespace)
+            }
+            Ok(TransferTarget::AzureAd(target)) => {
+                self.sign_in_to_transfer_target(*target);
+                None
+            }
+            Err(e) => {
+                self.queue_state_mut().pending_transfer = None;
+                Some(Msg::ShowError(format!("❌ {e}")))
+            }
+        }
+    }
+
+    /// Sign in as the Azure AD profile of the target namespace in the
+    /// background, showing its device code if it uses one
+    fn sign_in_to_transfer_target(&self, target: AzureAdTarget) {
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+        let http_client = self.http_client.clone();
+        let device_code = target.auth.auth_method == AUTH_METHOD_DEVICE_CODE;
+        if !device_code {
+            let _ = tx_to_main.send(Msg::LoadingActivity(LoadingActivityMsg::Start(format!(
+                "Signing in to '{}'...",
+                target.label
+            ))));
+        }
+
+        self.task_manager.execute_background(async move {
+            let result = azure_ad_target_namespace(target, http_client, &tx_to_main).await;
+            let done = if device_code {
+                Msg::AuthActivity(AuthActivityMsg::CancelAuthentication)
+            } else {
+                Msg::LoadingActivity(LoadingActivityMsg::Stop)
+            };
+            let _ = tx_to_main.send(done);
+
+            let msg = match result {
+                Ok(target_namespace) => Msg::MessageActivity(
+                    MessageActivityMsg::TransferTargetResolved(target_namespace),
+                ),
+                Err(e) => Msg::ShowError(format!("❌ {e}")),
+            };
+            tx_to_main
+                .send(msg)
+                .map_err(|e| AppError::Component(e.to_string()))
+        });
+    }
+
+    /// Ask for the target queue once the namespace is reachable
+    pub fn handle_transfer_target_resolved(
+        &mut self,
+        target_namespace: TargetNamespace,
+    ) -> Option<Msg> {
+        let initial_value = self
+            .queue_state()
+            .current_queue_name
+            .as_deref()
+            .map(send_target)
+            .unwrap_or_default()
+            .to_string();
+        let message = format!(
+            "Queue or topic in '{}' to send the messages to",
+            target_namespace.label
+        );
+        self.queue_state_mut()
+            .pending_transfer
+            .as_mut()?
+            .target_namespace = Some(target_namespace);
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Copy or Move to Another Namespace".to_string(),
+            message,
+            initial_value,
+            on_submit: |value| {
+                Msg::MessageActivity(MessageActivityMsg::TransferQueueEntered(value))
+            },
+        }))
+    }
+
+    /// Check the entered target queue and ask whether to copy or move
+    pub fn handle_transfer_queue_entered(&mut self, queue: String) -> Option<Msg> {
+        let queue = queue.trim();
+        if queue.is_empty() {
+            self.queue_state_mut().pending_transfer = None;
+            return None;
+        }
+        if queue.to_ascii_lowercase().ends_with(DEAD_LETTER_SUFFIX) {
+            self.queue_state_mut().pending_transfer = None;
+            return Some(Msg::ShowError(
+                "❌ Messages cannot be sent to a dead letter queue directly.".to_string(),
+            ));
+        }
+        self.queue_state_mut()
+            .pending_transfer
+            .as_mut()?
+            .target_queue = Some(queue.to_string());
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Copy or Move to Another Namespace".to_string(),
+            message: "copy - send copies and keep the messages here\nmove - delete the messages here once the target confirmed the send".to_string(),
+            initial_value: "copy".to_string(),
+            on_submit: |value| Msg::MessageActivity(MessageActivityMsg::TransferModeEntered(value)),
+        }))
+    }
+
+    /// Confirm the copy or move once its mode has been entered
+    pub fn handle_transfer_mode_entered(&mut self, mode: String) -> Option<Msg> {
+        let pending = self.queue_state_mut().pending_transfer.take()?;
+        let (Some(target_namespace), Some(target_queue)) =
+            (pending.target_namespace, pending.target_queue)
+        else {
+            log::warn!("Transfer mode entered without a complete target");
+            return None;
+        };
+        let delete_source = match mode.trim().to_ascii_lowercase().as_str() {
+            "c" | "copy" => false,
+            "m" | "move" => true,
+            other => {
+                return Some(Msg::ShowError(format!(
+                    "❌ Unknown mode '{other}', expected copy or move."
+                )));
+            }
+        };
+
+        let (deferred, _) = self.partition_deferred(pending.message_ids.clone());
+        if delete_source && !deferred.is_empty() {
+            return Some(Msg::ShowError(format!(
+                "❌ {} deferred message(s) cannot be moved to another namespace.\n\n💡 Copy them instead, or resend them here first.",
+                deferred.len()
+            )));
+        }
+
+        let count = pending.message_ids.len();
+        let destination = format!(
+            "'{target_queue}' in '{}'{}",
+            target_namespace.label,
+            target_namespace
+                .host()
+                .map(|host| format!(" ({host})"))
+                .unwrap_or_default()
+        );
+        let mut message = if delete_source {
+            format!(
+                "You are about to move {count} message{} to {destination}.\n\n📤 Action: Messages will be sent to the other namespace\n🗑️  Result: Messages will be DELETED here once the send is confirmed",
+                if count == 1 { "" } else { "s" }
+            )
+        } else {
+            format!(
+                "You are about to copy {count} message{} to {destination}.\n\n📤 Action: Copies will be sent to the other namespace\n📄 Result: Messages will REMAIN here",
+                if count == 1 { "" } else { "s" }
+            )
+        };
+        if delete_source {
+            message.push_str(&self.non_target_warning("Selected messages may be"));
+        }
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowConfirmation {
+            title: if delete_source {
+                "Move to Another Namespace".to_string()
+            } else {
+                "Copy to Another Namespace".to_string()
+            },
+            message,
+            on_confirm: Box::new(Msg::MessageActivity(MessageActivityMsg::TransferMessages(
+                MessageTransfer {
+                    message_ids: pending.message_ids,
+                    target_namespace,
+                    target_queue,
+                    delete_source,
+                },
+            ))),
+        }))
+    }
+}
+
+/// How the namespace entered for a copy or move is reached
+enum TransferTarget {
+    /// Reached with a connection string
+    Namespace(TargetNamespace),
+    /// Reached with the connection string fetched after signing in to Azure AD
+    AzureAd(Box<AzureAdTarget>),
+}
+
+/// Profile of a target namespace that authenticates with Azure AD
+struct AzureAdTarget {
+    label: String,
+    /// Azure AD settings of the profile, with its client secret decrypted
+    auth: AzureAdAuthConfig,
+    subscription_id: String,
+    resource_group: String,
+    namespace: String,
+}
+
+/// Resolves a profile name or a connection string to the namespace it points to
+fn resolve_target(target: &str) -> Result<TransferTarget, String> {
+    if target.contains('=') {
+        let namespace =
+            ConnectionStringParser::extract_namespace(target).map_err(|e| e.to_string())?;
+        return Ok(TransferTarget::Namespace(TargetNamespace::new(
+            namespace, target,
+        )));
+    }
+
+    config::validate_profile_name(target)?;
+    let profile = config::load_profile_config(target)?;
+    profile_target(
+        target,
+        &profile,
+        config::azure::get_master_password().as_deref(),
+    )
+}
+
+/// Resolves a profile with its own auth method, decrypting its secrets with
+/// the profile's own salts
+fn profile_target(
+    label: &str,
+    profile: &ProfileConfig,
+    password: Option<&str>,
+) -> Result<TransferTarget, String> {
+    let config = &profile.config;
+    let wrong_password = |e: &dyn std::fmt::Display| {
+        format!(
+            "Failed to decrypt the secrets of profile '{label}': {e}. The profile must use the same master password as the current one."
+        )
+    };
+
+    if AuthUtils::is_connection_string_auth(config) {
+        let password = password.ok_or_else(|| "Master password not set".to_string())?;
+        let connection_string = config
+            .servicebus()
+            .decrypt_connection_string(password)
+            .map_err(|e| wrong_password(&e))?
+            .ok_or_else(|| format!("Profile '{label}' has no connection string"))?;
+        return Ok(TransferTarget::Namespace(TargetNamespace::new(
+            label,
+            connection_string,
+        )));
+    }
+
+    // The fields themselves: the getters fall back to the current session's environment
+    let azure_ad = config.azure_ad();
+    let (Some(subscription_id), Some(resource_group), Some(namespace)) = (
+        azure_ad.subscription_id.as_deref(),
+        azure_ad.resource_group.as_deref(),
+        azure_ad.namespace.as_deref(),
+    ) else {
+        return Err(format!(
+            "Profile '{label}' uses {} but does not name its subscription, resource group and namespace; open it in quetty once to discover them",
+            AuthUtils::auth_method_description(config)
+        ));
+    };
+
+    let mut auth = AzureAdAuthConfig {
+        auth_method: azure_ad.auth_method.clone(),
+        tenant_id: azure_ad.tenant_id.clone(),
+        client_id: azure_ad.client_id.clone(),
+        client_secret: azure_ad.client_secret.clone(),
+        encrypted_client_secret: profile
+            .env_var(AZURE_AD_ENCRYPTED_CLIENT_SECRET)
+            .map(str::to_string),
+        client_secret_encryption_salt: profile
+            .env_var(AZURE_AD_CLIENT_SECRET_ENCRYPTION_SALT)
+            .map(str::to_string),
+        subscription_id: Some(subscription_id.to_string()),
+        resource_group: Some(resource_group.to_string()),
+        namespace: Some(namespace.to_string()),
+        ..AzureAdAuthConfig::default()
+    };
+    if AuthUtils::is_client_secret_auth(config) {
+        auth.client_secret = auth
+            .get_client_secret(password)
+            .map_err(|e| wrong_password(&e))?;
+    }
+    auth.encrypted_client_secret = None;
+    auth.client_secret_encryption_salt = None;
+
+    Ok(TransferTarget::AzureAd(Box::new(AzureAdTarget {
+        label: label.to_string(),
+        subscription_id: subscription_id.to_string(),
+        resource_group: resource_group.to_string(),
+        namespace: namespace.to_string(),
+        auth,
+    })))
+}
+
+/// Signs in to Azure AD as the target's profile and fetches the connection
+/// string of its namespace through the management API
+async fn azure_ad_target_namespace(
+    target: AzureAdTarget,
+    http_client: reqwest::Client,
+    tx_to_main: &Sender<Msg>,
+) -> Result<TargetNamespace, AppError> {
+    let provider = AzureAdProvider::new(target.auth, http_client.clone())?;
+    let token = if provider.flow_type() == AUTH_METHOD_DEVICE_CODE {
+        let device_info = provider.start_device_code_flow().await?;
+        let _ = tx_to_main.send(Msg::AuthActivity(AuthActivityMsg::ShowDeviceCode {
+            user_code: device_info.user_code.clone(),
+            verification_url: device_info.verification_uri.clone(),
+            message: device_info.message.clone(),
+            expires_in: device_info.expires_in,
+        }));
+        provider.poll_device_code_token(&device_info).await?
+    } else {
+        provider.authenticate().await?
+    };
+
+    let connection_string = AzureManagementClient::new(http_client)
+        .get_namespace_connection_string(
+            &token.token,
+            &target.subscription_id,
+            &target.resource_group,
+            &target.namespace,
+        )
+        .await?;
+    Ok(TargetNamespace::new(target.label, connection_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use quetty_server::encryption::ClientSecretEncryption;
+
+    const PASSWORD: &str = "staging-password";
+
+    /// Writes a profile with the given `config.toml` and `.env` and loads it
+    fn profile(config_toml: &str, env: &str) -> ProfileConfig {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("config.toml"), config_toml).unwrap();
+        std::fs::write(dir.path().join(".env"), env).unwrap();
+        config::load_profile_config_from_dir(dir.path()).unwrap()
+    }
+
+    fn azure_ad_env(extra: &str) -> String {
+        format!(
+            "AZURE_AD__TENANT_ID=tenant\nAZURE_AD__CLIENT_ID=client\nAZURE_AD__SUBSCRIPTION_ID=subscription\nAZURE_AD__RESOURCE_GROUP=staging-rg\nAZURE_AD__NAMESPACE=contoso-staging\n{extra}"
+        )
+    }
+
+    #[test]
+    fn test_resolve_target_from_connection_string() {
+        let Ok(TransferTarget::Namespace(target)) = resolve_target(
+            "Endpoint=sb://contoso-staging.servicebus.windows.net/;SharedAccessKeyName=send;SharedAccessKey=key",
+        ) else {
+            panic!("expected a namespace reached with its connection string");
+        };
+        assert_eq!(target.label, "contoso-staging");
+        assert_eq!(
+            target.host(),
+            Some("contoso-staging.servicebus.windows.net")
+        );
+
+        assert!(resolve_target("SharedAccessKeyName=send;SharedAccessKey=key").is_err());
+    }
+
+    #[test]
+    fn test_resolve_target_rejects_invalid_profile_names() {
+        assert!(resolve_target("../prod").is_err());
+        assert!(resolve_target("staging profile").is_err());
+    }
+
+    #[test]
+    fn test_profile_target_of_an_azure_ad_profile() {
+        let encryption = ClientSecretEncryption::new();
+        let encrypted = encryption
+            .encrypt_client_secret("staging-secret", PASSWORD)
+            .unwrap();
+        let staging = profile(
+            "[azure_ad]\nauth_method = \"client_secret\"\n",
+            &azure_ad_env(&format!(
+                "AZURE_AD__ENCRYPTED_CLIENT_SECRET={encrypted}\nAZURE_AD__CLIENT_SECRET_ENCRYPTION_SALT={}\n",
+                encryption.salt_base64()
+            )),
+        );
+
+        let Ok(TransferTarget::AzureAd(target)) =
+            profile_target("staging", &staging, Some(PASSWORD))
+        else {
+            panic!("expected a target reached through Azure AD");
+        };
+        assert_eq!(target.label, "staging");
+        assert_eq!(target.subscription_id, "subscription");
+        assert_eq!(target.resource_group, "staging-rg");
+        assert_eq!(target.namespace, "contoso-staging");
+        assert_eq!(target.auth.auth_method, "client_secret");
+        assert_eq!(target.auth.tenant_id.as_deref(), Some("tenant"));
+        assert_eq!(target.auth.client_id.as_deref(), Some("client"));
+        assert_eq!(target.auth.client_secret.as_deref(), Some("staging-secret"));
+        assert!(target.auth.encrypted_client_secret.is_none());
+
+        assert!(profile_target("staging", &staging, Some("another-password")).is_err());
+        assert!(profile_target("staging", &staging, None).is_err());
+    }
+
+    #[test]
+    fn test_profile_target_of_a_device_code_profile() {
+        let staging = profile(
+            "[azure_ad]\nauth_method = \"device_code\"\n",
+            &azure_ad_env(""),
+        );
+        let Ok(TransferTarget::AzureAd(target)) = profile_target("staging", &staging, None) else {
+            panic!("expected a target reached through Azure AD");
+        };
+        assert_eq!(target.auth.auth_method, "device_code");
+        assert!(target.auth.client_secret.is_none());
+
+        // Without its namespace there is nothing to sign in to
+        let incomplete = profile(
+            "[azure_ad]\nauth_method = \"device_code\"\n",
+            "AZURE_AD__TENANT_ID=tenant\nAZURE_AD__CLIENT_ID=client\n",
+        );
+        assert!(profile_target("staging", &incomplete, None).is_err());
+    }
+}
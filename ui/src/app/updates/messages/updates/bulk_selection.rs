@@ -1,5 +1,5 @@
 use crate::app::model::Model;
-use crate::components::common::{Msg, PopupActivityMsg};
+use crate::components::common::{MessageActivityMsg, Msg, PopupActivityMsg};
 use crate::config;
 use crate::error::AppError;
 use quetty_server::bulk_operations::MessageIdentifier;
@@ -175,7 +175,12 @@ where
             .selection_count();
 
         if selection_count == 0 {
-            return None;
+            // Without selections, Esc leaves the search results instead
+            return self
+                .queue_state()
+                .search
+                .is_some()
+                .then_some(Msg::MessageActivity(MessageActivityMsg::ClearSearch));
         }
 
         self.queue_state_mut().bulk_selection.clear_all();
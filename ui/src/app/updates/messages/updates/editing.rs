@@ -2,10 +2,12 @@ use crate::app::model::AppState;
 use crate::app::model::Model;
 use crate::app::updates::messages::async_operations;
 use crate::components::common::{ComponentId, MessageActivityMsg, Msg, PopupActivityMsg};
+use crate::components::message_details::envelope::{EditedMessage, parse_composed_message};
 use crate::error::AppError;
 use quetty_server::bulk_operations::MessageIdentifier;
 use quetty_server::service_bus_manager::{MessageData, ServiceBusCommand, ServiceBusResponse};
 use std::sync::Arc;
+use time::OffsetDateTime;
 
 use tuirealm::terminal::TerminalAdapter;
 
@@ -103,13 +105,38 @@ where
         Some(Msg::ForceRedraw)
     }
 
+    /// Parse edited content into a message, encoding an edited decoded body
+    /// back into the format of the original body
+    fn compose_edited_message(&self, edited: &EditedMessage) -> Result<MessageData, AppError> {
+        let message = parse_composed_message(&edited.content)?;
+        if edited.encode_with.is_empty() {
+            return Ok(message);
+        }
+
+        let body = self
+            .body_decoders
+            .encode(&edited.encode_with, message.content.as_bytes())
+            .map_err(|e| AppError::Config(format!("Failed to encode the edited body: {e}")))?;
+        log::debug!(
+            "Encoded edited body with {} ({} bytes)",
+            edited.encode_with.join(" → "),
+            body.len()
+        );
+        Ok(message.with_body(body))
+    }
+
     /// Handle sending edited message content as new message
-    pub fn handle_send_edited_message(&self, content: String) -> Option<Msg> {
+    pub fn handle_send_edited_message(&self, edited: EditedMessage) -> Option<Msg> {
         let queue_name = match self.get_current_queue() {
             Ok(name) => name,
             Err(e) => return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(e))),
         };
 
+        let message = match self.compose_edited_message(&edited) {
+            Ok(message) => message,
+            Err(e) => return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(e))),
+        };
+
         let repeat_count = self.queue_manager.queue_state.message_repeat_count;
         log::info!("Sending edited message content to queue: {queue_name} ({repeat_count} times)");
 
@@ -143,28 +170,51 @@ where
                 Box::pin(async move {
                     progress.report_progress("Preparing message for sending...");
 
-                    let result = if repeat_count == 1 {
+                    let outcome = if let Some(enqueue_time) =
+                        message.system_properties.scheduled_enqueue_time
+                    {
+                        progress.report_progress("Scheduling message...");
+                        Self::schedule_messages(
+                            service_bus_manager,
+                            queue_name,
+                            message,
+                            repeat_count,
+                            enqueue_time,
+                        )
+                        .await
+                        .map(|sequence_numbers| {
+                            format!(
+                                "✅ Scheduled {} message{} for {enqueue_time}\n\nSequence number{}: {}",
+                                sequence_numbers.len(),
+                                if sequence_numbers.len() == 1 { "" } else { "s" },
+                                if sequence_numbers.len() == 1 { "" } else { "s" },
+                                format_sequence_numbers(&sequence_numbers)
+                            )
+                        })
+                    } else if repeat_count == 1 {
                         progress.report_progress("Sending message...");
-                        Self::send_single_message(service_bus_manager, queue_name, content).await
+                        Self::send_single_message(service_bus_manager, queue_name, message)
+                            .await
+                            .map(|()| "✅ Message sent successfully!".to_string())
                     } else {
                         progress.report_progress(format!("Sending {repeat_count} messages..."));
                         Self::send_multiple_messages(
                             service_bus_manager,
                             queue_name,
-                            content,
+                            message,
                             repeat_count,
                         )
                         .await
+                        .map(|()| format!("✅ {repeat_count} messages sent successfully!"))
                     };
 
-                    if result.is_ok() {
+                    if outcome.is_ok() {
                         progress.report_progress("Message sent successfully!");
                     }
 
-                    let success_message = if repeat_count == 1 {
-                        "✅ Message sent successfully!".to_string()
-                    } else {
-                        format!("✅ {repeat_count} messages sent successfully!")
+                    let (result, success_message) = match outcome {
+                        Ok(success_message) => (Ok(()), success_message),
+                        Err(e) => (Err(e), String::new()),
                     };
 
                     async_operations::send_completion_messages(
@@ -183,7 +233,7 @@ where
     /// Handle replacing original message with edited content (send new + delete original)
     pub fn handle_replace_edited_message(
         &self,
-        content: String,
+        edited: EditedMessage,
         message_id: MessageIdentifier,
         max_position: usize,
     ) -> Option<Msg> {
@@ -197,23 +247,17 @@ where
                 false // Can't determine, assume it's not at start
             };
 
-        // Show confirmation dialog with delivery count warning if not at the beginning
+        // Show confirmation dialog with a warning if not at the beginning
         let title = "Replace Message".to_string();
         let mut message = "You are about to replace a message in the queue.\n\n📤 Action: Send new message with edited content\n🗑️  Result: Delete original message from queue\n⚠️   Warning: This action CANNOT be undone!".to_string();
 
-        // Add delivery count warning if the message is not at the beginning
+        // Explain the effect on messages in between if the message is not at the beginning
         if !is_message_at_start {
-            message.push_str("\n\n🚨 DELIVERY COUNT WARNING:\n");
-            message
-                .push_str("The message being replaced is not from the beginning of the queue.\n");
-            message
-                .push_str("This operation may increase delivery count of messages in between,\n");
-            message
-                .push_str("potentially moving them to the Dead Letter Queue if count exceeds 9.");
+            message.push_str(&self.non_target_warning("The message being replaced is"));
         }
 
         let on_confirm = Box::new(Msg::MessageActivity(
-            MessageActivityMsg::ReplaceEditedMessageConfirmed(content, message_id, max_position),
+            MessageActivityMsg::ReplaceEditedMessageConfirmed(edited, message_id, max_position),
         ));
 
         Some(Msg::PopupActivity(PopupActivityMsg::ShowConfirmation {
@@ -226,7 +270,7 @@ where
     /// Handle confirmed replace edited message operation
     pub fn handle_replace_edited_message_confirmed(
         &self,
-        content: String,
+        edited: EditedMessage,
         message_id: MessageIdentifier,
         max_position: usize,
     ) -> Option<Msg> {
@@ -237,6 +281,11 @@ where
 
         log::info!("Replacing message {message_id} with edited content in queue: {queue_name}");
 
+        let message = match self.compose_edited_message(&edited) {
+            Ok(message) => message,
+            Err(e) => return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(e))),
+        };
+
         let Some(service_bus_manager) = self.service_bus_manager.clone() else {
             log::warn!("Service bus manager not initialized");
             return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(
@@ -265,7 +314,7 @@ where
                     let result = async {
                         // Step 1: Send new message with edited content
                         progress.report_progress("Sending new message...");
-                        Self::send_single_message(Arc::clone(&service_bus_manager), queue_name.clone(), content)
+                        Self::send_single_message(Arc::clone(&service_bus_manager), queue_name.clone(), message)
                             .await?;
 
                         // Step 2: Delete original message using service bus manager
@@ -332,15 +381,15 @@ where
             tokio::sync::Mutex<quetty_server::service_bus_manager::ServiceBusManager>,
         >,
         queue_name: String,
-        content: String,
+        message: MessageData,
     ) -> Result<(), AppError> {
         log::info!(
-            "Sending message to queue: {} (content: {} bytes)",
+            "Sending message to queue: {} (content: {} bytes, {} application properties)",
             queue_name,
-            content.len()
+            message.content.len(),
+            message.application_properties.len()
         );
 
-        let message = MessageData::new(content);
         let command = ServiceBusCommand::SendMessage {
             queue_name: queue_name.clone(),
             message,
@@ -367,20 +416,56 @@ where
         }
     }
 
+    /// Schedule `count` copies of a message on a queue for `enqueue_time`
+    async fn schedule_messages(
+        service_bus_manager: std::sync::Arc<
+            tokio::sync::Mutex<quetty_server::service_bus_manager::ServiceBusManager>,
+        >,
+        queue_name: String,
+        message: MessageData,
+        count: usize,
+        enqueue_time: OffsetDateTime,
+    ) -> Result<Vec<i64>, AppError> {
+        log::info!("Scheduling message {count} times on queue {queue_name} for {enqueue_time}");
+
+        let command = ServiceBusCommand::ScheduleMessages {
+            queue_name: queue_name.clone(),
+            messages: vec![message; count],
+            enqueue_time,
+        };
+
+        let response = service_bus_manager
+            .lock()
+            .await
+            .execute_command(command)
+            .await;
+
+        match response {
+            ServiceBusResponse::MessagesScheduled {
+                sequence_numbers, ..
+            } => Ok(sequence_numbers),
+            ServiceBusResponse::Error { error } => {
+                log::error!("Failed to schedule messages on queue {queue_name}: {error}");
+                Err(AppError::ServiceBus(error.to_string()))
+            }
+            _ => Err(AppError::ServiceBus(
+                "Unexpected response for schedule messages".to_string(),
+            )),
+        }
+    }
+
     /// Send multiple messages to a queue using the service bus manager
     async fn send_multiple_messages(
         service_bus_manager: std::sync::Arc<
             tokio::sync::Mutex<quetty_server::service_bus_manager::ServiceBusManager>,
         >,
         queue_name: String,
-        content: String,
+        message: MessageData,
         count: usize,
     ) -> Result<(), AppError> {
         log::info!("Sending message {count} times to queue: {queue_name}");
 
-        let messages: Vec<MessageData> = (0..count)
-            .map(|_| MessageData::new(content.clone()))
-            .collect();
+        let messages: Vec<MessageData> = (0..count).map(|_| message.clone()).collect();
         let command = ServiceBusCommand::SendMessages {
             queue_name: queue_name.clone(),
             messages,
@@ -420,3 +505,18 @@ where
         }
     }
 }
+
+/// Format sequence numbers for display, abbreviating long lists
+pub fn format_sequence_numbers(sequence_numbers: &[i64]) -> String {
+    const MAX_SHOWN: usize = 5;
+    let shown = sequence_numbers
+        .iter()
+        .take(MAX_SHOWN)
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    match sequence_numbers.len().checked_sub(MAX_SHOWN) {
+        Some(hidden) if hidden > 0 => format!("{shown} and {hidden} more"),
+        _ => shown,
+    }
+}
@@ -64,6 +64,8 @@ where
 
         // Reset pagination state for new queue
         self.queue_state_mut().message_pagination.reset();
+        self.queue_state_mut().search = None;
+        self.queue_state_mut().follow = None;
 
         // Load messages for the new queue using the current page size
         let page_size = config::get_current_page_size();
@@ -92,6 +94,16 @@ where
 
     /// Handle new messages being loaded
     pub fn handle_new_messages_loaded(&mut self, messages: Vec<MessageModel>) -> Option<Msg> {
+        // Peeks started by follow mode only append, without moving focus or the cursor
+        if self
+            .queue_state_mut()
+            .follow
+            .as_mut()
+            .is_some_and(|follow| std::mem::take(&mut follow.polling))
+        {
+            return self.handle_followed_messages_loaded(messages);
+        }
+
         let message_count = messages.len();
 
         // Clear loading state first
@@ -473,6 +485,9 @@ where
         if let Some(ref mut messages) = self.queue_state_mut().messages {
             messages.retain(|msg| !message_ids.contains(&msg.id));
         }
+        if let Some(ref mut search) = self.queue_state_mut().search {
+            search.matches.retain(|msg| !message_ids.contains(&msg.id));
+        }
     }
 
     /// Clean up bulk selection state by removing specified messages
@@ -830,6 +845,7 @@ where
         successful_count: usize,
         failed_count: usize,
         total_count: usize,
+        still_deferred: Vec<i64>,
     ) -> Option<Msg> {
         // Invalidate and refresh stats cache for current queue since messages were deleted from it
         if let Some(queue_name) = &self.queue_state().current_queue_name {
@@ -856,7 +872,7 @@ where
 
         let not_found_count = total_count.saturating_sub(successful_count + failed_count);
 
-        let success_message = crate::app::bulk_operation_processor::BulkOperationPostProcessor::format_bulk_operation_result_message(
+        let mut success_message = crate::app::bulk_operation_processor::BulkOperationPostProcessor::format_bulk_operation_result_message(
             "delete",
             queue_name,
             successful_count,
@@ -865,6 +881,14 @@ where
             total_count,
             true, // is_delete
         );
+        if let Some(notice) =
+            crate::app::bulk_operation_processor::BulkOperationPostProcessor::format_still_deferred_notice(
+                &still_deferred,
+            )
+        {
+            success_message.push_str("\n\n");
+            success_message.push_str(&notice);
+        }
 
         Some(Msg::PopupActivity(
             crate::components::common::PopupActivityMsg::ShowSuccess(success_message),
@@ -1000,4 +1024,24 @@ where
         }
         None
     }
+
+    /// Load the open queue's statistics so they are recorded in its history.
+    ///
+    /// The cache is left alone, so the shown counts stay until the new ones arrive.
+    pub fn handle_sample_queue_statistics(&mut self) -> Option<Msg> {
+        if !config::get_config_or_panic().queue_stats_display_enabled() {
+            return None;
+        }
+        let queue_name = self.queue_state().current_queue_name.clone()?;
+        let base_queue_name = queue_name
+            .strip_suffix("/$deadletterqueue")
+            .unwrap_or(&queue_name)
+            .to_string();
+
+        log::debug!("Sampling queue statistics for: {base_queue_name}");
+        if let Err(e) = self.load_queue_statistics_from_api(&base_queue_name) {
+            log::error!("Failed to sample queue statistics: {e}");
+        }
+        None
+    }
 }
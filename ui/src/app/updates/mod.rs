@@ -1,10 +1,14 @@
+pub mod alerts;
 pub mod auth;
 pub mod azure_discovery;
 pub mod config;
+pub mod dashboard;
 pub mod help;
 pub mod loading;
 pub mod messages;
 pub mod namespace;
 pub mod popup;
 pub mod queue;
+pub mod queue_admin;
+pub mod subscription_rules;
 pub mod theme;
@@ -1,6 +1,7 @@
 use crate::app::model::Model;
 use crate::components::common::{ComponentId, Msg, PopupActivityMsg};
 use crate::error::AppError;
+use quetty_server::service_bus_manager::QueueDetails;
 use tuirealm::terminal::TerminalAdapter;
 
 impl<T> Model<T>
@@ -14,6 +15,8 @@ where
             PopupActivityMsg::ShowWarning(message) => self.handle_show_warning(message),
             PopupActivityMsg::ShowSuccess(message) => self.handle_show_success(message),
             PopupActivityMsg::CloseSuccess => self.handle_close_success(),
+            PopupActivityMsg::ShowQueueInfo(details) => self.handle_show_queue_info(*details),
+            PopupActivityMsg::CloseQueueInfo => self.handle_close_queue_info(),
             PopupActivityMsg::ShowConfirmation {
                 title,
                 message,
@@ -29,6 +32,13 @@ where
                 max_value,
             } => self.handle_show_number_input(title, message, min_value, max_value),
             PopupActivityMsg::NumberInputResult(value) => self.handle_number_input_result(value),
+            PopupActivityMsg::ShowTextInput {
+                title,
+                message,
+                initial_value,
+                on_submit,
+            } => self.handle_show_text_input(title, message, initial_value, on_submit),
+            PopupActivityMsg::TextInputResult(result) => self.handle_text_input_result(result),
             PopupActivityMsg::ShowPageSizePopup => self.handle_show_page_size_popup(),
             PopupActivityMsg::PageSizeResult(size) => self.handle_page_size_result(size),
             PopupActivityMsg::ClosePageSize => self.handle_close_page_size(),
@@ -85,12 +95,30 @@ where
 
     fn handle_close_success(&mut self) -> Option<Msg> {
         // Only try to unmount if it's actually mounted
-        if self.app.mounted(&ComponentId::SuccessPopup) {
-            if let Err(e) = self.unmount_success_popup() {
-                self.error_reporter
-                    .report_mount_error("SuccessPopup", "unmount", e);
-                return None;
-            }
+        if self.app.mounted(&ComponentId::SuccessPopup)
+            && let Err(e) = self.unmount_success_popup()
+        {
+            self.error_reporter
+                .report_mount_error("SuccessPopup", "unmount", e);
+            return None;
+        }
+        None
+    }
+
+    fn handle_show_queue_info(&mut self, details: QueueDetails) -> Option<Msg> {
+        if let Err(e) = self.mount_queue_info_popup(details) {
+            self.error_reporter
+                .report_mount_error("QueueInfoPopup", "mount", e);
+        }
+        None
+    }
+
+    fn handle_close_queue_info(&mut self) -> Option<Msg> {
+        if self.app.mounted(&ComponentId::QueueInfoPopup)
+            && let Err(e) = self.unmount_queue_info_popup()
+        {
+            self.error_reporter
+                .report_mount_error("QueueInfoPopup", "unmount", e);
         }
         None
     }
@@ -169,6 +197,41 @@ where
         ))
     }
 
+    fn handle_show_text_input(
+        &mut self,
+        title: String,
+        message: String,
+        initial_value: String,
+        on_submit: fn(String) -> Msg,
+    ) -> Option<Msg> {
+        if let Err(e) = self.mount_text_input_popup(title, message, initial_value, on_submit) {
+            self.error_reporter
+                .report_mount_error("TextInputPopup", "mount", e);
+            return None;
+        }
+
+        // Typed characters must not trigger global shortcuts
+        self.set_editing_message(true);
+        if let Err(e) = self.update_global_key_watcher_editing_state() {
+            self.error_reporter.report_key_watcher_error(e);
+        }
+        None
+    }
+
+    fn handle_text_input_result(&mut self, result: Option<Box<Msg>>) -> Option<Msg> {
+        if let Err(e) = self.unmount_text_input_popup() {
+            self.error_reporter
+                .report_mount_error("TextInputPopup", "unmount", e);
+        }
+
+        self.set_editing_message(false);
+        if let Err(e) = self.update_global_key_watcher_editing_state() {
+            self.error_reporter.report_key_watcher_error(e);
+        }
+
+        result.map(|msg| *msg)
+    }
+
     fn handle_show_page_size_popup(&mut self) -> Option<Msg> {
         if let Err(e) = self.mount_page_size_popup() {
             self.error_reporter
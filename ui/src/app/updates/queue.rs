@@ -1,5 +1,5 @@
 use crate::app::model::{AppState, Model};
-use crate::components::common::{Msg, QueueActivityMsg};
+use crate::components::common::{ComponentId, Msg, PopupActivityMsg, QueueActivityMsg};
 use crate::constants::env_vars::*;
 use crate::error::AppError;
 use quetty_server::service_bus_manager::{ServiceBusCommand, ServiceBusResponse};
@@ -77,6 +77,7 @@ where
         match msg {
             QueueActivityMsg::QueueSelected(queue) => {
                 log::info!("Queue selected: '{queue}' - initializing queue and loading statistics");
+                self.unmount_topic_picker();
                 self.queue_state_mut().set_selected_queue(queue);
                 self.new_consumer_for_queue();
 
@@ -152,6 +153,7 @@ where
                 let qs = self.queue_state_mut();
                 qs.pending_queue = None;
                 qs.current_queue_name = None;
+                qs.current_session_id = None;
                 qs.messages = None;
                 qs.message_pagination.reset();
                 qs.bulk_selection.clear_all();
@@ -221,31 +223,7 @@ where
             }
             QueueActivityMsg::ExitQueueFinalized => {
                 log::info!("Finalizing queue exit - returning to queue selection");
-
-                // Check if we're in discovery mode
-                if let (
-                    Some(subscription_id),
-                    Some(resource_group),
-                    Some(namespace),
-                    Some(auth_service),
-                ) = (
-                    &self.state_manager.selected_subscription,
-                    &self.state_manager.selected_resource_group,
-                    &self.state_manager.selected_namespace,
-                    &self.auth_service,
-                ) {
-                    log::info!("In discovery mode - loading queues with discovered values");
-                    self.queue_manager.load_queues_with_discovery(
-                        subscription_id.clone(),
-                        resource_group.clone(),
-                        namespace.clone(),
-                        auth_service.clone(),
-                        self.http_client.clone(),
-                    );
-                } else {
-                    log::info!("Using regular queue loading");
-                    self.load_queues();
-                }
+                self.reload_queue_list();
                 None
             }
             QueueActivityMsg::QueueSwitchCancelled => {
@@ -317,6 +295,209 @@ where
                 self.set_app_state(AppState::QueuePicker);
                 None
             }
+            QueueActivityMsg::TopicPickerRequested => {
+                // Prefer values picked during discovery over the saved configuration
+                let mut azure_ad_config = crate::config::get_config_or_panic().azure_ad().clone();
+                if let (Some(subscription_id), Some(resource_group), Some(namespace)) = (
+                    &self.state_manager.selected_subscription,
+                    &self.state_manager.selected_resource_group,
+                    &self.state_manager.selected_namespace,
+                ) {
+                    azure_ad_config.subscription_id = Some(subscription_id.clone());
+                    azure_ad_config.resource_group = Some(resource_group.clone());
+                    azure_ad_config.namespace = Some(namespace.clone());
+                }
+
+                self.queue_manager.load_topics(azure_ad_config);
+                None
+            }
+            QueueActivityMsg::TopicsLoaded(topics) => {
+                if let Err(e) = self.remount_topic_picker(topics) {
+                    self.error_reporter
+                        .report_simple(e, "QueueHandler", "topics_loaded");
+                }
+                None
+            }
+            QueueActivityMsg::TopicPickerClosed => {
+                self.unmount_topic_picker();
+                self.set_app_state(AppState::QueuePicker);
+                if let Err(e) = self.app.active(&ComponentId::QueuePicker) {
+                    self.error_reporter
+                        .report_activation_error("QueuePicker", &e);
+                }
+                None
+            }
+            QueueActivityMsg::SessionRequired(queue) => {
+                self.unmount_topic_picker();
+                self.queue_manager.load_sessions(queue);
+                None
+            }
+            QueueActivityMsg::SessionsLoaded(sessions) => {
+                let queue_name = self
+                    .queue_state()
+                    .current_queue_name
+                    .clone()
+                    .unwrap_or_default();
+                if let Err(e) = self.remount_session_picker(queue_name, sessions) {
+                    self.error_reporter
+                        .report_simple(e, "QueueHandler", "sessions_loaded");
+                }
+                None
+            }
+            QueueActivityMsg::SessionSelected(session_id) => {
+                log::info!("Session selected: '{session_id}'");
+                if self.state_manager.is_editing_message {
+                    self.set_editing_message(false);
+                    if let Err(e) = self.update_global_key_watcher_editing_state() {
+                        self.error_reporter.report_key_watcher_error(e);
+                    }
+                }
+                self.unmount_session_picker();
+
+                let qs = self.queue_state_mut();
+                qs.current_session_id = Some(session_id);
+                qs.pending_queue = qs.current_queue_name.clone();
+                qs.messages = None;
+                qs.message_pagination.reset();
+                self.new_consumer_for_queue();
+                None
+            }
+            QueueActivityMsg::SessionPickerClosed => {
+                self.unmount_session_picker();
+                let qs = self.queue_state_mut();
+                qs.pending_queue = None;
+                qs.current_queue_name = None;
+                qs.current_session_id = None;
+                self.set_app_state(AppState::QueuePicker);
+                if let Err(e) = self.app.active(&ComponentId::QueuePicker) {
+                    self.error_reporter
+                        .report_activation_error("QueuePicker", &e);
+                }
+                None
+            }
+            QueueActivityMsg::EditSessionState => {
+                if self.queue_state().current_session_id.is_none() {
+                    return Some(Msg::ShowError(
+                        "❌ Session state is only available for queues opened with a session."
+                            .to_string(),
+                    ));
+                }
+                self.queue_manager.load_session_state();
+                None
+            }
+            QueueActivityMsg::SessionStateLoaded { session_id, state } => {
+                let (initial_value, note) = match String::from_utf8(state) {
+                    Ok(text) => (text, ""),
+                    Err(e) => (
+                        String::from_utf8_lossy(e.as_bytes()).into_owned(),
+                        "\n⚠ Current state is not valid UTF-8 and is shown lossily.",
+                    ),
+                };
+                Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+                    title: "Session State".to_string(),
+                    message: format!(
+                        "Edit the state of session '{session_id}'.\nThe text is stored as UTF-8 bytes; submit an empty value to clear it.{note}"
+                    ),
+                    initial_value,
+                    on_submit: |value| {
+                        Msg::QueueActivity(QueueActivityMsg::SaveSessionState(value))
+                    },
+                }))
+            }
+            QueueActivityMsg::SaveSessionState(state) => {
+                self.queue_manager.save_session_state(state.into_bytes());
+                None
+            }
+            QueueActivityMsg::CreateQueueRequested => self.handle_create_queue_requested(),
+            QueueActivityMsg::CreateQueueNameEntered(queue_name) => {
+                self.handle_create_queue_name_entered(queue_name)
+            }
+            QueueActivityMsg::EditQueueRequested(queue_name) => {
+                self.handle_edit_queue_requested(queue_name)
+            }
+            QueueActivityMsg::QueueSettingsLoaded(details) => {
+                self.handle_queue_settings_loaded(details)
+            }
+            QueueActivityMsg::QueueSettingsEntered(text) => {
+                self.handle_queue_settings_entered(text)
+            }
+            QueueActivityMsg::DeleteQueueRequested(queue_name) => {
+                self.handle_delete_queue_requested(queue_name)
+            }
+            QueueActivityMsg::QueueAdminConfirmed(typed_name) => {
+                self.handle_queue_admin_confirmed(typed_name)
+            }
+            QueueActivityMsg::QueueAdminCompleted(message) => {
+                self.reload_queue_list();
+                Some(Msg::ShowSuccess(message))
+            }
+            QueueActivityMsg::SubscriptionRulesRequested => {
+                self.handle_subscription_rules_requested()
+            }
+            QueueActivityMsg::SubscriptionRulesLoaded(rules) => {
+                self.handle_subscription_rules_loaded(rules)
+            }
+            QueueActivityMsg::SubscriptionRulesClosed => self.handle_subscription_rules_closed(),
+            QueueActivityMsg::TestSubscriptionRule(rule_name) => {
+                self.handle_test_subscription_rule(rule_name)
+            }
+            QueueActivityMsg::AddSubscriptionRule => self.handle_add_subscription_rule(),
+            QueueActivityMsg::SubscriptionRuleNameEntered(name) => {
+                self.handle_subscription_rule_name_entered(name)
+            }
+            QueueActivityMsg::EditSubscriptionRule(rule_name) => {
+                self.handle_edit_subscription_rule(rule_name)
+            }
+            QueueActivityMsg::SubscriptionRuleFilterEntered(text) => {
+                self.handle_subscription_rule_filter_entered(text)
+            }
+            QueueActivityMsg::SubscriptionRuleActionEntered(text) => {
+                self.handle_subscription_rule_action_entered(text)
+            }
+            QueueActivityMsg::DeleteSubscriptionRule(rule_name) => {
+                self.handle_delete_subscription_rule(rule_name)
+            }
+            QueueActivityMsg::DeleteSubscriptionRuleConfirmed(rule_name) => {
+                self.handle_delete_subscription_rule_confirmed(rule_name)
+            }
+            QueueActivityMsg::SubscriptionRuleChanged(message) => {
+                self.handle_subscription_rule_changed(message)
+            }
+            QueueActivityMsg::DashboardRequested => self.handle_dashboard_requested(),
+            QueueActivityMsg::DashboardClosed => self.handle_dashboard_closed(),
+            QueueActivityMsg::DashboardViewChanged(view) => {
+                self.handle_dashboard_view_changed(view)
+            }
+            QueueActivityMsg::DashboardRefreshRequested => {
+                self.handle_dashboard_refresh_requested()
+            }
+            QueueActivityMsg::DashboardQueuesListed {
+                dashboard_id,
+                queues,
+            } => self.handle_dashboard_queues_listed(dashboard_id, queues),
+            QueueActivityMsg::DashboardQueueLoaded {
+                dashboard_id,
+                details,
+            } => self.handle_dashboard_queue_loaded(dashboard_id, details),
+            QueueActivityMsg::DashboardRefreshFailed {
+                dashboard_id,
+                queue_name,
+                error,
+            } => self.handle_dashboard_refresh_failed(dashboard_id, queue_name, error),
+            QueueActivityMsg::DashboardQueueOpened(queue_name) => {
+                self.handle_dashboard_queue_opened(queue_name)
+            }
+            QueueActivityMsg::AlertCheckDue => self.handle_alert_check_due(),
+            QueueActivityMsg::AlertQueueChecked { queue_name, counts } => {
+                self.handle_alert_queue_checked(queue_name, counts)
+            }
+            QueueActivityMsg::AlertCheckFinished => self.handle_alert_check_finished(),
+            QueueActivityMsg::AlertsRequested => self.handle_alerts_requested(),
+            QueueActivityMsg::AlertsClosed => self.handle_alerts_closed(),
+            QueueActivityMsg::AlertSelected(selected) => self.handle_alert_selected(selected),
+            QueueActivityMsg::AlertQueueOpened(queue_name) => {
+                self.handle_alert_queue_opened(queue_name)
+            }
             QueueActivityMsg::ExitQueueConfirmation => {
                 // This message is handled by update_handler to show the confirmation popup
                 // No further action needed here
@@ -325,6 +506,46 @@ where
         }
     }
 
+    /// Reload the queue picker list, with the discovered namespace when in discovery mode
+    fn reload_queue_list(&mut self) {
+        if let (Some(subscription_id), Some(resource_group), Some(namespace), Some(auth_service)) = (
+            &self.state_manager.selected_subscription,
+            &self.state_manager.selected_resource_group,
+            &self.state_manager.selected_namespace,
+            &self.auth_service,
+        ) {
+            log::info!("In discovery mode - loading queues with discovered values");
+            self.queue_manager.load_queues_with_discovery(
+                subscription_id.clone(),
+                resource_group.clone(),
+                namespace.clone(),
+                auth_service.clone(),
+                self.http_client.clone(),
+            );
+        } else {
+            log::info!("Using regular queue loading");
+            self.load_queues();
+        }
+    }
+
+    /// Unmount the topic picker if it is still mounted
+    fn unmount_topic_picker(&mut self) {
+        if self.app.mounted(&ComponentId::TopicPicker)
+            && let Err(e) = self.app.umount(&ComponentId::TopicPicker)
+        {
+            log::warn!("Failed to unmount topic picker: {e}");
+        }
+    }
+
+    /// Unmount the session picker if it is still mounted
+    fn unmount_session_picker(&mut self) {
+        if self.app.mounted(&ComponentId::SessionPicker)
+            && let Err(e) = self.app.umount(&ComponentId::SessionPicker)
+        {
+            log::warn!("Failed to unmount session picker: {e}");
+        }
+    }
+
     /// Load statistics for current queue - check cache first, then API if needed
     fn load_stats_for_current_queue(&mut self) {
         let queue_name = self
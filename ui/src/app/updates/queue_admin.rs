@@ -0,0 +1,574 @@
+use crate::app::model::Model;
+use crate::components::common::{Msg, PopupActivityMsg, QueueActivityMsg};
+use crate::components::message_details::envelope::parse_duration;
+use crate::config;
+use crate::error::AppError;
+use crate::utils::auth::AUTH_METHOD_CONNECTION_STRING;
+use quetty_server::service_bus_manager::{
+    EntityStatus, QueueDetails, QueueSettings, ServiceBusCommand, ServiceBusResponse,
+};
+use std::time::Duration;
+use tuirealm::terminal::TerminalAdapter;
+
+/// Settings prefilled when creating a queue
+const DEFAULT_CREATE_SETTINGS: &str = "max_delivery_count=10 lock_duration=1m ttl=unlimited";
+
+/// Bounds Service Bus accepts for the peek-lock duration
+const MIN_LOCK_DURATION: Duration = Duration::from_secs(5);
+const MAX_LOCK_DURATION: Duration = Duration::from_secs(5 * 60);
+
+/// Longest queue name the management API accepts
+const MAX_QUEUE_NAME_LENGTH: usize = 260;
+
+/// A queue change being entered; it runs once the queue name is typed to confirm it
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueueAdminOperation {
+    Create {
+        queue_name: String,
+        settings: QueueSettings,
+    },
+    Update {
+        queue_name: String,
+        settings: QueueSettings,
+    },
+    Delete {
+        queue_name: String,
+    },
+}
+
+impl QueueAdminOperation {
+    /// Queue the operation applies to, which also has to be typed to confirm it
+    pub fn queue_name(&self) -> &str {
+        match self {
+            Self::Create { queue_name, .. }
+            | Self::Update { queue_name, .. }
+            | Self::Delete { queue_name } => queue_name,
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Self::Create { .. } => "Create Queue",
+            Self::Update { .. } => "Edit Queue",
+            Self::Delete { .. } => "Delete Queue",
+        }
+    }
+
+    /// What confirming the operation will do
+    fn describe(&self) -> String {
+        match self {
+            Self::Create { queue_name, .. } => format!("Create queue '{queue_name}'."),
+            Self::Update { queue_name, .. } => {
+                format!("Change the settings of queue '{queue_name}'.")
+            }
+            Self::Delete { queue_name } => format!(
+                "⚠ Delete queue '{queue_name}' together with all of its messages. This cannot be undone."
+            ),
+        }
+    }
+}
+
+impl<T> Model<T>
+where
+    T: TerminalAdapter,
+{
+    /// Ask for the name of a queue to create
+    pub fn handle_create_queue_requested(&mut self) -> Option<Msg> {
+        if let Err(e) = management_api_available("Managing queues") {
+            return Some(Msg::ShowError(e));
+        }
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Create Queue".to_string(),
+            message: "Name of the new queue".to_string(),
+            initial_value: String::new(),
+            on_submit: |value| Msg::QueueActivity(QueueActivityMsg::CreateQueueNameEntered(value)),
+        }))
+    }
+
+    /// Check the entered name and ask for the settings of the new queue
+    pub fn handle_create_queue_name_entered(&mut self, queue_name: String) -> Option<Msg> {
+        let queue_name = queue_name.trim();
+        if queue_name.is_empty() {
+            return None;
+        }
+        if let Err(e) = validate_queue_name(queue_name) {
+            return Some(Msg::ShowError(format!("❌ {e}")));
+        }
+
+        self.queue_state_mut().pending_queue_admin = Some(QueueAdminOperation::Create {
+            queue_name: queue_name.to_string(),
+            settings: QueueSettings::default(),
+        });
+        Some(settings_input(
+            "Create Queue",
+            format!("Settings of the new queue '{queue_name}'"),
+            DEFAULT_CREATE_SETTINGS.to_string(),
+        ))
+    }
+
+    /// Load the current settings of a queue, to prefill them for editing
+    pub fn handle_edit_queue_requested(&mut self, queue_name: String) -> Option<Msg> {
+        if let Err(e) = management_api_available("Managing queues") {
+            return Some(Msg::ShowError(e));
+        }
+        let Some(service_bus_manager) = self.service_bus_manager.clone() else {
+            return Some(Msg::ShowError(
+                "❌ Not connected to a namespace.".to_string(),
+            ));
+        };
+
+        let queue_name = base_queue_name(&queue_name).to_string();
+        self.queue_state_mut().pending_queue_admin = Some(QueueAdminOperation::Update {
+            queue_name: queue_name.clone(),
+            settings: QueueSettings::default(),
+        });
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+
+        self.task_manager
+            .execute(format!("Loading settings of {queue_name}..."), async move {
+                let command = ServiceBusCommand::GetQueueDetails {
+                    queue_name: queue_name.clone(),
+                };
+                let response = service_bus_manager
+                    .lock()
+                    .await
+                    .execute_command(command)
+                    .await;
+
+                match response {
+                    ServiceBusResponse::QueueDetails { details } => {
+                        tx_to_main
+                            .send(Msg::QueueActivity(QueueActivityMsg::QueueSettingsLoaded(
+                                details,
+                            )))
+                            .map_err(|e| AppError::Component(e.to_string()))?;
+                        Ok(())
+                    }
+                    ServiceBusResponse::Error { error } => {
+                        log::warn!("Failed to load settings of {queue_name}: {error}");
+                        Err(AppError::from(error))
+                    }
+                    _ => Err(AppError::ServiceBus(
+                        "Unexpected response for queue settings".to_string(),
+                    )),
+                }
+            });
+        None
+    }
+
+    /// Show the loaded settings of the queue being edited
+    pub fn handle_queue_settings_loaded(&mut self, details: Box<QueueDetails>) -> Option<Msg> {
+        let Some(QueueAdminOperation::Update { queue_name, .. }) =
+            &self.queue_state().pending_queue_admin
+        else {
+            log::debug!(
+                "Ignoring settings of {}: no edit pending",
+                details.entity_path
+            );
+            return None;
+        };
+
+        Some(settings_input(
+            "Edit Queue",
+            format!(
+                "Settings of queue '{queue_name}'. Remove a setting to keep it as is; an empty forward target stops forwarding."
+            ),
+            format_queue_settings(&details),
+        ))
+    }
+
+    /// Parse the entered settings and ask to confirm the create or edit
+    pub fn handle_queue_settings_entered(&mut self, text: String) -> Option<Msg> {
+        let mut operation = self.queue_state_mut().pending_queue_admin.take()?;
+        let parsed = match parse_queue_settings(&text) {
+            Ok(parsed) => parsed,
+            Err(e) => return Some(Msg::ShowError(format!("❌ {e}"))),
+        };
+
+        match &mut operation {
+            QueueAdminOperation::Create { settings, .. } => *settings = parsed,
+            QueueAdminOperation::Update { settings, .. } => {
+                if parsed.is_empty() {
+                    return Some(Msg::ShowError(
+                        "❌ No settings entered, nothing was changed.".to_string(),
+                    ));
+                }
+                *settings = parsed;
+            }
+            QueueAdminOperation::Delete { .. } => {
+                log::warn!("Queue settings entered for a pending delete");
+                return None;
+            }
+        }
+
+        Some(self.confirm_queue_admin(operation))
+    }
+
+    /// Ask to confirm deleting a queue
+    pub fn handle_delete_queue_requested(&mut self, queue_name: String) -> Option<Msg> {
+        if let Err(e) = management_api_available("Managing queues") {
+            return Some(Msg::ShowError(e));
+        }
+
+        let queue_name = base_queue_name(&queue_name).to_string();
+        let is_open = self
+            .queue_state()
+            .current_queue_name
+            .as_deref()
+            .is_some_and(|current| base_queue_name(current) == queue_name);
+        if is_open {
+            return Some(Msg::ShowError(format!(
+                "❌ Queue '{queue_name}' is open; switch to another queue before deleting it."
+            )));
+        }
+
+        Some(self.confirm_queue_admin(QueueAdminOperation::Delete { queue_name }))
+    }
+
+    /// Run the pending operation if the typed name matches its queue
+    pub fn handle_queue_admin_confirmed(&mut self, typed_name: String) -> Option<Msg> {
+        let operation = self.queue_state_mut().pending_queue_admin.take()?;
+        if typed_name.trim() != operation.queue_name() {
+            return Some(Msg::ShowError(format!(
+                "❌ '{}' does not match the queue name '{}', nothing was changed.",
+                typed_name.trim(),
+                operation.queue_name()
+            )));
+        }
+        let Some(service_bus_manager) = self.service_bus_manager.clone() else {
+            return Some(Msg::ShowError(
+                "❌ Not connected to a namespace.".to_string(),
+            ));
+        };
+
+        let queue_name = operation.queue_name().to_string();
+        let (loading_message, command) = match operation {
+            QueueAdminOperation::Create {
+                queue_name,
+                settings,
+            } => (
+                format!("Creating queue {queue_name}..."),
+                ServiceBusCommand::CreateQueue {
+                    queue_name,
+                    settings,
+                },
+            ),
+            QueueAdminOperation::Update {
+                queue_name,
+                settings,
+            } => (
+                format!("Updating queue {queue_name}..."),
+                ServiceBusCommand::UpdateQueue {
+                    queue_name,
+                    settings,
+                },
+            ),
+            QueueAdminOperation::Delete { queue_name } => (
+                format!("Deleting queue {queue_name}..."),
+                ServiceBusCommand::DeleteQueue { queue_name },
+            ),
+        };
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+
+        self.task_manager.execute(loading_message, async move {
+            let response = service_bus_manager
+                .lock()
+                .await
+                .execute_command(command)
+                .await;
+
+            let message = match response {
+                ServiceBusResponse::QueueCreated { .. } => {
+                    format!("✅ Queue '{queue_name}' created.")
+                }
+                ServiceBusResponse::QueueUpdated { .. } => {
+                    format!("✅ Settings of queue '{queue_name}' updated.")
+                }
+                ServiceBusResponse::QueueDeleted { .. } => {
+                    format!("✅ Queue '{queue_name}' deleted.")
+                }
+                ServiceBusResponse::Error { error } => {
+                    log::warn!("Queue operation on {queue_name} failed: {error}");
+                    return Err(AppError::from(error));
+                }
+                _ => {
+                    return Err(AppError::ServiceBus(
+                        "Unexpected response for queue operation".to_string(),
+                    ));
+                }
+            };
+
+            tx_to_main
+                .send(Msg::QueueActivity(QueueActivityMsg::QueueAdminCompleted(
+                    message,
+                )))
+                .map_err(|e| AppError::Component(e.to_string()))?;
+            Ok(())
+        });
+        None
+    }
+
+    /// Remember the operation and ask for the queue name to confirm it
+    fn confirm_queue_admin(&mut self, operation: QueueAdminOperation) -> Msg {
+        let title = operation.title().to_string();
+        let message = format!("{}\nType the queue name to confirm:", operation.describe());
+        self.queue_state_mut().pending_queue_admin = Some(operation);
+
+        Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title,
+            message,
+            initial_value: String::new(),
+            on_submit: |value| Msg::QueueActivity(QueueActivityMsg::QueueAdminConfirmed(value)),
+        })
+    }
+}
+
+/// Administration goes through the management API, which needs Azure AD.
+///
+/// `action` names what is being attempted in the error, e.g. "Managing queues".
+pub(crate) fn management_api_available(action: &str) -> Result<(), String> {
+    let config = config::get_config_or_panic();
+    if config.azure_ad().auth_method == AUTH_METHOD_CONNECTION_STRING {
+        return Err(format!(
+            "❌ {action} needs the Azure Management API, which is not available with connection string authentication."
+        ));
+    }
+    if !config.queue_stats_use_management_api() {
+        return Err(format!(
+            "❌ {action} needs the Azure Management API, which is disabled by queue_stats_use_management_api."
+        ));
+    }
+    Ok(())
+}
+
+fn settings_input(title: &str, message: String, initial_value: String) -> Msg {
+    Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+        title: title.to_string(),
+        message: format!(
+            "{message}\nKeys: max_delivery_count, lock_duration, ttl, forward_to, forward_dlq_to, status"
+        ),
+        initial_value,
+        on_submit: |value| Msg::QueueActivity(QueueActivityMsg::QueueSettingsEntered(value)),
+    })
+}
+
+/// Queue a picker entry or an open DLQ belongs to
+fn base_queue_name(queue_name: &str) -> &str {
+    queue_name
+        .strip_suffix("/$deadletterqueue")
+        .or_else(|| queue_name.strip_suffix("/$deadletter"))
+        .unwrap_or(queue_name)
+}
+
+/// Check a name against the Service Bus naming rules for queues
+pub fn validate_queue_name(queue_name: &str) -> Result<(), String> {
+    if queue_name.is_empty() || queue_name.len() > MAX_QUEUE_NAME_LENGTH {
+        return Err(format!(
+            "Queue names must be 1 to {MAX_QUEUE_NAME_LENGTH} characters long."
+        ));
+    }
+    if let Some(invalid) = queue_name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '/')))
+    {
+        return Err(format!(
+            "'{invalid}' is not allowed in queue names; use letters, digits, '.', '-', '_' and '/'."
+        ));
+    }
+    let starts_and_ends_alphanumeric = queue_name
+        .chars()
+        .next()
+        .zip(queue_name.chars().last())
+        .is_some_and(|(first, last)| first.is_ascii_alphanumeric() && last.is_ascii_alphanumeric());
+    if !starts_and_ends_alphanumeric {
+        return Err("Queue names must start and end with a letter or digit.".to_string());
+    }
+    Ok(())
+}
+
+/// Parse settings entered as `key=value` pairs separated by spaces or commas
+pub fn parse_queue_settings(text: &str) -> Result<QueueSettings, String> {
+    let mut settings = QueueSettings::default();
+
+    for pair in text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|pair| !pair.is_empty())
+    {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("Expected key=value, got '{pair}'."))?;
+        let duration = |value: &str| {
+            if value.eq_ignore_ascii_case("unlimited") {
+                Ok(Duration::MAX)
+            } else {
+                parse_duration(value).map_err(|e| format!("Invalid {key} '{value}': {e}."))
+            }
+        };
+
+        match key.to_ascii_lowercase().as_str() {
+            "max_delivery_count" => {
+                let max = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|max| *max >= 1)
+                    .ok_or_else(|| format!("Invalid {key} '{value}': expected at least 1."))?;
+                settings.max_delivery_count = Some(max);
+            }
+            "lock_duration" => {
+                let lock = duration(value)?;
+                if !(MIN_LOCK_DURATION..=MAX_LOCK_DURATION).contains(&lock) {
+                    return Err(format!(
+                        "Invalid {key} '{value}': must be between 5s and 5m."
+                    ));
+                }
+                settings.lock_duration = Some(lock);
+            }
+            "ttl" => {
+                let ttl = duration(value)?;
+                if ttl.is_zero() {
+                    return Err(format!("Invalid {key} '{value}': must be longer than 0s."));
+                }
+                settings.default_message_time_to_live = Some(ttl);
+            }
+            "forward_to" => settings.forward_to = Some(value.to_string()),
+            "forward_dlq_to" => {
+                settings.forward_dead_lettered_messages_to = Some(value.to_string());
+            }
+            "status" => settings.status = Some(value.parse::<EntityStatus>()?),
+            _ => {
+                return Err(format!(
+                    "Unknown setting '{key}'; expected max_delivery_count, lock_duration, ttl, forward_to, forward_dlq_to or status."
+                ));
+            }
+        }
+    }
+
+    Ok(settings)
+}
+
+/// Current settings of a queue in the form [`parse_queue_settings`] reads
+pub fn format_queue_settings(details: &QueueDetails) -> String {
+    let mut pairs = Vec::new();
+    if let Some(max) = details.max_delivery_count {
+        pairs.push(format!("max_delivery_count={max}"));
+    }
+    if let Some(lock) = details.lock_duration {
+        pairs.push(format!("lock_duration={}", format_setting_duration(lock)));
+    }
+    if let Some(ttl) = details.default_message_time_to_live {
+        pairs.push(format!("ttl={}", format_setting_duration(ttl)));
+    }
+    pairs.push(format!(
+        "forward_to={}",
+        details.forward_to.as_deref().unwrap_or_default()
+    ));
+    pairs.push(format!(
+        "forward_dlq_to={}",
+        details
+            .forward_dead_lettered_messages_to
+            .as_deref()
+            .unwrap_or_default()
+    ));
+    if let Some(status) = details
+        .status
+        .as_deref()
+        .and_then(|status| status.parse::<EntityStatus>().ok())
+    {
+        pairs.push(format!("status={status}"));
+    }
+    pairs.join(" ")
+}
+
+/// Duration in the largest unit that represents it exactly
+fn format_setting_duration(duration: Duration) -> String {
+    if QueueDetails::is_unlimited(duration) {
+        return "unlimited".to_string();
+    }
+    let seconds = duration.as_secs();
+    match seconds {
+        0 => "0s".to_string(),
+        _ if seconds.is_multiple_of(86_400) => format!("{}d", seconds / 86_400),
+        _ if seconds.is_multiple_of(3_600) => format!("{}h", seconds / 3_600),
+        _ if seconds.is_multiple_of(60) => format!("{}m", seconds / 60),
+        _ => format!("{seconds}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_queue_settings() {
+        let settings = parse_queue_settings(
+            "max_delivery_count=5, lock_duration=30s ttl=unlimited forward_to= forward_dlq_to=errors status=send_disabled",
+        )
+        .unwrap();
+        assert_eq!(
+            settings,
+            QueueSettings {
+                max_delivery_count: Some(5),
+                lock_duration: Some(Duration::from_secs(30)),
+                default_message_time_to_live: Some(Duration::MAX),
+                forward_to: Some(String::new()),
+                forward_dead_lettered_messages_to: Some("errors".to_string()),
+                status: Some(EntityStatus::SendDisabled),
+            }
+        );
+        assert!(parse_queue_settings("  ").unwrap().is_empty());
+
+        assert!(parse_queue_settings("max_delivery_count=0").is_err());
+        assert!(parse_queue_settings("lock_duration=10m").is_err());
+        assert!(parse_queue_settings("lock_duration=2s").is_err());
+        assert!(parse_queue_settings("ttl=soon").is_err());
+        assert!(parse_queue_settings("status=paused").is_err());
+        assert!(parse_queue_settings("partitioning=true").is_err());
+        assert!(parse_queue_settings("ttl").is_err());
+    }
+
+    #[test]
+    fn test_format_queue_settings_round_trips() {
+        let details = QueueDetails {
+            entity_path: "orders".to_string(),
+            status: Some("Active".to_string()),
+            max_delivery_count: Some(10),
+            lock_duration: Some(Duration::from_secs(90)),
+            default_message_time_to_live: Some(Duration::from_secs(14 * 86_400)),
+            forward_dead_lettered_messages_to: Some("errors".to_string()),
+            ..QueueDetails::default()
+        };
+
+        let text = format_queue_settings(&details);
+        assert_eq!(
+            text,
+            "max_delivery_count=10 lock_duration=90s ttl=14d forward_to= forward_dlq_to=errors status=Active"
+        );
+        let settings = parse_queue_settings(&text).unwrap();
+        assert_eq!(settings.lock_duration, details.lock_duration);
+        assert_eq!(
+            settings.default_message_time_to_live,
+            details.default_message_time_to_live
+        );
+        assert_eq!(settings.status, Some(EntityStatus::Active));
+
+        assert!(parse_queue_settings(DEFAULT_CREATE_SETTINGS).is_ok());
+    }
+
+    #[test]
+    fn test_validate_queue_name() {
+        assert!(validate_queue_name("orders").is_ok());
+        assert!(validate_queue_name("feature/orders-42_v2.tmp").is_ok());
+        assert!(validate_queue_name("").is_err());
+        assert!(validate_queue_name("-orders").is_err());
+        assert!(validate_queue_name("orders.").is_err());
+        assert!(validate_queue_name("orders queue").is_err());
+        assert!(validate_queue_name(&"a".repeat(MAX_QUEUE_NAME_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_base_queue_name() {
+        assert_eq!(base_queue_name("orders/$deadletterqueue"), "orders");
+        assert_eq!(base_queue_name("orders/$deadletter"), "orders");
+        assert_eq!(base_queue_name("orders"), "orders");
+    }
+}
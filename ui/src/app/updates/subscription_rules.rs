@@ -0,0 +1,527 @@
+use crate::app::model::Model;
+use crate::app::updates::queue_admin::management_api_available;
+use crate::components::common::{ComponentId, Msg, PopupActivityMsg, QueueActivityMsg};
+use crate::components::subscription_rules_popup::{RuleTestMatch, RuleTestResult};
+use crate::error::AppError;
+use quetty_server::model::MessageModel;
+use quetty_server::service_bus_manager::{
+    DEFAULT_RULE_NAME, RuleFilter, ServiceBusCommand, ServiceBusResponse, SubscriptionRule,
+    format_subscription_path, parse_subscription_path,
+};
+use std::str::FromStr;
+use tuirealm::terminal::TerminalAdapter;
+
+/// Filter prefilled when adding a rule; it lets every message through
+const DEFAULT_RULE_FILTER: &str = "sql: 1=1";
+
+/// Longest rule name the management API accepts
+const MAX_RULE_NAME_LENGTH: usize = 50;
+
+/// Rules of the subscription shown in the rules popup
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubscriptionRulesState {
+    pub subscription_path: String,
+    pub rules: Vec<SubscriptionRule>,
+    /// Index of the rule selected when the popup is next shown
+    pub selected: usize,
+    /// Result of the last local test
+    pub test: Option<RuleTestResult>,
+    /// Rule being added or edited while its filter and action are entered
+    pub pending: Option<PendingRule>,
+}
+
+/// A rule being added or edited
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingRule {
+    pub name: String,
+    pub filter: Option<RuleFilter>,
+    /// Action of the rule being edited, prefilled when asking for it
+    pub action: Option<String>,
+}
+
+impl<T> Model<T>
+where
+    T: TerminalAdapter,
+{
+    /// Open the rules popup for the current subscription and load its rules
+    pub fn handle_subscription_rules_requested(&mut self) -> Option<Msg> {
+        let Some((topic, subscription)) = self
+            .queue_state()
+            .current_queue_name
+            .as_deref()
+            .and_then(parse_subscription_path)
+        else {
+            return Some(Msg::ShowError(
+                "❌ Rules belong to topic subscriptions; open a subscription to see its rules."
+                    .to_string(),
+            ));
+        };
+        if let Err(e) = management_api_available("Managing subscription rules") {
+            return Some(Msg::ShowError(e));
+        }
+
+        let subscription_path = format_subscription_path(topic, subscription);
+        self.queue_state_mut().subscription_rules = Some(SubscriptionRulesState {
+            subscription_path,
+            rules: Vec::new(),
+            selected: 0,
+            test: None,
+            pending: None,
+        });
+        self.load_subscription_rules()
+    }
+
+    /// Show the listed rules, keeping the selection of a reload
+    pub fn handle_subscription_rules_loaded(
+        &mut self,
+        rules: Vec<SubscriptionRule>,
+    ) -> Option<Msg> {
+        let Some(state) = self.queue_state_mut().subscription_rules.as_mut() else {
+            log::debug!("Ignoring listed rules: the rules popup was closed");
+            return None;
+        };
+        // A test of a rule that changed since no longer applies
+        if let Some(test) = &state.test
+            && !rules.iter().any(|rule| rule.name == test.rule_name)
+        {
+            state.test = None;
+        }
+        state.rules = rules;
+        self.remount_subscription_rules()
+    }
+
+    /// Close the rules popup and forget the rules
+    pub fn handle_subscription_rules_closed(&mut self) -> Option<Msg> {
+        self.queue_state_mut().subscription_rules = None;
+        if self.app.mounted(&ComponentId::SubscriptionRulesPopup)
+            && let Err(e) = self.unmount_subscription_rules_popup()
+        {
+            self.error_reporter
+                .report_mount_error("SubscriptionRulesPopup", "unmount", e);
+        }
+        None
+    }
+
+    /// Evaluate a rule against the loaded messages and show which would match
+    pub fn handle_test_subscription_rule(&mut self, rule_name: String) -> Option<Msg> {
+        let messages = &self.queue_state().message_pagination.all_loaded_messages;
+        let state = self.queue_state().subscription_rules.as_ref()?;
+        let index = state.rules.iter().position(|rule| rule.name == rule_name)?;
+        let test = test_rule(&state.rules[index], messages);
+
+        let state = self.queue_state_mut().subscription_rules.as_mut()?;
+        state.selected = index;
+        state.test = Some(test);
+        self.remount_subscription_rules()
+    }
+
+    /// Ask for the name of a rule to add
+    pub fn handle_add_subscription_rule(&mut self) -> Option<Msg> {
+        let subscription_path = &self
+            .queue_state()
+            .subscription_rules
+            .as_ref()?
+            .subscription_path;
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title: "Add Rule".to_string(),
+            message: format!("Name of the new rule of '{subscription_path}'"),
+            initial_value: String::new(),
+            on_submit: |value| {
+                Msg::QueueActivity(QueueActivityMsg::SubscriptionRuleNameEntered(value))
+            },
+        }))
+    }
+
+    /// Check the entered name and ask for the filter of the new rule
+    pub fn handle_subscription_rule_name_entered(&mut self, name: String) -> Option<Msg> {
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        let state = self.queue_state_mut().subscription_rules.as_mut()?;
+        if let Err(e) = validate_rule_name(name, &state.rules) {
+            return Some(Msg::ShowError(format!("❌ {e}")));
+        }
+
+        state.pending = Some(PendingRule {
+            name: name.to_string(),
+            filter: None,
+            action: None,
+        });
+        Some(filter_input(
+            "Add Rule",
+            format!("Filter of the new rule '{name}'"),
+            DEFAULT_RULE_FILTER.to_string(),
+        ))
+    }
+
+    /// Ask for the new filter of a rule, prefilled with the current one
+    pub fn handle_edit_subscription_rule(&mut self, rule_name: String) -> Option<Msg> {
+        let state = self.queue_state_mut().subscription_rules.as_mut()?;
+        let index = state.rules.iter().position(|rule| rule.name == rule_name)?;
+        let rule = &state.rules[index];
+        let initial_value = rule.filter.to_string();
+
+        state.pending = Some(PendingRule {
+            name: rule.name.clone(),
+            filter: None,
+            action: rule.action.clone(),
+        });
+        state.selected = index;
+        Some(filter_input(
+            "Edit Rule",
+            format!("Filter of rule '{rule_name}'"),
+            initial_value,
+        ))
+    }
+
+    /// Parse and check the entered filter, then ask for the rule action
+    pub fn handle_subscription_rule_filter_entered(&mut self, text: String) -> Option<Msg> {
+        let state = self.queue_state_mut().subscription_rules.as_mut()?;
+        let mut pending = state.pending.take()?;
+
+        let filter = match RuleFilter::from_str(&text) {
+            Ok(filter) => filter,
+            Err(e) => return Some(Msg::ShowError(format!("❌ {e}"))),
+        };
+        // Service Bus would reject the rule anyway; say where the problem is
+        if let Err(e) = filter.matcher() {
+            return Some(Msg::ShowError(format!("❌ Invalid SQL filter: {e}")));
+        }
+
+        let title = "Rule Action".to_string();
+        let message = format!(
+            "SQL action of rule '{}', e.g. SET priority = 'high'. Leave empty for no action.",
+            pending.name
+        );
+        let initial_value = pending.action.clone().unwrap_or_default();
+        pending.filter = Some(filter);
+        state.pending = Some(pending);
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+            title,
+            message,
+            initial_value,
+            on_submit: |value| {
+                Msg::QueueActivity(QueueActivityMsg::SubscriptionRuleActionEntered(value))
+            },
+        }))
+    }
+
+    /// Save the rule being added or edited
+    pub fn handle_subscription_rule_action_entered(&mut self, text: String) -> Option<Msg> {
+        let state = self.queue_state_mut().subscription_rules.as_mut()?;
+        let pending = state.pending.take()?;
+        let subscription_path = state.subscription_path.clone();
+        let action = text.trim();
+        let rule = SubscriptionRule {
+            name: pending.name,
+            filter: pending.filter?,
+            action: (!action.is_empty()).then(|| action.to_string()),
+        };
+
+        let rule_name = rule.name.clone();
+        self.run_rule_change(
+            format!("Saving rule {rule_name}..."),
+            ServiceBusCommand::SaveSubscriptionRule {
+                subscription_path,
+                rule,
+            },
+            format!("✅ Rule '{rule_name}' saved."),
+        )
+    }
+
+    /// Ask to confirm deleting a rule, warning when messages would stop arriving
+    pub fn handle_delete_subscription_rule(&mut self, rule_name: String) -> Option<Msg> {
+        let state = self.queue_state_mut().subscription_rules.as_mut()?;
+        let index = state.rules.iter().position(|rule| rule.name == rule_name)?;
+        state.selected = index;
+
+        let mut message = format!(
+            "Delete rule '{rule_name}' of '{}'?",
+            state.subscription_path
+        );
+        if state.rules.len() == 1 {
+            message.push_str(
+                "\n⚠ It is the only rule: the subscription will receive no messages until a rule is added.",
+            );
+        } else if rule_name == DEFAULT_RULE_NAME {
+            message.push_str(
+                "\n⚠ The default rule lets every message through; only messages matching the other rules will arrive.",
+            );
+        }
+
+        Some(Msg::PopupActivity(PopupActivityMsg::ShowConfirmation {
+            title: "Delete Rule".to_string(),
+            message,
+            on_confirm: Box::new(Msg::QueueActivity(
+                QueueActivityMsg::DeleteSubscriptionRuleConfirmed(rule_name),
+            )),
+        }))
+    }
+
+    /// Delete a rule once confirmed
+    pub fn handle_delete_subscription_rule_confirmed(&mut self, rule_name: String) -> Option<Msg> {
+        let subscription_path = self
+            .queue_state()
+            .subscription_rules
+            .as_ref()?
+            .subscription_path
+            .clone();
+
+        self.run_rule_change(
+            format!("Deleting rule {rule_name}..."),
+            ServiceBusCommand::DeleteSubscriptionRule {
+                subscription_path,
+                rule_name: rule_name.clone(),
+            },
+            format!("✅ Rule '{rule_name}' deleted."),
+        )
+    }
+
+    /// Reload the rules after a change and report it
+    pub fn handle_subscription_rule_changed(&mut self, message: String) -> Option<Msg> {
+        if let Some(error) = self.load_subscription_rules() {
+            return Some(error);
+        }
+        Some(Msg::ShowSuccess(message))
+    }
+
+    /// List the rules of the subscription in the rules popup state
+    fn load_subscription_rules(&mut self) -> Option<Msg> {
+        let subscription_path = self
+            .queue_state()
+            .subscription_rules
+            .as_ref()?
+            .subscription_path
+            .clone();
+        let Some(service_bus_manager) = self.service_bus_manager.clone() else {
+            return Some(Msg::ShowError(
+                "❌ Not connected to a namespace.".to_string(),
+            ));
+        };
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+
+        self.task_manager.execute(
+            format!("Loading rules of {subscription_path}..."),
+            async move {
+                let command = ServiceBusCommand::ListSubscriptionRules {
+                    subscription_path: subscription_path.clone(),
+                };
+                let response = service_bus_manager
+                    .lock()
+                    .await
+                    .execute_command(command)
+                    .await;
+
+                match response {
+                    ServiceBusResponse::SubscriptionRules { rules } => {
+                        tx_to_main
+                            .send(Msg::QueueActivity(
+                                QueueActivityMsg::SubscriptionRulesLoaded(rules),
+                            ))
+                            .map_err(|e| AppError::Component(e.to_string()))?;
+                        Ok(())
+                    }
+                    ServiceBusResponse::Error { error } => {
+                        log::warn!("Failed to list rules of {subscription_path}: {error}");
+                        Err(AppError::from(error))
+                    }
+                    _ => Err(AppError::ServiceBus(
+                        "Unexpected response for subscription rules".to_string(),
+                    )),
+                }
+            },
+        );
+        None
+    }
+
+    /// Run a rule save or delete, reporting `success_message` when it is done
+    fn run_rule_change(
+        &mut self,
+        loading_message: String,
+        command: ServiceBusCommand,
+        success_message: String,
+    ) -> Option<Msg> {
+        let Some(service_bus_manager) = self.service_bus_manager.clone() else {
+            return Some(Msg::ShowError(
+                "❌ Not connected to a namespace.".to_string(),
+            ));
+        };
+        let tx_to_main = self.state_manager.tx_to_main.clone();
+
+        self.task_manager.execute(loading_message, async move {
+            let response = service_bus_manager
+                .lock()
+                .await
+                .execute_command(command)
+                .await;
+
+            match response {
+                ServiceBusResponse::SubscriptionRuleSaved { .. }
+                | ServiceBusResponse::SubscriptionRuleDeleted { .. } => {}
+                ServiceBusResponse::Error { error } => {
+                    log::warn!("Subscription rule change failed: {error}");
+                    return Err(AppError::from(error));
+                }
+                _ => {
+                    return Err(AppError::ServiceBus(
+                        "Unexpected response for subscription rule change".to_string(),
+                    ));
+                }
+            }
+
+            tx_to_main
+                .send(Msg::QueueActivity(
+                    QueueActivityMsg::SubscriptionRuleChanged(success_message),
+                ))
+                .map_err(|e| AppError::Component(e.to_string()))?;
+            Ok(())
+        });
+        None
+    }
+
+    /// Show the rules popup for the current rules state
+    fn remount_subscription_rules(&mut self) -> Option<Msg> {
+        let state = self.queue_state().subscription_rules.clone()?;
+        if let Err(e) = self.mount_subscription_rules_popup(
+            state.subscription_path,
+            state.rules,
+            state.selected,
+            state.test,
+        ) {
+            self.error_reporter
+                .report_mount_error("SubscriptionRulesPopup", "mount", e);
+        }
+        None
+    }
+}
+
+fn filter_input(title: &str, message: String, initial_value: String) -> Msg {
+    Msg::PopupActivity(PopupActivityMsg::ShowTextInput {
+        title: title.to_string(),
+        message: format!(
+            "{message}\nSQL: sql: <expression>   Correlation: correlation: subject='x' user.region='eu'"
+        ),
+        initial_value,
+        on_submit: |value| {
+            Msg::QueueActivity(QueueActivityMsg::SubscriptionRuleFilterEntered(value))
+        },
+    })
+}
+
+/// Check a new rule name against the management API's naming rules
+pub fn validate_rule_name(name: &str, rules: &[SubscriptionRule]) -> Result<(), String> {
+    if name.len() > MAX_RULE_NAME_LENGTH {
+        return Err(format!(
+            "Rule names can be at most {MAX_RULE_NAME_LENGTH} characters long"
+        ));
+    }
+    if name.contains(['/', '\\', '?', '#']) {
+        return Err("Rule names cannot contain '/', '\\', '?' or '#'".to_string());
+    }
+    if rules
+        .iter()
+        .any(|rule| rule.name.eq_ignore_ascii_case(name))
+    {
+        return Err(format!(
+            "Rule '{name}' already exists; select it and press 'e' to edit it"
+        ));
+    }
+    Ok(())
+}
+
+/// Evaluate a rule filter against messages the way the subscription would
+pub fn test_rule(rule: &SubscriptionRule, messages: &[MessageModel]) -> RuleTestResult {
+    let (matches, error) = match rule.filter.matching(messages) {
+        Ok(matching) => (
+            matching
+                .into_iter()
+                .map(|message| RuleTestMatch {
+                    sequence: message.sequence,
+                    message_id: message.id.clone(),
+                    subject: message.properties.subject.clone(),
+                })
+                .collect(),
+            None,
+        ),
+        Err(e) => (Vec::new(), Some(e.to_string())),
+    };
+
+    RuleTestResult {
+        rule_name: rule.name.clone(),
+        tested: messages.len(),
+        matches,
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quetty_server::model::{BodyData, MessageState};
+
+    fn message(sequence: i64, subject: &str) -> MessageModel {
+        let mut message = MessageModel::new(
+            sequence,
+            format!("msg-{sequence}"),
+            time::OffsetDateTime::UNIX_EPOCH,
+            0,
+            MessageState::Active,
+            BodyData::RawString(String::new()),
+        );
+        message.properties.subject = Some(subject.to_string());
+        message
+    }
+
+    fn rule(name: &str, filter: &str) -> SubscriptionRule {
+        SubscriptionRule {
+            name: name.to_string(),
+            filter: RuleFilter::from_str(filter).unwrap(),
+            action: None,
+        }
+    }
+
+    #[test]
+    fn test_rule_reports_matching_messages() {
+        let messages = vec![
+            message(1, "OrderCreated"),
+            message(2, "OrderShipped"),
+            message(3, "OrderCreated"),
+        ];
+
+        let result = test_rule(
+            &rule("created", "correlation: subject='OrderCreated'"),
+            &messages,
+        );
+        assert_eq!(result.tested, 3);
+        assert_eq!(
+            result
+                .matches
+                .iter()
+                .map(|matched| matched.sequence)
+                .collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(result.error, None);
+
+        let result = test_rule(&rule("none", "sql: sys.Label = 'Refund'"), &messages);
+        assert!(result.matches.is_empty());
+    }
+
+    #[test]
+    fn test_rule_reports_filters_it_cannot_evaluate() {
+        let result = test_rule(&rule("broken", "sql: subject = "), &[message(1, "x")]);
+        assert!(result.matches.is_empty());
+        assert!(result.error.is_some());
+    }
+
+    #[test]
+    fn test_validate_rule_name() {
+        let rules = vec![rule(DEFAULT_RULE_NAME, "sql: 1=1")];
+        assert!(validate_rule_name("eu-orders", &rules).is_ok());
+        assert!(validate_rule_name("$default", &rules).is_err());
+        assert!(validate_rule_name("a/b", &rules).is_err());
+        assert!(validate_rule_name(&"r".repeat(51), &rules).is_err());
+    }
+}
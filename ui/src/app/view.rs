@@ -41,6 +41,54 @@ pub fn view_success_popup(
     Ok(())
 }
 
+// Render the queue properties popup centered on the screen using large sizing
+pub fn view_queue_info_popup(
+    app: &mut Application<ComponentId, Msg, NoUserEvent>,
+    f: &mut Frame,
+) -> Result<(), AppError> {
+    let popup_area = PopupLayout::large(f.area());
+    app.view(&ComponentId::QueueInfoPopup, f, popup_area);
+    app.active(&ComponentId::QueueInfoPopup)
+        .map_err(|e| AppError::Component(e.to_string()))?;
+    Ok(())
+}
+
+// Render the subscription rules popup centered on the screen using large sizing
+pub fn view_subscription_rules_popup(
+    app: &mut Application<ComponentId, Msg, NoUserEvent>,
+    f: &mut Frame,
+) -> Result<(), AppError> {
+    let popup_area = PopupLayout::large(f.area());
+    app.view(&ComponentId::SubscriptionRulesPopup, f, popup_area);
+    app.active(&ComponentId::SubscriptionRulesPopup)
+        .map_err(|e| AppError::Component(e.to_string()))?;
+    Ok(())
+}
+
+// Render the queue dashboard centered on the screen using large sizing
+pub fn view_queue_dashboard(
+    app: &mut Application<ComponentId, Msg, NoUserEvent>,
+    f: &mut Frame,
+) -> Result<(), AppError> {
+    let popup_area = PopupLayout::large(f.area());
+    app.view(&ComponentId::QueueDashboard, f, popup_area);
+    app.active(&ComponentId::QueueDashboard)
+        .map_err(|e| AppError::Component(e.to_string()))?;
+    Ok(())
+}
+
+// Render the alerts popup centered on the screen using large sizing
+pub fn view_alerts_popup(
+    app: &mut Application<ComponentId, Msg, NoUserEvent>,
+    f: &mut Frame,
+) -> Result<(), AppError> {
+    let popup_area = PopupLayout::large(f.area());
+    app.view(&ComponentId::AlertsPopup, f, popup_area);
+    app.active(&ComponentId::AlertsPopup)
+        .map_err(|e| AppError::Component(e.to_string()))?;
+    Ok(())
+}
+
 // Render the number input popup centered on the screen using standardized sizing
 pub fn view_number_input_popup(
     app: &mut Application<ComponentId, Msg, NoUserEvent>,
@@ -53,6 +101,18 @@ pub fn view_number_input_popup(
     Ok(())
 }
 
+// Render the text input popup centered on the screen using extra wide sizing
+pub fn view_text_input_popup(
+    app: &mut Application<ComponentId, Msg, NoUserEvent>,
+    f: &mut Frame,
+) -> Result<(), AppError> {
+    let popup_area = PopupLayout::extra_wide(f.area());
+    app.view(&ComponentId::TextInputPopup, f, popup_area);
+    app.active(&ComponentId::TextInputPopup)
+        .map_err(|e| AppError::Component(e.to_string()))?;
+    Ok(())
+}
+
 // Render the page size popup centered on the screen using standardized sizing
 pub fn view_page_size_popup(
     app: &mut Application<ComponentId, Msg, NoUserEvent>,
@@ -111,6 +171,11 @@ where
         return view_number_input_popup(app, f);
     }
 
+    // Then, try to render the text input popup if it exists
+    if app.mounted(&ComponentId::TextInputPopup) {
+        return view_text_input_popup(app, f);
+    }
+
     // Then, try to render the confirmation popup if it exists
     if app.mounted(&ComponentId::ConfirmationPopup) {
         return view_confirmation_popup(app, f);
@@ -121,6 +186,11 @@ where
         return view_success_popup(app, f);
     }
 
+    // Then, try to render the queue properties popup if it exists
+    if app.mounted(&ComponentId::QueueInfoPopup) {
+        return view_queue_info_popup(app, f);
+    }
+
     // Then, try to render the error popup if it exists
     if app.mounted(&ComponentId::ErrorPopup) {
         return view_error_popup(app, f);
@@ -133,6 +203,21 @@ where
         return Ok(());
     }
 
+    // Then, try to render the subscription rules popup, which the popups above
+    // are opened from
+    if app.mounted(&ComponentId::SubscriptionRulesPopup) {
+        return view_subscription_rules_popup(app, f);
+    }
+
+    // The alerts can be opened from anywhere, the dashboard included
+    if app.mounted(&ComponentId::AlertsPopup) {
+        return view_alerts_popup(app, f);
+    }
+
+    if app.mounted(&ComponentId::QueueDashboard) {
+        return view_queue_dashboard(app, f);
+    }
+
     // Then, try to render Azure discovery pickers if they exist
     if app.mounted(&ComponentId::SubscriptionPicker) {
         let popup_area = PopupLayout::medium(f.area());
@@ -182,6 +267,28 @@ pub fn view_queue_picker(
     Ok(())
 }
 
+pub fn view_topic_picker(
+    app: &mut Application<ComponentId, Msg, NoUserEvent>,
+    f: &mut Frame,
+    chunks: &[Rect],
+) -> Result<(), AppError> {
+    app.view(&ComponentId::TopicPicker, f, chunks[3]);
+    app.active(&ComponentId::TopicPicker)
+        .map_err(|e| AppError::Component(e.to_string()))?;
+    Ok(())
+}
+
+pub fn view_session_picker(
+    app: &mut Application<ComponentId, Msg, NoUserEvent>,
+    f: &mut Frame,
+    chunks: &[Rect],
+) -> Result<(), AppError> {
+    app.view(&ComponentId::SessionPicker, f, chunks[3]);
+    app.active(&ComponentId::SessionPicker)
+        .map_err(|e| AppError::Component(e.to_string()))?;
+    Ok(())
+}
+
 pub fn view_message_picker(
     app: &mut Application<ComponentId, Msg, NoUserEvent>,
     f: &mut Frame,
@@ -0,0 +1,162 @@
+//! Authentication and Service Bus connection of the subcommands
+//!
+//! Follows the TUI's authentication for the profile's auth method, without
+//! its prompts: the master password comes from [`QUETTY_MASTER_PASSWORD`],
+//! device code instructions are printed on stderr, and Azure AD profiles
+//! must already name their subscription, resource group and namespace.
+
+use super::CliError;
+use crate::components::common::{AuthActivityMsg, Msg};
+use crate::config::{self, AppConfig};
+use crate::constants::env_vars::{
+    AZURE_AD_CLIENT_SECRET, AZURE_AD_CLIENT_SECRET_ENCRYPTION_SALT,
+    AZURE_AD_ENCRYPTED_CLIENT_SECRET, QUETTY_MASTER_PASSWORD,
+};
+use crate::services::AuthService;
+use crate::utils::auth::AuthUtils;
+use azservicebus::{ServiceBusClient, ServiceBusClientOptions};
+use quetty_server::encryption::ClientSecretEncryption;
+use quetty_server::service_bus_manager::ServiceBusManager;
+use quetty_server::service_bus_manager::azure_management_client::{
+    AzureManagementClient, StatisticsConfig,
+};
+use std::sync::Arc;
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Authenticate with the configured method and create a Service Bus manager
+/// for the profile's namespace
+pub async fn connect(config: &AppConfig) -> Result<Arc<Mutex<ServiceBusManager>>, CliError> {
+    if let Ok(password) = std::env::var(QUETTY_MASTER_PASSWORD) {
+        config::azure::set_master_password(password);
+    }
+
+    let http_client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+
+    let connection_string = if AuthUtils::is_connection_string_auth(config) {
+        match config.servicebus().connection_string() {
+            Ok(Some(connection_string)) => connection_string,
+            Ok(None) => {
+                return Err(CliError::Auth(
+                    "No connection string configured; run `quetty --setup` for this profile"
+                        .to_string(),
+                ));
+            }
+            Err(e) => {
+                return Err(CliError::Auth(format!(
+                    "Connection string decryption failed: {e}. Set {QUETTY_MASTER_PASSWORD} to the master password of the profile"
+                )));
+            }
+        }
+    } else {
+        azure_ad_connection_string(config, http_client.clone()).await?
+    };
+
+    let client = ServiceBusClient::new_from_connection_string(
+        &connection_string,
+        ServiceBusClientOptions::default(),
+    )
+    .await
+    .map_err(|e| CliError::Operation(format!("Failed to connect to Service Bus: {e}")))?;
+    log::info!("Service Bus client created for non-interactive command");
+
+    let statistics_config = StatisticsConfig::new(
+        config.queue_stats_display_enabled(),
+        config.queue_stats_cache_ttl_seconds(),
+        config.queue_stats_use_management_api(),
+    );
+    Ok(Arc::new(Mutex::new(ServiceBusManager::new(
+        Arc::new(Mutex::new(client)),
+        http_client,
+        config.azure_ad().clone(),
+        statistics_config,
+        config.batch().clone(),
+        connection_string,
+    ))))
+}
+
+/// Sign in to Azure AD and fetch the connection string of the configured
+/// namespace through the management API
+async fn azure_ad_connection_string(
+    config: &AppConfig,
+    http_client: reqwest::Client,
+) -> Result<String, CliError> {
+    let azure_ad = config.azure_ad();
+    let (Ok(subscription_id), Ok(resource_group), Ok(namespace)) = (
+        azure_ad.subscription_id(),
+        azure_ad.resource_group(),
+        azure_ad.namespace(),
+    ) else {
+        return Err(CliError::Auth(format!(
+            "{} needs the subscription, resource group and namespace in the profile; run quetty interactively once to discover them",
+            AuthUtils::auth_method_description(config)
+        )));
+    };
+
+    if AuthUtils::is_client_secret_auth(config) && std::env::var(AZURE_AD_CLIENT_SECRET).is_err() {
+        decrypt_client_secret()?;
+    }
+
+    let (tx, rx) = mpsc::channel::<Msg>();
+    let auth_service = AuthService::new(azure_ad, tx, http_client.clone())?;
+    quetty_server::auth::set_global_auth_state(auth_service.auth_state_manager());
+    auth_service.initiate_authentication().await?;
+
+    // The service reports the outcome, and any device code, over the channel
+    loop {
+        match rx.try_recv() {
+            Ok(Msg::AuthActivity(AuthActivityMsg::ShowDeviceCode { message, .. })) => {
+                eprintln!("{message}");
+            }
+            Ok(Msg::AuthActivity(AuthActivityMsg::AuthenticationSuccess)) => break,
+            Ok(Msg::AuthActivity(AuthActivityMsg::AuthenticationFailed(error))) => {
+                return Err(CliError::Auth(error));
+            }
+            Ok(_) => {}
+            Err(TryRecvError::Empty) => tokio::time::sleep(Duration::from_millis(100)).await,
+            Err(TryRecvError::Disconnected) => {
+                return Err(CliError::Auth(
+                    "Authentication ended without a result".to_string(),
+                ));
+            }
+        }
+    }
+    log::info!("Azure AD authentication succeeded for non-interactive command");
+
+    let token = auth_service.get_management_token().await?;
+    AzureManagementClient::new(http_client)
+        .get_namespace_connection_string(&token, &subscription_id, &resource_group, &namespace)
+        .await
+        .map_err(|e| CliError::Auth(format!("Failed to fetch the connection string: {e}")))
+}
+
+/// Decrypt the profile's client secret with the master password into
+/// [`AZURE_AD_CLIENT_SECRET`], where the auth service reads it
+fn decrypt_client_secret() -> Result<(), CliError> {
+    let (Ok(encrypted), Ok(salt)) = (
+        std::env::var(AZURE_AD_ENCRYPTED_CLIENT_SECRET),
+        std::env::var(AZURE_AD_CLIENT_SECRET_ENCRYPTION_SALT),
+    ) else {
+        // A plain secret in the configuration is used as is
+        return Ok(());
+    };
+    let password = config::azure::get_master_password().ok_or_else(|| {
+        CliError::Auth(format!(
+            "The client secret is encrypted; set {QUETTY_MASTER_PASSWORD} to the master password of the profile"
+        ))
+    })?;
+
+    let secret = ClientSecretEncryption::from_salt_base64(&salt)
+        .and_then(|encryption| encryption.decrypt_client_secret(&encrypted, &password))
+        .map_err(|e| CliError::Auth(format!("Failed to decrypt client secret: {e}")))?;
+    // SAFETY: set before any task that reads the environment is started
+    unsafe {
+        std::env::set_var(AZURE_AD_CLIENT_SECRET, secret);
+    }
+    Ok(())
+}
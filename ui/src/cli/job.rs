@@ -0,0 +1,979 @@
+//! Declarative batch jobs run by `quetty run`
+//!
+//! A job file lists steps that each select messages of one entity, through
+//! an optional SQL filter, and act on them with the existing Service Bus
+//! commands:
+//!
+//! ```toml
+//! profile = "prod"
+//!
+//! [[steps]]
+//! name = "Resend timed out orders"
+//! action = "resend"
+//! queue = "orders"
+//! dlq = true
+//! filter = "DeadLetterReason LIKE '%Timeout%'"
+//!
+//! [[steps]]
+//! name = "Drop the rest after a week"
+//! action = "purge"
+//! queue = "orders"
+//! dlq = true
+//! older_than = "7d"
+//! ```
+//!
+//! The whole file is validated before connecting, steps run in order and a
+//! failed step skips the remaining ones.
+
+use super::operations::{Manager, peek_messages, run_command, send_and_delete, switch_queue};
+use super::{CliError, Outcome, entity_path, parse_cutoff};
+use crate::config::AppConfig;
+use quetty_server::bulk_operations::{ExportScope, MessageIdentifier};
+use quetty_server::export::ExportFormat;
+use quetty_server::filter::SqlFilter;
+use quetty_server::model::MessageModel;
+use quetty_server::service_bus_manager::{
+    DEAD_LETTER_SUFFIX, ServiceBusCommand, ServiceBusResponse,
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use tokio_util::sync::CancellationToken;
+
+/// Job file as written; unknown keys are rejected so a misspelt option
+/// cannot silently widen a destructive step
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JobFile {
+    profile: Option<String>,
+    #[serde(default)]
+    steps: Vec<StepFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StepFile {
+    name: Option<String>,
+    action: StepKind,
+    queue: String,
+    #[serde(default)]
+    dlq: bool,
+    filter: Option<String>,
+    limit: Option<usize>,
+    to: Option<String>,
+    older_than: Option<String>,
+    output: Option<String>,
+    format: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StepKind {
+    Resend,
+    Move,
+    Delete,
+    Purge,
+    Export,
+}
+
+/// What a step does with the messages it selects
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepAction {
+    /// Send dead-lettered messages back to their queue and delete them from
+    /// the dead letter queue
+    Resend {
+        target: String,
+    },
+    /// Send messages to another queue and delete them from the source
+    Move {
+        target: String,
+    },
+    Delete,
+    /// Delete every message, or those enqueued before the cutoff
+    Purge {
+        older_than: Option<OffsetDateTime>,
+    },
+    /// Write the messages to a new file
+    Export {
+        path: PathBuf,
+        format: ExportFormat,
+    },
+}
+
+impl StepKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Resend => "resend",
+            Self::Move => "move",
+            Self::Delete => "delete",
+            Self::Purge => "purge",
+            Self::Export => "export",
+        }
+    }
+}
+
+impl StepAction {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Resend { .. } => StepKind::Resend,
+            Self::Move { .. } => StepKind::Move,
+            Self::Delete => StepKind::Delete,
+            Self::Purge { .. } => StepKind::Purge,
+            Self::Export { .. } => StepKind::Export,
+        }
+        .name()
+    }
+}
+
+/// A validated step
+#[derive(Debug, Clone)]
+pub struct JobStep {
+    pub name: String,
+    /// Entity path the messages are taken from
+    pub queue_name: String,
+    pub filter: Option<SqlFilter>,
+    /// Maximum number of messages selected; defaults to `search_max_matches`
+    pub limit: Option<usize>,
+    pub action: StepAction,
+}
+
+/// A validated job
+#[derive(Debug, Clone)]
+pub struct Job {
+    /// Profile the job runs against unless `--profile` is given
+    pub profile: Option<String>,
+    pub steps: Vec<JobStep>,
+}
+
+impl Job {
+    /// Read and validate a job file
+    pub fn load(path: &Path) -> Result<Self, CliError> {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            CliError::Usage(format!("Failed to read job file {}: {e}", path.display()))
+        })?;
+        Self::parse(&text)
+            .map_err(|e| CliError::Usage(format!("Invalid job file {}: {e}", path.display())))
+    }
+
+    /// Parse and validate the text of a job file
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let file: JobFile = toml::from_str(text).map_err(|e| e.to_string())?;
+        if file.steps.is_empty() {
+            return Err("the job has no [[steps]]".to_string());
+        }
+        let steps = file
+            .steps
+            .into_iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let label = match &step.name {
+                    Some(name) => format!("step {} ({name})", index + 1),
+                    None => format!("step {}", index + 1),
+                };
+                JobStep::validate(step).map_err(|e| format!("{label}: {e}"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            profile: file.profile,
+            steps,
+        })
+    }
+}
+
+impl JobStep {
+    fn validate(step: StepFile) -> Result<Self, String> {
+        let queue = step.queue.trim();
+        if queue.is_empty() {
+            return Err("`queue` is empty".to_string());
+        }
+        let queue_name = entity_path(queue, step.dlq);
+
+        // Options that only some actions take
+        let unused = [
+            ("to", step.to.is_some(), StepKind::Move),
+            ("older_than", step.older_than.is_some(), StepKind::Purge),
+            ("output", step.output.is_some(), StepKind::Export),
+            ("format", step.format.is_some(), StepKind::Export),
+        ];
+        if let Some((option, ..)) = unused
+            .iter()
+            .find(|(_, given, kind)| *given && *kind != step.action)
+        {
+            return Err(format!(
+                "`{option}` is not used by {} steps",
+                step.action.name()
+            ));
+        }
+
+        let filter = step
+            .filter
+            .as_deref()
+            .map(|filter| SqlFilter::parse(filter).map_err(|e| format!("invalid filter: {e}")))
+            .transpose()?;
+        if step.limit == Some(0) {
+            return Err("`limit` must be at least 1".to_string());
+        }
+
+        let action = match step.action {
+            StepKind::Resend => {
+                if !queue_name.ends_with(DEAD_LETTER_SUFFIX) {
+                    return Err(
+                        "resend takes messages from the dead letter queue; set `dlq = true`"
+                            .to_string(),
+                    );
+                }
+                StepAction::Resend {
+                    target: queue_name
+                        .strip_suffix(DEAD_LETTER_SUFFIX)
+                        .unwrap_or(&queue_name)
+                        .to_string(),
+                }
+            }
+            StepKind::Move => StepAction::Move {
+                target: step
+                    .to
+                    .filter(|to| !to.trim().is_empty())
+                    .ok_or("move needs the target queue in `to`")?,
+            },
+            StepKind::Delete => {
+                if filter.is_none() {
+                    return Err(
+                        "delete needs a `filter`; use a purge step to delete every message"
+                            .to_string(),
+                    );
+                }
+                StepAction::Delete
+            }
+            StepKind::Purge => {
+                if filter.is_some() || step.limit.is_some() {
+                    return Err(
+                        "purge takes every message, or those `older_than` an age; use a delete step with a filter"
+                            .to_string(),
+                    );
+                }
+                StepAction::Purge {
+                    older_than: step.older_than.as_deref().map(parse_cutoff).transpose()?,
+                }
+            }
+            StepKind::Export => {
+                let output = step
+                    .output
+                    .ok_or("export needs the file to create in `output`")?;
+                let path = PathBuf::from(output);
+                let format = match step.format.as_deref() {
+                    Some(format) => ExportFormat::from_str(format).map_err(|e| e.to_string())?,
+                    None => ExportFormat::from_path(&path).unwrap_or_default(),
+                };
+                StepAction::Export { path, format }
+            }
+        };
+
+        Ok(Self {
+            name: step
+                .name
+                .unwrap_or_else(|| format!("{} {}", action.name(), queue_name)),
+            queue_name,
+            filter,
+            limit: step.limit,
+            action,
+        })
+    }
+
+    /// Whether the step acts on a selection of messages rather than the
+    /// whole entity
+    fn selects(&self) -> bool {
+        match self.action {
+            StepAction::Purge { .. } => false,
+            StepAction::Export { .. } => self.filter.is_some() || self.limit.is_some(),
+            _ => true,
+        }
+    }
+
+    /// Start of the report of the step: what it is configured to do
+    fn report(&self, index: usize) -> Report {
+        let mut report = Report::new();
+        report.insert("step".into(), json!(index + 1));
+        report.insert("name".into(), json!(self.name));
+        report.insert("action".into(), json!(self.action.name()));
+        report.insert("queue".into(), json!(self.queue_name));
+        if let Some(filter) = &self.filter {
+            report.insert("filter".into(), json!(filter.expression()));
+        }
+        match &self.action {
+            StepAction::Resend { target } | StepAction::Move { target } => {
+                report.insert("target".into(), json!(target));
+            }
+            StepAction::Purge {
+                older_than: Some(cutoff),
+            } => {
+                report.insert(
+                    "older_than".into(),
+                    json!(cutoff.format(&Rfc3339).unwrap_or_default()),
+                );
+            }
+            StepAction::Export { path, format } => {
+                report.insert("output".into(), json!(path));
+                report.insert("format".into(), json!(format.to_string()));
+            }
+            _ => {}
+        }
+        report
+    }
+}
+
+/// Report of one step in the job output
+type Report = serde_json::Map<String, Value>;
+
+/// Messages a step selected
+struct Selection {
+    messages: Vec<MessageModel>,
+    /// Messages examined to find them
+    scanned: usize,
+    /// Whether more messages may match than were selected
+    truncated: bool,
+}
+
+impl Selection {
+    /// Selection of the first `limit` messages peeked, from a peek of one
+    /// more than the limit to tell whether others remain
+    fn first(mut peeked: Vec<MessageModel>, limit: usize) -> Self {
+        let scanned = peeked.len();
+        let truncated = peeked.len() > limit;
+        peeked.truncate(limit);
+        Self {
+            messages: peeked,
+            scanned,
+            truncated,
+        }
+    }
+
+    fn ids(&self) -> Vec<MessageIdentifier> {
+        self.messages
+            .iter()
+            .map(MessageIdentifier::from_message)
+            .collect()
+    }
+
+    /// Add the selection to `report`, listing the selected sequence numbers
+    /// in a dry run
+    fn record(&self, report: &mut Report, dry_run: bool) {
+        report.insert("selected".into(), json!(self.messages.len()));
+        report.insert("scanned".into(), json!(self.scanned));
+        report.insert("truncated".into(), json!(self.truncated));
+        if dry_run {
+            let sequences: Vec<i64> = self.messages.iter().map(|m| m.sequence).collect();
+            report.insert("sequences".into(), json!(sequences));
+        }
+    }
+}
+
+/// Add the messages a purge would remove to the report of its dry run
+fn record_purge_targets(report: &mut Report, sequence_numbers: &[i64]) {
+    report.insert("selected".into(), json!(sequence_numbers.len()));
+    report.insert("sequences".into(), json!(sequence_numbers));
+}
+
+/// Select the messages a purge of `queue_name` would remove, the way the
+/// purge itself selects them
+async fn select_purge_targets(
+    manager: &Manager,
+    queue_name: &str,
+    older_than: Option<OffsetDateTime>,
+) -> Result<Vec<i64>, CliError> {
+    match run_command(
+        manager,
+        ServiceBusCommand::SelectPurgeTargets {
+            queue_name: queue_name.to_string(),
+            older_than,
+        },
+    )
+    .await?
+    {
+        ServiceBusResponse::PurgeTargetsSelected {
+            sequence_numbers, ..
+        } => Ok(sequence_numbers),
+        response => Err(super::operations::unexpected("purge selection", response)),
+    }
+}
+
+/// Select the messages of `queue_name` matching `filter`, or the first ones
+/// without a filter, up to `limit` or the profile's `search_max_matches`
+async fn select(
+    manager: &Manager,
+    config: &AppConfig,
+    queue_name: &str,
+    filter: Option<&SqlFilter>,
+    limit: Option<usize>,
+) -> Result<Selection, CliError> {
+    let limit = limit.unwrap_or_else(|| config.batch().search_max_matches());
+
+    let Some(filter) = filter else {
+        switch_queue(manager, queue_name, None).await?;
+        let peeked = peek_messages(manager, (limit as u32).saturating_add(1), None).await?;
+        return Ok(Selection::first(peeked, limit));
+    };
+
+    match run_command(
+        manager,
+        ServiceBusCommand::SearchMessages {
+            queue_name: queue_name.to_string(),
+            filter: filter.clone(),
+            max_matches: Some(limit),
+            progress: None,
+            cancel_token: CancellationToken::new(),
+        },
+    )
+    .await?
+    {
+        ServiceBusResponse::SearchCompleted { result, .. } => Ok(Selection {
+            messages: result.matches,
+            scanned: result.scanned,
+            truncated: result.truncated,
+        }),
+        response => Err(super::operations::unexpected("search", response)),
+    }
+}
+
+/// Counts a step reports after running
+#[derive(Default)]
+struct StepResult {
+    processed: usize,
+    failed: usize,
+    errors: Vec<String>,
+    /// Non-targets the step deferred and could not re-queue
+    still_deferred: Vec<i64>,
+}
+
+/// Add the outcome of a step to `report`, returning whether the step failed
+fn record_outcome(
+    report: &mut Report,
+    outcome: Result<StepResult, CliError>,
+    dry_run: bool,
+) -> bool {
+    let result = match outcome {
+        Ok(result) => result,
+        Err(error) => {
+            report.insert("status".into(), json!("failed"));
+            report.insert("error".into(), json!(error.message()));
+            return true;
+        }
+    };
+    if dry_run {
+        report.insert("status".into(), json!("dry_run"));
+        return false;
+    }
+
+    let failed = result.failed > 0 || !result.still_deferred.is_empty();
+    report.insert("processed".into(), json!(result.processed));
+    report.insert("failed".into(), json!(result.failed));
+    if !result.errors.is_empty() {
+        report.insert("errors".into(), json!(result.errors));
+    }
+    if !result.still_deferred.is_empty() {
+        report.insert("still_deferred".into(), json!(result.still_deferred));
+    }
+    report.insert(
+        "status".into(),
+        json!(if failed { "failed" } else { "completed" }),
+    );
+    failed
+}
+
+/// Run the selection and action of a step, adding its counts to `report`
+async fn run_step(
+    manager: &Manager,
+    step: &JobStep,
+    dry_run: bool,
+    config: &AppConfig,
+    report: &mut Report,
+) -> Result<StepResult, CliError> {
+    let selection = if step.selects() {
+        Some(
+            select(
+                manager,
+                config,
+                &step.queue_name,
+                step.filter.as_ref(),
+                step.limit,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    if let Some(selection) = &selection {
+        selection.record(report, dry_run);
+    } else if dry_run && let StepAction::Purge { older_than } = &step.action {
+        // A purge selects when it runs, so its dry run asks for the same
+        // selection without removing anything
+        let targets = select_purge_targets(manager, &step.queue_name, *older_than).await?;
+        record_purge_targets(report, &targets);
+    }
+    if dry_run {
+        return Ok(StepResult::default());
+    }
+
+    match (&step.action, selection) {
+        (StepAction::Resend { target } | StepAction::Move { target }, Some(selection)) => {
+            let result = send_and_delete(
+                manager,
+                &step.queue_name,
+                selection.ids(),
+                target,
+                selection.scanned,
+                config.resend(),
+            )
+            .await?;
+            Ok(StepResult {
+                processed: result.successful,
+                failed: result.failed + result.not_found,
+                errors: result.error_details,
+                still_deferred: result.still_deferred,
+            })
+        }
+        (StepAction::Delete, Some(selection)) => {
+            let message_ids = selection.ids();
+            if message_ids.is_empty() {
+                return Ok(StepResult::default());
+            }
+            switch_queue(manager, &step.queue_name, None).await?;
+            match run_command(
+                manager,
+                ServiceBusCommand::BulkDelete {
+                    message_ids,
+                    max_position: selection.scanned,
+                },
+            )
+            .await?
+            {
+                ServiceBusResponse::BulkOperationCompleted { result } => Ok(StepResult {
+                    processed: result.successful,
+                    failed: result.failed + result.not_found,
+                    errors: result.error_details,
+                    still_deferred: result.still_deferred,
+                }),
+                response => Err(super::operations::unexpected("delete", response)),
+            }
+        }
+        (StepAction::Purge { older_than }, _) => {
+            match run_command(
+                manager,
+                ServiceBusCommand::PurgeQueue {
+                    queue_name: step.queue_name.clone(),
+                    older_than: *older_than,
+                    progress: None,
+                    cancel_token: CancellationToken::new(),
+                },
+            )
+            .await?
+            {
+                ServiceBusResponse::QueuePurged { mut result, .. } => {
+                    let failed = result.errors.len() + result.missed.len();
+                    if !result.missed.is_empty() {
+                        result.errors.push(format!(
+                            "{} selected messages were never received: {:?}",
+                            result.missed.len(),
+                            result.missed
+                        ));
+                    }
+                    Ok(StepResult {
+                        processed: result.purged,
+                        failed,
+                        errors: result.errors,
+                        still_deferred: result.still_deferred,
+                    })
+                }
+                response => Err(super::operations::unexpected("purge", response)),
+            }
+        }
+        (StepAction::Export { path, format }, selection) => {
+            let scope = match selection {
+                Some(selection) => {
+                    ExportScope::Sequences(selection.messages.iter().map(|m| m.sequence).collect())
+                }
+                None => ExportScope::All,
+            };
+            match run_command(
+                manager,
+                ServiceBusCommand::ExportMessages {
+                    queue_name: step.queue_name.clone(),
+                    path: path.clone(),
+                    format: *format,
+                    scope,
+                    progress: None,
+                    cancel_token: CancellationToken::new(),
+                },
+            )
+            .await?
+            {
+                ServiceBusResponse::MessagesExported { result, .. } => Ok(StepResult {
+                    processed: result.exported,
+                    failed: result.missing,
+                    ..StepResult::default()
+                }),
+                response => Err(super::operations::unexpected("export", response)),
+            }
+        }
+        (_, None) => Err(CliError::Operation(format!(
+            "{} selected no messages",
+            step.name
+        ))),
+    }
+}
+
+/// Run the steps of a job in order, stopping at the first that fails, and
+/// report each of them; `config` is the profile the job runs with
+pub async fn run(
+    manager: &Manager,
+    job: &Job,
+    job_path: &str,
+    profile_name: &str,
+    dry_run: bool,
+    config: &AppConfig,
+) -> Outcome {
+    let total = job.steps.len();
+    let mut reports = Vec::with_capacity(total);
+    let mut failed = false;
+
+    for (index, step) in job.steps.iter().enumerate() {
+        let mut report = step.report(index);
+        if failed {
+            report.insert("status".into(), json!("skipped"));
+            reports.push(Value::Object(report));
+            continue;
+        }
+
+        eprintln!("[{}/{total}] {}", index + 1, step.name);
+        log::info!("Job step {}/{total}: {}", index + 1, step.name);
+        let outcome = run_step(manager, step, dry_run, config, &mut report).await;
+        if let Err(error) = &outcome {
+            log::error!("Job step {} failed: {}", step.name, error.message());
+        }
+        failed = record_outcome(&mut report, outcome, dry_run);
+        reports.push(Value::Object(report));
+    }
+
+    Outcome {
+        output: json!({
+            "job": job_path,
+            "profile": profile_name,
+            "dry_run": dry_run,
+            "steps": reports,
+        }),
+        complete: !failed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quetty_server::model::{BodyData, MessageState};
+
+    const JOB: &str = r#"
+        [[steps]]
+        action = "resend"
+        queue = "orders"
+        dlq = true
+        filter = "DeadLetterReason LIKE '%Timeout%'"
+
+        [[steps]]
+        action = "move"
+        queue = "orders"
+        to = "retry"
+        limit = 5
+
+        [[steps]]
+        action = "delete"
+        queue = "orders"
+        filter = "sys.DeliveryCount > 3"
+
+        [[steps]]
+        action = "purge"
+        queue = "orders"
+        dlq = true
+        older_than = "2024-01-01T00:00:00Z"
+
+        [[steps]]
+        action = "export"
+        queue = "payments"
+        output = "payments.json"
+    "#;
+
+    fn selection(sequences: &[i64]) -> Selection {
+        Selection {
+            messages: sequences
+                .iter()
+                .map(|&sequence| {
+                    MessageModel::new(
+                        sequence,
+                        format!("msg-{sequence}"),
+                        OffsetDateTime::UNIX_EPOCH,
+                        0,
+                        MessageState::Active,
+                        BodyData::RawString(String::new()),
+                    )
+                })
+                .collect(),
+            scanned: 10,
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_job() {
+        let job = Job::parse(
+            r#"
+            profile = "prod"
+
+            [[steps]]
+            name = "Resend timed out orders"
+            action = "resend"
+            queue = "orders"
+            dlq = true
+            filter = "DeadLetterReason LIKE '%Timeout%'"
+
+            [[steps]]
+            action = "purge"
+            queue = "orders"
+            dlq = true
+            older_than = "7d"
+
+            [[steps]]
+            action = "export"
+            queue = "payments"
+            output = "payments.csv"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(job.profile.as_deref(), Some("prod"));
+        assert_eq!(job.steps.len(), 3);
+        assert_eq!(job.steps[0].queue_name, "orders/$deadletterqueue");
+        assert_eq!(
+            job.steps[0].action,
+            StepAction::Resend {
+                target: "orders".to_string()
+            }
+        );
+        assert!(job.steps[0].selects());
+        assert_eq!(job.steps[1].name, "purge orders/$deadletterqueue");
+        assert!(matches!(
+            job.steps[1].action,
+            StepAction::Purge {
+                older_than: Some(_)
+            }
+        ));
+        assert!(!job.steps[1].selects());
+        assert_eq!(
+            job.steps[2].action,
+            StepAction::Export {
+                path: PathBuf::from("payments.csv"),
+                format: ExportFormat::Csv
+            }
+        );
+        assert!(!job.steps[2].selects());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_steps() {
+        let step_error = |step: &str| Job::parse(&format!("[[steps]]\n{step}")).unwrap_err();
+
+        assert!(Job::parse("profile = \"prod\"").is_err());
+        assert!(step_error("action = \"resend\"\nqueue = \"orders\"").contains("dlq = true"));
+        assert!(step_error("action = \"move\"\nqueue = \"orders\"").contains("`to`"));
+        assert!(step_error("action = \"delete\"\nqueue = \"orders\"").contains("filter"));
+        assert!(
+            step_error(
+                "action = \"purge\"\nqueue = \"orders\"\nfilter = \"sys.DeliveryCount > 3\""
+            )
+            .contains("delete step")
+        );
+        assert!(
+            step_error(
+                "action = \"move\"\nqueue = \"orders\"\nto = \"retry\"\nolder_than = \"7d\""
+            )
+            .contains("`older_than` is not used by move steps")
+        );
+        assert!(
+            step_error("action = \"delete\"\nqueue = \"orders\"\nfilter = \"sys.DeliveryCount >\"")
+                .starts_with("step 1: invalid filter")
+        );
+        // A misspelt option is an error rather than ignored
+        assert!(
+            step_error("action = \"purge\"\nqueue = \"orders\"\nolder_then = \"7d\"")
+                .contains("older_then")
+        );
+        assert!(step_error("action = \"archive\"\nqueue = \"orders\"").contains("archive"));
+        assert!(step_error("action = \"purge\"\nqueue = \"  \"").contains("`queue` is empty"));
+        assert!(
+            step_error("action = \"move\"\nqueue = \"orders\"\nto = \"retry\"\nlimit = 0")
+                .contains("`limit` must be at least 1")
+        );
+        assert!(step_error("action = \"move\"\nqueue = \"orders\"\nto = \" \"").contains("`to`"));
+        assert!(
+            step_error("action = \"purge\"\nqueue = \"orders\"\nolder_than = \"soon\"")
+                .starts_with("step 1: ")
+        );
+        assert!(step_error("action = \"export\"\nqueue = \"orders\"").contains("`output`"));
+        assert!(
+            step_error("action = \"export\"\nqueue = \"orders\"\noutput = \"x\"\nformat = \"xml\"")
+                .contains("xml")
+        );
+        // Errors name the step they come from
+        let error = Job::parse(
+            "[[steps]]\naction = \"purge\"\nqueue = \"orders\"\n\n\
+             [[steps]]\nname = \"Tidy\"\naction = \"delete\"\nqueue = \"orders\"",
+        )
+        .unwrap_err();
+        assert!(error.starts_with("step 2 (Tidy): "));
+    }
+
+    #[test]
+    fn test_step_report() {
+        let job = Job::parse(JOB).unwrap();
+        let reports: Vec<Report> = job
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| step.report(index))
+            .collect();
+
+        assert_eq!(reports[0]["step"], json!(1));
+        assert_eq!(reports[0]["name"], json!("resend orders/$deadletterqueue"));
+        assert_eq!(reports[0]["action"], json!("resend"));
+        assert_eq!(reports[0]["queue"], json!("orders/$deadletterqueue"));
+        assert_eq!(
+            reports[0]["filter"],
+            json!("DeadLetterReason LIKE '%Timeout%'")
+        );
+        assert_eq!(reports[0]["target"], json!("orders"));
+        assert_eq!(reports[1]["target"], json!("retry"));
+        assert!(!reports[1].contains_key("filter"));
+        assert!(!reports[2].contains_key("target"));
+        assert_eq!(reports[3]["older_than"], json!("2024-01-01T00:00:00Z"));
+        assert_eq!(reports[4]["output"], json!("payments.json"));
+        assert_eq!(reports[4]["format"], json!("json"));
+
+        // Completed steps report their counts
+        let mut report = reports[1].clone();
+        let failed = record_outcome(
+            &mut report,
+            Ok(StepResult {
+                processed: 4,
+                ..StepResult::default()
+            }),
+            false,
+        );
+        assert!(!failed);
+        assert_eq!(report["status"], json!("completed"));
+        assert_eq!(report["processed"], json!(4));
+        assert_eq!(report["failed"], json!(0));
+        assert!(!report.contains_key("errors"));
+        assert!(!report.contains_key("still_deferred"));
+
+        // Failed messages fail the step
+        let mut report = reports[1].clone();
+        let failed = record_outcome(
+            &mut report,
+            Ok(StepResult {
+                processed: 3,
+                failed: 1,
+                errors: vec!["lock lost".to_string()],
+                ..StepResult::default()
+            }),
+            false,
+        );
+        assert!(failed);
+        assert_eq!(report["status"], json!("failed"));
+        assert_eq!(report["errors"], json!(["lock lost"]));
+
+        // So do messages left deferred
+        let mut report = reports[3].clone();
+        let failed = record_outcome(
+            &mut report,
+            Ok(StepResult {
+                processed: 2,
+                still_deferred: vec![9],
+                ..StepResult::default()
+            }),
+            false,
+        );
+        assert!(failed);
+        assert_eq!(report["status"], json!("failed"));
+        assert_eq!(report["still_deferred"], json!([9]));
+
+        // And errors, which replace the counts
+        let mut report = reports[2].clone();
+        let failed = record_outcome(
+            &mut report,
+            Err(CliError::Operation("connection lost".to_string())),
+            false,
+        );
+        assert!(failed);
+        assert_eq!(report["status"], json!("failed"));
+        assert_eq!(report["error"], json!("connection lost"));
+        assert!(!report.contains_key("processed"));
+    }
+
+    #[test]
+    fn test_dry_run_report_of_each_step() {
+        let job = Job::parse(JOB).unwrap();
+        let mut reports = Vec::new();
+        for (index, step) in job.steps.iter().enumerate() {
+            let mut report = step.report(index);
+            if step.selects() {
+                selection(&[3, 7]).record(&mut report, true);
+            } else if let StepAction::Purge { .. } = step.action {
+                record_purge_targets(&mut report, &[1, 2, 4]);
+            }
+            assert!(!record_outcome(
+                &mut report,
+                Ok(StepResult::default()),
+                true
+            ));
+            reports.push(report);
+        }
+
+        for report in &reports {
+            assert_eq!(report["status"], json!("dry_run"));
+            assert!(!report.contains_key("processed"));
+            assert!(!report.contains_key("failed"));
+        }
+        for report in &reports[..3] {
+            assert_eq!(report["selected"], json!(2));
+            assert_eq!(report["scanned"], json!(10));
+            assert_eq!(report["truncated"], json!(false));
+            assert_eq!(report["sequences"], json!([3, 7]));
+        }
+        // A purge lists what its own selection would remove
+        assert_eq!(reports[3]["selected"], json!(3));
+        assert_eq!(reports[3]["sequences"], json!([1, 2, 4]));
+        assert!(!reports[3].contains_key("scanned"));
+        // An unfiltered export takes the whole entity and selects nothing
+        assert!(!reports[4].contains_key("selected"));
+
+        // Outside a dry run selections leave out the sequence numbers
+        let mut report = job.steps[0].report(0);
+        selection(&[3, 7]).record(&mut report, false);
+        assert_eq!(report["selected"], json!(2));
+        assert!(!report.contains_key("sequences"));
+
+        // An unfiltered selection of exactly `limit` messages is complete
+        let mut report = job.steps[1].report(1);
+        Selection::first(selection(&[3, 7]).messages, 2).record(&mut report, true);
+        assert_eq!(report["selected"], json!(2));
+        assert_eq!(report["truncated"], json!(false));
+
+        // It is truncated only when a message remains past the limit
+        let mut report = job.steps[1].report(1);
+        Selection::first(selection(&[3, 7, 9]).messages, 2).record(&mut report, true);
+        assert_eq!(report["selected"], json!(2));
+        assert_eq!(report["scanned"], json!(3));
+        assert_eq!(report["truncated"], json!(true));
+        assert_eq!(report["sequences"], json!([3, 7]));
+    }
+}
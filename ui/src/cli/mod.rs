@@ -0,0 +1,501 @@
+//! Non-interactive subcommands
+//!
+//! `quetty peek`, `send`, `export`, `purge`, `stats` and `move` run one
+//! operation through the same [`ServiceBusManager`] commands and profile
+//! authentication as the TUI, print the result as JSON on stdout and report
+//! failures as JSON on stderr with a distinct exit code, so they can be used
+//! from CI jobs and runbooks. `quetty run` runs the steps of a [`job`] file.
+//!
+//! [`ServiceBusManager`]: quetty_server::service_bus_manager::ServiceBusManager
+
+mod connection;
+pub mod job;
+mod operations;
+
+use crate::app::updates::messages::bulk::parse_purge_cutoff;
+use crate::config::{self, ConfigLoadResult};
+use crate::error::AppError;
+use crate::logger;
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use quetty_server::export::ExportFormat;
+use quetty_server::model::PropertyValue;
+use quetty_server::service_bus_manager::{DEAD_LETTER_SUFFIX, ServiceBusCommand};
+use serde_json::{Value, json};
+use std::path::Path;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// The operation succeeded
+pub const EXIT_SUCCESS: i32 = 0;
+/// The operation failed or only partly succeeded
+pub const EXIT_FAILURE: i32 = 1;
+/// The arguments were invalid; also what clap exits with
+pub const EXIT_USAGE: i32 = 2;
+/// The configuration could not be loaded or authentication failed
+pub const EXIT_AUTH: i32 = 3;
+
+/// Names of the non-interactive subcommands
+pub const SUBCOMMANDS: [&str; 7] = ["peek", "send", "export", "purge", "stats", "move", "run"];
+
+/// Result of a subcommand: the JSON printed on stdout, and whether the
+/// operation fully succeeded
+#[derive(Debug)]
+pub struct Outcome {
+    pub output: Value,
+    pub complete: bool,
+}
+
+impl Outcome {
+    fn complete(output: Value) -> Self {
+        Self {
+            output,
+            complete: true,
+        }
+    }
+}
+
+/// Failure of a subcommand, carrying its exit code
+#[derive(Debug)]
+pub enum CliError {
+    /// Invalid arguments
+    Usage(String),
+    /// Configuration that cannot be loaded, or failed authentication
+    Auth(String),
+    /// A Service Bus operation that failed
+    Operation(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Usage(_) => EXIT_USAGE,
+            Self::Auth(_) => EXIT_AUTH,
+            Self::Operation(_) => EXIT_FAILURE,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Usage(_) => "usage",
+            Self::Auth(_) => "auth",
+            Self::Operation(_) => "operation",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            Self::Usage(message) | Self::Auth(message) | Self::Operation(message) => message,
+        }
+    }
+}
+
+impl From<AppError> for CliError {
+    fn from(error: AppError) -> Self {
+        match error {
+            AppError::Config(message) | AppError::Auth(message) => Self::Auth(message),
+            other => Self::Operation(other.to_string()),
+        }
+    }
+}
+
+/// Subcommand definitions added to the `quetty` command
+pub fn subcommands() -> Vec<Command> {
+    let queue = || {
+        Arg::new("queue")
+            .required(true)
+            .value_name("QUEUE")
+            .help("Queue name, or topic/Subscriptions/subscription path")
+    };
+    let dlq = || {
+        Arg::new("dlq")
+            .long("dlq")
+            .action(ArgAction::SetTrue)
+            .help("Use the dead letter queue of QUEUE")
+    };
+
+    vec![
+        Command::new("peek")
+            .about("Print messages of a queue without locking them")
+            .arg(queue())
+            .arg(dlq())
+            .arg(
+                Arg::new("count")
+                    .long("count")
+                    .short('n')
+                    .value_name("N")
+                    .default_value("10")
+                    .value_parser(value_parser!(u32).range(1..))
+                    .help("Number of messages to peek"),
+            )
+            .arg(
+                Arg::new("from-sequence")
+                    .long("from-sequence")
+                    .value_name("SEQUENCE")
+                    .value_parser(value_parser!(i64))
+                    .help("Start at this sequence number instead of the first message"),
+            )
+            .arg(
+                Arg::new("session")
+                    .long("session")
+                    .value_name("ID")
+                    .help("Session to peek on a session-enabled queue"),
+            ),
+        Command::new("send")
+            .about("Send one message to a queue or topic")
+            .arg(queue())
+            .arg(
+                Arg::new("body")
+                    .long("body")
+                    .value_name("TEXT")
+                    .help("Message body; read from stdin when neither --body nor --file is given"),
+            )
+            .arg(
+                Arg::new("file")
+                    .long("file")
+                    .value_name("FILE")
+                    .conflicts_with("body")
+                    .help("Read the message body from a file"),
+            )
+            .arg(
+                Arg::new("property")
+                    .long("property")
+                    .short('P')
+                    .value_name("KEY=VALUE")
+                    .action(ArgAction::Append)
+                    .value_parser(parse_property)
+                    .help("Application property; numbers, true, false and null are typed, quote to force a string"),
+            )
+            .arg(
+                Arg::new("content-type")
+                    .long("content-type")
+                    .value_name("TYPE")
+                    .help("MIME content type of the body"),
+            )
+            .arg(
+                Arg::new("correlation-id")
+                    .long("correlation-id")
+                    .value_name("ID")
+                    .help("Correlation identifier"),
+            )
+            .arg(
+                Arg::new("session-id")
+                    .long("session-id")
+                    .value_name("ID")
+                    .help("Session of the message, for session-enabled queues"),
+            ),
+        Command::new("export")
+            .about("Write every message of a queue to a file")
+            .arg(queue())
+            .arg(dlq())
+            .arg(
+                Arg::new("output")
+                    .long("output")
+                    .short('o')
+                    .value_name("FILE")
+                    .required(true)
+                    .help("File to create; an existing file is not overwritten"),
+            )
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .value_name("FORMAT")
+                    .value_parser(ExportFormat::from_str)
+                    .help("ndjson, json or csv (default: from the file extension, else ndjson)"),
+            ),
+        Command::new("purge")
+            .about("Delete the messages of a queue")
+            .arg(queue())
+            .arg(dlq())
+            .arg(
+                Arg::new("older-than")
+                    .long("older-than")
+                    .value_name("AGE")
+                    .value_parser(parse_cutoff)
+                    .help("Only delete messages enqueued before an age such as 7d or an RFC 3339 timestamp"),
+            )
+            .arg(
+                Arg::new("yes")
+                    .long("yes")
+                    .action(ArgAction::SetTrue)
+                    .required(true)
+                    .help("Confirm that the messages are deleted"),
+            ),
+        Command::new("stats")
+            .about("Print the counts and status of queues")
+            .arg(queue().num_args(1..)),
+        Command::new("move")
+            .about("Move messages from the front of a queue to another queue")
+            .arg(queue())
+            .arg(dlq())
+            .arg(
+                Arg::new("to")
+                    .long("to")
+                    .value_name("QUEUE")
+                    .required(true)
+                    .help("Queue or topic the messages are sent to"),
+            )
+            .arg(
+                Arg::new("count")
+                    .long("count")
+                    .short('n')
+                    .value_name("N")
+                    .default_value("100")
+                    .value_parser(value_parser!(u32).range(1..))
+                    .help("Maximum number of messages to move"),
+            ),
+        Command::new("run")
+            .about("Run the steps of a job file against a profile")
+            .arg(
+                Arg::new("job")
+                    .required(true)
+                    .value_name("JOB")
+                    .help("TOML job file listing the steps"),
+            )
+            .arg(
+                Arg::new("dry-run")
+                    .long("dry-run")
+                    .action(ArgAction::SetTrue)
+                    .help("Report what each step would select without changing anything"),
+            ),
+    ]
+}
+
+/// Run a subcommand for a profile or configuration file, printing its
+/// outcome, and return the process exit code
+pub async fn run(
+    name: &str,
+    args: &ArgMatches,
+    custom_config_path: Option<&str>,
+    profile_name: &str,
+) -> i32 {
+    match execute(name, args, custom_config_path, profile_name).await {
+        Ok(outcome) => {
+            println!("{}", outcome.output);
+            if outcome.complete {
+                EXIT_SUCCESS
+            } else {
+                EXIT_FAILURE
+            }
+        }
+        Err(error) => {
+            log::error!("quetty {name} failed: {}", error.message());
+            eprintln!(
+                "{}",
+                json!({ "error": error.message(), "kind": error.kind() })
+            );
+            error.exit_code()
+        }
+    }
+}
+
+async fn execute(
+    name: &str,
+    args: &ArgMatches,
+    custom_config_path: Option<&str>,
+    profile_name: &str,
+) -> Result<Outcome, CliError> {
+    // A job is validated before connecting, and names its profile unless
+    // `--profile` is given
+    let job = load_job(name, args)?;
+    let profile_name = match &job {
+        Some((_, job)) if args.value_source("profile") != Some(ValueSource::CommandLine) => {
+            job.profile.as_deref().unwrap_or(profile_name)
+        }
+        _ => profile_name,
+    };
+
+    let config_result = match custom_config_path {
+        Some(path) => config::init_config_from_path(path),
+        None => config::get_config_for_profile(profile_name),
+    };
+    let config = match config_result {
+        ConfigLoadResult::Success(config) => config.as_ref(),
+        ConfigLoadResult::LoadError(error) | ConfigLoadResult::DeserializeError(error) => {
+            return Err(CliError::Auth(error.to_string()));
+        }
+    };
+
+    // Logs go to the log file, so stdout only carries the JSON output
+    if let Err(e) = logger::setup_logger() {
+        eprintln!("Failed to initialize logger: {e}");
+    }
+    log::info!("Running quetty {name} with profile '{profile_name}'");
+
+    if let Err(errors) = config.validate() {
+        let messages: Vec<String> = errors.iter().map(|error| error.user_message()).collect();
+        return Err(CliError::Auth(messages.join("; ")));
+    }
+
+    let service_bus_manager = connection::connect(config).await?;
+    let outcome = match &job {
+        Some((job_path, job)) => Ok(job::run(
+            &service_bus_manager,
+            job,
+            job_path,
+            profile_name,
+            args.get_flag("dry-run"),
+            config,
+        )
+        .await),
+        None => operations::execute(name, args, service_bus_manager.clone(), config.resend()).await,
+    };
+
+    let response = service_bus_manager
+        .lock()
+        .await
+        .execute_command(ServiceBusCommand::DisposeAllResources)
+        .await;
+    log::debug!("Disposed Service Bus resources: {response:?}");
+
+    outcome
+}
+
+/// Path and contents of the job file of a `run` subcommand; other
+/// subcommands have no `job` argument and run no job
+fn load_job<'a>(
+    name: &str,
+    args: &'a ArgMatches,
+) -> Result<Option<(&'a String, job::Job)>, CliError> {
+    if name != "run" {
+        return Ok(None);
+    }
+    args.get_one::<String>("job")
+        .map(|job_path| Ok((job_path, job::Job::load(Path::new(job_path))?)))
+        .transpose()
+}
+
+/// Entity path of a queue, or of its dead letter queue
+fn entity_path(queue_name: &str, dead_letter: bool) -> String {
+    if dead_letter && !queue_name.ends_with(DEAD_LETTER_SUFFIX) {
+        format!("{queue_name}{DEAD_LETTER_SUFFIX}")
+    } else {
+        queue_name.to_string()
+    }
+}
+
+/// Parse the `--older-than` cutoff of a purge like the purge popup does
+fn parse_cutoff(value: &str) -> Result<OffsetDateTime, String> {
+    parse_purge_cutoff(value, OffsetDateTime::now_utc())?
+        .ok_or_else(|| "expected an age such as 7d or 12h, or an RFC 3339 timestamp".to_string())
+}
+
+/// Parse a `KEY=VALUE` application property, typing the value like the
+/// message composer does
+fn parse_property(value: &str) -> Result<(String, PropertyValue), String> {
+    let (key, value) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got `{value}`"))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("the property name is empty".to_string());
+    }
+    Ok((
+        key.to_string(),
+        crate::components::message_details::envelope::parse_property_value(value.trim()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_property() {
+        assert_eq!(
+            parse_property("priority=high").unwrap(),
+            ("priority".to_string(), PropertyValue::String("high".into()))
+        );
+        assert_eq!(
+            parse_property("attempt = 3").unwrap(),
+            ("attempt".to_string(), PropertyValue::Int(3))
+        );
+        assert_eq!(
+            parse_property("code=\"42\"").unwrap().1,
+            PropertyValue::String("42".into())
+        );
+        assert_eq!(
+            parse_property("filter=a=b").unwrap().1,
+            PropertyValue::String("a=b".into())
+        );
+        assert!(parse_property("priority").is_err());
+        assert!(parse_property("=high").is_err());
+    }
+
+    #[test]
+    fn test_subcommand_arguments() {
+        let command = Command::new("quetty").subcommands(subcommands());
+        assert_eq!(
+            command
+                .get_subcommands()
+                .map(|subcommand| subcommand.get_name())
+                .collect::<Vec<_>>(),
+            SUBCOMMANDS
+        );
+
+        let matches = command
+            .clone()
+            .try_get_matches_from(["quetty", "stats", "orders", "payments"])
+            .unwrap();
+        let (_, args) = matches.subcommand().unwrap();
+        assert_eq!(
+            args.get_many::<String>("queue")
+                .unwrap()
+                .collect::<Vec<_>>(),
+            ["orders", "payments"]
+        );
+
+        assert!(
+            command
+                .clone()
+                .try_get_matches_from(["quetty", "move", "orders"])
+                .is_err()
+        );
+        assert!(
+            command
+                .try_get_matches_from(["quetty", "send", "orders", "--body", "x", "--file", "y"])
+                .is_err()
+        );
+
+        assert_eq!(entity_path("orders", true), "orders/$deadletterqueue");
+        assert_eq!(
+            entity_path("orders/$deadletterqueue", true),
+            "orders/$deadletterqueue"
+        );
+        assert_eq!(entity_path("orders", false), "orders");
+    }
+
+    #[test]
+    fn test_load_job_only_for_run() {
+        let command = Command::new("quetty").subcommands(subcommands());
+
+        let matches = command
+            .clone()
+            .try_get_matches_from(["quetty", "peek", "orders", "--count", "5"])
+            .unwrap();
+        let (name, args) = matches.subcommand().unwrap();
+        assert!(load_job(name, args).unwrap().is_none());
+
+        let dir = tempfile::tempdir().unwrap();
+        let job_path = dir.path().join("job.toml");
+        std::fs::write(
+            &job_path,
+            "profile = \"prod\"\n[[steps]]\naction = \"purge\"\nqueue = \"orders\"\n",
+        )
+        .unwrap();
+        let matches = command
+            .clone()
+            .try_get_matches_from(["quetty", "run", job_path.to_str().unwrap()])
+            .unwrap();
+        let (name, args) = matches.subcommand().unwrap();
+        let (path, job) = load_job(name, args).unwrap().unwrap();
+        assert_eq!(Path::new(path), job_path);
+        assert_eq!(job.profile.as_deref(), Some("prod"));
+
+        let matches = command
+            .try_get_matches_from(["quetty", "run", "missing-job.toml"])
+            .unwrap();
+        let (name, args) = matches.subcommand().unwrap();
+        assert!(matches!(load_job(name, args), Err(CliError::Usage(_))));
+    }
+}
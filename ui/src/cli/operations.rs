@@ -0,0 +1,399 @@
+//! The operations behind the subcommands, each one or more
+//! [`ServiceBusCommand`]s whose response is turned into JSON output
+
+use super::{CliError, Outcome, entity_path};
+use quetty_server::bulk_operations::{BulkOperationResult, ExportScope, MessageIdentifier};
+use quetty_server::export::ExportFormat;
+use quetty_server::model::{MessageModel, PropertyValue};
+use quetty_server::service_bus_manager::{
+    MessageData, QueueType, ResendOptions, ServiceBusCommand, ServiceBusManager, ServiceBusResponse,
+};
+use serde_json::json;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+pub(super) type Manager = Arc<Mutex<ServiceBusManager>>;
+
+/// Run the named subcommand against a connected manager; `resend_options`
+/// are those of the loaded profile
+pub async fn execute(
+    name: &str,
+    args: &clap::ArgMatches,
+    manager: Manager,
+    resend_options: &ResendOptions,
+) -> Result<Outcome, CliError> {
+    let queue_name = || {
+        let queue_name = args
+            .get_one::<String>("queue")
+            .map(String::as_str)
+            .unwrap_or_default();
+        // `stats` has no `--dlq`
+        entity_path(
+            queue_name,
+            matches!(args.try_get_one::<bool>("dlq"), Ok(Some(true))),
+        )
+    };
+
+    match name {
+        "peek" => {
+            let count = args.get_one::<u32>("count").copied().unwrap_or(10);
+            let from_sequence = args.get_one::<i64>("from-sequence").copied();
+            let session_id = args.get_one::<String>("session").cloned();
+            peek(&manager, queue_name(), count, from_sequence, session_id).await
+        }
+        "send" => send(&manager, queue_name(), message_from_args(args)?).await,
+        "export" => {
+            let path = PathBuf::from(
+                args.get_one::<String>("output")
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+            let format = args
+                .get_one::<ExportFormat>("format")
+                .copied()
+                .or_else(|| ExportFormat::from_path(&path))
+                .unwrap_or_default();
+            export(&manager, queue_name(), path, format).await
+        }
+        "purge" => {
+            let older_than = args.get_one::<OffsetDateTime>("older-than").copied();
+            purge(&manager, queue_name(), older_than).await
+        }
+        "stats" => {
+            let queue_names = args
+                .get_many::<String>("queue")
+                .map(|names| names.cloned().collect())
+                .unwrap_or_default();
+            stats(&manager, queue_names).await
+        }
+        "move" => {
+            let target_queue = args.get_one::<String>("to").cloned().unwrap_or_default();
+            let count = args.get_one::<u32>("count").copied().unwrap_or(100);
+            move_messages(&manager, queue_name(), target_queue, count, resend_options).await
+        }
+        other => Err(CliError::Usage(format!("Unknown subcommand `{other}`"))),
+    }
+}
+
+pub(super) async fn run_command(
+    manager: &Manager,
+    command: ServiceBusCommand,
+) -> Result<ServiceBusResponse, CliError> {
+    match manager.lock().await.execute_command(command).await {
+        ServiceBusResponse::Error { error } => Err(CliError::Operation(error.to_string())),
+        response => Ok(response),
+    }
+}
+
+pub(super) fn unexpected(operation: &str, response: ServiceBusResponse) -> CliError {
+    log::error!("Unexpected response to {operation}: {response:?}");
+    CliError::Operation(format!("Unexpected response to {operation}"))
+}
+
+/// Make `queue_name` the current queue of the manager
+pub(super) async fn switch_queue(
+    manager: &Manager,
+    queue_name: &str,
+    session_id: Option<String>,
+) -> Result<(), CliError> {
+    run_command(
+        manager,
+        ServiceBusCommand::SwitchQueue {
+            queue_name: queue_name.to_string(),
+            queue_type: QueueType::from_queue_name(queue_name),
+            session_id,
+        },
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Peek up to `count` messages of the current queue, a page at a time
+pub(super) async fn peek_messages(
+    manager: &Manager,
+    count: u32,
+    mut from_sequence: Option<i64>,
+) -> Result<Vec<MessageModel>, CliError> {
+    let mut messages: Vec<MessageModel> = Vec::new();
+    while messages.len() < count as usize {
+        let max_count = count - messages.len() as u32;
+        let batch = match run_command(
+            manager,
+            ServiceBusCommand::PeekMessages {
+                max_count,
+                from_sequence,
+            },
+        )
+        .await?
+        {
+            ServiceBusResponse::MessagesReceived { messages } => messages,
+            response => return Err(unexpected("peek", response)),
+        };
+        let Some(last) = batch.last() else {
+            break;
+        };
+        from_sequence = Some(last.sequence + 1);
+        messages.extend(batch);
+    }
+    messages.truncate(count as usize);
+    Ok(messages)
+}
+
+async fn peek(
+    manager: &Manager,
+    queue_name: String,
+    count: u32,
+    from_sequence: Option<i64>,
+    session_id: Option<String>,
+) -> Result<Outcome, CliError> {
+    switch_queue(manager, &queue_name, session_id).await?;
+    let messages = peek_messages(manager, count, from_sequence).await?;
+    Ok(Outcome::complete(json!({
+        "queue": queue_name,
+        "count": messages.len(),
+        "messages": messages,
+    })))
+}
+
+/// Build the message to send from `--body`, `--file` or stdin, and the
+/// property options
+fn message_from_args(args: &clap::ArgMatches) -> Result<MessageData, CliError> {
+    let mut message = match (
+        args.get_one::<String>("body"),
+        args.get_one::<String>("file"),
+    ) {
+        (Some(body), _) => MessageData::new(body.clone()),
+        (None, Some(file)) => MessageData::from_bytes(
+            std::fs::read(file)
+                .map_err(|e| CliError::Operation(format!("Failed to read {file}: {e}")))?,
+        ),
+        (None, None) => {
+            let mut body = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut body)
+                .map_err(|e| CliError::Operation(format!("Failed to read stdin: {e}")))?;
+            MessageData::from_bytes(body)
+        }
+    };
+
+    if let Some(properties) = args.get_many::<(String, PropertyValue)>("property") {
+        message.application_properties.extend(properties.cloned());
+    }
+    let system = &mut message.system_properties;
+    system.content_type = args.get_one::<String>("content-type").cloned();
+    system.correlation_id = args.get_one::<String>("correlation-id").cloned();
+    system.session_id = args.get_one::<String>("session-id").cloned();
+    Ok(message)
+}
+
+async fn send(
+    manager: &Manager,
+    queue_name: String,
+    message: MessageData,
+) -> Result<Outcome, CliError> {
+    match run_command(
+        manager,
+        ServiceBusCommand::SendMessage {
+            queue_name: queue_name.clone(),
+            message,
+        },
+    )
+    .await?
+    {
+        ServiceBusResponse::MessageSent { .. } => {
+            Ok(Outcome::complete(json!({ "queue": queue_name, "sent": 1 })))
+        }
+        response => Err(unexpected("send", response)),
+    }
+}
+
+async fn export(
+    manager: &Manager,
+    queue_name: String,
+    path: PathBuf,
+    format: ExportFormat,
+) -> Result<Outcome, CliError> {
+    let result = match run_command(
+        manager,
+        ServiceBusCommand::ExportMessages {
+            queue_name: queue_name.clone(),
+            path,
+            format,
+            scope: ExportScope::All,
+            progress: None,
+            cancel_token: CancellationToken::new(),
+        },
+    )
+    .await?
+    {
+        ServiceBusResponse::MessagesExported { result, .. } => result,
+        response => return Err(unexpected("export", response)),
+    };
+
+    Ok(Outcome {
+        output: json!({
+            "queue": queue_name,
+            "path": result.path,
+            "format": result.format.to_string(),
+            "exported": result.exported,
+            "scanned": result.scanned,
+            "elapsed_ms": result.elapsed.as_millis() as u64,
+        }),
+        complete: !result.cancelled,
+    })
+}
+
+async fn purge(
+    manager: &Manager,
+    queue_name: String,
+    older_than: Option<OffsetDateTime>,
+) -> Result<Outcome, CliError> {
+    let result = match run_command(
+        manager,
+        ServiceBusCommand::PurgeQueue {
+            queue_name: queue_name.clone(),
+            older_than,
+            progress: None,
+            cancel_token: CancellationToken::new(),
+        },
+    )
+    .await?
+    {
+        ServiceBusResponse::QueuePurged { result, .. } => result,
+        response => return Err(unexpected("purge", response)),
+    };
+
+    Ok(Outcome {
+        output: json!({
+            "queue": queue_name,
+            "purged": result.purged,
+            "elapsed_ms": result.elapsed.as_millis() as u64,
+            "errors": result.errors,
+            "still_deferred": result.still_deferred,
+            "missed": result.missed,
+        }),
+        complete: result.errors.is_empty()
+            && !result.cancelled
+            && result.still_deferred.is_empty()
+            && result.missed.is_empty(),
+    })
+}
+
+/// Counts of each queue; a queue that cannot be read is reported with its
+/// error and fails the command
+async fn stats(manager: &Manager, queue_names: Vec<String>) -> Result<Outcome, CliError> {
+    let mut queues = Vec::with_capacity(queue_names.len());
+    let mut complete = true;
+    for queue_name in queue_names {
+        let response = run_command(
+            manager,
+            ServiceBusCommand::GetQueueDetails {
+                queue_name: queue_name.clone(),
+            },
+        )
+        .await;
+        queues.push(match response {
+            Ok(ServiceBusResponse::QueueDetails { details }) => json!({
+                "queue": queue_name,
+                "status": details.status,
+                "message_count": details.message_count,
+                "active_message_count": details.active_message_count,
+                "dead_letter_message_count": details.dead_letter_message_count,
+                "scheduled_message_count": details.scheduled_message_count,
+                "transfer_message_count": details.transfer_message_count,
+                "size_in_bytes": details.size_in_bytes,
+            }),
+            Ok(response) => {
+                complete = false;
+                json!({ "queue": queue_name, "error": unexpected("stats", response).message() })
+            }
+            Err(error) => {
+                complete = false;
+                json!({ "queue": queue_name, "error": error.message() })
+            }
+        });
+    }
+    Ok(Outcome {
+        output: json!({ "queues": queues }),
+        complete,
+    })
+}
+
+/// Send the first `count` messages of a queue to `target_queue` and delete
+/// them from the source, like the TUI's move to another queue
+async fn move_messages(
+    manager: &Manager,
+    queue_name: String,
+    target_queue: String,
+    count: u32,
+    resend_options: &ResendOptions,
+) -> Result<Outcome, CliError> {
+    switch_queue(manager, &queue_name, None).await?;
+    let message_ids: Vec<MessageIdentifier> = peek_messages(manager, count, None)
+        .await?
+        .iter()
+        .map(MessageIdentifier::from_message)
+        .collect();
+
+    let max_position = message_ids.len();
+    let result = send_and_delete(
+        manager,
+        &queue_name,
+        message_ids,
+        &target_queue,
+        max_position,
+        resend_options,
+    )
+    .await?;
+
+    Ok(Outcome {
+        output: json!({
+            "source": queue_name,
+            "target": target_queue,
+            "requested": result.total_requested,
+            "moved": result.successful,
+            "failed": result.failed,
+            "not_found": result.not_found,
+            "errors": result.error_details,
+            "still_deferred": result.still_deferred,
+        }),
+        complete: result.failed == 0 && result.not_found == 0 && result.still_deferred.is_empty(),
+    })
+}
+
+/// Send messages of `queue_name` to `target_queue` and delete them from the
+/// source; `max_position` is how far into the source they may be
+pub(super) async fn send_and_delete(
+    manager: &Manager,
+    queue_name: &str,
+    message_ids: Vec<MessageIdentifier>,
+    target_queue: &str,
+    max_position: usize,
+    resend_options: &ResendOptions,
+) -> Result<BulkOperationResult, CliError> {
+    if message_ids.is_empty() {
+        return Ok(BulkOperationResult::new(0));
+    }
+    switch_queue(manager, queue_name, None).await?;
+    match run_command(
+        manager,
+        ServiceBusCommand::BulkSend {
+            message_ids,
+            target_queue: target_queue.to_string(),
+            target_namespace: None,
+            should_delete_source: true,
+            repeat_count: 1,
+            max_position,
+            resend_options: resend_options.clone(),
+        },
+    )
+    .await?
+    {
+        ServiceBusResponse::BulkOperationCompleted { result } => Ok(result),
+        response => Err(unexpected("move", response)),
+    }
+}
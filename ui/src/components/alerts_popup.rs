@@ -0,0 +1,294 @@
+use crate::components::common::{Msg, QueueActivityMsg};
+use crate::components::state::ComponentState;
+use crate::config::alerts::AlertThreshold;
+use crate::theme::ThemeManager;
+use chrono::{DateTime, Local};
+use tuirealm::{
+    Component, Event, MockComponent, NoUserEvent,
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::{
+        Frame,
+        layout::{Alignment, Constraint, Direction, Layout, Rect},
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+    },
+};
+
+/// A configured threshold and what the last check found
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertStatus {
+    pub queue_name: String,
+    pub threshold: AlertThreshold,
+    /// Count the threshold compares at the last check; `None` until the
+    /// queue has been checked or when its counts could not be read
+    pub value: Option<u64>,
+    /// When the threshold was first found breached, while it stays breached
+    pub breached_since: Option<DateTime<Local>>,
+}
+
+impl AlertStatus {
+    pub fn new(queue_name: String, threshold: AlertThreshold) -> Self {
+        Self {
+            queue_name,
+            threshold,
+            value: None,
+            breached_since: None,
+        }
+    }
+
+    pub fn is_breached(&self) -> bool {
+        self.breached_since.is_some()
+    }
+
+    /// Breached alerts are listed first, then those that could not be checked
+    fn rank(&self) -> u8 {
+        match (self.is_breached(), self.value) {
+            (true, _) => 0,
+            (false, None) => 1,
+            (false, Some(_)) => 2,
+        }
+    }
+}
+
+/// Badge shown in the top row while thresholds are breached; `unseen` when
+/// a breach was raised after the alerts were last shown
+pub fn alert_badge(breached: usize, unseen: bool) -> Option<Paragraph<'static>> {
+    if breached == 0 {
+        return None;
+    }
+    let key = crate::config::get_config_or_panic().keys().alerts();
+    let (fg, bg) = if unseen {
+        (Color::Black, ThemeManager::status_error())
+    } else {
+        (ThemeManager::status_warning(), Color::Reset)
+    };
+    let text = match breached {
+        1 => format!(" ⚠ 1 queue alert [{key}] "),
+        n => format!(" ⚠ {n} queue alerts [{key}] "),
+    };
+    Some(
+        Paragraph::new(Line::from(Span::styled(
+            text,
+            Style::default().fg(fg).bg(bg).add_modifier(Modifier::BOLD),
+        )))
+        .alignment(Alignment::Right),
+    )
+}
+
+/// Popup listing the configured queue thresholds, breached ones first.
+///
+/// # Events
+///
+/// - `KeyEvent::Up` / `KeyEvent::Down` (or `k` / `j`) - Select an alert
+/// - `KeyEvent::Enter` - Open the queue of the selected alert
+/// - `KeyEvent::Esc` - Close the popup
+///
+/// # Messages
+///
+/// Emits `QueueActivityMsg::AlertSelected`, `QueueActivityMsg::AlertQueueOpened`
+/// and `QueueActivityMsg::AlertsClosed`.
+pub struct AlertsPopup {
+    statuses: Vec<AlertStatus>,
+    selected: usize,
+    status: String,
+    is_mounted: bool,
+}
+
+impl AlertsPopup {
+    /// Creates a popup for the alerts, selecting `selected` in display order;
+    /// `status` describes the last check
+    pub fn new(mut statuses: Vec<AlertStatus>, selected: usize, status: String) -> Self {
+        statuses.sort_by_key(AlertStatus::rank);
+        let selected = selected.min(statuses.len().saturating_sub(1));
+        Self {
+            statuses,
+            selected,
+            status,
+            is_mounted: false,
+        }
+    }
+
+    fn item(status: &AlertStatus) -> ListItem<'static> {
+        let (marker, color) = match (status.is_breached(), status.value) {
+            (true, _) => ("●", ThemeManager::status_error()),
+            (false, None) => ("○", ThemeManager::text_muted()),
+            (false, Some(_)) => ("●", ThemeManager::status_success()),
+        };
+        let value = match status.value {
+            Some(value) => format!("now {value}"),
+            None => "not checked".to_string(),
+        };
+        let since = status
+            .breached_since
+            .map(|since| format!("since {}", since.format("%H:%M:%S")))
+            .unwrap_or_default();
+
+        ListItem::new(Line::from(vec![
+            Span::styled(format!("{marker} "), Style::default().fg(color)),
+            Span::styled(
+                format!("{:<32} ", status.queue_name),
+                Style::default()
+                    .fg(ThemeManager::text_primary())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{:<24} ", status.threshold.to_string()),
+                Style::default().fg(ThemeManager::text_primary()),
+            ),
+            Span::styled(format!("{value:<16} "), Style::default().fg(color)),
+            Span::styled(since, Style::default().fg(ThemeManager::text_muted())),
+        ]))
+    }
+
+    fn select(&mut self, selected: usize) -> Option<Msg> {
+        self.selected = selected;
+        Some(Msg::QueueActivity(QueueActivityMsg::AlertSelected(
+            selected,
+        )))
+    }
+}
+
+impl MockComponent for AlertsPopup {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ThemeManager::primary_accent()))
+            .title(" Queue Alerts ")
+            .title_alignment(Alignment::Center)
+            .title_style(
+                Style::default()
+                    .fg(ThemeManager::title_accent())
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        if self.statuses.is_empty() {
+            frame.render_widget(
+                Paragraph::new(Line::from(Span::styled(
+                    "No alert thresholds are configured. Add queues under [alerts] in config.toml.",
+                    Style::default().fg(ThemeManager::status_warning()),
+                ))),
+                chunks[0],
+            );
+        } else {
+            let list = List::new(self.statuses.iter().map(Self::item).collect::<Vec<_>>())
+                .highlight_style(
+                    Style::default()
+                        .fg(ThemeManager::selection_fg())
+                        .bg(ThemeManager::selection_bg())
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol("▶ ");
+            let mut state = ListState::default();
+            state.select(Some(self.selected));
+            frame.render_stateful_widget(list, chunks[0], &mut state);
+        }
+
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                self.status.clone(),
+                Style::default().fg(ThemeManager::text_muted()),
+            ))),
+            chunks[1],
+        );
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "↑/↓ select • Enter open queue • Esc close",
+                Style::default().fg(ThemeManager::text_muted()),
+            )))
+            .alignment(Alignment::Center),
+            chunks[2],
+        );
+    }
+
+    fn query(&self, _attr: tuirealm::Attribute) -> Option<tuirealm::AttrValue> {
+        None
+    }
+
+    fn attr(&mut self, _attr: tuirealm::Attribute, _value: tuirealm::AttrValue) {
+        // No attributes supported
+    }
+
+    fn state(&self) -> tuirealm::State {
+        tuirealm::State::None
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, NoUserEvent> for AlertsPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let Event::Keyboard(KeyEvent { code, .. }) = ev else {
+            return None;
+        };
+        match code {
+            Key::Esc => Some(Msg::QueueActivity(QueueActivityMsg::AlertsClosed)),
+            Key::Up | Key::Char('k') => self.select(self.selected.saturating_sub(1)),
+            Key::Down | Key::Char('j') => {
+                let last = self.statuses.len().saturating_sub(1);
+                self.select((self.selected + 1).min(last))
+            }
+            Key::Enter => {
+                let status = self.statuses.get(self.selected)?;
+                Some(Msg::QueueActivity(QueueActivityMsg::AlertQueueOpened(
+                    status.queue_name.clone(),
+                )))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ComponentState for AlertsPopup {
+    fn mount(&mut self) -> crate::error::AppResult<()> {
+        log::debug!("Mounting AlertsPopup component");
+
+        if self.is_mounted {
+            log::warn!("AlertsPopup is already mounted");
+            return Ok(());
+        }
+
+        self.is_mounted = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breached_alerts_are_listed_first() {
+        let threshold: AlertThreshold = "dlq_count > 0".parse().unwrap();
+        let mut ok = AlertStatus::new("orders".to_string(), threshold);
+        ok.value = Some(0);
+        let unchecked = AlertStatus::new("invoices".to_string(), threshold);
+        let mut breached = AlertStatus::new("payments".to_string(), threshold);
+        breached.value = Some(3);
+        breached.breached_since = Some(Local::now());
+
+        let popup = AlertsPopup::new(vec![ok, unchecked, breached], 5, String::new());
+        let names: Vec<_> = popup
+            .statuses
+            .iter()
+            .map(|status| status.queue_name.as_str())
+            .collect();
+        assert_eq!(names, ["payments", "invoices", "orders"]);
+        assert_eq!(popup.selected, 2);
+    }
+}
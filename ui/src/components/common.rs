@@ -1,9 +1,15 @@
 use crate::app::updates::messages::pagination::QueueStatsCache;
+use crate::app::updates::messages::transfer::MessageTransfer;
+use crate::components::message_details::envelope::EditedMessage;
+use crate::components::queue_dashboard::DashboardView;
 use crate::error::AppError;
-use quetty_server::bulk_operations::MessageIdentifier;
+use quetty_server::bulk_operations::{ImportOptions, MessageIdentifier};
 use quetty_server::model::MessageModel;
-use quetty_server::service_bus_manager::QueueInfo;
+use quetty_server::service_bus_manager::{
+    DeferredMessageAction, QueueDetails, QueueInfo, SubscriptionRule, TargetNamespace, TopicInfo,
+};
 use std::fmt;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -15,13 +21,20 @@ pub enum ComponentId {
     GlobalKeyWatcher,
     NamespacePicker,
     QueuePicker,
+    TopicPicker,
+    SessionPicker,
     Messages,
     MessageDetails,
     LoadingIndicator,
     ErrorPopup,
     SuccessPopup,
+    QueueInfoPopup,
+    SubscriptionRulesPopup,
+    QueueDashboard,
+    AlertsPopup,
     ConfirmationPopup,
     NumberInputPopup,
+    TextInputPopup,
     PageSizePopup,
     HelpScreen,
     ThemePicker,
@@ -39,6 +52,8 @@ impl fmt::Display for ComponentId {
             ComponentId::TextLabel => write!(f, "TextLabel"),
             ComponentId::NamespacePicker => write!(f, "NamespacePicker"),
             ComponentId::QueuePicker => write!(f, "QueuePicker"),
+            ComponentId::TopicPicker => write!(f, "TopicPicker"),
+            ComponentId::SessionPicker => write!(f, "SessionPicker"),
             ComponentId::Messages => write!(f, "Messages"),
             ComponentId::MessageDetails => write!(f, "MessageDetails"),
             ComponentId::GlobalKeyWatcher => write!(f, "GlobalKeyWatcher"),
@@ -46,8 +61,13 @@ impl fmt::Display for ComponentId {
             ComponentId::ConfirmationPopup => write!(f, "ConfirmationPopup"),
             ComponentId::ErrorPopup => write!(f, "ErrorPopup"),
             ComponentId::SuccessPopup => write!(f, "SuccessPopup"),
+            ComponentId::QueueInfoPopup => write!(f, "QueueInfoPopup"),
+            ComponentId::SubscriptionRulesPopup => write!(f, "SubscriptionRulesPopup"),
+            ComponentId::QueueDashboard => write!(f, "QueueDashboard"),
+            ComponentId::AlertsPopup => write!(f, "AlertsPopup"),
             ComponentId::HelpScreen => write!(f, "HelpScreen"),
             ComponentId::NumberInputPopup => write!(f, "NumberInputPopup"),
+            ComponentId::TextInputPopup => write!(f, "TextInputPopup"),
             ComponentId::PageSizePopup => write!(f, "PageSizePopup"),
             ComponentId::ThemePicker => write!(f, "ThemePicker"),
             ComponentId::ConfigScreen => write!(f, "ConfigScreen"),
@@ -59,7 +79,9 @@ impl fmt::Display for ComponentId {
     }
 }
 
+#[derive(Default)]
 pub enum Msg {
+    #[default]
     AppClose,
     ForceRedraw,
     Tick,
@@ -217,6 +239,112 @@ pub enum QueueActivityMsg {
     ExitQueueFinalized,
     /// Queue selected from manual entry mode - needs to exit editing mode first
     QueueSelectedFromManualEntry(String),
+    /// User asked to browse topics and their subscriptions
+    TopicPickerRequested,
+    /// Topics with their subscriptions have been discovered
+    TopicsLoaded(Vec<TopicInfo>),
+    /// User left the topic picker without selecting a subscription
+    TopicPickerClosed,
+    /// The selected queue requires a session before it can be opened
+    SessionRequired(String),
+    /// Sessions holding messages have been discovered for the queue
+    SessionsLoaded(Vec<String>),
+    /// User picked a session of the current queue
+    SessionSelected(String),
+    /// User left the session picker without selecting a session
+    SessionPickerClosed,
+    /// User asked to view and edit the state of the current session
+    EditSessionState,
+    /// Current session state has been read
+    SessionStateLoaded {
+        session_id: String,
+        state: Vec<u8>,
+    },
+    /// User submitted new state for the current session
+    SaveSessionState(String),
+    /// User asked to create a queue
+    CreateQueueRequested,
+    /// Name of the queue to create has been entered
+    CreateQueueNameEntered(String),
+    /// User asked to edit the settings of a queue
+    EditQueueRequested(String),
+    /// Current settings of the queue being edited have been read
+    QueueSettingsLoaded(Box<QueueDetails>),
+    /// Settings for the queue being created or edited have been entered
+    QueueSettingsEntered(String),
+    /// User asked to delete a queue
+    DeleteQueueRequested(String),
+    /// Queue name has been typed to confirm a create, edit or delete
+    QueueAdminConfirmed(String),
+    /// Queue create, edit or delete finished, with a message for the user
+    QueueAdminCompleted(String),
+    /// User asked to see the rules of the current subscription
+    SubscriptionRulesRequested,
+    /// Rules of the current subscription have been listed
+    SubscriptionRulesLoaded(Vec<SubscriptionRule>),
+    /// Subscription rules popup was closed
+    SubscriptionRulesClosed,
+    /// User asked to test a rule against the loaded messages
+    TestSubscriptionRule(String),
+    /// User asked to add a rule to the current subscription
+    AddSubscriptionRule,
+    /// Name of the rule to add has been entered
+    SubscriptionRuleNameEntered(String),
+    /// User asked to edit a rule
+    EditSubscriptionRule(String),
+    /// Filter of the rule being added or edited has been entered
+    SubscriptionRuleFilterEntered(String),
+    /// Action of the rule being added or edited has been entered
+    SubscriptionRuleActionEntered(String),
+    /// User asked to delete a rule
+    DeleteSubscriptionRule(String),
+    /// Deleting a rule has been confirmed
+    DeleteSubscriptionRuleConfirmed(String),
+    /// A rule was saved or deleted, with a message for the user
+    SubscriptionRuleChanged(String),
+    /// User asked for the dashboard of all queues
+    DashboardRequested,
+    /// Dashboard was closed
+    DashboardClosed,
+    /// Sorting, filter or selection of the dashboard changed
+    DashboardViewChanged(DashboardView),
+    /// Dashboard refresh is due, by the user or the refresh interval
+    DashboardRefreshRequested,
+    /// Queues of the namespace have been listed for a dashboard refresh
+    DashboardQueuesListed {
+        dashboard_id: u64,
+        queues: Vec<String>,
+    },
+    /// Counts of a queue have been read for a dashboard refresh
+    DashboardQueueLoaded {
+        dashboard_id: u64,
+        details: Box<QueueDetails>,
+    },
+    /// Listing or reading a queue for a dashboard refresh failed
+    DashboardRefreshFailed {
+        dashboard_id: u64,
+        queue_name: Option<String>,
+        error: String,
+    },
+    /// User chose a queue on the dashboard
+    DashboardQueueOpened(String),
+    /// Alert thresholds are due to be checked
+    AlertCheckDue,
+    /// Counts of a watched queue have been read; `None` when unavailable
+    AlertQueueChecked {
+        queue_name: String,
+        counts: Option<(u64, u64)>,
+    },
+    /// Every watched queue has been checked
+    AlertCheckFinished,
+    /// User asked for the alerts list
+    AlertsRequested,
+    /// Alerts list was closed
+    AlertsClosed,
+    /// Selection in the alerts list changed
+    AlertSelected(usize),
+    /// User chose the queue of an alert
+    AlertQueueOpened(String),
 }
 
 #[derive(Debug, PartialEq)]
@@ -394,9 +522,80 @@ pub enum MessageActivityMsg {
     BulkSendToDLQWithDelete(Vec<MessageIdentifier>),
     BulkResendFromDLQ(Vec<MessageIdentifier>, bool),
     BulkRemoveMessagesFromState(Vec<String>),
-    SendEditedMessage(String),
-    ReplaceEditedMessage(String, MessageIdentifier),
-    ReplaceEditedMessageConfirmed(String, MessageIdentifier, usize),
+    /// Cancel the selected (or highlighted) scheduled messages, after confirmation
+    CancelScheduledSelected,
+    /// Confirmed cancellation of scheduled messages
+    CancelScheduledMessages(Vec<MessageIdentifier>),
+    /// Defer the selected (or highlighted) active messages, or resend deferred
+    /// ones, after confirmation
+    DeferSelected,
+    /// Confirmed deferral of active messages
+    DeferMessages(Vec<MessageIdentifier>),
+    /// Confirmed settlement of deferred messages
+    SettleDeferredMessages(Vec<MessageIdentifier>, DeferredMessageAction),
+    /// Ask for the cutoff of a purge of the current queue or DLQ
+    PurgeQueueRequested,
+    /// Cutoff entered for a purge; empty purges every message
+    PurgeCutoffEntered(String),
+    /// Confirmed purge, optionally limited to messages enqueued before the cutoff
+    PurgeQueue(Option<time::OffsetDateTime>),
+    /// Ask for a SQL filter to search the current queue or DLQ with
+    SearchRequested,
+    /// Filter entered for a search; empty leaves the current search
+    SearchExpressionEntered(String),
+    /// Matches of the latest batch scanned by a running search
+    SearchProgress {
+        search_id: u64,
+        scanned: usize,
+        matches: Vec<MessageModel>,
+    },
+    /// A search walked the whole queue or stopped at the match limit
+    SearchFinished {
+        search_id: u64,
+        scanned: usize,
+        truncated: bool,
+    },
+    /// The running search was cancelled from the loading indicator
+    SearchCancelled,
+    /// Leave the search results and show the loaded pages again
+    ClearSearch,
+    /// Ask which messages of the current queue or DLQ to export
+    ExportRequested,
+    /// Scope entered for an export: selection, page or all
+    ExportScopeEntered(String),
+    /// File path entered for an export; the extension picks the format
+    ExportPathEntered(String),
+    /// Ask for an NDJSON export to replay into the current queue
+    ImportRequested,
+    /// File path entered for an import
+    ImportPathEntered(String),
+    /// Options entered for an import: ids, rate and dry-run
+    ImportOptionsEntered(String),
+    /// Confirmed import of a file with the given options
+    ImportMessages(PathBuf, ImportOptions),
+    /// Ask where to copy or move the selected messages in another namespace
+    TransferRequested,
+    /// Profile name or connection string entered as the target namespace
+    TransferTargetEntered(String),
+    /// Target namespace reached after signing in as its Azure AD profile
+    TransferTargetResolved(TargetNamespace),
+    /// Queue or topic entered in the target namespace
+    TransferQueueEntered(String),
+    /// Whether to copy or move, as entered
+    TransferModeEntered(String),
+    /// Confirmed copy or move to another namespace
+    TransferMessages(MessageTransfer),
+    /// Start following the current queue or DLQ for new messages, or stop
+    ToggleFollow,
+    /// Pause or resume follow mode
+    ToggleFollowPaused,
+    /// Interval tick of the follow run with this id
+    FollowTick(u64),
+    /// Show the runtime counts and configuration of the current queue
+    QueueInfoRequested,
+    SendEditedMessage(EditedMessage),
+    ReplaceEditedMessage(EditedMessage, MessageIdentifier),
+    ReplaceEditedMessageConfirmed(EditedMessage, MessageIdentifier, usize),
     ComposeNewMessage,
     SetMessageRepeatCount,
     UpdateRepeatCount(usize),
@@ -408,9 +607,13 @@ pub enum MessageActivityMsg {
         successful_count: usize,
         failed_count: usize,
         total_count: usize,
+        /// Non-target messages that could not be re-queued and stay deferred
+        still_deferred: Vec<i64>,
     },
     ForceReloadMessages,
     RefreshQueueStatistics,
+    /// Interval tick recording the open queue's statistics into its history
+    SampleQueueStatistics,
 }
 
 #[derive(Debug, PartialEq)]
@@ -430,6 +633,8 @@ pub enum PopupActivityMsg {
     ShowWarning(String),
     ShowSuccess(String),
     CloseSuccess,
+    ShowQueueInfo(Box<QueueDetails>),
+    CloseQueueInfo,
     ShowConfirmation {
         title: String,
         message: String,
@@ -441,8 +646,16 @@ pub enum PopupActivityMsg {
         min_value: usize,
         max_value: usize,
     },
+    ShowTextInput {
+        title: String,
+        message: String,
+        initial_value: String,
+        on_submit: fn(String) -> Msg,
+    },
     ShowPageSizePopup,
     NumberInputResult(usize),
+    /// Message built from the submitted text, `None` when cancelled
+    TextInputResult(Option<Box<Msg>>),
     PageSizeResult(usize),
     ConfirmationResult(bool),
     ClosePageSize,
@@ -456,6 +669,8 @@ impl PartialEq for PopupActivityMsg {
             (PopupActivityMsg::ShowWarning(w1), PopupActivityMsg::ShowWarning(w2)) => w1 == w2,
             (PopupActivityMsg::ShowSuccess(s1), PopupActivityMsg::ShowSuccess(s2)) => s1 == s2,
             (PopupActivityMsg::CloseSuccess, PopupActivityMsg::CloseSuccess) => true,
+            (PopupActivityMsg::ShowQueueInfo(d1), PopupActivityMsg::ShowQueueInfo(d2)) => d1 == d2,
+            (PopupActivityMsg::CloseQueueInfo, PopupActivityMsg::CloseQueueInfo) => true,
             (PopupActivityMsg::ClosePageSize, PopupActivityMsg::ClosePageSize) => true,
             (
                 PopupActivityMsg::ConfirmationResult(b1),
@@ -467,14 +682,11 @@ impl PartialEq for PopupActivityMsg {
             (PopupActivityMsg::PageSizeResult(p1), PopupActivityMsg::PageSizeResult(p2)) => {
                 p1 == p2
             }
-            // ShowConfirmation, ShowNumberInput, and ShowPageSizePopup are not compared due to Box types
+            (PopupActivityMsg::TextInputResult(m1), PopupActivityMsg::TextInputResult(m2)) => {
+                m1 == m2
+            }
+            // ShowConfirmation, ShowNumberInput, ShowTextInput, and ShowPageSizePopup are not compared due to Box types
             _ => false,
         }
     }
 }
-
-impl Default for Msg {
-    fn default() -> Self {
-        Self::AppClose
-    }
-}
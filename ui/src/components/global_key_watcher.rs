@@ -1,4 +1,4 @@
-use crate::components::common::Msg;
+use crate::components::common::{Msg, QueueActivityMsg};
 use crate::config;
 use tui_realm_stdlib::Phantom;
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
@@ -50,6 +50,8 @@ impl Component<Msg, NoUserEvent> for GlobalKeyWatcher {
                     Some(Msg::MessageActivity(
                         crate::components::common::MessageActivityMsg::ForceReloadMessages,
                     ))
+                } else if c == keys.alerts() {
+                    Some(Msg::QueueActivity(QueueActivityMsg::AlertsRequested))
                 } else {
                     None
                 }
@@ -67,6 +69,9 @@ impl Component<Msg, NoUserEvent> for GlobalKeyWatcher {
                 // Handle Shift+C for config (uppercase C)
                 if c.eq_ignore_ascii_case(&keys.config()) {
                     Some(Msg::ToggleConfigScreen)
+                } else if c == keys.alerts() {
+                    // Symbols such as `!` are typed with shift on most layouts
+                    Some(Msg::QueueActivity(QueueActivityMsg::AlertsRequested))
                 } else {
                     None
                 }
@@ -76,6 +76,7 @@ impl HelpContent {
                     format!("[{}]", keys.refresh()),
                     "Refresh messages (reload from beginning)",
                 )
+                .add_single_key(format!("[{}]", keys.alerts()), "Show queue alerts")
                 .add_single_key("[Esc]".to_string(), "Go back / Cancel operation"),
             // Navigation Section
             HelpSection::new("NAVIGATION", "🧭")
@@ -89,6 +90,21 @@ impl HelpContent {
                     "Select / Open item",
                 )
                 .add_single_key("[PgUp] [PgDn]".to_string(), "Scroll page up/down"),
+            // Queue Picker Section
+            HelpSection::new("QUEUE PICKER", "🗂️")
+                .add_single_key(format!("[{}]", keys.create_queue()), "Create a queue")
+                .add_single_key(
+                    format!("[{}]", keys.edit_queue()),
+                    "Edit settings of the highlighted queue",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.delete_queue()),
+                    "Delete the highlighted queue",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.queue_dashboard()),
+                    "Dashboard of all queues",
+                ),
             // Queue & Message Management Section
             HelpSection::new("QUEUE & MESSAGE MANAGEMENT", "📋")
                 .add_multiple_keys(
@@ -113,6 +129,10 @@ impl HelpContent {
                     format!("[{}]", keys.toggle_dlq()),
                     "Toggle Main ↔ Dead Letter Queue",
                 )
+                .add_single_key(
+                    format!("[{}]", keys.session_state()),
+                    "View/edit session state",
+                )
                 .add_single_key("[Enter]".to_string(), "View message details"),
             // Message Composition Section
             HelpSection::new("MESSAGE COMPOSITION", "✍️")
@@ -139,7 +159,10 @@ impl HelpContent {
                     "[Ctrl+Shift+A]".to_string(),
                     "Select all loaded messages (all pages)",
                 )
-                .add_single_key("[Esc]".to_string(), "Clear selections / Exit bulk mode"),
+                .add_single_key(
+                    "[Esc]".to_string(),
+                    "Clear selections / Exit bulk mode / Leave search",
+                ),
             // Message Operations Section
             HelpSection::new("MESSAGE OPERATIONS", "⚡")
                 .add_multiple_keys(
@@ -154,6 +177,50 @@ impl HelpContent {
                 .add_single_key(
                     format!("[{}]", keys.resend_and_delete_from_dlq()),
                     "Resend and delete from DLQ (⚠️ DEV)",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.cancel_scheduled()),
+                    "Cancel scheduled message(s)",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.defer_message()),
+                    "Defer message(s) / resend deferred message(s)",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.purge_queue()),
+                    "Purge all messages of the queue or DLQ (⚠️ DEV)",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.search_messages()),
+                    "Search the whole queue or DLQ with a SQL filter",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.export_messages()),
+                    "Export selection, page or queue to NDJSON/JSON/CSV",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.import_messages()),
+                    "Replay an NDJSON export into the queue",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.transfer_messages()),
+                    "Copy or move to a queue in another namespace",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.follow_messages()),
+                    "Follow the queue for new messages / stop following",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.pause_follow()),
+                    "Pause / resume follow mode",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.queue_info()),
+                    "Show queue counts and settings",
+                )
+                .add_single_key(
+                    format!("[{}]", keys.subscription_rules()),
+                    "List, edit and test subscription rules",
                 ),
             // Add note as a special section
             HelpSection::new("Note", "💡")
@@ -183,6 +250,10 @@ impl HelpContent {
                     ],
                     "Copy message content to clipboard",
                 )
+                .add_single_key(
+                    format!("[{}]", keys.toggle_body_view()),
+                    "Cycle body views: decoded, text, hex, base64",
+                )
                 .add_single_key(
                     "[e] [i]".to_string(),
                     "Enter edit mode to modify message content",
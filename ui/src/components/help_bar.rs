@@ -120,6 +120,32 @@ impl HelpBar {
                     (" Navigate ".to_string(), false),
                     (format!("[{}]", keys.queue_select()), true),
                     (" Select ".to_string(), false),
+                    ("[t]".to_string(), true),
+                    (" Topics ".to_string(), false),
+                    ("[Esc]".to_string(), true),
+                    (" Back ".to_string(), false),
+                ]
+            }
+            ComponentId::TopicPicker => {
+                let keys = config::get_config_or_panic().keys();
+                vec![
+                    (format!("[{}/{}]", keys.up(), keys.down()), true),
+                    (" Navigate ".to_string(), false),
+                    (format!("[{}]", keys.queue_select()), true),
+                    (" Open ".to_string(), false),
+                    ("[Esc]".to_string(), true),
+                    (" Back ".to_string(), false),
+                ]
+            }
+            ComponentId::SessionPicker => {
+                let keys = config::get_config_or_panic().keys();
+                vec![
+                    (format!("[{}/{}]", keys.up(), keys.down()), true),
+                    (" Navigate ".to_string(), false),
+                    (format!("[{}]", keys.queue_select()), true),
+                    (" Open ".to_string(), false),
+                    ("[m]".to_string(), true),
+                    (" Enter ID ".to_string(), false),
                     ("[Esc]".to_string(), true),
                     (" Back ".to_string(), false),
                 ]
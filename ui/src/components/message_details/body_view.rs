@@ -0,0 +1,223 @@
+use base64::{Engine as _, engine::general_purpose};
+use quetty_server::decoding::DecodedBody;
+use quetty_server::model::BodyData;
+
+/// Bytes shown on each line of a hex dump
+const HEX_BYTES_PER_LINE: usize = 16;
+
+/// Characters shown on each line of the base64 view
+const BASE64_LINE_WIDTH: usize = 76;
+
+/// How a message body is displayed in the details pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyView {
+    /// Output of the body decoders
+    Decoded,
+    /// Pretty-printed JSON or plain text
+    Text,
+    /// Offset, hex bytes and printable ASCII, like `hexdump -C`
+    Hex,
+    /// Standard base64, wrapped at 76 characters
+    Base64,
+}
+
+impl BodyView {
+    /// Views available for a body, the default first.
+    ///
+    /// Decoder output comes first when a decoder applied. Binary bodies are
+    /// otherwise shown as raw bytes and text bodies as text.
+    pub fn available(body: &BodyData, has_decoded: bool) -> Vec<BodyView> {
+        let mut views = Vec::with_capacity(3);
+        if has_decoded {
+            views.push(BodyView::Decoded);
+        }
+        if body.is_binary() {
+            views.extend([BodyView::Hex, BodyView::Base64]);
+        } else {
+            views.push(BodyView::Text);
+        }
+        views
+    }
+
+    /// The view after this one in `views`, wrapping around
+    pub fn next_in(self, views: &[BodyView]) -> BodyView {
+        let position = views.iter().position(|view| *view == self).unwrap_or(0);
+        views
+            .get((position + 1) % views.len().max(1))
+            .copied()
+            .unwrap_or(self)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BodyView::Decoded => "decoded",
+            BodyView::Text => "text",
+            BodyView::Hex => "hex",
+            BodyView::Base64 => "base64",
+        }
+    }
+}
+
+/// Outcome of running the body decoders on a message
+pub type DecodedView = Result<DecodedBody, String>;
+
+/// Format a message body for display in the given view. The hex and base64
+/// views show `raw_body`, the bytes as received.
+pub fn format_body(
+    body: &BodyData,
+    raw_body: &[u8],
+    decoded: Option<&DecodedView>,
+    view: BodyView,
+) -> Vec<String> {
+    match view {
+        BodyView::Decoded => match decoded {
+            Some(Ok(decoded)) => format_text(&decoded.body),
+            Some(Err(e)) => {
+                // Show what went wrong above the undecoded body
+                let mut lines = vec![format!("⚠️  Body decoding failed: {e}"), String::new()];
+                lines.extend(format_text(body));
+                lines
+            }
+            None => format_text(body),
+        },
+        BodyView::Text => format_text(body),
+        BodyView::Hex => hex_dump(raw_body),
+        BodyView::Base64 => base64_lines(raw_body),
+    }
+}
+
+/// Pretty-printed JSON, text lines, or a hex dump for binary content
+fn format_text(body: &BodyData) -> Vec<String> {
+    match body {
+        BodyData::ValidJson(json) => match serde_json::to_string_pretty(json) {
+            Ok(json_str) => json_str.lines().map(String::from).collect(),
+            Err(e) => vec![format!("JSON formatting error: {e}")],
+        },
+        BodyData::RawString(body_str) => body_str.lines().map(String::from).collect(),
+        BodyData::Binary(bytes) => hex_dump(bytes),
+    }
+}
+
+/// Format bytes as a canonical hex dump with offsets and an ASCII column
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(HEX_BYTES_PER_LINE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut hex = String::with_capacity(HEX_BYTES_PER_LINE * 3 + 1);
+            for position in 0..HEX_BYTES_PER_LINE {
+                if position == HEX_BYTES_PER_LINE / 2 {
+                    hex.push(' ');
+                }
+                match chunk.get(position) {
+                    Some(byte) => hex.push_str(&format!("{byte:02x} ")),
+                    None => hex.push_str("   "),
+                }
+            }
+
+            let ascii: String = chunk
+                .iter()
+                .map(|&byte| {
+                    if byte.is_ascii_graphic() || byte == b' ' {
+                        byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+
+            format!("{:08x}  {}|{}|", index * HEX_BYTES_PER_LINE, hex, ascii)
+        })
+        .collect()
+}
+
+/// Encode bytes as base64 split into fixed-width lines
+pub fn base64_lines(bytes: &[u8]) -> Vec<String> {
+    let encoded = general_purpose::STANDARD.encode(bytes);
+    // Base64 output is pure ASCII, so splitting on byte boundaries is safe
+    encoded
+        .as_bytes()
+        .chunks(BASE64_LINE_WIDTH)
+        .map(|line| String::from_utf8_lossy(line).into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_dump_layout() {
+        let bytes: Vec<u8> = (0x41..0x41 + 18).collect();
+        let lines = hex_dump(&bytes);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "00000000  41 42 43 44 45 46 47 48  49 4a 4b 4c 4d 4e 4f 50 |ABCDEFGHIJKLMNOP|"
+        );
+        // Short last lines keep the ASCII column aligned
+        assert_eq!(lines[1], format!("00000010  51 52 {}|QR|", " ".repeat(43)));
+        assert_eq!(lines[0].len(), lines[1].len() + 14);
+    }
+
+    #[test]
+    fn test_hex_dump_masks_non_printable_bytes() {
+        let lines = hex_dump(&[0x1f, 0x8b, b'a', b' ', 0x00]);
+        assert!(lines[0].ends_with("|..a .|"));
+    }
+
+    #[test]
+    fn test_base64_view_wraps_lines() {
+        let bytes = vec![0xffu8; 120];
+        let lines = format_body(
+            &BodyData::Binary(bytes.clone()),
+            &bytes,
+            None,
+            BodyView::Base64,
+        );
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[..2].iter().all(|line| line.len() == 76));
+        assert_eq!(
+            general_purpose::STANDARD.decode(lines.concat()).unwrap(),
+            bytes
+        );
+    }
+
+    #[test]
+    fn test_hex_view_shows_json_bodies_as_received() {
+        let raw = b"{ \"id\":  1 }";
+        let lines = format_body(&BodyData::from_bytes(raw), raw, None, BodyView::Hex);
+        assert!(lines[0].ends_with("|{ \"id\":  1 }|"));
+    }
+
+    #[test]
+    fn test_available_views() {
+        let binary = BodyData::Binary(vec![0xff]);
+        let text = BodyData::RawString("hello".to_string());
+
+        assert_eq!(
+            BodyView::available(&binary, false),
+            vec![BodyView::Hex, BodyView::Base64]
+        );
+        assert_eq!(
+            BodyView::available(&text, true),
+            vec![BodyView::Decoded, BodyView::Text]
+        );
+
+        let views = BodyView::available(&binary, true);
+        assert_eq!(BodyView::Decoded.next_in(&views), BodyView::Hex);
+        assert_eq!(BodyView::Base64.next_in(&views), BodyView::Decoded);
+    }
+
+    #[test]
+    fn test_decoding_failure_is_shown_above_the_body() {
+        let body = BodyData::RawString("raw".to_string());
+        let decoded = Err("gzip decoding failed: invalid header".to_string());
+        let lines = format_body(&body, b"raw", Some(&decoded), BodyView::Decoded);
+
+        assert!(lines[0].contains("invalid header"));
+        assert_eq!(lines.last().map(String::as_str), Some("raw"));
+    }
+}
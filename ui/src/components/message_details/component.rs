@@ -1,7 +1,9 @@
+use super::body_view::{BodyView, DecodedView, format_body};
+use super::envelope::EditedMessage;
 use crate::components::common::Msg;
 use crate::components::state::ComponentState;
 use crate::error::AppError;
-use quetty_server::model::{BodyData, MessageModel};
+use quetty_server::model::MessageModel;
 use tuirealm::{
     AttrValue, Attribute, Component, Frame, MockComponent, NoUserEvent, State, StateValue,
     command::{Cmd, CmdResult},
@@ -18,9 +20,11 @@ pub struct MessageDetails {
     pub cursor_col: usize,
     pub is_focused: bool,
     pub visible_lines: usize,
-    pub is_editing: bool,            // Track if we're in edit mode
-    pub is_dirty: bool,              // Track if content has been modified
+    pub is_editing: bool,                  // Track if we're in edit mode
+    pub is_dirty: bool,                    // Track if content has been modified
     pub repeat_count: Option<usize>, // Track how many times message will be sent (for composition mode)
+    pub body_view: BodyView,         // How the body is displayed
+    pub decoded_body: Option<DecodedView>, // Output of the body decoders, if any applied
 }
 
 impl MessageDetails {
@@ -29,7 +33,14 @@ impl MessageDetails {
     }
 
     pub fn new_with_focus(message: Option<MessageModel>, is_focused: bool) -> Self {
-        let message_content = Self::format_message_content(&message);
+        let body_view = match &message {
+            Some(message) => BodyView::available(&message.body, false)[0],
+            None => BodyView::Text,
+        };
+        let message_content = match &message {
+            Some(data) => format_body(&data.body, &data.raw_body, None, body_view),
+            None => vec!["No message selected".to_string()],
+        };
         let original_content = message_content.clone();
 
         Self {
@@ -44,6 +55,8 @@ impl MessageDetails {
             is_editing: false,
             is_dirty: false,
             repeat_count: None,
+            body_view,
+            decoded_body: None,
         }
     }
 
@@ -68,43 +81,129 @@ impl MessageDetails {
             is_editing: true, // Start in edit mode for composition
             is_dirty: false,
             repeat_count: Some(repeat_count),
+            body_view: BodyView::Text,
+            decoded_body: None,
         }
     }
 
-    /// Format message content based on the message data type
-    fn format_message_content(message: &Option<MessageModel>) -> Vec<String> {
-        match message {
-            Some(data) => {
-                match &data.body {
-                    BodyData::ValidJson(json) => {
-                        // If it's valid JSON, show it pretty-printed
-                        match serde_json::to_string_pretty(json) {
-                            Ok(json_str) => json_str.lines().map(String::from).collect(),
-                            Err(e) => vec![format!("JSON formatting error: {}", e)],
-                        }
-                    }
-                    BodyData::RawString(body_str) => {
-                        // Show raw string with line breaks
-                        body_str.lines().map(String::from).collect()
-                    }
-                }
-            }
+    /// Attach the output of the body decoders and show it by default.
+    pub fn with_decoded_body(mut self, decoded: Option<DecodedView>) -> Self {
+        self.decoded_body = decoded;
+        if let Some(view) = self.available_views().first() {
+            self.body_view = *view;
+        }
+        self.message_content = self.format_message_content();
+        self.original_content = self.message_content.clone();
+        self
+    }
+
+    /// Format message content based on the message data type and selected view
+    fn format_message_content(&self) -> Vec<String> {
+        match &self.current_message {
+            Some(data) => format_body(
+                &data.body,
+                &data.raw_body,
+                self.decoded_body.as_ref(),
+                self.body_view,
+            ),
             None => vec!["No message selected".to_string()],
         }
     }
 
+    /// Views the current message body can be shown in, the default first
+    pub fn available_views(&self) -> Vec<BodyView> {
+        match &self.current_message {
+            Some(message) if self.repeat_count.is_none() => {
+                BodyView::available(&message.body, self.decoded_body.is_some())
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether the current message has a binary body, which cannot be edited as text
+    pub fn has_binary_body(&self) -> bool {
+        self.repeat_count.is_none()
+            && self
+                .current_message
+                .as_ref()
+                .is_some_and(|message| message.body.is_binary())
+    }
+
+    /// Whether the decoded body can be edited and encoded back into the
+    /// format of the original body before sending
+    pub fn can_edit_decoded(&self) -> bool {
+        self.repeat_count.is_none()
+            && matches!(
+                &self.decoded_body,
+                Some(Ok(decoded)) if decoded.encodable && !decoded.body.is_binary()
+            )
+    }
+
+    /// Label of the selected view for the status bar, including the decoders
+    /// that produced decoded output
+    pub fn body_view_label(&self) -> String {
+        match (&self.body_view, &self.decoded_body) {
+            (BodyView::Decoded, Some(Ok(decoded))) => {
+                format!("decoded: {}", decoded.steps.join(" → "))
+            }
+            (BodyView::Decoded, Some(Err(_))) => "decoding failed".to_string(),
+            (view, _) => view.label().to_string(),
+        }
+    }
+
+    /// Switch to the next body view.
+    ///
+    /// Returns false if the body has only one view or is being edited.
+    pub fn cycle_body_view(&mut self) -> bool {
+        let views = self.available_views();
+        if views.len() < 2 || self.is_editing {
+            return false;
+        }
+        self.set_body_view(self.body_view.next_in(&views));
+        true
+    }
+
+    /// Show the body in `view` and reset the cursor
+    pub fn set_body_view(&mut self, view: BodyView) {
+        self.body_view = view;
+        self.message_content = self.format_message_content();
+        self.original_content = self.message_content.clone();
+        self.cursor_line = 0;
+        self.cursor_col = 0;
+        self.scroll_offset = 0;
+    }
+
     /// Get current edited content as string
     pub fn get_edited_content(&self) -> String {
         self.message_content.join("\n")
     }
 
-    /// Validate message content before sending
+    /// Decoders the edited content is encoded with before sending; empty
+    /// unless the decoded body is shown
+    pub fn encode_steps(&self) -> &[String] {
+        match (&self.body_view, &self.decoded_body) {
+            (BodyView::Decoded, Some(Ok(decoded))) if self.can_edit_decoded() => &decoded.steps,
+            _ => &[],
+        }
+    }
+
+    /// Edited content ready to be sent, marked for encoding when the decoded
+    /// body was edited
+    pub fn edited_message(&self) -> EditedMessage {
+        EditedMessage {
+            content: self.get_edited_content(),
+            encode_with: self.encode_steps().to_vec(),
+        }
+    }
+
+    /// Validate message content (property header block and body) before sending
     pub fn validate_message_content(&self, content: &str) -> Result<(), AppError> {
         use super::validation::CompleteMessageValidator;
         use crate::validation::Validator;
 
+        let message = super::envelope::parse_composed_message(content)?;
         let validator = CompleteMessageValidator::azure_default();
-        validator.validate(content).map_err(Into::into)
+        validator.validate(&message.content).map_err(Into::into)
     }
 }
 
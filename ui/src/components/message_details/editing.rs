@@ -44,12 +44,13 @@ impl MessageDetails {
         }
 
         let line_idx = self.cursor_line + self.scroll_offset;
-        if let Some(line) = self.message_content.get_mut(line_idx) {
-            if self.cursor_col > 0 && self.cursor_col <= line.len() {
-                line.remove(self.cursor_col - 1);
-                self.cursor_col -= 1;
-                self.is_dirty = true;
-            }
+        if let Some(line) = self.message_content.get_mut(line_idx)
+            && self.cursor_col > 0
+            && self.cursor_col <= line.len()
+        {
+            line.remove(self.cursor_col - 1);
+            self.cursor_col -= 1;
+            self.is_dirty = true;
         }
     }
 
@@ -60,11 +61,11 @@ impl MessageDetails {
         }
 
         let line_idx = self.cursor_line + self.scroll_offset;
-        if let Some(line) = self.message_content.get_mut(line_idx) {
-            if self.cursor_col < line.len() {
-                line.remove(self.cursor_col);
-                self.is_dirty = true;
-            }
+        if let Some(line) = self.message_content.get_mut(line_idx)
+            && self.cursor_col < line.len()
+        {
+            line.remove(self.cursor_col);
+            self.is_dirty = true;
         }
     }
 
@@ -0,0 +1,255 @@
+use super::validation::MessageValidationError;
+use quetty_server::model::PropertyValue;
+use quetty_server::service_bus_manager::{MessageData, SystemProperties};
+use std::time::Duration;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+/// Line separating the property header block from the message body
+pub const HEADER_SEPARATOR: &str = "---";
+
+/// Editor content on its way to being sent
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditedMessage {
+    /// Optional property header block and the body, see [`parse_composed_message`]
+    pub content: String,
+    /// Decoders whose output the body was edited as, outermost first; the
+    /// body is encoded back through them before sending
+    pub encode_with: Vec<String>,
+}
+
+/// Split composed content into an optional property header block and the body.
+///
+/// A header block is only recognised when the content does not start with JSON
+/// and contains a `---` separator line, e.g.:
+///
+/// ```text
+/// CorrelationId: order-42
+/// tenant: "acme"
+/// retries: 3
+/// ---
+/// {"orderId": 42}
+/// ```
+pub fn split_header_block(content: &str) -> (Option<&str>, &str) {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        return (None, content);
+    }
+
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if line.trim_end() == HEADER_SEPARATOR {
+            let header = &content[..offset];
+            let body = &content[offset + line.len()..];
+            return (Some(header), body);
+        }
+        offset += line.len();
+    }
+
+    (None, content)
+}
+
+/// Parse composed editor content into message data with its properties.
+///
+/// Known system property names (MessageId, CorrelationId, SessionId,
+/// ContentType, Subject/Label, ReplyTo, TimeToLive/TTL,
+/// ScheduledEnqueueTime/DeliverAt) are matched case-insensitively; every other
+/// header becomes a typed application property.
+pub fn parse_composed_message(content: &str) -> Result<MessageData, MessageValidationError> {
+    let (header, body) = split_header_block(content);
+    let mut message = MessageData::new(body.to_string());
+
+    let Some(header) = header else {
+        return Ok(message);
+    };
+
+    for line in header.lines().filter(|line| !line.trim().is_empty()) {
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(MessageValidationError::invalid_property(
+                line.trim(),
+                "expected 'Name: value'",
+            ));
+        };
+        let name = name.trim();
+        let value = value.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(MessageValidationError::invalid_property(
+                name,
+                "property names cannot be empty or contain spaces",
+            ));
+        }
+
+        match apply_header(&mut message.system_properties, name, value) {
+            Some(Ok(())) => {}
+            Some(Err(reason)) => {
+                return Err(MessageValidationError::invalid_property(name, reason));
+            }
+            None => {
+                message
+                    .application_properties
+                    .insert(name.to_string(), parse_property_value(value));
+            }
+        }
+    }
+
+    Ok(message)
+}
+
+/// Apply a system property header. Returns `None` if `name` is not a system property.
+fn apply_header(
+    properties: &mut SystemProperties,
+    name: &str,
+    value: &str,
+) -> Option<Result<(), String>> {
+    let text = || Some(value.to_string());
+    match name.to_ascii_lowercase().as_str() {
+        "messageid" => properties.message_id = text(),
+        "correlationid" => properties.correlation_id = text(),
+        "sessionid" => properties.session_id = text(),
+        "contenttype" => properties.content_type = text(),
+        "subject" | "label" => properties.subject = text(),
+        "replyto" => properties.reply_to = text(),
+        "timetolive" | "ttl" => match parse_duration(value) {
+            Ok(ttl) => properties.time_to_live = Some(ttl),
+            Err(e) => return Some(Err(e)),
+        },
+        "scheduledenqueuetime" | "deliverat" => {
+            match parse_enqueue_time(value, OffsetDateTime::now_utc()) {
+                Ok(time) => properties.scheduled_enqueue_time = Some(time),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        _ => return None,
+    }
+    Some(Ok(()))
+}
+
+/// Infer a typed application property value from header text.
+///
+/// Quoted values are always strings; otherwise booleans, `null`, integers
+/// and floats are recognised before falling back to a plain string.
+pub fn parse_property_value(value: &str) -> PropertyValue {
+    if let Some(quoted) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        return PropertyValue::String(quoted.to_string());
+    }
+
+    match value {
+        "true" => PropertyValue::Bool(true),
+        "false" => PropertyValue::Bool(false),
+        "null" => PropertyValue::Null,
+        _ => {
+            if let Ok(int) = value.parse::<i64>() {
+                PropertyValue::Int(int)
+            } else if let Ok(double) = value.parse::<f64>() {
+                PropertyValue::Double(double)
+            } else {
+                PropertyValue::String(value.to_string())
+            }
+        }
+    }
+}
+
+/// Parse a duration given in seconds or with an `s`/`m`/`h`/`d` suffix
+pub(crate) fn parse_duration(value: &str) -> Result<Duration, String> {
+    let (number, multiplier) = match value.char_indices().last() {
+        Some((idx, 's')) => (&value[..idx], 1),
+        Some((idx, 'm')) => (&value[..idx], 60),
+        Some((idx, 'h')) => (&value[..idx], 3_600),
+        Some((idx, 'd')) => (&value[..idx], 86_400),
+        _ => (value, 1),
+    };
+
+    number
+        .trim()
+        .parse::<u64>()
+        .map(|amount| Duration::from_secs(amount * multiplier))
+        .map_err(|_| "expected seconds or a number with s/m/h/d suffix".to_string())
+}
+
+/// Parse a delivery time given either as an RFC 3339 timestamp or relative to
+/// `now` as `+<duration>` (e.g. `+15m`).
+fn parse_enqueue_time(value: &str, now: OffsetDateTime) -> Result<OffsetDateTime, String> {
+    if let Some(offset) = value.strip_prefix('+') {
+        return parse_duration(offset).map(|offset| now + offset);
+    }
+
+    OffsetDateTime::parse(value, &Rfc3339)
+        .map_err(|e| format!("expected an RFC 3339 timestamp or +<duration> ({e})"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_content_has_no_header() {
+        let message = parse_composed_message("{\"a\": 1}\n---\n").unwrap();
+        assert_eq!(message.content, "{\"a\": 1}\n---\n");
+        assert!(message.application_properties.is_empty());
+        assert!(message.system_properties.is_empty());
+    }
+
+    #[test]
+    fn test_header_block_is_parsed() {
+        let content =
+            "CorrelationId: order-42\nttl: 5m\nretries: 3\ntenant: \"007\"\n---\n{\"orderId\": 42}";
+        let message = parse_composed_message(content).unwrap();
+
+        assert_eq!(message.content, "{\"orderId\": 42}");
+        assert_eq!(
+            message.system_properties.correlation_id.as_deref(),
+            Some("order-42")
+        );
+        assert_eq!(
+            message.system_properties.time_to_live,
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            message.application_properties["retries"],
+            PropertyValue::Int(3)
+        );
+        assert_eq!(
+            message.application_properties["tenant"],
+            PropertyValue::String("007".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_header_is_rejected() {
+        assert!(parse_composed_message("not a header\n---\n{}").is_err());
+        assert!(parse_composed_message("TTL: soon\n---\n{}").is_err());
+        assert!(parse_composed_message("ScheduledEnqueueTime: tomorrow\n---\n{}").is_err());
+    }
+
+    #[test]
+    fn test_property_value_inference() {
+        assert_eq!(parse_property_value("true"), PropertyValue::Bool(true));
+        assert_eq!(parse_property_value("-7"), PropertyValue::Int(-7));
+        assert_eq!(parse_property_value("1.5"), PropertyValue::Double(1.5));
+        assert_eq!(
+            parse_property_value("eu-west"),
+            PropertyValue::String("eu-west".to_string())
+        );
+    }
+
+    #[test]
+    fn test_deliver_at_accepts_relative_and_absolute_times() {
+        let now = OffsetDateTime::parse("2024-05-01T10:00:00Z", &Rfc3339).unwrap();
+
+        assert_eq!(
+            parse_enqueue_time("+15m", now).unwrap(),
+            now + Duration::from_secs(900)
+        );
+        assert_eq!(
+            parse_enqueue_time("2024-05-02T08:30:00Z", now).unwrap(),
+            OffsetDateTime::parse("2024-05-02T08:30:00Z", &Rfc3339).unwrap()
+        );
+        assert!(parse_enqueue_time("tomorrow", now).is_err());
+
+        let message = parse_composed_message("DeliverAt: +1h\n---\nretry").unwrap();
+        assert!(message.system_properties.scheduled_enqueue_time.is_some());
+    }
+}
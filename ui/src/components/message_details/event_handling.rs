@@ -1,3 +1,4 @@
+use super::body_view::BodyView;
 use super::component::MessageDetails;
 use crate::components::common::{MessageActivityMsg, Msg, PopupActivityMsg};
 use crate::config;
@@ -30,8 +31,8 @@ pub fn handle_event(details: &mut MessageDetails, ev: Event<NoUserEvent>) -> Opt
         }) if c == config::get_config_or_panic().keys().send_edited_message() => {
             if details.is_editing && details.is_dirty {
                 // Validate content before sending
-                let edited_content = details.get_edited_content();
-                if let Err(validation_error) = details.validate_message_content(&edited_content) {
+                let edited = details.edited_message();
+                if let Err(validation_error) = details.validate_message_content(&edited.content) {
                     return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(
                         validation_error,
                     )));
@@ -39,7 +40,7 @@ pub fn handle_event(details: &mut MessageDetails, ev: Event<NoUserEvent>) -> Opt
 
                 // Send edited content as new message (keep original)
                 return Some(Msg::MessageActivity(MessageActivityMsg::SendEditedMessage(
-                    edited_content,
+                    edited,
                 )));
             } else if details.is_editing && !details.is_dirty {
                 return Some(Msg::PopupActivity(PopupActivityMsg::ShowSuccess(
@@ -59,8 +60,8 @@ pub fn handle_event(details: &mut MessageDetails, ev: Event<NoUserEvent>) -> Opt
             if details.is_editing && details.is_dirty {
                 if let Some(message) = &details.current_message {
                     // Validate content before replacing
-                    let edited_content = details.get_edited_content();
-                    if let Err(validation_error) = details.validate_message_content(&edited_content)
+                    let edited = details.edited_message();
+                    if let Err(validation_error) = details.validate_message_content(&edited.content)
                     {
                         return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(
                             validation_error,
@@ -70,7 +71,7 @@ pub fn handle_event(details: &mut MessageDetails, ev: Event<NoUserEvent>) -> Opt
                     // Replace original message with edited content
                     let message_id = message.id.clone();
                     return Some(Msg::MessageActivity(
-                        MessageActivityMsg::ReplaceEditedMessage(edited_content, message_id.into()),
+                        MessageActivityMsg::ReplaceEditedMessage(edited, message_id.into()),
                     ));
                 } else {
                     return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(
@@ -84,11 +85,48 @@ pub fn handle_event(details: &mut MessageDetails, ev: Event<NoUserEvent>) -> Opt
             }
         }
 
+        // Binary bodies are shown as hex or base64, so editing that text would
+        // corrupt the payload; only decoded content that can be encoded back
+        // is editable
+        Event::Keyboard(KeyEvent {
+            code: Key::Char('e') | Key::Char('i'),
+            modifiers: KeyModifiers::NONE,
+        }) if !details.is_editing && details.has_binary_body() && !details.can_edit_decoded() => {
+            return Some(Msg::PopupActivity(PopupActivityMsg::ShowError(
+                AppError::State(
+                    "Binary message bodies cannot be edited as text. Resend the message to send its original bytes unchanged.".to_string(),
+                ),
+            )));
+        }
+
+        // Cycle between the decoded, text, hex and base64 views of the body
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE,
+        }) if !details.is_editing
+            && details.available_views().len() > 1
+            && c == config::get_config_or_panic().keys().toggle_body_view() =>
+        {
+            details.cycle_body_view();
+        }
+
         // Toggle edit mode with 'e' or 'i' key (similar to vim)
         Event::Keyboard(KeyEvent {
             code: Key::Char('e') | Key::Char('i'),
             modifiers: KeyModifiers::NONE,
         }) if !details.is_editing => {
+            // Decoded content is edited when it can be encoded back into the
+            // body; otherwise edits apply to the body as sent
+            let view = if details.can_edit_decoded()
+                && (details.body_view == BodyView::Decoded || details.has_binary_body())
+            {
+                BodyView::Decoded
+            } else {
+                BodyView::Text
+            };
+            if details.body_view != view {
+                details.set_body_view(view);
+            }
             details.toggle_edit_mode();
             return Some(Msg::MessageActivity(MessageActivityMsg::EditingModeStarted));
         }
@@ -1,7 +1,10 @@
+pub mod body_view;
 pub mod component;
 pub mod editing;
+pub mod envelope;
 pub mod event_handling;
 pub mod navigation;
+pub mod properties;
 pub mod rendering;
 pub mod validation;
 
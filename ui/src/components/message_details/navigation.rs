@@ -38,10 +38,10 @@ impl MessageDetails {
     /// Move cursor right one character
     pub fn move_cursor_right(&mut self) {
         let current_line_idx = self.cursor_line + self.scroll_offset;
-        if let Some(line) = self.message_content.get(current_line_idx) {
-            if self.cursor_col < line.len() {
-                self.cursor_col += 1;
-            }
+        if let Some(line) = self.message_content.get(current_line_idx)
+            && self.cursor_col < line.len()
+        {
+            self.cursor_col += 1;
         }
     }
 
@@ -105,10 +105,10 @@ impl MessageDetails {
     /// Adjust cursor column if it's beyond the end of the current line
     pub fn adjust_cursor_column(&mut self) {
         let current_line_idx = self.cursor_line + self.scroll_offset;
-        if let Some(line) = self.message_content.get(current_line_idx) {
-            if self.cursor_col > line.len() {
-                self.cursor_col = line.len();
-            }
+        if let Some(line) = self.message_content.get(current_line_idx)
+            && self.cursor_col > line.len()
+        {
+            self.cursor_col = line.len();
         }
     }
 }
@@ -0,0 +1,107 @@
+use crate::theme::ThemeManager;
+use quetty_server::model::MessageModel;
+use tuirealm::ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+};
+
+/// Maximum share of the details pane the properties section may take (1/N)
+const MAX_HEIGHT_DIVISOR: u16 = 3;
+
+/// Build the display lines for the properties section of a message
+pub fn create_property_lines(message: &MessageModel) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let properties = &message.properties;
+
+    let system_entries = properties.system_property_entries();
+    let name_width = system_entries
+        .iter()
+        .map(|(name, _)| name.len())
+        .chain(
+            properties
+                .application_properties
+                .keys()
+                .map(|key| key.len()),
+        )
+        .max()
+        .unwrap_or(0);
+
+    for (name, value) in system_entries {
+        let value_style = if name.starts_with("DeadLetter") {
+            Style::default().fg(ThemeManager::status_error())
+        } else {
+            Style::default().fg(ThemeManager::text_primary())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{name:<name_width$}  "),
+                Style::default().fg(ThemeManager::header_accent()),
+            ),
+            Span::styled(value, value_style),
+        ]));
+    }
+
+    if !properties.application_properties.is_empty() {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            "Application properties",
+            Style::default()
+                .fg(ThemeManager::title_accent())
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (key, value) in &properties.application_properties {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{key:<name_width$}  "),
+                    Style::default().fg(ThemeManager::header_accent()),
+                ),
+                Span::styled(
+                    value.to_string(),
+                    Style::default().fg(ThemeManager::text_primary()),
+                ),
+                Span::styled(
+                    format!("  ({})", value.type_name()),
+                    Style::default().fg(ThemeManager::text_muted()),
+                ),
+            ]));
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No system or application properties set",
+            Style::default().fg(ThemeManager::text_muted()),
+        )));
+    }
+
+    lines
+}
+
+/// Height (including borders) the properties section needs within `area`
+pub fn section_height(line_count: usize, area: Rect) -> u16 {
+    let wanted = (line_count as u16).saturating_add(2);
+    wanted.min(area.height / MAX_HEIGHT_DIVISOR)
+}
+
+/// Create the properties paragraph shown above the message body
+pub fn create_properties_widget(lines: Vec<Line<'static>>) -> Paragraph<'static> {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ThemeManager::text_muted()))
+        .title(" 🏷️ Properties ")
+        .title_alignment(Alignment::Left)
+        .title_style(
+            Style::default()
+                .fg(ThemeManager::title_accent())
+                .add_modifier(Modifier::BOLD),
+        );
+
+    Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+}
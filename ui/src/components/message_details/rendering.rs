@@ -1,9 +1,10 @@
 use super::component::MessageDetails;
+use super::properties;
 use crate::theme::ThemeManager;
 use tuirealm::{
     Frame,
     ratatui::{
-        layout::{Alignment, Rect},
+        layout::{Alignment, Constraint, Layout, Rect},
         style::{Color, Modifier, Style},
         text::{Line, Span},
         widgets::{Block, BorderType, Borders, Paragraph, Wrap},
@@ -11,6 +12,9 @@ use tuirealm::{
 };
 
 pub fn render_message_details(details: &mut MessageDetails, frame: &mut Frame, area: Rect) {
+    // Show broker and application properties above the body for existing messages
+    let area = render_properties_section(details, frame, area);
+
     // Calculate available area for content (excluding borders)
     let content_height = area.height.saturating_sub(2); // 2 for borders only
     let visible_lines = content_height as usize;
@@ -33,8 +37,31 @@ pub fn render_message_details(details: &mut MessageDetails, frame: &mut Frame, a
     frame.render_widget(status_bar, status_area);
 }
 
+/// Render the properties section and return the remaining area for the body
+fn render_properties_section(details: &MessageDetails, frame: &mut Frame, area: Rect) -> Rect {
+    let Some(message) = details.current_message.as_ref() else {
+        return area;
+    };
+    if details.repeat_count.is_some() {
+        // Composition mode - the message is a template, not a received message
+        return area;
+    }
+
+    let lines = properties::create_property_lines(message);
+    let height = properties::section_height(lines.len(), area);
+    if height < 3 {
+        return area;
+    }
+
+    let [properties_area, body_area] =
+        Layout::vertical([Constraint::Length(height), Constraint::Min(0)]).areas(area);
+    frame.render_widget(properties::create_properties_widget(lines), properties_area);
+
+    body_area
+}
+
 /// Create the block widget with proper styling
-fn create_block(details: &MessageDetails) -> Block {
+fn create_block(details: &MessageDetails) -> Block<'_> {
     let border_color = if details.is_focused {
         if details.is_editing {
             Color::Red // Red border when editing
@@ -69,7 +96,7 @@ fn create_block(details: &MessageDetails) -> Block {
 }
 
 /// Create content lines for display
-fn create_content_lines(details: &MessageDetails, visible_lines: usize) -> Vec<Line> {
+fn create_content_lines(details: &MessageDetails, visible_lines: usize) -> Vec<Line<'_>> {
     let mut lines = Vec::new();
 
     // Calculate display range
@@ -158,7 +185,7 @@ fn create_cursor_highlighted_spans<'a>(
 }
 
 /// Create the status bar showing current position and mode
-fn create_status_bar(details: &MessageDetails) -> Paragraph {
+fn create_status_bar(details: &MessageDetails) -> Paragraph<'_> {
     let status_text = if details.is_editing {
         let keys = crate::config::get_config_or_panic().keys();
 
@@ -172,15 +199,32 @@ fn create_status_bar(details: &MessageDetails) -> Paragraph {
         } else {
             String::new()
         };
+        // Edited decoded content is encoded back before sending
+        let encode_info = match details.encode_steps() {
+            [] => String::new(),
+            steps => format!(" | Encodes with {}", steps.join(" → ")),
+        };
 
         format!(
-            "Ln {}, Col {} | EDIT MODE{} | Ctrl+{}: Send | Ctrl+{}: Replace | ESC: Cancel",
+            "Ln {}, Col {} | EDIT MODE{}{} | Ctrl+{}: Send | Ctrl+{}: Replace | ESC: Cancel",
             details.cursor_line + details.scroll_offset + 1,
             details.cursor_col + 1,
             repeat_info,
+            encode_info,
             keys.send_edited_message(),
             keys.replace_edited_message()
         )
+    } else if details.available_views().len() > 1 {
+        let keys = crate::config::get_config_or_panic().keys();
+        let views = details.available_views();
+        format!(
+            "Ln {}, Col {} | {} | {}: {} view | ESC: Back to messages",
+            details.cursor_line + details.scroll_offset + 1,
+            details.cursor_col + 1,
+            details.body_view_label().to_uppercase(),
+            keys.toggle_body_view(),
+            details.body_view.next_in(&views).label()
+        )
     } else {
         format!(
             "Ln {}, Col {} | Press 'e' or 'i' to edit | ESC: Back to messages",
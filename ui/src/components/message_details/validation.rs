@@ -12,6 +12,8 @@ pub enum MessageValidationError {
     InvalidJson { reason: String },
     /// Text encoding validation error
     InvalidCharacters { characters: String },
+    /// Property header validation error
+    InvalidProperty { name: String, reason: String },
 }
 
 impl MessageValidationError {
@@ -28,6 +30,11 @@ impl MessageValidationError {
                     "Message contains invalid characters!\n\nInvalid characters: {characters}\n\n\n                    Please remove these characters and try again."
                 )
             }
+            MessageValidationError::InvalidProperty { name, reason } => {
+                format!(
+                    "Invalid message property!\n\nProperty: {name}\nError: {reason}\n\n\n                    Please fix the property header and try again."
+                )
+            }
         }
     }
 
@@ -38,6 +45,14 @@ impl MessageValidationError {
         }
     }
 
+    /// Create an invalid property header error
+    pub fn invalid_property(name: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::InvalidProperty {
+            name: name.into(),
+            reason: reason.into(),
+        }
+    }
+
     /// Create an invalid characters error
     pub fn invalid_characters(characters: impl Into<String>) -> Self {
         Self::InvalidCharacters {
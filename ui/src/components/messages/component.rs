@@ -1,3 +1,4 @@
+use crate::app::managers::queue_stats_history::QueueStatsRates;
 use crate::components::base_popup::PopupBuilder;
 use crate::components::common::{Msg, QueueType};
 use crate::components::messages::rendering::{
@@ -8,6 +9,7 @@ use crate::components::messages::selection::{format_pagination_status, format_qu
 use crate::theme::ThemeManager;
 use quetty_server::bulk_operations::MessageIdentifier;
 use quetty_server::model::MessageModel;
+use std::collections::HashSet;
 use tui_realm_stdlib::Table;
 use tuirealm::command::{Cmd, CmdResult};
 use tuirealm::event::{Key, KeyEvent};
@@ -49,6 +51,53 @@ pub struct PaginationInfo {
     pub queue_total_messages: Option<u64>,
     /// Age of queue statistics in seconds (if available)
     pub queue_stats_age_seconds: Option<i64>,
+    /// Whole-queue search whose matches are listed instead of the loaded pages
+    pub search: Option<SearchStatus>,
+    /// Follow mode, while the queue is followed for new messages
+    pub follow: Option<FollowStatus>,
+    /// Recent statistics history, once the queue has been sampled twice
+    pub stats_trend: Option<StatsTrend>,
+}
+
+/// Recent statistics of the queue, drawn as sparklines in the title
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatsTrend {
+    /// Active message counts, oldest first
+    pub active_counts: Vec<u64>,
+    /// Dead letter message counts, oldest first
+    pub dlq_counts: Vec<u64>,
+    /// Estimated flow over the last minutes, if they hold two samples
+    pub rates: Option<QueueStatsRates>,
+}
+
+/// Progress of a whole-queue search, shown in the title and status line
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchStatus {
+    /// SQL filter expression of the search
+    pub expression: String,
+    /// Number of matches found so far
+    pub matched: usize,
+    /// Number of messages examined so far
+    pub scanned: usize,
+    /// Whether the search is still running
+    pub running: bool,
+    /// Whether the search stopped at the match limit
+    pub truncated: bool,
+    /// Whether the search was cancelled
+    pub cancelled: bool,
+}
+
+/// State of follow mode, shown in the title and status line
+#[derive(Debug, Clone, PartialEq)]
+pub struct FollowStatus {
+    /// Whether peeking is paused
+    pub paused: bool,
+    /// Number of messages that arrived while following
+    pub received: usize,
+    /// Arrival rate in messages per second
+    pub rate_per_second: f64,
+    /// Sequence numbers of the arrived messages, highlighted in the list
+    pub new_sequences: HashSet<i64>,
 }
 
 pub struct Messages {
@@ -296,11 +345,22 @@ impl MockComponent for Messages {
                     cells.push(Cell::from(checkbox_text));
                 }
 
+                // Messages that arrived in follow mode stand out by their sequence number
+                let is_new = self
+                    .pagination_info()
+                    .as_ref()
+                    .and_then(|info| info.follow.as_ref())
+                    .is_some_and(|follow| follow.new_sequences.contains(&msg.sequence));
+                let sequence_style = if is_new {
+                    RatatuiStyle::default()
+                        .fg(ThemeManager::status_success())
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    RatatuiStyle::default().fg(ThemeManager::message_sequence())
+                };
+
                 // Add the message data cells with proper theming
-                cells.push(
-                    Cell::from(msg.sequence.to_string())
-                        .style(RatatuiStyle::default().fg(ThemeManager::message_sequence())),
-                );
+                cells.push(Cell::from(msg.sequence.to_string()).style(sequence_style));
                 cells.push(
                     Cell::from(msg.id.to_string())
                         .style(RatatuiStyle::default().fg(ThemeManager::message_id())),
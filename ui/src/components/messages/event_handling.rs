@@ -175,6 +175,106 @@ pub fn handle_event(messages: &mut Messages, ev: Event<NoUserEvent>) -> Option<M
             return Some(Msg::QueueActivity(QueueActivityMsg::ToggleDeadLetterQueue));
         }
 
+        // Scheduled message cancellation (selection or current message)
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE,
+        }) if c == config::get_config_or_panic().keys().cancel_scheduled() => {
+            return Some(Msg::MessageActivity(
+                MessageActivityMsg::CancelScheduledSelected,
+            ));
+        }
+
+        // Defer active messages, or resend deferred ones (selection or current message)
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE,
+        }) if c == config::get_config_or_panic().keys().defer_message() => {
+            return Some(Msg::MessageActivity(MessageActivityMsg::DeferSelected));
+        }
+
+        // Purge the whole queue or DLQ
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) if c == config::get_config_or_panic().keys().purge_queue() => {
+            return Some(Msg::MessageActivity(
+                MessageActivityMsg::PurgeQueueRequested,
+            ));
+        }
+
+        // Search the whole queue or DLQ
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) if c == config::get_config_or_panic().keys().search_messages() => {
+            return Some(Msg::MessageActivity(MessageActivityMsg::SearchRequested));
+        }
+
+        // Export the selection, current page or whole queue to a file
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) if c == config::get_config_or_panic().keys().export_messages() => {
+            return Some(Msg::MessageActivity(MessageActivityMsg::ExportRequested));
+        }
+
+        // Replay an NDJSON export into the current queue
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) if c == config::get_config_or_panic().keys().import_messages() => {
+            return Some(Msg::MessageActivity(MessageActivityMsg::ImportRequested));
+        }
+
+        // Copy or move to a queue in another namespace
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) if c == config::get_config_or_panic().keys().transfer_messages() => {
+            return Some(Msg::MessageActivity(MessageActivityMsg::TransferRequested));
+        }
+
+        // Follow the queue for new messages
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) if c == config::get_config_or_panic().keys().follow_messages() => {
+            return Some(Msg::MessageActivity(MessageActivityMsg::ToggleFollow));
+        }
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) if c == config::get_config_or_panic().keys().pause_follow() => {
+            return Some(Msg::MessageActivity(MessageActivityMsg::ToggleFollowPaused));
+        }
+
+        // Runtime counts and configuration of the queue
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) if c == config::get_config_or_panic().keys().queue_info() => {
+            return Some(Msg::MessageActivity(MessageActivityMsg::QueueInfoRequested));
+        }
+
+        // Rules of the current subscription
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) if c == config::get_config_or_panic().keys().subscription_rules() => {
+            return Some(Msg::QueueActivity(
+                QueueActivityMsg::SubscriptionRulesRequested,
+            ));
+        }
+
+        // Session state of session-enabled queues
+        Event::Keyboard(KeyEvent {
+            code: Key::Char(c),
+            modifiers: KeyModifiers::NONE,
+        }) if c == config::get_config_or_panic().keys().session_state() => {
+            return Some(Msg::QueueActivity(QueueActivityMsg::EditSessionState));
+        }
+
         // Message composition
         Event::Keyboard(KeyEvent {
             code: Key::Char(c),
@@ -6,4 +6,4 @@ pub mod rendering;
 pub mod selection;
 
 // Re-export main types for backwards compatibility
-pub use component::{Messages, PaginationInfo};
+pub use component::{FollowStatus, Messages, PaginationInfo, SearchStatus, StatsTrend};
@@ -1,7 +1,10 @@
 // Bulk selection UI logic for messages table
 use crate::components::common::QueueType;
 use crate::components::common::{MessageActivityMsg, Msg};
-use crate::components::messages::PaginationInfo;
+use crate::components::messages::{FollowStatus, PaginationInfo, SearchStatus, StatsTrend};
+
+/// Number of samples drawn in a sparkline
+const SPARKLINE_POINTS: usize = 12;
 
 /// Create a message identifier from index - this will send a message to get the actual message data
 pub fn create_toggle_message_selection(index: usize) -> Msg {
@@ -11,11 +14,22 @@ pub fn create_toggle_message_selection(index: usize) -> Msg {
 /// Format queue display string for the title
 pub fn format_queue_display(info: &PaginationInfo) -> String {
     let queue_name = info.queue_name.as_deref().unwrap_or("Unknown Queue");
-    match info.queue_type {
+    if let Some(search) = &info.search {
+        return format!("🔎 Search ({queue_name}): {}", search.expression);
+    }
+    let mut display = match info.queue_type {
         QueueType::Main => format!("📬 Messages ({queue_name}) [Main → d:DLQ]"),
         QueueType::DeadLetter => {
             format!("💀 Dead Letter Queue ({queue_name}) [DLQ → d:Main]")
         }
+    };
+    if let Some(trend) = &info.stats_trend {
+        display.push_str(&format!(" │ {}", format_stats_trend(trend)));
+    }
+    match &info.follow {
+        Some(follow) if follow.paused => format!("{display} ⏸ Paused"),
+        Some(_) => format!("{display} ● Following"),
+        None => display,
     }
 }
 
@@ -50,11 +64,20 @@ pub fn format_navigation_hints(info: &PaginationInfo) -> String {
 
 /// Format complete pagination status line
 pub fn format_pagination_status(info: &PaginationInfo) -> String {
+    if let Some(search) = &info.search {
+        return format_search_status(search);
+    }
+
     let bulk_info = format_bulk_info(info);
     let navigation_hints = format_navigation_hints(info);
+    let follow_info = info
+        .follow
+        .as_ref()
+        .map(|follow| format!(" {}", format_follow_status(follow)))
+        .unwrap_or_default();
 
     if info.total_messages_loaded == 0 {
-        format!("No messages available {bulk_info}")
+        format!("No messages available {bulk_info}{follow_info}")
     } else {
         let base_status = format!(
             "Page {}/{} • {} loaded • {} on page",
@@ -85,6 +108,117 @@ pub fn format_pagination_status(info: &PaginationInfo) -> String {
             String::new()
         };
 
-        format!("{base_status}{queue_info} {navigation_hints} {bulk_info}")
+        format!("{base_status}{queue_info} {navigation_hints} {bulk_info}{follow_info}")
+    }
+}
+
+/// Format the follow mode part of the status line
+pub fn format_follow_status(follow: &FollowStatus) -> String {
+    let state = if follow.paused {
+        "⏸ Paused"
+    } else {
+        "● Following"
+    };
+    format!(
+        "• {state} • {} new • {:.1}/s",
+        follow.received, follow.rate_per_second
+    )
+}
+
+/// Format the status line of a whole-queue search
+pub fn format_search_status(search: &SearchStatus) -> String {
+    let state = if search.running {
+        "Searching..."
+    } else if search.cancelled {
+        "Search cancelled"
+    } else if search.truncated {
+        "Match limit reached"
+    } else {
+        "Search complete"
+    };
+
+    format!(
+        "{state} • {} match{} • {} scanned • [/] edit • [Esc] back to pages",
+        search.matched,
+        if search.matched == 1 { "" } else { "es" },
+        search.scanned
+    )
+}
+
+/// Format the statistics history as sparklines with the estimated rates
+pub fn format_stats_trend(trend: &StatsTrend) -> String {
+    let active = format_sparkline(&trend.active_counts, SPARKLINE_POINTS);
+    let dlq = format_sparkline(&trend.dlq_counts, SPARKLINE_POINTS);
+    match &trend.rates {
+        Some(rates) => {
+            let dlq_net = rates.dlq_net_per_minute();
+            let direction = if dlq_net > 0.0 {
+                "↑"
+            } else if dlq_net < 0.0 {
+                "↓"
+            } else {
+                "→"
+            };
+            format!(
+                "{active} in {:.1} out {:.1}/min │ DLQ {dlq} {direction} {dlq_net:+.1}/min",
+                rates.active_in_per_minute, rates.active_out_per_minute
+            )
+        }
+        None => format!("{active} │ DLQ {dlq}"),
+    }
+}
+
+/// Draw the last `max_points` values as a line of block characters, scaled
+/// between their minimum and maximum
+pub fn format_sparkline(values: &[u64], max_points: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let values = &values[values.len().saturating_sub(max_points)..];
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = max - min;
+    values
+        .iter()
+        .map(|value| match range {
+            0 => BARS[0],
+            _ => BARS[((value - min) * (BARS.len() as u64 - 1) / range) as usize],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::managers::queue_stats_history::QueueStatsRates;
+
+    #[test]
+    fn test_format_sparkline() {
+        assert_eq!(format_sparkline(&[], 12), "");
+        assert_eq!(format_sparkline(&[5, 5, 5], 12), "▁▁▁");
+        assert_eq!(format_sparkline(&[0, 7, 14], 12), "▁▄█");
+        // Only the latest points are drawn
+        assert_eq!(format_sparkline(&[100, 0, 1, 2], 2), "▁█");
+    }
+
+    #[test]
+    fn test_format_stats_trend_shows_dlq_direction() {
+        let mut trend = StatsTrend {
+            active_counts: vec![1, 2],
+            dlq_counts: vec![4, 3],
+            rates: None,
+        };
+        assert_eq!(format_stats_trend(&trend), "▁█ │ DLQ █▁");
+
+        trend.rates = Some(QueueStatsRates {
+            active_in_per_minute: 2.0,
+            active_out_per_minute: 0.5,
+            dlq_in_per_minute: 0.0,
+            dlq_out_per_minute: 1.0,
+        });
+        assert_eq!(
+            format_stats_trend(&trend),
+            "▁█ in 2.0 out 0.5/min │ DLQ █▁ ↓ -1.0/min"
+        );
     }
 }
@@ -17,8 +17,10 @@
 //! - **[`namespace_picker`]** - Azure Service Bus namespace selection
 //! - **[`queue_picker`]** - Queue selection and browsing
 //! - **[`resource_group_picker`]** - Azure resource group selection
+//! - **[`session_picker`]** - Session selection for session-enabled queues
 //! - **[`subscription_picker`]** - Azure subscription selection
 //! - **[`theme_picker`]** - Theme and color scheme selection
+//! - **[`topic_picker`]** - Topic and topic subscription selection
 //!
 //! ### Configuration Components
 //! Components for application configuration and setup:
@@ -33,6 +35,7 @@
 //! - **[`number_input_popup`]** - Numeric input dialog
 //! - **[`page_size_popup`]** - Pagination configuration
 //! - **[`success_popup`]** - Success message display
+//! - **[`text_input_popup`]** - Free-form text input dialog
 //!
 //! ### Display Components
 //! Components for information display and user guidance:
@@ -176,20 +179,27 @@ pub mod validation_patterns;
 pub mod namespace_picker;
 pub mod queue_picker;
 pub mod resource_group_picker;
+pub mod session_picker;
 pub mod subscription_picker;
 pub mod theme_picker;
+pub mod topic_picker;
 
 // Configuration components
 pub mod config_screen;
 pub mod password_popup;
 
 // Popup components
+pub mod alerts_popup;
 pub mod auth_popup;
 pub mod confirmation_popup;
 pub mod error_popup;
 pub mod number_input_popup;
 pub mod page_size_popup;
+pub mod queue_dashboard;
+pub mod queue_info_popup;
+pub mod subscription_rules_popup;
 pub mod success_popup;
+pub mod text_input_popup;
 
 // Display components
 pub mod help;
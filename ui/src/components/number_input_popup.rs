@@ -118,7 +118,7 @@ impl NumberInputPopup {
     }
 
     /// Renders the input field with appropriate styling based on validation state.
-    fn render_input_field(&self) -> Line {
+    fn render_input_field(&self) -> Line<'_> {
         let input_text = if self.current_input.is_empty() {
             "Type a number..."
         } else {
@@ -142,16 +142,16 @@ impl NumberInputPopup {
     }
 
     /// Renders validation feedback if there's an error.
-    fn render_validation_feedback(&self) -> Option<Line> {
+    fn render_validation_feedback(&self) -> Option<Line<'_>> {
         let validation_state = self.get_validation_state();
 
-        if !validation_state.is_valid {
-            if let Some(error_message) = validation_state.error_message {
-                return Some(Line::from(Span::styled(
-                    format!("⚠ {error_message}"),
-                    Style::default().fg(ThemeManager::status_error()),
-                )));
-            }
+        if !validation_state.is_valid
+            && let Some(error_message) = validation_state.error_message
+        {
+            return Some(Line::from(Span::styled(
+                format!("⚠ {error_message}"),
+                Style::default().fg(ThemeManager::status_error()),
+            )));
         }
         None
     }
@@ -71,7 +71,7 @@ impl PageSizePopup {
     }
 
     /// Renders the list of page size options.
-    fn render_options(&self) -> Vec<Line> {
+    fn render_options(&self) -> Vec<Line<'_>> {
         self.options
             .iter()
             .enumerate()
@@ -0,0 +1,523 @@
+use crate::components::common::{Msg, QueueActivityMsg};
+use crate::components::queue_info_popup::format_bytes;
+use crate::components::state::ComponentState;
+use crate::theme::ThemeManager;
+use std::cmp::Ordering;
+use tuirealm::{
+    Component, Event, MockComponent, NoUserEvent,
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::{
+        Frame,
+        layout::{Alignment, Constraint, Direction, Layout, Rect},
+        style::{Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, BorderType, Borders, Cell, Paragraph, Row, Table, TableState},
+    },
+};
+
+/// Width of the numeric columns
+const COUNT_WIDTH: u16 = 11;
+
+/// Columns of the dashboard, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashboardColumn {
+    Name,
+    Active,
+    DeadLetter,
+    DeadLetterGrowth,
+    Scheduled,
+    Size,
+}
+
+impl DashboardColumn {
+    pub const ALL: [Self; 6] = [
+        Self::Name,
+        Self::Active,
+        Self::DeadLetter,
+        Self::DeadLetterGrowth,
+        Self::Scheduled,
+        Self::Size,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Name => "Queue",
+            Self::Active => "Active",
+            Self::DeadLetter => "DLQ",
+            Self::DeadLetterGrowth => "DLQ Δ",
+            Self::Scheduled => "Scheduled",
+            Self::Size => "Size",
+        }
+    }
+
+    /// Column sorted by the digit key `1`-`6`
+    fn from_digit(c: char) -> Option<Self> {
+        let index = c.to_digit(10)?.checked_sub(1)? as usize;
+        Self::ALL.get(index).copied()
+    }
+}
+
+/// Metrics of one queue; counts are `None` until the queue has been read
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DashboardRow {
+    pub queue_name: String,
+    pub active: Option<u64>,
+    pub dead_letter: Option<u64>,
+    /// Change of the dead letter count over the statistics history window
+    pub dead_letter_growth: Option<i64>,
+    pub scheduled: Option<u64>,
+    pub size_in_bytes: Option<u64>,
+    /// Why the last read of the queue failed
+    pub error: Option<String>,
+}
+
+impl DashboardRow {
+    fn compare(&self, other: &Self, column: DashboardColumn) -> Option<Ordering> {
+        match column {
+            DashboardColumn::Name => Some(self.queue_name.cmp(&other.queue_name)),
+            DashboardColumn::Active => compare_present(self.active, other.active),
+            DashboardColumn::DeadLetter => compare_present(self.dead_letter, other.dead_letter),
+            DashboardColumn::DeadLetterGrowth => {
+                compare_present(self.dead_letter_growth, other.dead_letter_growth)
+            }
+            DashboardColumn::Scheduled => compare_present(self.scheduled, other.scheduled),
+            DashboardColumn::Size => compare_present(self.size_in_bytes, other.size_in_bytes),
+        }
+    }
+}
+
+/// Compares two values, or `None` when either is missing
+fn compare_present<T: Ord>(a: Option<T>, b: Option<T>) -> Option<Ordering> {
+    Some(a?.cmp(&b?))
+}
+
+/// Sorting, filtering and selection of the dashboard, kept across refreshes
+#[derive(Debug, Clone, PartialEq)]
+pub struct DashboardView {
+    pub sort: DashboardColumn,
+    pub descending: bool,
+    /// Case-insensitive part of the queue names to show
+    pub filter: String,
+    /// Whether keys are typed into the filter
+    pub filter_editing: bool,
+    /// Queue under the cursor
+    pub selected: Option<String>,
+}
+
+impl Default for DashboardView {
+    /// Queues whose dead letter queue grows the most come first
+    fn default() -> Self {
+        Self {
+            sort: DashboardColumn::DeadLetterGrowth,
+            descending: true,
+            filter: String::new(),
+            filter_editing: false,
+            selected: None,
+        }
+    }
+}
+
+/// Rows matching the filter in display order.
+///
+/// Rows without a value for the sort column come last in either direction,
+/// and ties are ordered by queue name.
+pub fn visible_rows<'a>(rows: &'a [DashboardRow], view: &DashboardView) -> Vec<&'a DashboardRow> {
+    let filter = view.filter.to_lowercase();
+    let mut visible: Vec<&DashboardRow> = rows
+        .iter()
+        .filter(|row| row.queue_name.to_lowercase().contains(&filter))
+        .collect();
+
+    visible.sort_by(|a, b| {
+        let by_column = match a.compare(b, view.sort) {
+            Some(ordering) if view.descending => ordering.reverse(),
+            Some(ordering) => ordering,
+            None => {
+                let a_missing = a.compare(a, view.sort).is_none();
+                let b_missing = b.compare(b, view.sort).is_none();
+                a_missing.cmp(&b_missing)
+            }
+        };
+        by_column.then_with(|| a.queue_name.cmp(&b.queue_name))
+    });
+    visible
+}
+
+/// Every queue of the namespace with its counts.
+///
+/// Rows and the status line come from the model, which refreshes them on an
+/// interval; sorting, filtering and selection are reported back so they
+/// survive the remount that shows new data.
+///
+/// # Events
+///
+/// - `KeyEvent::Up` / `KeyEvent::Down` (or `k` / `j`) - Select a queue
+/// - `1`-`6` - Sort by a column; again to reverse the order
+/// - `/` - Type a filter; `Enter` keeps it, `Esc` clears it
+/// - `R` - Refresh now
+/// - `KeyEvent::Enter` - Open the selected queue
+/// - `KeyEvent::Esc` - Close the dashboard
+pub struct QueueDashboard {
+    rows: Vec<DashboardRow>,
+    view: DashboardView,
+    status: String,
+    is_mounted: bool,
+}
+
+impl QueueDashboard {
+    /// Creates a dashboard of `rows` shown as `view`, with `status` below them
+    pub fn new(rows: Vec<DashboardRow>, view: DashboardView, status: String) -> Self {
+        Self {
+            rows,
+            view,
+            status,
+            is_mounted: false,
+        }
+    }
+
+    fn selected_index(&self, visible: &[&DashboardRow]) -> usize {
+        self.view
+            .selected
+            .as_ref()
+            .and_then(|name| visible.iter().position(|row| &row.queue_name == name))
+            .unwrap_or(0)
+    }
+
+    fn move_selection(&mut self, down: bool) -> Option<Msg> {
+        let visible = visible_rows(&self.rows, &self.view);
+        if visible.is_empty() {
+            return None;
+        }
+        let index = self.selected_index(&visible);
+        let index = if down {
+            (index + 1).min(visible.len() - 1)
+        } else {
+            index.saturating_sub(1)
+        };
+        self.view.selected = Some(visible[index].queue_name.clone());
+        self.view_changed()
+    }
+
+    fn view_changed(&self) -> Option<Msg> {
+        Some(Msg::QueueActivity(QueueActivityMsg::DashboardViewChanged(
+            self.view.clone(),
+        )))
+    }
+
+    fn header(&self) -> Row<'static> {
+        let cells = DashboardColumn::ALL.iter().enumerate().map(|(i, column)| {
+            let marker = match (self.view.sort == *column, self.view.descending) {
+                (true, true) => " ▼",
+                (true, false) => " ▲",
+                (false, _) => "",
+            };
+            Cell::from(format!("{} {}{marker}", i + 1, column.title())).style(
+                Style::default()
+                    .fg(ThemeManager::header_accent())
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+        Row::new(cells.collect::<Vec<_>>())
+    }
+
+    fn row(row: &DashboardRow) -> Row<'static> {
+        let count = |value: Option<u64>| value.map_or("—".to_string(), |v| v.to_string());
+        let muted = Style::default().fg(ThemeManager::text_muted());
+        let primary = Style::default().fg(ThemeManager::text_primary());
+
+        if let Some(error) = &row.error {
+            return Row::new(vec![
+                Cell::from(row.queue_name.clone()).style(primary),
+                Cell::from(format!("⚠ {error}"))
+                    .style(Style::default().fg(ThemeManager::status_error())),
+            ]);
+        }
+
+        let dead_letter_style = match row.dead_letter {
+            Some(count) if count > 0 => Style::default().fg(ThemeManager::status_warning()),
+            _ => muted,
+        };
+        let (growth, growth_style) = match row.dead_letter_growth {
+            Some(growth) if growth > 0 => (
+                format!("+{growth}"),
+                Style::default()
+                    .fg(ThemeManager::status_error())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Some(growth) if growth < 0 => (
+                growth.to_string(),
+                Style::default().fg(ThemeManager::status_success()),
+            ),
+            Some(_) => ("0".to_string(), muted),
+            None => ("—".to_string(), muted),
+        };
+
+        Row::new(vec![
+            Cell::from(row.queue_name.clone()).style(primary),
+            Cell::from(count(row.active)).style(primary),
+            Cell::from(count(row.dead_letter)).style(dead_letter_style),
+            Cell::from(growth).style(growth_style),
+            Cell::from(count(row.scheduled)).style(primary),
+            Cell::from(row.size_in_bytes.map_or("—".to_string(), format_bytes)).style(primary),
+        ])
+    }
+}
+
+impl MockComponent for QueueDashboard {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ThemeManager::primary_accent()))
+            .title(format!(" Queue Dashboard ({} queues) ", self.rows.len()))
+            .title_alignment(Alignment::Center)
+            .title_style(
+                Style::default()
+                    .fg(ThemeManager::title_accent())
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let filter_line = if self.view.filter_editing {
+            Line::from(vec![
+                Span::styled("Filter: ", Style::default().fg(ThemeManager::status_info())),
+                Span::styled(
+                    format!("{}█", self.view.filter),
+                    Style::default().fg(ThemeManager::text_primary()),
+                ),
+            ])
+        } else if self.view.filter.is_empty() {
+            Line::from(Span::styled(
+                "Press / to filter by name",
+                Style::default().fg(ThemeManager::text_muted()),
+            ))
+        } else {
+            Line::from(vec![
+                Span::styled("Filter: ", Style::default().fg(ThemeManager::text_muted())),
+                Span::styled(
+                    self.view.filter.clone(),
+                    Style::default().fg(ThemeManager::text_primary()),
+                ),
+            ])
+        };
+        frame.render_widget(Paragraph::new(filter_line), chunks[0]);
+
+        let visible = visible_rows(&self.rows, &self.view);
+        let selected = self.selected_index(&visible);
+        let rows: Vec<Row> = visible.iter().map(|row| Self::row(row)).collect();
+        let mut widths = vec![Constraint::Min(20)];
+        widths.extend([Constraint::Length(COUNT_WIDTH); 5]);
+        let table = Table::new(rows, widths)
+            .header(self.header())
+            .column_spacing(2)
+            .row_highlight_style(
+                Style::default()
+                    .bg(ThemeManager::selection_bg())
+                    .fg(ThemeManager::selection_fg())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("► ");
+        let mut state = TableState::default();
+        if !visible.is_empty() {
+            state.select(Some(selected));
+        }
+        frame.render_stateful_widget(table, chunks[1], &mut state);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                self.status.clone(),
+                Style::default().fg(ThemeManager::text_muted()),
+            ))),
+            chunks[2],
+        );
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "↑/↓ select • 1-6 sort • / filter • R refresh • Enter open • Esc close",
+                Style::default().fg(ThemeManager::text_muted()),
+            )))
+            .alignment(Alignment::Center),
+            chunks[3],
+        );
+    }
+
+    fn query(&self, _attr: tuirealm::Attribute) -> Option<tuirealm::AttrValue> {
+        None
+    }
+
+    fn attr(&mut self, _attr: tuirealm::Attribute, _value: tuirealm::AttrValue) {
+        // No attributes supported
+    }
+
+    fn state(&self) -> tuirealm::State {
+        tuirealm::State::None
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, NoUserEvent> for QueueDashboard {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let Event::Keyboard(KeyEvent { code, .. }) = ev else {
+            return None;
+        };
+
+        if self.view.filter_editing {
+            match code {
+                Key::Char(c) => self.view.filter.push(c),
+                Key::Backspace => {
+                    self.view.filter.pop();
+                }
+                Key::Enter => self.view.filter_editing = false,
+                Key::Esc => {
+                    self.view.filter.clear();
+                    self.view.filter_editing = false;
+                }
+                _ => return None,
+            }
+            return self.view_changed();
+        }
+
+        match code {
+            Key::Esc => Some(Msg::QueueActivity(QueueActivityMsg::DashboardClosed)),
+            Key::Enter => {
+                let visible = visible_rows(&self.rows, &self.view);
+                let row = visible.get(self.selected_index(&visible))?;
+                Some(Msg::QueueActivity(QueueActivityMsg::DashboardQueueOpened(
+                    row.queue_name.clone(),
+                )))
+            }
+            Key::Up | Key::Char('k') => self.move_selection(false),
+            Key::Down | Key::Char('j') => self.move_selection(true),
+            Key::Char('/') => {
+                self.view.filter_editing = true;
+                self.view_changed()
+            }
+            Key::Char('R') => Some(Msg::QueueActivity(
+                QueueActivityMsg::DashboardRefreshRequested,
+            )),
+            Key::Char(c) => {
+                let column = DashboardColumn::from_digit(c)?;
+                if self.view.sort == column {
+                    self.view.descending = !self.view.descending;
+                } else {
+                    self.view.sort = column;
+                    // Names read best A-Z, counts largest first
+                    self.view.descending = column != DashboardColumn::Name;
+                }
+                self.view_changed()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ComponentState for QueueDashboard {
+    fn mount(&mut self) -> crate::error::AppResult<()> {
+        log::debug!("Mounting QueueDashboard component");
+
+        if self.is_mounted {
+            log::warn!("QueueDashboard is already mounted");
+            return Ok(());
+        }
+
+        self.is_mounted = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str, dead_letter: Option<u64>, growth: Option<i64>) -> DashboardRow {
+        DashboardRow {
+            queue_name: name.to_string(),
+            dead_letter,
+            dead_letter_growth: growth,
+            ..DashboardRow::default()
+        }
+    }
+
+    fn names(rows: Vec<&DashboardRow>) -> Vec<&str> {
+        rows.iter().map(|row| row.queue_name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_visible_rows_sort_missing_values_last() {
+        let rows = vec![
+            row("payments", Some(2), Some(0)),
+            row("orders", Some(40), Some(12)),
+            row("audit", None, None),
+            row("billing", Some(2), Some(-3)),
+        ];
+
+        let view = DashboardView::default();
+        assert_eq!(
+            names(visible_rows(&rows, &view)),
+            vec!["orders", "payments", "billing", "audit"]
+        );
+
+        let view = DashboardView {
+            sort: DashboardColumn::DeadLetter,
+            descending: false,
+            ..DashboardView::default()
+        };
+        // Equal counts fall back to the name
+        assert_eq!(
+            names(visible_rows(&rows, &view)),
+            vec!["billing", "payments", "orders", "audit"]
+        );
+    }
+
+    #[test]
+    fn test_visible_rows_filter_ignores_case() {
+        let rows = vec![
+            row("Orders-EU", None, None),
+            row("orders-us", None, None),
+            row("payments", None, None),
+        ];
+        let view = DashboardView {
+            sort: DashboardColumn::Name,
+            descending: false,
+            filter: "ORDERS".to_string(),
+            ..DashboardView::default()
+        };
+        assert_eq!(
+            names(visible_rows(&rows, &view)),
+            vec!["Orders-EU", "orders-us"]
+        );
+    }
+
+    #[test]
+    fn test_sort_keys_toggle_direction() {
+        let mut dashboard =
+            QueueDashboard::new(Vec::new(), DashboardView::default(), String::new());
+        let press = |dashboard: &mut QueueDashboard, c: char| {
+            dashboard.on(Event::Keyboard(KeyEvent::from(Key::Char(c))))
+        };
+
+        press(&mut dashboard, '1');
+        assert_eq!(dashboard.view.sort, DashboardColumn::Name);
+        assert!(!dashboard.view.descending);
+        press(&mut dashboard, '1');
+        assert!(dashboard.view.descending);
+        press(&mut dashboard, '3');
+        assert_eq!(dashboard.view.sort, DashboardColumn::DeadLetter);
+        assert!(dashboard.view.descending);
+        assert_eq!(press(&mut dashboard, '9'), None);
+    }
+}
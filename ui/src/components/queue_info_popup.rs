@@ -0,0 +1,365 @@
+use crate::components::common::{Msg, PopupActivityMsg};
+use crate::components::state::ComponentState;
+use crate::theme::ThemeManager;
+use chrono::{DateTime, Local, Utc};
+use quetty_server::service_bus_manager::QueueDetails;
+use std::time::Duration;
+use tuirealm::{
+    Component, Event, MockComponent, NoUserEvent,
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::{
+        Frame,
+        layout::{Alignment, Rect},
+        style::{Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, BorderType, Borders, Paragraph},
+    },
+};
+
+/// Width of the label column
+const LABEL_WIDTH: usize = 28;
+
+/// A titled group of label/value rows
+type InfoSection = (&'static str, Vec<(&'static str, String)>);
+
+/// Popup listing the runtime counts and configuration of a queue or subscription.
+///
+/// The settings that decide when messages are dead-lettered are grouped
+/// together, so the cause of a dead letter can be read off at a glance.
+///
+/// # Events
+///
+/// - `KeyEvent::Up` / `KeyEvent::Down` (or `k` / `j`) - Scroll
+/// - `KeyEvent::Enter` / `KeyEvent::Esc` - Close the popup
+///
+/// # Messages
+///
+/// Emits `Msg::PopupActivity(PopupActivityMsg::CloseQueueInfo)` when closed.
+pub struct QueueInfoPopup {
+    details: QueueDetails,
+    scroll: u16,
+    is_mounted: bool,
+}
+
+impl QueueInfoPopup {
+    /// Creates a popup showing the given properties
+    pub fn new(details: QueueDetails) -> Self {
+        Self {
+            details,
+            scroll: 0,
+            is_mounted: false,
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for (title, rows) in queue_info_sections(&self.details) {
+            if rows.is_empty() {
+                continue;
+            }
+            lines.push(Line::from(Span::styled(
+                title,
+                Style::default()
+                    .fg(ThemeManager::help_section_title())
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for (label, value) in rows {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {label:<LABEL_WIDTH$}"),
+                        Style::default().fg(ThemeManager::text_muted()),
+                    ),
+                    Span::styled(value, Style::default().fg(ThemeManager::text_primary())),
+                ]));
+            }
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            "↑/↓ to scroll, Enter or Esc to close",
+            Style::default().fg(ThemeManager::text_muted()),
+        )));
+        lines
+    }
+}
+
+impl MockComponent for QueueInfoPopup {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ThemeManager::primary_accent()))
+            .title(format!(" Properties of {} ", self.details.entity_path))
+            .title_alignment(Alignment::Center)
+            .title_style(
+                Style::default()
+                    .fg(ThemeManager::title_accent())
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let lines = self.lines();
+        // Keep the last line reachable without scrolling past it
+        let visible = area.height.saturating_sub(2);
+        let max_scroll = (lines.len() as u16).saturating_sub(visible);
+        self.scroll = self.scroll.min(max_scroll);
+
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Left)
+            .scroll((self.scroll, 0));
+        frame.render_widget(paragraph, area);
+    }
+
+    fn query(&self, _attr: tuirealm::Attribute) -> Option<tuirealm::AttrValue> {
+        None
+    }
+
+    fn attr(&mut self, _attr: tuirealm::Attribute, _value: tuirealm::AttrValue) {
+        // No attributes supported
+    }
+
+    fn state(&self) -> tuirealm::State {
+        tuirealm::State::None
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, NoUserEvent> for QueueInfoPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter | Key::Esc,
+                ..
+            }) => Some(Msg::PopupActivity(PopupActivityMsg::CloseQueueInfo)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Up | Key::Char('k'),
+                ..
+            }) => {
+                self.scroll = self.scroll.saturating_sub(1);
+                Some(Msg::ForceRedraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down | Key::Char('j'),
+                ..
+            }) => {
+                // Clamped to the content when drawn
+                self.scroll = self.scroll.saturating_add(1);
+                Some(Msg::ForceRedraw)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ComponentState for QueueInfoPopup {
+    fn mount(&mut self) -> crate::error::AppResult<()> {
+        log::debug!("Mounting QueueInfoPopup component");
+
+        if self.is_mounted {
+            log::warn!("QueueInfoPopup is already mounted");
+            return Ok(());
+        }
+
+        self.is_mounted = true;
+        Ok(())
+    }
+}
+
+/// Groups the properties for display; rows the entity does not report are left out
+fn queue_info_sections(details: &QueueDetails) -> Vec<InfoSection> {
+    let count = |value: Option<u64>| value.map(|count| count.to_string());
+
+    let size = details
+        .size_in_bytes
+        .map(|bytes| match details.max_size_in_megabytes {
+            Some(max) => format!("{} of {max} MB", format_bytes(bytes)),
+            None => format_bytes(bytes),
+        });
+    let ttl = details.default_message_time_to_live.map(format_duration);
+    let duplicate_detection = details.requires_duplicate_detection.map(|enabled| {
+        match (enabled, details.duplicate_detection_history_time_window) {
+            (true, Some(window)) => format!("yes, {} window", format_duration(window)),
+            (enabled, _) => format_flag(enabled),
+        }
+    });
+
+    let rows = |rows: Vec<(&'static str, Option<String>)>| {
+        rows.into_iter()
+            .filter_map(|(label, value)| Some((label, value?)))
+            .collect()
+    };
+
+    vec![
+        (
+            "Messages",
+            rows(vec![
+                ("Total", count(details.message_count)),
+                ("Active", count(details.active_message_count)),
+                ("Dead-lettered", count(details.dead_letter_message_count)),
+                ("Scheduled", count(details.scheduled_message_count)),
+                ("Transfer", count(details.transfer_message_count)),
+                (
+                    "Transfer dead-lettered",
+                    count(details.transfer_dead_letter_message_count),
+                ),
+                ("Size", size),
+            ]),
+        ),
+        (
+            "Dead-lettering",
+            rows(vec![
+                (
+                    "Max delivery count",
+                    details.max_delivery_count.map(|max| max.to_string()),
+                ),
+                ("Default message TTL", ttl),
+                (
+                    "Dead-letter on expiration",
+                    details
+                        .dead_lettering_on_message_expiration
+                        .map(format_flag),
+                ),
+                (
+                    "Forward dead letters to",
+                    Some(format_target(&details.forward_dead_lettered_messages_to)),
+                ),
+            ]),
+        ),
+        (
+            "Delivery",
+            rows(vec![
+                ("Lock duration", details.lock_duration.map(format_duration)),
+                ("Sessions", details.requires_session.map(format_flag)),
+                ("Partitioning", details.enable_partitioning.map(format_flag)),
+                ("Duplicate detection", duplicate_detection),
+                ("Forward to", Some(format_target(&details.forward_to))),
+                (
+                    "Auto-delete on idle",
+                    details.auto_delete_on_idle.map(format_duration),
+                ),
+            ]),
+        ),
+        (
+            "Entity",
+            rows(vec![
+                ("Status", details.status.clone()),
+                ("Created", details.created_at.map(format_time)),
+                ("Updated", details.updated_at.map(format_time)),
+                (
+                    "Last accessed",
+                    Some(details.accessed_at.map_or("never".to_string(), format_time)),
+                ),
+            ]),
+        ),
+    ]
+}
+
+/// Compact duration such as `30s`, `1h 30m` or `14d`; Service Bus' maximum is `unlimited`
+fn format_duration(duration: Duration) -> String {
+    if QueueDetails::is_unlimited(duration) {
+        return "unlimited".to_string();
+    }
+    if duration < Duration::from_secs(1) {
+        return format!("{}ms", duration.as_millis());
+    }
+
+    let seconds = duration.as_secs();
+    let parts = [
+        (seconds / 86_400, "d"),
+        (seconds % 86_400 / 3_600, "h"),
+        (seconds % 3_600 / 60, "m"),
+        (seconds % 60, "s"),
+    ];
+    parts
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{value}{unit}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+fn format_flag(enabled: bool) -> String {
+    if enabled { "yes" } else { "no" }.to_string()
+}
+
+fn format_target(target: &Option<String>) -> String {
+    target.clone().unwrap_or_else(|| "—".to_string())
+}
+
+fn format_time(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(30)), "30s");
+        assert_eq!(format_duration(Duration::from_secs(5_400)), "1h 30m");
+        assert_eq!(format_duration(Duration::from_secs(14 * 86_400)), "14d");
+        assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+        assert_eq!(
+            format_duration(Duration::from_secs(10_675_199 * 86_400 + 10_085)),
+            "unlimited"
+        );
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(4096), "4.0 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 + 512 * 1024), "5.5 MB");
+    }
+
+    #[test]
+    fn test_sections_leave_out_unreported_rows() {
+        let details = QueueDetails {
+            entity_path: "events/Subscriptions/audit".to_string(),
+            active_message_count: Some(4),
+            max_delivery_count: Some(10),
+            requires_duplicate_detection: Some(true),
+            duplicate_detection_history_time_window: Some(Duration::from_secs(600)),
+            forward_dead_lettered_messages_to: Some("archive".to_string()),
+            ..QueueDetails::default()
+        };
+
+        let sections = queue_info_sections(&details);
+        let find = |label: &str| {
+            sections
+                .iter()
+                .flat_map(|(_, rows)| rows)
+                .find(|(row, _)| *row == label)
+                .map(|(_, value)| value.as_str())
+        };
+
+        assert_eq!(find("Active"), Some("4"));
+        assert_eq!(find("Scheduled"), None);
+        assert_eq!(find("Size"), None);
+        assert_eq!(find("Max delivery count"), Some("10"));
+        assert_eq!(find("Duplicate detection"), Some("yes, 10m window"));
+        assert_eq!(find("Forward dead letters to"), Some("archive"));
+        assert_eq!(find("Forward to"), Some("—"));
+        assert_eq!(find("Last accessed"), Some("never"));
+    }
+}
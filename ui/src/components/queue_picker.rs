@@ -12,6 +12,10 @@ use tuirealm::{Component, Frame, MockComponent};
 
 const CMD_RESULT_QUEUE_SELECTED: &str = "QueueSelected";
 const CMD_RESULT_NAMESPACE_UNSELECTED: &str = "NamespaceUnselected";
+const CMD_RESULT_CREATE_QUEUE: &str = "CreateQueue";
+const CMD_RESULT_EDIT_QUEUE: &str = "EditQueue";
+const CMD_RESULT_DELETE_QUEUE: &str = "DeleteQueue";
+const CMD_RESULT_OPEN_DASHBOARD: &str = "OpenDashboard";
 
 pub struct QueuePicker {
     queues: Vec<String>,
@@ -84,8 +88,9 @@ impl MockComponent for QueuePicker {
             })
             .collect();
         // Use PopupBuilder for consistent styling
-        let popup_block = PopupBuilder::new("Queue Picker")
-            .create_block_with_title("  🗂️  Select a Queue • Press 'd' for Azure Discovery  ");
+        let popup_block = PopupBuilder::new("Queue Picker").create_block_with_title(
+            "  🗂️  Select a Queue • Press 't' for Topics • Press 'd' for Azure Discovery  ",
+        );
 
         if self.manual_entry_mode {
             // Show manual queue entry
@@ -93,7 +98,7 @@ impl MockComponent for QueuePicker {
             use tuirealm::ratatui::widgets::Paragraph;
 
             let help_text = format!(
-                "Enter queue name:\n\n{}_\n\n• Press Enter to connect\n• Press ESC to cancel",
+                "Enter queue name or <topic>/Subscriptions/<subscription>:\n\n{}_\n\n• Press Enter to connect\n• Press ESC to cancel",
                 self.manual_queue_name
             );
 
@@ -108,12 +113,18 @@ impl MockComponent for QueuePicker {
             use tuirealm::ratatui::layout::Alignment;
             use tuirealm::ratatui::widgets::Paragraph;
 
+            let create_hint = format!(
+                "➕ Press '{}' to create a queue",
+                config::get_config_or_panic().keys().create_queue()
+            );
             let help_text = [
                 "",
                 "🔍 No queues available for automatic discovery",
                 "",
                 "📝 Press 'm' to MANUALLY ENTER a queue name",
+                "📡 Press 't' to browse topics and their subscriptions",
                 "🌐 Press 'd' to change Azure subscription/resource group/namespace",
+                &create_hint,
                 "⬅️  Press ESC to go back",
                 "",
                 "Note: Connection string authentication requires manual queue entry",
@@ -235,8 +246,8 @@ impl Component<Msg, NoUserEvent> for QueuePicker {
             }) => {
                 if self.manual_entry_mode {
                     // In manual entry mode, add character to queue name
-                    if self.manual_queue_name.len() < 64 {
-                        // Reasonable limit
+                    if self.manual_queue_name.len() < 320 {
+                        // Long enough for <topic>/Subscriptions/<subscription> paths
                         self.manual_queue_name.push(c);
                         CmdResult::Changed(tuirealm::State::None)
                     } else {
@@ -275,10 +286,30 @@ impl Component<Msg, NoUserEvent> for QueuePicker {
                         } else {
                             CmdResult::None
                         }
+                    } else if c == 't' {
+                        log::info!("User pressed 't' key - opening topic picker");
+                        CmdResult::Custom("OpenTopicPicker", tuirealm::State::None)
                     } else if c == 'd' {
                         // Enter Azure discovery mode to select subscription/resource group/namespace
                         log::info!("User pressed 'd' key - starting Azure discovery");
                         CmdResult::Custom("StartAzureDiscovery", tuirealm::State::None)
+                    } else if c == keys.create_queue() {
+                        CmdResult::Custom(CMD_RESULT_CREATE_QUEUE, tuirealm::State::None)
+                    } else if c == keys.edit_queue() || c == keys.delete_queue() {
+                        let result = if c == keys.edit_queue() {
+                            CMD_RESULT_EDIT_QUEUE
+                        } else {
+                            CMD_RESULT_DELETE_QUEUE
+                        };
+                        match self.queues.get(self.selected).cloned() {
+                            Some(queue) => CmdResult::Custom(
+                                result,
+                                tuirealm::State::One(tuirealm::StateValue::String(queue)),
+                            ),
+                            None => CmdResult::None,
+                        }
+                    } else if c == keys.queue_dashboard() {
+                        CmdResult::Custom(CMD_RESULT_OPEN_DASHBOARD, tuirealm::State::None)
                     } else {
                         CmdResult::None
                     }
@@ -314,9 +345,36 @@ impl Component<Msg, NoUserEvent> for QueuePicker {
                     None
                 }
             }
+            CmdResult::Custom("OpenTopicPicker", _) => {
+                Some(Msg::QueueActivity(QueueActivityMsg::TopicPickerRequested))
+            }
             CmdResult::Custom("StartAzureDiscovery", _) => Some(Msg::AzureDiscovery(
                 AzureDiscoveryMsg::StartInteractiveDiscovery,
             )),
+            CmdResult::Custom(CMD_RESULT_CREATE_QUEUE, _) => {
+                Some(Msg::QueueActivity(QueueActivityMsg::CreateQueueRequested))
+            }
+            CmdResult::Custom(CMD_RESULT_EDIT_QUEUE, state) => {
+                if let tuirealm::State::One(tuirealm::StateValue::String(queue)) = state {
+                    Some(Msg::QueueActivity(QueueActivityMsg::EditQueueRequested(
+                        queue,
+                    )))
+                } else {
+                    None
+                }
+            }
+            CmdResult::Custom(CMD_RESULT_DELETE_QUEUE, state) => {
+                if let tuirealm::State::One(tuirealm::StateValue::String(queue)) = state {
+                    Some(Msg::QueueActivity(QueueActivityMsg::DeleteQueueRequested(
+                        queue,
+                    )))
+                } else {
+                    None
+                }
+            }
+            CmdResult::Custom(CMD_RESULT_OPEN_DASHBOARD, _) => {
+                Some(Msg::QueueActivity(QueueActivityMsg::DashboardRequested))
+            }
             CmdResult::Changed(_) => Some(Msg::ForceRedraw),
             _ => None,
         }
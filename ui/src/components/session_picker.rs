@@ -0,0 +1,283 @@
+use crate::components::base_popup::PopupBuilder;
+use crate::components::common::{Msg, QueueActivityMsg};
+use crate::config;
+use crate::theme::ThemeManager;
+use tuirealm::command::CmdResult;
+use tuirealm::event::{Event, Key, KeyEvent, NoUserEvent};
+use tuirealm::props::TextModifiers;
+use tuirealm::ratatui::layout::{Alignment, Rect};
+use tuirealm::ratatui::style::Style;
+use tuirealm::ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use tuirealm::{Component, Frame, MockComponent};
+
+const CMD_RESULT_SESSION_SELECTED: &str = "SessionSelected";
+const CMD_RESULT_CLOSED: &str = "SessionPickerClosed";
+const CMD_RESULT_SET_EDITING_MODE: &str = "SetEditingMode";
+
+/// Maximum length of a Service Bus session ID
+const MAX_SESSION_ID_LENGTH: usize = 128;
+
+/// Picker shown between queue selection and the message list for
+/// session-enabled queues.
+///
+/// Lists the sessions that currently hold messages. Sessions locked by other
+/// receivers are not discoverable, so an ID can also be typed in with `m`.
+pub struct SessionPicker {
+    queue_name: String,
+    sessions: Vec<String>,
+    selected: usize,
+    manual_entry_mode: bool,
+    manual_session_id: String,
+}
+
+impl SessionPicker {
+    pub fn new(queue_name: String, sessions: Vec<String>) -> Self {
+        Self {
+            queue_name,
+            sessions,
+            selected: 0,
+            manual_entry_mode: false,
+            manual_session_id: String::new(),
+        }
+    }
+
+    fn move_selection(&mut self, down: bool) -> CmdResult {
+        if down {
+            if self.selected + 1 < self.sessions.len() {
+                self.selected += 1;
+            }
+        } else {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        CmdResult::Changed(tuirealm::State::One(tuirealm::StateValue::Usize(
+            self.selected,
+        )))
+    }
+
+    fn select(&mut self) -> CmdResult {
+        let session_id = if self.manual_entry_mode {
+            let session_id = self.manual_session_id.trim().to_string();
+            if session_id.is_empty() {
+                return CmdResult::None;
+            }
+            self.manual_entry_mode = false;
+            session_id
+        } else {
+            match self.sessions.get(self.selected) {
+                Some(session_id) => session_id.clone(),
+                None => return CmdResult::None,
+            }
+        };
+
+        CmdResult::Custom(
+            CMD_RESULT_SESSION_SELECTED,
+            tuirealm::State::One(tuirealm::StateValue::String(session_id)),
+        )
+    }
+
+    fn set_manual_entry_mode(&mut self, enabled: bool) -> CmdResult {
+        self.manual_entry_mode = enabled;
+        self.manual_session_id.clear();
+        CmdResult::Custom(
+            CMD_RESULT_SET_EDITING_MODE,
+            tuirealm::State::One(tuirealm::StateValue::Bool(enabled)),
+        )
+    }
+
+    fn view_manual_entry(&self, frame: &mut Frame, area: Rect) {
+        let popup_block =
+            PopupBuilder::new("Session Picker").create_block_with_title("  ✏️  Enter Session ID  ");
+        let text = format!(
+            "\nQueue: {}\n\nSession ID: {}_\n\nPress Enter to open • Esc to cancel",
+            self.queue_name, self.manual_session_id
+        );
+        let paragraph = Paragraph::new(text)
+            .block(popup_block)
+            .style(Style::default().fg(ThemeManager::status_info()))
+            .alignment(Alignment::Center);
+        frame.render_widget(paragraph, area);
+    }
+}
+
+impl MockComponent for SessionPicker {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        if self.manual_entry_mode {
+            self.view_manual_entry(frame, area);
+            return;
+        }
+
+        let popup_block = PopupBuilder::new("Session Picker").create_block_with_title(format!(
+            "  🔑 {} • Select a Session • m to enter manually  ",
+            self.queue_name
+        ));
+
+        if self.sessions.is_empty() {
+            let paragraph = Paragraph::new(
+                "\n🔍 No sessions with messages found\n\n📝 Press 'm' to enter a session ID manually\n\n⬅️  Press ESC to go back",
+            )
+            .block(popup_block)
+            .style(Style::default().fg(ThemeManager::status_info()))
+            .alignment(Alignment::Center);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .sessions
+            .iter()
+            .map(|session_id| {
+                ListItem::new(format!("🔑 {session_id}"))
+                    .style(Style::default().fg(ThemeManager::status_info()))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(popup_block)
+            .highlight_style(
+                Style::default()
+                    .fg(ThemeManager::status_info())
+                    .bg(ThemeManager::surface())
+                    .add_modifier(TextModifiers::BOLD),
+            )
+            .highlight_symbol("▶ ");
+
+        let mut state = ListState::default();
+        state.select(Some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn query(&self, _attr: tuirealm::Attribute) -> Option<tuirealm::AttrValue> {
+        None
+    }
+
+    fn attr(&mut self, _attr: tuirealm::Attribute, _value: tuirealm::AttrValue) {}
+
+    fn state(&self) -> tuirealm::State {
+        tuirealm::State::One(tuirealm::StateValue::Usize(self.selected))
+    }
+
+    fn perform(&mut self, _cmd: tuirealm::command::Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, NoUserEvent> for SessionPicker {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.select(),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                if self.manual_entry_mode {
+                    self.set_manual_entry_mode(false)
+                } else {
+                    CmdResult::Custom(CMD_RESULT_CLOSED, tuirealm::State::None)
+                }
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) if self.manual_entry_mode => {
+                self.manual_session_id.pop();
+                CmdResult::Changed(tuirealm::State::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(c), ..
+            }) if self.manual_entry_mode => {
+                if self.manual_session_id.len() < MAX_SESSION_ID_LENGTH {
+                    self.manual_session_id.push(c);
+                    CmdResult::Changed(tuirealm::State::None)
+                } else {
+                    CmdResult::None
+                }
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) if !self.manual_entry_mode => self.move_selection(true),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) if !self.manual_entry_mode => {
+                self.move_selection(false)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(c), ..
+            }) => {
+                let keys = config::get_config_or_panic().keys();
+                if c == 'm' {
+                    self.set_manual_entry_mode(true)
+                } else if c == keys.down() {
+                    self.move_selection(true)
+                } else if c == keys.up() {
+                    self.move_selection(false)
+                } else if c == keys.queue_select() {
+                    self.select()
+                } else {
+                    CmdResult::None
+                }
+            }
+            _ => CmdResult::None,
+        };
+
+        match cmd_result {
+            CmdResult::Custom(
+                CMD_RESULT_SESSION_SELECTED,
+                tuirealm::State::One(tuirealm::StateValue::String(session_id)),
+            ) => Some(Msg::QueueActivity(QueueActivityMsg::SessionSelected(
+                session_id,
+            ))),
+            CmdResult::Custom(CMD_RESULT_CLOSED, _) => {
+                Some(Msg::QueueActivity(QueueActivityMsg::SessionPickerClosed))
+            }
+            CmdResult::Custom(
+                CMD_RESULT_SET_EDITING_MODE,
+                tuirealm::State::One(tuirealm::StateValue::Bool(editing)),
+            ) => Some(Msg::SetEditingMode(editing)),
+            CmdResult::Changed(_) => Some(Msg::ForceRedraw),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picker() -> SessionPicker {
+        SessionPicker::new(
+            "orders".to_string(),
+            vec!["customer-1".to_string(), "customer-2".to_string()],
+        )
+    }
+
+    #[test]
+    fn test_select_listed_session() {
+        let mut picker = picker();
+        picker.move_selection(true);
+        picker.move_selection(true);
+
+        match picker.select() {
+            CmdResult::Custom(
+                CMD_RESULT_SESSION_SELECTED,
+                tuirealm::State::One(tuirealm::StateValue::String(session_id)),
+            ) => assert_eq!(session_id, "customer-2"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_manual_entry_requires_non_empty_id() {
+        let mut picker = SessionPicker::new("orders".to_string(), Vec::new());
+        assert!(matches!(picker.select(), CmdResult::None));
+
+        picker.set_manual_entry_mode(true);
+        assert!(matches!(picker.select(), CmdResult::None));
+
+        picker.manual_session_id = " tenant-42 ".to_string();
+        match picker.select() {
+            CmdResult::Custom(
+                CMD_RESULT_SESSION_SELECTED,
+                tuirealm::State::One(tuirealm::StateValue::String(session_id)),
+            ) => assert_eq!(session_id, "tenant-42"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert!(!picker.manual_entry_mode);
+    }
+}
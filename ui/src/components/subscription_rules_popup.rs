@@ -0,0 +1,316 @@
+use crate::components::common::{Msg, QueueActivityMsg};
+use crate::components::state::ComponentState;
+use crate::theme::ThemeManager;
+use quetty_server::service_bus_manager::{RuleFilter, SubscriptionRule};
+use tuirealm::{
+    Component, Event, MockComponent, NoUserEvent,
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent},
+    ratatui::{
+        Frame,
+        layout::{Alignment, Constraint, Direction, Layout, Rect},
+        style::{Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    },
+};
+
+/// Matching messages listed in a test result before the rest are summarized
+const MAX_LISTED_MATCHES: usize = 50;
+
+/// A message a rule matched during a local test
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleTestMatch {
+    pub sequence: i64,
+    pub message_id: String,
+    pub subject: Option<String>,
+}
+
+/// Outcome of testing a rule against the loaded messages
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleTestResult {
+    pub rule_name: String,
+    /// Number of loaded messages the rule was tested against
+    pub tested: usize,
+    pub matches: Vec<RuleTestMatch>,
+    /// Why the rule could not be evaluated locally
+    pub error: Option<String>,
+}
+
+/// Popup listing the rules of a topic subscription.
+///
+/// The selected rule can be tested against the loaded messages; the result is
+/// shown below its filter and action.
+///
+/// # Events
+///
+/// - `KeyEvent::Up` / `KeyEvent::Down` (or `k` / `j`) - Select a rule
+/// - `KeyEvent::Enter` - Test the selected rule against the loaded messages
+/// - `a` / `e` / `x` - Add a rule, edit or delete the selected one
+/// - `KeyEvent::Esc` - Close the popup
+///
+/// # Messages
+///
+/// Emits `Msg::QueueActivity` rule messages, and
+/// `QueueActivityMsg::SubscriptionRulesClosed` when closed.
+pub struct SubscriptionRulesPopup {
+    subscription_path: String,
+    rules: Vec<SubscriptionRule>,
+    selected: usize,
+    test: Option<RuleTestResult>,
+    is_mounted: bool,
+}
+
+impl SubscriptionRulesPopup {
+    /// Creates a popup for the rules of a subscription, selecting `selected`
+    /// and showing the result of the last test
+    pub fn new(
+        subscription_path: String,
+        rules: Vec<SubscriptionRule>,
+        selected: usize,
+        test: Option<RuleTestResult>,
+    ) -> Self {
+        let selected = selected.min(rules.len().saturating_sub(1));
+        Self {
+            subscription_path,
+            rules,
+            selected,
+            test,
+            is_mounted: false,
+        }
+    }
+
+    fn selected_rule_name(&self) -> Option<String> {
+        self.rules.get(self.selected).map(|rule| rule.name.clone())
+    }
+
+    fn rule_items(&self) -> Vec<ListItem<'static>> {
+        self.rules
+            .iter()
+            .map(|rule| {
+                let (kind, filter) = match &rule.filter {
+                    RuleFilter::Sql(expression) => ("SQL", expression.clone()),
+                    RuleFilter::Correlation(filter) => ("Correlation", filter.to_string()),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<24}", rule.name),
+                        Style::default()
+                            .fg(ThemeManager::text_primary())
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{kind:<12}"),
+                        Style::default().fg(ThemeManager::text_muted()),
+                    ),
+                    Span::styled(filter, Style::default().fg(ThemeManager::text_primary())),
+                ]))
+            })
+            .collect()
+    }
+
+    fn detail_lines(&self) -> Vec<Line<'static>> {
+        let Some(rule) = self.rules.get(self.selected) else {
+            return vec![Line::from(Span::styled(
+                "The subscription has no rules, so it receives no messages. Press 'a' to add one.",
+                Style::default().fg(ThemeManager::status_warning()),
+            ))];
+        };
+
+        let label = |text: &'static str| {
+            Span::styled(
+                format!("{text:<10}"),
+                Style::default().fg(ThemeManager::text_muted()),
+            )
+        };
+        let value =
+            |text: String| Span::styled(text, Style::default().fg(ThemeManager::text_primary()));
+
+        let mut lines = vec![
+            Line::from(vec![label("Filter"), value(rule.filter.to_string())]),
+            Line::from(vec![
+                label("Action"),
+                value(rule.action.clone().unwrap_or_else(|| "—".to_string())),
+            ]),
+            Line::from(""),
+        ];
+
+        match self
+            .test
+            .as_ref()
+            .filter(|test| test.rule_name == rule.name)
+        {
+            Some(test) => lines.extend(test_lines(test)),
+            None => lines.push(Line::from(Span::styled(
+                "Press Enter to test this rule against the loaded messages",
+                Style::default().fg(ThemeManager::text_muted()),
+            ))),
+        }
+        lines
+    }
+}
+
+fn test_lines(test: &RuleTestResult) -> Vec<Line<'static>> {
+    if let Some(error) = &test.error {
+        return vec![Line::from(Span::styled(
+            format!("Cannot test locally: {error}"),
+            Style::default().fg(ThemeManager::status_error()),
+        ))];
+    }
+
+    let summary_color = if test.matches.is_empty() {
+        ThemeManager::status_warning()
+    } else {
+        ThemeManager::status_success()
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "Matches {} of {} loaded message(s)",
+            test.matches.len(),
+            test.tested
+        ),
+        Style::default()
+            .fg(summary_color)
+            .add_modifier(Modifier::BOLD),
+    ))];
+
+    for matched in test.matches.iter().take(MAX_LISTED_MATCHES) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  #{:<10}", matched.sequence),
+                Style::default().fg(ThemeManager::text_muted()),
+            ),
+            Span::styled(
+                matched.message_id.clone(),
+                Style::default().fg(ThemeManager::text_primary()),
+            ),
+            Span::styled(
+                matched
+                    .subject
+                    .as_ref()
+                    .map(|subject| format!("  {subject}"))
+                    .unwrap_or_default(),
+                Style::default().fg(ThemeManager::text_muted()),
+            ),
+        ]));
+    }
+    if test.matches.len() > MAX_LISTED_MATCHES {
+        lines.push(Line::from(Span::styled(
+            format!("  … and {} more", test.matches.len() - MAX_LISTED_MATCHES),
+            Style::default().fg(ThemeManager::text_muted()),
+        )));
+    }
+    lines
+}
+
+impl MockComponent for SubscriptionRulesPopup {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ThemeManager::primary_accent()))
+            .title(format!(" Rules of {} ", self.subscription_path))
+            .title_alignment(Alignment::Center)
+            .title_style(
+                Style::default()
+                    .fg(ThemeManager::title_accent())
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let list_height = (self.rules.len() as u16).clamp(1, 10);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(list_height),
+                Constraint::Length(1),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let list = List::new(self.rule_items())
+            .highlight_style(
+                Style::default()
+                    .fg(ThemeManager::selection_fg())
+                    .bg(ThemeManager::selection_bg())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("▶ ");
+        let mut state = ListState::default();
+        if !self.rules.is_empty() {
+            state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        let details = Paragraph::new(self.detail_lines()).wrap(Wrap { trim: false });
+        frame.render_widget(details, chunks[2]);
+
+        let footer = Paragraph::new(Line::from(Span::styled(
+            "↑/↓ select • Enter test • a add • e edit • x delete • Esc close",
+            Style::default().fg(ThemeManager::text_muted()),
+        )))
+        .alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[3]);
+    }
+
+    fn query(&self, _attr: tuirealm::Attribute) -> Option<tuirealm::AttrValue> {
+        None
+    }
+
+    fn attr(&mut self, _attr: tuirealm::Attribute, _value: tuirealm::AttrValue) {
+        // No attributes supported
+    }
+
+    fn state(&self) -> tuirealm::State {
+        tuirealm::State::None
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, NoUserEvent> for SubscriptionRulesPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let Event::Keyboard(KeyEvent { code, .. }) = ev else {
+            return None;
+        };
+        let msg = match code {
+            Key::Esc => QueueActivityMsg::SubscriptionRulesClosed,
+            Key::Up | Key::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                return Some(Msg::ForceRedraw);
+            }
+            Key::Down | Key::Char('j') => {
+                if self.selected + 1 < self.rules.len() {
+                    self.selected += 1;
+                }
+                return Some(Msg::ForceRedraw);
+            }
+            Key::Char('a') => QueueActivityMsg::AddSubscriptionRule,
+            Key::Enter => QueueActivityMsg::TestSubscriptionRule(self.selected_rule_name()?),
+            Key::Char('e') => QueueActivityMsg::EditSubscriptionRule(self.selected_rule_name()?),
+            Key::Char('x') | Key::Delete => {
+                QueueActivityMsg::DeleteSubscriptionRule(self.selected_rule_name()?)
+            }
+            _ => return None,
+        };
+        Some(Msg::QueueActivity(msg))
+    }
+}
+
+impl ComponentState for SubscriptionRulesPopup {
+    fn mount(&mut self) -> crate::error::AppResult<()> {
+        log::debug!("Mounting SubscriptionRulesPopup component");
+
+        if self.is_mounted {
+            log::warn!("SubscriptionRulesPopup is already mounted");
+            return Ok(());
+        }
+
+        self.is_mounted = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+use crate::components::common::{Msg, PopupActivityMsg};
+use crate::components::state::ComponentState;
+use crate::theme::ThemeManager;
+use tuirealm::{
+    Component, Event, MockComponent, NoUserEvent, State, StateValue,
+    command::{Cmd, CmdResult},
+    event::{Key, KeyEvent, KeyModifiers},
+    ratatui::{
+        Frame,
+        layout::{Alignment, Rect},
+        style::{Modifier, Style},
+        text::{Line, Span, Text},
+        widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    },
+};
+
+/// Maximum number of characters accepted by the popup
+const MAX_INPUT_LENGTH: usize = 4096;
+
+/// A popup component for free-form single-line text input.
+///
+/// The caller supplies a constructor for the message to emit with the
+/// submitted text, so the popup can be reused by any flow that needs a value.
+///
+/// # Usage
+///
+/// ```rust
+/// use quetty::components::common::{Msg, QueueActivityMsg};
+/// use quetty::components::text_input_popup::TextInputPopup;
+///
+/// let popup = TextInputPopup::new(
+///     "Session State".to_string(),
+///     "Enter the new state".to_string(),
+///     String::new(),
+///     |value| Msg::QueueActivity(QueueActivityMsg::SaveSessionState(value)),
+/// );
+/// ```
+///
+/// # Messages
+///
+/// Emits `Msg::PopupActivity(PopupActivityMsg::TextInputResult(Some(msg)))` on
+/// Enter and `TextInputResult(None)` on Esc.
+pub struct TextInputPopup {
+    title: String,
+    message: String,
+    current_input: String,
+    on_submit: fn(String) -> Msg,
+}
+
+impl TextInputPopup {
+    /// Creates a new text input popup pre-filled with `initial_value`.
+    pub fn new(
+        title: String,
+        message: String,
+        initial_value: String,
+        on_submit: fn(String) -> Msg,
+    ) -> Self {
+        Self {
+            title,
+            message,
+            current_input: initial_value,
+            on_submit,
+        }
+    }
+
+    fn submit(&self) -> Msg {
+        Msg::PopupActivity(PopupActivityMsg::TextInputResult(Some(Box::new((self
+            .on_submit)(
+            self.current_input.clone(),
+        )))))
+    }
+}
+
+impl MockComponent for TextInputPopup {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ThemeManager::primary_accent()))
+            .title(format!(" {} ", self.title))
+            .title_alignment(Alignment::Center);
+
+        let mut lines = vec![Line::from("")];
+        for line in self.message.lines() {
+            lines.push(Line::from(line));
+        }
+        lines.push(Line::from(""));
+
+        lines.push(Line::from(vec![
+            Span::styled("Input: ", Style::default().fg(ThemeManager::text_primary())),
+            Span::styled(
+                format!("{}_", self.current_input),
+                Style::default().fg(ThemeManager::status_success()),
+            ),
+        ]));
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(
+                "[Enter]",
+                Style::default()
+                    .fg(ThemeManager::status_success())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Accept    "),
+            Span::styled(
+                "[Esc]",
+                Style::default()
+                    .fg(ThemeManager::status_error())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Cancel"),
+        ]));
+
+        let paragraph = Paragraph::new(Text::from(lines))
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .style(
+                Style::default()
+                    .fg(ThemeManager::popup_text())
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        frame.render_widget(paragraph, area);
+    }
+
+    fn query(&self, _attr: tuirealm::Attribute) -> Option<tuirealm::AttrValue> {
+        None
+    }
+
+    fn attr(&mut self, _attr: tuirealm::Attribute, _value: tuirealm::AttrValue) {}
+
+    fn state(&self) -> State {
+        State::One(StateValue::String(self.current_input.clone()))
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TextInputPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::PopupActivity(PopupActivityMsg::TextInputResult(None)))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => Some(self.submit()),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(c),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            }) => {
+                if self.current_input.chars().count() < MAX_INPUT_LENGTH {
+                    self.current_input.push(c);
+                }
+                Some(Msg::ForceRedraw)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => {
+                self.current_input.pop();
+                Some(Msg::ForceRedraw)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ComponentState for TextInputPopup {
+    fn mount(&mut self) -> crate::error::AppResult<()> {
+        log::debug!("Mounting TextInputPopup component");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::common::QueueActivityMsg;
+
+    fn popup(initial_value: &str) -> TextInputPopup {
+        TextInputPopup::new(
+            "Title".to_string(),
+            "Message".to_string(),
+            initial_value.to_string(),
+            |value| Msg::QueueActivity(QueueActivityMsg::SaveSessionState(value)),
+        )
+    }
+
+    fn key(code: Key) -> Event<NoUserEvent> {
+        Event::Keyboard(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn test_submit_builds_message_from_edited_input() {
+        let mut popup = popup("ab");
+        popup.on(key(Key::Backspace));
+        popup.on(key(Key::Char('z')));
+
+        assert_eq!(
+            popup.on(key(Key::Enter)),
+            Some(Msg::PopupActivity(PopupActivityMsg::TextInputResult(Some(
+                Box::new(Msg::QueueActivity(QueueActivityMsg::SaveSessionState(
+                    "az".to_string()
+                )))
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_escape_cancels() {
+        let mut popup = popup("state");
+        assert_eq!(
+            popup.on(key(Key::Esc)),
+            Some(Msg::PopupActivity(PopupActivityMsg::TextInputResult(None)))
+        );
+    }
+}
@@ -234,10 +234,10 @@ impl Component<Msg, NoUserEvent> for ThemePicker {
                         }
                     }
                     PickerMode::SelectingFlavor => {
-                        if let Some((_, flavors)) = self.themes.get(self.theme_selected) {
-                            if self.flavor_selected + 1 < flavors.len() {
-                                self.flavor_selected += 1;
-                            }
+                        if let Some((_, flavors)) = self.themes.get(self.theme_selected)
+                            && self.flavor_selected + 1 < flavors.len()
+                        {
+                            self.flavor_selected += 1;
                         }
                     }
                 }
@@ -347,10 +347,10 @@ impl Component<Msg, NoUserEvent> for ThemePicker {
                             }
                         }
                         PickerMode::SelectingFlavor => {
-                            if let Some((_, flavors)) = self.themes.get(self.theme_selected) {
-                                if self.flavor_selected + 1 < flavors.len() {
-                                    self.flavor_selected += 1;
-                                }
+                            if let Some((_, flavors)) = self.themes.get(self.theme_selected)
+                                && self.flavor_selected + 1 < flavors.len()
+                            {
+                                self.flavor_selected += 1;
                             }
                         }
                     }
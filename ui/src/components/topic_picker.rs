@@ -0,0 +1,287 @@
+use crate::components::base_popup::PopupBuilder;
+use crate::components::common::{Msg, QueueActivityMsg};
+use crate::config;
+use crate::theme::ThemeManager;
+use quetty_server::service_bus_manager::{TopicInfo, format_subscription_path};
+use tuirealm::command::CmdResult;
+use tuirealm::event::{Event, Key, KeyEvent, NoUserEvent};
+use tuirealm::props::TextModifiers;
+use tuirealm::ratatui::layout::{Alignment, Rect};
+use tuirealm::ratatui::style::Style;
+use tuirealm::ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use tuirealm::{Component, Frame, MockComponent};
+
+const CMD_RESULT_SUBSCRIPTION_SELECTED: &str = "SubscriptionSelected";
+const CMD_RESULT_CLOSED: &str = "TopicPickerClosed";
+
+/// Two-level picker: choose a topic, then one of its subscriptions.
+///
+/// Selecting a subscription opens it like a queue using its entity path
+/// (`<topic>/Subscriptions/<subscription>`).
+pub struct TopicPicker {
+    topics: Vec<TopicInfo>,
+    selected_topic: usize,
+    /// Index of the highlighted subscription once a topic has been opened
+    selected_subscription: Option<usize>,
+}
+
+impl TopicPicker {
+    pub fn new(topics: Vec<TopicInfo>) -> Self {
+        Self {
+            topics,
+            selected_topic: 0,
+            selected_subscription: None,
+        }
+    }
+
+    fn current_topic(&self) -> Option<&TopicInfo> {
+        self.topics.get(self.selected_topic)
+    }
+
+    fn item_count(&self) -> usize {
+        match self.selected_subscription {
+            Some(_) => self
+                .current_topic()
+                .map(|topic| topic.subscriptions.len())
+                .unwrap_or(0),
+            None => self.topics.len(),
+        }
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_subscription.unwrap_or(self.selected_topic)
+    }
+
+    fn move_selection(&mut self, down: bool) -> CmdResult {
+        let count = self.item_count();
+        let index = self.selected_index();
+        let new_index = if down {
+            if index + 1 < count { index + 1 } else { index }
+        } else {
+            index.saturating_sub(1)
+        };
+
+        match self.selected_subscription.as_mut() {
+            Some(selected) => *selected = new_index,
+            None => self.selected_topic = new_index,
+        }
+        CmdResult::Changed(tuirealm::State::One(tuirealm::StateValue::Usize(new_index)))
+    }
+
+    fn select(&mut self) -> CmdResult {
+        match self.selected_subscription {
+            None => {
+                if self.current_topic().is_some() {
+                    self.selected_subscription = Some(0);
+                    CmdResult::Changed(tuirealm::State::None)
+                } else {
+                    CmdResult::None
+                }
+            }
+            Some(index) => {
+                let Some(topic) = self.current_topic() else {
+                    return CmdResult::None;
+                };
+                match topic.subscriptions.get(index) {
+                    Some(subscription) => CmdResult::Custom(
+                        CMD_RESULT_SUBSCRIPTION_SELECTED,
+                        tuirealm::State::One(tuirealm::StateValue::String(
+                            format_subscription_path(&topic.name, subscription),
+                        )),
+                    ),
+                    None => CmdResult::None,
+                }
+            }
+        }
+    }
+
+    fn back(&mut self) -> CmdResult {
+        if self.selected_subscription.take().is_some() {
+            CmdResult::Changed(tuirealm::State::None)
+        } else {
+            CmdResult::Custom(CMD_RESULT_CLOSED, tuirealm::State::None)
+        }
+    }
+}
+
+impl MockComponent for TopicPicker {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let (title, labels): (String, Vec<String>) = match self.selected_subscription {
+            Some(_) => {
+                let topic = self.current_topic();
+                (
+                    format!(
+                        "  📡 {} • Select a Subscription • Esc to go back  ",
+                        topic.map(|t| t.name.as_str()).unwrap_or_default()
+                    ),
+                    topic
+                        .map(|t| {
+                            t.subscriptions
+                                .iter()
+                                .map(|subscription| format!("📥 {subscription}"))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                )
+            }
+            None => (
+                "  📡 Select a Topic  ".to_string(),
+                self.topics
+                    .iter()
+                    .map(|topic| {
+                        format!(
+                            "📡 {} ({} subscriptions)",
+                            topic.name,
+                            topic.subscriptions.len()
+                        )
+                    })
+                    .collect(),
+            ),
+        };
+
+        let popup_block = PopupBuilder::new("Topic Picker").create_block_with_title(title);
+
+        if labels.is_empty() {
+            let help_text = match self.selected_subscription {
+                Some(_) => "\nThis topic has no subscriptions\n\n⬅️  Press ESC to go back",
+                None => {
+                    "\n🔍 No topics found in this namespace\n\n📝 Subscriptions can also be entered manually in the queue picker\n   as <topic>/Subscriptions/<subscription>\n\n⬅️  Press ESC to go back"
+                }
+            };
+            let paragraph = Paragraph::new(help_text)
+                .block(popup_block)
+                .style(Style::default().fg(ThemeManager::status_info()))
+                .alignment(Alignment::Center);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let items: Vec<ListItem> = labels
+            .into_iter()
+            .map(|label| {
+                ListItem::new(label).style(Style::default().fg(ThemeManager::status_info()))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(popup_block)
+            .highlight_style(
+                Style::default()
+                    .fg(ThemeManager::status_info())
+                    .bg(ThemeManager::surface())
+                    .add_modifier(TextModifiers::BOLD),
+            )
+            .highlight_symbol("▶ ");
+
+        let mut state = ListState::default();
+        state.select(Some(self.selected_index()));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn query(&self, _attr: tuirealm::Attribute) -> Option<tuirealm::AttrValue> {
+        None
+    }
+
+    fn attr(&mut self, _attr: tuirealm::Attribute, _value: tuirealm::AttrValue) {}
+
+    fn state(&self) -> tuirealm::State {
+        tuirealm::State::One(tuirealm::StateValue::Usize(self.selected_index()))
+    }
+
+    fn perform(&mut self, _cmd: tuirealm::command::Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TopicPicker {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.move_selection(true),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => self.move_selection(false),
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.select(),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => self.back(),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(c), ..
+            }) => {
+                let keys = config::get_config_or_panic().keys();
+                if c == keys.down() {
+                    self.move_selection(true)
+                } else if c == keys.up() {
+                    self.move_selection(false)
+                } else if c == keys.queue_select() {
+                    self.select()
+                } else {
+                    CmdResult::None
+                }
+            }
+            _ => CmdResult::None,
+        };
+
+        match cmd_result {
+            CmdResult::Custom(
+                CMD_RESULT_SUBSCRIPTION_SELECTED,
+                tuirealm::State::One(tuirealm::StateValue::String(entity_path)),
+            ) => Some(Msg::QueueActivity(QueueActivityMsg::QueueSelected(
+                entity_path,
+            ))),
+            CmdResult::Custom(CMD_RESULT_CLOSED, _) => {
+                Some(Msg::QueueActivity(QueueActivityMsg::TopicPickerClosed))
+            }
+            CmdResult::Changed(_) => Some(Msg::ForceRedraw),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picker() -> TopicPicker {
+        TopicPicker::new(vec![
+            TopicInfo {
+                name: "events".to_string(),
+                subscriptions: vec!["billing".to_string(), "audit".to_string()],
+            },
+            TopicInfo {
+                name: "alerts".to_string(),
+                subscriptions: vec![],
+            },
+        ])
+    }
+
+    #[test]
+    fn test_subscription_selection_builds_entity_path() {
+        let mut picker = picker();
+        picker.select();
+        picker.move_selection(true);
+
+        match picker.select() {
+            CmdResult::Custom(
+                CMD_RESULT_SUBSCRIPTION_SELECTED,
+                tuirealm::State::One(tuirealm::StateValue::String(path)),
+            ) => assert_eq!(path, "events/Subscriptions/audit"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_back_returns_to_topics_then_closes() {
+        let mut picker = picker();
+        picker.move_selection(true);
+        picker.select();
+        assert_eq!(picker.item_count(), 0);
+        assert!(matches!(picker.select(), CmdResult::None));
+
+        assert!(matches!(picker.back(), CmdResult::Changed(_)));
+        assert_eq!(picker.selected_index(), 1);
+        assert!(matches!(
+            picker.back(),
+            CmdResult::Custom(CMD_RESULT_CLOSED, _)
+        ));
+    }
+}
@@ -168,24 +168,24 @@ impl Validator<str> for NumericRangeValidator {
             .parse()
             .map_err(|_| CommonValidationError::invalid_format(&self.field_name, "valid number"))?;
 
-        if let Some(min) = self.min {
-            if number < min {
-                return Err(CommonValidationError::out_of_range(
-                    &self.field_name,
-                    Some(min.to_string()),
-                    self.max.map(|m| m.to_string()),
-                ));
-            }
+        if let Some(min) = self.min
+            && number < min
+        {
+            return Err(CommonValidationError::out_of_range(
+                &self.field_name,
+                Some(min.to_string()),
+                self.max.map(|m| m.to_string()),
+            ));
         }
 
-        if let Some(max) = self.max {
-            if number > max {
-                return Err(CommonValidationError::out_of_range(
-                    &self.field_name,
-                    self.min.map(|m| m.to_string()),
-                    Some(max.to_string()),
-                ));
-            }
+        if let Some(max) = self.max
+            && number > max
+        {
+            return Err(CommonValidationError::out_of_range(
+                &self.field_name,
+                self.min.map(|m| m.to_string()),
+                Some(max.to_string()),
+            ));
         }
 
         Ok(())
@@ -225,24 +225,24 @@ impl Validator<str> for StringLengthValidator {
     fn validate(&self, input: &str) -> Result<(), Self::Error> {
         let length = input.len();
 
-        if let Some(min_length) = self.min_length {
-            if length < min_length {
-                return Err(CommonValidationError::too_short(
-                    &self.field_name,
-                    min_length,
-                    length,
-                ));
-            }
+        if let Some(min_length) = self.min_length
+            && length < min_length
+        {
+            return Err(CommonValidationError::too_short(
+                &self.field_name,
+                min_length,
+                length,
+            ));
         }
 
-        if let Some(max_length) = self.max_length {
-            if length > max_length {
-                return Err(CommonValidationError::too_long(
-                    &self.field_name,
-                    max_length,
-                    length,
-                ));
-            }
+        if let Some(max_length) = self.max_length
+            && length > max_length
+        {
+            return Err(CommonValidationError::too_long(
+                &self.field_name,
+                max_length,
+                length,
+            ));
         }
 
         Ok(())
@@ -0,0 +1,195 @@
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Queues watched in the background, with the thresholds that raise an alert
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct AlertsConfig {
+    /// How often the watched queues are checked (default: 60s, minimum: 10s)
+    check_interval_seconds: Option<u64>,
+    #[serde(default)]
+    queues: Vec<QueueAlertConfig>,
+}
+
+/// Thresholds of one watched queue or subscription, e.g. `dlq_count > 0`
+#[derive(Debug, Deserialize, Clone)]
+pub struct QueueAlertConfig {
+    /// Queue name, or `topic/Subscriptions/subscription` path
+    pub name: String,
+    #[serde(default)]
+    pub thresholds: Vec<String>,
+}
+
+impl AlertsConfig {
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_secs(self.check_interval_seconds.unwrap_or(60).max(10))
+    }
+
+    pub fn queues(&self) -> &[QueueAlertConfig] {
+        &self.queues
+    }
+
+    /// Every valid threshold with the queue it watches, in configured order;
+    /// invalid thresholds are reported by config validation
+    pub fn thresholds(&self) -> Vec<(String, AlertThreshold)> {
+        self.queues
+            .iter()
+            .flat_map(|queue| {
+                queue.thresholds.iter().filter_map(|threshold| {
+                    threshold
+                        .parse()
+                        .ok()
+                        .map(|threshold| (queue.name.clone(), threshold))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Count a threshold compares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertMetric {
+    ActiveCount,
+    DeadLetterCount,
+}
+
+impl AlertMetric {
+    /// Value of the metric for the given counts
+    pub fn value(self, active_count: u64, dlq_count: u64) -> u64 {
+        match self {
+            Self::ActiveCount => active_count,
+            Self::DeadLetterCount => dlq_count,
+        }
+    }
+}
+
+impl fmt::Display for AlertMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ActiveCount => write!(f, "active_count"),
+            Self::DeadLetterCount => write!(f, "dlq_count"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertComparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+}
+
+impl fmt::Display for AlertComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = match self {
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Equal => "==",
+        };
+        write!(f, "{operator}")
+    }
+}
+
+/// Condition on a queue count, such as `active_count > 5000`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlertThreshold {
+    pub metric: AlertMetric,
+    pub comparison: AlertComparison,
+    pub limit: u64,
+}
+
+impl AlertThreshold {
+    /// Whether `value` of the threshold's metric breaches it
+    pub fn is_breached_by(&self, value: u64) -> bool {
+        match self.comparison {
+            AlertComparison::Greater => value > self.limit,
+            AlertComparison::GreaterOrEqual => value >= self.limit,
+            AlertComparison::Less => value < self.limit,
+            AlertComparison::LessOrEqual => value <= self.limit,
+            AlertComparison::Equal => value == self.limit,
+        }
+    }
+}
+
+impl fmt::Display for AlertThreshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.metric, self.comparison, self.limit)
+    }
+}
+
+impl FromStr for AlertThreshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let metric_end = s
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(s.len());
+        let metric = match &s[..metric_end] {
+            "active_count" => AlertMetric::ActiveCount,
+            "dlq_count" => AlertMetric::DeadLetterCount,
+            "" => return Err("expected a count, e.g. `dlq_count > 0`".to_string()),
+            other => {
+                return Err(format!(
+                    "unknown count `{other}`, expected `active_count` or `dlq_count`"
+                ));
+            }
+        };
+
+        let rest = s[metric_end..].trim_start();
+        // Two-character operators first, so `>=` is not read as `>`
+        let (comparison, limit) = [
+            (">=", AlertComparison::GreaterOrEqual),
+            ("<=", AlertComparison::LessOrEqual),
+            ("==", AlertComparison::Equal),
+            (">", AlertComparison::Greater),
+            ("<", AlertComparison::Less),
+        ]
+        .into_iter()
+        .find_map(|(operator, comparison)| {
+            rest.strip_prefix(operator)
+                .map(|limit| (comparison, limit.trim()))
+        })
+        .ok_or_else(|| "expected one of >, >=, <, <= or == after the count".to_string())?;
+
+        let limit = limit
+            .parse()
+            .map_err(|_| format!("`{limit}` is not a whole number"))?;
+        Ok(Self {
+            metric,
+            comparison,
+            limit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_thresholds() {
+        let threshold: AlertThreshold = "dlq_count > 0".parse().unwrap();
+        assert_eq!(threshold.metric, AlertMetric::DeadLetterCount);
+        assert!(threshold.is_breached_by(1));
+        assert!(!threshold.is_breached_by(0));
+
+        let threshold: AlertThreshold = "active_count>=5000".parse().unwrap();
+        assert_eq!(threshold.comparison, AlertComparison::GreaterOrEqual);
+        assert_eq!(threshold.to_string(), "active_count >= 5000");
+        assert!(threshold.is_breached_by(5000));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_thresholds() {
+        assert!("scheduled_count > 0".parse::<AlertThreshold>().is_err());
+        assert!("dlq_count ! 0".parse::<AlertThreshold>().is_err());
+        assert!("dlq_count > -1".parse::<AlertThreshold>().is_err());
+        assert!("> 0".parse::<AlertThreshold>().is_err());
+    }
+}
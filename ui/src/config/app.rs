@@ -1,5 +1,10 @@
 use super::{
-    LoggingConfig, azure::ServicebusConfig, keys::KeyBindingsConfig, limits::*, ui::UIConfig,
+    LoggingConfig,
+    alerts::{AlertThreshold, AlertsConfig},
+    azure::ServicebusConfig,
+    keys::KeyBindingsConfig,
+    limits::*,
+    ui::UIConfig,
     validation::ConfigValidationError,
 };
 use crate::constants::env_vars::*;
@@ -8,7 +13,8 @@ use crate::utils::auth::{
     AUTH_METHOD_CLIENT_SECRET, AUTH_METHOD_CONNECTION_STRING, AUTH_METHOD_DEVICE_CODE, AuthUtils,
 };
 use quetty_server::bulk_operations::BatchConfig;
-use quetty_server::service_bus_manager::AzureAdConfig;
+use quetty_server::decoding::DecoderConfig;
+use quetty_server::service_bus_manager::{AzureAdConfig, ResendOptions};
 use serde::Deserialize;
 use std::time::Duration;
 
@@ -16,6 +22,7 @@ use std::time::Duration;
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
     page_size: Option<u32>,
+    peek_interval: Option<f64>,
     crossterm_input_listener_interval_ms: Option<u64>,
     crossterm_input_listener_retries: Option<usize>,
     poll_timeout_ms: Option<u64>,
@@ -24,6 +31,12 @@ pub struct AppConfig {
     queue_stats_display_enabled: Option<bool>,
     queue_stats_cache_ttl_seconds: Option<u64>,
     queue_stats_use_management_api: Option<bool>,
+    queue_stats_history_minutes: Option<u64>,
+    queue_stats_history_interval_seconds: Option<u64>,
+    queue_stats_history_persist: Option<bool>,
+    // Queue dashboard configuration
+    dashboard_refresh_seconds: Option<u64>,
+    dashboard_concurrency: Option<usize>,
     // Azure resource cache configuration
     azure_resource_cache_ttl_seconds: Option<u64>,
     azure_resource_cache_max_entries: Option<usize>,
@@ -40,6 +53,12 @@ pub struct AppConfig {
     azure_ad: AzureAdConfig,
     #[serde(default)]
     logging: LoggingConfig,
+    #[serde(default)]
+    resend: ResendOptions,
+    #[serde(default)]
+    decoders: DecoderConfig,
+    #[serde(default)]
+    alerts: AlertsConfig,
     theme: Option<ThemeConfig>,
 }
 
@@ -118,6 +137,19 @@ impl AppConfig {
             });
         }
 
+        // Validate alert thresholds
+        for queue in self.alerts.queues() {
+            for threshold in &queue.thresholds {
+                if let Err(reason) = threshold.parse::<AlertThreshold>() {
+                    errors.push(ConfigValidationError::AlertThreshold {
+                        queue: queue.name.clone(),
+                        threshold: threshold.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+
         // Validate authentication configuration
         self.validate_auth_config(&mut errors);
 
@@ -138,6 +170,15 @@ impl AppConfig {
         self.page_size()
     }
 
+    /// How often follow mode peeks for new messages
+    pub fn peek_interval(&self) -> Duration {
+        Duration::from_secs_f64(
+            self.peek_interval
+                .unwrap_or(1.0)
+                .max(MIN_PEEK_INTERVAL_SECS),
+        )
+    }
+
     pub fn crossterm_input_listener_interval(&self) -> Duration {
         Duration::from_millis(self.crossterm_input_listener_interval_ms.unwrap_or(10))
     }
@@ -167,6 +208,32 @@ impl AppConfig {
         self.queue_stats_use_management_api.unwrap_or(true)
     }
 
+    /// How far back the statistics history of each queue reaches
+    pub fn queue_stats_history_minutes(&self) -> u64 {
+        self.queue_stats_history_minutes.unwrap_or(60).max(1)
+    }
+
+    /// How often statistics of the open queue are sampled; 0 samples only
+    /// when the statistics are loaded anyway
+    pub fn queue_stats_history_interval_seconds(&self) -> u64 {
+        self.queue_stats_history_interval_seconds.unwrap_or(60)
+    }
+
+    /// Whether the statistics history is stored in the profile directory
+    pub fn queue_stats_history_persist(&self) -> bool {
+        self.queue_stats_history_persist.unwrap_or(false)
+    }
+
+    /// How often the queue dashboard reads the counts of every queue
+    pub fn dashboard_refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.dashboard_refresh_seconds.unwrap_or(60).max(5))
+    }
+
+    /// How many queues the dashboard reads at the same time
+    pub fn dashboard_concurrency(&self) -> usize {
+        self.dashboard_concurrency.unwrap_or(4).max(1)
+    }
+
     pub fn azure_resource_cache_ttl_seconds(&self) -> u64 {
         self.azure_resource_cache_ttl_seconds.unwrap_or(300) // 5 minutes default
     }
@@ -200,6 +267,18 @@ impl AppConfig {
         &self.logging
     }
 
+    pub fn resend(&self) -> &ResendOptions {
+        &self.resend
+    }
+
+    pub fn decoders(&self) -> &DecoderConfig {
+        &self.decoders
+    }
+
+    pub fn alerts(&self) -> &AlertsConfig {
+        &self.alerts
+    }
+
     pub fn theme(&self) -> ThemeConfig {
         self.theme.clone().unwrap_or_default()
     }
@@ -32,19 +32,9 @@ impl ServicebusConfig {
     /// Returns None if no encrypted connection string is configured
     /// Returns Err if decryption fails or password is not set
     pub fn connection_string(&self) -> Result<Option<String>, EncryptionError> {
-        let encrypted = match self.encrypted_connection_string() {
-            Some(enc) => enc,
-            None => return Ok(None),
-        };
-
-        let salt = match self.encryption_salt() {
-            Some(s) => s,
-            None => {
-                return Err(EncryptionError::InvalidData(
-                    "Encryption salt not found in configuration".to_string(),
-                ));
-            }
-        };
+        if self.encrypted_connection_string().is_none() {
+            return Ok(None);
+        }
 
         let password = get_master_password().ok_or_else(|| {
             EncryptionError::DecryptionFailed(
@@ -52,8 +42,26 @@ impl ServicebusConfig {
             )
         })?;
 
+        self.decrypt_connection_string(&password)
+    }
+
+    /// Decrypt the Service Bus connection string with the given master password
+    /// Returns None if no encrypted connection string is configured
+    pub fn decrypt_connection_string(
+        &self,
+        password: &str,
+    ) -> Result<Option<String>, EncryptionError> {
+        let encrypted = match self.encrypted_connection_string() {
+            Some(enc) => enc,
+            None => return Ok(None),
+        };
+
+        let salt = self.encryption_salt().ok_or_else(|| {
+            EncryptionError::InvalidData("Encryption salt not found in configuration".to_string())
+        })?;
+
         let encryption = ConnectionStringEncryption::from_salt_base64(salt)?;
-        let decrypted = encryption.decrypt_connection_string(encrypted, &password)?;
+        let decrypted = encryption.decrypt_connection_string(encrypted, password)?;
 
         Ok(Some(decrypted))
     }
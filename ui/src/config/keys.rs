@@ -9,6 +9,7 @@ pub struct KeyBindingsConfig {
     key_theme: Option<char>,
     key_config: Option<char>,
     key_refresh: Option<char>,
+    key_alerts: Option<char>,
 
     // Navigation keys
     key_down: Option<char>,
@@ -29,6 +30,7 @@ pub struct KeyBindingsConfig {
     key_yank_message: Option<char>,
     key_send_edited_message: Option<char>,
     key_replace_edited_message: Option<char>,
+    key_toggle_body_view: Option<char>,
 
     // Bulk selection keys
     key_toggle_selection: Option<char>,
@@ -46,6 +48,46 @@ pub struct KeyBindingsConfig {
     // Page size selection
     key_page_size: Option<char>,
 
+    // Session-enabled queues
+    key_session_state: Option<char>,
+
+    // Scheduled messages
+    key_cancel_scheduled: Option<char>,
+
+    // Deferred messages
+    key_defer_message: Option<char>,
+
+    // Purge
+    key_purge_queue: Option<char>,
+
+    // Search
+    key_search_messages: Option<char>,
+
+    // Export
+    key_export_messages: Option<char>,
+
+    // Import
+    key_import_messages: Option<char>,
+
+    // Copy or move to another namespace
+    key_transfer_messages: Option<char>,
+
+    // Follow mode
+    key_follow_messages: Option<char>,
+    key_pause_follow: Option<char>,
+
+    // Queue properties
+    key_queue_info: Option<char>,
+    key_subscription_rules: Option<char>,
+
+    // Queue administration
+    key_create_queue: Option<char>,
+    key_edit_queue: Option<char>,
+    key_delete_queue: Option<char>,
+
+    // Queue dashboard
+    key_queue_dashboard: Option<char>,
+
     // Confirmation keys
     key_confirm_yes: Option<char>,
     key_confirm_no: Option<char>,
@@ -73,6 +115,10 @@ impl KeyBindingsConfig {
         self.key_refresh.unwrap_or('r')
     }
 
+    pub fn alerts(&self) -> char {
+        self.key_alerts.unwrap_or('!')
+    }
+
     // Navigation keys
     pub fn down(&self) -> char {
         self.key_down.unwrap_or('j')
@@ -133,6 +179,10 @@ impl KeyBindingsConfig {
         self.key_replace_edited_message.unwrap_or('r')
     }
 
+    pub fn toggle_body_view(&self) -> char {
+        self.key_toggle_body_view.unwrap_or('b')
+    }
+
     // Bulk selection keys
     pub fn toggle_selection(&self) -> char {
         self.key_toggle_selection.unwrap_or(' ')
@@ -169,6 +219,82 @@ impl KeyBindingsConfig {
         self.key_page_size.unwrap_or('z')
     }
 
+    // Session-enabled queues
+    pub fn session_state(&self) -> char {
+        self.key_session_state.unwrap_or('e')
+    }
+
+    // Scheduled messages
+    pub fn cancel_scheduled(&self) -> char {
+        self.key_cancel_scheduled.unwrap_or('u')
+    }
+
+    // Deferred messages
+    pub fn defer_message(&self) -> char {
+        self.key_defer_message.unwrap_or('f')
+    }
+
+    // Purge
+    pub fn purge_queue(&self) -> char {
+        self.key_purge_queue.unwrap_or('X')
+    }
+
+    // Search
+    pub fn search_messages(&self) -> char {
+        self.key_search_messages.unwrap_or('/')
+    }
+
+    // Export
+    pub fn export_messages(&self) -> char {
+        self.key_export_messages.unwrap_or('E')
+    }
+
+    // Import
+    pub fn import_messages(&self) -> char {
+        self.key_import_messages.unwrap_or('I')
+    }
+
+    // Copy or move to another namespace
+    pub fn transfer_messages(&self) -> char {
+        self.key_transfer_messages.unwrap_or('T')
+    }
+
+    // Follow mode
+    pub fn follow_messages(&self) -> char {
+        self.key_follow_messages.unwrap_or('F')
+    }
+
+    pub fn pause_follow(&self) -> char {
+        self.key_pause_follow.unwrap_or('w')
+    }
+
+    // Queue properties
+    pub fn queue_info(&self) -> char {
+        self.key_queue_info.unwrap_or('i')
+    }
+
+    pub fn subscription_rules(&self) -> char {
+        self.key_subscription_rules.unwrap_or('L')
+    }
+
+    // Queue administration
+    pub fn create_queue(&self) -> char {
+        self.key_create_queue.unwrap_or('a')
+    }
+
+    pub fn edit_queue(&self) -> char {
+        self.key_edit_queue.unwrap_or('e')
+    }
+
+    pub fn delete_queue(&self) -> char {
+        self.key_delete_queue.unwrap_or('X')
+    }
+
+    // Queue dashboard
+    pub fn queue_dashboard(&self) -> char {
+        self.key_queue_dashboard.unwrap_or('v')
+    }
+
     // Confirmation keys
     pub fn confirm_yes(&self) -> char {
         self.key_confirm_yes.unwrap_or('y')
@@ -24,6 +24,9 @@ pub const MIN_PAGE_SIZE: u32 = 100;
 /// Maximum page size for message display
 pub const MAX_PAGE_SIZE: u32 = 1000;
 
+/// Shortest interval follow mode peeks for new messages at (seconds)
+pub const MIN_PEEK_INTERVAL_SECS: f64 = 0.1;
+
 /// Queue statistics configuration limits
 /// Minimum TTL for queue statistics cache (30 seconds)
 pub const MIN_QUEUE_STATS_CACHE_TTL_SECONDS: u64 = 30;
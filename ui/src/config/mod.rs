@@ -16,6 +16,7 @@
 //! ## Configuration Structure
 //!
 //! The configuration is organized into logical modules:
+//! - [`alerts`] - Queue alert thresholds
 //! - [`app`] - Core application settings
 //! - [`auth`] - Authentication configuration
 //! - [`azure`] - Azure-specific settings
@@ -50,6 +51,8 @@
 use config::{Config, Environment, File};
 use serde::Deserialize;
 
+/// Queue thresholds watched in the background
+pub mod alerts;
 /// Core application configuration types and loading
 pub mod app;
 /// Authentication-related configuration
@@ -137,10 +140,10 @@ impl ProfileCache {
 
     fn get_cached_profile_list(&self) -> Option<Vec<String>> {
         let cached_list = self.cached_list.read().ok()?;
-        if let Some((ref list, last_checked)) = *cached_list {
-            if self.is_cached_valid(last_checked) {
-                return Some(list.clone());
-            }
+        if let Some((ref list, last_checked)) = *cached_list
+            && self.is_cached_valid(last_checked)
+        {
+            return Some(list.clone());
         }
         None
     }
@@ -169,6 +172,9 @@ fn get_profile_cache() -> &'static ProfileCache {
     PROFILE_CACHE.get_or_init(ProfileCache::new)
 }
 
+/// Profile the configuration was loaded for; unset for a custom config file
+static CURRENT_PROFILE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
 /// Current page size that can be dynamically changed by the user
 static CURRENT_PAGE_SIZE: std::sync::OnceLock<std::sync::Mutex<Option<u32>>> =
     std::sync::OnceLock::new();
@@ -358,18 +364,17 @@ fn load_config_with_custom_path(custom_config_path: Option<&str>) -> ConfigLoadR
 /// A reference to the [`ConfigLoadResult`] with static lifetime
 pub fn get_config() -> &'static ConfigLoadResult {
     // Check if we have reloaded configuration first
-    if let Some(reloadable_lock) = RELOADABLE_CONFIG.get() {
-        if let Ok(guard) = reloadable_lock.read() {
-            if let Some(ref reloaded_config) = *guard {
-                log::debug!("Using reloaded configuration instead of cached config");
-                // We have reloaded configuration - convert it to a static reference
-                // This is safe because we're returning a reference to data that lives
-                // as long as the static RELOADABLE_CONFIG
-                return unsafe {
-                    std::mem::transmute::<&ConfigLoadResult, &ConfigLoadResult>(reloaded_config)
-                };
-            }
-        }
+    if let Some(reloadable_lock) = RELOADABLE_CONFIG.get()
+        && let Ok(guard) = reloadable_lock.read()
+        && let Some(ref reloaded_config) = *guard
+    {
+        log::debug!("Using reloaded configuration instead of cached config");
+        // We have reloaded configuration - convert it to a static reference
+        // This is safe because we're returning a reference to data that lives
+        // as long as the static RELOADABLE_CONFIG
+        return unsafe {
+            std::mem::transmute::<&ConfigLoadResult, &ConfigLoadResult>(reloaded_config)
+        };
     }
 
     // Fall back to initial configuration loading
@@ -429,10 +434,10 @@ pub fn get_config_or_panic() -> &'static AppConfig {
 /// ```
 pub fn get_current_page_size() -> u32 {
     let current_page_size = CURRENT_PAGE_SIZE.get_or_init(|| std::sync::Mutex::new(None));
-    if let Ok(guard) = current_page_size.lock() {
-        if let Some(size) = *guard {
-            return size;
-        }
+    if let Ok(guard) = current_page_size.lock()
+        && let Some(size) = *guard
+    {
+        return size;
     }
     // Fall back to config value
     get_config_or_panic().max_messages()
@@ -541,7 +546,17 @@ pub fn init_config_from_path(config_path: &str) -> &'static ConfigLoadResult {
 
 /// Get configuration for specified profile and store it globally
 pub fn get_config_for_profile(profile_name: &str) -> &'static ConfigLoadResult {
-    CONFIG.get_or_init(|| load_config_for_profile(profile_name))
+    CONFIG.get_or_init(|| {
+        let _ = CURRENT_PROFILE.set(profile_name.to_string());
+        load_config_for_profile(profile_name)
+    })
+}
+
+/// Directory of the profile the configuration was loaded for, if any
+pub fn current_profile_dir() -> Option<std::path::PathBuf> {
+    CURRENT_PROFILE
+        .get()
+        .and_then(|profile_name| safe_profile_path(profile_name).ok())
 }
 
 /// Validate profile name for security and correctness
@@ -641,17 +656,16 @@ pub fn list_available_profiles() -> Vec<String> {
         let profiles_dir = config_dir.join("profiles");
         if let Ok(entries) = std::fs::read_dir(profiles_dir) {
             for entry in entries.flatten() {
-                if let Ok(metadata) = entry.metadata() {
-                    if metadata.is_dir() {
-                        if let Some(name) = entry.file_name().to_str() {
-                            // Validate profile name for security
-                            if validate_profile_name(name).is_ok() {
-                                // Check if it has a .env file (basic validation that it's a real profile)
-                                let env_path = entry.path().join(".env");
-                                if env_path.exists() {
-                                    profiles.push(name.to_string());
-                                }
-                            }
+                if let Ok(metadata) = entry.metadata()
+                    && metadata.is_dir()
+                    && let Some(name) = entry.file_name().to_str()
+                {
+                    // Validate profile name for security
+                    if validate_profile_name(name).is_ok() {
+                        // Check if it has a .env file (basic validation that it's a real profile)
+                        let env_path = entry.path().join(".env");
+                        if env_path.exists() {
+                            profiles.push(name.to_string());
                         }
                     }
                 }
@@ -672,6 +686,92 @@ pub fn invalidate_profile_cache() {
     get_profile_cache().invalidate();
 }
 
+/// Configuration of another profile, read without touching the process
+/// environment or the configuration of the current session.
+pub struct ProfileConfig {
+    /// Settings of the profile over the embedded defaults
+    pub config: AppConfig,
+    /// Variables of the profile's `.env` file
+    env: std::collections::HashMap<String, String>,
+}
+
+impl ProfileConfig {
+    /// Gets a variable of the profile's `.env` file
+    pub fn env_var(&self, key: &str) -> Option<&str> {
+        self.env
+            .get(key)
+            .map(String::as_str)
+            .filter(|value| !value.trim().is_empty())
+    }
+}
+
+/// Loads the configuration of another profile, such as the target of a
+/// copy or move to another namespace.
+///
+/// Its secrets stay encrypted with the profile's own salts until used.
+///
+/// # Errors
+///
+/// Returns an error if the profile does not exist or its configuration
+/// cannot be loaded
+pub fn load_profile_config(profile_name: &str) -> Result<ProfileConfig, String> {
+    let profile_dir = safe_profile_path(profile_name)?;
+    if !profile_exists(profile_name) {
+        return Err(format!("Profile '{profile_name}' does not exist"));
+    }
+
+    load_profile_config_from_dir(&profile_dir)
+        .map_err(|e| format!("Failed to load profile '{profile_name}': {e}"))
+}
+
+/// Loads the profile stored in `profile_dir`: the embedded defaults, its
+/// `config.toml` and `keys.toml`, and its `.env` variables on top
+pub fn load_profile_config_from_dir(
+    profile_dir: &std::path::Path,
+) -> Result<ProfileConfig, String> {
+    use crate::config::defaults::{DEFAULT_CONFIG, DEFAULT_KEYS};
+
+    let env_path = profile_dir.join(".env");
+    let content = std::fs::read_to_string(&env_path)
+        .map_err(|e| format!("Failed to read {}: {e}", env_path.display()))?;
+    let env: std::collections::HashMap<String, String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            (key.trim().to_string(), value.to_string())
+        })
+        .collect();
+
+    let mut config_builder = Config::builder()
+        .add_source(File::from_str(DEFAULT_CONFIG, config::FileFormat::Toml))
+        .add_source(File::from_str(DEFAULT_KEYS, config::FileFormat::Toml));
+    for file_name in ["config.toml", "keys.toml"] {
+        let path = profile_dir.join(file_name);
+        if path.exists() {
+            config_builder = config_builder.add_source(File::from(path));
+        }
+    }
+    let config = config_builder
+        .add_source(
+            Environment::default()
+                .separator("__")
+                .source(Some(env.clone())),
+        )
+        .build()
+        .map_err(|e| e.to_string())?
+        .try_deserialize::<AppConfig>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(ProfileConfig { config, env })
+}
+
 /// Load configuration for a specific profile
 fn load_config_for_profile(profile_name: &str) -> ConfigLoadResult {
     // Validate profile name and get safe path
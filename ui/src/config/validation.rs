@@ -37,6 +37,12 @@ pub enum ConfigValidationError {
     InvalidAzureAdFlow { flow: String },
     #[error("Conflicting authentication configuration: {message}")]
     ConflictingAuthConfig { message: String },
+    #[error("Invalid alert threshold `{threshold}` for {queue}: {reason}")]
+    AlertThreshold {
+        queue: String,
+        threshold: String,
+        reason: String,
+    },
 }
 
 impl ConfigValidationError {
@@ -122,6 +128,20 @@ impl ConfigValidationError {
             ConfigValidationError::ConflictingAuthConfig { message } => {
                 format!("Conflicting authentication configuration!\n\n{message}")
             }
+            ConfigValidationError::AlertThreshold {
+                queue,
+                threshold,
+                reason,
+            } => {
+                format!(
+                    "Invalid alert threshold!\n\n\
+                    Queue: {queue}\n\
+                    Threshold: {threshold}\n\
+                    Problem: {reason}\n\n\
+                    Thresholds look like `dlq_count > 0` or `active_count >= 5000`.\n\
+                    Please update alerts.queues in config.toml."
+                )
+            }
         }
     }
 }
@@ -16,3 +16,6 @@ pub const AZURE_AD_NAMESPACE: &str = "AZURE_AD__NAMESPACE";
 pub const SERVICEBUS_ENCRYPTED_CONNECTION_STRING: &str = "SERVICEBUS__ENCRYPTED_CONNECTION_STRING";
 pub const SERVICEBUS_ENCRYPTION_SALT: &str = "SERVICEBUS__ENCRYPTION_SALT";
 pub const SERVICEBUS_QUEUE_NAME: &str = "SERVICEBUS__QUEUE_NAME";
+
+// Non-interactive subcommands
+pub const QUETTY_MASTER_PASSWORD: &str = "QUETTY_MASTER_PASSWORD";
@@ -12,10 +12,12 @@
 //! - Message browsing, editing, and bulk operations
 //! - Configuration management with encryption support
 //! - Error handling and user feedback systems
+//! - Non-interactive subcommands for scripts and CI
 //!
 //! ## Modules
 //!
 //! - [`app`] - Main application logic and component orchestration
+//! - [`cli`] - Non-interactive subcommands with JSON output
 //! - [`components`] - UI components and message handling
 //! - [`config`] - Configuration management and persistence
 //! - [`constants`] - Global constants for environment variables and shared values
@@ -30,6 +32,7 @@
 
 pub mod app;
 
+pub mod cli;
 pub mod components;
 pub mod config;
 pub mod constants;
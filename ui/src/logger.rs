@@ -37,12 +37,11 @@ pub fn setup_logger() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Clean up old log files if enabled
-    if config.logging().cleanup_on_startup() {
-        if let Err(e) =
+    if config.logging().cleanup_on_startup()
+        && let Err(e) =
             cleanup_old_log_files(directory, filename, config.logging().max_backup_files())
-        {
-            eprintln!("Warning: Failed to clean up old log files: {e}");
-        }
+    {
+        eprintln!("Warning: Failed to clean up old log files: {e}");
     }
 
     // Create rolling file appender with size-based rotation
@@ -116,18 +115,18 @@ fn cleanup_old_log_files(
         let file_name = entry.file_name();
         let file_name_str = file_name.to_string_lossy();
 
-        if file_name_str.starts_with(&backup_pattern) {
-            if let Ok(metadata) = entry.metadata() {
-                log_files.push((
-                    entry.path(),
-                    metadata.modified().unwrap_or(std::time::UNIX_EPOCH),
-                ));
-            }
+        if file_name_str.starts_with(&backup_pattern)
+            && let Ok(metadata) = entry.metadata()
+        {
+            log_files.push((
+                entry.path(),
+                metadata.modified().unwrap_or(std::time::UNIX_EPOCH),
+            ));
         }
     }
 
     // Sort by modification time (newest first)
-    log_files.sort_by(|a, b| b.1.cmp(&a.1));
+    log_files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
 
     // Remove excess backup files
     if log_files.len() > max_backups as usize {
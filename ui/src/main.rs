@@ -1,17 +1,8 @@
-mod app;
-mod components;
-mod config;
-mod constants;
-mod error;
-mod logger;
-mod services;
-mod theme;
-mod utils;
-mod validation;
-
-use app::application_lifecycle::ApplicationLifecycle;
 use clap::{Arg, Command};
-use config::{get_config_dir, is_config_initialized, wizard::SetupWizard};
+use quetty::app::application_lifecycle::ApplicationLifecycle;
+use quetty::cli;
+use quetty::config::{get_config_dir, is_config_initialized, wizard::SetupWizard};
+use quetty::logger;
 use std::error::Error as StdError;
 
 /// Main entry point for the Quetty application.
@@ -56,6 +47,7 @@ async fn main() -> Result<(), Box<dyn StdError>> {
                 .long("config")
                 .short('c')
                 .value_name("FILE")
+                .global(true)
                 .help("Use custom configuration file"),
         )
         .arg(
@@ -75,8 +67,10 @@ async fn main() -> Result<(), Box<dyn StdError>> {
                 .long("profile")
                 .short('p')
                 .value_name("NAME")
+                .global(true)
                 .help("Use specified profile (default: 'default')"),
         )
+        .subcommands(cli::subcommands())
         .get_matches();
 
     // Handle --config-dir flag
@@ -116,6 +110,12 @@ async fn main() -> Result<(), Box<dyn StdError>> {
     // Get custom config path if provided
     let custom_config_path = matches.get_one::<String>("config").map(|s| s.as_str());
 
+    // Run a non-interactive subcommand instead of the TUI
+    if let Some((name, args)) = matches.subcommand() {
+        let code = cli::run(name, args, custom_config_path, profile_name).await;
+        std::process::exit(code);
+    }
+
     // Initialize application and get configured model (this will set up the config)
     let mut model =
         ApplicationLifecycle::initialize_with_config_and_profile(custom_config_path, profile_name)
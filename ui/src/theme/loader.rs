@@ -52,14 +52,15 @@ impl ThemeLoader {
     fn get_global_themes_dir(&self) -> Option<PathBuf> {
         use crate::config::setup::get_themes_dir;
 
-        if let Ok(global_themes_dir) = get_themes_dir() {
-            if global_themes_dir.exists() && global_themes_dir.is_dir() {
-                log::info!(
-                    "Found global themes directory: {}",
-                    global_themes_dir.display()
-                );
-                return Some(global_themes_dir);
-            }
+        if let Ok(global_themes_dir) = get_themes_dir()
+            && global_themes_dir.exists()
+            && global_themes_dir.is_dir()
+        {
+            log::info!(
+                "Found global themes directory: {}",
+                global_themes_dir.display()
+            );
+            return Some(global_themes_dir);
         }
         None
     }
@@ -135,13 +136,13 @@ impl ThemeLoader {
         let mut embedded_theme_map: HashMap<String, Vec<String>> = HashMap::new();
 
         for theme_key in embedded_themes.keys() {
-            if let Some((theme_name, flavor_file)) = theme_key.split_once('/') {
-                if let Some(flavor_name) = flavor_file.strip_suffix(".toml") {
-                    embedded_theme_map
-                        .entry(theme_name.to_string())
-                        .or_default()
-                        .push(flavor_name.to_string());
-                }
+            if let Some((theme_name, flavor_file)) = theme_key.split_once('/')
+                && let Some(flavor_name) = flavor_file.strip_suffix(".toml")
+            {
+                embedded_theme_map
+                    .entry(theme_name.to_string())
+                    .or_default()
+                    .push(flavor_name.to_string());
             }
         }
 
@@ -254,29 +255,29 @@ impl ThemeLoader {
                 .map_err(|e| AppError::Config(format!("Failed to read directory entry: {e}")))?;
 
             let path = entry.path();
-            if path.is_dir() {
-                if let Some(theme_name) = path.file_name().and_then(|n| n.to_str()) {
-                    // Validate theme name
-                    if self.theme_name_validator.validate(theme_name).is_ok() {
-                        let flavors = self.discover_flavors(&path)?;
-                        if !flavors.is_empty() {
-                            // Check if we already have this theme from embedded or other sources
-                            if let Some(existing_theme) =
-                                themes.iter_mut().find(|(name, _)| name == theme_name)
-                            {
-                                // Merge filesystem flavors with existing ones
-                                for flavor in flavors {
-                                    if !existing_theme.1.contains(&flavor) {
-                                        existing_theme.1.push(flavor);
-                                    }
+            if path.is_dir()
+                && let Some(theme_name) = path.file_name().and_then(|n| n.to_str())
+            {
+                // Validate theme name
+                if self.theme_name_validator.validate(theme_name).is_ok() {
+                    let flavors = self.discover_flavors(&path)?;
+                    if !flavors.is_empty() {
+                        // Check if we already have this theme from embedded or other sources
+                        if let Some(existing_theme) =
+                            themes.iter_mut().find(|(name, _)| name == theme_name)
+                        {
+                            // Merge filesystem flavors with existing ones
+                            for flavor in flavors {
+                                if !existing_theme.1.contains(&flavor) {
+                                    existing_theme.1.push(flavor);
                                 }
-                                existing_theme.1.sort();
-                                log::debug!("Merged {dir_type} flavors for theme '{theme_name}'");
-                            } else {
-                                // Add new filesystem theme
-                                themes.push((theme_name.to_string(), flavors));
-                                log::debug!("Added new {dir_type} theme '{theme_name}'");
                             }
+                            existing_theme.1.sort();
+                            log::debug!("Merged {dir_type} flavors for theme '{theme_name}'");
+                        } else {
+                            // Add new filesystem theme
+                            themes.push((theme_name.to_string(), flavors));
+                            log::debug!("Added new {dir_type} theme '{theme_name}'");
                         }
                     }
                 }
@@ -301,12 +302,13 @@ impl ThemeLoader {
                 .map_err(|e| AppError::Config(format!("Failed to read directory entry: {e}")))?;
 
             let path = entry.path();
-            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("toml") {
-                if let Some(flavor_name) = path.file_stem().and_then(|n| n.to_str()) {
-                    // Validate flavor name
-                    if self.flavor_name_validator.validate(flavor_name).is_ok() {
-                        flavors.push(flavor_name.to_string());
-                    }
+            if path.is_file()
+                && path.extension().and_then(|s| s.to_str()) == Some("toml")
+                && let Some(flavor_name) = path.file_stem().and_then(|n| n.to_str())
+            {
+                // Validate flavor name
+                if self.flavor_name_validator.validate(flavor_name).is_ok() {
+                    flavors.push(flavor_name.to_string());
                 }
             }
         }
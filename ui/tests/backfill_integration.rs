@@ -1,7 +1,7 @@
 use quetty::app::queue_state::QueueState;
 use quetty::app::updates::messages::MessagePaginationState;
 use quetty::config;
-use quetty_server::model::{BodyData, MessageModel, MessageState};
+use quetty_server::model::{BodyData, MessageModel, MessageProperties, MessageState};
 use quetty_server::service_bus_manager::QueueType;
 use std::collections::HashSet;
 
@@ -24,6 +24,8 @@ mod helpers {
             delivery_count: 1,
             state: MessageState::Active,
             body: BodyData::RawString(format!("Test message {id}")),
+            raw_body: format!("Test message {id}").into_bytes(),
+            properties: MessageProperties::default(),
         }
     }
 
@@ -1,5 +1,5 @@
 use quetty::app::updates::messages::MessagePaginationState;
-use quetty_server::model::{BodyData, MessageModel, MessageState};
+use quetty_server::model::{BodyData, MessageModel, MessageProperties, MessageState};
 use std::time::{Duration, SystemTime};
 
 /// Helper to create a test message
@@ -12,6 +12,8 @@ fn create_test_message(id: &str, sequence: i64) -> MessageModel {
         delivery_count: 1,
         state: MessageState::Active,
         body: BodyData::RawString(format!("Test message {id}")),
+        raw_body: format!("Test message {id}").into_bytes(),
+        properties: MessageProperties::default(),
     }
 }
 
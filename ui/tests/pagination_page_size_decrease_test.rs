@@ -6,7 +6,7 @@
 /// The system should use the first 100 messages from the loaded 1998 messages,
 /// NOT reload from the API starting at sequence 2000+.
 use quetty::app::updates::messages::MessagePaginationState;
-use quetty_server::model::{BodyData, MessageModel, MessageState};
+use quetty_server::model::{BodyData, MessageModel, MessageProperties, MessageState};
 use std::time::{Duration, SystemTime};
 
 /// Helper to create a test message
@@ -19,6 +19,8 @@ fn create_test_message(id: &str, sequence: i64) -> MessageModel {
         delivery_count: 1,
         state: MessageState::Active,
         body: BodyData::RawString(format!("Test message {id}")),
+        raw_body: format!("Test message {id}").into_bytes(),
+        properties: MessageProperties::default(),
     }
 }
 
@@ -1,6 +1,6 @@
 use quetty::app::updates::messages::MessagePaginationState;
 use quetty::config;
-use quetty_server::model::{BodyData, MessageModel, MessageState};
+use quetty_server::model::{BodyData, MessageModel, MessageProperties, MessageState};
 use std::time::{Duration, SystemTime};
 
 /// Helper function to create a test message with a specific sequence
@@ -13,6 +13,8 @@ fn create_test_message(id: &str, sequence: i64) -> MessageModel {
         delivery_count: 1,
         state: MessageState::Active,
         body: BodyData::RawString(format!("Test message {id}")),
+        raw_body: format!("Test message {id}").into_bytes(),
+        properties: MessageProperties::default(),
     }
 }
 